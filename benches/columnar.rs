@@ -0,0 +1,119 @@
+// Copyright © 2024 Pathway
+
+//! Benchmarks of gathering the arguments of the rows of a batch for the expressions
+//! evaluated over columns, into a separate `Vec` allocated for each row as before and into
+//! a `RowBuffer` reused for the following batches, with and without the evaluation of an
+//! expression, to show the part of the time of a batch saved by the reuse.
+//!
+//! Run with `cargo bench --bench columnar`.
+
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use pathway_engine::engine::expression::columnar::{ColumnarBatch, RowBuffer, COLUMNAR_BATCH_SIZE};
+use pathway_engine::engine::{AnyExpression, Expression, FloatExpression, IntExpression, Value};
+
+const ARITIES: [usize; 3] = [1, 4, 16];
+
+/// Rows of integers, floats and short strings, as the tuples of the values of a table.
+fn input_rows(arity: usize) -> Vec<Value> {
+    (0..COLUMNAR_BATCH_SIZE)
+        .map(|row| {
+            Value::from(
+                (0..arity)
+                    .map(|column| match column % 3 {
+                        0 => Value::Int((row * arity + column) as i64),
+                        1 => Value::from(row as f64 / 3.0),
+                        _ => Value::from("status"),
+                    })
+                    .collect::<Vec<_>>()
+                    .as_slice(),
+            )
+        })
+        .collect()
+}
+
+fn argument(index: usize) -> Arc<Expression> {
+    Arc::new(Expression::Any(AnyExpression::Argument(index)))
+}
+
+/// `x + 1.0` on the float in the second column, or the integer in the first one.
+fn expression(arity: usize) -> Expression {
+    if arity > 1 {
+        Expression::Float(FloatExpression::Add(
+            argument(1),
+            Arc::new(Expression::Float(FloatExpression::Const(1.0))),
+        ))
+    } else {
+        Expression::Int(IntExpression::Add(
+            argument(0),
+            Arc::new(Expression::Int(IntExpression::Const(1))),
+        ))
+    }
+}
+
+fn row_values(row: &Value) -> impl Iterator<Item = Value> + '_ {
+    row.as_tuple().unwrap().iter().cloned()
+}
+
+fn bench_gather(c: &mut Criterion) {
+    let mut group = c.benchmark_group("columnar_gather");
+    group.throughput(Throughput::Elements(COLUMNAR_BATCH_SIZE as u64));
+    for arity in ARITIES {
+        let rows = input_rows(arity);
+        let expression = expression(arity);
+
+        group.bench_with_input(BenchmarkId::new("vec_per_row", arity), &arity, |b, _| {
+            b.iter(|| {
+                let args: Vec<Vec<Value>> =
+                    rows.iter().map(|row| row_values(row).collect()).collect();
+                let rows: Vec<&[Value]> = args.iter().map(Vec::as_slice).collect();
+                rows.len()
+            });
+        });
+        let mut row_buffer = RowBuffer::default();
+        group.bench_with_input(BenchmarkId::new("row_buffer", arity), &arity, |b, _| {
+            b.iter(|| {
+                for row in &rows {
+                    row_buffer.push_row(row_values(row));
+                }
+                let len = row_buffer.rows().len();
+                row_buffer.clear();
+                len
+            });
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("vec_per_row_and_eval", arity),
+            &arity,
+            |b, _| {
+                b.iter(|| {
+                    let args: Vec<Vec<Value>> =
+                        rows.iter().map(|row| row_values(row).collect()).collect();
+                    let rows: Vec<&[Value]> = args.iter().map(Vec::as_slice).collect();
+                    expression.eval_batch(&ColumnarBatch::new(&rows))
+                });
+            },
+        );
+        let mut row_buffer = RowBuffer::default();
+        group.bench_with_input(
+            BenchmarkId::new("row_buffer_and_eval", arity),
+            &arity,
+            |b, _| {
+                b.iter(|| {
+                    for row in &rows {
+                        row_buffer.push_row(row_values(row));
+                    }
+                    let results = expression.eval_batch(&ColumnarBatch::new(&row_buffer.rows()));
+                    row_buffer.clear();
+                    results
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_gather);
+criterion_main!(benches);
@@ -0,0 +1,93 @@
+// Copyright © 2024 Pathway
+
+//! Benchmarks of the matrix multiplication of the arrays of different types and sizes,
+//! from the small ones computed by a single thread to the large ones split into tiles.
+//!
+//! They aren't run in CI. To compare a change with the base version by hand, save the
+//! results of the base version with `cargo bench --bench mat_mul -- --save-baseline base`
+//! and compare the changed one with `cargo bench --bench mat_mul -- --baseline base`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ndarray::{ArrayD, IxDyn};
+
+use pathway_engine::engine::SparseMatrix;
+use pathway_engine::mat_mul::{mat_mul, sparse_dense_mat_mul, sparse_mat_mul};
+
+const SIZES: [usize; 4] = [16, 128, 512, 1024];
+
+fn matrix<T>(rows: usize, columns: usize, element: impl Fn(usize) -> T) -> ArrayD<T> {
+    ArrayD::from_shape_fn(IxDyn(&[rows, columns]), |index| {
+        element(index[0] * columns + index[1])
+    })
+}
+
+/// A matrix with about one element in a hundred stored, spread over the rows and columns.
+fn sparse_matrix(rows: usize, columns: usize) -> SparseMatrix {
+    let mut indptr = vec![0];
+    let mut indices = Vec::new();
+    let mut data = Vec::new();
+    for row in 0..rows {
+        indices.extend((row % 100..columns).step_by(100));
+        data.resize(indices.len(), 0.5);
+        indptr.push(indices.len());
+    }
+    SparseMatrix::new((rows, columns), indptr, indices, data).unwrap()
+}
+
+fn bench_dense(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mat_mul");
+    group.sample_size(10);
+    for size in SIZES {
+        group.throughput(Throughput::Elements((size * size * size) as u64));
+
+        let a = matrix(size, size, |i| (i % 7) as f64 - 3.0);
+        let b = matrix(size, size, |i| (i % 5) as f64 + 0.5);
+        group.bench_with_input(BenchmarkId::new("float", size), &size, |bencher, _| {
+            bencher.iter(|| mat_mul(&a.view(), &b.view()).unwrap());
+        });
+
+        let a32 = a.mapv(|x| x as f32);
+        let b32 = b.mapv(|x| x as f32);
+        group.bench_with_input(BenchmarkId::new("float32", size), &size, |bencher, _| {
+            bencher.iter(|| mat_mul(&a32.view(), &b32.view()).unwrap());
+        });
+
+        let a_int = matrix(size, size, |i| (i % 7) as i64 - 3);
+        let b_int = matrix(size, size, |i| (i % 5) as i64);
+        group.bench_with_input(BenchmarkId::new("int", size), &size, |bencher, _| {
+            bencher.iter(|| mat_mul(&a_int.view(), &b_int.view()).unwrap());
+        });
+
+        group.throughput(Throughput::Elements((size * size) as u64));
+        let v = ArrayD::from_shape_fn(IxDyn(&[size]), |index| (index[0] % 3) as f64);
+        group.bench_with_input(
+            BenchmarkId::new("float_by_vector", size),
+            &size,
+            |bencher, _| {
+                bencher.iter(|| mat_mul(&a.view(), &v.view()).unwrap());
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_sparse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sparse_mat_mul");
+    group.sample_size(10);
+    for size in [1024, 8192] {
+        let a = sparse_matrix(size, size);
+        let b = sparse_matrix(size, size);
+        let dense = matrix(size, 64, |i| (i % 5) as f64);
+        group.throughput(Throughput::Elements(a.nnz() as u64));
+        group.bench_with_input(BenchmarkId::new("by_dense", size), &size, |bencher, _| {
+            bencher.iter(|| sparse_dense_mat_mul(&a, &dense.view()).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("by_sparse", size), &size, |bencher, _| {
+            bencher.iter(|| sparse_mat_mul(&a, &b).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_dense, bench_sparse);
+criterion_main!(benches);
@@ -2,6 +2,7 @@
 
 import json
 import pathlib
+import re
 
 from utils import KafkaTestContext
 
@@ -37,6 +38,56 @@ def test_kafka_raw(tmp_path: pathlib.Path, kafka_context: KafkaTestContext):
     )
 
 
+def test_kafka_topic_pattern(tmp_path: pathlib.Path, kafka_context: KafkaTestContext):
+    kafka_context.fill(["foo"])
+    new_topic = f"{kafka_context.input_topic}-new"
+
+    table = pw.io.kafka.read(
+        rdkafka_settings={
+            **kafka_context.default_rdkafka_settings(),
+            "topic.metadata.refresh.interval.ms": "500",
+        },
+        topic_pattern=f"^{re.escape(kafka_context.input_topic)}.*",
+        format="raw",
+        autocommit_duration_ms=100,
+    )
+
+    pw.io.csv.write(table, str(tmp_path / "output.csv"))
+
+    checker = expect_csv_checker(
+        """
+        data
+        foo
+        bar
+        """,
+        tmp_path / "output.csv",
+        usecols=["data"],
+        index_col=["data"],
+    )
+    first_only_checker = expect_csv_checker(
+        """
+        data
+        foo
+        """,
+        tmp_path / "output.csv",
+        usecols=["data"],
+        index_col=["data"],
+    )
+
+    new_topic_created = False
+
+    def create_topic_when_first_read():
+        # the topic created after the first one is read has to be discovered
+        nonlocal new_topic_created
+        if not new_topic_created and first_only_checker():
+            kafka_context.create_extra_topic(new_topic)
+            kafka_context.fill(["bar"], topic=new_topic)
+            new_topic_created = True
+        return checker()
+
+    wait_result_with_checker(create_topic_when_first_read, 30)
+
+
 def test_kafka_json(tmp_path: pathlib.Path, kafka_context: KafkaTestContext):
     kafka_context.fill(
         [
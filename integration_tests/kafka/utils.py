@@ -22,6 +22,7 @@ class KafkaTestContext:
         )
         self._input_topic = f"integration-tests-{uuid4()}"
         self._output_topic = f"integration-tests-{uuid4()}"
+        self._extra_topics: list[str] = []
         self._create_topic(self.input_topic)
         self._create_topic(self.output_topic)
 
@@ -33,20 +34,29 @@ class KafkaTestContext:
     def _delete_topic(self, name: str) -> None:
         self._admin.delete_topics(topics=[name])
 
-    def send(self, message: str | tuple[str, str]) -> None:
+    def create_extra_topic(self, name: str) -> None:
+        """Creates a topic deleted on the teardown, like the input and output ones"""
+        self._create_topic(name)
+        self._extra_topics.append(name)
+
+    def send(self, message: str | tuple[str, str], topic: str | None = None) -> None:
         if isinstance(message, tuple):
             (key, value) = message
         else:
             (key, value) = str(uuid4()), message
-        self._producer.send(self.input_topic, key=key.encode(), value=value.encode())
+        self._producer.send(
+            topic or self.input_topic, key=key.encode(), value=value.encode()
+        )
 
     def set_input_topic_partitions(self, num_partitions: int):
         self._delete_topic(self._input_topic)
         self._create_topic(self._input_topic, num_partitions)
 
-    def fill(self, messages: Iterable[str | tuple[str, str]]) -> None:
+    def fill(
+        self, messages: Iterable[str | tuple[str, str]], topic: str | None = None
+    ) -> None:
         for msg in messages:
-            self.send(msg)
+            self.send(msg, topic)
         self._producer.flush()
 
     def read_topic(self, topic, poll_timeout_ms: int = 1000) -> list[str]:
@@ -76,6 +86,8 @@ class KafkaTestContext:
     def teardown(self) -> None:
         self._delete_topic(self.input_topic)
         self._delete_topic(self.output_topic)
+        for topic in self._extra_topics:
+            self._delete_topic(topic)
         self._producer.close()
         self._admin.close()
 
@@ -24,6 +24,7 @@ import pathway.universes as universes
 from pathway import debug, demo, io
 from pathway.internals import (
     UDF,
+    AlertThresholds,
     ClassArg,
     ColumnExpression,
     ColumnReference,
@@ -36,14 +37,19 @@ from pathway.internals import (
     JoinMode,
     JoinResult,
     Json,
+    LoggingConfig,
     MonitoringLevel,
     Pointer,
+    RowConverter,
+    RunContext,
+    RunStats,
     Schema,
     SchemaProperties,
     Table,
     TableLike,
     TableSlice,
     UDFAsync,
+    UDFBatch,
     UDFSync,
     __version__,
     apply,
@@ -72,13 +78,16 @@ from pathway.internals import (
     method,
     numba_apply,
     output_attribute,
+    register_serializer,
     require,
     right,
     run,
     run_all,
+    run_context,
     schema_builder,
     schema_from_csv,
     schema_from_dict,
+    schema_from_row_type,
     schema_from_types,
     sql,
     table_transformer,
@@ -86,6 +95,7 @@ from pathway.internals import (
     transformer,
     udf,
     udf_async,
+    udf_batch,
     unwrap,
 )
 from pathway.internals.api import PathwayType as Type, PersistenceMode
@@ -114,8 +124,11 @@ __all__ = [
     "apply",
     "udf",
     "udf_async",
+    "udf_batch",
+    "register_serializer",
     "UDF",
     "UDFAsync",
+    "UDFBatch",
     "UDFSync",
     "apply_async",
     "apply_with_type",
@@ -136,6 +149,8 @@ __all__ = [
     "reducers",
     "transformer",
     "schema_from_types",
+    "schema_from_row_type",
+    "RowConverter",
     "Table",
     "TableLike",
     "ColumnReference",
@@ -143,6 +158,8 @@ __all__ = [
     "Schema",
     "Pointer",
     "MonitoringLevel",
+    "LoggingConfig",
+    "AlertThresholds",
     "WindowJoinResult",
     "this",
     "left",
@@ -154,6 +171,9 @@ __all__ = [
     "sql",
     "run",
     "run_all",
+    "run_context",
+    "RunContext",
+    "RunStats",
     "if_else",
     "make_tuple",
     "Type",
@@ -267,6 +287,10 @@ Table.interval_join_left = temporal.interval_join_left
 Table.interval_join_right = temporal.interval_join_right
 Table.interval_join_outer = temporal.interval_join_outer
 
+Table.range_join = temporal.range_join
+Table.range_join_inner = temporal.range_join_inner
+Table.range_join_left = temporal.range_join_left
+
 Table.interpolate = statistical.interpolate
 Table.windowby = temporal.windowby
 Table.diff = ordered.diff
@@ -17,20 +17,42 @@ def plural(n, singular, plural):
     return f"{n} {plural}"
 
 
-def spawn_program(threads, processes, first_port, program, arguments, env_base):
+def spawn_program(
+    threads,
+    processes,
+    first_port,
+    program,
+    arguments,
+    env_base,
+    addresses=None,
+    process_id=None,
+):
+    if addresses is not None:
+        processes = len(addresses)
     processes_str = plural(processes, "process", "processes")
     workers_str = plural(processes * threads, "total worker", "total workers")
     click.echo(f"Preparing {processes_str} ({workers_str})", err=True)
-    run_id = uuid.uuid4()
+    if process_id is None:
+        process_ids = range(processes)
+        run_id = str(uuid.uuid4())
+    else:
+        # the other processes are spawned on other machines, so the run ID is derived
+        # from the addresses to be the same for all of them
+        process_ids = [process_id]
+        run_id = env_base.get(
+            "PATHWAY_RUN_ID", str(uuid.uuid5(uuid.NAMESPACE_URL, ",".join(addresses)))
+        )
     process_handles = []
     try:
-        for process_id in range(processes):
+        for process_id in process_ids:
             env = env_base.copy()
             env["PATHWAY_THREADS"] = str(threads)
             env["PATHWAY_PROCESSES"] = str(processes)
             env["PATHWAY_FIRST_PORT"] = str(first_port)
+            if addresses is not None:
+                env["PATHWAY_ADDRESSES"] = ",".join(addresses)
             env["PATHWAY_PROCESS_ID"] = str(process_id)
-            env["PATHWAY_RUN_ID"] = str(run_id)
+            env["PATHWAY_RUN_ID"] = run_id
             handle = subprocess.Popen([program] + list(arguments), env=env)
             process_handles.append(handle)
         for handle in process_handles:
@@ -76,6 +98,19 @@ def cli() -> None:
     default=10000,
     help="first port to use for communication",
 )
+@click.option(
+    "--addresses",
+    type=str,
+    metavar="HOST:PORT,...",
+    help="addresses of all processes of a cluster spanning multiple machines, "
+    "in the order of process IDs; overrides --processes and --first-port",
+)
+@click.option(
+    "--process-id",
+    type=int,
+    metavar="ID",
+    help="spawn only the process with this ID, e.g. the one running on this machine",
+)
 @click.option("--record", is_flag=True, help="record data in the input connectors")
 @click.option(
     "--record_path",
@@ -85,13 +120,42 @@ def cli() -> None:
 )
 @click.argument("program")
 @click.argument("arguments", nargs=-1)
-def spawn(threads, processes, first_port, record, record_path, program, arguments):
+def spawn(
+    threads,
+    processes,
+    first_port,
+    addresses,
+    process_id,
+    record,
+    record_path,
+    program,
+    arguments,
+):
     env = os.environ.copy()
     if record:
         env["PATHWAY_REPLAY_STORAGE"] = record_path
         env["PATHWAY_SNAPSHOT_ACCESS"] = "record"
         env["PATHWAY_CONTINUE_AFTER_REPLAY"] = "true"
-    spawn_program(threads, processes, first_port, program, arguments, env)
+    if addresses is not None:
+        addresses = [address.strip() for address in addresses.split(",")]
+        if process_id is not None and not 0 <= process_id < len(addresses):
+            raise click.BadParameter(
+                f"there are only {len(addresses)} addresses", param_hint="--process-id"
+            )
+    elif process_id is not None:
+        raise click.BadParameter(
+            "can only be used together with --addresses", param_hint="--process-id"
+        )
+    spawn_program(
+        threads,
+        processes,
+        first_port,
+        program,
+        arguments,
+        env,
+        addresses=addresses,
+        process_id=process_id,
+    )
 
 
 @cli.command(
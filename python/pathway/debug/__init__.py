@@ -5,15 +5,18 @@ from __future__ import annotations
 import functools
 import io
 import itertools
+import math
 import re
+import threading
 from collections.abc import Iterable
 from os import PathLike
 from warnings import warn
 
 import pandas as pd
+import pyarrow as pa
 
 from pathway import persistence
-from pathway.internals import Json, api, parse_graph
+from pathway.internals import Json, api, dtype as dt, expression as expr, parse_graph
 from pathway.internals.datasource import DataSourceOptions, PandasDataSource
 from pathway.internals.decorators import table_from_datasource
 from pathway.internals.fingerprints import fingerprint
@@ -22,6 +25,7 @@ from pathway.internals.monitoring import MonitoringLevel
 from pathway.internals.runtime_type_check import check_arg_types
 from pathway.internals.schema import Schema, schema_from_pandas
 from pathway.internals.table import Table
+from pathway.internals.table_subscription import subscribe
 from pathway.internals.trace import trace_user_frame
 from pathway.io._utils import read_schema
 from pathway.io.python import ConnectorSubject, read
@@ -214,6 +218,35 @@ def table_to_pandas(table: Table, *, include_id: bool = True):
     return res
 
 
+@check_arg_types
+@trace_user_frame
+def table_to_arrow(table: Table, *, include_id: bool = True) -> pa.Table:
+    """Computes the table and returns its rows as an Arrow table, converted column by
+    column in the engine. With ``include_id``, the ids of the rows are in the ``id``
+    column. The pointers are converted to strings, and so are the JSON values.
+    It's e.g. for polars: ``polars.from_arrow(pw.debug.table_to_arrow(table))``.
+    """
+    captured = _compute_table(table)
+    batch = api.captured_table_to_arrow(
+        captured, list(table._columns.keys()), include_id=include_id
+    )
+    return pa.Table.from_batches([batch])
+
+
+def estimated_size(expression: expr.ColumnExpression) -> expr.ColumnExpression:
+    """Returns the approximate number of bytes taken by the values of an expression
+    in the engine, including the strings, the arrays and the nested tuples they
+    contain. It's the size by which the states of the operators are accounted for
+    ``operator_state_limit`` of ``pw.run``, so it helps to find the oversized rows,
+    e.g. ``table.filter(pw.debug.estimated_size(pw.make_tuple(*table)) > 10_000)``.
+    """
+    return expr.MethodCallExpression(
+        ((dt.ANY, dt.INT, api.Expression.estimated_size),),
+        "estimated_size",
+        expression,
+    )
+
+
 def _validate_dataframe(df: pd.DataFrame) -> None:
     for pseudocolumn in api.PANDAS_PSEUDOCOLUMNS:
         if pseudocolumn in df.columns:
@@ -276,6 +309,10 @@ def table_from_pandas(
     column ``__time__``, rows will be split into batches with timestamps from the column.
     A special column ``__diff__`` can be used to set an event type - with ``1`` treated
     as inserting the row and ``-1`` as removing it.
+
+    The columns of numbers, booleans, strings, bytes, datetimes and durations are passed
+    to the engine in bulk, as Arrow arrays. A polars DataFrame can be converted with its
+    ``to_pandas`` method.
     """
     if id_from is not None and schema is not None:
         raise ValueError("parameters `schema` and `id_from` are mutually exclusive")
@@ -608,3 +645,187 @@ class StreamGenerator:
             snapshot_access=api.SnapshotAccess.REPLAY,
             persistence_mode=api.PersistenceMode.SPEEDRUN_REPLAY,
         )
+
+
+class _StepByStepRunClosed(Exception):
+    pass
+
+
+class StepByStepRun:
+    """Runs the computation one epoch at a time, on demand, so that the contents of
+    the tables after each epoch can be inspected, e.g. in tests of streaming logic,
+    without waiting for the results.
+
+    The computation is run in a background thread, like by ``pw.run``, with the tables
+    subscribed to. It's paused each time the changes of the tables at all the times
+    before some time are passed to the subscriptions. The epochs are the times
+    of the changes of the tables, so they don't depend on how many of them
+    the computation processed at once before being paused.
+
+    Args:
+        tables: the tables whose contents are inspected.
+        persistence_config: the config passed to ``pw.run``, e.g. the one of
+            a ``StreamGenerator``.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> t1 = pw.debug.table_from_markdown('''
+    ... value | __time__
+    ... 1     | 2
+    ... 2     | 2
+    ... 3     | 4
+    ... ''')
+    >>> t2 = t1.reduce(sum=pw.reducers.sum(pw.this.value))
+    >>> with pw.debug.StepByStepRun(t2) as run:
+    ...     while run.step() is not None:
+    ...         print(run.table_state(t2, include_id=False)["sum"].tolist())
+    [3]
+    [6]
+    """
+
+    def __init__(
+        self,
+        *tables: Table,
+        persistence_config: persistence.Config | None = None,
+    ) -> None:
+        self._tables = tables
+        self._persistence_config = persistence_config
+        self._condition = threading.Condition()
+        self._updates: list[list[tuple[int, api.Pointer, dict, bool]]] = [
+            [] for _ in tables
+        ]
+        # the frontiers of the subscriptions and the one the computation is paused at
+        self._frontiers = [0.0] * len(tables)
+        self._frontier = 0.0
+        self._time = -1
+        self._paused = False
+        self._finished = False
+        self._closed = False
+        self._error: Exception | None = None
+        self._thread: threading.Thread | None = None
+        for index, table in enumerate(tables):
+            self._subscribe(index, table)
+
+    def _subscribe(self, index: int, table: Table) -> None:
+        def on_change(key, row, time, is_addition):
+            with self._condition:
+                self._updates[index].append((time, key, row, is_addition))
+
+        def on_commit(time):
+            with self._condition:
+                self._frontiers[index] = time
+                self._pause_at_frontier()
+
+        def on_end():
+            with self._condition:
+                self._frontiers[index] = math.inf
+
+        subscribe(
+            table,
+            skip_persisted_batch=False,
+            on_change=on_change,
+            on_end=on_end,
+            on_commit=on_commit,
+        )
+
+    def _times(self, start: float, end: float) -> set[int]:
+        return {
+            time
+            for updates in self._updates
+            for time, *_ in updates
+            if start <= time < end
+        }
+
+    def _pause_at_frontier(self) -> None:
+        if self._closed:
+            raise _StepByStepRunClosed()
+        frontier = min(self._frontiers)
+        if frontier <= self._frontier or frontier == math.inf:
+            return
+        has_changes = bool(self._times(self._frontier, frontier))
+        self._frontier = frontier
+        if not has_changes:
+            return
+        self._paused = True
+        self._condition.notify_all()
+        self._condition.wait_for(lambda: not self._paused or self._closed)
+        if self._closed:
+            raise _StepByStepRunClosed()
+
+    def _run(self) -> None:
+        try:
+            GraphRunner(
+                parse_graph.G,
+                monitoring_level=MonitoringLevel.NONE,
+                persistence_config=self._persistence_config,
+            ).run_outputs()
+        except Exception as e:
+            with self._condition:
+                if not self._closed:
+                    self._error = e
+        finally:
+            with self._condition:
+                self._finished = True
+                self._frontier = math.inf
+                self._condition.notify_all()
+
+    def step(self) -> int | None:
+        """Advances to the next epoch, running the computation until its changes of
+        the tables are known, and returns its time. Returns None once the computation
+        is finished and all the epochs are passed."""
+        with self._condition:
+            while True:
+                times = self._times(self._time + 1, self._frontier)
+                if times:
+                    self._time = min(times)
+                    return self._time
+                if self._finished:
+                    return None
+                if self._thread is None:
+                    self._thread = threading.Thread(
+                        target=self._run, name="pathway:step-by-step", daemon=True
+                    )
+                    self._thread.start()
+                self._paused = False
+                self._condition.notify_all()
+                self._condition.wait_for(lambda: self._paused or self._finished)
+                if self._error is not None:
+                    raise self._error
+
+    def table_state(self, table: Table, *, include_id: bool = True) -> pd.DataFrame:
+        """Returns the contents of the table at the end of the current epoch, in
+        the same format as ``table_to_pandas``."""
+        [index] = [i for i, other in enumerate(self._tables) if other is table]
+        with self._condition:
+            updates = [
+                update for update in self._updates[index] if update[0] <= self._time
+            ]
+        # deletions before insertions of the same key at the same time
+        updates.sort(key=lambda update: (update[0], update[3]))
+        rows: dict[api.Pointer, dict] = {}
+        for _time, key, row, is_addition in updates:
+            if is_addition:
+                rows[key] = row
+            else:
+                rows.pop(key, None)
+        keys = list(rows.keys())
+        data = list(rows.values())
+        columns = list(table._columns.keys())
+        if include_id:
+            return pd.DataFrame(data, index=keys, columns=columns)
+        return pd.DataFrame(data, columns=columns)
+
+    def close(self) -> None:
+        """Stops the computation, if it's still running, and waits for it to end."""
+        with self._condition:
+            self._closed = True
+            self._condition.notify_all()
+        if self._thread is not None:
+            self._thread.join()
+
+    def __enter__(self) -> StepByStepRun:
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback) -> None:
+        self.close()
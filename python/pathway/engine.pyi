@@ -10,6 +10,8 @@ from collections.abc import Callable, Iterable
 from enum import Enum
 from typing import Any, Generic, TypeVar, Union, final
 
+import pyarrow as pa
+
 from pathway.internals.api import CapturedStream, CombineMany, S, Value
 from pathway.internals.column_path import ColumnPath
 from pathway.internals.dtype import DType
@@ -17,6 +19,8 @@ from pathway.internals.monitoring import StatsMonitor
 
 _T = TypeVar("_T")
 
+KEY_BITS: int
+
 @final
 class Pointer(Generic[_T]):
     pass
@@ -65,6 +69,7 @@ class ColumnProperties:
     dtype: PathwayType | None = None
     trace: Trace | None = None
     append_only: bool = False
+    nullable: bool = True
 
 class TableProperties:
     @staticmethod
@@ -73,6 +78,7 @@ class TableProperties:
     def from_column_properties(
         column_properties: Iterable[tuple[ColumnPath, ColumnProperties]]
     ) -> TableProperties: ...
+    def columns(self) -> list[tuple[tuple[int, ...], ColumnProperties]]: ...
 
 @dataclasses.dataclass(frozen=True)
 class ConnectorProperties:
@@ -150,6 +156,13 @@ class Reducer:
     COUNT: Reducer
     @staticmethod
     def stateful_many(combine_many: CombineMany[S]) -> Reducer: ...
+    @staticmethod
+    def retractable(
+        init: Callable[[], Any],
+        add: Callable[..., Any],
+        retract: Callable[..., Any],
+        extract: Callable[[Any], Value],
+    ) -> Reducer: ...
 
 class UnaryOperator:
     INV: UnaryOperator
@@ -208,6 +221,10 @@ class Expression:
     @staticmethod
     def float_abs(lhs: Expression, rhs: Expression) -> Expression: ...
     @staticmethod
+    def pointer_sample_rank(pointer: Expression, seed: Expression) -> Expression: ...
+    @staticmethod
+    def estimated_size(expr: Expression) -> Expression: ...
+    @staticmethod
     def cast(
         expr: Expression, source_type: PathwayType, target_type: PathwayType
     ) -> Expression | None: ...
@@ -245,6 +262,14 @@ class Expression:
     @staticmethod
     def date_time_naive_weekday(expr: Expression) -> Expression: ...
     @staticmethod
+    def date_time_naive_iso_week(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_naive_iso_year(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_naive_quarter(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_naive_day_of_year(expr: Expression) -> Expression: ...
+    @staticmethod
     def date_time_naive_strptime(expr: Expression, fmt: Expression) -> Expression: ...
     @staticmethod
     def date_time_naive_strftime(expr: Expression, fmt: Expression) -> Expression: ...
@@ -257,6 +282,14 @@ class Expression:
         expr: Expression, unit: Expression
     ) -> Expression: ...
     @staticmethod
+    def date_time_naive_from_excel_serial(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_naive_from_julian_day(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_naive_excel_serial(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_naive_julian_day(expr: Expression) -> Expression: ...
+    @staticmethod
     def date_time_naive_to_utc(
         expr: Expression, from_timezone: Expression
     ) -> Expression: ...
@@ -265,6 +298,18 @@ class Expression:
     @staticmethod
     def date_time_naive_floor(expr: Expression, duration: Expression) -> Expression: ...
     @staticmethod
+    def date_time_naive_floor_to_calendar_unit(
+        expr: Expression, unit: Expression
+    ) -> Expression: ...
+    @staticmethod
+    def date_time_naive_round_to_calendar_unit(
+        expr: Expression, unit: Expression
+    ) -> Expression: ...
+    @staticmethod
+    def date_time_naive_add_calendar_units(
+        expr: Expression, unit: Expression, count: Expression
+    ) -> Expression: ...
+    @staticmethod
     def date_time_utc_nanosecond(expr: Expression) -> Expression: ...
     @staticmethod
     def date_time_utc_microsecond(expr: Expression) -> Expression: ...
@@ -289,10 +334,32 @@ class Expression:
     @staticmethod
     def date_time_utc_weekday(expr: Expression) -> Expression: ...
     @staticmethod
+    def date_time_utc_iso_week(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_utc_iso_year(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_utc_quarter(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_utc_day_of_year(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_utc_julian_day(expr: Expression) -> Expression: ...
+    @staticmethod
     def date_time_utc_strptime(expr: Expression, fmt: Expression) -> Expression: ...
     @staticmethod
+    def date_time_utc_parse_rfc3339(expr: Expression) -> Expression: ...
+    @staticmethod
+    def date_time_utc_parse_rfc2822(expr: Expression) -> Expression: ...
+    @staticmethod
     def date_time_utc_strftime(expr: Expression, fmt: Expression) -> Expression: ...
     @staticmethod
+    def date_time_utc_strftime_in_timezone(
+        expr: Expression, fmt: Expression, timezone: Expression
+    ) -> Expression: ...
+    @staticmethod
+    def date_time_utc_offset_seconds(
+        expr: Expression, timezone: Expression
+    ) -> Expression: ...
+    @staticmethod
     def date_time_utc_to_naive(
         expr: Expression, to_timezone: Expression
     ) -> Expression: ...
@@ -301,6 +368,30 @@ class Expression:
     @staticmethod
     def date_time_utc_floor(expr: Expression, duration: Expression) -> Expression: ...
     @staticmethod
+    def date_time_utc_floor_to_calendar_unit(
+        expr: Expression, unit: Expression
+    ) -> Expression: ...
+    @staticmethod
+    def date_time_utc_round_to_calendar_unit(
+        expr: Expression, unit: Expression
+    ) -> Expression: ...
+    @staticmethod
+    def date_time_utc_add_calendar_units(
+        expr: Expression, unit: Expression, count: Expression
+    ) -> Expression: ...
+    @staticmethod
+    def date_time_utc_floor_to_calendar_unit_in_timezone(
+        expr: Expression, unit: Expression, timezone: Expression
+    ) -> Expression: ...
+    @staticmethod
+    def date_time_utc_round_to_calendar_unit_in_timezone(
+        expr: Expression, unit: Expression, timezone: Expression
+    ) -> Expression: ...
+    @staticmethod
+    def date_time_utc_add_calendar_units_in_timezone(
+        expr: Expression, unit: Expression, count: Expression, timezone: Expression
+    ) -> Expression: ...
+    @staticmethod
     def duration_nanoseconds(expr: Expression) -> Expression: ...
     @staticmethod
     def duration_microseconds(expr: Expression) -> Expression: ...
@@ -390,6 +481,7 @@ class Scope:
         ],
         *,
         limit: int | None = None,
+        fail_on_limit: bool = False,
     ) -> tuple[list[LegacyTable], list[LegacyTable]]:
         """Fixed-point iteration
 
@@ -412,6 +504,17 @@ class Scope:
         rows: Iterable[DataRow],
         dt: DType,
     ) -> Table: ...
+    def static_table_from_arrow(
+        self,
+        batch: pa.RecordBatch,
+        properties: ConnectorProperties,
+        value_columns: list[str],
+        key_columns: list[str],
+        unsafe_trusted_ids: bool = False,
+        time_column: str | None = None,
+        diff_column: str | None = None,
+        shard_column: str | None = None,
+    ) -> Table: ...
     def map_column(
         self,
         table: LegacyTable,
@@ -439,6 +542,15 @@ class Scope:
         column_paths: list[ColumnPath],
         function: Callable[..., Value],
         properties: TableProperties,
+        max_in_flight: int | None = None,
+    ) -> Table: ...
+    def batch_apply_table(
+        self,
+        table: Table,
+        column_paths: list[ColumnPath],
+        function: Callable[..., Any],
+        properties: TableProperties,
+        max_batch_size: int | None = None,
     ) -> Table: ...
     def gradual_broadcast(
         self,
@@ -470,6 +582,20 @@ class Scope:
         table: Table,
         table_properties: TableProperties,
     ) -> Table: ...
+    def expire_after(
+        self,
+        table: Table,
+        duration: int,
+        table_properties: TableProperties,
+    ) -> Table: ...
+    def deduplicate_within(
+        self,
+        table: Table,
+        fingerprint_column_path: ColumnPath,
+        time_column_path: ColumnPath,
+        expiration_column_path: ColumnPath,
+        table_properties: TableProperties,
+    ) -> Table: ...
     def freeze(
         self,
         table: Table,
@@ -477,6 +603,20 @@ class Scope:
         current_time_path: ColumnPath,
         table_properties: TableProperties,
     ) -> Table: ...
+    def freeze_late(
+        self,
+        table: Table,
+        threshold_time_path: ColumnPath,
+        current_time_path: ColumnPath,
+        table_properties: TableProperties,
+    ) -> Table: ...
+    def reshard_table(
+        self,
+        table: Table,
+        column_paths: Iterable[ColumnPath],
+        table_properties: TableProperties,
+        parallelism: int | None = None,
+    ) -> Table: ...
     def buffer(
         self,
         table: Table,
@@ -572,6 +712,9 @@ class Scope:
     def flatten_table(
         self, table: Table, path: ColumnPath, table_properties: TableProperties
     ) -> Table: ...
+    def changelog_table(
+        self, table: Table, table_properties: TableProperties
+    ) -> Table: ...
     def sort_table(
         self,
         table: Table,
@@ -579,6 +722,39 @@ class Scope:
         instance_column_path: ColumnPath,
         table_properties: TableProperties,
     ) -> Table: ...
+    def session_window_table(
+        self,
+        table: Table,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        max_gap: Value,
+        table_properties: TableProperties,
+    ) -> Table: ...
+    def top_n_table(
+        self,
+        table: Table,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        n: int,
+        descending: bool,
+        table_properties: TableProperties,
+    ) -> Table: ...
+    def rank_table(
+        self,
+        table: Table,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        descending: bool,
+        table_properties: TableProperties,
+    ) -> Table: ...
+    def cumulative_table(
+        self,
+        table: Table,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        reducers: list[tuple[Reducer, list[ColumnPath]]],
+        table_properties: TableProperties,
+    ) -> Table: ...
     def probe_table(self, table: Table, operator_id: int): ...
     def subscribe_table(
         self,
@@ -588,6 +764,7 @@ class Scope:
         on_change: Callable,
         on_time_end: Callable,
         on_end: Callable,
+        on_commit: Callable | None = None,
     ): ...
     def output_table(
         self,
@@ -596,6 +773,13 @@ class Scope:
         data_sink: DataStorage,
         data_format: DataFormat,
     ): ...
+    def serve_table(
+        self,
+        table: Table,
+        column_paths: Iterable[ColumnPath],
+        name: str,
+        column_names: list[str],
+    ): ...
 
 def run_with_new_graph(
     logic: Callable[[Scope], Iterable[tuple[Table, list[ColumnPath]]]],
@@ -606,8 +790,30 @@ def run_with_new_graph(
     monitoring_level: MonitoringLevel = MonitoringLevel.NONE,
     with_http_server: bool = False,
     persistence_config: PersistenceConfig | None = None,
+    batch_latency_target_ms: int | None = None,
+    operator_state_limit: int | None = None,
+    fail_on_operator_state_limit: bool = False,
+    consistent_outputs: bool = False,
+    otlp_endpoint: str | None = None,
+    shutdown_handle: ShutdownHandle | None = None,
+    topology: list[tuple[int, str, list[int]]] = [],
+    alert_thresholds: AlertThresholds | None = None,
 ) -> list[CapturedStream]: ...
 def unsafe_make_pointer(arg) -> Pointer: ...
+def captured_table_to_arrow(
+    captured: CapturedStream, column_names: list[str], include_id: bool = True
+) -> pa.RecordBatch: ...
+def restore_savepoint(persistence_config: PersistenceConfig, name: str) -> None: ...
+def refresh_engine_logging(*, trace: bool = False) -> None: ...
+def export_persisted_rows(
+    persistence_config: PersistenceConfig, persistent_id: str
+) -> tuple[list[str], list[tuple[Pointer, list[Value]]]]: ...
+def import_persisted_rows(
+    persistence_config: PersistenceConfig,
+    persistent_id: str,
+    columns: list[str],
+    rows: list[tuple[Pointer, list[Value]]],
+) -> None: ...
 
 class DataFormat:
     value_fields: Any
@@ -633,6 +839,10 @@ class DataStorage:
     mock_events: dict[tuple[str, int], list[SnapshotEvent]] | None
     table_name: str | None
     column_names: list[str] | None
+    transaction_id: str | None
+    python_sink: PythonSink | None
+    azure_blob_storage_settings: AzureBlobStorageSettings | None
+    gcs_settings: GcsSettings | None
     def __init__(self, *args, **kwargs): ...
 
 class CsvParserSettings:
@@ -641,6 +851,24 @@ class CsvParserSettings:
 class AwsS3Settings:
     def __init__(self, *args, **kwargs): ...
 
+class AzureBlobStorageSettings:
+    def __init__(
+        self,
+        account_name: str,
+        container_name: str,
+        account_key: str | None = None,
+        sas_token: str | None = None,
+        endpoint: str | None = None,
+    ) -> None: ...
+
+class GcsSettings:
+    def __init__(
+        self,
+        bucket_name: str,
+        credentials_json: str | None = None,
+        endpoint: str | None = None,
+    ) -> None: ...
+
 class ValueField:
     name: str
     def __init__(self, *args, **kwargs): ...
@@ -649,14 +877,46 @@ class ValueField:
 class PythonSubject:
     def __init__(self, *args, **kwargs): ...
 
+class PythonSink:
+    def __init__(
+        self, write: Callable[[bytes], None], flush: Callable[[], None]
+    ) -> None: ...
+
 class ElasticSearchAuth:
     def __init__(self, *args, **kwargs): ...
 
 class ElasticSearchParams:
     def __init__(self, *args, **kwargs): ...
 
+class ShutdownHandle:
+    def __init__(self) -> None: ...
+    def request(self) -> None: ...
+
+class AlertThresholds:
+    def __init__(
+        self,
+        *,
+        max_output_staleness_ms: int | None = None,
+        max_source_lag: int | None = None,
+        max_checkpoint_age_ms: int | None = None,
+        webhook_url: str | None = None,
+    ) -> None: ...
+
 class PersistenceConfig:
     def __init__(self, *args, **kwargs): ...
+    def request_savepoint(self, name: str) -> None: ...
+    def register_checkpoint_hooks(
+        self,
+        *,
+        on_checkpoint_start: Callable[[int | None], None] | None = None,
+        on_checkpoint_complete: Callable[[int | None], None] | None = None,
+        on_restore_complete: Callable[[int], None] | None = None,
+    ) -> None: ...
+
+class ColumnMigration:
+    def __init__(
+        self, *, renamed_from: str | None = None, fill_value: Value | None = None
+    ): ...
 
 class PersistenceMode(Enum):
     BATCH: PersistenceMode
@@ -47,10 +47,15 @@ from pathway.internals.joins import (
     join_right,
 )
 from pathway.internals.json import Json
-from pathway.internals.monitoring import MonitoringLevel
+from pathway.internals.monitoring import (
+    AlertThresholds,
+    LoggingConfig,
+    MonitoringLevel,
+)
 from pathway.internals.operator import iterate_universe
+from pathway.internals.row_conversion import RowConverter, schema_from_row_type
 from pathway.internals.row_transformer import ClassArg
-from pathway.internals.run import run, run_all
+from pathway.internals.run import RunContext, RunStats, run, run_all, run_context
 from pathway.internals.schema import (
     Schema,
     SchemaProperties,
@@ -60,12 +65,21 @@ from pathway.internals.schema import (
     schema_from_dict,
     schema_from_types,
 )
+from pathway.internals.serialization import register_serializer
 from pathway.internals.sql import sql
 from pathway.internals.table import Table, groupby
 from pathway.internals.table_like import TableLike
 from pathway.internals.table_slice import TableSlice
 from pathway.internals.thisclass import left, right, this
-from pathway.internals.udf import UDF, UDFAsync, UDFSync, udf, udf_async
+from pathway.internals.udf import (
+    UDF,
+    UDFAsync,
+    UDFBatch,
+    UDFSync,
+    udf,
+    udf_async,
+    udf_batch,
+)
 from pathway.internals.version import __version__
 
 __all__ = [
@@ -77,8 +91,11 @@ __all__ = [
     "apply",
     "udf",
     "udf_async",
+    "udf_batch",
+    "register_serializer",
     "UDF",
     "UDFAsync",
+    "UDFBatch",
     "UDFSync",
     "apply_async",
     "apply_with_type",
@@ -91,6 +108,8 @@ __all__ = [
     "transformer",
     "iterate_universe",
     "schema_from_types",
+    "schema_from_row_type",
+    "RowConverter",
     "GroupedTable",
     "GroupedJoinResult",
     "JoinResult",
@@ -103,6 +122,8 @@ __all__ = [
     "Schema",
     "Pointer",
     "MonitoringLevel",
+    "LoggingConfig",
+    "AlertThresholds",
     "WindowJoinResult",
     "this",
     "left",
@@ -115,6 +136,9 @@ __all__ = [
     "sql",
     "run",
     "run_all",
+    "run_context",
+    "RunContext",
+    "RunStats",
     "numba_apply",
     "__version__",
     "universes",
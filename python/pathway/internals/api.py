@@ -7,6 +7,7 @@ from typing import TYPE_CHECKING, Any, Generic, Protocol, TypeAlias, TypeVar, Un
 
 import numpy as np
 import pandas as pd
+import pyarrow as pa
 
 from pathway.engine import *
 from pathway.internals import dtype as dt, json
@@ -80,12 +81,88 @@ SHARD_PSEUDOCOLUMN = "__shard__"
 PANDAS_PSEUDOCOLUMNS = {TIME_PSEUDOCOLUMN, DIFF_PSEUDOCOLUMN, SHARD_PSEUDOCOLUMN}
 
 
+_ARROW_INDEX_COLUMN = "__index__"
+
+
+def _arrow_array_from_pandas(values: pd.Series | pd.Index) -> pa.Array | None:
+    """Converts the values to an Arrow array, if the engine converts it the same way
+    as the Python objects. The objects are converted only if they are all strings
+    or all bytes, as otherwise Arrow may unify their types, e.g. ints with floats."""
+    try:
+        array = pa.array(values, from_pandas=True)
+    except (pa.ArrowException, TypeError, ValueError):
+        return None
+    supported = [
+        pa.types.is_null,
+        pa.types.is_string,
+        pa.types.is_large_string,
+        pa.types.is_binary,
+        pa.types.is_large_binary,
+    ]
+    if values.dtype != object:
+        supported += [
+            pa.types.is_boolean,
+            pa.types.is_integer,
+            pa.types.is_floating,
+            pa.types.is_timestamp,
+            pa.types.is_duration,
+        ]
+    if not any(is_supported(array.type) for is_supported in supported):
+        return None
+    return array
+
+
+def _static_table_from_arrow(
+    scope,
+    df: pd.DataFrame,
+    connector_properties: ConnectorProperties,
+    id_from: list[str] | None,
+) -> Table | None:
+    """Passes the columns of the dataframe to the engine in bulk, as Arrow arrays.
+    Returns None if any of the columns can't be passed this way."""
+    columns: dict[str, pd.Series | pd.Index] = {
+        str(name): df[name] for name in df.columns
+    }
+    if id_from is None:
+        if _ARROW_INDEX_COLUMN in columns:
+            return None
+        columns[_ARROW_INDEX_COLUMN] = df.index
+        key_columns = [_ARROW_INDEX_COLUMN]
+    else:
+        key_columns = [str(name) for name in id_from]
+    arrays = [_arrow_array_from_pandas(values) for values in columns.values()]
+    if any(array is None for array in arrays):
+        return None
+    batch = pa.RecordBatch.from_arrays(arrays, names=list(columns.keys()))
+
+    def pseudocolumn(name: str) -> str | None:
+        return name if name in df.columns else None
+
+    return scope.static_table_from_arrow(
+        batch,
+        connector_properties,
+        value_columns=[
+            str(name) for name in df.columns if name not in PANDAS_PSEUDOCOLUMNS
+        ],
+        key_columns=key_columns,
+        unsafe_trusted_ids=id_from is None and connector_properties.unsafe_trusted_ids,
+        time_column=pseudocolumn(TIME_PSEUDOCOLUMN),
+        diff_column=pseudocolumn(DIFF_PSEUDOCOLUMN),
+        shard_column=pseudocolumn(SHARD_PSEUDOCOLUMN),
+    )
+
+
 def static_table_from_pandas(
     scope,
     df: pd.DataFrame,
     connector_properties: ConnectorProperties | None = None,
     id_from: list[str] | None = None,
 ) -> Table:
+    if connector_properties is not None:
+        table = _static_table_from_arrow(scope, df, connector_properties, id_from)
+        if table is not None:
+            return table
+
     ids = ids_from_pandas(df, connector_properties, id_from)
 
     data = {}
@@ -105,7 +182,10 @@ def static_table_from_pandas(
                     dtype = type(v)
                     break
             column_properties.append(
-                ColumnProperties(dtype=dt.wrap(dtype).map_to_engine())
+                ColumnProperties(
+                    dtype=dt.wrap(dtype).map_to_engine(),
+                    nullable=any(v is None for v in data[c]),
+                )
             )
         connector_properties = ConnectorProperties(column_properties=column_properties)
 
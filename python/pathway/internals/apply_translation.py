@@ -0,0 +1,293 @@
+# Copyright © 2024 Pathway
+
+"""Translation of simple functions passed to ``pw.apply`` into native expressions.
+
+A function whose body is a single expression built from arithmetic, comparisons,
+boolean operators, conditional expressions and a few string methods is translated
+into an equivalent column expression, so that it's evaluated by the engine instead
+of calling the function (and holding the GIL) for every row. The translation is done
+only if the types of all the arguments are known to be ``int``, ``float``, ``str``
+or ``bool`` and the result is the same as the one of the function for all the values
+(apart from the integers not fitting in 64 bits, which the columns can't hold anyway).
+Otherwise the function is applied as usual.
+"""
+
+from __future__ import annotations
+
+import ast
+import builtins
+import inspect
+import textwrap
+from collections.abc import Callable, Mapping, Sequence
+from itertools import chain
+from typing import Any
+
+from pathway.internals import dtype as dt, expression as expr
+
+# The Python types of the translated expressions, by their dtypes
+_SIMPLE_TYPES: dict[dt.DType, type] = {
+    dt.INT: int,
+    dt.FLOAT: float,
+    dt.STR: str,
+    dt.BOOL: bool,
+}
+
+# String methods with the same results for all the arguments of the given types
+_STRING_METHODS: dict[str, tuple[tuple[type, ...], type]] = {
+    "lower": ((), str),
+    "upper": ((), str),
+    "swapcase": ((), str),
+    "title": ((), str),
+    "strip": ((), str),
+    "startswith": ((str,), bool),
+    "endswith": ((str,), bool),
+    "removeprefix": ((str,), str),
+    "removesuffix": ((str,), str),
+    "replace": ((str, str), str),
+}
+
+
+class _NotTranslatable(Exception):
+    pass
+
+
+_Typed = tuple[expr.ColumnExpression, type]
+
+
+def _require(condition: bool) -> None:
+    if not condition:
+        raise _NotTranslatable()
+
+
+def _python_type(value: Any) -> type:
+    _require(type(value) in (int, float, str, bool))
+    return type(value)
+
+
+def _function_body(fun: Callable) -> tuple[ast.arguments, ast.expr]:
+    """Finds the arguments and the returned expression of the function in its source."""
+    try:
+        source = textwrap.dedent(inspect.getsource(fun))
+        tree = ast.parse(source)
+    except (OSError, TypeError, SyntaxError):
+        raise _NotTranslatable()
+    if fun.__name__ == "<lambda>":
+        lambdas = [node for node in ast.walk(tree) if isinstance(node, ast.Lambda)]
+        # with more lambdas in the same lines, it's not known which one is the function
+        _require(len(lambdas) == 1)
+        return lambdas[0].args, lambdas[0].body
+    functions = [
+        node
+        for node in ast.walk(tree)
+        if isinstance(node, ast.FunctionDef) and node.name == fun.__name__
+    ]
+    _require(len(functions) == 1)
+    [function] = functions
+    body = function.body
+    if (
+        len(body) > 1
+        and isinstance(body[0], ast.Expr)
+        and isinstance(body[0].value, ast.Constant)
+        and isinstance(body[0].value.value, str)
+    ):
+        body = body[1:]  # skipping the docstring
+    _require(len(body) == 1 and isinstance(body[0], ast.Return))
+    returned = body[0].value
+    _require(returned is not None)
+    assert returned is not None
+    return function.args, returned
+
+
+def _to_float(typed: _Typed) -> expr.ColumnExpression:
+    expression, python_type = typed
+    if python_type is int:
+        return expr.CastExpression(float, expression)
+    return expression
+
+
+class _Translator:
+    def __init__(self, fun: Callable, arguments: dict[str, _Typed]) -> None:
+        self.arguments = arguments
+        closure_vars = inspect.getclosurevars(fun)
+        self.names = {
+            **closure_vars.builtins,
+            **closure_vars.globals,
+            **closure_vars.nonlocals,
+        }
+
+    def translate(self, node: ast.expr) -> _Typed:
+        match node:
+            case ast.Constant(value=value):
+                return expr.ColumnConstExpression(value), _python_type(value)
+            case ast.Name(id=name):
+                if name in self.arguments:
+                    return self.arguments[name]
+                _require(name in self.names)
+                value = self.names[name]
+                return expr.ColumnConstExpression(value), _python_type(value)
+            case ast.BinOp(left=left, op=op, right=right):
+                return self.translate_binary(
+                    op, self.translate(left), self.translate(right)
+                )
+            case ast.UnaryOp(op=op, operand=operand):
+                expression, python_type = self.translate(operand)
+                if isinstance(op, ast.Not):
+                    _require(python_type is bool)
+                    return ~expression, bool
+                _require(python_type in (int, float))
+                if isinstance(op, ast.USub):
+                    return -expression, python_type
+                _require(isinstance(op, ast.UAdd))
+                return expression, python_type
+            case ast.BoolOp(op=op, values=values):
+                # if_else evaluates only the branch taken, like the short-circuiting
+                # of Python, so e.g. a division by zero in the other one doesn't fail
+                typed_values = [self.translate(value) for value in values]
+                _require(all(python_type is bool for _, python_type in typed_values))
+                result = typed_values[-1][0]
+                for expression, _ in reversed(typed_values[:-1]):
+                    if isinstance(op, ast.And):
+                        result = expr.IfElseExpression(expression, result, False)
+                    else:
+                        result = expr.IfElseExpression(expression, True, result)
+                return result, bool
+            case ast.Compare(left=left, ops=ops, comparators=comparators):
+                operands = [self.translate(left)] + [
+                    self.translate(comparator) for comparator in comparators
+                ]
+                comparisons = [
+                    self.translate_comparison(op, lhs, rhs)
+                    for op, lhs, rhs in zip(ops, operands, operands[1:])
+                ]
+                result = comparisons[-1]
+                for comparison in reversed(comparisons[:-1]):
+                    result = expr.IfElseExpression(comparison, result, False)
+                return result, bool
+            case ast.IfExp(test=test, body=body, orelse=orelse):
+                condition, condition_type = self.translate(test)
+                _require(condition_type is bool)
+                then, then_type = self.translate(body)
+                else_, else_type = self.translate(orelse)
+                _require(then_type is else_type)
+                return expr.IfElseExpression(condition, then, else_), then_type
+            case ast.Call(func=ast.Attribute(value=value, attr=method), args=args):
+                _require(not node.keywords and method in _STRING_METHODS)
+                expression, python_type = self.translate(value)
+                _require(python_type is str)
+                arg_types, result_type = _STRING_METHODS[method]
+                typed_args = [self.translate(arg) for arg in args]
+                _require(
+                    tuple(python_type for _, python_type in typed_args) == arg_types
+                )
+                method_args = [expression for expression, _ in typed_args]
+                return getattr(expression.str, method)(*method_args), result_type
+            case ast.Call(func=ast.Name(id=name), args=[arg]):
+                _require(not node.keywords)
+                function = self.names.get(name)
+                _require(name not in self.arguments)
+                expression, python_type = self.translate(arg)
+                if function is builtins.len and python_type is str:
+                    return expression.str.len(), int
+                if function is builtins.abs and python_type in (int, float):
+                    return abs(expression), python_type
+                if function is builtins.float and python_type is int:
+                    return _to_float((expression, python_type)), float
+                raise _NotTranslatable()
+            case _:
+                raise _NotTranslatable()
+
+    def translate_binary(
+        self, op: ast.operator, left: _Typed, right: _Typed
+    ) -> _Typed:
+        left_type, right_type = left[1], right[1]
+        numeric = left_type in (int, float) and right_type in (int, float)
+        match op:
+            case ast.Add() if left_type is str and right_type is str:
+                return left[0] + right[0], str
+            case ast.Add() | ast.Sub() | ast.Mult() if numeric:
+                if left_type is int and right_type is int:
+                    lhs, rhs = left[0], right[0]
+                    result_type: type = int
+                else:
+                    lhs, rhs = _to_float(left), _to_float(right)
+                    result_type = float
+                if isinstance(op, ast.Add):
+                    return lhs + rhs, result_type
+                if isinstance(op, ast.Sub):
+                    return lhs - rhs, result_type
+                return lhs * rhs, result_type
+            case ast.Div() if numeric:
+                return _to_float(left) / _to_float(right), float
+            case ast.FloorDiv() if left_type is int and right_type is int:
+                return left[0] // right[0], int
+            case ast.Mod() if left_type is int and right_type is int:
+                return left[0] % right[0], int
+            case _:
+                raise _NotTranslatable()
+
+    def translate_comparison(
+        self, op: ast.cmpop, left: _Typed, right: _Typed
+    ) -> expr.ColumnExpression:
+        left_type, right_type = left[1], right[1]
+        if left_type in (int, float) and right_type in (int, float):
+            if left_type is int and right_type is int:
+                lhs, rhs = left[0], right[0]
+            else:
+                lhs, rhs = _to_float(left), _to_float(right)
+        else:
+            _require(left_type is right_type)
+            _require(left_type is str or isinstance(op, (ast.Eq, ast.NotEq)))
+            lhs, rhs = left[0], right[0]
+        match op:
+            case ast.Eq():
+                return lhs == rhs
+            case ast.NotEq():
+                return lhs != rhs
+            case ast.Lt():
+                return lhs < rhs
+            case ast.LtE():
+                return lhs <= rhs
+            case ast.Gt():
+                return lhs > rhs
+            case ast.GtE():
+                return lhs >= rhs
+            case _:
+                raise _NotTranslatable()
+
+
+def translate_apply(
+    fun: Callable,
+    return_type: dt.DType,
+    args: Sequence[tuple[expr.ColumnExpression, dt.DType]],
+    kwargs: Mapping[str, tuple[expr.ColumnExpression, dt.DType]],
+) -> expr.ColumnExpression | None:
+    """Returns a native expression equivalent to applying the function to the arguments
+    of the given dtypes, or None if the function can't be translated. The expression has
+    the return type of the function."""
+    if not inspect.isfunction(fun):
+        return None
+    try:
+        arguments, body = _function_body(fun)
+        _require(
+            not arguments.posonlyargs
+            and not arguments.kwonlyargs
+            and not arguments.defaults
+            and arguments.vararg is None
+            and arguments.kwarg is None
+        )
+        names = [argument.arg for argument in arguments.args]
+        _require(len(names) == len(args) + len(kwargs))
+        typed_arguments: dict[str, _Typed] = {}
+        for name, (arg, dtype) in chain(zip(names, args), kwargs.items()):
+            _require(name in names and name not in typed_arguments)
+            _require(dtype in _SIMPLE_TYPES)
+            typed_arguments[name] = (arg, _SIMPLE_TYPES[dtype])
+        expression, python_type = _Translator(fun, typed_arguments).translate(body)
+    except _NotTranslatable:
+        return None
+
+    if return_type != dt.ANY and not dt.dtype_equivalence(
+        return_type, dt.wrap(python_type)
+    ):
+        return None
+    return expr.DeclareTypeExpression(return_type, expression)
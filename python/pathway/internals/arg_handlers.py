@@ -150,6 +150,9 @@ def join_kwargs_handler(*, allow_how: bool, allow_id: bool):
         if "defaults" in kwargs:
             processed_kwargs["defaults"] = kwargs.pop("defaults")
 
+        if "indicator" in kwargs:
+            processed_kwargs["indicator"] = kwargs.pop("indicator")
+
         if "left_instance" in kwargs and "right_instance" in kwargs:
             processed_kwargs["left_instance"] = kwargs.pop("left_instance")
             processed_kwargs["right_instance"] = kwargs.pop("right_instance")
@@ -3,11 +3,14 @@
 from __future__ import annotations
 
 import asyncio
+import datetime
 import functools
 import inspect
 import os
 import random
+import time
 from abc import ABC, abstractmethod
+from collections import OrderedDict
 from collections.abc import Callable
 from pathlib import Path
 from typing import ClassVar
@@ -262,6 +265,57 @@ class DefaultCache(DiskCache):
         return super()._get_cache(func)
 
 
+class InMemoryCache(CacheStrategy):
+    """
+    Caching strategy keeping results in memory.
+    Results are not shared between runs of the program.
+
+    Args:
+        ttl: Time after which a cached result expires and the function is called
+            again. Defaults to None, indicating that results never expire.
+        max_size: Maximum number of cached results. When it is exceeded, the least
+            recently used result is dropped. Defaults to None, indicating no limit.
+    """
+
+    _ttl: datetime.timedelta | None
+    _max_size: int | None
+    _entries: OrderedDict[str, tuple[float | None, object]]
+
+    def __init__(
+        self,
+        ttl: datetime.timedelta | None = None,
+        max_size: int | None = None,
+    ) -> None:
+        super().__init__()
+        if ttl is not None and ttl <= datetime.timedelta(0):
+            raise ValueError("ttl has to be positive.")
+        if max_size is not None and max_size <= 0:
+            raise ValueError("max_size has to be positive.")
+        self._ttl = ttl
+        self._max_size = max_size
+        self._entries = OrderedDict()
+
+    async def invoke(self, func: Callable, /, *args, **kwargs):
+        key = str((args, kwargs))
+        entry = self._entries.get(key)
+        if entry is not None:
+            expires_at, result = entry
+            if expires_at is None or time.monotonic() < expires_at:
+                self._entries.move_to_end(key)
+                return result
+            del self._entries[key]
+
+        result = await func(*args, **kwargs)
+        expires_at = None
+        if self._ttl is not None:
+            expires_at = time.monotonic() + self._ttl.total_seconds()
+        self._entries[key] = (expires_at, result)
+        self._entries.move_to_end(key)
+        if self._max_size is not None and len(self._entries) > self._max_size:
+            self._entries.popitem(last=False)
+        return result
+
+
 __all__ = [
     "with_capacity",
     "with_retry_strategy",
@@ -274,4 +328,5 @@ __all__ = [
     "FixedDelayRetryStrategy",
     "CacheStrategy",
     "DefaultCache",
+    "InMemoryCache",
 ]
@@ -8,7 +8,7 @@ from dataclasses import dataclass
 from functools import cached_property
 from itertools import chain
 from types import EllipsisType
-from typing import TYPE_CHECKING, ClassVar
+from typing import TYPE_CHECKING, Any, ClassVar
 
 import pathway.internals as pw
 from pathway.internals import column_properties as cp, dtype as dt, trace
@@ -18,6 +18,7 @@ from pathway.internals.parse_graph import G
 from pathway.internals.universe import Universe
 
 if TYPE_CHECKING:
+    from pathway.internals import api
     from pathway.internals.expression import InternalColRef
     from pathway.internals.operator import OutputHandle
     from pathway.internals.table import Table
@@ -482,6 +483,48 @@ class ForgetImmediatelyContext(Context):
         return self.orig_id_column.universe.subset()
 
 
+@dataclass(eq=False, frozen=True)
+class ExpireAfterContext(Context):
+    """Context of `table._expire_after() operation."""
+
+    orig_id_column: IdColumn
+    duration: int
+
+    def column_dependencies_external(self) -> Iterable[Column]:
+        return [self.orig_id_column]
+
+    def universe_dependencies(self) -> Iterable[Universe]:
+        return [self.orig_id_column.universe]
+
+    @cached_property
+    def universe(self) -> Universe:
+        return self.orig_id_column.universe.subset()
+
+
+@dataclass(eq=False, frozen=True)
+class DeduplicateWithinContext(Context):
+    """Context of `table._deduplicate_within() operation."""
+
+    orig_id_column: IdColumn
+    fingerprint_column: ColumnWithExpression
+    time_column: ColumnWithExpression
+    expiration_column: ColumnWithExpression
+
+    def column_dependencies_internal(self) -> Iterable[Column]:
+        return [self.fingerprint_column, self.time_column, self.expiration_column]
+
+    def column_dependencies_external(self) -> Iterable[Column]:
+        return [self.orig_id_column]
+
+    def universe_dependencies(self) -> Iterable[Universe]:
+        return [self.orig_id_column.universe]
+
+    @cached_property
+    def universe(self) -> Universe:
+        # deletions are ignored, so kept rows can outlive the rows of the input
+        return Universe()
+
+
 @dataclass(eq=False, frozen=True)
 class FilterOutForgettingContext(Context):
     """Context of `table._filter_out_results_of_forgetting() operation."""
@@ -504,11 +547,38 @@ class FreezeContext(TimeColumnContext):
     """Context of `table._freeze() operation."""
 
 
+@dataclass(eq=False, frozen=True)
+class FreezeLateContext(TimeColumnContext):
+    """Context of `table._freeze_late() operation."""
+
+
 @dataclass(eq=False, frozen=True)
 class BufferContext(TimeColumnContext):
     """Context of `table._buffer() operation."""
 
 
+@dataclass(eq=False, frozen=True)
+class ReshardContext(Context):
+    """Context of `table.reshard_by() operation."""
+
+    orig_id_column: IdColumn
+    reshard_columns: tuple[ColumnWithExpression, ...]
+    parallelism: int | None
+
+    def column_dependencies_internal(self) -> Iterable[Column]:
+        return self.reshard_columns
+
+    def column_dependencies_external(self) -> Iterable[Column]:
+        return [self.orig_id_column]
+
+    def universe_dependencies(self) -> Iterable[Universe]:
+        return [self.orig_id_column.universe]
+
+    @cached_property
+    def universe(self) -> Universe:
+        return self.orig_id_column.universe.subset()
+
+
 @dataclass(eq=False, frozen=True)
 class ReindexContext(Context):
     """Context of `table.with_id() operation."""
@@ -816,6 +886,34 @@ class FlattenContext(Context):
         )
 
 
+@dataclass(eq=False, frozen=True)
+class ChangelogContext(
+    Context, column_properties_evaluator=cp.AppendOnlyPropsEvaluator
+):
+    """Context of `table.changelog() operation."""
+
+    orig_universe: Universe
+
+    def universe_dependencies(self) -> Iterable[Universe]:
+        return [self.orig_universe]
+
+    @cached_property
+    def universe(self) -> Universe:
+        return Universe()
+
+    @cached_property
+    def time_column(self) -> Column:
+        return MaterializedColumn(
+            self.universe, cp.ColumnProperties(dtype=dt.INT, append_only=True)
+        )
+
+    @cached_property
+    def diff_column(self) -> Column:
+        return MaterializedColumn(
+            self.universe, cp.ColumnProperties(dtype=dt.INT, append_only=True)
+        )
+
+
 @dataclass(eq=False, frozen=True)
 class SortingContext(Context):
     """Context of table.sort() operation."""
@@ -844,3 +942,120 @@ class SortingContext(Context):
         return MaterializedColumn(
             self.universe, cp.ColumnProperties(dtype=dt.Optional(dt.POINTER))
         )
+
+
+@dataclass(eq=False, frozen=True)
+class RankContext(Context):
+    """Context of table._rank() operation."""
+
+    key_column: ColumnWithExpression
+    instance_column: ColumnWithExpression
+    descending: bool
+
+    def column_dependencies_internal(self) -> Iterable[Column]:
+        return [self.key_column, self.instance_column]
+
+    def universe_dependencies(self) -> Iterable[Universe]:
+        return [self.universe]
+
+    @cached_property
+    def universe(self) -> Universe:
+        return self.key_column.universe
+
+    @cached_property
+    def row_number_column(self) -> Column:
+        return MaterializedColumn(self.universe, cp.ColumnProperties(dtype=dt.INT))
+
+    @cached_property
+    def rank_column(self) -> Column:
+        return MaterializedColumn(self.universe, cp.ColumnProperties(dtype=dt.INT))
+
+    @cached_property
+    def dense_rank_column(self) -> Column:
+        return MaterializedColumn(self.universe, cp.ColumnProperties(dtype=dt.INT))
+
+
+@dataclass(eq=False, frozen=True)
+class CumulativeContext(Context):
+    """Context of table._cumulative() operation."""
+
+    key_column: ColumnWithExpression
+    instance_column: ColumnWithExpression
+    reducers: tuple[tuple[api.Reducer, ColumnWithExpression | None, dt.DType], ...]
+
+    def column_dependencies_internal(self) -> Iterable[Column]:
+        arguments = [column for _, column, _ in self.reducers if column is not None]
+        return [self.key_column, self.instance_column, *arguments]
+
+    def universe_dependencies(self) -> Iterable[Universe]:
+        return [self.universe]
+
+    @cached_property
+    def universe(self) -> Universe:
+        return self.key_column.universe
+
+    @cached_property
+    def aggregate_columns(self) -> list[Column]:
+        return [
+            MaterializedColumn(self.universe, cp.ColumnProperties(dtype=dtype))
+            for _, _, dtype in self.reducers
+        ]
+
+
+@dataclass(eq=False, frozen=True)
+class TopNContext(Context):
+    """Context of table._top_n() operation."""
+
+    key_column: ColumnWithExpression
+    instance_column: ColumnWithExpression
+    n: int
+    descending: bool
+
+    def column_dependencies_internal(self) -> Iterable[Column]:
+        return [self.key_column, self.instance_column]
+
+    def universe_dependencies(self) -> Iterable[Universe]:
+        return [self.key_column.universe]
+
+    @cached_property
+    def universe(self) -> Universe:
+        return self.key_column.universe.subset()
+
+    @cached_property
+    def position_column(self) -> Column:
+        return MaterializedColumn(self.universe, cp.ColumnProperties(dtype=dt.INT))
+
+
+@dataclass(eq=False, frozen=True)
+class SessionWindowContext(Context):
+    """Context of session windows with a fixed maximal gap."""
+
+    key_column: ColumnWithExpression
+    instance_column: ColumnWithExpression
+    max_gap: Any
+
+    def column_dependencies_internal(self) -> Iterable[Column]:
+        return [self.key_column, self.instance_column]
+
+    def universe_dependencies(self) -> Iterable[Universe]:
+        return [self.universe]
+
+    @cached_property
+    def universe(self) -> Universe:
+        return self.key_column.universe
+
+    @cached_property
+    def window_column(self) -> Column:
+        return MaterializedColumn(self.universe, cp.ColumnProperties(dtype=dt.POINTER))
+
+    @cached_property
+    def window_start_column(self) -> Column:
+        return MaterializedColumn(
+            self.universe, cp.ColumnProperties(dtype=self.key_column.dtype)
+        )
+
+    @cached_property
+    def window_end_column(self) -> Column:
+        return MaterializedColumn(
+            self.universe, cp.ColumnProperties(dtype=self.key_column.dtype)
+        )
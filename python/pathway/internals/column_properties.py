@@ -34,6 +34,11 @@ class DefaultPropsEvaluator(ColumnPropertiesEvaluator):
         return False
 
 
+class AppendOnlyPropsEvaluator(ColumnPropertiesEvaluator):
+    def _append_only(self, column: clmn.ColumnWithContext) -> bool:
+        return True
+
+
 class PreserveDependenciesPropsEvaluator(ColumnPropertiesEvaluator):
     def _append_only(self, column: clmn.ColumnWithContext):
         return self._has_property(column, "append_only", True)
@@ -8,6 +8,7 @@ from collections.abc import Callable
 from functools import wraps
 from typing import (
     Any,
+    Literal,
     Mapping,
     ParamSpec,
     TypeVar,
@@ -33,11 +34,17 @@ from pathway.internals.trace import trace_user_frame
 T = TypeVar("T")
 P = ParamSpec("P")
 
+# The maximum number of calls of an async function passed to ``apply`` or
+# ``apply_with_type`` awaited at once. Further rows wait until some of the calls
+# complete, so that e.g. an API isn't flooded with requests.
+ASYNC_APPLY_CAPACITY = 64
+
 
 @check_arg_types
 def iterate(
     func,
     iteration_limit: int | None = None,
+    on_iteration_limit: Literal["stop", "raise"] = "stop",
     **kwargs: table.Table | op.iterate_universe,
 ):
     """Iterate function until fixed point.
@@ -47,6 +54,13 @@ def iterate(
     either a single Table, a tuple of Tables, or a dict of Tables, respectively.
     Initial arguments to function are passed through kwargs.
 
+    The number of iterations can be bounded with ``iteration_limit``. If the fixed
+    point is not reached within the limit, the result of the last iteration is
+    returned when ``on_iteration_limit`` is ``"stop"``, and the computation fails
+    with an error when it is ``"raise"``. The number of updates produced by every
+    iteration and the number of iterations needed to reach the fixed point are
+    logged at the debug level.
+
     Example:
 
     >>> import pathway as pw
@@ -83,9 +97,18 @@ def iterate(
     """
     if iteration_limit is not None and iteration_limit < 1:
         raise ValueError("wrong iteration limit")
+    if on_iteration_limit == "raise" and (
+        iteration_limit is None or iteration_limit < 2
+    ):
+        raise ValueError(
+            "on_iteration_limit='raise' requires an iteration limit of at least 2"
+        )
     fn_spec = function_spec(func)
     return G.add_iterate(
-        fn_spec, lambda node: node(**kwargs), iteration_limit=iteration_limit
+        fn_spec,
+        lambda node: node(**kwargs),
+        iteration_limit=iteration_limit,
+        fail_on_iteration_limit=on_iteration_limit == "raise",
     )
 
 
@@ -98,6 +121,9 @@ def apply(
 ) -> expr.ColumnExpression:
     """Applies function to column expressions, column-wise.
     Output column type deduced from type-annotations of a function.
+    An async function is awaited by the engine, like with ``apply_async``, with at most
+    64 calls in flight at once. Use ``pw.udf_async`` with ``capacity`` to set another
+    limit.
 
     Example:
 
@@ -118,6 +144,10 @@ def apply(
     Bobdog
     Bobdog
     """
+    if inspect.iscoroutinefunction(fun):
+        return expr.AsyncApplyExpression(
+            fun, None, *args, max_in_flight=ASYNC_APPLY_CAPACITY, **kwargs
+        )
     return expr.ApplyExpression(fun, None, *args, **kwargs)
 
 
@@ -183,6 +213,9 @@ def apply_with_type(
 ) -> expr.ColumnExpression:
     """Applies function to column expressions, column-wise.
     Output column type is provided explicitly.
+    An async function is awaited by the engine, like with ``apply_async``, with at most
+    64 calls in flight at once. Use ``pw.udf_async`` with ``capacity`` to set another
+    limit.
 
     Example:
 
@@ -201,6 +234,10 @@ def apply_with_type(
     Bobdog
     Bobdog
     """
+    if inspect.iscoroutinefunction(fun):
+        return expr.AsyncApplyExpression(
+            fun, ret_type, *args, max_in_flight=ASYNC_APPLY_CAPACITY, **kwargs
+        )
     return expr.ApplyExpression(fun, ret_type, *args, **kwargs)
 
 
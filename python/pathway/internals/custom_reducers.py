@@ -3,14 +3,15 @@
 import pickle
 from abc import ABC, abstractmethod
 from collections import Counter
-from typing import ParamSpec, Protocol, TypeVar
+from collections.abc import Callable
+from typing import Any, ParamSpec, Protocol, TypeVar
 
 from typing_extensions import Self
 
 from pathway.internals import api, expression as expr
 from pathway.internals.column import ColumnExpression
 from pathway.internals.common import apply_with_type
-from pathway.internals.reducers import StatefulManyReducer
+from pathway.internals.reducers import RetractableReducer, StatefulManyReducer
 from pathway.internals.shadows.inspect import signature
 
 P = ParamSpec("P")
@@ -59,6 +60,53 @@ def stateful_single(combine_single: CombineSingle[S, ...]) -> ReducerProtocol:
     return stateful_many(wrapper)
 
 
+def retractable_reducer(
+    *,
+    init: Callable[[], Any],
+    add: Callable[..., Any],
+    retract: Callable[..., Any],
+    extract: Callable[[Any], api.Value],
+) -> ReducerProtocol:
+    """Creates a reducer from Python callables operating on the state of a group.
+    The state of a new group is created by ``init``. Then ``add`` is called with the state
+    and the values of each row inserted into the group, and ``retract`` with the state
+    and the values of each row removed from it. Both return the new state, which can be
+    modified in place. The value of the group is computed from its state by ``extract``.
+
+    The state is never recomputed from all the rows of the group, so ``retract`` has to
+    revert ``add`` exactly. The callables are called for all the groups changed at
+    a time at once, without releasing the GIL in between.
+
+    >>> import pathway as pw
+    >>> from collections import Counter
+    >>> count_distinct = pw.reducers.retractable_reducer(
+    ...     init=Counter,
+    ...     add=lambda counts, value: counts + Counter([value]),
+    ...     retract=lambda counts, value: counts - Counter([value]),
+    ...     extract=len,
+    ... )
+    >>> t1 = pw.debug.table_from_markdown('''
+    ... owner | pet
+    ... Alice | dog
+    ... Bob   | cat
+    ... Alice | cat
+    ... Alice | dog
+    ... ''')
+    >>> t2 = t1.groupby(t1.owner).reduce(t1.owner, pets=count_distinct(t1.pet))
+    >>> pw.debug.compute_and_print(t2, include_id=False)
+    owner | pets
+    Alice | 2
+    Bob   | 1
+    """
+
+    def wrapper(*args: expr.ColumnExpression | api.Value) -> expr.ColumnExpression:
+        return expr.ReducerExpression(
+            RetractableReducer(init, add, retract, extract), *args
+        )
+
+    return wrapper
+
+
 class BaseCustomAccumulator(ABC):
     """Utility class for defining custom accumulators, used for custom reducers.
     Custom accumulators should inherit from this class, and should implement ``from_row``,
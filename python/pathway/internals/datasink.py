@@ -26,3 +26,9 @@ class CallbackDataSink(DataSink):
     on_time_end: Callable[[int], None]
     on_end: Callable[[], None]
     skip_persisted_batch: bool
+    on_commit: Callable[[int], None] | None = None
+
+
+@dataclass(frozen=True)
+class ServedTableDataSink(DataSink):
+    name: str
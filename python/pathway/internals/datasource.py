@@ -31,6 +31,7 @@ class DataSource(ABC):
                 api.ColumnProperties(
                     dtype=column.dtype.map_to_engine(),
                     append_only=column.append_only,
+                    nullable=column.dtype.is_nullable(),
                 )
             )
 
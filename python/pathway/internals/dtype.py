@@ -29,6 +29,9 @@ class DType(ABC):
     def map_to_engine(self) -> api.PathwayType:
         return self.to_engine() or api.PathwayType.ANY
 
+    def is_nullable(self) -> bool:
+        return isinstance(self, (Optional, _NoneDType, _AnyDType))
+
     @abstractmethod
     def is_value_compatible(self, arg) -> bool:
         ...
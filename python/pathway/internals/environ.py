@@ -22,6 +22,19 @@ runtime_typechecking = os.environ.get(
     "yes",
 )
 
+expression_fusion = os.environ.get("PATHWAY_EXPRESSION_FUSION", "true").lower() in (
+    "1",
+    "true",
+    "yes",
+)
+
+native_apply = os.environ.get("PATHWAY_NATIVE_APPLY", "true").lower() in (
+    "1",
+    "true",
+    "yes",
+)
+
+
 
 def get_replay_config():
     if replay_storage := os.environ.get("PATHWAY_REPLAY_STORAGE"):
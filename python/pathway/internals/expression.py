@@ -798,11 +798,14 @@ class NumbaApplyExpression(ApplyExpression):
 
 
 class AsyncApplyExpression(ApplyExpression):
+    _max_in_flight: int | None
+
     def __init__(
         self,
         fun: Callable,
         return_type: Any,
         *args: ColumnExpression | Value,
+        max_in_flight: int | None = None,
         **kwargs: ColumnExpression | Value,
     ):
         super().__init__(
@@ -811,6 +814,22 @@ class AsyncApplyExpression(ApplyExpression):
             *args,
             **kwargs,
         )
+        self._max_in_flight = max_in_flight
+
+
+class BatchApplyExpression(ApplyExpression):
+    _max_batch_size: int | None
+
+    def __init__(
+        self,
+        fun: Callable,
+        return_type: Any,
+        *args: ColumnExpression | Value,
+        max_batch_size: int | None = None,
+        **kwargs: ColumnExpression | Value,
+    ):
+        super().__init__(fun, dt.wrap(return_type), *args, **kwargs)
+        self._max_batch_size = max_batch_size
 
 
 class CastExpression(ColumnExpression):
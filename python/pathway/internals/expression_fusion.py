@@ -0,0 +1,96 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pathway.internals.column as clmn
+from pathway.internals import expression as expr
+from pathway.internals.expression_visitor import IdentityTransform
+from pathway.internals.universe import Universe
+
+# Expressions that are cheap to evaluate and don't depend on anything but their
+# arguments, so evaluating them again in a later operator doesn't change the result.
+_FUSIBLE_EXPRESSIONS = (
+    expr.ColumnReference,
+    expr.ColumnConstExpression,
+    expr.ColumnUnaryOpExpression,
+    expr.ColumnBinaryOpExpression,
+    expr.CastExpression,
+    expr.CoalesceExpression,
+    expr.IfElseExpression,
+    expr.IsNoneExpression,
+    expr.IsNotNoneExpression,
+    expr.MakeTupleExpression,
+    expr.GetExpression,
+    expr.MethodCallExpression,
+)
+
+# Inlining a column into every place referencing it can blow up the size of chained
+# expressions, so only small ones are inlined.
+MAX_FUSED_EXPRESSION_SIZE = 32
+
+
+def _size(expression: expr.ColumnExpression) -> int | None:
+    """Returns the number of nodes of a fusible expression and None otherwise."""
+    if type(expression) not in _FUSIBLE_EXPRESSIONS:
+        return None
+    size = 1
+    for dependency in expression._deps:
+        dependency_size = _size(dependency)
+        if dependency_size is None:
+            return None
+        size += dependency_size
+    return size
+
+
+def _narrows_types(expression: expr.ColumnExpression) -> bool:
+    """Checks if optional types of column references are narrowed in the expression,
+    e.g. in ``pw.if_else(t.a.is_not_none(), t.a + 1, 0)``. Narrowing works only on
+    column references, so columns must not be replaced there."""
+    if isinstance(expression, expr.RequireExpression):
+        return True
+    if isinstance(expression, expr.IfElseExpression) and isinstance(
+        expression._if, (expr.IsNoneExpression, expr.IsNotNoneExpression)
+    ):
+        return True
+    return any(_narrows_types(dependency) for dependency in expression._deps)
+
+
+class ExpressionFusion(IdentityTransform):
+    """Replaces references to columns computed row-wise in the same universe with
+    the expressions defining them.
+
+    A ``select`` on a table produced by another ``select`` (or ``with_columns``,
+    ``filter`` condition, ...) references columns computed by the previous operator.
+    When such a column is defined by a small built-in expression, its definition is
+    substituted, so the whole chain is evaluated in a single pass and the previous
+    operator can be skipped if nothing else needs its columns.
+    """
+
+    universe: Universe
+
+    def __init__(self, universe: Universe) -> None:
+        self.universe = universe
+        super().__init__()
+
+    def eval_column_val(
+        self, expression: expr.ColumnReference, **kwargs
+    ) -> expr.ColumnExpression:
+        column = expression._column
+        if (
+            isinstance(column, clmn.ColumnWithExpression)
+            and type(column.context) is clmn.RowwiseContext
+            and column.universe == self.universe
+            and (size := _size(column.expression)) is not None
+            and size <= MAX_FUSED_EXPRESSION_SIZE
+        ):
+            # the definition was already fused when the column was created
+            return IdentityTransform().eval_expression(column.expression)
+        return super().eval_column_val(expression, **kwargs)
+
+
+def fuse_expression(
+    expression: expr.ColumnExpression, universe: Universe
+) -> expr.ColumnExpression:
+    if isinstance(expression, expr.ColumnReference) or _narrows_types(expression):
+        return expression
+    return ExpressionFusion(universe).eval_expression(expression)
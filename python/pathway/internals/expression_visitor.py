@@ -32,6 +32,7 @@ class ExpressionVisitor(ABC):
             expr.IfElseExpression: self.eval_ifelse,
             expr.NumbaApplyExpression: self.eval_numbaapply,
             expr.AsyncApplyExpression: self.eval_async_apply,
+            expr.BatchApplyExpression: self.eval_batch_apply,
             expr.MakeTupleExpression: self.eval_make_tuple,
             expr.GetExpression: self.eval_get,
             expr.MethodCallExpression: self.eval_method_call,
@@ -79,6 +80,10 @@ class ExpressionVisitor(ABC):
     def eval_async_apply(self, expression: expr.AsyncApplyExpression):
         ...
 
+    @abstractmethod
+    def eval_batch_apply(self, expression: expr.BatchApplyExpression):
+        ...
+
     @abstractmethod
     def eval_pointer(self, expression: expr.PointerExpression):
         ...
@@ -221,7 +226,27 @@ class IdentityTransform(ExpressionVisitor):
             for name, arg in expression._kwargs.items()
         }
         return expr.AsyncApplyExpression(
-            expression._fun, expression._return_type, *expr_args, **expr_kwargs
+            expression._fun,
+            expression._return_type,
+            *expr_args,
+            max_in_flight=expression._max_in_flight,
+            **expr_kwargs,
+        )
+
+    def eval_batch_apply(
+        self, expression: expr.BatchApplyExpression, **kwargs
+    ) -> expr.BatchApplyExpression:
+        expr_args = [self.eval_expression(arg, **kwargs) for arg in expression._args]
+        expr_kwargs = {
+            name: self.eval_expression(arg, **kwargs)
+            for name, arg in expression._kwargs.items()
+        }
+        return expr.BatchApplyExpression(
+            expression._fun,
+            expression._return_type,
+            *expr_args,
+            max_batch_size=expression._max_batch_size,
+            **expr_kwargs,
         )
 
     def eval_pointer(
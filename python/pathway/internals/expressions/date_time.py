@@ -9,6 +9,16 @@ import pathway.internals.expression as expr
 from pathway.internals import api, dtype as dt
 
 
+_CALENDAR_UNITS = ("hour", "day", "month", "quarter", "year")
+
+
+def _check_calendar_unit(unit: str) -> None:
+    if unit not in _CALENDAR_UNITS:
+        raise ValueError(
+            f"calendar unit has to be one of {', '.join(_CALENDAR_UNITS)} but is {unit}."
+        )
+
+
 def _str_as_duration(freq: str) -> pd.Timedelta:
     duration = pd.tseries.frequencies.to_offset(freq)
     if duration is None:
@@ -494,7 +504,11 @@ class DateTimeNamespace:
                 unit,
             )
 
-    def strftime(self, fmt: expr.ColumnExpression | str) -> expr.ColumnExpression:
+    def strftime(
+        self,
+        fmt: expr.ColumnExpression | str,
+        timezone: expr.ColumnExpression | str | None = None,
+    ) -> expr.ColumnExpression:
         """Converts a DateTime to a string.
 
         Args:
@@ -502,6 +516,9 @@ class DateTimeNamespace:
             `chrono <https://docs.rs/chrono/latest/chrono/format/strftime/index.html>`_ \
             library. In most cases they are identical to standard python specifiers in \
             `strftime <https://docs.python.org/3/library/datetime.html#strftime-strptime-behavior>`_ .
+            timezone: The timezone in which a DateTimeUtc is rendered. \
+            Fields and offset specifiers (like ``%z``) then refer to this timezone. \
+            Only allowed for DateTimeUtc. If not set, UTC is used.
 
         Returns:
             str
@@ -532,8 +549,38 @@ class DateTimeNamespace:
         15.05.2023 | May 15, 2023      | 14:13:23 | 02:13:23 PM
         25.03.2023 | March 25, 2023    | 10:13:00 | 10:13:00 AM
         26.03.2023 | March 26, 2023    | 12:13:00 | 12:13:00 PM
+        >>>
+        >>> table_utc = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |               t1
+        ...    1 | 2023-03-25T10:13:00+00:00
+        ...    2 | 2023-03-26T12:13:00+00:00
+        ... '''
+        ... ).select(t1=pw.this.t1.dt.strptime(fmt="%Y-%m-%dT%H:%M:%S%z"))
+        >>> table_formatted = table_utc.select(
+        ...     warsaw=pw.this.t1.dt.strftime("%Y-%m-%dT%H:%M:%S%z", "Europe/Warsaw"),
+        ... )
+        >>> pw.debug.compute_and_print(table_formatted, include_id=False)
+        warsaw
+        2023-03-25T11:13:00+0100
+        2023-03-26T14:13:00+0200
         """
 
+        if timezone is not None:
+            return expr.MethodCallExpression(
+                (
+                    (
+                        (dt.DATE_TIME_UTC, dt.STR, dt.STR),
+                        dt.STR,
+                        api.Expression.date_time_utc_strftime_in_timezone,
+                    ),
+                ),
+                "dt.strftime",
+                self._expression,
+                fmt,
+                timezone,
+            )
+
         return expr.MethodCallExpression(
             (
                 (
@@ -561,7 +608,8 @@ class DateTimeNamespace:
         a %z specifier is used, timezone-aware DateTime is created.
         Then the timezone is converted to a server timezone (see examples).
         If the string contains no timezone, a naive (not aware of timezone) DateTime
-        is created.
+        is created. Leap seconds (e.g. ``23:59:60.5``) are accepted and clamped to
+        the last nanosecond of the preceding second.
 
         Args:
             fmt: Format string. We use the specifiers of \
@@ -657,6 +705,87 @@ class DateTimeNamespace:
             fmt,
         )
 
+    def parse_rfc3339(self) -> expr.ColumnExpression:
+        """Converts a string in the RFC 3339 format (e.g. ``2023-03-25T10:13:00+01:00``)
+        to a DateTimeUtc. Unlike ``strptime``, it does not need a format string and
+        accepts all offset notations allowed by the standard (e.g. ``Z``).
+
+        Returns:
+            DateTimeUtc
+
+        Example:
+
+        >>> import pathway as pw
+        >>> table = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |               t1
+        ...    1 | 1970-02-03T10:13:00-02:00
+        ...    2 | 2023-03-25T10:13:00.000000012Z
+        ...    3 | 2023-05-15T14:13:23+00:30
+        ... '''
+        ... )
+        >>> table_with_datetime = table.select(t1=table.t1.dt.parse_rfc3339())
+        >>> pw.debug.compute_and_print(table_with_datetime, include_id=False)
+        t1
+        1970-02-03 12:13:00+00:00
+        2023-03-25 10:13:00.000000012+00:00
+        2023-05-15 13:43:23+00:00
+        """
+
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.STR,),
+                    dt.DATE_TIME_UTC,
+                    api.Expression.date_time_utc_parse_rfc3339,
+                ),
+            ),
+            "dt.parse_rfc3339",
+            self._expression,
+        )
+
+    def parse_rfc2822(self) -> expr.ColumnExpression:
+        """Converts a string in the RFC 2822 format
+        (e.g. ``Sat, 25 Mar 2023 10:13:00 +0100``) to a DateTimeUtc. Unlike
+        ``strptime``, it does not need a format string and accepts optional parts
+        of the format, like the day of the week or seconds.
+
+        Returns:
+            DateTimeUtc
+
+        Example:
+
+        >>> import pandas as pd
+        >>> import pathway as pw
+        >>> t = pw.debug.table_from_pandas(
+        ...     pd.DataFrame(
+        ...         {
+        ...             "t1": [
+        ...                 "Tue, 3 Feb 1970 10:13:00 -0200",
+        ...                 "Sat, 25 Mar 2023 10:13:00 GMT",
+        ...             ]
+        ...         }
+        ...     )
+        ... )
+        >>> table_with_datetime = t.select(t1=t.t1.dt.parse_rfc2822())
+        >>> pw.debug.compute_and_print(table_with_datetime, include_id=False)
+        t1
+        1970-02-03 12:13:00+00:00
+        2023-03-25 10:13:00+00:00
+        """
+
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.STR,),
+                    dt.DATE_TIME_UTC,
+                    api.Expression.date_time_utc_parse_rfc2822,
+                ),
+            ),
+            "dt.parse_rfc2822",
+            self._expression,
+        )
+
     def to_utc(
         self, from_timezone: expr.ColumnExpression | str
     ) -> expr.ColumnExpression:
@@ -837,6 +966,59 @@ class DateTimeNamespace:
             timezone,
         )
 
+    def offset_seconds(
+        self, timezone: expr.ColumnExpression | str
+    ) -> expr.ColumnExpression:
+        """Returns the offset of a given timezone from UTC (in seconds) at the moment
+        described by a DateTimeUtc. The offset includes daylight saving time if it is
+        in effect. To get calendar fields (like the hour or the day) in a given timezone,
+        convert the DateTimeUtc with ``to_naive_in_timezone`` first.
+
+        Args:
+            timezone: The timezone to compute the offset for. A list \
+            of all timezone names can be found \
+            `here <https://en.wikipedia.org/wiki/List_of_tz_database_time_zones>`_.
+
+        Returns:
+            int
+
+        Example:
+
+        >>> import pathway as pw
+        >>> t1 = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |           date_utc
+        ...    1 | 2023-03-26T00:59:59+00:00
+        ...    2 | 2023-03-26T01:00:00+00:00
+        ...    3 | 2023-07-01T12:00:00+00:00
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S%z"
+        >>> t2 = t1.select(date_utc=pw.this.date_utc.dt.strptime(fmt=fmt))
+        >>> t3 = t2.with_columns(
+        ...     warsaw=pw.this.date_utc.dt.offset_seconds("Europe/Warsaw"),
+        ...     new_york=pw.this.date_utc.dt.offset_seconds("America/New_York"),
+        ... )
+        >>> pw.debug.compute_and_print(t3, include_id=False)
+        date_utc                  | warsaw | new_york
+        2023-03-26 00:59:59+00:00 | 3600   | -14400
+        2023-03-26 01:00:00+00:00 | 7200   | -14400
+        2023-07-01 12:00:00+00:00 | 7200   | -14400
+        """
+
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_UTC, dt.STR),
+                    dt.INT,
+                    api.Expression.date_time_utc_offset_seconds,
+                ),
+            ),
+            "dt.offset_seconds",
+            self._expression,
+            timezone,
+        )
+
     def add_duration_in_timezone(
         self,
         duration: expr.ColumnExpression | pd.Timedelta,
@@ -1116,6 +1298,261 @@ class DateTimeNamespace:
             args_used_for_repr=[self._expression, duration],
         )
 
+    def floor_calendar(
+        self, unit: str, timezone: expr.ColumnExpression | str | None = None
+    ) -> expr.ColumnExpression:
+        """Truncates DateTime to the beginning of a calendar unit (hour, day, month,
+        quarter or year). Unlike ``floor``, the length of the bucket is not a fixed
+        duration. DateTimeUtc values are truncated in the given timezone (UTC if not
+        set), so a day bucket containing a DST change lasts 23 or 25 hours.
+
+        Args:
+            unit: calendar unit. It has to be one of 'hour', 'day', 'month', \
+'quarter', 'year'.
+            timezone: The timezone in which a DateTimeUtc is truncated. \
+            Only allowed for DateTimeUtc.
+
+        Returns:
+            DateTimeNaive or DateTimeUtc depending on the type of an object \
+                the method was called on
+
+        Example:
+
+        >>> import pathway as pw
+        >>> t1 = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |         date
+        ...    1 | 2023-02-14T10:00:00
+        ...    2 | 2023-05-31T23:59:59
+        ...    3 | 2023-11-15T00:00:00
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> t2 = t1.select(date=pw.this.date.dt.strptime(fmt=fmt))
+        >>> res = t2.with_columns(
+        ...     month=pw.this.date.dt.floor_calendar("month"),
+        ...     quarter=pw.this.date.dt.floor_calendar("quarter"),
+        ...     year=pw.this.date.dt.floor_calendar("year"),
+        ... )
+        >>> pw.debug.compute_and_print(res, include_id=False)
+        date                | month               | quarter             | year
+        2023-02-14 10:00:00 | 2023-02-01 00:00:00 | 2023-01-01 00:00:00 | 2023-01-01 00:00:00
+        2023-05-31 23:59:59 | 2023-05-01 00:00:00 | 2023-04-01 00:00:00 | 2023-01-01 00:00:00
+        2023-11-15 00:00:00 | 2023-11-01 00:00:00 | 2023-10-01 00:00:00 | 2023-01-01 00:00:00
+        >>>
+        >>> t3 = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |         date
+        ...    1 | 2023-03-26T12:00:00+00:00
+        ... '''
+        ... ).select(date=pw.this.date.dt.strptime(fmt="%Y-%m-%dT%H:%M:%S%z"))
+        >>> res = t3.select(
+        ...     day=pw.this.date.dt.floor_calendar("day", "Europe/Warsaw"),
+        ...     next_day=pw.this.date.dt.floor_calendar(
+        ...         "day", "Europe/Warsaw"
+        ...     ).dt.add_calendar_units("day", 1, "Europe/Warsaw"),
+        ... )
+        >>> pw.debug.compute_and_print(res, include_id=False)
+        day                       | next_day
+        2023-03-25 23:00:00+00:00 | 2023-03-26 22:00:00+00:00
+        """
+        _check_calendar_unit(unit)
+        if timezone is not None:
+            return expr.MethodCallExpression(
+                (
+                    (
+                        (dt.DATE_TIME_UTC, dt.STR, dt.STR),
+                        dt.DATE_TIME_UTC,
+                        api.Expression.date_time_utc_floor_to_calendar_unit_in_timezone,
+                    ),
+                ),
+                "dt.floor_calendar",
+                self._expression,
+                unit,
+                timezone,
+            )
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE, dt.STR),
+                    dt.DATE_TIME_NAIVE,
+                    api.Expression.date_time_naive_floor_to_calendar_unit,
+                ),
+                (
+                    (dt.DATE_TIME_UTC, dt.STR),
+                    dt.DATE_TIME_UTC,
+                    api.Expression.date_time_utc_floor_to_calendar_unit,
+                ),
+            ),
+            "dt.floor_calendar",
+            self._expression,
+            unit,
+        )
+
+    def round_calendar(
+        self, unit: str, timezone: expr.ColumnExpression | str | None = None
+    ) -> expr.ColumnExpression:
+        """Rounds DateTime to the nearest beginning of a calendar unit (hour, day,
+        month, quarter or year). Values lying exactly in the middle are rounded up.
+        DateTimeUtc values are rounded in the given timezone (UTC if not set).
+
+        Args:
+            unit: calendar unit. It has to be one of 'hour', 'day', 'month', \
+'quarter', 'year'.
+            timezone: The timezone in which a DateTimeUtc is rounded. \
+            Only allowed for DateTimeUtc.
+
+        Returns:
+            DateTimeNaive or DateTimeUtc depending on the type of an object \
+                the method was called on
+
+        Example:
+
+        >>> import pathway as pw
+        >>> t1 = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |         date
+        ...    1 | 2023-02-14T10:00:00
+        ...    2 | 2023-05-31T23:59:59
+        ...    3 | 2023-11-15T00:00:00
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> t2 = t1.select(date=pw.this.date.dt.strptime(fmt=fmt))
+        >>> res = t2.with_columns(
+        ...     month=pw.this.date.dt.round_calendar("month"),
+        ...     quarter=pw.this.date.dt.round_calendar("quarter"),
+        ...     year=pw.this.date.dt.round_calendar("year"),
+        ... )
+        >>> pw.debug.compute_and_print(res, include_id=False)
+        date                | month               | quarter             | year
+        2023-02-14 10:00:00 | 2023-02-01 00:00:00 | 2023-01-01 00:00:00 | 2023-01-01 00:00:00
+        2023-05-31 23:59:59 | 2023-06-01 00:00:00 | 2023-07-01 00:00:00 | 2023-01-01 00:00:00
+        2023-11-15 00:00:00 | 2023-11-01 00:00:00 | 2023-10-01 00:00:00 | 2024-01-01 00:00:00
+        >>>
+        >>> t3 = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |         date
+        ...    1 | 2023-03-26T10:00:00+00:00
+        ...    2 | 2023-03-26T11:00:00+00:00
+        ... '''
+        ... ).select(date=pw.this.date.dt.strptime(fmt="%Y-%m-%dT%H:%M:%S%z"))
+        >>> res = t3.select(day=pw.this.date.dt.round_calendar("day", "Europe/Warsaw"))
+        >>> pw.debug.compute_and_print(res, include_id=False)
+        day
+        2023-03-25 23:00:00+00:00
+        2023-03-26 22:00:00+00:00
+        """
+        _check_calendar_unit(unit)
+        if timezone is not None:
+            return expr.MethodCallExpression(
+                (
+                    (
+                        (dt.DATE_TIME_UTC, dt.STR, dt.STR),
+                        dt.DATE_TIME_UTC,
+                        api.Expression.date_time_utc_round_to_calendar_unit_in_timezone,
+                    ),
+                ),
+                "dt.round_calendar",
+                self._expression,
+                unit,
+                timezone,
+            )
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE, dt.STR),
+                    dt.DATE_TIME_NAIVE,
+                    api.Expression.date_time_naive_round_to_calendar_unit,
+                ),
+                (
+                    (dt.DATE_TIME_UTC, dt.STR),
+                    dt.DATE_TIME_UTC,
+                    api.Expression.date_time_utc_round_to_calendar_unit,
+                ),
+            ),
+            "dt.round_calendar",
+            self._expression,
+            unit,
+        )
+
+    def add_calendar_units(
+        self,
+        unit: str,
+        count: expr.ColumnExpression | int,
+        timezone: expr.ColumnExpression | str | None = None,
+    ) -> expr.ColumnExpression:
+        """Shifts DateTime by a number of calendar units (hours, days, months, quarters
+        or years). If the resulting day does not exist in the target month, the last
+        day of that month is used.
+
+        Args:
+            unit: calendar unit. It has to be one of 'hour', 'day', 'month', \
+'quarter', 'year'.
+            count: number of units to add. Can be negative.
+            timezone: The timezone in which a DateTimeUtc is shifted. Days and \
+            longer units keep the local time in this timezone. \
+            Only allowed for DateTimeUtc.
+
+        Returns:
+            DateTimeNaive or DateTimeUtc depending on the type of an object \
+                the method was called on
+
+        Example:
+
+        >>> import pathway as pw
+        >>> t1 = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |         date
+        ...    1 | 2023-01-31T12:00:00
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> t2 = t1.select(date=pw.this.date.dt.strptime(fmt=fmt))
+        >>> res = t2.with_columns(
+        ...     next_month=pw.this.date.dt.add_calendar_units("month", 1),
+        ...     next_quarter=pw.this.date.dt.add_calendar_units("quarter", 1),
+        ...     previous_year=pw.this.date.dt.add_calendar_units("year", -1),
+        ... )
+        >>> pw.debug.compute_and_print(res, include_id=False)
+        date                | next_month          | next_quarter        | previous_year
+        2023-01-31 12:00:00 | 2023-02-28 12:00:00 | 2023-04-30 12:00:00 | 2022-01-31 12:00:00
+        """
+        _check_calendar_unit(unit)
+        if timezone is not None:
+            return expr.MethodCallExpression(
+                (
+                    (
+                        (dt.DATE_TIME_UTC, dt.STR, dt.INT, dt.STR),
+                        dt.DATE_TIME_UTC,
+                        api.Expression.date_time_utc_add_calendar_units_in_timezone,
+                    ),
+                ),
+                "dt.add_calendar_units",
+                self._expression,
+                unit,
+                count,
+                timezone,
+            )
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE, dt.STR, dt.INT),
+                    dt.DATE_TIME_NAIVE,
+                    api.Expression.date_time_naive_add_calendar_units,
+                ),
+                (
+                    (dt.DATE_TIME_UTC, dt.STR, dt.INT),
+                    dt.DATE_TIME_UTC,
+                    api.Expression.date_time_utc_add_calendar_units,
+                ),
+            ),
+            "dt.add_calendar_units",
+            self._expression,
+            unit,
+            count,
+        )
+
     def nanoseconds(self) -> expr.ColumnExpression:
         """The total number of nanoseconds in a Duration.
 
@@ -1611,3 +2048,369 @@ class DateTimeNamespace:
             "dt.weekday",
             self._expression,
         )
+
+    def iso_week(self) -> expr.ColumnExpression:
+        """
+        Extracts the ISO 8601 week number (1-53) from a DateTime. Days at the
+        beginning of January can belong to the last week of the previous year,
+        so group by ``iso_year`` and ``iso_week`` together.
+
+        Returns:
+            int
+
+        Example:
+
+        >>> import pathway as pw
+        >>> table = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |               t1
+        ...    1 | 2021-01-01T10:13:00
+        ...    2 | 2023-03-25T10:13:00
+        ...    3 | 2023-05-15T14:13:23
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> table_with_datetime = table.select(t1=pw.this.t1.dt.strptime(fmt=fmt))
+        >>> table_with_week = table_with_datetime.with_columns(week=pw.this.t1.dt.iso_week())
+        >>> pw.debug.compute_and_print(table_with_week, include_id=False)
+        t1                  | week
+        2021-01-01 10:13:00 | 53
+        2023-03-25 10:13:00 | 12
+        2023-05-15 14:13:23 | 20
+        """
+
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE,),
+                    dt.INT,
+                    api.Expression.date_time_naive_iso_week,
+                ),
+                (
+                    (dt.DATE_TIME_UTC,),
+                    dt.INT,
+                    api.Expression.date_time_utc_iso_week,
+                ),
+            ),
+            "dt.iso_week",
+            self._expression,
+        )
+
+    def iso_year(self) -> expr.ColumnExpression:
+        """
+        Extracts the ISO 8601 week-numbering year from a DateTime. It differs from
+        the calendar year for days at the turn of the year that belong to a week
+        of the neighbouring year.
+
+        Returns:
+            int
+
+        Example:
+
+        >>> import pathway as pw
+        >>> table = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |               t1
+        ...    1 | 2021-01-01T10:13:00
+        ...    2 | 2021-12-31T10:13:00
+        ...    3 | 2024-12-31T14:13:23
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> table_with_datetime = table.select(t1=pw.this.t1.dt.strptime(fmt=fmt))
+        >>> table_with_week = table_with_datetime.with_columns(
+        ...     year=pw.this.t1.dt.iso_year(), week=pw.this.t1.dt.iso_week()
+        ... )
+        >>> pw.debug.compute_and_print(table_with_week, include_id=False)
+        t1                  | year | week
+        2021-01-01 10:13:00 | 2020 | 53
+        2021-12-31 10:13:00 | 2021 | 52
+        2024-12-31 14:13:23 | 2025 | 1
+        """
+
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE,),
+                    dt.INT,
+                    api.Expression.date_time_naive_iso_year,
+                ),
+                (
+                    (dt.DATE_TIME_UTC,),
+                    dt.INT,
+                    api.Expression.date_time_utc_iso_year,
+                ),
+            ),
+            "dt.iso_year",
+            self._expression,
+        )
+
+    def quarter(self) -> expr.ColumnExpression:
+        """
+        Extracts the quarter of the year (1-4) from a DateTime.
+
+        Returns:
+            int
+
+        Example:
+
+        >>> import pathway as pw
+        >>> table = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |               t1
+        ...    1 | 2023-03-31T23:59:59
+        ...    2 | 2023-04-01T00:00:00
+        ...    3 | 2023-12-31T12:00:00
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> table_with_datetime = table.select(t1=pw.this.t1.dt.strptime(fmt=fmt))
+        >>> table_with_quarter = table_with_datetime.with_columns(
+        ...     quarter=pw.this.t1.dt.quarter()
+        ... )
+        >>> pw.debug.compute_and_print(table_with_quarter, include_id=False)
+        t1                  | quarter
+        2023-03-31 23:59:59 | 1
+        2023-04-01 00:00:00 | 2
+        2023-12-31 12:00:00 | 4
+        """
+
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE,),
+                    dt.INT,
+                    api.Expression.date_time_naive_quarter,
+                ),
+                (
+                    (dt.DATE_TIME_UTC,),
+                    dt.INT,
+                    api.Expression.date_time_utc_quarter,
+                ),
+            ),
+            "dt.quarter",
+            self._expression,
+        )
+
+    def day_of_year(self) -> expr.ColumnExpression:
+        """
+        Extracts the ordinal day of the year (1-366) from a DateTime.
+
+        Returns:
+            int
+
+        Example:
+
+        >>> import pathway as pw
+        >>> table = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |               t1
+        ...    1 | 2023-01-01T00:00:00
+        ...    2 | 2023-03-25T10:13:00
+        ...    3 | 2024-12-31T12:00:00
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> table_with_datetime = table.select(t1=pw.this.t1.dt.strptime(fmt=fmt))
+        >>> table_with_day_of_year = table_with_datetime.with_columns(
+        ...     day_of_year=pw.this.t1.dt.day_of_year()
+        ... )
+        >>> pw.debug.compute_and_print(table_with_day_of_year, include_id=False)
+        t1                  | day_of_year
+        2023-01-01 00:00:00 | 1
+        2023-03-25 10:13:00 | 84
+        2024-12-31 12:00:00 | 366
+        """
+
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE,),
+                    dt.INT,
+                    api.Expression.date_time_naive_day_of_year,
+                ),
+                (
+                    (dt.DATE_TIME_UTC,),
+                    dt.INT,
+                    api.Expression.date_time_utc_day_of_year,
+                ),
+            ),
+            "dt.day_of_year",
+            self._expression,
+        )
+
+    def from_excel_serial(self) -> expr.ColumnExpression:
+        """
+        Converts an Excel serial date (number of days since 1899-12-30, with the
+        fractional part denoting the time of day) to DateTimeNaive. Excel's
+        non-existent date 1900-02-29 (serial 60) is rejected.
+
+        Returns:
+            DateTimeNaive
+
+        Example:
+
+        >>> import pathway as pw
+        >>> serials = pw.debug.table_from_markdown(
+        ...     '''
+        ...   | serial
+        ... 1 |     1.0
+        ... 2 | 45000.0
+        ... 3 | 45000.5
+        ... '''
+        ... )
+        >>> datetimes = serials.select(date=pw.this.serial.dt.from_excel_serial())
+        >>> pw.debug.compute_and_print(datetimes, include_id=False)
+        date
+        1900-01-01 00:00:00
+        2023-03-15 00:00:00
+        2023-03-15 12:00:00
+        """
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.FLOAT,),
+                    dt.DATE_TIME_NAIVE,
+                    api.Expression.date_time_naive_from_excel_serial,
+                ),
+            ),
+            "dt.from_excel_serial",
+            self._expression,
+        )
+
+    def to_excel_serial(self) -> expr.ColumnExpression:
+        """
+        Converts a DateTimeNaive to an Excel serial date (number of days since
+        1899-12-30, with the fractional part denoting the time of day).
+
+        Returns:
+            float
+
+        Example:
+
+        >>> import pathway as pw
+        >>> table = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |               t1
+        ...    1 | 2023-03-15T18:00:00
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> table_with_datetime = table.select(t1=pw.this.t1.dt.strptime(fmt=fmt))
+        >>> serials = table_with_datetime.select(serial=pw.this.t1.dt.to_excel_serial())
+        >>> pw.debug.compute_and_print(serials, include_id=False)
+        serial
+        45000.75
+        """
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE,),
+                    dt.FLOAT,
+                    api.Expression.date_time_naive_excel_serial,
+                ),
+            ),
+            "dt.to_excel_serial",
+            self._expression,
+        )
+
+    def from_julian_day(self) -> expr.ColumnExpression:
+        """
+        Converts a Julian day number to DateTimeNaive.
+
+        Returns:
+            DateTimeNaive
+
+        Example:
+
+        >>> import pathway as pw
+        >>> julian_days = pw.debug.table_from_markdown(
+        ...     '''
+        ...   | julian_day
+        ... 1 | 2451545.0
+        ... 2 | 2460000.5
+        ... '''
+        ... )
+        >>> datetimes = julian_days.select(date=pw.this.julian_day.dt.from_julian_day())
+        >>> pw.debug.compute_and_print(datetimes, include_id=False)
+        date
+        2000-01-01 12:00:00
+        2023-02-25 00:00:00
+        """
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.FLOAT,),
+                    dt.DATE_TIME_NAIVE,
+                    api.Expression.date_time_naive_from_julian_day,
+                ),
+            ),
+            "dt.from_julian_day",
+            self._expression,
+        )
+
+    def utc_from_julian_day(self) -> expr.ColumnExpression:
+        """
+        Converts a Julian day number to DateTimeUtc.
+
+        Returns:
+            DateTimeUtc
+
+        Example:
+
+        >>> import pathway as pw
+        >>> julian_days = pw.debug.table_from_markdown(
+        ...     '''
+        ...   | julian_day
+        ... 1 | 2451545.0
+        ... 2 | 2460000.5
+        ... '''
+        ... )
+        >>> datetimes = julian_days.select(date=pw.this.julian_day.dt.utc_from_julian_day())
+        >>> pw.debug.compute_and_print(datetimes, include_id=False)
+        date
+        2000-01-01 12:00:00+00:00
+        2023-02-25 00:00:00+00:00
+        """
+        return self.from_julian_day().dt.to_utc("UTC")
+
+    def to_julian_day(self) -> expr.ColumnExpression:
+        """
+        Converts a DateTime to a Julian day number. Naive DateTimes are assumed
+        to be in UTC.
+
+        Returns:
+            float
+
+        Example:
+
+        >>> import pathway as pw
+        >>> table = pw.debug.table_from_markdown(
+        ...     '''
+        ...      |               t1
+        ...    1 | 2023-03-15T18:00:00
+        ... '''
+        ... )
+        >>> fmt = "%Y-%m-%dT%H:%M:%S"
+        >>> table_with_datetime = table.select(t1=pw.this.t1.dt.strptime(fmt=fmt))
+        >>> julian_days = table_with_datetime.select(jd=pw.this.t1.dt.to_julian_day())
+        >>> pw.debug.compute_and_print(julian_days, include_id=False)
+        jd
+        2460019.25
+        """
+        return expr.MethodCallExpression(
+            (
+                (
+                    (dt.DATE_TIME_NAIVE,),
+                    dt.FLOAT,
+                    api.Expression.date_time_naive_julian_day,
+                ),
+                (
+                    (dt.DATE_TIME_UTC,),
+                    dt.FLOAT,
+                    api.Expression.date_time_utc_julian_day,
+                ),
+            ),
+            "dt.to_julian_day",
+            self._expression,
+        )
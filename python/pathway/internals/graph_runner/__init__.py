@@ -2,7 +2,9 @@
 
 from __future__ import annotations
 
+import datetime
 from collections.abc import Callable, Iterable
+from typing import Any, Literal
 
 from pathway.internals import api, environ, parse_graph as graph, table, trace
 from pathway.internals.column_path import ColumnPath
@@ -11,10 +13,17 @@ from pathway.internals.graph_runner.row_transformer_operator_handler import (  #
     RowTransformerOperatorHandler,
 )
 from pathway.internals.graph_runner.scope_context import ScopeContext
+from pathway.internals.graph_runner.shutdown import shutdown_on_sigterm
 from pathway.internals.graph_runner.state import ScopeState
 from pathway.internals.graph_runner.storage_graph import OperatorStorageGraph
 from pathway.internals.helpers import StableSet
-from pathway.internals.monitoring import MonitoringLevel, monitor_stats
+from pathway.internals.monitoring import (
+    AlertThresholds,
+    LoggingConfig,
+    MonitoringLevel,
+    StatsObserver,
+    monitor_stats,
+)
 from pathway.internals.operator import (
     ContextualizedIntermediateOperator,
     InputOperator,
@@ -42,6 +51,15 @@ class GraphRunner:
         default_logging: bool = True,
         persistence_config: PersistenceConfig | None = None,
         runtime_typechecking: bool | None = None,
+        batch_latency_target: datetime.timedelta | None = None,
+        operator_state_limit: int | None = None,
+        on_operator_state_limit: Literal["warn", "raise"] = "warn",
+        consistent_outputs: bool = False,
+        otlp_endpoint: str | None = None,
+        logging_config: LoggingConfig | None = None,
+        alert_thresholds: AlertThresholds | None = None,
+        shutdown_handle: api.ShutdownHandle | None = None,
+        on_stats: Callable[[Any], None] | None = None,
     ) -> None:
         self._graph = input_graph
         self.debug = debug
@@ -56,6 +74,15 @@ class GraphRunner:
             self.runtime_typechecking = environ.runtime_typechecking
         else:
             self.runtime_typechecking = runtime_typechecking
+        self.batch_latency_target = batch_latency_target
+        self.operator_state_limit = operator_state_limit
+        self.on_operator_state_limit = on_operator_state_limit
+        self.consistent_outputs = consistent_outputs
+        self.otlp_endpoint = otlp_endpoint
+        self.logging_config = logging_config or LoggingConfig()
+        self.alert_thresholds = alert_thresholds
+        self.shutdown_handle = shutdown_handle
+        self.on_stats = on_stats
 
     def run_tables(
         self,
@@ -124,17 +151,44 @@ class GraphRunner:
             for operator in context.nodes
             if isinstance(operator, ContextualizedIntermediateOperator)
         ]
+        topology = [
+            (
+                operator.id,
+                operator.label(),
+                [
+                    dependency.id
+                    for dependency in operator.input_operators()
+                    if dependency in context.nodes
+                ],
+            )
+            for operator in context.nodes
+        ]
         monitoring_level = self.monitoring_level.to_internal()
 
+        shutdown_handle = self.shutdown_handle or api.ShutdownHandle()
+
         with new_event_loop() as event_loop, monitor_stats(
-            monitoring_level, node_names, self.default_logging
-        ) as stats_monitor:
+            monitoring_level, node_names, self.default_logging, self.logging_config
+        ) as stats_monitor, shutdown_on_sigterm(shutdown_handle):
+            if self.on_stats is not None:
+                stats_monitor = StatsObserver(self.on_stats, stats_monitor)
+                if monitoring_level == api.MonitoringLevel.NONE:
+                    # the stats are reported by the engine only when monitoring
+                    monitoring_level = api.MonitoringLevel.IN_OUT
             if self.persistence_config:
                 self.persistence_config.on_before_run()
                 persistence_engine_config = self.persistence_config.engine_config
             else:
                 persistence_engine_config = None
 
+            fail_on_operator_state_limit = self.on_operator_state_limit == "raise"
+            if self.batch_latency_target is not None:
+                batch_latency_target_ms = int(
+                    self.batch_latency_target.total_seconds() * 1000
+                )
+            else:
+                batch_latency_target_ms = None
+
             try:
                 return api.run_with_new_graph(
                     logic,
@@ -144,6 +198,18 @@ class GraphRunner:
                     monitoring_level=monitoring_level,
                     with_http_server=self.with_http_server,
                     persistence_config=persistence_engine_config,
+                    batch_latency_target_ms=batch_latency_target_ms,
+                    operator_state_limit=self.operator_state_limit,
+                    fail_on_operator_state_limit=fail_on_operator_state_limit,
+                    consistent_outputs=self.consistent_outputs,
+                    otlp_endpoint=self.otlp_endpoint,
+                    shutdown_handle=shutdown_handle,
+                    topology=topology,
+                    alert_thresholds=(
+                        self.alert_thresholds._to_engine()
+                        if self.alert_thresholds is not None
+                        else None
+                    ),
                 )
             except api.EngineErrorWithTrace as e:
                 error, frame = e.args
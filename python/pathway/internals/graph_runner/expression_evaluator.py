@@ -75,6 +75,7 @@ class ExpressionEvaluator(ABC):
             trace=column.trace.to_engine(),
             dtype=props.dtype.map_to_engine(),
             append_only=props.append_only,
+            nullable=props.dtype.is_nullable(),
         )
 
     def expression_type(self, expression: expr.ColumnExpression) -> dt.DType:
@@ -436,6 +437,34 @@ class RowwiseEvaluator(
             paths,
             fun,
             self._table_properties(output_storage),
+            max_in_flight=expression._max_in_flight,
+        )
+
+        assert eval_state is not None
+        eval_state.set_temporary_table(output_storage, engine_table)
+        return self.eval_dependency(tmp_column, eval_state=eval_state)
+
+    def eval_batch_apply(
+        self,
+        expression: expr.BatchApplyExpression,
+        eval_state: RowwiseEvalState | None = None,
+    ):
+        fun, args = self._prepare_positional_apply(
+            fun=expression._fun, args=expression._args, kwargs=expression._kwargs
+        )
+
+        columns, input_storage, engine_input_table = self.run_subexpressions(args)
+        tmp_column = clmn.MaterializedColumn(
+            self.context.universe, ColumnProperties(dtype=expression._dtype)
+        )
+        output_storage = Storage.flat(self.context.universe, [tmp_column])
+        paths = [input_storage.get_path(column) for column in columns]
+        engine_table = self.scope.batch_apply_table(
+            engine_input_table,
+            paths,
+            fun,
+            self._table_properties(output_storage),
+            max_batch_size=expression._max_batch_size,
         )
 
         assert eval_state is not None
@@ -793,6 +822,43 @@ class ForgetImmediatelyEvaluator(
         )
 
 
+class ExpireAfterEvaluator(ExpressionEvaluator, context_type=clmn.ExpireAfterContext):
+    context: clmn.ExpireAfterContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        properties = self._table_properties(output_storage)
+
+        return self.scope.expire_after(
+            self.state.get_table(input_storage),
+            self.context.duration,
+            properties,
+        )
+
+
+class DeduplicateWithinEvaluator(
+    ExpressionEvaluator, context_type=clmn.DeduplicateWithinContext
+):
+    context: clmn.DeduplicateWithinContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        fingerprint_column_path = input_storage.get_path(
+            self.context.fingerprint_column
+        )
+        time_column_path = input_storage.get_path(self.context.time_column)
+        expiration_column_path = input_storage.get_path(self.context.expiration_column)
+        properties = self._table_properties(output_storage)
+
+        return self.scope.deduplicate_within(
+            self.state.get_table(input_storage),
+            fingerprint_column_path,
+            time_column_path,
+            expiration_column_path,
+            properties,
+        )
+
+
 class FilterOutForgettingContext(
     ExpressionEvaluator, context_type=clmn.FilterOutForgettingContext
 ):
@@ -824,6 +890,23 @@ class FreezeEvaluator(ExpressionEvaluator, context_type=clmn.FreezeContext):
         )
 
 
+class FreezeLateEvaluator(ExpressionEvaluator, context_type=clmn.FreezeLateContext):
+    context: clmn.FreezeLateContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        threshold_column_path = input_storage.get_path(self.context.threshold_column)
+        time_column_path = input_storage.get_path(self.context.time_column)
+        properties = self._table_properties(output_storage)
+
+        return self.scope.freeze_late(
+            self.state.get_table(input_storage),
+            threshold_column_path,
+            time_column_path,
+            properties,
+        )
+
+
 class BufferEvaluator(ExpressionEvaluator, context_type=clmn.BufferContext):
     context: clmn.BufferContext
 
@@ -841,6 +924,24 @@ class BufferEvaluator(ExpressionEvaluator, context_type=clmn.BufferContext):
         )
 
 
+class ReshardEvaluator(ExpressionEvaluator, context_type=clmn.ReshardContext):
+    context: clmn.ReshardContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        column_paths = [
+            input_storage.get_path(column) for column in self.context.reshard_columns
+        ]
+        properties = self._table_properties(output_storage)
+
+        return self.scope.reshard_table(
+            self.state.get_table(input_storage),
+            column_paths,
+            properties,
+            self.context.parallelism,
+        )
+
+
 class IntersectEvaluator(ExpressionEvaluator, context_type=clmn.IntersectContext):
     context: clmn.IntersectContext
 
@@ -1172,6 +1273,17 @@ class FlattenEvaluator(ExpressionEvaluator, context_type=clmn.FlattenContext):
         )
 
 
+class ChangelogEvaluator(ExpressionEvaluator, context_type=clmn.ChangelogContext):
+    context: clmn.ChangelogContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        properties = self._table_properties(output_storage)
+        return self.scope.changelog_table(
+            self.state.get_table(input_storage), properties
+        )
+
+
 class SortingEvaluator(ExpressionEvaluator, context_type=clmn.SortingContext):
     context: clmn.SortingContext
 
@@ -1186,3 +1298,81 @@ class SortingEvaluator(ExpressionEvaluator, context_type=clmn.SortingContext):
             instance_column_path,
             properties,
         )
+
+
+class RankEvaluator(ExpressionEvaluator, context_type=clmn.RankContext):
+    context: clmn.RankContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        key_column_path = input_storage.get_path(self.context.key_column)
+        instance_column_path = input_storage.get_path(self.context.instance_column)
+        properties = self._table_properties(output_storage)
+        return self.scope.rank_table(
+            self.state.get_table(input_storage),
+            key_column_path,
+            instance_column_path,
+            self.context.descending,
+            properties,
+        )
+
+
+class CumulativeEvaluator(ExpressionEvaluator, context_type=clmn.CumulativeContext):
+    context: clmn.CumulativeContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        key_column_path = input_storage.get_path(self.context.key_column)
+        instance_column_path = input_storage.get_path(self.context.instance_column)
+        reducers = [
+            (
+                reducer,
+                [input_storage.get_path(column)] if column is not None else [],
+            )
+            for reducer, column, _ in self.context.reducers
+        ]
+        properties = self._table_properties(output_storage)
+        return self.scope.cumulative_table(
+            self.state.get_table(input_storage),
+            key_column_path,
+            instance_column_path,
+            reducers,
+            properties,
+        )
+
+
+class TopNEvaluator(ExpressionEvaluator, context_type=clmn.TopNContext):
+    context: clmn.TopNContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        key_column_path = input_storage.get_path(self.context.key_column)
+        instance_column_path = input_storage.get_path(self.context.instance_column)
+        properties = self._table_properties(output_storage)
+        return self.scope.top_n_table(
+            self.state.get_table(input_storage),
+            key_column_path,
+            instance_column_path,
+            self.context.n,
+            self.context.descending,
+            properties,
+        )
+
+
+class SessionWindowEvaluator(
+    ExpressionEvaluator, context_type=clmn.SessionWindowContext
+):
+    context: clmn.SessionWindowContext
+
+    def run(self, output_storage: Storage, *input_storages: Storage) -> api.Table:
+        [input_storage] = input_storages
+        key_column_path = input_storage.get_path(self.context.key_column)
+        instance_column_path = input_storage.get_path(self.context.instance_column)
+        properties = self._table_properties(output_storage)
+        return self.scope.session_window_table(
+            self.state.get_table(input_storage),
+            key_column_path,
+            instance_column_path,
+            self.context.max_gap,
+            properties,
+        )
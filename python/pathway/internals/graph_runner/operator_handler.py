@@ -12,7 +12,11 @@ from abc import ABC, abstractmethod
 from typing import TYPE_CHECKING, ClassVar, Generic, TypeVar
 
 from pathway.internals import api, trace
-from pathway.internals.datasink import CallbackDataSink, GenericDataSink
+from pathway.internals.datasink import (
+    CallbackDataSink,
+    GenericDataSink,
+    ServedTableDataSink,
+)
 from pathway.internals.datasource import (
     EmptyDataSource,
     GenericDataSource,
@@ -177,6 +181,14 @@ class OutputOperatorHandler(
                 on_time_end=datasink.on_time_end,
                 on_end=datasink.on_end,
                 skip_persisted_batch=datasink.skip_persisted_batch,
+                on_commit=datasink.on_commit,
+            )
+        elif isinstance(datasink, ServedTableDataSink):
+            self.scope.serve_table(
+                table=engine_table,
+                column_paths=column_paths,
+                name=datasink.name,
+                column_names=list(table._columns.keys()),
             )
         else:
             raise RuntimeError("datasink not supported")
@@ -294,6 +306,7 @@ class IterateOperatorHandler(
                 extra=extra,
                 logic=iterate_logic,
                 limit=operator.iteration_limit,
+                fail_on_limit=operator.fail_on_iteration_limit,
             )
 
         # store iteration result in outer scope state
@@ -84,6 +84,10 @@ class AddNewColumnsPathEvaluator(
         clmn.RowwiseContext,
         clmn.JoinRowwiseContext,
         clmn.SortingContext,
+        clmn.SessionWindowContext,
+        clmn.RankContext,
+        clmn.CumulativeContext,
+        clmn.TopNContext,
         clmn.GradualBroadcastContext,
     ],
 ):
@@ -204,8 +208,12 @@ class NoNewColumnsPathEvaluator(
         clmn.HavingContext,
         clmn.ForgetContext,
         clmn.ForgetImmediatelyContext,
+        clmn.ExpireAfterContext,
+        clmn.DeduplicateWithinContext,
         clmn.FilterOutForgettingContext,
         clmn.FreezeContext,
+        clmn.FreezeLateContext,
+        clmn.ReshardContext,
         clmn.BufferContext,
         clmn.HavingContext,
     ],
@@ -291,6 +299,28 @@ class FlattenPathEvaluator(PathEvaluator, context_types=[clmn.FlattenContext]):
         return Storage(self.context.universe, paths)
 
 
+class ChangelogPathEvaluator(PathEvaluator, context_types=[clmn.ChangelogContext]):
+    context: clmn.ChangelogContext
+
+    def compute(
+        self,
+        output_columns: Iterable[clmn.Column],
+        input_storages: dict[Universe, Storage],
+    ) -> Storage:
+        input_storage = input_storages[self.context.orig_universe]
+        paths = {}
+        for column in output_columns:
+            if column == self.context.time_column:
+                paths[column] = ColumnPath((1,))
+            elif column == self.context.diff_column:
+                paths[column] = ColumnPath((2,))
+            else:
+                assert isinstance(column, clmn.ColumnWithReference)
+                original_column = column.expression._column
+                paths[column] = (0,) + input_storage.get_path(original_column)
+        return Storage(self.context.universe, paths)
+
+
 class PromiseSameUniversePathEvaluator(
     PathEvaluator,
     context_types=[
@@ -0,0 +1,34 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import contextlib
+import signal
+import threading
+from collections.abc import Iterator
+
+from pathway.internals import api
+
+
+@contextlib.contextmanager
+def shutdown_on_sigterm(shutdown_handle: api.ShutdownHandle) -> Iterator[None]:
+    """Stops the computation gracefully on SIGTERM: the data read so far is processed
+    and written by the outputs and, with persistence, the final checkpoint is made
+    before ``pw.run`` returns."""
+
+    if threading.current_thread() is not threading.main_thread():
+        # Signal handlers can only be set in the main thread
+        yield
+        return
+
+    def handler(signum, frame) -> None:
+        shutdown_handle.request()
+
+    previous_handler = signal.signal(signal.SIGTERM, handler)
+    try:
+        yield
+    finally:
+        signal.signal(
+            signal.SIGTERM,
+            signal.SIG_DFL if previous_handler is None else previous_handler,
+        )
@@ -147,6 +147,8 @@ class Joinable(TableLike, DesugaringContext):
         how: JoinMode = JoinMode.INNER,
         left_instance: expr.ColumnReference | None = None,
         right_instance: expr.ColumnReference | None = None,
+        defaults: dict[expr.ColumnReference, Any] = {},
+        indicator: str | None = None,
     ) -> JoinResult:
         """Join self with other using the given join expression.
 
@@ -159,6 +161,12 @@ class Joinable(TableLike, DesugaringContext):
               correspond to inner, left, right and outer join respectively.
             left_instance/right_instance: optional arguments describing partitioning of the data into
               separate instances
+            defaults: dictionary column -> default value. For rows that were not
+              matched, missing values of these columns are replaced with the default
+              value instead of `None`.
+            indicator: if set, a column with this name is added to the result. It
+              contains `"both"` for matched rows and `"left_only"` or `"right_only"`
+              for rows present only on one side of the join.
 
         Returns:
             JoinResult: an object on which `.select()` may be called to extract relevant
@@ -194,6 +202,8 @@ class Joinable(TableLike, DesugaringContext):
             id=id,
             left_instance=left_instance,
             right_instance=right_instance,
+            defaults=defaults,
+            indicator=indicator,
         )
 
     @trace_user_frame
@@ -263,6 +273,8 @@ class Joinable(TableLike, DesugaringContext):
         id: expr.ColumnReference | None = None,
         left_instance: expr.ColumnReference | None = None,
         right_instance: expr.ColumnReference | None = None,
+        defaults: dict[expr.ColumnReference, Any] = {},
+        indicator: str | None = None,
     ) -> JoinResult:
         """
         Left-joins two tables or join results.
@@ -273,6 +285,12 @@ class Joinable(TableLike, DesugaringContext):
             id: optional id column of the result
             left_instance/right_instance: optional arguments describing partitioning of the data into
               separate instances
+            defaults: dictionary column -> default value. For rows that were not
+              matched, missing values of these columns are replaced with the default
+              value instead of `None`.
+            indicator: if set, a column with this name is added to the result. It
+              contains `"both"` for matched rows and `"left_only"` or `"right_only"`
+              for rows present only on one side of the join.
 
         Remarks:
         args cannot contain id column from either of tables, \
@@ -282,7 +300,8 @@ class Joinable(TableLike, DesugaringContext):
 
         Behavior:
         - for rows from the left side that were not matched with the right side,
-        missing values on the right are replaced with `None`
+        missing values on the right are replaced with `None`, or with values from
+        `defaults`
         - rows from the right side that were not matched with the left side are skipped
         - for rows that were matched the behavior is the same as that of an inner join.
 
@@ -328,6 +347,8 @@ class Joinable(TableLike, DesugaringContext):
             id=id,
             left_instance=left_instance,
             right_instance=right_instance,
+            defaults=defaults,
+            indicator=indicator,
         )
 
     @trace_user_frame
@@ -340,6 +361,8 @@ class Joinable(TableLike, DesugaringContext):
         id: expr.ColumnReference | None = None,
         left_instance: expr.ColumnReference | None = None,
         right_instance: expr.ColumnReference | None = None,
+        defaults: dict[expr.ColumnReference, Any] = {},
+        indicator: str | None = None,
     ) -> JoinResult:
         """
         Outer-joins two tables or join results.
@@ -350,6 +373,12 @@ class Joinable(TableLike, DesugaringContext):
             id: optional id column of the result
             left_instance/right_instance: optional arguments describing partitioning of the data into separate
               instances
+            defaults: dictionary column -> default value. For rows that were not
+              matched, missing values of these columns are replaced with the default
+              value instead of `None`.
+            indicator: if set, a column with this name is added to the result. It
+              contains `"both"` for matched rows and `"left_only"` or `"right_only"`
+              for rows present only on one side of the join.
 
         Remarks: args cannot contain id column from either of tables, \
         as the result table has id column with auto-generated ids; \
@@ -359,7 +388,8 @@ class Joinable(TableLike, DesugaringContext):
         Behavior:
         - rows from the left side that were not matched with the right side are skipped
         - for rows from the right side that were not matched with the left side,
-        missing values on the left are replaced with `None`
+        missing values on the left are replaced with `None`, or with values from
+        `defaults`
         - for rows that were matched the behavior is the same as that of an inner join.
 
         Returns:
@@ -408,6 +438,8 @@ class Joinable(TableLike, DesugaringContext):
             id=id,
             left_instance=left_instance,
             right_instance=right_instance,
+            defaults=defaults,
+            indicator=indicator,
         )
 
     @trace_user_frame
@@ -420,6 +452,8 @@ class Joinable(TableLike, DesugaringContext):
         id: expr.ColumnReference | None = None,
         left_instance: expr.ColumnReference | None = None,
         right_instance: expr.ColumnReference | None = None,
+        defaults: dict[expr.ColumnReference, Any] = {},
+        indicator: str | None = None,
     ) -> JoinResult:
         """Outer-joins two tables or join results.
 
@@ -428,6 +462,12 @@ class Joinable(TableLike, DesugaringContext):
             *on: Columns to join, syntax `self.col1 == other.col2`
             id: optional id column of the result
             instance: optional argument describing partitioning of the data into separate instances
+            defaults: dictionary column -> default value. For rows that were not
+              matched, missing values of these columns are replaced with the default
+              value instead of `None`.
+            indicator: if set, a column with this name is added to the result. It
+              contains `"both"` for matched rows and `"left_only"` or `"right_only"`
+              for rows present only on one side of the join.
 
         Remarks: args cannot contain id column from either of tables, \
             as the result table has id column with auto-generated ids; \
@@ -436,9 +476,11 @@ class Joinable(TableLike, DesugaringContext):
 
         Behavior:
         - for rows from the left side that were not matched with the right side,
-        missing values on the right are replaced with `None`
+        missing values on the right are replaced with `None`, or with values from
+        `defaults`
         - for rows from the right side that were not matched with the left side,
-        missing values on the left are replaced with `None`
+        missing values on the left are replaced with `None`, or with values from
+        `defaults`
         - for rows that were matched the behavior is the same as that of an inner join.
 
         Returns:
@@ -476,6 +518,17 @@ class Joinable(TableLike, DesugaringContext):
         12 | 12   | 324
         13 |      |
         13 |      |
+        >>> result = t1.join_outer(
+        ...     t2, t1.a == t2.c, defaults={t1.b: 0, t2.d: 0}, indicator="side"
+        ... ).select(t1.b, t2.d, pw.this.side)
+        >>> pw.debug.compute_and_print(result, include_id=False)
+        b   | d   | side
+        0   | 213 | right_only
+        0   | 214 | right_only
+        111 | 211 | both
+        112 | 212 | both
+        113 | 0   | left_only
+        114 | 0   | left_only
         """
         return JoinResult._table_join(
             self,
@@ -485,6 +538,8 @@ class Joinable(TableLike, DesugaringContext):
             id=id,
             left_instance=left_instance,
             right_instance=right_instance,
+            defaults=defaults,
+            indicator=indicator,
         )
 
     @property
@@ -535,6 +590,7 @@ class JoinResult(Joinable, OperatorInput):
     _joined_on_names: StableSet[str]
     _all_colnames: StableSet[str]
     _join_mode: JoinMode
+    _indicator: str | None
 
     def __init__(
         self,
@@ -548,6 +604,7 @@ class JoinResult(Joinable, OperatorInput):
         _substitution: dict[thisclass.ThisMetaclass, Joinable],
         _joined_on_names: StableSet[str],
         _join_mode: JoinMode,
+        _indicator: str | None = None,
     ):
         super().__init__(_context)
         self._inner_table = _inner_table
@@ -557,12 +614,15 @@ class JoinResult(Joinable, OperatorInput):
         self._substitution = {**_substitution, thisclass.this: self}
         self._joined_on_names = _joined_on_names
         self._join_mode = _join_mode
+        self._indicator = _indicator
         self._original_left = _original_left
         self._original_right = _original_right
         assert _original_left._subtables().isdisjoint(_original_right._subtables())
         self._all_colnames = StableSet.union(
             _original_left.keys(), _original_right.keys()
         )
+        if _indicator is not None:
+            self._all_colnames.add(_indicator)
         self._chained_join_desugaring = SubstitutionDesugaring(self._substitutions()[1])
 
     @staticmethod
@@ -605,6 +665,8 @@ class JoinResult(Joinable, OperatorInput):
         name = self._column_deprecation_rename(name)
         if name == "id":
             return self._inner_table.id
+        elif name == self._indicator:
+            return self._inner_table[name]
         elif name in self._joined_on_names:
             if self._join_mode is JoinMode.INNER:
                 return self._original_left[name]
@@ -732,6 +794,7 @@ class JoinResult(Joinable, OperatorInput):
             _substitution=self._substitution,
             _joined_on_names=self._joined_on_names,
             _join_mode=self._join_mode,
+            _indicator=self._indicator,
         )
 
     @trace_user_frame
@@ -880,30 +943,59 @@ class JoinResult(Joinable, OperatorInput):
         original_left: Joinable,
         original_right: Joinable,
         common_column_names: StableSet[str],
+        mode: JoinMode,
+        defaults: dict[expr.ColumnReference, Any],
+        indicator: str | None,
     ) -> tuple[Table, dict[expr.InternalColRef, expr.ColumnReference]]:
+        from pathway.internals.common import coalesce, if_else
+
         left_table, left_substitutions = original_left._substitutions()
         right_table, right_substitutions = original_right._substitutions()
         cnt = itertools.count(0)
         expressions: dict[str, expr.ColumnExpression] = {}
         colref_to_name_mapping: dict[expr.InternalColRef, str] = {}
-        for table, subs in [
-            (left_table, left_substitutions),
-            (right_table, right_substitutions),
+        missing_defaults = {ref._to_internal(): value for ref, value in defaults.items()}
+        for table, subs, can_be_missing in [
+            (left_table, left_substitutions, mode in [JoinMode.RIGHT, JoinMode.OUTER]),
+            (right_table, right_substitutions, mode in [JoinMode.LEFT, JoinMode.OUTER]),
         ]:
             if len(subs) == 0:  # tables have empty subs, so set them here
                 for ref in table:
                     subs[ref._to_internal()] = ref
             subs_total = subs | {table.id._to_internal(): table.id}
             for int_ref, expression in subs_total.items():
+                if int_ref in missing_defaults:
+                    if not can_be_missing:
+                        raise ValueError(
+                            f"Cannot set a default value for column {int_ref._name},"
+                            + " as its side of the join is never missing."
+                        )
+                    expression = if_else(
+                        table.id.is_not_none(),
+                        expression,
+                        missing_defaults.pop(int_ref),
+                    )
                 inner_name = f"_pw_{next(cnt)}"
                 expressions[inner_name] = expression
                 colref_to_name_mapping[int_ref] = inner_name
-        from pathway.internals.common import coalesce
+        if missing_defaults:
+            name = next(iter(missing_defaults))._name
+            raise ValueError(
+                f"Cannot set a default value for column {name}, as it is not an input"
+                + " of the join."
+            )
 
         for name in common_column_names:
             if name != "id":
                 expressions[name] = coalesce(original_left[name], original_right[name])
 
+        if indicator is not None:
+            expressions[indicator] = if_else(
+                left_table.id.is_none(),
+                "right_only",
+                if_else(right_table.id.is_none(), "left_only", "both"),
+            )
+
         inner_table = JoinResult._join(context, **expressions)
         final_mapping = {
             colref: inner_table[name] for colref, name in colref_to_name_mapping.items()
@@ -912,6 +1004,9 @@ class JoinResult(Joinable, OperatorInput):
             if name != "id":
                 colref = inner_table[name]
                 final_mapping[colref._to_internal()] = colref
+        if indicator is not None:
+            colref = inner_table[indicator]
+            final_mapping[colref._to_internal()] = colref
         final_mapping[inner_table.id._to_internal()] = inner_table.id
 
         rowwise_context = clmn.JoinRowwiseContext.from_mapping(
@@ -932,6 +1027,8 @@ class JoinResult(Joinable, OperatorInput):
         id: expr.ColumnReference | None = None,
         left_instance: expr.ColumnReference | None = None,
         right_instance: expr.ColumnReference | None = None,
+        defaults: dict[expr.ColumnReference, Any] = {},
+        indicator: str | None = None,
     ) -> JoinResult:
         if left == right:
             raise ValueError(
@@ -1019,11 +1116,21 @@ class JoinResult(Joinable, OperatorInput):
                 mode in [JoinMode.LEFT, JoinMode.OUTER],
                 mode in [JoinMode.RIGHT, JoinMode.OUTER],
             )
+        if indicator is not None:
+            if mode == JoinMode.INNER:
+                raise ValueError("Cannot add an indicator column to an inner join.")
+            if indicator in left.keys() or indicator in right.keys():
+                raise ValueError(
+                    f"Indicator column {indicator} appears in an input of the join."
+                )
         inner_table, columns_mapping = JoinResult._prepare_inner_table_with_mapping(
             context,
             left,
             right,
             common_column_names,
+            mode,
+            defaults,
+            indicator,
         )
         return JoinResult(
             context,
@@ -1036,6 +1143,7 @@ class JoinResult(Joinable, OperatorInput):
             substitution,
             common_column_names,
             mode,
+            indicator,
         )
 
 
@@ -1086,6 +1194,8 @@ def join(
     how: JoinMode = JoinMode.INNER,
     left_instance: expr.ColumnReference | None = None,
     right_instance: expr.ColumnReference | None = None,
+    defaults: dict[expr.ColumnReference, Any] = {},
+    indicator: str | None = None,
 ) -> JoinResult:
     """Join self with other using the given join expression.
 
@@ -1099,6 +1209,12 @@ def join(
             correspond to inner, left, right and outer join respectively.
         left_instance/right_instance: optional arguments describing partitioning of the data into
             separate instances
+        defaults: dictionary column -> default value. For rows that were not
+            matched, missing values of these columns are replaced with the default
+            value instead of `None`.
+        indicator: if set, a column with this name is added to the result. It
+            contains `"both"` for matched rows and `"left_only"` or `"right_only"`
+            for rows present only on one side of the join.
 
     Returns:
         JoinResult: an object on which `.select()` may be called to extract relevant
@@ -1133,6 +1249,8 @@ def join(
         how=how,
         left_instance=left_instance,
         right_instance=right_instance,
+        defaults=defaults,
+        indicator=indicator,
     )
 
 
@@ -1192,6 +1310,8 @@ def join_left(
     id: expr.ColumnReference | None = None,
     left_instance: expr.ColumnReference | None = None,
     right_instance: expr.ColumnReference | None = None,
+    defaults: dict[expr.ColumnReference, Any] = {},
+    indicator: str | None = None,
 ) -> JoinResult:
     """
     Left-joins two tables or join results.
@@ -1203,6 +1323,12 @@ def join_left(
         id: optional id column of the result
         left_instance/right_instance: optional arguments describing partitioning of the data into
             separate instances
+        defaults: dictionary column -> default value. For rows that were not
+            matched, missing values of these columns are replaced with the default
+            value instead of `None`.
+        indicator: if set, a column with this name is added to the result. It
+            contains `"both"` for matched rows and `"left_only"` or `"right_only"`
+            for rows present only on one side of the join.
 
     Remarks:
     args cannot contain id column from either of tables, \
@@ -1212,7 +1338,8 @@ def join_left(
 
     Behavior:
     - for rows from the left side that were not matched with the right side,
-    missing values on the right are replaced with `None`
+    missing values on the right are replaced with `None`, or with values from
+    `defaults`
     - rows from the right side that were not matched with the left side are skipped
     - for rows that were matched the behavior is the same as that of an inner join.
 
@@ -1251,7 +1378,13 @@ def join_left(
     13 |      |
     """
     return left.join_left(
-        right, *on, id=id, left_instance=left_instance, right_instance=right_instance
+        right,
+        *on,
+        id=id,
+        left_instance=left_instance,
+        right_instance=right_instance,
+        defaults=defaults,
+        indicator=indicator,
     )
 
 
@@ -1262,6 +1395,8 @@ def join_right(
     id: expr.ColumnReference | None = None,
     left_instance: expr.ColumnReference | None = None,
     right_instance: expr.ColumnReference | None = None,
+    defaults: dict[expr.ColumnReference, Any] = {},
+    indicator: str | None = None,
 ) -> JoinResult:
     """
     Outer-joins two tables or join results.
@@ -1273,6 +1408,12 @@ def join_right(
         id: optional id column of the result
         left_instance/right_instance: optional arguments describing partitioning of the data into separate
             instances
+        defaults: dictionary column -> default value. For rows that were not
+            matched, missing values of these columns are replaced with the default
+            value instead of `None`.
+        indicator: if set, a column with this name is added to the result. It
+            contains `"both"` for matched rows and `"left_only"` or `"right_only"`
+            for rows present only on one side of the join.
 
     Remarks: args cannot contain id column from either of tables, \
     as the result table has id column with auto-generated ids; \
@@ -1282,7 +1423,8 @@ def join_right(
     Behavior:
     - rows from the left side that were not matched with the right side are skipped
     - for rows from the right side that were not matched with the left side,
-    missing values on the left are replaced with `None`
+    missing values on the left are replaced with `None`, or with values from
+    `defaults`
     - for rows that were matched the behavior is the same as that of an inner join.
 
     Returns:
@@ -1324,7 +1466,13 @@ def join_right(
 
     """
     return left.join_right(
-        right, *on, id=id, left_instance=left_instance, right_instance=right_instance
+        right,
+        *on,
+        id=id,
+        left_instance=left_instance,
+        right_instance=right_instance,
+        defaults=defaults,
+        indicator=indicator,
     )
 
 
@@ -1335,6 +1483,8 @@ def join_outer(
     id: expr.ColumnReference | None = None,
     left_instance: expr.ColumnReference | None = None,
     right_instance: expr.ColumnReference | None = None,
+    defaults: dict[expr.ColumnReference, Any] = {},
+    indicator: str | None = None,
 ) -> JoinResult:
     """Outer-joins two tables or join results.
 
@@ -1344,6 +1494,12 @@ def join_outer(
         *on: Columns to join, syntax `self.col1 == other.col2`
         id: optional id column of the result
         instance: optional argument describing partitioning of the data into separate instances
+        defaults: dictionary column -> default value. For rows that were not
+          matched, missing values of these columns are replaced with the default
+          value instead of `None`.
+        indicator: if set, a column with this name is added to the result. It
+          contains `"both"` for matched rows and `"left_only"` or `"right_only"`
+          for rows present only on one side of the join.
 
     Remarks: args cannot contain id column from either of tables, \
         as the result table has id column with auto-generated ids; \
@@ -1352,9 +1508,11 @@ def join_outer(
 
     Behavior:
     - for rows from the left side that were not matched with the right side,
-    missing values on the right are replaced with `None`
+    missing values on the right are replaced with `None`, or with values from
+    `defaults`
     - for rows from the right side that were not matched with the left side,
-    missing values on the left are replaced with `None`
+    missing values on the left are replaced with `None`, or with values from
+    `defaults`
     - for rows that were matched the behavior is the same as that of an inner join.
 
     Returns:
@@ -1394,5 +1552,11 @@ def join_outer(
     13 |      |
     """
     return left.join_outer(
-        right, *on, id=id, left_instance=left_instance, right_instance=right_instance
+        right,
+        *on,
+        id=id,
+        left_instance=left_instance,
+        right_instance=right_instance,
+        defaults=defaults,
+        indicator=indicator,
     )
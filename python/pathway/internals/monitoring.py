@@ -1,9 +1,13 @@
 # Copyright © 2024 Pathway
 
 import contextlib
+import datetime
+import json
 import logging
+from dataclasses import dataclass
 from enum import Enum
-from typing import Any
+from collections.abc import Callable, Mapping
+from typing import Any, Literal
 
 from rich import box
 from rich.align import Align
@@ -17,6 +21,11 @@ from rich.table import Table
 
 from pathway.internals import api
 
+ENGINE_LOGGER = "pathway.engine"
+ENGINE_SUBSYSTEMS = ("connectors", "persistence", "dataflow")
+# The level the trace logs of the engine are passed to Python with
+TRACE = 5
+
 
 class ConsolePrintingToBuffer(Console):
     def __init__(self) -> None:
@@ -72,15 +81,16 @@ class MonitoringOutput:
         self, table: Table, node_name: str, stats: Any, skip_lag: bool = False
     ) -> None:
         if stats is None:
-            table.add_row(node_name, "no info", "")
+            table.add_row(node_name, "no info", "", "")
         elif stats.done:
-            table.add_row(node_name, "finished", "")
+            table.add_row(node_name, "finished", "", "")
         else:
             latency = self.get_latency(stats.time)
             table.add_row(
                 node_name,
                 "initializing" if latency is None else f"{latency}",
                 "" if skip_lag else f"{stats.lag}",
+                "" if stats.pending_records is None else f"{stats.pending_records}",
             )
 
     def get_connectors_table(self) -> Table:
@@ -91,6 +101,15 @@ class MonitoringOutput:
         table.add_column("no. messages in the last minibatch", justify="right")
         table.add_column("in the last minute", justify="right")
         table.add_column("since start", justify="right")
+        table.add_column("lag", justify="right")
+        table.add_column("queue", justify="right")
+
+        def format_lag(entry: Any) -> str:
+            if entry.lag_messages is not None:
+                return f"{entry.lag_messages} messages"
+            elif entry.backlog_files is not None:
+                return f"{entry.backlog_files} files, {entry.backlog_bytes} B"
+            return "-"
 
         for name, entry in self.data.connector_stats:
             table.add_row(
@@ -100,6 +119,36 @@ class MonitoringOutput:
                 else f"{entry.num_messages_recently_committed}",
                 f"{entry.num_messages_in_last_minute}",
                 f"{entry.num_messages_from_start}",
+                format_lag(entry),
+                f"{entry.queue_depth}",
+            )
+        return table
+
+    def get_outputs_table(self) -> Table:
+        table = Table(
+            caption="The latency of the rows is measured from their ingestion to "
+            + "their emission by the output.",
+            box=box.SIMPLE,
+        )
+        table.add_column("output", justify="left")
+        table.add_column("no. rows", justify="right")
+        table.add_column(r"p50 latency \[ms]", justify="right")
+        table.add_column(r"p99 latency \[ms]", justify="right")
+
+        def format_bound(histogram: Any, quantile: float) -> str:
+            bound = histogram.quantile_bound(quantile)
+            if histogram.count == 0:
+                return "-"
+            elif bound is None:
+                return f">{histogram.bucket_bounds_ms()[-1]}"
+            return f"<={bound}"
+
+        for name, histogram in self.data.output_latencies:
+            table.add_row(
+                name,
+                f"{histogram.count}",
+                format_bound(histogram, 0.5),
+                format_bound(histogram, 0.99),
             )
         return table
 
@@ -115,7 +164,8 @@ class MonitoringOutput:
                 "You can find the latency for each operator in the computation graph"
                 + " displayed above. The latency is measured as the difference between "
                 + "the time when the operator processed the data and the time when "
-                + "pathway acquired the data."
+                + "pathway acquired the data. The pending records were sent to "
+                + "the operator and not processed yet."
             )
         table = Table(caption=caption, box=box.SIMPLE)
         table.add_column("operator", justify="left")
@@ -127,6 +177,7 @@ class MonitoringOutput:
             r"lag to input \[ms]",
             justify="right",
         )
+        table.add_column("pending records", justify="right")
 
         self.log_line(table, "input", self.data.input_stats, skip_lag=True)
         max_operator_rows_to_print = max_height - 4
@@ -140,7 +191,7 @@ class MonitoringOutput:
                 break
             self.log_line(table, node_name, self.data.operators_stats.get(id_))
         if max_operator_rows_to_print < len(self.node_names):
-            table.add_row("...", "...", "...")
+            table.add_row("...", "...", "...", "...")
 
         self.log_line(table, "output", self.data.output_stats)
 
@@ -151,7 +202,9 @@ class MonitoringOutput:
     ) -> RenderResult:
         layout = Layout(name="monitoring_inner")
         layout.split_row(Layout(name="connectors"), Layout(name="operators"))
-        layout["connectors"].update(Align.center(self.get_connectors_table()))
+        layout["connectors"].update(
+            Align.center(Group(self.get_connectors_table(), self.get_outputs_table()))
+        )
         layout["operators"].update(
             Align.center(self.get_operators_table(options.max_height - 2))
         )
@@ -183,33 +236,182 @@ class StatsMonitor:
         self.layout["monitoring"].update(MonitoringOutput(self.node_names, data, now))
 
 
+class StatsObserver:
+    """Passes the stats of the computation reported by the engine to a callback,
+    as well as to the monitor of the dashboard, if any."""
+
+    def __init__(
+        self, callback: Callable[[Any], None], stats_monitor: StatsMonitor | None
+    ) -> None:
+        self.callback = callback
+        self.stats_monitor = stats_monitor
+
+    def update_monitoring(self, data: Any, now: int) -> None:
+        if self.stats_monitor is not None:
+            self.stats_monitor.update_monitoring(data, now)
+        self.callback(data)
+
+
+@dataclass(frozen=True)
+class LoggingConfig:
+    """Configures the logging handler set up by Pathway.
+
+    Args:
+        format: ``"text"`` for the log lines meant to be read, ``"json"`` for a JSON
+            object per line, meant for log aggregators. Besides the time, the level,
+            the logger and the message, the objects contain the context of the logs
+            made by the engine: the ``worker_id``, the ``epoch`` the worker processes,
+            and the ``connector`` and the ``operator_id`` the log concerns, if known.
+            The progress dashboard always shows the logs as text.
+        level: the minimal level of the logs, e.g. ``logging.DEBUG`` or ``"DEBUG"``.
+        engine_levels: the levels of the logs of the subsystems of the engine during
+            the run: ``"connectors"``, ``"persistence"`` and ``"dataflow"`` (including
+            the logs of timely and differential dataflow), e.g.
+            ``{"connectors": "DEBUG"}``. The logs of the engine are made by the Python
+            loggers named ``pathway.engine.<subsystem>``, which can be configured with
+            ``logging`` as well. The level ``5`` enables the trace logs.
+    """
+
+    format: Literal["text", "json"] = "text"
+    level: int | str = logging.INFO
+    engine_levels: Mapping[str, int | str] | None = None
+
+    def __post_init__(self) -> None:
+        for subsystem in self.engine_levels or {}:
+            if subsystem not in ENGINE_SUBSYSTEMS:
+                raise ValueError(
+                    f"unknown subsystem of the engine: {subsystem!r},"
+                    + f" expected one of {', '.join(ENGINE_SUBSYSTEMS)}"
+                )
+
+
+@dataclass(frozen=True)
+class AlertThresholds:
+    """Configures the alerts raised when the health of the computation degrades.
+
+    An alert is logged as a warning when any threshold is exceeded and logged again
+    when it's resolved. The active alerts are also reported by the http server, in
+    ``/healthz`` and in the ``alert_active`` and ``alerts_raised_total`` metrics.
+
+    Args:
+        max_output_staleness: the maximal delay between the wall clock and the time
+            the output has advanced to.
+        max_source_lag: the maximal number of the messages (or the files, for the file
+            sources) not read yet by an input connector.
+        max_checkpoint_age: the maximal time since the last checkpoint of the persisted
+            state.
+        webhook_url: if set, the alerts are posted as JSON to this address when raised
+            and when resolved.
+    """
+
+    max_output_staleness: datetime.timedelta | None = None
+    max_source_lag: int | None = None
+    max_checkpoint_age: datetime.timedelta | None = None
+    webhook_url: str | None = None
+
+    def _to_engine(self) -> api.AlertThresholds:
+        def to_ms(duration: datetime.timedelta | None) -> int | None:
+            if duration is None:
+                return None
+            return int(duration.total_seconds() * 1000)
+
+        return api.AlertThresholds(
+            max_output_staleness_ms=to_ms(self.max_output_staleness),
+            max_source_lag=self.max_source_lag,
+            max_checkpoint_age_ms=to_ms(self.max_checkpoint_age),
+            webhook_url=self.webhook_url,
+        )
+
+
+class JsonFormatter(logging.Formatter):
+    """Formats the log records as JSON objects, including the context of the engine
+    attached to the records as their attributes."""
+
+    CONTEXT_ATTRIBUTES = ("worker_id", "epoch", "connector", "operator_id")
+
+    def format(self, record: logging.LogRecord) -> str:
+        entry = {
+            "time": datetime.datetime.fromtimestamp(
+                record.created, tz=datetime.timezone.utc
+            ).isoformat(),
+            "level": record.levelname,
+            "logger": record.name,
+            "message": record.getMessage(),
+        }
+        for attribute in self.CONTEXT_ATTRIBUTES:
+            value = getattr(record, attribute, None)
+            if value is not None:
+                entry[attribute] = value
+        if record.exc_info:
+            entry["exception"] = self.formatException(record.exc_info)
+        return json.dumps(entry, default=str)
+
+
+def _refresh_engine_logging() -> None:
+    names = [ENGINE_LOGGER] + [
+        f"{ENGINE_LOGGER}.{subsystem}" for subsystem in ENGINE_SUBSYSTEMS
+    ]
+    api.refresh_engine_logging(
+        trace=any(logging.getLogger(name).isEnabledFor(TRACE) for name in names)
+    )
+
+
+@contextlib.contextmanager
+def engine_log_levels(engine_levels: Mapping[str, int | str] | None):
+    """Sets the levels of the loggers of the subsystems of the engine for the run and
+    makes the engine use the current levels of its loggers, as it caches them."""
+    engine_levels = engine_levels or {}
+    loggers = {
+        subsystem: logging.getLogger(f"{ENGINE_LOGGER}.{subsystem}")
+        for subsystem in engine_levels
+    }
+    previous_levels = {subsystem: logger.level for subsystem, logger in loggers.items()}
+    for subsystem, logger in loggers.items():
+        logger.setLevel(engine_levels[subsystem])
+    _refresh_engine_logging()
+    try:
+        yield
+    finally:
+        for subsystem, logger in loggers.items():
+            logger.setLevel(previous_levels[subsystem])
+        _refresh_engine_logging()
+
+
 @contextlib.contextmanager
 def monitor_stats(
     monitoring_level: api.MonitoringLevel,
     node_names: list[tuple[int, str]],
     default_logging: bool,
+    logging_config: LoggingConfig | None = None,
     refresh_per_second: int = 4,
 ):
+    if logging_config is None:
+        logging_config = LoggingConfig()
     if monitoring_level != api.MonitoringLevel.ALL:
         node_names = []
     if monitoring_level != api.MonitoringLevel.NONE:
         stats_monitor = StatsMonitor(node_names)
         handler = stats_monitor.get_logging_handler()
-        logging.basicConfig(level=logging.INFO, handlers=[])
+        logging.basicConfig(level=logging_config.level, handlers=[])
         logging.getLogger().addHandler(handler)
         with Live(
             stats_monitor.layout, refresh_per_second=refresh_per_second, screen=True
-        ):
+        ), engine_log_levels(logging_config.engine_levels):
             yield stats_monitor
         logging.getLogger().removeHandler(handler)
     else:
-        if default_logging:
+        if default_logging and logging_config.format == "json":
+            json_handler = logging.StreamHandler()
+            json_handler.setFormatter(JsonFormatter())
+            logging.basicConfig(level=logging_config.level, handlers=[json_handler])
+        elif default_logging:
             logging.basicConfig(
-                level=logging.INFO,
+                level=logging_config.level,
                 format="[%(asctime)s]:%(levelname)s:%(message)s",
                 datefmt="%Y-%m-%dT%H:%M:%S",
             )
-        yield None
+        with engine_log_levels(logging_config.engine_levels):
+            yield None
 
 
 class MonitoringLevel(Enum):
@@ -290,6 +290,7 @@ class IterateOperator(OperatorFromDef):
     """Subscope holding nodes created by iteration logic."""
 
     iteration_limit: int | None
+    fail_on_iteration_limit: bool
 
     iterated: ArgTuple
     iterated_with_universe: ArgTuple
@@ -310,10 +311,12 @@ class IterateOperator(OperatorFromDef):
         id: int,
         scope: Scope,
         iteration_limit: int | None = None,
+        fail_on_iteration_limit: bool = False,
     ):
         super().__init__(func_spec, id)
         self.scope = scope
         self.iteration_limit = iteration_limit
+        self.fail_on_iteration_limit = fail_on_iteration_limit
         self._universe_mapping = defaultdict(Universe)
 
     def __call__(self, **kwargs):
@@ -4,10 +4,11 @@ from __future__ import annotations
 
 import hashlib
 import itertools
+import json
 from collections.abc import Callable
-from typing import TYPE_CHECKING, Any, TypeVar
+from typing import TYPE_CHECKING, Any, Literal, TypeVar
 
-from pathway.internals import operator, trace
+from pathway.internals import datasink, datasource, operator, trace
 from pathway.internals.helpers import FunctionSpec, StableSet
 from pathway.internals.universe_solver import UniverseSolver
 
@@ -103,6 +104,7 @@ class ParseGraph:
         body: FunctionSpec,
         clb: Callable[[operator.IterateOperator], Any],
         iteration_limit: int | None = None,
+        fail_on_iteration_limit: bool = False,
     ):
         """Adds iterate operator.
 
@@ -115,7 +117,11 @@ class ParseGraph:
         """
         iterate_scope = Scope()
         node = operator.IterateOperator(
-            body, next(self.node_id_sequence), iterate_scope, iteration_limit
+            body,
+            next(self.node_id_sequence),
+            iterate_scope,
+            iteration_limit,
+            fail_on_iteration_limit,
         )
         node.set_graph(self)
         self._current_scope.add_node(node)
@@ -138,6 +144,36 @@ class ParseGraph:
     def sig(self):
         return hashlib.sha256(repr(self).encode()).hexdigest()
 
+    def explain(self, format: Literal["json", "dot"] = "json") -> str:
+        """Serializes the operators added so far, allowing to review or visualize
+        the computation before running it.
+
+        Every operator is described with its id, its label, the scope it belongs to
+        (scope 0 is the global one, other scopes are bodies of ``pw.iterate``),
+        the place in user code where it was created and the schemas of its output
+        tables. Connectors additionally have their storage type and persistent id,
+        which identifies their state in a persistence snapshot. Edges connect
+        operators with the operators using their outputs.
+
+        Args:
+            format: ``"json"`` for a JSON document with lists of operators and edges,
+                or ``"dot"`` for a graph in the DOT language, which can be rendered
+                with Graphviz.
+        """
+        operators = []
+        edges = []
+        for scope_index, scope in enumerate(self.scopes):
+            for node in scope.nodes:
+                operators.append(_describe_operator(node, scope_index))
+                for dependency in node.input_operators():
+                    edges.append({"from": dependency.id, "to": node.id})
+        if format == "json":
+            return json.dumps({"operators": operators, "edges": edges}, indent=2)
+        elif format == "dot":
+            return _plan_to_dot(operators, edges)
+        else:
+            raise ValueError(f"unsupported format of the plan: {format}")
+
     def __repr__(self):
         rows = []
         for scope_index, scope in enumerate(self.scopes):
@@ -152,4 +188,74 @@ class ParseGraph:
         )
 
 
+def _describe_operator(node: operator.Operator, scope_index: int) -> dict[str, Any]:
+    user_frame = node.trace.user_frame
+    description: dict[str, Any] = {
+        "id": node.id,
+        "label": node.label(),
+        "kind": type(node).__name__,
+        "scope": scope_index,
+        "trace": (
+            f"{user_frame.filename}:{user_frame.line_number}"
+            if user_frame is not None
+            else None
+        ),
+        "outputs": [
+            {
+                "name": output.name,
+                "schema": {
+                    name: repr(dtype)
+                    for name, dtype in output.value.schema._dtypes().items()
+                },
+            }
+            for output in node.outputs
+        ],
+    }
+    if isinstance(node, operator.InputOperator) and isinstance(
+        node.datasource, datasource.GenericDataSource
+    ):
+        description["storage_type"] = node.datasource.datastorage.storage_type
+        description["persistent_id"] = node.datasource.datastorage.persistent_id
+    if isinstance(node, operator.OutputOperator) and isinstance(
+        node.datasink, datasink.GenericDataSink
+    ):
+        description["storage_type"] = node.datasink.datastorage.storage_type
+    return description
+
+
+def _plan_to_dot(operators: list[dict[str, Any]], edges: list[dict[str, Any]]) -> str:
+    def quote(text: str) -> str:
+        return json.dumps(text, ensure_ascii=False)
+
+    scopes: dict[int, list[str]] = {}
+    for description in operators:
+        label_lines = [f"{description['id']} [{description['label']}]"]
+        if description["trace"] is not None:
+            label_lines.append(description["trace"])
+        for output in description["outputs"]:
+            label_lines.extend(
+                f"{name}: {dtype}" for name, dtype in output["schema"].items()
+            )
+        if description.get("persistent_id") is not None:
+            label_lines.append(f"persistent_id: {description['persistent_id']}")
+        label = quote("\n".join(label_lines))
+        shape = "ellipse" if "storage_type" in description else "box"
+        scopes.setdefault(description["scope"], []).append(
+            f"n{description['id']} [label={label}, shape={shape}];"
+        )
+
+    lines = ["digraph {"]
+    for scope_index, nodes in scopes.items():
+        if scope_index == 0:
+            lines.extend(f"  {node}" for node in nodes)
+        else:
+            lines.append(f"  subgraph cluster_{scope_index} {{")
+            lines.append(f'    label="iterate scope {scope_index}";')
+            lines.extend(f"    {node}" for node in nodes)
+            lines.append("  }")
+    lines.extend(f"  n{edge['from']} -> n{edge['to']};" for edge in edges)
+    lines.append("}")
+    return "\n".join(lines)
+
+
 G = ParseGraph()
@@ -4,6 +4,8 @@ from __future__ import annotations
 
 import builtins
 from abc import ABC, abstractmethod
+from collections.abc import Callable
+from typing import Any
 from warnings import warn
 
 import numpy as np
@@ -11,6 +13,7 @@ import numpy as np
 from pathway.internals import api, dtype as dt, expression as expr
 from pathway.internals.column import ColumnExpression, GroupedContext
 from pathway.internals.common import apply_with_type
+from pathway.internals.shadows.inspect import signature
 
 
 class Reducer(ABC):
@@ -178,6 +181,28 @@ class StatefulManyReducer(Reducer):
         return api.Reducer.stateful_many(self.combine_many)
 
 
+class RetractableReducer(Reducer):
+    name = "retractable"
+
+    def __init__(
+        self,
+        init: Callable[[], Any],
+        add: Callable[..., Any],
+        retract: Callable[..., Any],
+        extract: Callable[[Any], api.Value],
+    ):
+        self.init = init
+        self.add = add
+        self.retract = retract
+        self.extract = extract
+
+    def return_type(self, arg_types: list[dt.DType]) -> dt.DType:
+        return dt.wrap(signature(self.extract).return_annotation)
+
+    def engine_reducer(self, arg_types: list[dt.DType]) -> api.Reducer:
+        return api.Reducer.retractable(self.init, self.add, self.retract, self.extract)
+
+
 _min = TypePreservingUnaryReducer(name="min", engine_reducer=api.Reducer.MIN)
 _max = TypePreservingUnaryReducer(name="max", engine_reducer=api.Reducer.MAX)
 _sum = SumReducer(name="sum")
@@ -0,0 +1,187 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import dataclasses
+import operator
+from collections.abc import Callable, Sequence
+from typing import Any, Generic, TypeVar, get_type_hints, is_typeddict
+
+from pathway.internals import dtype as dt
+from pathway.internals.schema import Schema, column_definition, schema_builder
+
+T = TypeVar("T")
+
+
+def _fields(row_type: type) -> dict[str, tuple[Any, Any]]:
+    """Returns the type hints of the fields of the row type and their defaults."""
+    hints = get_type_hints(row_type)
+    if dataclasses.is_dataclass(row_type):
+        fields = {}
+        for field in dataclasses.fields(row_type):
+            if not field.init:
+                raise TypeError(
+                    f"field {field.name!r} of {row_type.__qualname__} is not set"
+                    + " by its constructor"
+                )
+            if field.default is not dataclasses.MISSING:
+                default = field.default
+            elif field.default_factory is not dataclasses.MISSING:
+                default = field.default_factory()
+            else:
+                default = dataclasses.MISSING
+            fields[field.name] = (hints[field.name], default)
+        return fields
+    if is_typeddict(row_type):
+        return {name: (hint, dataclasses.MISSING) for name, hint in hints.items()}
+    raise TypeError(f"{row_type.__qualname__} is neither a dataclass nor a TypedDict")
+
+
+def schema_from_row_type(row_type: type) -> type[Schema]:
+    """Constructs the schema with the columns of the fields of a dataclass or a
+    ``TypedDict``, of the types of the fields. The default values of the fields of
+    a dataclass are the default values of the columns.
+
+    Example:
+
+    >>> import dataclasses
+    >>> import pathway as pw
+    >>> @dataclasses.dataclass
+    ... class Point:
+    ...     x: int
+    ...     y: float = 0.0
+    ...
+    >>> pw.schema_from_row_type(Point)
+    <pathway.Schema types={'x': <class 'int'>, 'y': <class 'float'>}>
+    """
+    columns = {}
+    for name, (hint, default) in _fields(row_type).items():
+        if default is dataclasses.MISSING:
+            columns[name] = column_definition(dtype=hint)
+        else:
+            columns[name] = column_definition(dtype=hint, default_value=default)
+    return schema_builder(columns=columns, name=row_type.__qualname__)
+
+
+def _value_converter(dtype: dt.DType) -> Callable[[Any], Any] | None:
+    # the integers in the float columns are stored as floats, like by the connectors
+    if dtype == dt.FLOAT:
+        return float
+    if dtype == dt.Optional(dt.FLOAT):
+        return lambda value: value if value is None else float(value)
+    return None
+
+
+class RowConverter(Generic[T]):
+    """Converts the rows of a table, i.e. the lists of the values of its columns, to
+    the objects of a dataclass or a ``TypedDict`` with the fields named as the columns,
+    and back.
+
+    Everything depending on the row type, like the order of the fields and the types
+    of the values, is found once, when the converter is created, so converting a row
+    costs about as much as constructing an object. The values of the objects converted
+    to rows are checked against the types of the fields. The rows converted to objects
+    are not checked, as the types of the columns are checked against the types
+    of the fields when the converter is created.
+
+    Args:
+        row_type: the dataclass or the ``TypedDict``.
+        schema: the schema of the rows, defaults to the schema of the row type. The
+            columns of the schema have to be the fields of the row type, in any order,
+            with the types of the fields or their subtypes.
+
+    Example:
+
+    >>> import dataclasses
+    >>> import pathway as pw
+    >>> @dataclasses.dataclass
+    ... class Point:
+    ...     x: int
+    ...     y: int
+    ...
+    >>> t = pw.debug.table_from_markdown('''
+    ... y | x
+    ... 2 | 1
+    ... ''')
+    >>> converter = pw.RowConverter(Point, t.schema)
+    >>> converter.from_values([2, 1])
+    Point(x=1, y=2)
+    >>> converter.to_values(Point(x=3, y=4))
+    [4, 3]
+    """
+
+    _make: Callable[[Sequence[Any]], Any]
+    _get: Callable[[Any], Sequence[Any]]
+
+    def __init__(self, row_type: type[T], schema: type[Schema] | None = None) -> None:
+        row_schema = schema_from_row_type(row_type)
+        if schema is None:
+            schema = row_schema
+        field_names = row_schema.column_names()
+        column_names = schema.column_names()
+        if set(column_names) != set(field_names):
+            raise ValueError(
+                f"the columns {column_names} don't match the fields {field_names}"
+                + f" of {row_type.__qualname__}"
+            )
+        field_dtypes = row_schema._dtypes()
+        column_dtypes = schema._dtypes()
+        for name in column_names:
+            if not dt.dtype_issubclass(column_dtypes[name], field_dtypes[name]):
+                raise TypeError(
+                    f"the type {column_dtypes[name]} of the column {name!r} doesn't"
+                    + f" match the type {field_dtypes[name]} of the field"
+                    + f" of {row_type.__qualname__}"
+                )
+
+        self.row_type = row_type
+        self.schema = schema
+        self._column_names = column_names
+        # the positions of the fields in the rows
+        self._positions = [column_names.index(name) for name in field_names]
+        self._columns_in_field_order = self._positions == list(range(len(field_names)))
+        self._checks = [
+            (name, column_dtypes[name], _value_converter(column_dtypes[name]))
+            for name in column_names
+        ]
+        if dataclasses.is_dataclass(row_type):
+            if any(field.kw_only for field in dataclasses.fields(row_type)):
+                self._make = lambda values: row_type(**dict(zip(field_names, values)))
+            else:
+                self._make = lambda values: row_type(*values)
+            getter = operator.attrgetter(*column_names)
+        else:
+            self._make = lambda values: dict(zip(field_names, values))
+            getter = operator.itemgetter(*column_names)
+        if len(column_names) == 1:
+            self._get = lambda row: (getter(row),)
+        else:
+            self._get = getter
+
+    def from_values(self, values: Sequence[Any]) -> T:
+        """Converts the values of the columns of a row to an object of the row type."""
+        if self._columns_in_field_order:
+            return self._make(values)
+        return self._make([values[position] for position in self._positions])
+
+    def to_values(self, row: T) -> list[Any]:
+        """Converts an object of the row type to the values of the columns of a row,
+        checking them against the types of the columns."""
+        values = list(self._get(row))
+        for index, (value, (name, dtype, converter)) in enumerate(
+            zip(values, self._checks)
+        ):
+            if not dtype.is_value_compatible(value):
+                raise TypeError(
+                    f"the value {value!r} of the field {name!r} of"
+                    + f" {self.row_type.__qualname__} is not of the type {dtype}"
+                )
+            if converter is not None:
+                values[index] = converter(value)
+        return values
+
+    def from_dict(self, row: dict[str, Any]) -> T:
+        """Converts a row given as a dict from the column names to the values, like
+        the ones passed to the callbacks of ``pw.io.subscribe``, to an object of
+        the row type."""
+        return self.from_values([row[name] for name in self._column_names])
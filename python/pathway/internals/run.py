@@ -1,9 +1,20 @@
 # Copyright © 2024 Pathway
 
+import contextlib
+import datetime
+import threading
+import time
+from dataclasses import dataclass, field
+from typing import Any, Literal
 
-from pathway.internals import parse_graph
+from pathway.internals import api, parse_graph
 from pathway.internals.graph_runner import GraphRunner
-from pathway.internals.monitoring import MonitoringLevel
+from pathway.internals.graph_runner.shutdown import shutdown_on_sigterm
+from pathway.internals.monitoring import (
+    AlertThresholds,
+    LoggingConfig,
+    MonitoringLevel,
+)
 from pathway.internals.runtime_type_check import check_arg_types
 from pathway.persistence import Config as PersistenceConfig
 
@@ -15,6 +26,13 @@ def run(
     with_http_server: bool = False,
     default_logging: bool = True,
     persistence_config: PersistenceConfig | None = None,
+    batch_latency_target: datetime.timedelta | None = None,
+    operator_state_limit: int | None = None,
+    on_operator_state_limit: Literal["warn", "raise"] = "warn",
+    consistent_outputs: bool = False,
+    otlp_endpoint: str | None = None,
+    logging_config: LoggingConfig | None = None,
+    alert_thresholds: AlertThresholds | None = None,
 ):
     """Runs the computation graph.
 
@@ -26,10 +44,56 @@ def run(
             NONE and IN_OUT based on output interactivity.
         with_http_server: whether to start a http server with runtime metrics. Learn
             more in a `tutorial </developers/tutorials/prometheus-monitoring/>`_ .
+            The server requires bearer tokens if ``PATHWAY_MONITORING_HTTP_TOKENS``
+            is set to ``NAME:TOKEN`` pairs and uses TLS if
+            ``PATHWAY_MONITORING_HTTP_TLS_CERT`` and ``PATHWAY_MONITORING_HTTP_TLS_KEY``
+            are set, verifying the client certificates against
+            ``PATHWAY_MONITORING_HTTP_TLS_CLIENT_CA`` if it's set as well.
+            ``PATHWAY_MONITORING_HTTP_AUTHORIZATION``, e.g.
+            ``/shutdown=admin;/healthz=*``, limits the routes to the listed clients.
         default_logging: whether to allow pathway to set its own logging handler. Set
             it to False if you want to set your own logging handler.
         persistence_config: the config for persisting the state in case this
-            persistence is required.
+            persistence is required. If the keys are derived with SipHash, i.e.
+            ``PATHWAY_KEY_HASHING`` is ``siphash``, its seed has to be set in
+            ``PATHWAY_KEY_HASHING_SEED``, so that the keys are the same in every run.
+        batch_latency_target: if set, rows are passed to Python functions in batches,
+            and the GIL is released between them. Batch sizes grow while the input
+            keeps up and shrink so that the 99th percentile of the processing times
+            of batches stays below this target. Defaults to None, indicating that all
+            available rows are processed at once.
+        operator_state_limit: the limit, in bytes, of the approximate size of the state
+            kept by a single operator in a single worker. Defaults to None, indicating
+            no limit. The approximate state sizes are available in the stats of
+            operators when monitoring all operators.
+        on_operator_state_limit: what happens when an operator exceeds
+            ``operator_state_limit``. If ``"warn"``, a warning is logged. If
+            ``"raise"``, the computation stops with an error.
+        consistent_outputs: if set, every output (an output connector or a
+            ``pw.io.subscribe`` callback) gets the data for a given time only once the
+            data for this time is complete in all outputs. Then no output gets ahead
+            of the others, e.g. a dashboard reading two output tables never sees
+            one of them updated with changes the other one cannot have yet. The
+            outputs are as late as the slowest one. Defaults to False.
+        otlp_endpoint: if set, traces of the computation are exported to the OpenTelemetry
+            collector at this address (e.g. ``"http://localhost:4317"``) using
+            OTLP over gRPC. There are spans of the batches of the input connectors, of
+            the processing of each time by a worker, and of the checkpoints of the
+            persisted state. With ``monitoring_level`` set to
+            ``pathway.MonitoringLevel.ALL``, the processing of a time by each operator has
+            a span as well. All of them carry the time they concern in the
+            ``pathway.epoch`` attribute. The
+            service is named after the ``OTEL_SERVICE_NAME`` environment variable,
+            ``pathway`` by default. Defaults to None, indicating no export.
+        logging_config: the format and the level of the logs written by the logging
+            handler set up with ``default_logging``, e.g.
+            ``pw.LoggingConfig(format="json")`` for log aggregators, and the levels
+            of the logs of the subsystems of the engine. Defaults to None,
+            indicating text logs of the INFO level.
+        alert_thresholds: the limits of the health of the computation, e.g.
+            ``pw.AlertThresholds(max_output_staleness=datetime.timedelta(minutes=1))``.
+            The alerts raised when they are exceeded are logged and reported by the
+            http server. Defaults to None, indicating no alerts.
     """
     GraphRunner(
         parse_graph.G,
@@ -38,6 +102,13 @@ def run(
         with_http_server=with_http_server,
         default_logging=default_logging,
         persistence_config=persistence_config,
+        batch_latency_target=batch_latency_target,
+        operator_state_limit=operator_state_limit,
+        on_operator_state_limit=on_operator_state_limit,
+        consistent_outputs=consistent_outputs,
+        otlp_endpoint=otlp_endpoint,
+        logging_config=logging_config,
+        alert_thresholds=alert_thresholds,
     ).run_outputs()
 
 
@@ -57,3 +128,165 @@ def run_all(
         default_logging=default_logging,
         runtime_typechecking=runtime_typechecking,
     ).run_all()
+
+
+@dataclass(frozen=True)
+class RunStats:
+    """The statistics of a run of the computation, as of its end.
+
+    Attributes:
+        duration: the time the computation ran for.
+        cancelled: whether the computation was stopped before its inputs ended.
+        input_messages: the numbers of the messages read by the input connectors,
+            by their names.
+        output_rows: the numbers of the rows emitted by the outputs, by their names.
+        checkpoints: the number of the checkpoints of the persisted state.
+        failed_checkpoints: the number of the checkpoints that failed.
+    """
+
+    duration: datetime.timedelta
+    cancelled: bool
+    input_messages: dict[str, int] = field(default_factory=dict)
+    output_rows: dict[str, int] = field(default_factory=dict)
+    checkpoints: int = 0
+    failed_checkpoints: int = 0
+
+    @classmethod
+    def _from_engine(
+        cls, stats: Any, duration: datetime.timedelta, cancelled: bool
+    ) -> "RunStats":
+        if stats is None:
+            return cls(duration=duration, cancelled=cancelled)
+        persistence_stats = stats.persistence_stats
+        return cls(
+            duration=duration,
+            cancelled=cancelled,
+            input_messages={
+                name: connector_stats.num_messages_from_start
+                for name, connector_stats in stats.connector_stats
+            },
+            output_rows={
+                name: histogram.count for name, histogram in stats.output_latencies
+            },
+            checkpoints=(
+                persistence_stats.checkpoints if persistence_stats is not None else 0
+            ),
+            failed_checkpoints=(
+                persistence_stats.failed_checkpoints
+                if persistence_stats is not None
+                else 0
+            ),
+        )
+
+
+class RunContext:
+    """Runs the computation graph in the background for the duration of a ``with``
+    block, see ``pw.run_context``."""
+
+    def __init__(self, **kwargs: Any) -> None:
+        self._kwargs = kwargs
+        self._shutdown_handle = api.ShutdownHandle()
+        self._last_stats: Any = None
+        self._error: BaseException | None = None
+        self._cancelled = False
+        self._start = 0.0
+        self._exit_stack = contextlib.ExitStack()
+        self._thread = threading.Thread(target=self._run, name="pathway:run")
+        self.stats: RunStats | None = None
+        """The statistics of the run, set once it's finished."""
+
+    def _on_stats(self, stats: Any) -> None:
+        self._last_stats = stats
+
+    def _run(self) -> None:
+        try:
+            GraphRunner(
+                parse_graph.G,
+                shutdown_handle=self._shutdown_handle,
+                on_stats=self._on_stats,
+                **self._kwargs,
+            ).run_outputs()
+        except BaseException as e:
+            self._error = e
+
+    def cancel(self) -> None:
+        """Stops the computation gracefully: the data read so far is processed and
+        written by the outputs and, with persistence, the final checkpoint is made.
+        Doesn't wait for the computation to finish."""
+        self._cancelled = True
+        self._shutdown_handle.request()
+
+    def wait(self, timeout: float | None = None) -> bool:
+        """Waits for the computation to finish, at most ``timeout`` seconds if it's set.
+        Returns whether the computation is finished."""
+        self._thread.join(timeout)
+        return not self._thread.is_alive()
+
+    def __enter__(self) -> "RunContext":
+        # the computation stops gracefully on SIGTERM, as with ``pw.run``,
+        # although it's not run in the main thread
+        self._exit_stack.enter_context(shutdown_on_sigterm(self._shutdown_handle))
+        self._start = time.monotonic()
+        self._thread.start()
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback) -> bool:
+        interrupted = exc_type is not None and issubclass(exc_type, KeyboardInterrupt)
+        if exc_type is not None:
+            self.cancel()
+        try:
+            while True:
+                try:
+                    self._thread.join()
+                    break
+                except KeyboardInterrupt:
+                    if self._cancelled:
+                        # interrupted again while draining, so not waiting any longer
+                        raise
+                    self.cancel()
+                    interrupted = True
+        finally:
+            self._exit_stack.close()
+            self.stats = RunStats._from_engine(
+                self._last_stats,
+                duration=datetime.timedelta(seconds=time.monotonic() - self._start),
+                cancelled=self._cancelled,
+            )
+        if self._error is not None and exc_type is None:
+            raise self._error
+        # the interruption only cancels the computation
+        return interrupted
+
+
+def run_context(**kwargs: Any) -> RunContext:
+    """Runs the computation graph in the background while the ``with`` block is
+    executed, and waits for it to finish at the end of the block.
+
+    A ``KeyboardInterrupt``, e.g. on Ctrl+C, in the block or while waiting for
+    the computation cancels it gracefully, like ``RunContext.cancel``: the data read
+    so far is processed and written by the outputs and, with persistence, the final
+    checkpoint is made. Then the block is exited without the exception. A second
+    interruption while waiting stops waiting. Any other exception in the block cancels
+    the computation the same way and is raised once it's finished. The connectors and
+    the persistence are always closed at the exit of the block, and the statistics
+    of the run are in ``RunContext.stats``.
+
+    Args:
+        kwargs: the arguments of ``pw.run``.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> t = pw.debug.table_from_markdown('''
+    ... a
+    ... 1
+    ... 2
+    ... ''')
+    >>> pw.io.subscribe(t, on_change=lambda key, row, time, is_addition: None)
+    >>> with pw.run_context(monitoring_level=pw.MonitoringLevel.NONE) as run:
+    ...     run.wait()
+    True
+    >>> run.stats.cancelled
+    False
+    """
+    return RunContext(**kwargs)
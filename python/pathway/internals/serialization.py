@@ -0,0 +1,106 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+from collections.abc import Callable
+from dataclasses import dataclass
+from typing import Any
+
+from pathway.internals.runtime_type_check import check_arg_types
+
+
+@dataclass(frozen=True)
+class _Serializer:
+    name: str
+    serialize: Callable[[Any], bytes]
+    deserialize: Callable[[bytes], Any]
+
+
+_serializers_by_type: dict[type, _Serializer] = {}
+_serializers_by_name: dict[str, _Serializer] = {}
+
+
+@check_arg_types
+def register_serializer(
+    cls: type,
+    serialize: Callable[[Any], bytes],
+    deserialize: Callable[[bytes], Any],
+    *,
+    name: str | None = None,
+) -> None:
+    """Registers the functions converting the objects of a class to bytes and back,
+    so that the objects can be values of the columns of tables.
+
+    The objects of the classes not supported by Pathway are carried by the engine
+    serialized with the serializer registered for their class (or for its closest
+    base class), e.g. when they are passed between the workers or persisted. They are
+    deserialized each time they are passed to a Python function. Registering
+    a serializer for ``object`` makes the objects of all the classes without their own
+    serializers supported.
+
+    Args:
+        cls: the class of the objects.
+        serialize: the function converting an object to bytes.
+        deserialize: the function converting bytes back to an object.
+        name: the name identifying the serializer in the serialized objects. The
+            objects are deserialized with the serializer of the same name, so the name,
+            as well as the serialized form, has to stay the same as long as the objects
+            are persisted. Defaults to the qualified name of the class.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> class Point:
+    ...     def __init__(self, x, y):
+    ...         self.x = x
+    ...         self.y = y
+    ...
+    >>> pw.register_serializer(
+    ...     Point,
+    ...     lambda point: f"{point.x},{point.y}".encode(),
+    ...     lambda data: Point(*map(int, data.decode().split(","))),
+    ... )
+    >>> t1 = pw.debug.table_from_markdown('''
+    ... x | y
+    ... 1 | 2
+    ... 3 | 4
+    ... ''')
+    >>> t2 = t1.select(point=pw.apply(Point, pw.this.x, pw.this.y))
+    >>> t3 = t2.select(sum=pw.apply_with_type(lambda p: p.x + p.y, int, pw.this.point))
+    >>> pw.debug.compute_and_print(t3, include_id=False)
+    sum
+    3
+    7
+    """
+    if name is None:
+        name = f"{cls.__module__}.{cls.__qualname__}"
+    existing = _serializers_by_name.get(name)
+    if existing is not None and _serializers_by_type.get(cls) is not existing:
+        raise ValueError(f"serializer {name!r} is already registered for another class")
+    serializer = _Serializer(name=name, serialize=serialize, deserialize=deserialize)
+    previous = _serializers_by_type.get(cls)
+    if previous is not None:
+        del _serializers_by_name[previous.name]
+    _serializers_by_type[cls] = serializer
+    _serializers_by_name[name] = serializer
+
+
+def _serialize(obj: Any) -> tuple[str, bytes] | None:
+    for cls in type(obj).__mro__:
+        serializer = _serializers_by_type.get(cls)
+        if serializer is not None:
+            data = serializer.serialize(obj)
+            if not isinstance(data, bytes):
+                raise TypeError(
+                    f"serializer {serializer.name!r} returned {type(data).__name__}"
+                    + " instead of bytes"
+                )
+            return serializer.name, data
+    return None
+
+
+def _deserialize(name: str, data: bytes) -> Any:
+    serializer = _serializers_by_name.get(name)
+    if serializer is None:
+        raise ValueError(f"no serializer {name!r} is registered")
+    return serializer.deserialize(data)
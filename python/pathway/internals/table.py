@@ -9,7 +9,14 @@ from typing import TYPE_CHECKING, Any, Generic, TypeVar, cast, overload
 
 import pathway.internals.column as clmn
 import pathway.internals.expression as expr
-from pathway.internals import dtype as dt, groupbys, thisclass, universes
+from pathway.internals import (
+    api,
+    dtype as dt,
+    environ,
+    groupbys,
+    thisclass,
+    universes,
+)
 from pathway.internals.api import Value
 from pathway.internals.arg_handlers import (
     arg_handler,
@@ -27,6 +34,7 @@ from pathway.internals.desugaring import (
     combine_args_kwargs,
     desugar,
 )
+from pathway.internals.expression_fusion import fuse_expression
 from pathway.internals.expression_visitor import collect_tables
 from pathway.internals.helpers import SetOnceProperty, StableSet
 from pathway.internals.joins import Joinable, JoinResult
@@ -85,6 +93,9 @@ class Table(
             interval_join_left,
             interval_join_outer,
             interval_join_right,
+            range_join,
+            range_join_inner,
+            range_join_left,
             window_join,
             window_join_inner,
             window_join_left,
@@ -628,6 +639,35 @@ class Table(
         context = clmn.ForgetImmediatelyContext(self._id_column)
         return self._table_with_context(context)
 
+    @trace_user_frame
+    @desugar
+    @check_arg_types
+    @contextualized_operator
+    def _expire_after(
+        self,
+        duration: int,
+    ) -> Table:
+        context = clmn.ExpireAfterContext(self._id_column, duration)
+        return self._table_with_context(context)
+
+    @trace_user_frame
+    @desugar
+    @check_arg_types
+    @contextualized_operator
+    def _deduplicate_within(
+        self,
+        fingerprint_column: expr.ColumnExpression,
+        time_column: expr.ColumnExpression,
+        expiration_column: expr.ColumnExpression,
+    ) -> Table:
+        context = clmn.DeduplicateWithinContext(
+            self._id_column,
+            self._eval(fingerprint_column),
+            self._eval(time_column),
+            self._eval(expiration_column),
+        )
+        return self._table_with_context(context)
+
     @trace_user_frame
     @desugar
     @check_arg_types
@@ -657,6 +697,23 @@ class Table(
         )
         return self._table_with_context(context)
 
+    @trace_user_frame
+    @desugar
+    @check_arg_types
+    @contextualized_operator
+    def _freeze_late(
+        self,
+        threshold_column: expr.ColumnExpression,
+        time_column: expr.ColumnExpression,
+    ) -> Table:
+        # Returns exactly the entries that `_freeze` with the same arguments ignores.
+        context = clmn.FreezeLateContext(
+            self._id_column,
+            self._eval(threshold_column),
+            self._eval(time_column),
+        )
+        return self._table_with_context(context)
+
     @trace_user_frame
     @desugar
     @check_arg_types
@@ -673,6 +730,60 @@ class Table(
         )
         return self._table_with_context(context)
 
+    @trace_user_frame
+    @desugar
+    @check_arg_types
+    @contextualized_operator
+    def reshard_by(
+        self,
+        *args: expr.ColumnExpression,
+        parallelism: int | None = None,
+    ) -> Table[TSchema]:
+        """Redistributes the rows of the table among workers according to the values
+        of given expressions. Rows with equal values end up on the same worker.
+
+        Stateless operations applied to the result (like ``select``, ``filter`` or
+        ``flatten``) run on the workers holding the rows, so resharding allows to
+        spread an expensive computation evenly, e.g. when the rows are read by a
+        single worker. Operations grouping or joining the rows distribute them
+        according to their own keys again.
+
+        Args:
+            args: expressions defining the distribution. If none are given, rows are
+                distributed by their ids.
+            parallelism: if set, only the first ``parallelism`` workers get the rows.
+                Has to be positive.
+
+        Returns:
+            Table: table with the same rows and columns.
+
+        Example:
+
+        >>> import pathway as pw
+        >>> t1 = pw.debug.table_from_markdown('''
+        ... owner | pet
+        ... Alice | dog
+        ... Bob   | cat
+        ... Alice | cat
+        ... ''')
+        >>> t2 = t1.reshard_by(t1.owner, parallelism=2)
+        >>> pw.debug.compute_and_print(t2, include_id=False)
+        owner | pet
+        Alice | cat
+        Alice | dog
+        Bob   | cat
+        """
+        if parallelism is not None and parallelism <= 0:
+            raise ValueError("parallelism has to be positive.")
+        context = clmn.ReshardContext(
+            self._id_column,
+            tuple(self._eval(arg) for arg in args),
+            parallelism,
+        )
+        result = self._table_with_context(context)
+        G.universe_solver.register_as_equal(self._universe, result._universe)
+        return result
+
     @contextualized_operator
     @check_arg_types
     def difference(self, other: Table) -> Table[TSchema]:
@@ -1852,6 +1963,58 @@ class Table(
             _context=context,
         )
 
+    @trace_user_frame
+    @check_arg_types
+    def changelog(self) -> Table:
+        """Returns the changes of the table as an append-only table.
+
+        Every change of a row becomes a separate row of the result, with the values
+        of all columns, the ``time`` of the change and its ``diff``: ``1`` for an
+        insertion and ``-1`` for a deletion. An update of a row is a deletion of the
+        old values and an insertion of the new ones at the same time. The result can
+        be written with any output connector to keep the raw update stream.
+
+        Example:
+
+        >>> import pathway as pw
+        >>> t1 = pw.debug.table_from_markdown('''
+        ...   | pet | age | __time__ | __diff__
+        ... 1 | Dog | 2   | 2        | 1
+        ... 7 | Cat | 5   | 2        | 1
+        ... 1 | Dog | 2   | 4        | -1
+        ... 1 | Dog | 3   | 4        | 1
+        ... ''')
+        >>> t2 = t1.changelog()
+        >>> pw.debug.compute_and_print(t2, include_id=False)
+        pet | age | time | diff
+        Cat | 5   | 2    | 1
+        Dog | 2   | 2    | 1
+        Dog | 2   | 4    | -1
+        Dog | 3   | 4    | 1
+        """
+        for name in ("time", "diff"):
+            if name in self._columns:
+                raise ValueError(
+                    f"Table.changelog() cannot be used on a table with column {name!r}."
+                )
+        return self._changelog()
+
+    @contextualized_operator
+    def _changelog(self) -> Table:
+        context = clmn.ChangelogContext(orig_universe=self._universe)
+        columns = {
+            name: self._wrap_column_in_context(context, column, name)
+            for name, column in self._columns.items()
+        }
+        return Table(
+            _columns={
+                **columns,
+                "time": context.time_column,
+                "diff": context.diff_column,
+            },
+            _context=context,
+        )
+
     @trace_user_frame
     @desugar
     @contextualized_operator
@@ -1924,6 +2087,205 @@ class Table(
             _context=context,
         )
 
+    @trace_user_frame
+    @desugar
+    @check_arg_types
+    def top_n(
+        self,
+        key: expr.ColumnExpression,
+        n: int,
+        *,
+        instance: expr.ColumnExpression | None = None,
+        descending: bool = False,
+        with_position: bool = False,
+    ) -> Table:
+        """Keeps the ``n`` rows with the smallest values of ``key``.
+
+        The result is maintained incrementally: inserting or deleting a row updates
+        only the rows entering or leaving the top ``n``, without sorting the whole
+        table again. Ties are broken by row ids, in the ascending order also when
+        ``descending`` is set, the same as in the ranks of the rows.
+
+        Args:
+            key: An expression to sort by.
+            n: The number of rows to keep. Has to be positive.
+            instance: An expression with instance. If set, ``n`` rows are kept within
+                every instance.
+            descending: If True, keeps the rows with the largest values of ``key``.
+            with_position: If True, adds a ``position`` column containing the position
+                of a row in the order, starting from 0. Positions are updated whenever
+                rows enter or leave the top ``n``, so the result contains the whole
+                ordered prefix.
+
+        Returns:
+            Table: The rows of ``self`` among the first ``n`` in the order.
+
+        Example:
+
+        >>> import pathway as pw
+        >>> table = pw.debug.table_from_markdown('''
+        ... name    | team | score
+        ... Alice   | A    | 80
+        ... Bob     | A    | 90
+        ... Charlie | B    | 70
+        ... David   | A    | 85
+        ... Eve     | B    | 95
+        ... ''')
+        >>> best = table.top_n(pw.this.score, 2, descending=True, with_position=True)
+        >>> pw.debug.compute_and_print(best, include_id=False)
+        name  | team | score | position
+        Bob   | A    | 90    | 1
+        Eve   | B    | 95    | 0
+        >>> best_in_team = table.top_n(
+        ...     pw.this.score, 1, instance=pw.this.team, descending=True
+        ... )
+        >>> pw.debug.compute_and_print(best_in_team, include_id=False)
+        name | team | score
+        Bob  | A    | 90
+        Eve  | B    | 95
+        """
+        if n <= 0:
+            raise ValueError("n has to be positive.")
+        ranked = self._top_n(key, n, instance=instance, descending=descending)
+        result = self.restrict(ranked)
+        if with_position:
+            result = result.with_columns(position=ranked._pw_position)
+        return result
+
+    @trace_user_frame
+    @desugar
+    @contextualized_operator
+    @check_arg_types
+    def _top_n(
+        self,
+        key: expr.ColumnExpression,
+        n: int,
+        instance: expr.ColumnExpression | None = None,
+        descending: bool = False,
+    ) -> Table:
+        instance = clmn.ColumnExpression._wrap(instance)
+        context = clmn.TopNContext(
+            self._eval(key),
+            self._eval(instance),
+            n,
+            descending,
+        )
+        return Table(
+            _columns={"_pw_position": context.position_column},
+            _context=context,
+        )
+
+    @trace_user_frame
+    @desugar
+    @contextualized_operator
+    @check_arg_types
+    def _rank(
+        self,
+        key: expr.ColumnExpression,
+        instance: expr.ColumnExpression | None = None,
+        descending: bool = False,
+    ) -> Table:
+        """Numbers rows in the order of ``key`` within every instance.
+
+        Returns a table with columns ``row_number``, ``rank`` and ``dense_rank``,
+        all starting from 1.
+        """
+        instance = clmn.ColumnExpression._wrap(instance)
+        context = clmn.RankContext(
+            self._eval(key),
+            self._eval(instance),
+            descending,
+        )
+        return Table(
+            _columns={
+                "row_number": context.row_number_column,
+                "rank": context.rank_column,
+                "dense_rank": context.dense_rank_column,
+            },
+            _context=context,
+        )
+
+    @trace_user_frame
+    @desugar
+    @contextualized_operator
+    @check_arg_types
+    def _cumulative(
+        self,
+        key: expr.ColumnExpression,
+        instance: expr.ColumnExpression | None = None,
+        **reducers: expr.ColumnExpression,
+    ) -> Table:
+        """Computes running aggregates in the order of ``key`` within every instance.
+
+        The aggregate of a row covers the row and all rows preceding it. Only
+        ``pw.reducers.count``, ``pw.reducers.sum``, ``pw.reducers.min`` and
+        ``pw.reducers.max`` are supported.
+        """
+        instance = clmn.ColumnExpression._wrap(instance)
+        aggregates = []
+        for name, reducer_expression in reducers.items():
+            if isinstance(reducer_expression, expr.CountExpression):
+                aggregates.append((api.Reducer.COUNT, None, dt.INT))
+            elif isinstance(
+                reducer_expression, expr.ReducerExpression
+            ) and reducer_expression._reducer.name in ("sum", "min", "max"):
+                [arg] = reducer_expression._args
+                arg_type = self.eval_type(arg)
+                reducer = reducer_expression._reducer
+                aggregates.append(
+                    (
+                        reducer.engine_reducer([arg_type]),
+                        self._eval(arg),
+                        reducer.return_type([arg_type]),
+                    )
+                )
+            else:
+                raise ValueError(
+                    f"{name}: only count, sum, min and max reducers can be computed"
+                    + " cumulatively."
+                )
+        context = clmn.CumulativeContext(
+            self._eval(key),
+            self._eval(instance),
+            tuple(aggregates),
+        )
+        return Table(
+            _columns=dict(zip(reducers, context.aggregate_columns)),
+            _context=context,
+        )
+
+    @trace_user_frame
+    @desugar
+    @contextualized_operator
+    @check_arg_types
+    def _session_windows(
+        self,
+        key: expr.ColumnExpression,
+        max_gap: Any,
+        instance: expr.ColumnExpression | None = None,
+    ) -> Table:
+        """Assigns rows to sessions. Two consecutive (with respect to ``key``) rows
+        of the same instance belong to the same session if ``next - prev < max_gap``.
+
+        Returns a table with columns ``_pw_window``, ``_pw_window_start`` and
+        ``_pw_window_end`` containing the session id and the smallest and the largest
+        key in the session.
+        """
+        instance = clmn.ColumnExpression._wrap(instance)
+        context = clmn.SessionWindowContext(
+            self._eval(key),
+            self._eval(instance),
+            max_gap,
+        )
+        return Table(
+            _columns={
+                "_pw_window": context.window_column,
+                "_pw_window_start": context.window_start_column,
+                "_pw_window_end": context.window_end_column,
+            },
+            _context=context,
+        )
+
     def _set_source(self, source: OutputHandle):
         self._source = source
         if not hasattr(self._id_column, "lineage"):
@@ -1988,6 +2350,8 @@ class Table(
         """Desugar expression and wrap it in given context."""
         if context is None:
             context = self._rowwise_context
+        if environ.expression_fusion and type(context) is clmn.RowwiseContext:
+            expression = fuse_expression(expression, context.universe)
         column = expression._column_with_expression_cls(
             context=context,
             universe=context.universe,
@@ -75,6 +75,26 @@ class OnTimeEndCallback(Protocol):
         ...
 
 
+class OnCommitCallback(Protocol):
+    """
+    The callback to be called once all the changes at times before the given one are
+    passed to the other callbacks, in all the workers. It is called in a single worker
+    and is required to accept one parameter: time.
+    """
+
+    def __call__(self, time: int) -> None:
+        """
+        The callable part of the callback.
+
+        Args:
+            time: the new frontier of the computation, i.e. the changes at earlier
+                times are all delivered and no more changes at them will come
+        Returns:
+            None
+        """
+        ...
+
+
 def subscribe(
     table,
     *,
@@ -82,6 +102,7 @@ def subscribe(
     on_change: OnChangeCallback,
     on_time_end: OnTimeEndCallback = lambda time: None,
     on_end: OnFinishCallback = lambda: None,
+    on_commit: OnCommitCallback | None = None,
 ):
     """
     Calls a callback function on_change on every change happening in table. This method
@@ -101,6 +122,9 @@ def subscribe(
           names row, time and is_addition respectively.
         on_time_end: the callback function to be called on each closed time of computation.
         on_end: the callback function to be called when the stream of changes ends.
+        on_commit: the callback function to be called with the new frontier of the
+          computation once the changes at the times before it are delivered by all
+          the workers.
     Returns:
         None
     """
@@ -134,6 +158,6 @@ def subscribe(
     return table_to_datasink(
         table,
         datasink.CallbackDataSink(
-            on_change_wrapper, on_time_end, on_end, skip_persisted_batch
+            on_change_wrapper, on_time_end, on_end, skip_persisted_batch, on_commit
         ),
     )
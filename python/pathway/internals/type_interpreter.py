@@ -10,6 +10,7 @@ from types import EllipsisType
 from typing import TYPE_CHECKING, Any, TypeVar
 
 from pathway.internals import dtype as dt, expression as expr
+from pathway.internals.apply_translation import translate_apply
 from pathway.internals.expression_printer import get_expression_info
 from pathway.internals.expression_visitor import IdentityTransform
 from pathway.internals.json import Json
@@ -243,9 +244,27 @@ class TypeInterpreter(IdentityTransform):
         expression: expr.ApplyExpression,
         state: TypeInterpreterState | None = None,
         **kwargs,
-    ) -> expr.ApplyExpression:
-        expression = super().eval_apply(expression, state=state, **kwargs)
-        return _wrap(expression, expression._return_type)
+    ) -> expr.ColumnExpression:
+        from pathway.internals import environ
+
+        typed = super().eval_apply(expression, state=state, **kwargs)
+        if environ.native_apply:
+            # the arguments are translated as they are, the translation is typed as a whole
+            translated = translate_apply(
+                expression._fun,
+                expression._return_type,
+                [
+                    (arg, typed_arg._dtype)
+                    for arg, typed_arg in zip(expression._args, typed._args)
+                ],
+                {
+                    name: (arg, typed._kwargs[name]._dtype)
+                    for name, arg in expression._kwargs.items()
+                },
+            )
+            if translated is not None:
+                return self.eval_expression(translated, state=state, **kwargs)
+        return _wrap(typed, typed._return_type)
 
     def eval_numbaapply(
         self,
@@ -265,6 +284,15 @@ class TypeInterpreter(IdentityTransform):
         expression = super().eval_async_apply(expression, state=state, **kwargs)
         return _wrap(expression, expression._return_type)
 
+    def eval_batch_apply(
+        self,
+        expression: expr.BatchApplyExpression,
+        state: TypeInterpreterState | None = None,
+        **kwargs,
+    ) -> expr.BatchApplyExpression:
+        expression = super().eval_batch_apply(expression, state=state, **kwargs)
+        return _wrap(expression, expression._return_type)
+
     def eval_call(
         self,
         expression: expr.ColumnCallExpression,
@@ -589,9 +617,10 @@ class JoinTypeInterpreter(TypeInterpreter):
     ) -> dt.DType:
         dtype = expression._column.dtype
         assert state is not None
-        if (expression.table == self.left and self.optionalize_left) or (
-            expression.table == self.right and self.optionalize_right
-        ):
+        if (
+            (expression.table == self.left and self.optionalize_left)
+            or (expression.table == self.right and self.optionalize_right)
+        ) and not state.check_colref_to_unoptionalize_from_tables(expression):
             return dt.Optional(dtype)
         return super()._eval_column_val(expression, state=state)
 
@@ -5,11 +5,11 @@ from __future__ import annotations
 import abc
 import functools
 from collections.abc import Callable
-from typing import overload
+from typing import Any, overload
 
-from pathway.internals import asynchronous, common
+from pathway.internals import asynchronous, common, expression as expr
 
-__all__ = ["udf", "udf_async", "UDF", "UDFSync", "UDFAsync"]
+__all__ = ["udf", "udf_async", "udf_batch", "UDF", "UDFSync", "UDFAsync", "UDFBatch"]
 
 
 class UDF(abc.ABC):
@@ -45,6 +45,10 @@ class UDFSyncFunction(UDFSync):
     """Create a Python UDF (universal data function) out of a callable.
 
     The output type of the UDF is determined based on its type annotation.
+    An ``async def`` function is awaited by the engine without blocking it, like with
+    :py:func:`udf_async`, so that e.g. many requests to an API are in flight at once.
+    At most 64 calls are in flight, :py:func:`udf_async` with ``capacity`` sets
+    another limit.
 
     Example:
 
@@ -100,8 +104,10 @@ class UDFAsync(UDF):
         """Init UDFAsync.
 
         Args:
-            capacity: Maximum number of concurrent operations allowed.
-                Defaults to None, indicating no specific limit.
+            capacity: Maximum number of concurrent operations allowed. Rows waiting
+                for a free slot are not passed to the function until one of the
+                running operations completes. Defaults to None, indicating no specific
+                limit.
             retry_strategy: Strategy for handling retries in case of failures.
                 Defaults to None.
             cache_strategy: Defines the caching mechanism. If set to None and a persistency
@@ -123,7 +129,9 @@ class UDFAsync(UDF):
             cache_strategy=self.cache_strategy,
         )(self.__wrapped__)
 
-        return common.apply_async(func, *args, **kwargs)
+        return expr.AsyncApplyExpression(
+            func, None, *args, max_in_flight=self.capacity, **kwargs
+        )
 
 
 class UDFAsyncFunction(UDFAsync):
@@ -211,3 +219,118 @@ def udf_async(
             raise TypeError("udf_async should be used with keyword arguments only")
 
         return decorator(fun)
+
+
+class UDFBatch(UDF):
+    """
+    UDF's that are executed as python functions on whole batches of rows at once.
+
+    To implement your own UDF as a class please implement the `__wrapped__` function.
+    It gets one argument per column, holding the values of the batch, and returns
+    the results for the rows of the batch, in the same order.
+    """
+
+    __wrapped__: Callable
+    return_type: Any
+    max_batch_size: int | None
+
+    def __init__(
+        self, *, return_type: Any = Any, max_batch_size: int | None = None
+    ) -> None:
+        """Init UDFBatch.
+
+        Args:
+            return_type: The type of the results for the rows. Defaults to Any.
+            max_batch_size: Maximum number of rows passed to the function at once.
+                Defaults to None, indicating no specific limit.
+        """
+
+        super().__init__()
+        self.return_type = return_type
+        self.max_batch_size = max_batch_size
+
+    def __call__(self, *args, **kwargs):
+        return expr.BatchApplyExpression(
+            self.__wrapped__,
+            self.return_type,
+            *args,
+            max_batch_size=self.max_batch_size,
+            **kwargs,
+        )
+
+
+class UDFBatchFunction(UDFBatch):
+    """
+    UDF's that wrap Python functions operating on batches.
+    """
+
+    def __init__(self, func: Callable, **kwargs):
+        super().__init__(**kwargs)
+
+        # this sets __wrapped__
+        functools.update_wrapper(self, func)
+
+
+@overload
+def udf_batch(fun: Callable) -> Callable:
+    ...
+
+
+@overload
+def udf_batch(
+    *,
+    return_type: Any = Any,
+    max_batch_size: int | None = None,
+) -> Callable[[Callable], Callable]:
+    ...
+
+
+def udf_batch(
+    fun: Callable | None = None,
+    *,
+    return_type: Any = Any,
+    max_batch_size: int | None = None,
+):
+    r"""Create a Python UDF (universal data function) applied to batches of rows.
+
+    The function is called once per batch instead of once per row, which reduces
+    the overhead of calling Python for simple transformations. It gets one argument
+    per column: a NumPy array if all the values of the column in the batch are ints
+    or all are floats, and a list otherwise. It returns a sequence, e.g. a NumPy array,
+    with the results for the rows of the batch, in the same order.
+
+    Args:
+        return_type: The type of the results for the rows. Defaults to Any.
+        max_batch_size: Maximum number of rows passed to the function at once.
+            Defaults to None, indicating no specific limit.
+    Example:
+
+    >>> import pathway as pw
+    >>> @pw.udf_batch(return_type=int)
+    ... def add(left, right):
+    ...     return left + right
+    >>> t1 = pw.debug.table_from_markdown('''
+    ... a  b
+    ... 1  10
+    ... 2  20
+    ... 3  30''')
+    >>> t2 = t1.select(c = add(t1.a, t1.b))
+    >>> pw.debug.compute_and_print(t2, include_id=False)
+    c
+    11
+    22
+    33
+    """
+
+    def decorator(fun: Callable) -> Callable:
+        return UDFBatchFunction(
+            fun, return_type=return_type, max_batch_size=max_batch_size
+        )
+
+    if fun is None:
+        return decorator
+    else:
+        if not callable(fun):
+            raise TypeError("udf_batch should be used with keyword arguments only")
+
+        return decorator(fun)
@@ -3,6 +3,7 @@
 from datetime import datetime, timedelta
 from typing import Any
 
+import numpy as np
 import pandas as pd
 from dateutil import tz
 
@@ -63,3 +64,25 @@ def _value_to_json(value: json.JsonValue) -> json.Json:
 def _json_dumps(obj: Any) -> str:
     """Serialize obj as a JSON formatted string."""
     return json.Json.dumps(obj)
+
+
+def _sparse_matrix_to_rust(
+    matrix: Any,
+) -> tuple[tuple[int, int], np.ndarray, np.ndarray, np.ndarray]:
+    """Returns (shape, indptr, indices, data) of the scipy.sparse matrix in CSR format"""
+    csr = matrix.tocsr()
+    return (
+        csr.shape,
+        csr.indptr.astype(np.int64),
+        csr.indices.astype(np.int64),
+        csr.data.astype(np.float64),
+    )
+
+
+def _sparse_matrix_from_rust(
+    shape: tuple[int, int], indptr: np.ndarray, indices: np.ndarray, data: np.ndarray
+) -> Any:
+    """Returns scipy.sparse.csr_matrix with the given CSR parts"""
+    from scipy import sparse
+
+    return sparse.csr_matrix((data, indices, indptr), shape=shape)
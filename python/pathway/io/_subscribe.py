@@ -4,6 +4,7 @@ from __future__ import annotations
 
 from pathway.internals.table_subscription import (
     OnChangeCallback,
+    OnCommitCallback,
     OnFinishCallback,
     OnTimeEndCallback,
     subscribe as internal_subscribe,
@@ -15,6 +16,7 @@ def subscribe(
     on_change: OnChangeCallback,
     on_end: OnFinishCallback = lambda: None,
     on_time_end: OnTimeEndCallback = lambda time: None,
+    on_commit: OnCommitCallback | None = None,
 ):
     """
     Calls a callback function on_change on every change happening in table.
@@ -28,6 +30,11 @@ def subscribe(
           names row, time and is_addition respectively.
         on_end: the callback to be called when the stream of changes ends.
         on_time_end: the callback function to be called on each closed time of computation.
+        on_commit: the callback function to be called, in a single worker, with the new
+          frontier of the computation once the changes at the times before it are passed
+          to the other callbacks in all the workers. It marks the end of an epoch, e.g.
+          to commit a transaction with the changes received so far. When the stream
+          of changes ends, on_end is called instead.
     Returns:
         None
 
@@ -66,4 +73,5 @@ def subscribe(
         on_change=on_change,
         on_time_end=on_time_end,
         on_end=on_end,
+        on_commit=on_commit,
     )
@@ -5,7 +5,9 @@ from __future__ import annotations
 from collections.abc import Callable
 from typing import Any
 
+from pathway.internals import datasink
 from pathway.internals.api import PathwayType, Pointer
+from pathway.internals.decorators import table_to_datasink
 from pathway.internals.runtime_type_check import check_arg_types
 from pathway.internals.schema import Schema
 from pathway.internals.table import Table
@@ -285,4 +287,49 @@ def write(
     subscribe(table, callback)
 
 
-__all__ = ["read", "write", "RetryPolicy", "rest_connector", "PathwayWebserver"]
+@check_arg_types
+@trace_user_frame
+def serve_table(table: Table, name: str) -> None:
+    """Makes the rows of the table available for lookups with the monitoring http server,
+    which needs to be enabled with ``with_http_server=True`` in ``pw.run``. The rows
+    are kept in memory and updated once the computation of each time is finished.
+
+    ``GET /tables/<name>/<id>`` returns the row with the given id as a JSON object
+    with the columns of the table and the ``id``. ``GET /tables/<name>`` returns
+    a JSON array of the rows, limited to the ones with the columns equal to the values
+    given in the query string, e.g. ``/tables/pets?owner=Alice``. A value is compared
+    with the column serialized to JSON and, if it's not a valid JSON, with the column
+    being a string.
+
+    The changes of the table can be streamed over a WebSocket connected to
+    ``/tables/<name>/changes``. Each change is sent as a JSON text frame
+    ``{"type": "insert" | "delete", "time": ..., "row": {...}}``, with ``row`` as in
+    the lookups. The rows present at the time of connecting are sent first, as inserts,
+    so the client can keep a consistent copy of the table. A client falling too far
+    behind the changes is disconnected and needs to reconnect.
+
+    With multiple processes, the server of each process serves the rows kept by
+    the process.
+
+    Args:
+        table: table to be served.
+        name: the name of the table in the paths of the lookups.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> pets = pw.debug.table_from_markdown("owner pet \\n Alice dog \\n Bob cat")
+    >>> pw.io.http.serve_table(pets, "pets")
+    """
+
+    table_to_datasink(table, datasink.ServedTableDataSink(name))
+
+
+__all__ = [
+    "read",
+    "write",
+    "serve_table",
+    "RetryPolicy",
+    "rest_connector",
+    "PathwayWebserver",
+]
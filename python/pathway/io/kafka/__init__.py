@@ -22,6 +22,9 @@ SUPPORTED_INPUT_FORMATS: set[str] = {
     "raw",
 }
 
+# How often the topics matching a pattern are looked for, unless set in rdkafka_settings
+TOPIC_PATTERN_REFRESH_INTERVAL_MS = "30000"
+
 
 @check_arg_types
 @trace_user_frame
@@ -29,6 +32,7 @@ def read(
     rdkafka_settings: dict,
     topic: str | list[str] | None = None,
     *,
+    topic_pattern: str | None = None,
     schema: type[Schema] | None = None,
     format="raw",
     debug_data=None,
@@ -50,6 +54,14 @@ def read(
         rdkafka_settings: Connection settings in the format of `librdkafka
             <https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md>`_.
         topic: Name of topic in Kafka from which the data should be read.
+        topic_pattern: Regular expression, in the syntax of librdkafka, matching the names
+            of the topics from which the data should be read, instead of a single ``topic``.
+            The topics created later are read as well, once librdkafka refreshes
+            the metadata of the cluster, every ``topic.metadata.refresh.interval.ms``
+            milliseconds, which defaults to 30 seconds here. Their messages are read from
+            the position given by ``auto.offset.reset``, so it should be set to
+            ``"earliest"`` for them to be read from the beginning. The offsets are
+            persisted for each topic and partition.
         schema: Schema of the resulting table.
         format: format of the input data, "raw", "csv", or "json".
         debug_data: Static data replacing original one when debug mode is active.
@@ -70,7 +82,8 @@ def read(
             be persisted or ``None``, if there is no need to persist the state of this table.
             When a program restarts, it restores the state for all input tables according to what
             was saved for their ``persistent_id``. This way it's possible to configure the start of
-            computations from the moment they were terminated last time.
+            computations from the moment they were terminated last time. The offsets of the
+            consumer group are then committed only together with the persisted state.
         value_columns: Columns to extract for a table, required for format other than
             "raw". [will be deprecated soon]
         primary_key: In case the table should have a primary key generated according to
@@ -216,9 +229,29 @@ def read(
     ...        "pet_height": "/pet/measurements/1"
     ...    },
     ... )
+
+    The messages of all the topics whose names start with ``animals-``, including
+    the ones created while the program runs, can be read into a single table with
+    ``topic_pattern``:
+
+    >>> t = pw.io.kafka.read(
+    ...    rdkafka_settings,
+    ...    topic_pattern="^animals-.*",
+    ...    format="json",
+    ...    schema=InputSchema,
+    ... )
     """
     # The data_storage is common to all kafka connectors
 
+    if topic_pattern is not None:
+        if topic or kwargs.get("topic_names"):
+            raise ValueError("Only one of topic and topic_pattern can be specified")
+        # librdkafka treats the topic names starting with "^" as regular expressions
+        topic = topic_pattern if topic_pattern.startswith("^") else "^" + topic_pattern
+        rdkafka_settings = {
+            "topic.metadata.refresh.interval.ms": TOPIC_PATTERN_REFRESH_INTERVAL_MS,
+            **rdkafka_settings,
+        }
     if not topic:
         topic_names = kwargs.get("topic_names")
         if not topic_names:
@@ -316,7 +349,8 @@ topic.
             be persisted or ``None``, if there is no need to persist the state of this table.
             When a program restarts, it restores the state for all input tables according to what
             was saved for their ``persistent_id``. This way it's possible to configure the start of
-            computations from the moment they were terminated last time.
+            computations from the moment they were terminated last time. The offsets of the
+            consumer group are then committed only together with the persisted state.
 
     Returns:
         Table: The table read.
@@ -409,7 +443,8 @@ topic.
             be persisted or ``None``, if there is no need to persist the state of this table.
             When a program restarts, it restores the state for all input tables according to what
             was saved for their ``persistent_id``. This way it's possible to configure the start of
-            computations from the moment they were terminated last time.
+            computations from the moment they were terminated last time. The offsets of the
+            consumer group are then committed only together with the persisted state.
 
     Returns:
         Table: The table read.
@@ -475,6 +510,8 @@ def write(
         table: the table to output.
         rdkafka_settings: Connection settings in the format of
             `librdkafka <https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md>`_.
+            If ``transactional.id`` is set, the output is sent in Kafka transactions,
+            see below.
         topic_name: name of topic in Kafka to which the data should be sent.
         format: format of the input data, currently "json" and "dsv" are supported.
         delimiter: field delimiter to be used in case of delimiter-separated values
@@ -483,6 +520,21 @@ def write(
     Returns:
         None
 
+    Transactions:
+
+    With ``transactional.id`` set, the output is written by a single worker and each
+    batch of finished times becomes visible to ``read_committed`` consumers at once.
+    When persistence is enabled, a transaction is committed only after the
+    checkpoint containing its data is saved, so the data is not sent again after a
+    restart. Kafka doesn't allow resuming a transaction after a restart, therefore
+    the data waiting for the checkpoint is saved in the persistent storage. Each
+    transaction also commits the offset of the consumer group
+    ``<transactional.id>-epochs`` for partition 0 of the topic, marking the last
+    batch sent. After a restart, the batches covered by the last checkpoint and not
+    marked as sent are sent again, so a failure between saving the checkpoint and
+    committing the transaction doesn't lose them. If a commit fails, the checkpoint
+    is reported as failed and the batches are retried with the next checkpoint.
+
     Limitations:
 
     For future proofing, the format is configurable, but (for now) only JSON is available.
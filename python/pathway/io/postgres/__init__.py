@@ -20,6 +20,7 @@ def write(
     postgres_settings: dict,
     table_name: str,
     max_batch_size: int | None = None,
+    transaction_id: str | None = None,
 ) -> None:
     """Writes ``table``'s stream of updates to a postgres table.
 
@@ -31,10 +32,24 @@ def write(
         table_name: Name of the target table.
         max_batch_size: Maximum number of entries allowed to be committed within a \
 single transaction.
+        transaction_id: If set, the output of each batch of finished times is \
+committed atomically using Postgres two-phase commit, see below. It has to be unique \
+among the writers using the database and stay the same between runs. The batches \
+are not split according to ``max_batch_size`` then.
 
     Returns:
         None
 
+    Transactions:
+
+    With ``transaction_id`` set, the output is written by a single worker. Each batch
+    of finished times is first stored in a prepared transaction, which requires
+    ``max_prepared_transactions`` to be positive on the server. When persistence is
+    enabled, the transaction is committed only after the checkpoint containing its
+    data is saved. After a restart, the prepared transactions left by the previous
+    run are committed if the saved checkpoint contains them and rolled back
+    otherwise, so no data is written twice.
+
     Example:
 
     Consider there's a need to output a stream of updates from a table in Pathway to
@@ -94,6 +109,7 @@ single transaction.
         storage_type="postgres",
         connection_string=_connection_string_from_settings(postgres_settings),
         max_batch_size=max_batch_size,
+        transaction_id=transaction_id,
     )
     data_format = api.DataFormat(
         format_type="sql",
@@ -117,6 +133,7 @@ def write_snapshot(
     table_name: str,
     primary_key: list[str],
     max_batch_size: int | None = None,
+    transaction_id: str | None = None,
 ) -> None:
     """Maintains a snapshot of a table within a Postgres table.
 
@@ -129,10 +146,18 @@ def write_snapshot(
         primary_key: Names of the fields which serve as a primary key in the Postgres table.
         max_batch_size: Maximum number of entries allowed to be committed within a \
 single transaction.
+        transaction_id: If set, the output of each batch of finished times is \
+committed atomically using Postgres two-phase commit, see below. It has to be unique \
+among the writers using the database and stay the same between runs. The batches \
+are not split according to ``max_batch_size`` then.
 
     Returns:
         None
 
+    Transactions:
+
+    ``transaction_id`` works as in :py:func:`write`.
+
     Example:
 
     Consider there is a table ``stats`` in Pathway, containing the average number of requests to some
@@ -177,6 +202,7 @@ single transaction.
         storage_type="postgres",
         connection_string=_connection_string_from_settings(postgres_settings),
         max_batch_size=max_batch_size,
+        transaction_id=transaction_id,
     )
     data_format = api.DataFormat(
         format_type="sql_snapshot",
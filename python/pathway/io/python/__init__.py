@@ -11,7 +11,8 @@ import pandas as pd
 import panel as pn
 from IPython.display import display
 
-from pathway.internals import Table, api, datasource
+from pathway.internals import Table, api, datasink, datasource
+from pathway.internals._io_helpers import _format_output_value_fields
 from pathway.internals.api import DataEventType, PathwayType, Pointer, SessionType
 from pathway.internals.decorators import table_from_datasource
 from pathway.internals.runtime_type_check import check_arg_types
@@ -128,14 +129,14 @@ class ConnectorSubject(ABC):
 
     def commit(self) -> None:
         """Sends a commit message."""
-        self._buffer.put((DataEventType.INSERT, None, b"*COMMIT*", None))
+        self._buffer.put((DataEventType.INSERT, None, b"*COMMIT*", None, None))
 
     def close(self) -> None:
         """Sends a sentinel message.
 
         Should be called to indicate that no new messages will be sent.
         """
-        self._buffer.put((DataEventType.INSERT, None, b"*FINISH*", None))
+        self._buffer.put((DataEventType.INSERT, None, b"*FINISH*", None, None))
 
     def start(self) -> None:
         """Runs a separate thread with function feeding data into buffer.
@@ -153,7 +154,9 @@ class ConnectorSubject(ABC):
                     self.on_stop()
                     self.close()
 
-        self._thread = threading.Thread(target=target)
+        # a subject that never finishes must not keep the process alive
+        # once the computation is stopped
+        self._thread = threading.Thread(target=target, daemon=not self._is_finite())
         self._thread.start()
 
     def end(self) -> None:
@@ -170,13 +173,13 @@ class ConnectorSubject(ABC):
         self, key: Pointer | None, message: bytes, metadata: bytes | None = None
     ) -> None:
         if self._session_type == SessionType.NATIVE:
-            self._buffer.put((DataEventType.INSERT, key, message, metadata))
+            self._buffer.put((DataEventType.INSERT, key, message, metadata, None))
         elif self._session_type == SessionType.UPSERT:
             if not self._deletions_enabled:
                 raise ValueError(
                     f"Trying to upsert a row in {type(self)} but deletions_enabled is set to False."
                 )
-            self._buffer.put((DataEventType.UPSERT, key, message, metadata))
+            self._buffer.put((DataEventType.UPSERT, key, message, metadata, None))
         else:
             raise NotImplementedError(f"session type {self._session_type} not handled")
 
@@ -187,7 +190,7 @@ class ConnectorSubject(ABC):
             raise ValueError(
                 f"Trying to delete a row in {type(self)} but deletions_enabled is set to False."
             )
-        self._buffer.put((DataEventType.DELETE, key, message, metadata))
+        self._buffer.put((DataEventType.DELETE, key, message, metadata, None))
 
     def _read(self) -> Any:
         """Allows to retrieve data from a buffer.
@@ -220,11 +223,145 @@ class ConnectorSubject(ABC):
     def _deletions_enabled(self) -> bool:
         return True
 
+    @property
+    def _replays_from_beginning(self) -> bool:
+        """
+        Denotes if the subject produces its messages again from the beginning after a
+        restart of a persisted computation. If so, the messages read before the restart
+        are skipped, so that they are not duplicated. A subject which itself continues
+        from where it stopped should return False.
+        """
+        return True
+
+    def _engine_subject(self) -> api.PythonSubject:
+        return api.PythonSubject(
+            start=self.start,
+            read=self._read,
+            end=self.end,
+            is_internal=self._is_internal(),
+            deletions_enabled=self._deletions_enabled,
+            replays_from_beginning=self._replays_from_beginning,
+        )
+
+
+class ConnectorSource(ABC):
+    """An abstract class allowing to create custom python connectors reading from
+    sources that keep track of the position in them, like message queues or logs.
+
+    Unlike :py:class:`ConnectorSubject`, which sends the messages from its own thread,
+    the source is polled by pathway engine with :py:meth:`read`. Each message comes
+    with a cursor, i.e. the position in the source right after the message. The cursor
+    can be an int, a string, bytes or a tuple of them.
+
+    With persistence enabled, the cursors are persisted together with the state of
+    the computation. After a restart, the last persisted cursor is passed to
+    :py:meth:`seek`, so that the source continues from where it stopped, and
+    :py:meth:`on_persisted` is called whenever the messages up to a cursor are
+    persisted, e.g. to acknowledge them.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> from pathway.io.python import ConnectorSource
+    >>>
+    >>> class MySchema(pw.Schema):
+    ...     a: int
+    ...
+    >>>
+    >>> class MySource(ConnectorSource):
+    ...     def __init__(self, n: int) -> None:
+    ...         super().__init__()
+    ...         self.position = 0
+    ...         self.n = n
+    ...     def read(self):
+    ...         if self.position == self.n:
+    ...             return None
+    ...         self.position += 1
+    ...         return {"a": self.position}, self.position
+    ...     def seek(self, cursor):
+    ...         self.position = cursor
+    ...
+    >>>
+    >>> table = pw.io.python.read(MySource(3), schema=MySchema)
+    >>> pw.debug.compute_and_print(table, include_id=False)
+    a
+    1
+    2
+    3
+    """
+
+    _already_used: bool
+
+    def __init__(self) -> None:
+        self._already_used = False
+
+    @abstractmethod
+    def read(self) -> tuple[dict | str | bytes, Any] | None:
+        """Returns the next message together with the cursor after it, or None once
+        the source is exhausted. It may block until a message is available.
+
+        A message is a dict representing json, a string or bytes, depending on
+        the format of the connector.
+        """
+        ...
+
+    def seek(self, cursor: Any) -> None:
+        """Moves the source to the cursor persisted last, so that the next message read
+        is the one after it. Called before the first :py:meth:`read` after a restart.
+        """
+        raise NotImplementedError(
+            f"{type(self).__name__} can't resume from a persisted cursor"
+        )
+
+    def on_persisted(self, cursor: Any) -> None:
+        """Called once the messages up to the cursor are persisted, so that they won't
+        be requested again after a restart."""
+        pass
+
+    def close(self) -> None:
+        """Called after :py:meth:`read` returns None."""
+        pass
+
+    @property
+    def _with_metadata(self) -> bool:
+        return False
+
+    @property
+    def _session_type(self) -> SessionType:
+        return SessionType.NATIVE
+
+    @property
+    def _deletions_enabled(self) -> bool:
+        return False
+
+    def _read(self) -> Any:
+        entry = self.read()
+        if entry is None:
+            return (DataEventType.INSERT, None, b"*FINISH*", None, None)
+        message, cursor = entry
+        if isinstance(message, dict):
+            message = json.dumps(message, ensure_ascii=False)
+        if isinstance(message, str):
+            message = message.encode(encoding="utf-8")
+        return (DataEventType.INSERT, None, message, None, cursor)
+
+    def _engine_subject(self) -> api.PythonSubject:
+        return api.PythonSubject(
+            start=lambda: None,
+            read=self._read,
+            end=self.close,
+            is_internal=False,
+            deletions_enabled=self._deletions_enabled,
+            replays_from_beginning=False,
+            seek=self.seek,
+            on_persisted=self.on_persisted,
+        )
+
 
 @check_arg_types
 @trace_user_frame
 def read(
-    subject: ConnectorSubject,
+    subject: ConnectorSubject | ConnectorSource,
     *,
     schema: type[Schema] | None = None,
     format: str = "json",
@@ -236,10 +373,11 @@ def read(
     default_values: dict[str, Any] | None = None,
     persistent_id: str | None = None,
 ) -> Table:
-    """Reads a table from a ConnectorSubject.
+    """Reads a table from a ConnectorSubject or a ConnectorSource.
 
     Args:
-        subject: An instance of a :py:class:`~pathway.python.ConnectorSubject`.
+        subject: An instance of a :py:class:`~pathway.python.ConnectorSubject` or
+            a :py:class:`~pathway.python.ConnectorSource`.
         schema: Schema of the resulting table.
         format: Format of the data produced by a subject, "json", "raw" or "binary". In case of
             a "raw" format, table with single "data" column will be produced.
@@ -261,7 +399,10 @@ def read(
 will be persisted or ``None``, if there is no need to persist the state of this table. \
 When a program restarts, it restores the state for all input tables according to what \
 was saved for their ``persistent_id``. This way it's possible to configure the start of \
-computations from the moment they were terminated last time.
+computations from the moment they were terminated last time. The messages that the \
+subject produces again after the restart are skipped, unless its \
+``_replays_from_beginning`` property returns False. A ConnectorSource resumes from the \
+persisted cursor instead.
 
     Returns:
         Table: The table read.
@@ -269,8 +410,8 @@ computations from the moment they were terminated last time.
 
     if subject._already_used:
         raise ValueError(
-            "You can't use the same ConnectorSubject object in more than one Python connector."
-            + "If you want to use the same ConnectorSubject twice, create two separate objects of this class."
+            f"You can't use the same {type(subject).__name__} object in more than one Python connector."
+            + " If you want to use it twice, create two separate objects of this class."
         )
     subject._already_used = True
 
@@ -306,13 +447,7 @@ computations from the moment they were terminated last time.
     )
     data_storage = api.DataStorage(
         storage_type="python",
-        python_subject=api.PythonSubject(
-            start=subject.start,
-            read=subject._read,
-            end=subject.end,
-            is_internal=subject._is_internal(),
-            deletions_enabled=subject._deletions_enabled,
-        ),
+        python_subject=subject._engine_subject(),
         read_method=internal_read_method(format),
         persistent_id=persistent_id,
         mode=mode,
@@ -331,6 +466,72 @@ computations from the moment they were terminated last time.
     )
 
 
+class ConnectorSink(ABC):
+    """An abstract class allowing to create custom python connectors writing
+    the stream of updates of a table.
+
+    Each update is passed to :py:meth:`write` as a dict with the values of the columns,
+    converted to json, and two additional fields: ``time``, the time of the update, and
+    ``diff``, 1 for an inserted row and -1 for a removed one.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> from pathway.io.python import ConnectorSink
+    >>>
+    >>> class MySink(ConnectorSink):
+    ...     def __init__(self) -> None:
+    ...         self.rows = []
+    ...     def write(self, row: dict) -> None:
+    ...         self.rows.append(row)
+    ...
+    >>>
+    >>> t = pw.debug.table_from_markdown("a \\n 1 \\n 2")
+    >>> sink = MySink()
+    >>> pw.io.python.write(t, sink)
+    >>> pw.run(monitoring_level=pw.MonitoringLevel.NONE)
+    >>> sorted(row["a"] for row in sink.rows)
+    [1, 2]
+    """
+
+    @abstractmethod
+    def write(self, row: dict[str, Any]) -> None:
+        """Called with each update of the table."""
+        ...
+
+    def flush(self) -> None:
+        """Called once all the updates of a time are passed to :py:meth:`write`."""
+        pass
+
+    def _write(self, payload: bytes) -> None:
+        self.write(json.loads(payload))
+
+
+@check_arg_types
+@trace_user_frame
+def write(table: Table, sink: ConnectorSink) -> None:
+    """Writes the stream of updates of a table to a ConnectorSink.
+
+    Args:
+        table: Table to be written.
+        sink: An instance of a :py:class:`~pathway.python.ConnectorSink`.
+
+    Returns:
+        None
+    """
+
+    data_storage = api.DataStorage(
+        storage_type="python",
+        python_sink=api.PythonSink(write=sink._write, flush=sink.flush),
+    )
+    data_format = api.DataFormat(
+        format_type="jsonlines",
+        key_field_names=[],
+        value_fields=_format_output_value_fields(table),
+    )
+    table.to(datasink.GenericDataSink(data_storage, data_format))
+
+
 class InteractiveCsvPlayer(ConnectorSubject):
     q: queue.Queue
 
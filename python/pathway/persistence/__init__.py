@@ -1,11 +1,18 @@
 # Copyright © 2024 Pathway
 
 import os
-from dataclasses import KW_ONLY, dataclass
+from dataclasses import KW_ONLY, dataclass, field
+from functools import cached_property
+from collections.abc import Callable
+from typing import Any, Literal
+
+import pandas as pd
 
 from pathway.internals import api
 from pathway.internals._io_helpers import AwsS3Settings
 
+GCS_XML_API_ENDPOINT = "https://storage.googleapis.com"
+
 
 class Backend:
     """
@@ -66,6 +73,168 @@ as they are used by S3 connectors.
             ),
         )
 
+    @classmethod
+    def azure(
+        cls,
+        root_path: str,
+        *,
+        container_name: str,
+        account_name: str | None = None,
+        account_key: str | None = None,
+        sas_token: str | None = None,
+        connection_string: str | None = None,
+        endpoint: str | None = None,
+    ):
+        """
+        Configure the Azure Blob Storage backend. The snapshots and the metadata are
+        kept in the container with the same layout as for the S3 backend.
+
+        The requests are authorized either with the access key of the storage
+        account or with a shared access signature. If neither of them nor a connection
+        string is given, they are taken from the ``AZURE_STORAGE_CONNECTION_STRING``
+        environment variable, or from ``AZURE_STORAGE_ACCOUNT`` together with
+        ``AZURE_STORAGE_KEY`` or ``AZURE_STORAGE_SAS_TOKEN``.
+
+        Args:
+            root_path: path to the root in the container, which will be used to store \
+persisted data;
+            container_name: the name of the blob container;
+            account_name: the name of the storage account;
+            account_key: the access key of the storage account;
+            sas_token: a shared access signature allowing to list, read, write and \
+delete the blobs in the container;
+            connection_string: the connection string of the storage account, as \
+shown in the Azure portal. It's used instead of the other credentials;
+            endpoint: the URL of the blob service, if it's not the one of the account \
+in the Azure public cloud, e.g. for a sovereign cloud or the Azurite emulator.
+
+        Returns:
+            Class instance denoting the Azure Blob Storage backend with root \
+directory as ``root_path``.
+        """
+        if (
+            connection_string is None
+            and account_key is None
+            and sas_token is None
+            and "AZURE_STORAGE_CONNECTION_STRING" in os.environ
+        ):
+            connection_string = os.environ["AZURE_STORAGE_CONNECTION_STRING"]
+        if connection_string is not None:
+            settings = _parse_azure_connection_string(connection_string)
+            account_name = settings.get("AccountName", account_name)
+            account_key = settings.get("AccountKey")
+            sas_token = settings.get("SharedAccessSignature")
+            if endpoint is None:
+                endpoint = settings.get("BlobEndpoint")
+            if endpoint is None and "EndpointSuffix" in settings:
+                protocol = settings.get("DefaultEndpointsProtocol", "https")
+                endpoint = (
+                    f"{protocol}://{account_name}.blob.{settings['EndpointSuffix']}"
+                )
+        elif account_key is None and sas_token is None:
+            account_key = os.environ.get("AZURE_STORAGE_KEY")
+            if account_key is None:
+                sas_token = os.environ.get("AZURE_STORAGE_SAS_TOKEN")
+        if account_name is None:
+            account_name = os.environ.get("AZURE_STORAGE_ACCOUNT")
+        if account_name is None:
+            raise ValueError("the name of the Azure storage account isn't specified")
+        if account_key is None and sas_token is None:
+            raise ValueError(
+                "neither the account key nor a shared access signature is specified "
+                f"for the Azure storage account {account_name!r}"
+            )
+        return cls(
+            api.DataStorage(
+                storage_type="azure",
+                path=root_path,
+                azure_blob_storage_settings=api.AzureBlobStorageSettings(
+                    account_name=account_name,
+                    container_name=container_name,
+                    account_key=account_key,
+                    sas_token=sas_token,
+                    endpoint=endpoint,
+                ),
+            ),
+        )
+
+    @classmethod
+    def gcs(
+        cls,
+        root_path: str,
+        *,
+        bucket_name: str,
+        credentials_file: str | os.PathLike[str] | None = None,
+        hmac_access_id: str | None = None,
+        hmac_secret: str | None = None,
+        endpoint: str | None = None,
+    ):
+        """
+        Configure the Google Cloud Storage backend. The snapshots and the metadata are
+        kept in the bucket with the same layout as for the S3 backend.
+
+        The requests are authorized with the credentials of a service account or of
+        a user, in the JSON format of the key files. If ``credentials_file`` isn't
+        given, the application default credentials are used: the file pointed to by
+        the ``GOOGLE_APPLICATION_CREDENTIALS`` environment variable, the credentials
+        saved by ``gcloud auth application-default login``, or the service account of
+        the machine, when running on Google Cloud.
+
+        An HMAC key can be given instead, in which case the bucket is accessed with
+        its S3-compatible XML API, as by the S3 backend.
+
+        Args:
+            root_path: path to the root in the bucket, which will be used to store \
+persisted data;
+            bucket_name: the name of the Google Cloud Storage bucket;
+            credentials_file: the path to the JSON key file of a service account that \
+can read and write the objects in the bucket, or to the credentials of a user;
+            hmac_access_id: the access ID of an HMAC key of such a service account;
+            hmac_secret: the secret of this HMAC key;
+            endpoint: the URL of the storage API, if it's not the one of Google Cloud, \
+e.g. for an emulator.
+
+        Returns:
+            Class instance denoting the Google Cloud Storage backend with root \
+directory as ``root_path``.
+        """
+        if (hmac_access_id is None) != (hmac_secret is None):
+            raise ValueError("both hmac_access_id and hmac_secret must be specified")
+        if hmac_access_id is not None:
+            if credentials_file is not None:
+                raise ValueError(
+                    "credentials_file can't be used together with an HMAC key"
+                )
+            return cls.s3(
+                root_path,
+                AwsS3Settings(
+                    bucket_name=bucket_name,
+                    access_key=hmac_access_id,
+                    secret_access_key=hmac_secret,
+                    with_path_style=True,
+                    region="auto",
+                    endpoint=endpoint or GCS_XML_API_ENDPOINT,
+                ),
+            )
+
+        if credentials_file is None:
+            credentials_file = _default_gcs_credentials_file()
+        credentials_json = None
+        if credentials_file is not None:
+            with open(credentials_file) as f:
+                credentials_json = f.read()
+        return cls(
+            api.DataStorage(
+                storage_type="gcs",
+                path=root_path,
+                gcs_settings=api.GcsSettings(
+                    bucket_name=bucket_name,
+                    credentials_json=credentials_json,
+                    endpoint=endpoint,
+                ),
+            ),
+        )
+
     @classmethod
     def mock(cls, events: dict[tuple[str, int], list[api.SnapshotEvent]]):
         return cls(api.DataStorage(storage_type="mock", mock_events=events))
@@ -79,6 +248,57 @@ as they are used by S3 connectors.
             os.environ["PATHWAY_PERSISTENT_STORAGE"] = os.fspath(self._fs_path)
 
 
+def _parse_azure_connection_string(connection_string: str) -> dict[str, str]:
+    settings = {}
+    for part in connection_string.split(";"):
+        if not part.strip():
+            continue
+        name, separator, value = part.partition("=")
+        if not separator:
+            raise ValueError(f"malformed Azure storage connection string: {part!r}")
+        settings[name.strip()] = value.strip()
+    return settings
+
+
+def _default_gcs_credentials_file() -> str | None:
+    """
+    Returns the file with the application default credentials of Google Cloud, or
+    ``None`` if there is none, so that the ones of the machine are used.
+    """
+    if "GOOGLE_APPLICATION_CREDENTIALS" in os.environ:
+        return os.environ["GOOGLE_APPLICATION_CREDENTIALS"]
+    if os.name == "nt":
+        config_dir = os.path.join(os.environ.get("APPDATA", ""), "gcloud")
+    else:
+        config_dir = os.environ.get(
+            "CLOUDSDK_CONFIG", os.path.expanduser("~/.config/gcloud")
+        )
+    path = os.path.join(config_dir, "application_default_credentials.json")
+    if os.path.exists(path):
+        return path
+    return None
+
+
+@dataclass(frozen=True)
+class ColumnMigration:
+    """
+    Describe how a column of an input changed since its state was persisted. The
+    columns that keep their name and type, or whose type is widened from ``int`` to
+    ``float``, don't need a migration.
+
+    Args:
+        renamed_from: the name of the column in the persisted state, if the column \
+was renamed;
+        fill_value: the value of the column in the persisted rows, if the column was \
+added. If not set, the default value of the column is used, or ``None`` if it has no \
+default.
+    """
+
+    _: KW_ONLY
+    renamed_from: str | None = None
+    fill_value: Any = None
+
+
 @dataclass(frozen=True)
 class Config:
     """
@@ -93,24 +313,128 @@ class Config:
         snapshot_storage: snapshots backend configuration;
         snapshot_interval_ms: the desired duration between snapshot updates in \
 milliseconds;
+        snapshot_interval_rows: if set, the snapshot is also updated once this many \
+rows were read since the previous update, without waiting for ``snapshot_interval_ms``. \
+It bounds the amount of data read again after a failure under a bursty load;
+        snapshot_interval_bytes: like ``snapshot_interval_rows``, but for the size \
+of the rows read since the previous update in bytes;
+        replay_until: if set, the computation is run only on the data persisted for \
+the times up to this one, materializing the tables as of this past time. The \
+persisted state is not modified. It can only be used when replaying the snapshot \
+without continuing after the replay;
+        snapshot_compression: if set, the new snapshot data is compressed in blocks \
+with this algorithm, either ``"zstd"`` or the faster but weaker ``"lz4"``. The \
+algorithm is recorded in each block, so snapshots are read regardless of how they \
+were compressed. The metadata isn't compressed, as it only keeps small checkpoint \
+records;
+        snapshot_compression_level: the level of the ``"zstd"`` compression, from 1 \
+(the fastest) to 22 (the best compression), 3 by default;
+        encryption_key: if set, the snapshots and the metadata are encrypted with \
+AES-256-GCM using this 32-byte key. The same key is needed to read them back, so it \
+has to be kept safely, e.g. in a key management service, from which it can be fetched \
+before constructing the config;
+        migrate_unencrypted_data: if set together with ``encryption_key``, the \
+snapshots and the metadata persisted before the encryption was enabled are encrypted \
+at the start of the run. Otherwise, such data is rejected, so it has to be set for a \
+single run after enabling the encryption. The snapshots of the inputs not read in that \
+run stay unencrypted;
+        schema_migrations: the migrations of the columns of the inputs, whose schemas \
+changed since their state was persisted, by the ``persistent_id`` of the input and the \
+name of the column. The persisted rows are matched with the current schema by column \
+names, so the columns added with a default value or as optional ones, the removed \
+columns and the types widened from ``int`` to ``float`` don't need to be listed here;
+        keep_last_checkpoints: if set, only the last this many checkpoints of the \
+persisted state are kept in the metadata storage, together with the ones made within \
+``keep_checkpoints_days``. The older checkpoints are only needed to fall back to, if the \
+newer ones get corrupted. Once no kept checkpoint refers to an input removed from the \
+computation, its snapshot is removed as well. If neither this nor \
+``keep_checkpoints_days`` is set, all checkpoints and snapshots are kept;
+        keep_checkpoints_days: if set, the checkpoints made within this many days are \
+kept, together with the last ``keep_last_checkpoints`` ones;
+        background_checkpoints: if set, waiting for the snapshots to be written and \
+committing the checkpoint is done in a background thread, so that the checkpoints of \
+large inputs, e.g. uploaded to S3, don't delay the processing. A new checkpoint is only \
+started once the previous one is finished, and the last one is always waited for. \
+In a computation run in several processes, the checkpoints are always made in the \
+background, as the processes wait for each other to prepare them;
     """
 
     _: KW_ONLY
     snapshot_interval_ms: int = 0
+    snapshot_interval_rows: int | None = None
+    snapshot_interval_bytes: int | None = None
     metadata_storage: Backend
     snapshot_storage: Backend
     snapshot_access: api.SnapshotAccess
     persistence_mode: api.PersistenceMode
     continue_after_replay: bool
+    replay_until: int | None = None
+    snapshot_compression: Literal["zstd", "lz4"] | None = None
+    snapshot_compression_level: int | None = None
+    encryption_key: bytes | None = field(default=None, repr=False)
+    migrate_unencrypted_data: bool = False
+    schema_migrations: dict[str, dict[str, ColumnMigration]] = field(
+        default_factory=dict
+    )
+    keep_last_checkpoints: int | None = None
+    keep_checkpoints_days: float | None = None
+    background_checkpoints: bool = False
+
+    def __post_init__(self):
+        if self.replay_until is not None and (
+            self.snapshot_access != api.SnapshotAccess.REPLAY
+            or self.continue_after_replay
+        ):
+            raise ValueError(
+                "replay_until can only be used with snapshot_access=REPLAY"
+                + " and continue_after_replay=False"
+            )
+        if self.snapshot_compression not in [None, "zstd", "lz4"]:
+            raise ValueError('snapshot_compression has to be "zstd" or "lz4"')
+        if self.snapshot_compression_level is not None:
+            if self.snapshot_compression != "zstd":
+                raise ValueError(
+                    "snapshot_compression_level can only be used with zstd compression"
+                )
+            if not 1 <= self.snapshot_compression_level <= 22:
+                raise ValueError(
+                    "snapshot_compression_level has to be between 1 and 22"
+                )
+        if self.encryption_key is not None and len(self.encryption_key) != 32:
+            raise ValueError("encryption_key has to be 32 bytes long")
+        if self.migrate_unencrypted_data and self.encryption_key is None:
+            raise ValueError(
+                "migrate_unencrypted_data can only be used with encryption_key"
+            )
+        for name in [
+            "snapshot_interval_rows",
+            "snapshot_interval_bytes",
+            "keep_last_checkpoints",
+            "keep_checkpoints_days",
+        ]:
+            value = getattr(self, name)
+            if value is not None and value <= 0:
+                raise ValueError(f"{name} has to be positive")
 
     @classmethod
     def simple_config(
         cls,
         backend: Backend,
         snapshot_interval_ms=0,
+        snapshot_interval_rows=None,
+        snapshot_interval_bytes=None,
         snapshot_access=api.SnapshotAccess.FULL,
         persistence_mode=api.PersistenceMode.PERSISTING,
         continue_after_replay=True,
+        replay_until=None,
+        snapshot_compression=None,
+        snapshot_compression_level=None,
+        encryption_key=None,
+        migrate_unencrypted_data=False,
+        schema_migrations=None,
+        keep_last_checkpoints=None,
+        keep_checkpoints_days=None,
+        background_checkpoints=False,
     ):
         """
         Construct config from a single instance of the \
@@ -122,6 +446,29 @@ snapshot.
             snapshot_interval_ms: the desired freshness of the persisted snapshot in \
 milliseconds. The greater the value is, the more the amount of time that the snapshot \
 may fall behind, and the less computational resources are required.
+            snapshot_interval_rows: if set, the snapshot is also updated once this \
+many rows were read since the previous update.
+            snapshot_interval_bytes: if set, the snapshot is also updated once the \
+rows read since the previous update take this many bytes.
+            replay_until: if set, only the data persisted for the times up to this one \
+is replayed and the persisted state is not modified.
+            snapshot_compression: if set, the new snapshot data is compressed with \
+this algorithm, either ``"zstd"`` or ``"lz4"``.
+            snapshot_compression_level: the level of the ``"zstd"`` compression, from \
+1 to 22.
+            encryption_key: if set, the snapshots and the metadata are encrypted \
+with AES-256-GCM using this 32-byte key.
+            migrate_unencrypted_data: if set, the data persisted before the \
+encryption was enabled is encrypted instead of being rejected. It's needed for a \
+single run after enabling the encryption.
+            schema_migrations: the migrations of the columns of the inputs, whose \
+schemas changed since their state was persisted, by ``persistent_id`` and column name.
+            keep_last_checkpoints: if set, only the last this many checkpoints are \
+kept, together with the ones made within ``keep_checkpoints_days``.
+            keep_checkpoints_days: if set, the checkpoints made within this many days \
+are kept, together with the last ``keep_last_checkpoints`` ones.
+            background_checkpoints: if set, the checkpoints are committed in a \
+background thread, without delaying the processing.
 
         Returns:
             Persistence config.
@@ -129,23 +476,159 @@ may fall behind, and the less computational resources are required.
 
         return cls(
             snapshot_interval_ms=snapshot_interval_ms,
+            snapshot_interval_rows=snapshot_interval_rows,
+            snapshot_interval_bytes=snapshot_interval_bytes,
             metadata_storage=backend,
             snapshot_storage=backend,
             snapshot_access=snapshot_access,
             persistence_mode=persistence_mode,
             continue_after_replay=continue_after_replay,
+            replay_until=replay_until,
+            snapshot_compression=snapshot_compression,
+            snapshot_compression_level=snapshot_compression_level,
+            encryption_key=encryption_key,
+            migrate_unencrypted_data=migrate_unencrypted_data,
+            schema_migrations=schema_migrations or {},
+            keep_last_checkpoints=keep_last_checkpoints,
+            keep_checkpoints_days=keep_checkpoints_days,
+            background_checkpoints=background_checkpoints,
         )
 
-    @property
+    @cached_property
     def engine_config(self):
         return api.PersistenceConfig(
             snapshot_interval_ms=self.snapshot_interval_ms,
+            snapshot_interval_rows=self.snapshot_interval_rows,
+            snapshot_interval_bytes=self.snapshot_interval_bytes,
             metadata_storage=self.metadata_storage.engine_data_storage,
             stream_storage=self.snapshot_storage.engine_data_storage,
             snapshot_access=self.snapshot_access,
             persistence_mode=self.persistence_mode,
             continue_after_replay=self.continue_after_replay,
+            replay_until=self.replay_until,
+            snapshot_compression=self.snapshot_compression,
+            snapshot_compression_level=self.snapshot_compression_level,
+            encryption_key=self.encryption_key,
+            migrate_unencrypted_data=self.migrate_unencrypted_data,
+            schema_migrations={
+                persistent_id: {
+                    column: api.ColumnMigration(
+                        renamed_from=migration.renamed_from,
+                        fill_value=migration.fill_value,
+                    )
+                    for column, migration in migrations.items()
+                }
+                for persistent_id, migrations in self.schema_migrations.items()
+            },
+            keep_last_checkpoints=self.keep_last_checkpoints,
+            keep_checkpoints_ms=(
+                int(self.keep_checkpoints_days * 24 * 60 * 60 * 1000)
+                if self.keep_checkpoints_days is not None
+                else None
+            ),
+            background_checkpoints=self.background_checkpoints,
+        )
+
+    def savepoint(self, name: str) -> None:
+        """
+        Request a named savepoint of the computation run with this config. It is taken
+        at the next commit of the persisted state, consistently in all workers, and
+        kept until it is overwritten by a savepoint with the same name. The computation
+        can be later restarted from it with ``pw.persistence.restore_savepoint``, e.g.
+        to upgrade the program or to run it on a copy of the storage.
+
+        A savepoint can also be requested with ``POST /savepoint/NAME`` to the
+        monitoring http server, if it is enabled. In a computation run in several
+        processes, the savepoint has to be requested in each of them.
+
+        Args:
+            name: the name of the savepoint. Only letters, digits, ``_`` and ``-`` are
+                allowed.
+        """
+        self.engine_config.request_savepoint(name)
+
+    def register_checkpoint_hooks(
+        self,
+        *,
+        on_checkpoint_start: Callable[[int | None], None] | None = None,
+        on_checkpoint_complete: Callable[[int | None], None] | None = None,
+        on_restore_complete: Callable[[int], None] | None = None,
+    ) -> None:
+        """
+        Register callbacks coordinating external systems, e.g. a metadata catalog or
+        a sink flushed at the checkpoints, with the checkpoints of the persisted state.
+        The callbacks are called with the time of the checkpoint, which is ``None`` for
+        the final checkpoint, made after all output is finished. In a computation run
+        in several processes, each of them calls its own callbacks.
+
+        Args:
+            on_checkpoint_start: called when a checkpoint of the state up to the given \
+time starts.
+            on_checkpoint_complete: called after the checkpoint of the state up to the \
+given time is committed. It's not called if the checkpoint fails.
+            on_restore_complete: called once per run, when the persisted state is \
+replayed and the outputs have finished the time of the replayed data.
+        """
+        self.engine_config.register_checkpoint_hooks(
+            on_checkpoint_start=on_checkpoint_start,
+            on_checkpoint_complete=on_checkpoint_complete,
+            on_restore_complete=on_restore_complete,
         )
 
     def on_before_run(self):
         self.snapshot_storage.store_path_in_env_variable()
+
+
+def restore_savepoint(config: Config, name: str) -> None:
+    """
+    Restore the persisted state saved in the savepoint ``name``, so that the next run
+    with this config starts from it. The state persisted after the savepoint is
+    removed, so to keep it, restore the savepoint on a copy of the storage.
+
+    Args:
+        config: the persistence config of the computation.
+        name: the name of the savepoint, as given to ``Config.savepoint``.
+    """
+    api.restore_savepoint(config.engine_config, name)
+
+
+def export_to_parquet(
+    config: Config, persistent_id: str, path: str | os.PathLike
+) -> None:
+    """
+    Export the persisted rows of the input with ``persistent_id`` to a Parquet file.
+    Only the rows of the inputs are persisted, so they are exported as of the last
+    committed state, or as of ``config.replay_until``, if it's set. The file has an
+    ``id`` column with the keys of the rows and a column for each column of the input.
+
+    Args:
+        config: the persistence config of the computation.
+        persistent_id: the persistent id of the input.
+        path: the path of the Parquet file.
+    """
+    columns, rows = api.export_persisted_rows(config.engine_config, persistent_id)
+    df = pd.DataFrame([values for _key, values in rows], columns=columns, dtype=object)
+    df.insert(0, "id", [str(int(key)) for key, _values in rows])
+    df.to_parquet(path)
+
+
+def import_from_parquet(
+    config: Config, persistent_id: str, path: str | os.PathLike
+) -> None:
+    """
+    Replace the persisted rows of the input with ``persistent_id`` with the rows of a
+    Parquet file exported with ``export_to_parquet``. The position in the source of
+    the input is kept, so the next run continues reading it where the persisted state
+    ends.
+
+    Args:
+        config: the persistence config of the computation.
+        persistent_id: the persistent id of the input.
+        path: the path of the Parquet file.
+    """
+    df = pd.read_parquet(path)
+    columns = [column for column in df.columns if column != "id"]
+    keys = [api.unsafe_make_pointer(int(key)) for key in df["id"]]
+    values = [df[column].tolist() for column in columns]
+    rows = [(key, list(row_values)) for key, *row_values in zip(keys, *values)]
+    api.import_persisted_rows(config.engine_config, persistent_id, columns, rows)
@@ -1,6 +1,7 @@
 # Copyright © 2024 Pathway
 
 from pathway.internals.custom_reducers import (
+    retractable_reducer,
     stateful_many,
     stateful_single,
     udf_reducer,
@@ -33,6 +34,7 @@ __all__ = [
     "min",
     "ndarray",
     "npsum",
+    "retractable_reducer",
     "sorted_tuple",
     "stateful_many",
     "stateful_single",
@@ -1,8 +1,17 @@
 # Copyright © 2024 Pathway
 
 
+from .analytic import lag, lead, rank
+from .cumulative import running_avg, running_max, running_min, running_sum
 from .diff import diff
 
 __all__ = [
     "diff",
+    "lag",
+    "lead",
+    "rank",
+    "running_avg",
+    "running_max",
+    "running_min",
+    "running_sum",
 ]
@@ -0,0 +1,195 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pathway as pw
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+
+
+@check_arg_types
+@trace_user_frame
+def rank(
+    self: pw.Table,
+    key: pw.ColumnExpression,
+    *,
+    instance: pw.ColumnExpression | None = None,
+    descending: bool = False,
+) -> pw.Table:
+    """
+    Numbers the rows of a table in the order defined by ``key``.
+
+    Three columns are added, all starting from 1:
+
+    - ``row_number``: the position of a row in the order. Rows with equal values of
+      ``key`` are ordered by their ids.
+    - ``rank``: one more than the number of rows with a smaller ``key``. Rows with
+      equal values of ``key`` get the same rank and the next rank is skipped.
+    - ``dense_rank``: one more than the number of distinct smaller values of ``key``.
+
+    The numbers are maintained incrementally. A change of a row updates the numbers of
+    the rows of its instance only.
+
+    Args:
+        - ``key`` (``ColumnExpression``): An expression to order by.
+        - ``instance`` (``ColumnExpression`` or ``None``): An expression with instance.
+            If set, rows are numbered within every instance separately.
+        - ``descending`` (``bool``): If True, rows with larger values of ``key`` come
+            first.
+
+    Returns:
+        ``Table``: The table with ``row_number``, ``rank`` and ``dense_rank`` columns
+        added.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> table = pw.debug.table_from_markdown('''
+    ... name  | team | score
+    ... Alice | A    | 90
+    ... Bob   | A    | 80
+    ... Carol | A    | 90
+    ... David | A    | 70
+    ... Eve   | B    | 60
+    ... ''')
+    >>> ranked = pw.ordered.rank(
+    ...     table, pw.this.score, instance=pw.this.team, descending=True
+    ... )
+    >>> pw.debug.compute_and_print(
+    ...     ranked.select(pw.this.name, pw.this.rank, pw.this.dense_rank),
+    ...     include_id=False,
+    ... )
+    name  | rank | dense_rank
+    Alice | 1    | 1
+    Bob   | 3    | 2
+    Carol | 1    | 1
+    David | 4    | 3
+    Eve   | 1    | 1
+    """
+    return self + self._rank(key, instance=instance, descending=descending)
+
+
+def _shifted_pointers(
+    self: pw.Table,
+    key: pw.ColumnExpression,
+    instance: pw.ColumnExpression | None,
+    offset: int,
+    direction: str,
+) -> pw.Table:
+    if offset <= 0:
+        raise ValueError("offset has to be positive.")
+    ordered = self.sort(key=key, instance=instance)
+    pointers = ordered.select(pointer=ordered[direction])
+    for _ in range(offset - 1):
+        pointers = pointers.select(
+            pointer=ordered.ix(pointers.pointer, optional=True)[direction]
+        )
+    return pointers
+
+
+def _shifted_values(
+    self: pw.Table,
+    pointers: pw.Table,
+    values: tuple[pw.ColumnReference, ...],
+    prefix: str,
+) -> pw.Table:
+    shifted = self.ix(pointers.pointer, optional=True)
+    return self + pointers.select(
+        **{prefix + value.name: shifted[value.name] for value in values}
+    )
+
+
+@check_arg_types
+@trace_user_frame
+def lag(
+    self: pw.Table,
+    key: pw.ColumnExpression,
+    *values: pw.ColumnReference,
+    instance: pw.ColumnExpression | None = None,
+    offset: int = 1,
+) -> pw.Table:
+    """
+    Adds the values of ``values`` columns from the row ``offset`` positions earlier
+    in the order defined by ``key``.
+
+    Args:
+        - ``key`` (``ColumnExpression``): An expression to order by. Rows with equal
+            values of ``key`` are ordered by their ids.
+        - ``*values`` (``ColumnReference``): The columns to take values from.
+        - ``instance`` (``ColumnExpression`` or ``None``): An expression with instance.
+            If set, only rows of the same instance are considered.
+        - ``offset`` (``int``): How many rows back to look. Has to be positive.
+
+    Returns:
+        ``Table``: The table with a ``lag_<name>`` column added for every column in
+        ``values``. It is ``None`` if there are fewer than ``offset`` earlier rows.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> table = pw.debug.table_from_markdown('''
+    ... t | price
+    ... 1 | 10
+    ... 2 | 12
+    ... 3 | 11
+    ... 4 | 15
+    ... ''')
+    >>> result = pw.ordered.lag(table, pw.this.t, pw.this.price, offset=2)
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    t | price | lag_price
+    1 | 10    |
+    2 | 12    |
+    3 | 11    | 10
+    4 | 15    | 12
+    """
+    values = tuple(self[value] for value in values)
+    pointers = _shifted_pointers(self, key, instance, offset, "prev")
+    return _shifted_values(self, pointers, values, "lag_")
+
+
+@check_arg_types
+@trace_user_frame
+def lead(
+    self: pw.Table,
+    key: pw.ColumnExpression,
+    *values: pw.ColumnReference,
+    instance: pw.ColumnExpression | None = None,
+    offset: int = 1,
+) -> pw.Table:
+    """
+    Adds the values of ``values`` columns from the row ``offset`` positions later
+    in the order defined by ``key``.
+
+    Args:
+        - ``key`` (``ColumnExpression``): An expression to order by. Rows with equal
+            values of ``key`` are ordered by their ids.
+        - ``*values`` (``ColumnReference``): The columns to take values from.
+        - ``instance`` (``ColumnExpression`` or ``None``): An expression with instance.
+            If set, only rows of the same instance are considered.
+        - ``offset`` (``int``): How many rows forward to look. Has to be positive.
+
+    Returns:
+        ``Table``: The table with a ``lead_<name>`` column added for every column in
+        ``values``. It is ``None`` if there are fewer than ``offset`` later rows.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> table = pw.debug.table_from_markdown('''
+    ... t | price
+    ... 1 | 10
+    ... 2 | 12
+    ... 3 | 11
+    ... 4 | 15
+    ... ''')
+    >>> result = pw.ordered.lead(table, pw.this.t, pw.this.price)
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    t | price | lead_price
+    1 | 10    | 12
+    2 | 12    | 11
+    3 | 11    | 15
+    4 | 15    |
+    """
+    values = tuple(self[value] for value in values)
+    pointers = _shifted_pointers(self, key, instance, offset, "next")
+    return _shifted_values(self, pointers, values, "lead_")
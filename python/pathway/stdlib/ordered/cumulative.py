@@ -0,0 +1,226 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pathway as pw
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+
+
+def _running(
+    self: pw.Table,
+    time: pw.ColumnExpression,
+    values: tuple[pw.ColumnReference, ...],
+    instance: pw.ColumnExpression | None,
+    prefix: str,
+    reducer,
+) -> pw.Table:
+    values = tuple(self[value] for value in values)
+    aggregates = self._cumulative(
+        time,
+        instance=instance,
+        **{prefix + value.name: reducer(value) for value in values},
+    )
+    return self + aggregates
+
+
+@check_arg_types
+@trace_user_frame
+def running_sum(
+    self: pw.Table,
+    time: pw.ColumnExpression,
+    *values: pw.ColumnReference,
+    instance: pw.ColumnExpression | None = None,
+) -> pw.Table:
+    """
+    Adds running sums of the ``values`` columns in the order defined by ``time``.
+
+    The running sum of a row covers the row and all rows preceding it. Rows with
+    equal values of ``time`` are ordered by their ids. The sums are maintained
+    incrementally, also when rows arrive out of order: a row inserted in the middle
+    updates the sums of the rows following it.
+
+    Args:
+        - ``time`` (``ColumnExpression``): An expression to order by, usually an
+            event time.
+        - ``*values`` (``ColumnReference``): The columns to sum.
+        - ``instance`` (``ColumnExpression`` or ``None``): An expression with instance.
+            If set, sums are computed within every instance separately.
+
+    Returns:
+        ``Table``: The table with a ``running_sum_<name>`` column added for every
+        column in ``values``.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> table = pw.debug.table_from_markdown('''
+    ... t | user | amount
+    ... 1 | a    | 10
+    ... 2 | b    | 5
+    ... 3 | a    | 20
+    ... 4 | a    | 5
+    ... ''')
+    >>> result = pw.ordered.running_sum(
+    ...     table, pw.this.t, pw.this.amount, instance=pw.this.user
+    ... )
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    t | user | amount | running_sum_amount
+    1 | a    | 10     | 10
+    2 | b    | 5      | 5
+    3 | a    | 20     | 30
+    4 | a    | 5      | 35
+    """
+    return _running(self, time, values, instance, "running_sum_", pw.reducers.sum)
+
+
+@check_arg_types
+@trace_user_frame
+def running_avg(
+    self: pw.Table,
+    time: pw.ColumnExpression,
+    *values: pw.ColumnReference,
+    instance: pw.ColumnExpression | None = None,
+) -> pw.Table:
+    """
+    Adds running averages of the ``values`` columns in the order defined by ``time``.
+
+    The running average of a row covers the row and all rows preceding it. Rows with
+    equal values of ``time`` are ordered by their ids. The averages are maintained
+    incrementally, also when rows arrive out of order.
+
+    Args:
+        - ``time`` (``ColumnExpression``): An expression to order by, usually an
+            event time.
+        - ``*values`` (``ColumnReference``): The columns to average.
+        - ``instance`` (``ColumnExpression`` or ``None``): An expression with instance.
+            If set, averages are computed within every instance separately.
+
+    Returns:
+        ``Table``: The table with a ``running_avg_<name>`` column added for every
+        column in ``values``.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> table = pw.debug.table_from_markdown('''
+    ... t | amount
+    ... 1 | 10
+    ... 2 | 20
+    ... 3 | 60
+    ... ''')
+    >>> result = pw.ordered.running_avg(table, pw.this.t, pw.this.amount)
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    t | amount | running_avg_amount
+    1 | 10     | 10.0
+    2 | 20     | 15.0
+    3 | 60     | 30.0
+    """
+    values = tuple(self[value] for value in values)
+    sums = self._cumulative(
+        time,
+        instance=instance,
+        _pw_count=pw.reducers.count(),
+        **{f"_pw_sum_{value.name}": pw.reducers.sum(value) for value in values},
+    )
+    return self + sums.select(
+        **{
+            f"running_avg_{value.name}": sums[f"_pw_sum_{value.name}"]
+            / sums._pw_count
+            for value in values
+        }
+    )
+
+
+@check_arg_types
+@trace_user_frame
+def running_min(
+    self: pw.Table,
+    time: pw.ColumnExpression,
+    *values: pw.ColumnReference,
+    instance: pw.ColumnExpression | None = None,
+) -> pw.Table:
+    """
+    Adds running minima of the ``values`` columns in the order defined by ``time``.
+
+    The running minimum of a row covers the row and all rows preceding it. Rows with
+    equal values of ``time`` are ordered by their ids. The minima are maintained
+    incrementally, also when rows arrive out of order or are deleted.
+
+    Args:
+        - ``time`` (``ColumnExpression``): An expression to order by, usually an
+            event time.
+        - ``*values`` (``ColumnReference``): The columns to take minima of.
+        - ``instance`` (``ColumnExpression`` or ``None``): An expression with instance.
+            If set, minima are computed within every instance separately.
+
+    Returns:
+        ``Table``: The table with a ``running_min_<name>`` column added for every
+        column in ``values``.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> table = pw.debug.table_from_markdown('''
+    ... t | price
+    ... 1 | 12
+    ... 2 | 15
+    ... 3 | 9
+    ... 4 | 11
+    ... ''')
+    >>> result = pw.ordered.running_min(table, pw.this.t, pw.this.price)
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    t | price | running_min_price
+    1 | 12    | 12
+    2 | 15    | 12
+    3 | 9     | 9
+    4 | 11    | 9
+    """
+    return _running(self, time, values, instance, "running_min_", pw.reducers.min)
+
+
+@check_arg_types
+@trace_user_frame
+def running_max(
+    self: pw.Table,
+    time: pw.ColumnExpression,
+    *values: pw.ColumnReference,
+    instance: pw.ColumnExpression | None = None,
+) -> pw.Table:
+    """
+    Adds running maxima of the ``values`` columns in the order defined by ``time``.
+
+    The running maximum of a row covers the row and all rows preceding it. Rows with
+    equal values of ``time`` are ordered by their ids. The maxima are maintained
+    incrementally, also when rows arrive out of order or are deleted.
+
+    Args:
+        - ``time`` (``ColumnExpression``): An expression to order by, usually an
+            event time.
+        - ``*values`` (``ColumnReference``): The columns to take maxima of.
+        - ``instance`` (``ColumnExpression`` or ``None``): An expression with instance.
+            If set, maxima are computed within every instance separately.
+
+    Returns:
+        ``Table``: The table with a ``running_max_<name>`` column added for every
+        column in ``values``.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> table = pw.debug.table_from_markdown('''
+    ... t | price
+    ... 1 | 12
+    ... 2 | 15
+    ... 3 | 9
+    ... 4 | 17
+    ... ''')
+    >>> result = pw.ordered.running_max(table, pw.this.t, pw.this.price)
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    t | price | running_max_price
+    1 | 12    | 12
+    2 | 15    | 15
+    3 | 9     | 15
+    4 | 17    | 17
+    """
+    return _running(self, time, values, instance, "running_max_", pw.reducers.max)
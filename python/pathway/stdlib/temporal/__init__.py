@@ -15,6 +15,8 @@ from ._asof_now_join import (
     asof_now_join_inner,
     asof_now_join_left,
 )
+from ._date_range import date_range
+from ._deduplicate import deduplicate_within
 from ._interval_join import (
     Interval,
     IntervalJoinResult,
@@ -25,6 +27,15 @@ from ._interval_join import (
     interval_join_outer,
     interval_join_right,
 )
+from ._late import split_late
+from ._pattern import match_pattern
+from ._range_join import (
+    RangeJoinResult,
+    range_join,
+    range_join_inner,
+    range_join_left,
+)
+from ._ttl import with_ttl
 from ._window import Window, intervals_over, session, sliding, tumbling, windowby
 from ._window_join import (
     WindowJoinResult,
@@ -45,6 +56,7 @@ __all__ = [
     "AsofJoinResult",
     "AsofNowJoinResult",
     "IntervalJoinResult",
+    "RangeJoinResult",
     "WindowJoinResult",
     "asof_join",
     "asof_join_left",
@@ -53,12 +65,16 @@ __all__ = [
     "asof_now_join",
     "asof_now_join_inner",
     "asof_now_join_left",
+    "date_range",
     "interval_join",
     "interval_join_inner",
     "interval_join_left",
     "interval_join_right",
     "interval_join_outer",
     "intervals_over",
+    "range_join",
+    "range_join_inner",
+    "range_join_left",
     "window_join",
     "window_join_inner",
     "window_join_left",
@@ -72,6 +88,10 @@ __all__ = [
     "tumbling",
     "sliding",
     "session",
+    "split_late",
+    "match_pattern",
+    "deduplicate_within",
+    "with_ttl",
     "common_behavior",
     "CommonBehavior",
     "ExactlyOnceBehavior",
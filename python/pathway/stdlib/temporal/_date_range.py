@@ -0,0 +1,114 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import datetime
+import time
+
+import pathway.internals as pw
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+
+_NAIVE_EPOCH = datetime.datetime(1970, 1, 1)
+_UTC_EPOCH = datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def _to_nanoseconds(value: datetime.datetime | datetime.timedelta) -> int:
+    if isinstance(value, datetime.datetime):
+        epoch = _NAIVE_EPOCH if value.tzinfo is None else _UTC_EPOCH
+        value = value - epoch
+    return value // datetime.timedelta(microseconds=1) * 1000
+
+
+class _TickSchema(pw.Schema):
+    timestamp: int = pw.column_definition(primary_key=True)
+
+
+@check_arg_types
+@trace_user_frame
+def date_range(
+    start: datetime.datetime,
+    end: datetime.datetime | None = None,
+    *,
+    step: datetime.timedelta,
+    autocommit_duration_ms: int = 1000,
+) -> pw.Table:
+    """Generates a table with a single column ``t`` containing DateTimes from
+    ``start`` (inclusive) to ``end`` (exclusive) separated by ``step``.
+
+    If ``end`` is not provided, the table is a stream that never finishes. Each tick
+    is emitted once the wall clock reaches it, so the table can be used as a clock
+    driving periodic computations. Ticks lying in the past are emitted immediately.
+    The generator then runs in a daemon thread, so waiting for the next tick
+    never prevents the program from exiting.
+
+    The column is of type DateTimeUtc if ``start`` is timezone-aware and
+    DateTimeNaive otherwise.
+
+    Args:
+        start: the first generated DateTime.
+        end: the upper bound (exclusive) of generated DateTimes. If None, \
+DateTimes are generated indefinitely.
+        step: the distance between two consecutive DateTimes. Has to be positive.
+        autocommit_duration_ms: the maximum time between two commits. Every
+          autocommit_duration_ms milliseconds, the generated ticks are
+          committed and pushed into Pathway's computation graph.
+
+    Returns:
+        Table: a table with a single column ``t``.
+
+    Example:
+
+    >>> import datetime
+    >>> import pathway as pw
+    >>> t = pw.temporal.date_range(
+    ...     datetime.datetime(2024, 1, 1, 12),
+    ...     datetime.datetime(2024, 1, 1, 13),
+    ...     step=datetime.timedelta(minutes=15),
+    ... )
+    >>> pw.debug.compute_and_print(t, include_id=False)
+    t
+    2024-01-01 12:00:00
+    2024-01-01 12:15:00
+    2024-01-01 12:30:00
+    2024-01-01 12:45:00
+    """
+    if step <= datetime.timedelta(0):
+        raise ValueError("date_range error: step has to be positive.")
+    if end is not None and (start.tzinfo is None) != (end.tzinfo is None):
+        raise ValueError(
+            "date_range error: start and end have to be both timezone-aware "
+            "or both naive."
+        )
+
+    start_ns = _to_nanoseconds(start)
+    end_ns = _to_nanoseconds(end) if end is not None else None
+    step_ns = _to_nanoseconds(step)
+    is_utc = start.tzinfo is not None
+
+    class DateRangeSubject(pw.io.python.ConnectorSubject):
+        def run(self):
+            timestamp = start_ns
+            while end_ns is None or timestamp < end_ns:
+                if end_ns is None:
+                    now = datetime.datetime.now(
+                        datetime.timezone.utc if is_utc else None
+                    )
+                    delay = (timestamp - _to_nanoseconds(now)) / 1_000_000_000
+                    if delay > 0:
+                        time.sleep(delay)
+                self.next_json({"timestamp": timestamp})
+                timestamp += step_ns
+
+        def _is_finite(self) -> bool:
+            return end_ns is not None
+
+    ticks = pw.io.python.read(
+        DateRangeSubject(),
+        schema=_TickSchema,
+        autocommit_duration_ms=autocommit_duration_ms,
+    )
+    if is_utc:
+        return ticks.select(t=pw.this.timestamp.dt.utc_from_timestamp(unit="ns"))
+    else:
+        return ticks.select(t=pw.this.timestamp.dt.from_timestamp(unit="ns"))
@@ -0,0 +1,87 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pathway.internals as pw
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+
+from .utils import IntervalType, TimeEventType, check_joint_types, zero_length_interval
+
+
+@check_arg_types
+@trace_user_frame
+def deduplicate_within(
+    table: pw.Table,
+    *on: pw.ColumnExpression,
+    time_expr: pw.ColumnExpression,
+    horizon: IntervalType,
+) -> pw.Table:
+    """Drops rows that duplicate a recently kept row.
+
+    Two rows are duplicates if they have equal values of the ``on`` expressions, or
+    of all columns if ``on`` is empty. A row is dropped if a duplicate of it was kept
+    less than ``horizon`` earlier, as measured by ``time_expr``. Otherwise, it is kept
+    and suppresses its duplicates for the next ``horizon``.
+
+    Only fingerprints of the kept rows and their times are stored. They are pruned
+    once the watermark, the maximal ``time_expr`` seen so far, is at least
+    ``horizon`` past them. Rows that are later than ``horizon`` with respect to the
+    watermark are dropped, as their duplicates might have been pruned already.
+
+    The operator is meant for append-only streams: deletions in ``table`` are
+    ignored, and a kept row stays in the result.
+
+    Args:
+        table: the table to deduplicate.
+        *on: expressions identifying duplicates. If empty, all columns are used.
+        time_expr: time of a row.
+        horizon: time for which a kept row suppresses its duplicates. Has to be
+            positive.
+
+    Returns:
+        Table: the kept rows of ``table``.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> t = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | sensor | t  | __time__
+    ...   1 | a      | 1  |     2
+    ...   2 | a      | 3  |     4
+    ...   3 | b      | 4  |     4
+    ...   4 | a      | 7  |     6
+    ... '''
+    ... )
+    >>> result = pw.temporal.deduplicate_within(
+    ...     t, t.sensor, time_expr=t.t, horizon=5
+    ... )
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    sensor | t
+    a      | 1
+    a      | 7
+    b      | 4
+    """
+    check_joint_types(
+        {
+            "time_expr": (time_expr, TimeEventType),
+            "horizon": (horizon, IntervalType),
+        }
+    )
+    if horizon <= zero_length_interval(type(horizon)):  # type: ignore[operator]
+        raise ValueError("horizon has to be positive.")
+    if not on:
+        on = tuple(table)
+
+    table_with_times = table.with_columns(
+        _pw_fingerprint=table.pointer_from(*on),
+        _pw_time=time_expr,
+        _pw_expiration=time_expr + horizon,
+    )
+    result = table_with_times._deduplicate_within(
+        pw.this._pw_fingerprint, pw.this._pw_time, pw.this._pw_expiration
+    )
+    return result.without(
+        pw.this._pw_fingerprint, pw.this._pw_time, pw.this._pw_expiration
+    )
@@ -0,0 +1,79 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pathway.internals as pw
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+
+from .utils import IntervalType, TimeEventType, check_joint_types, zero_length_interval
+
+
+@check_arg_types
+@trace_user_frame
+def split_late(
+    table: pw.Table,
+    time_expr: pw.ColumnExpression,
+    allowed_lateness: IntervalType,
+) -> tuple[pw.Table, pw.Table]:
+    """Splits a table into entries arriving on time and entries arriving too late.
+
+    The watermark is the maximal ``time_expr`` seen so far. An entry is too late if
+    its time is at least ``allowed_lateness`` older than the watermark at the moment
+    it arrives. This is the same rule ``common_behavior(cutoff=...)`` uses to
+    ignore entries, so the on-time table can be passed to temporal operators while
+    the too-late entries are handled separately, e.g. written to a dedicated sink,
+    instead of being dropped silently. Deletions of entries are routed in the same
+    way, so a too-late deletion of an on-time entry ends up in the second table.
+
+    As in temporal operators, the watermark is updated only after all entries that
+    arrived at the same moment are processed.
+
+    Args:
+        table: the table to split.
+        time_expr: time of an entry.
+        allowed_lateness: how much older than the watermark an entry can be to still
+            be on time. Has to be non-negative.
+
+    Returns:
+        tuple[Table, Table]: entries that are on time and entries that are too late.
+        Both have the columns of ``table``.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> t = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | t  | __time__
+    ...   1 | 10 |     2
+    ...   2 | 4  |     4
+    ...   3 | 8  |     4
+    ...   4 | 14 |     6
+    ...   5 | 12 |     8
+    ... '''
+    ... )
+    >>> on_time, late = pw.temporal.split_late(t, t.t, allowed_lateness=3)
+    >>> pw.debug.compute_and_print(on_time, include_id=False)
+    t
+    8
+    10
+    12
+    14
+    >>> pw.debug.compute_and_print(late, include_id=False)
+    t
+    4
+    """
+    check_joint_types(
+        {
+            "time_expr": (time_expr, TimeEventType),
+            "allowed_lateness": (allowed_lateness, IntervalType),
+        }
+    )
+    if allowed_lateness < zero_length_interval(type(allowed_lateness)):  # type: ignore[operator]
+        raise ValueError("allowed_lateness has to be non-negative.")
+
+    table_with_time = table.with_columns(_pw_time=time_expr)
+    threshold = pw.this._pw_time + allowed_lateness
+    on_time = table_with_time._freeze(threshold, pw.this._pw_time)
+    late = table_with_time._freeze_late(threshold, pw.this._pw_time)
+    return on_time.without(pw.this._pw_time), late.without(pw.this._pw_time)
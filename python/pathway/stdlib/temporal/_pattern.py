@@ -0,0 +1,176 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+
+import pathway.internals as pw
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+from pathway.stdlib.utils.col import unpack_col
+
+from .utils import IntervalType, TimeEventType, check_joint_types
+
+
+@dataclass(frozen=True)
+class _Step:
+    variable: str
+    forbidden: tuple[str, ...]
+
+
+def _parse_pattern(pattern: str, variables: list[str]) -> list[_Step]:
+    steps: list[_Step] = []
+    forbidden: list[str] = []
+    for token in pattern.split():
+        negated = token.startswith("!")
+        name = token[1:] if negated else token
+        if name not in variables:
+            raise ValueError(f"variable {name} used in the pattern is not defined.")
+        if not negated:
+            steps.append(_Step(name, tuple(forbidden)))
+            forbidden = []
+        elif not steps:
+            raise ValueError("pattern cannot start with a negated variable.")
+        else:
+            forbidden.append(name)
+    if not steps:
+        raise ValueError("pattern has to contain at least one variable.")
+    names = [step.variable for step in steps]
+    if len(set(names)) != len(names):
+        raise ValueError("non-negated variables of the pattern have to be distinct.")
+    if forbidden:
+        raise ValueError("pattern cannot end with a negated variable.")
+    return steps
+
+
+def _find_matches(events, steps: list[_Step], variables: list[str], within):
+    # events are tuples (time, id, *flags), sorted by time and id
+    position = {name: i + 2 for i, name in enumerate(variables)}
+    matches = []
+    for start, event in enumerate(events):
+        if not event[position[steps[0].variable]]:
+            continue
+        captured = [event[1]]
+        for candidate in events[start + 1 :]:
+            if len(captured) == len(steps):
+                break
+            if within is not None and candidate[0] - event[0] > within:
+                break
+            step = steps[len(captured)]
+            if candidate[position[step.variable]]:
+                captured.append(candidate[1])
+            elif any(candidate[position[name]] for name in step.forbidden):
+                break
+        if len(captured) == len(steps):
+            matches.append(tuple(captured))
+    return tuple(matches)
+
+
+@check_arg_types
+@trace_user_frame
+def match_pattern(
+    table: pw.Table,
+    time_expr: pw.ColumnExpression,
+    pattern: str,
+    *,
+    within: IntervalType | None = None,
+    instance: pw.ColumnExpression | None = None,
+    **define: pw.ColumnExpression,
+) -> pw.Table:
+    """Finds sequences of rows following a pattern, like SQL ``MATCH_RECOGNIZE``.
+
+    Rows are ordered by ``time_expr`` and then by their ids. Every keyword argument
+    defines a variable: a row can be assigned to the variable if the given boolean
+    expression is true for it. The pattern is a space-separated sequence of variables,
+    e.g. ``"A B C"`` matches a row of ``A``, followed later by a row of ``B``, followed
+    later by a row of ``C``. Other rows may occur in between, unless they are forbidden:
+    a variable preceded by ``!`` must not occur between its neighbours, e.g.
+    ``"A !C B"`` matches ``A`` followed by ``B`` with no ``C`` in between.
+
+    Every row of the first variable starts at most one match, ending at the earliest
+    possible rows. If ``within`` is set, the last row of a match has to be at most
+    ``within`` after the first one.
+
+    The matches of an instance are recomputed whenever a row of this instance changes,
+    so rows arriving out of order are handled correctly. Since all rows of an instance
+    are kept, consider bounding them with ``pw.temporal.with_ttl``.
+
+    Args:
+        table: the table with rows to match.
+        time_expr: time of a row.
+        pattern: the pattern to find.
+        within: maximal time between the first and the last row of a match.
+        instance: if set, matches are searched within every instance separately.
+        **define: boolean expressions defining the variables used in the pattern.
+
+    Returns:
+        Table: a table with one row per match. Its id is the id of the first row of
+        the match. For every non-negated variable, it has a column of the same name
+        with the id of the matched row. Columns ``match_start`` and ``match_end``
+        contain the times of the first and the last row of the match.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> events = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | user | t  | kind
+    ...   1 | a    | 1  | cart
+    ...   2 | a    | 3  | remove
+    ...   3 | a    | 4  | buy
+    ...   4 | b    | 2  | cart
+    ...   5 | b    | 5  | buy
+    ...   6 | b    | 9  | cart
+    ...   7 | b    | 20 | buy
+    ... '''
+    ... )
+    >>> matches = pw.temporal.match_pattern(
+    ...     events,
+    ...     events.t,
+    ...     "A !C B",
+    ...     within=5,
+    ...     instance=events.user,
+    ...     A=events.kind == "cart",
+    ...     B=events.kind == "buy",
+    ...     C=events.kind == "remove",
+    ... )
+    >>> pw.debug.compute_and_print(
+    ...     matches.select(pw.this.match_start, pw.this.match_end), include_id=False
+    ... )
+    match_start | match_end
+    2           | 5
+    """
+    variables = list(define)
+    steps = _parse_pattern(pattern, variables)
+    if within is not None:
+        check_joint_types(
+            {
+                "time_expr": (time_expr, TimeEventType),
+                "within": (within, IntervalType),
+            }
+        )
+
+    flags = table.select(
+        _pw_time=time_expr,
+        _pw_instance=instance if instance is not None else 0,
+        _pw_event=pw.make_tuple(time_expr, table.id, *define.values()),
+    )
+    reduced = flags.groupby(flags._pw_instance).reduce(
+        _pw_events=pw.reducers.sorted_tuple(flags._pw_event)
+    )
+    matched = reduced.select(
+        _pw_matches=pw.apply(
+            lambda events: _find_matches(events, steps, variables, within),
+            reduced._pw_events,
+        )
+    ).flatten(pw.this._pw_matches)
+
+    names = [step.variable for step in steps]
+    result = unpack_col(matched._pw_matches, *names).update_types(
+        **{name: pw.Pointer for name in names}
+    )
+    result = result.with_id(result[names[0]])
+    return result.with_columns(
+        match_start=flags.ix(result[names[0]])._pw_time,
+        match_end=flags.ix(result[names[-1]])._pw_time,
+    )
@@ -0,0 +1,528 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import datetime
+from typing import Any
+
+import pathway.internals as pw
+from pathway.internals import dtype as dt
+from pathway.internals.arg_handlers import (
+    arg_handler,
+    join_kwargs_handler,
+    select_args_handler,
+)
+from pathway.internals.desugaring import (
+    DesugaringContext,
+    TableSubstitutionDesugaring,
+    combine_args_kwargs,
+    desugar,
+)
+from pathway.internals.joins import validate_join_condition
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+from pathway.internals.type_interpreter import eval_type
+
+from ._interval_join import _NonZeroDifferenceIntervalJoinResult
+from .temporal_behavior import CommonBehavior, apply_temporal_behavior
+from .utils import (
+    IntervalType,
+    TimeEventType,
+    check_joint_types,
+    get_default_origin,
+    zero_length_interval,
+)
+
+
+def _forget_expired_ranges(
+    events: pw.Table, ranges: pw.Table, behavior: CommonBehavior | None
+) -> pw.Table:
+    """Removes ranges ending at least `cutoff` before the maximal time of `events`.
+
+    The maximal time is tracked on a table containing both events and ranges. Ranges
+    have no time there, so only events advance it.
+    """
+    if behavior is None or behavior.cutoff is None:
+        return ranges
+    watermarked = pw.Table.concat_reindex(
+        events.select(
+            _pw_range_id=events.id,
+            _pw_is_range=False,
+            _pw_time=pw.this._pw_time,
+            _pw_threshold=pw.this._pw_time + behavior.cutoff,
+        ),
+        ranges.select(
+            _pw_range_id=ranges.id,
+            _pw_is_range=True,
+            _pw_time=None,
+            _pw_threshold=pw.this._pw_end + behavior.cutoff,
+        ),
+    )
+    watermarked = watermarked._freeze(pw.this._pw_threshold, pw.this._pw_time)
+    watermarked = watermarked._forget(
+        pw.this._pw_threshold, pw.this._pw_time, behavior.keep_results
+    )
+    alive = watermarked.filter(pw.this._pw_is_range).with_id(pw.this._pw_range_id)
+    pw.universes.promise_is_subset_of(alive, ranges)
+    return ranges.restrict(alive)
+
+
+def _bucket_range(first: int, last: int) -> list[int]:
+    return list(range(first, last + 1))
+
+
+class RangeJoinResult(DesugaringContext):
+    """Result of a range join between tables."""
+
+    _left: pw.Table
+    _right: pw.Table
+    _join_result: pw.JoinResult
+    _table_substitution: dict[pw.TableLike, pw.Table]
+    _mode: pw.JoinMode
+    _filter_out_results_of_forgetting: bool
+
+    def __init__(
+        self,
+        left: pw.Table,
+        right: pw.Table,
+        join_result: pw.JoinResult,
+        table_substitution: dict[pw.TableLike, pw.Table],
+        mode: pw.JoinMode,
+        _filter_out_results_of_forgetting: bool,
+    ):
+        self._left = left
+        self._right = right
+        self._join_result = join_result
+        self._table_substitution = table_substitution
+        self._mode = mode
+        self._filter_out_results_of_forgetting = _filter_out_results_of_forgetting
+        self._substitution = {
+            pw.left: left,
+            pw.right: right,
+            pw.this: pw.this,  # type: ignore[dict-item]
+        }
+
+    @staticmethod
+    def _range_join(
+        left: pw.Table,
+        right: pw.Table,
+        left_time_expression: pw.ColumnExpression,
+        right_start_expression: pw.ColumnExpression,
+        right_end_expression: pw.ColumnExpression,
+        *on: pw.ColumnExpression,
+        bucket_size: int | float | datetime.timedelta,
+        behavior: CommonBehavior | None = None,
+        mode: pw.JoinMode,
+        left_instance: pw.ColumnReference | None = None,
+        right_instance: pw.ColumnReference | None = None,
+    ) -> RangeJoinResult:
+        """Creates a RangeJoinResult. Time is divided into buckets of size `bucket_size`.
+        Each row of left is assigned to the bucket containing its time and each range
+        of right to all buckets it overlaps. Rows are joined within buckets and then
+        the result is filtered.
+        """
+        check_joint_types(
+            {
+                "self_time": (left_time_expression, TimeEventType),
+                "other_start": (right_start_expression, TimeEventType),
+                "other_end": (right_end_expression, TimeEventType),
+                "bucket_size": (bucket_size, IntervalType),
+            }
+        )
+        if bucket_size <= zero_length_interval(type(bucket_size)):  # type: ignore[operator]
+            raise ValueError("bucket_size has to be positive.")
+        if left == right:
+            raise ValueError(
+                "Cannot join table with itself. Use <table>.copy() as one of the arguments of the join."
+            )
+        if mode != pw.JoinMode.INNER and mode != pw.JoinMode.LEFT:
+            raise ValueError(
+                "range_join can only use modes pathway.JoinMode.INNER or pathway.JoinMode.LEFT"
+            )
+        if left_instance is not None and right_instance is not None:
+            on = (*on, left_instance == right_instance)
+        else:
+            assert left_instance is None and right_instance is None
+
+        shift = get_default_origin(eval_type(left_time_expression))
+        left_with_time = left.with_columns(_pw_time=left_time_expression)
+        right_with_range = right.with_columns(
+            _pw_time=right_start_expression, _pw_end=right_end_expression
+        )
+        right_with_range = _forget_expired_ranges(
+            left_with_time, right_with_range, behavior
+        )
+        left_with_time = apply_temporal_behavior(left_with_time, behavior)
+
+        left_bucketed = left_with_time.with_columns(
+            _pw_bucket=pw.cast(int, (pw.this._pw_time - shift) // bucket_size)
+        )
+        right_buckets = right_with_range.select(
+            _pw_bucket=pw.apply_with_type(
+                _bucket_range,
+                dt.List(dt.INT),
+                pw.cast(int, (pw.this._pw_time - shift) // bucket_size),
+                pw.cast(int, (pw.this._pw_end - shift) // bucket_size),
+            )
+        )
+        right_bucketed = right_buckets.flatten(
+            right_buckets._pw_bucket, *right_with_range
+        )
+
+        for cond in on:
+            cond_left, cond_right, cond = validate_join_condition(cond, left, right)
+            cond._left = left_bucketed[cond_left._name]
+            cond._right = right_bucketed[cond_right._name]
+
+        join_result = left_bucketed.join(
+            right_bucketed,
+            left_bucketed._pw_bucket == right_bucketed._pw_bucket,
+            *on,
+        ).filter(
+            (pw.right._pw_time <= pw.left._pw_time)
+            & (pw.left._pw_time < pw.right._pw_end)
+        )
+
+        table_substitution: dict[pw.TableLike, pw.Table] = {
+            left: left_bucketed,
+            right: right_bucketed,
+        }
+        filter_out_results_of_forgetting = (
+            behavior is not None
+            and behavior.cutoff is not None
+            and behavior.keep_results
+        )
+        return RangeJoinResult(
+            left_bucketed,
+            right_bucketed,
+            join_result,
+            table_substitution,
+            mode,
+            _filter_out_results_of_forgetting=filter_out_results_of_forgetting,
+        )
+
+    @property
+    def _desugaring(self) -> TableSubstitutionDesugaring:
+        return TableSubstitutionDesugaring(self._table_substitution)
+
+    @desugar
+    @arg_handler(handler=select_args_handler)
+    @trace_user_frame
+    def select(self, *args: pw.ColumnReference, **kwargs: Any) -> pw.Table:
+        """
+        Computes a result of a range join.
+
+        Args:
+            args: Column references.
+            kwargs: Column expressions with their new assigned names.
+
+        Returns:
+            Table: Created table.
+
+        Example:
+
+        >>> import pathway as pw
+        >>> events = pw.debug.table_from_markdown(
+        ...     '''
+        ...     | product | t
+        ...   1 | A       | 3
+        ...   2 | A       | 12
+        ...   3 | B       | 5
+        ... '''
+        ... )
+        >>> prices = pw.debug.table_from_markdown(
+        ...     '''
+        ...     | product | price | valid_from | valid_to
+        ...   1 | A       | 10    | 0          | 10
+        ...   2 | A       | 15    | 10         | 20
+        ...   3 | B       | 7     | 0          | 20
+        ... '''
+        ... )
+        >>> result = events.range_join(
+        ...     prices,
+        ...     events.t,
+        ...     prices.valid_from,
+        ...     prices.valid_to,
+        ...     events.product == prices.product,
+        ...     bucket_size=10,
+        ... ).select(events.product, events.t, prices.price)
+        >>> pw.debug.compute_and_print(result, include_id=False)
+        product | t  | price
+        A       | 3  | 10
+        A       | 12 | 15
+        B       | 5  | 7
+        """
+        all_args = combine_args_kwargs(
+            args, kwargs, exclude_columns={"_pw_time", "_pw_end", "_pw_bucket"}
+        )
+        joined = self._join_result.select(_pw_left_id=pw.left.id, **all_args)
+        result = joined.without(joined._pw_left_id)
+        if self._mode == pw.JoinMode.LEFT:
+            unmatched_left = _NonZeroDifferenceIntervalJoinResult._get_unmatched_rows(
+                joined, self._left, self._right, all_args, True
+            )
+            result = pw.Table.concat_reindex(result, unmatched_left)
+
+        if self._filter_out_results_of_forgetting:
+            result = result._filter_out_results_of_forgetting()
+        return result
+
+
+@desugar(substitution={pw.left: "self", pw.right: "other"})
+@arg_handler(handler=join_kwargs_handler(allow_how=True, allow_id=False))
+@check_arg_types
+@trace_user_frame
+def range_join(
+    self: pw.Table,
+    other: pw.Table,
+    self_time: pw.ColumnExpression,
+    other_start: pw.ColumnExpression,
+    other_end: pw.ColumnExpression,
+    *on: pw.ColumnExpression,
+    bucket_size: int | float | datetime.timedelta,
+    behavior: CommonBehavior | None = None,
+    how: pw.JoinMode = pw.JoinMode.INNER,
+    left_instance: pw.ColumnReference | None = None,
+    right_instance: pw.ColumnReference | None = None,
+) -> RangeJoinResult:
+    """Performs a range join of self with other. A row of self is joined with a row
+    of other if `other_start <= self_time < other_end` and conditions in `on` are
+    satisfied. It is useful for enriching events with dimension tables whose rows
+    are valid in a given period.
+
+    Args:
+        other:  the right side of a join.
+        self_time: time expression in self.
+        other_start: expression in other giving the start of the range (inclusive).
+        other_end: expression in other giving the end of the range (exclusive).
+        on:  a list of column expressions. Each must have == as the top level
+            operation and be of the form LHS: ColumnReference == RHS: ColumnReference.
+        bucket_size: rows are matched within time buckets of this size. A range of \
+            other is stored in every bucket it overlaps, so the size should be \
+            comparable to the typical length of a range.
+        behavior: defines a temporal behavior of a join. The watermark is \
+            the maximal already seen `self_time`. `delay` and `cutoff` work for \
+            self as in `interval_join`. Additionally, with `cutoff` set, a range \
+            of other is ignored and garbage collected once `other_end + cutoff` \
+            is less or equal to the watermark.
+        how: decides whether to run `range_join_inner` or `range_join_left`.
+            Default is INNER.
+        left_instance/right_instance: optional arguments describing partitioning of the data into separate instances
+
+    Returns:
+        RangeJoinResult: a result of the range join. A method `.select()`
+        can be called on it to extract relevant columns from the result of a join.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> events = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | product | t
+    ...   1 | A       | 3
+    ...   2 | A       | 12
+    ...   3 | A       | 25
+    ...   4 | B       | 5
+    ... '''
+    ... )
+    >>> prices = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | product | price | valid_from | valid_to
+    ...   1 | A       | 10    | 0          | 10
+    ...   2 | A       | 15    | 10         | 20
+    ...   3 | B       | 7     | 0          | 20
+    ... '''
+    ... )
+    >>> result = events.range_join(
+    ...     prices,
+    ...     events.t,
+    ...     prices.valid_from,
+    ...     prices.valid_to,
+    ...     events.product == prices.product,
+    ...     bucket_size=10,
+    ...     how=pw.JoinMode.LEFT,
+    ... ).select(events.product, events.t, prices.price)
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    product | t  | price
+    A       | 3  | 10
+    A       | 12 | 15
+    A       | 25 |
+    B       | 5  | 7
+    """
+    return RangeJoinResult._range_join(
+        self,
+        other,
+        self_time,
+        other_start,
+        other_end,
+        *on,
+        bucket_size=bucket_size,
+        behavior=behavior,
+        mode=how,
+        left_instance=left_instance,
+        right_instance=right_instance,
+    )
+
+
+@desugar(substitution={pw.left: "self", pw.right: "other"})
+@arg_handler(handler=join_kwargs_handler(allow_how=False, allow_id=False))
+@check_arg_types
+@trace_user_frame
+def range_join_inner(
+    self: pw.Table,
+    other: pw.Table,
+    self_time: pw.ColumnExpression,
+    other_start: pw.ColumnExpression,
+    other_end: pw.ColumnExpression,
+    *on: pw.ColumnExpression,
+    bucket_size: int | float | datetime.timedelta,
+    behavior: CommonBehavior | None = None,
+    left_instance: pw.ColumnReference | None = None,
+    right_instance: pw.ColumnReference | None = None,
+) -> RangeJoinResult:
+    """Performs a range join of self with other. A row of self is joined with a row
+    of other if `other_start <= self_time < other_end` and conditions in `on` are
+    satisfied. Rows of self without a matching range are dropped.
+
+    Args:
+        other:  the right side of a join.
+        self_time: time expression in self.
+        other_start: expression in other giving the start of the range (inclusive).
+        other_end: expression in other giving the end of the range (exclusive).
+        on:  a list of column expressions. Each must have == as the top level
+            operation and be of the form LHS: ColumnReference == RHS: ColumnReference.
+        bucket_size: size of time buckets in which rows are matched, see `range_join`.
+        behavior: defines a temporal behavior of a join, see `range_join`.
+        left_instance/right_instance: optional arguments describing partitioning of the data into separate instances
+
+    Returns:
+        RangeJoinResult: a result of the range join. A method `.select()`
+        can be called on it to extract relevant columns from the result of a join.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> events = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | product | t
+    ...   1 | A       | 3
+    ...   2 | A       | 12
+    ...   3 | A       | 25
+    ... '''
+    ... )
+    >>> prices = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | product | price | valid_from | valid_to
+    ...   1 | A       | 10    | 0          | 10
+    ...   2 | A       | 15    | 10         | 20
+    ... '''
+    ... )
+    >>> result = events.range_join_inner(
+    ...     prices,
+    ...     events.t,
+    ...     prices.valid_from,
+    ...     prices.valid_to,
+    ...     events.product == prices.product,
+    ...     bucket_size=10,
+    ... ).select(events.t, prices.price)
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    t  | price
+    3  | 10
+    12 | 15
+    """
+    return RangeJoinResult._range_join(
+        self,
+        other,
+        self_time,
+        other_start,
+        other_end,
+        *on,
+        bucket_size=bucket_size,
+        behavior=behavior,
+        mode=pw.JoinMode.INNER,
+        left_instance=left_instance,
+        right_instance=right_instance,
+    )
+
+
+@desugar(substitution={pw.left: "self", pw.right: "other"})
+@arg_handler(handler=join_kwargs_handler(allow_how=False, allow_id=False))
+@check_arg_types
+@trace_user_frame
+def range_join_left(
+    self: pw.Table,
+    other: pw.Table,
+    self_time: pw.ColumnExpression,
+    other_start: pw.ColumnExpression,
+    other_end: pw.ColumnExpression,
+    *on: pw.ColumnExpression,
+    bucket_size: int | float | datetime.timedelta,
+    behavior: CommonBehavior | None = None,
+    left_instance: pw.ColumnReference | None = None,
+    right_instance: pw.ColumnReference | None = None,
+) -> RangeJoinResult:
+    """Performs a range join of self with other. A row of self is joined with a row
+    of other if `other_start <= self_time < other_end` and conditions in `on` are
+    satisfied. Rows of self without a matching range are kept, with missing values
+    on the right side replaced with `None`.
+
+    Args:
+        other:  the right side of a join.
+        self_time: time expression in self.
+        other_start: expression in other giving the start of the range (inclusive).
+        other_end: expression in other giving the end of the range (exclusive).
+        on:  a list of column expressions. Each must have == as the top level
+            operation and be of the form LHS: ColumnReference == RHS: ColumnReference.
+        bucket_size: size of time buckets in which rows are matched, see `range_join`.
+        behavior: defines a temporal behavior of a join, see `range_join`.
+        left_instance/right_instance: optional arguments describing partitioning of the data into separate instances
+
+    Returns:
+        RangeJoinResult: a result of the range join. A method `.select()`
+        can be called on it to extract relevant columns from the result of a join.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> events = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | product | t
+    ...   1 | A       | 3
+    ...   2 | A       | 12
+    ...   3 | A       | 25
+    ... '''
+    ... )
+    >>> prices = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | product | price | valid_from | valid_to
+    ...   1 | A       | 10    | 0          | 10
+    ...   2 | A       | 15    | 10         | 20
+    ... '''
+    ... )
+    >>> result = events.range_join_left(
+    ...     prices,
+    ...     events.t,
+    ...     prices.valid_from,
+    ...     prices.valid_to,
+    ...     events.product == prices.product,
+    ...     bucket_size=10,
+    ... ).select(events.t, prices.price)
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    t  | price
+    3  | 10
+    12 | 15
+    25 |
+    """
+    return RangeJoinResult._range_join(
+        self,
+        other,
+        self_time,
+        other_start,
+        other_end,
+        *on,
+        bucket_size=bucket_size,
+        behavior=behavior,
+        mode=pw.JoinMode.LEFT,
+        left_instance=left_instance,
+        right_instance=right_instance,
+    )
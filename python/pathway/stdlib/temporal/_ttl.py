@@ -0,0 +1,94 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import datetime
+
+import pathway.internals as pw
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+
+from .utils import IntervalType, TimeEventType, check_joint_types, zero_length_interval
+
+
+@check_arg_types
+@trace_user_frame
+def with_ttl(
+    table: pw.Table,
+    ttl: IntervalType,
+    *,
+    time_expr: pw.ColumnExpression | None = None,
+) -> pw.Table:
+    """Retracts entries of a table once they are older than ``ttl``.
+
+    Joins and aggregations keep state for all entries of their inputs. Passing
+    a table through ``with_ttl`` before such an operator bounds its state: expired
+    entries are removed from the operator's state and their contribution to the
+    results is retracted, e.g. a join result involving an expired entry disappears
+    and an aggregate stops including it.
+
+    If ``time_expr`` is given, the age of an entry is measured in event time.
+    The watermark is the maximal ``time_expr`` seen so far, and an entry is
+    retracted once the watermark is at least ``ttl`` past its time. Entries that are
+    already expired when they arrive are ignored. As in temporal operators, the
+    watermark is updated only after all entries that arrived at the same moment are
+    processed.
+
+    If ``time_expr`` is not given, the age is measured in processing time: every
+    entry is retracted ``ttl`` after it was inserted, i.e. when the time of the
+    computation reaches the insertion time plus ``ttl``. An update of an entry
+    resets its age. In that case, ``ttl`` has to be a ``datetime.timedelta``,
+    as the time of the computation is measured in milliseconds.
+
+    Args:
+        table: the table whose entries expire.
+        ttl: time after which an entry expires. Has to be positive.
+        time_expr: time of an entry. If None, processing time is used.
+
+    Returns:
+        Table: the entries of ``table`` that have not expired yet.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> t = pw.debug.table_from_markdown(
+    ...     '''
+    ...     | user | t  | __time__
+    ...   1 | a    | 1  |     2
+    ...   2 | b    | 3  |     2
+    ...   3 | a    | 6  |     4
+    ...   4 | b    | 12 |     6
+    ... '''
+    ... )
+    >>> recent = pw.temporal.with_ttl(t, 5, time_expr=t.t)
+    >>> result = recent.groupby(pw.this.user).reduce(
+    ...     pw.this.user, n=pw.reducers.count()
+    ... )
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    user | n
+    b    | 1
+    """
+    if time_expr is None:
+        if not isinstance(ttl, datetime.timedelta):
+            raise ValueError(
+                "ttl has to be a datetime.timedelta when processing time is used."
+            )
+        if ttl <= datetime.timedelta(0):
+            raise ValueError("ttl has to be positive.")
+        duration_ms = -(-ttl // datetime.timedelta(milliseconds=1))
+        return table._expire_after(duration_ms)
+
+    check_joint_types(
+        {
+            "time_expr": (time_expr, TimeEventType),
+            "ttl": (ttl, IntervalType),
+        }
+    )
+    if ttl <= zero_length_interval(type(ttl)):  # type: ignore[operator]
+        raise ValueError("ttl has to be positive.")
+
+    table_with_time = table.with_columns(_pw_time=time_expr)
+    threshold = pw.this._pw_time + ttl
+    table_with_time = table_with_time._freeze(threshold, pw.this._pw_time)
+    table_with_time = table_with_time._forget(threshold, pw.this._pw_time, False)
+    return table_with_time.without(pw.this._pw_time)
@@ -17,6 +17,7 @@ from pathway.internals.arg_handlers import (
     windowby_handler,
 )
 from pathway.internals.desugaring import desugar
+from pathway.internals.expressions.date_time import _check_calendar_unit
 from pathway.internals.joins import validate_join_condition
 from pathway.internals.runtime_type_check import check_arg_types
 from pathway.internals.trace import trace_user_frame
@@ -128,6 +129,18 @@ class _SessionWindow(Window):
                     "window.max_gap": (self.max_gap, IntervalType),
                 }
             )
+            sessions = table._session_windows(key, self.max_gap, instance=instance)
+            return table.with_columns(
+                sessions._pw_window,
+                sessions._pw_window_start,
+                sessions._pw_window_end,
+                _pw_instance=instance,
+            ).groupby(
+                pw.this._pw_window,
+                pw.this._pw_window_start,
+                pw.this._pw_window_end,
+                instance=pw.this._pw_instance,
+            )
 
         target = self._compute_group_repr(table, key, instance)
         tmp = target.groupby(target._pw_window).reduce(
@@ -586,6 +599,136 @@ class _IntervalsOverWindow(Window):
         )
 
 
+@dataclasses.dataclass
+class _CalendarWindow(Window):
+    unit: str
+    timezone: str | None = None
+    ratio: int = 1
+
+    def _floor(self, time_expression: pw.ColumnExpression) -> pw.ColumnExpression:
+        if self.timezone is None:
+            return time_expression.dt.floor_calendar(self.unit)
+        return time_expression.dt.floor_calendar(self.unit, self.timezone)
+
+    def _shift(
+        self, window_start: pw.ColumnExpression, count: int
+    ) -> pw.ColumnExpression:
+        if self.timezone is None:
+            shifted = window_start.dt.add_calendar_units(self.unit, count)
+        else:
+            shifted = window_start.dt.add_calendar_units(
+                self.unit, count, self.timezone
+            )
+        # a window can start later than at local midnight if midnight falls
+        # into a DST gap, so the shifted start has to be aligned again
+        return self._floor(shifted)
+
+    def _assign_windows(
+        self, table: pw.Table, time_expression: pw.ColumnExpression, **kwargs: Any
+    ) -> pw.Table:
+        current_start = self._floor(time_expression)
+        if self.ratio == 1:
+            target = table.with_columns(_pw_window_start=current_start, **kwargs)
+        else:
+            # an entry belongs to the windows starting in the last `ratio` units
+            target = table.flatten(
+                _pw_window_start=pw.make_tuple(
+                    *(self._shift(current_start, -k) for k in range(self.ratio))
+                ),
+                **{name: table[name] for name in table.column_names()},
+                **kwargs,
+            )
+        # window ends are aligned in local time, so windows containing
+        # a DST change are shorter or longer than usual
+        return target.with_columns(
+            _pw_window_end=self._shift(target._pw_window_start, self.ratio)
+        )
+
+    @check_arg_types
+    def _apply(
+        self,
+        table: pw.Table,
+        key: pw.ColumnExpression,
+        behavior: Behavior | None,
+        instance: pw.ColumnExpression | None,
+    ) -> pw.GroupedTable:
+        if behavior is not None:
+            raise NotImplementedError(
+                "behavior is not supported in windows with calendar units"
+            )
+        if not isinstance(eval_type(key), (dt.DateTimeNaive, dt.DateTimeUtc)):
+            raise TypeError(
+                "windows with calendar units require DateTimeNaive "
+                f"or DateTimeUtc keys but got {eval_type(key)}"
+            )
+        if self.timezone is not None and not isinstance(eval_type(key), dt.DateTimeUtc):
+            raise TypeError(
+                "windows with calendar units in a timezone require DateTimeUtc "
+                f"keys but got {eval_type(key)}"
+            )
+
+        target = self._assign_windows(table, key, _pw_key=key, _pw_instance=instance)
+        return target.groupby(
+            target._pw_window_start,
+            target._pw_window_end,
+            instance=target._pw_instance,
+        )
+
+    @check_arg_types
+    def _join(
+        self,
+        left: pw.Table,
+        right: pw.Table,
+        left_time_expression: pw.ColumnExpression,
+        right_time_expression: pw.ColumnExpression,
+        *on: pw.ColumnExpression,
+        mode: pw.JoinMode,
+        left_instance: pw.ColumnReference | None = None,
+        right_instance: pw.ColumnReference | None = None,
+    ) -> WindowJoinResult:
+        check_joint_types(
+            {
+                "left_time_expression": (left_time_expression, TimeEventType),
+                "right_time_expression": (right_time_expression, TimeEventType),
+            }
+        )
+
+        left_window = self._assign_windows(left, left_time_expression)
+        right_window = self._assign_windows(right, right_time_expression)
+
+        for cond in on:
+            cond_left, cond_right, cond = validate_join_condition(cond, left, right)
+            cond._left = left_window[cond_left._name]
+            cond._right = right_window[cond_right._name]
+
+        join_result = pw.JoinResult._table_join(
+            left_window,
+            right_window,
+            left_window._pw_window_start == right_window._pw_window_start,
+            left_window._pw_window_end == right_window._pw_window_end,
+            *on,
+            mode=mode,
+            left_instance=left_instance,
+            right_instance=right_instance,
+        )
+
+        return WindowJoinResult(join_result, left, right, left_window, right_window)
+
+
+def _calendar_window(
+    unit: str,
+    origin: int | float | datetime.datetime | None,
+    timezone: str | None,
+    ratio: int,
+) -> Window:
+    if origin is not None:
+        raise ValueError("origin is not supported in windows with calendar units")
+    _check_calendar_unit(unit)
+    if ratio < 1:
+        raise ValueError("ratio has to be positive")
+    return _CalendarWindow(unit=unit, timezone=timezone, ratio=ratio)
+
+
 @check_arg_types
 @trace_user_frame
 def session(
@@ -654,10 +797,11 @@ def session(
 @trace_user_frame
 @arg_handler(handler=offset_deprecation)
 def sliding(
-    hop: int | float | datetime.timedelta,
+    hop: int | float | datetime.timedelta | str,
     duration: int | float | datetime.timedelta | None = None,
     ratio: int | None = None,
     origin: int | float | datetime.datetime | None = None,
+    timezone: str | None = None,
 ) -> Window:
     """Allows grouping together elements within a window of a given length sliding
     across ordered time-like data column according to a specified interval (hop)
@@ -668,10 +812,14 @@ def sliding(
         Exactly one of the arguments `hop` or `ratio` should be provided.
 
     Args:
-        hop: frequency of a window
+        hop: frequency of a window. For DateTime columns it can also be one of
+            calendar units: 'hour', 'day', 'month', 'quarter', 'year'. The length
+            of the window then has to be given with ``ratio``.
         duration: length of the window
         ratio: used as an alternative way to specify duration as hop * ratio
         origin: a point in time at which the first window begins
+        timezone: the timezone in which windows with calendar units are aligned.
+            Only allowed for DateTimeUtc columns.
 
     Returns:
         Window: object to pass as an argument to `.windowby()`
@@ -719,6 +867,15 @@ def sliding(
     elif duration is not None and ratio is not None:
         raise ValueError("Cannot provide both [duration, ratio] at the same time.")
 
+    if isinstance(hop, str):
+        if ratio is None:
+            raise ValueError(
+                "sliding windows with calendar units require the ratio parameter"
+            )
+        return _calendar_window(hop, origin, timezone, ratio)
+    if timezone is not None:
+        raise ValueError("timezone is only supported in windows with calendar units")
+
     return _SlidingWindow(
         duration=duration,
         hop=hop,
@@ -731,8 +888,9 @@ def sliding(
 @trace_user_frame
 @arg_handler(handler=offset_deprecation)
 def tumbling(
-    duration: int | float | datetime.timedelta,
+    duration: int | float | datetime.timedelta | str,
     origin: int | float | datetime.datetime | None = None,
+    timezone: str | None = None,
 ) -> Window:
     """Allows grouping together elements within a window of a given length tumbling
     across ordered time-like data column starting from a given origin.
@@ -741,8 +899,14 @@ def tumbling(
         Usually used as an argument of `.windowby()`.
 
     Args:
-        duration: length of the window
-        origin: a point in time at which the first window begins
+        duration: length of the window. For DateTime columns it can also be one of
+            calendar units: 'hour', 'day', 'month', 'quarter', 'year'. Windows then
+            start at the beginning of each calendar unit and have variable length.
+        origin: a point in time at which the first window begins. Not supported
+            for calendar units.
+        timezone: the timezone in which windows with calendar units are aligned.
+            Only allowed for DateTimeUtc columns. Days containing a DST change
+            last 23 or 25 hours.
 
     Returns:
         Window: object to pass as an argument to `.windowby()`
@@ -777,7 +941,52 @@ def tumbling(
     0            | 10               | 15             | 12    | 14    | 3
     0            | 15               | 20             | 15    | 17    | 3
     1            | 10               | 15             | 12    | 13    | 2
+
+    >>> t = pw.debug.table_from_markdown(
+    ... '''
+    ...      | t
+    ...    1 | 2023-01-15T10:00:00
+    ...    2 | 2023-01-31T23:00:00
+    ...    3 | 2023-02-01T00:00:00
+    ...    4 | 2023-03-20T12:00:00
+    ... ''').select(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"))
+    >>> result = t.windowby(
+    ...     t.t, window=pw.temporal.tumbling(duration="month")
+    ... ).reduce(
+    ...   pw.this._pw_window_start,
+    ...   pw.this._pw_window_end,
+    ...   count=pw.reducers.count(),
+    ... )
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    _pw_window_start    | _pw_window_end      | count
+    2023-01-01 00:00:00 | 2023-02-01 00:00:00 | 2
+    2023-02-01 00:00:00 | 2023-03-01 00:00:00 | 1
+    2023-03-01 00:00:00 | 2023-04-01 00:00:00 | 1
+
+    >>> t = pw.debug.table_from_markdown(
+    ... '''
+    ...      | t
+    ...    1 | 2023-03-25T12:00:00+00:00
+    ...    2 | 2023-03-26T12:00:00+00:00
+    ...    3 | 2023-03-26T22:30:00+00:00
+    ... ''').select(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S%z"))
+    >>> result = t.windowby(
+    ...     t.t, window=pw.temporal.tumbling(duration="day", timezone="Europe/Warsaw")
+    ... ).reduce(
+    ...   pw.this._pw_window_start,
+    ...   pw.this._pw_window_end,
+    ...   count=pw.reducers.count(),
+    ... )
+    >>> pw.debug.compute_and_print(result, include_id=False)
+    _pw_window_start          | _pw_window_end            | count
+    2023-03-24 23:00:00+00:00 | 2023-03-25 23:00:00+00:00 | 1
+    2023-03-25 23:00:00+00:00 | 2023-03-26 22:00:00+00:00 | 1
+    2023-03-26 22:00:00+00:00 | 2023-03-27 22:00:00+00:00 | 1
     """
+    if isinstance(duration, str):
+        return _calendar_window(duration, origin, timezone, 1)
+    if timezone is not None:
+        raise ValueError("timezone is only supported in windows with calendar units")
     return _SlidingWindow(
         duration=None,
         hop=duration,
@@ -2,7 +2,15 @@
 
 from __future__ import annotations
 
-from . import async_transformer, bucketing, col, filtering, pandas_transformer
+from . import (
+    async_transformer,
+    bucketing,
+    col,
+    filtering,
+    lookup,
+    pandas_transformer,
+    sampling,
+)
 
 __all__ = [
     "bucketing",
@@ -10,4 +18,6 @@ __all__ = [
     "pandas_transformer",
     "async_transformer",
     "filtering",
+    "lookup",
+    "sampling",
 ]
@@ -0,0 +1,130 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import datetime
+import logging
+from collections.abc import Callable
+from typing import Any, Literal, Optional
+
+import pathway.internals as pw
+from pathway.internals import asynchronous
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+
+
+@check_arg_types
+@trace_user_frame
+def lookup_join(
+    table: pw.Table,
+    key: pw.ColumnExpression,
+    fetch: Callable[[Any], dict[str, Any] | None],
+    schema: type[pw.Schema],
+    *,
+    how: Literal["left", "inner"] = "left",
+    on_error: Literal["raise", "ignore"] = "raise",
+    capacity: int | None = None,
+    retry_strategy: asynchronous.AsyncRetryStrategy | None = None,
+    cache_ttl: datetime.timedelta | None = None,
+    cache_size: int | None = None,
+) -> pw.Table:
+    """Enriches rows of a table with data looked up in an external store.
+
+    Reference data too large to be read into a table, e.g. kept in a database or
+    behind an HTTP service, can be joined this way. For every row, ``fetch`` is called
+    with the value of ``key`` and returns a dictionary with the values of the columns
+    of ``schema``, or None if the key is not present in the store. ``fetch`` can be
+    a regular or an async function. Calls are made asynchronously, at most
+    ``capacity`` at a time, and their results are cached in memory, so a key that is
+    looked up again does not cause a call until its cached result expires.
+
+    Args:
+        table: the table to enrich.
+        key: the value passed to ``fetch``.
+        fetch: a function looking up a key in the external store.
+        schema: the schema of the looked up data.
+        how: if ``"left"``, rows with keys not present in the store are kept and the
+            columns of ``schema`` are None for them. If ``"inner"``, such rows are
+            dropped.
+        on_error: if ``"raise"``, an exception raised by ``fetch`` stops the
+            computation. If ``"ignore"``, the exception is logged and the key is
+            treated as not present in the store. Exceptions are handled after
+            all retries of ``retry_strategy``.
+        capacity: maximum number of concurrent calls of ``fetch``. Defaults to None,
+            indicating no limit.
+        retry_strategy: strategy for retrying failed calls. Defaults to None,
+            indicating no retries.
+        cache_ttl: time after which a cached result expires. Defaults to None,
+            indicating that results never expire.
+        cache_size: maximum number of cached results. Defaults to None, indicating
+            no limit.
+
+    Returns:
+        Table: ``table`` with the columns of ``schema`` added.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> orders = pw.debug.table_from_markdown('''
+    ... order | product
+    ... 1     | apple
+    ... 2     | pear
+    ... 3     | plum
+    ... ''')
+    >>> PRICES = {"apple": 3, "pear": 5}
+    >>> class PriceSchema(pw.Schema):
+    ...     price: int
+    >>> def fetch_price(product):
+    ...     if product not in PRICES:
+    ...         return None
+    ...     return {"price": PRICES[product]}
+    >>> enriched = pw.utils.lookup.lookup_join(
+    ...     orders, orders.product, fetch_price, PriceSchema, capacity=4
+    ... )
+    >>> pw.debug.compute_and_print(enriched, include_id=False)
+    order | product | price
+    1     | apple   | 3
+    2     | pear    | 5
+    3     | plum    |
+    """
+    names = schema.column_names()
+    typehints = schema.typehints()
+
+    fetch_with_options = asynchronous.async_options(
+        capacity=capacity,
+        retry_strategy=retry_strategy,
+        cache_strategy=asynchronous.InMemoryCache(ttl=cache_ttl, max_size=cache_size),
+    )(fetch)
+
+    async def lookup(key_value):
+        try:
+            result = await fetch_with_options(key_value)
+        except Exception:
+            if on_error == "raise":
+                raise
+            logging.exception(f"lookup of key {key_value!r} failed, ignoring it")
+            return None
+        if result is None:
+            return None
+        return tuple(result[name] for name in names)
+
+    looked_up = table.select(_pw_lookup=pw.apply_async(lookup, key))
+    if how == "inner":
+        looked_up = looked_up.filter(looked_up._pw_lookup.is_not_none())
+        columns = {
+            name: pw.apply_with_type(
+                lambda row, i=i: row[i], typehints[name], looked_up._pw_lookup
+            )
+            for i, name in enumerate(names)
+        }
+        return table.restrict(looked_up) + looked_up.select(**columns)
+
+    columns = {
+        name: pw.apply_with_type(
+            lambda row, i=i: None if row is None else row[i],
+            Optional[typehints[name]],
+            looked_up._pw_lookup,
+        )
+        for i, name in enumerate(names)
+    }
+    return table + looked_up.select(**columns)
@@ -0,0 +1,116 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pathway.internals as pw
+from pathway.internals import api, dtype as dt, expression as expr
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.trace import trace_user_frame
+
+
+def _sample_rank(table: pw.Table, seed: int) -> expr.ColumnExpression:
+    return expr.MethodCallExpression(
+        (
+            (
+                (dt.POINTER, dt.INT),
+                dt.FLOAT,
+                api.Expression.pointer_sample_rank,
+            ),
+        ),
+        "sample_rank",
+        table.id,
+        seed,
+    )
+
+
+@check_arg_types
+@trace_user_frame
+def bernoulli_sample(table: pw.Table, fraction: float, *, seed: int = 0) -> pw.Table:
+    """Keeps every row of a table with probability ``fraction``.
+
+    Whether a row is kept depends only on its id and on ``seed``, so the filter is
+    stateless: an updated row stays in the sample (or out of it) and its deletion is
+    passed on to the sample. Samples taken with the same seed are consistent with each
+    other, e.g. a row kept with a smaller ``fraction`` is also kept with a larger one.
+
+    Args:
+        table: the table to sample.
+        fraction: the probability of keeping a row, between 0 and 1.
+        seed: the seed of the sampling. Different seeds give independent samples.
+
+    Returns:
+        Table: The rows of ``table`` included in the sample.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> from pathway.stdlib.utils.sampling import bernoulli_sample
+    >>> table = pw.debug.table_from_markdown('''
+    ... value
+    ... 1
+    ... 2
+    ... 3
+    ... ''')
+    >>> sample = bernoulli_sample(table, 1.0)
+    >>> pw.debug.compute_and_print(sample, include_id=False)
+    value
+    1
+    2
+    3
+    """
+    if not 0 <= fraction <= 1:
+        raise ValueError("fraction has to be between 0 and 1.")
+    return table.filter(_sample_rank(table, seed) < fraction)
+
+
+@check_arg_types
+@trace_user_frame
+def reservoir_sample(
+    table: pw.Table,
+    k: int,
+    *,
+    instance: pw.ColumnExpression | None = None,
+    seed: int = 0,
+) -> pw.Table:
+    """Keeps a uniform sample of ``k`` rows of a table, optionally within instances.
+
+    Every row gets a pseudorandom rank computed from its id and ``seed``, and the
+    ``k`` rows with the smallest ranks are kept. The sample is maintained under
+    updates: a new row replaces a sampled one if its rank is smaller, and when
+    a sampled row is deleted, the row with the next smallest rank takes its place.
+    Instances with at most ``k`` rows are kept in full.
+
+    Args:
+        table: the table to sample.
+        k: the number of rows to keep. Has to be positive.
+        instance: an expression with instance. If set, ``k`` rows are kept within
+            every instance.
+        seed: the seed of the sampling. Different seeds give independent samples.
+
+    Returns:
+        Table: The rows of ``table`` included in the sample.
+
+    Example:
+
+    >>> import pathway as pw
+    >>> from pathway.stdlib.utils.sampling import reservoir_sample
+    >>> table = pw.debug.table_from_markdown('''
+    ... team | score
+    ... A    | 80
+    ... A    | 90
+    ... A    | 85
+    ... B    | 70
+    ... B    | 95
+    ... C    | 60
+    ... ''')
+    >>> sample = reservoir_sample(table, 2, instance=pw.this.team)
+    >>> sizes = sample.groupby(pw.this.team).reduce(
+    ...     pw.this.team, size=pw.reducers.count()
+    ... )
+    >>> pw.debug.compute_and_print(sizes, include_id=False)
+    team | size
+    A    | 2
+    B    | 2
+    C    | 1
+    """
+    return table.top_n(_sample_rank(table, seed), k, instance=instance)
@@ -0,0 +1,35 @@
+# Copyright © 2024 Pathway
+
+import os
+import sys
+
+import pathway as pw
+
+
+class InputSchema(pw.Schema):
+    value: int
+
+
+def main():
+    input_path, output_path, persistence_path = sys.argv[1:4]
+
+    table = pw.io.csv.read(
+        input_path, schema=InputSchema, mode="static", persistent_id="input"
+    )
+
+    def on_change(key, row, time, is_addition):
+        process_id = os.environ["PATHWAY_PROCESS_ID"]
+        with open(os.path.join(output_path, f"process_{process_id}"), "a") as f:
+            f.write(f"{row['value']}\n")
+
+    pw.io.subscribe(table, on_change)
+
+    pw.run(
+        persistence_config=pw.persistence.Config.simple_config(
+            pw.persistence.Backend.filesystem(persistence_path)
+        )
+    )
+
+
+if __name__ == "__main__":
+    main()
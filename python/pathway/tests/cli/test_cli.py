@@ -80,3 +80,76 @@ def test_record_replay_through_cli(tmp_path: pathlib.Path):
 
     # Without replay (and with empty input connector), there are no rows
     run_record(replay_dir, timestamp_file, 0, 0)
+
+
+def test_spawn_single_process_of_cluster(tmp_path: pathlib.Path):
+    script = (
+        "import os, sys; "
+        "path = sys.argv[1] + os.environ['PATHWAY_PROCESS_ID']; "
+        "open(path, 'w').write("
+        "os.environ['PATHWAY_ADDRESSES'] + ' ' + os.environ['PATHWAY_PROCESSES'])"
+    )
+    runner = CliRunner()
+    result = runner.invoke(
+        cli.spawn,
+        [
+            "--addresses",
+            "10.0.0.1:2000, 10.0.0.2:2000",
+            "--process-id",
+            "1",
+            "python",
+            "-c",
+            script,
+            str(tmp_path / "process_"),
+        ],
+    )
+    assert result.exit_code == 0
+    assert sorted(path.name for path in tmp_path.iterdir()) == ["process_1"]
+    assert (tmp_path / "process_1").read_text() == "10.0.0.1:2000,10.0.0.2:2000 2"
+
+
+def test_spawn_process_id_requires_addresses():
+    runner = CliRunner()
+    result = runner.invoke(cli.spawn, ["--process-id", "0", "python", "-c", "pass"])
+    assert result.exit_code != 0
+    assert "--addresses" in result.output
+
+
+def test_persistence_in_cluster(tmp_path: pathlib.Path):
+    input_path = tmp_path / "input"
+    output_path = tmp_path / "output"
+    persistence_path = tmp_path / "PStorage"
+    input_path.mkdir()
+    output_path.mkdir()
+    script_path = os.path.join(os.path.dirname(__file__), "persistence.py")
+
+    def run() -> list[int]:
+        for path in output_path.iterdir():
+            path.unlink()
+        runner = CliRunner()
+        result = runner.invoke(
+            cli.spawn,
+            [
+                "-n",
+                "2",
+                "python",
+                script_path,
+                str(input_path),
+                str(output_path),
+                str(persistence_path),
+            ],
+        )
+        assert result.exit_code == 0
+        return sorted(
+            int(line)
+            for path in output_path.iterdir()
+            for line in path.read_text().splitlines()
+        )
+
+    (input_path / "1.csv").write_text("value\n1\n2\n")
+    assert run() == [1, 2]
+
+    # The processes restart from the checkpoint they agreed on, so only the new
+    # rows are read
+    (input_path / "2.csv").write_text("value\n3\n")
+    assert run() == [3]
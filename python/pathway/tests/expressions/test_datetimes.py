@@ -539,6 +539,39 @@ def test_strptime_utc_errors_on_wrong_specifier() -> None:
         run_all()
 
 
+@pytest.mark.parametrize(
+    "timestamp,unit",
+    [(2**62, "s"), (2**62, "us"), (1e20, "s"), (float("inf"), "ms")],
+)
+def test_from_timestamp_errors_on_overflow(timestamp: int | float, unit: str) -> None:
+    table_from_pandas(pd.DataFrame({"a": [timestamp]})).select(
+        t=pw.this.a.dt.from_timestamp(unit=unit)
+    )
+    with pytest.raises(OverflowError, match="overflow in date time arithmetic"):
+        run_all()
+
+
+def test_add_duration_errors_on_overflow() -> None:
+    table_from_pandas(
+        pd.DataFrame(
+            {
+                "t": [pd.Timestamp("2200-01-01")],
+                "d": [pd.Timedelta(days=100_000)],
+            }
+        )
+    ).select(t=pw.this.t + pw.this.d)
+    with pytest.raises(OverflowError, match="overflow in date time arithmetic"):
+        run_all()
+
+
+def test_round_errors_on_overflow() -> None:
+    table_from_pandas(
+        pd.DataFrame({"t": [pd.Timestamp("2262-04-11T23:47:16")]})
+    ).select(t=pw.this.t.dt.round(datetime.timedelta(days=1)))
+    with pytest.raises(OverflowError, match="overflow in date time arithmetic"):
+        run_all()
+
+
 def test_strptime_utc_errors_on_wrong_format() -> None:
     table_from_pandas(pd.DataFrame({"a": ["2023-03-26T16:43:21.12-0100"]})).select(
         t=pw.this.a.dt.strptime("%Y-%m-%d %H:%M:%S.%f%z")
@@ -0,0 +1,142 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pytest
+
+import pathway as pw
+from pathway.tests.utils import T, assert_stream_equality, assert_table_equality
+
+
+def test_rank_with_ties():
+    t = T(
+        """
+          | k | v
+        1 | a | 5
+        2 | a | 3
+        3 | a | 5
+        4 | a | 7
+        5 | b | 1
+    """
+    )
+    res = pw.ordered.rank(t, t.v, instance=t.k).select(pw.this.rank, pw.this.dense_rank)
+
+    expected = T(
+        """
+          | rank | dense_rank
+        1 | 2    | 2
+        2 | 1    | 1
+        3 | 2    | 2
+        4 | 4    | 3
+        5 | 1    | 1
+    """
+    )
+    assert_table_equality(res, expected)
+
+
+def test_rank_descending_row_number():
+    t = T(
+        """
+          | v
+        1 | 10
+        2 | 30
+        3 | 20
+    """
+    )
+    res = pw.ordered.rank(t, t.v, descending=True)
+
+    expected = T(
+        """
+          | v  | row_number | rank | dense_rank
+        1 | 10 | 3          | 3    | 3
+        2 | 30 | 1          | 1    | 1
+        3 | 20 | 2          | 2    | 2
+    """
+    )
+    assert_table_equality(res, expected)
+
+
+def test_rank_updates():
+    t = T(
+        """
+          | v | __time__ | __diff__
+        1 | 1 |     2    |     1
+        2 | 3 |     2    |     1
+        3 | 2 |     4    |     1
+        2 | 3 |     6    |    -1
+    """
+    )
+    res = pw.ordered.rank(t, t.v).select(pw.this.rank)
+
+    expected = T(
+        """
+          | rank | __time__ | __diff__
+        1 | 1    |     2    |     1
+        2 | 2    |     2    |     1
+        2 | 2    |     4    |    -1
+        2 | 3    |     4    |     1
+        3 | 2    |     4    |     1
+        2 | 3    |     6    |    -1
+    """
+    )
+    assert_stream_equality(res, expected)
+
+
+def test_lag_and_lead():
+    t = T(
+        """
+          | k | t | v
+        1 | a | 1 | 10
+        2 | a | 2 | 20
+        3 | a | 3 | 30
+        4 | b | 1 | 40
+    """
+    )
+    res = pw.ordered.lag(t, t.t, t.v, instance=t.k)
+    res = pw.ordered.lead(res, pw.this.t, pw.this.v, instance=pw.this.k)
+
+    expected = T(
+        """
+          | k | t | v  | lag_v | lead_v
+        1 | a | 1 | 10 |       | 20
+        2 | a | 2 | 20 | 10    | 30
+        3 | a | 3 | 30 | 20    |
+        4 | b | 1 | 40 |       |
+    """
+    )
+    assert_table_equality(res, expected)
+
+
+def test_lag_offset():
+    t = T(
+        """
+          | t | v
+        1 | 1 | 10
+        2 | 2 | 20
+        3 | 3 | 30
+        4 | 4 | 40
+    """
+    )
+    res = pw.ordered.lead(t, t.t, t.v, offset=3)
+
+    expected = T(
+        """
+          | t | v  | lead_v
+        1 | 1 | 10 | 40
+        2 | 2 | 20 |
+        3 | 3 | 30 |
+        4 | 4 | 40 |
+    """
+    )
+    assert_table_equality(res, expected)
+
+
+def test_lag_non_positive_offset():
+    t = T(
+        """
+        t | v
+        1 | 10
+    """
+    )
+    with pytest.raises(ValueError, match="offset has to be positive"):
+        pw.ordered.lag(t, t.t, t.v, offset=0)
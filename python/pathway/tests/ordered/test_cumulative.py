@@ -0,0 +1,115 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pytest
+
+import pathway as pw
+from pathway.tests.utils import T, assert_stream_equality, assert_table_equality
+
+
+def test_running_sum_with_instance():
+    t = T(
+        """
+          | k | t | v
+        1 | a | 1 | 10
+        2 | a | 3 | 30
+        3 | b | 2 | 5
+        4 | a | 2 | 20
+    """
+    )
+    res = pw.ordered.running_sum(t, t.t, t.v, instance=t.k)
+
+    expected = T(
+        """
+          | k | t | v  | running_sum_v
+        1 | a | 1 | 10 | 10
+        2 | a | 3 | 30 | 60
+        3 | b | 2 | 5  | 5
+        4 | a | 2 | 20 | 30
+    """
+    )
+    assert_table_equality(res, expected)
+
+
+def test_running_sum_out_of_order():
+    t = T(
+        """
+          | t | v  | __time__
+        1 | 1 | 10 |     2
+        2 | 3 | 30 |     2
+        3 | 2 | 20 |     4
+    """
+    )
+    res = pw.ordered.running_sum(t, t.t, t.v).select(pw.this.running_sum_v)
+
+    expected = T(
+        """
+          | running_sum_v | __time__ | __diff__
+        1 | 10            |     2    |     1
+        2 | 40            |     2    |     1
+        2 | 40            |     4    |    -1
+        2 | 60            |     4    |     1
+        3 | 30            |     4    |     1
+    """
+    )
+    assert_stream_equality(res, expected)
+
+
+def test_running_avg_min_max():
+    t = T(
+        """
+          | t | v
+        1 | 1 | 4.0
+        2 | 2 | 2.0
+        3 | 3 | 6.0
+    """
+    )
+    res = pw.ordered.running_avg(t, t.t, t.v)
+    res = pw.ordered.running_min(res, pw.this.t, pw.this.v)
+    res = pw.ordered.running_max(res, pw.this.t, pw.this.v)
+
+    expected = T(
+        """
+          | t | v   | running_avg_v | running_min_v | running_max_v
+        1 | 1 | 4.0 | 4.0           | 4.0           | 4.0
+        2 | 2 | 2.0 | 3.0           | 2.0           | 4.0
+        3 | 3 | 6.0 | 4.0           | 2.0           | 6.0
+    """
+    )
+    assert_table_equality(res, expected)
+
+
+def test_running_min_after_deletion():
+    t = T(
+        """
+          | t | v | __time__ | __diff__
+        1 | 1 | 3 |     2    |     1
+        2 | 2 | 5 |     2    |     1
+        1 | 1 | 3 |     4    |    -1
+    """
+    )
+    res = pw.ordered.running_min(t, t.t, t.v).select(pw.this.running_min_v)
+
+    expected = T(
+        """
+          | running_min_v | __time__ | __diff__
+        1 | 3             |     2    |     1
+        2 | 3             |     2    |     1
+        1 | 3             |     4    |    -1
+        2 | 3             |     4    |    -1
+        2 | 5             |     4    |     1
+    """
+    )
+    assert_stream_equality(res, expected)
+
+
+def test_cumulative_unsupported_reducer():
+    t = T(
+        """
+        t | v
+        1 | 10
+    """
+    )
+    with pytest.raises(ValueError, match="only count, sum, min and max"):
+        t._cumulative(t.t, x=pw.reducers.argmax(t.v))
@@ -0,0 +1,107 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import datetime
+import pathlib
+
+import pandas as pd
+import pytest
+
+import pathway as pw
+from pathway.tests.utils import (
+    assert_table_equality_wo_index,
+    needs_multiprocessing_fork,
+    wait_result_with_checker,
+)
+
+
+def test_date_range_naive():
+    t = pw.temporal.date_range(
+        datetime.datetime(2023, 12, 31, 23),
+        datetime.datetime(2024, 1, 1, 1),
+        step=datetime.timedelta(minutes=40),
+    )
+    expected = pw.debug.table_from_pandas(
+        pd.DataFrame(
+            {
+                "t": [
+                    pd.Timestamp("2023-12-31 23:00:00"),
+                    pd.Timestamp("2023-12-31 23:40:00"),
+                    pd.Timestamp("2024-01-01 00:20:00"),
+                ]
+            }
+        )
+    )
+    assert_table_equality_wo_index(t, expected)
+
+
+def test_date_range_utc():
+    tz = datetime.timezone(datetime.timedelta(hours=2))
+    t = pw.temporal.date_range(
+        datetime.datetime(2024, 1, 1, 12, tzinfo=tz),
+        datetime.datetime(2024, 1, 1, 12, 0, 1, tzinfo=tz),
+        step=datetime.timedelta(milliseconds=400),
+    )
+    expected = pw.debug.table_from_pandas(
+        pd.DataFrame(
+            {
+                "t": [
+                    pd.Timestamp("2024-01-01 10:00:00.000+00:00"),
+                    pd.Timestamp("2024-01-01 10:00:00.400+00:00"),
+                    pd.Timestamp("2024-01-01 10:00:00.800+00:00"),
+                ]
+            }
+        )
+    )
+    assert_table_equality_wo_index(t, expected)
+
+
+def test_date_range_errors():
+    with pytest.raises(ValueError, match="step has to be positive"):
+        pw.temporal.date_range(
+            datetime.datetime(2024, 1, 1),
+            datetime.datetime(2024, 1, 2),
+            step=datetime.timedelta(0),
+        )
+    with pytest.raises(ValueError, match="both timezone-aware or both naive"):
+        pw.temporal.date_range(
+            datetime.datetime(2024, 1, 1),
+            datetime.datetime(2024, 1, 2, tzinfo=datetime.timezone.utc),
+            step=datetime.timedelta(hours=1),
+        )
+
+
+class TicksChecker:
+    def __init__(self, path: pathlib.Path, n_ticks: int):
+        self.path = path
+        self.n_ticks = n_ticks
+
+    def __call__(self) -> bool:
+        try:
+            result = pd.read_csv(self.path)
+        except Exception:
+            return False
+        ticks = pd.to_datetime(result["t"])
+        # ticks are never emitted before the wall clock reaches them
+        assert (ticks <= pd.Timestamp.now()).all()
+        return len(ticks) >= self.n_ticks
+
+    def provide_information_on_failure(self) -> str:
+        if not self.path.exists():
+            return f"{self.path} does not exist"
+        with open(self.path) as f:
+            return f"Final output contents:\n{f.read()}"
+
+
+@needs_multiprocessing_fork
+def test_date_range_streaming(tmp_path: pathlib.Path):
+    output_path = tmp_path / "output.csv"
+    # the first 20 ticks lie in the past, the rest is emitted in real time
+    t = pw.temporal.date_range(
+        datetime.datetime.now() - datetime.timedelta(seconds=2),
+        step=datetime.timedelta(milliseconds=100),
+        autocommit_duration_ms=10,
+    )
+    pw.io.csv.write(t, output_path)
+    wait_result_with_checker(TicksChecker(output_path, 40), 30)
@@ -0,0 +1,111 @@
+# Copyright © 2024 Pathway
+
+import datetime
+
+import pytest
+
+import pathway as pw
+from pathway.tests.utils import T, assert_stream_equality_wo_index
+
+
+def test_deduplicate_within():
+    t = T(
+        """
+          | k | t  | __time__
+        1 | a | 1  |     2
+        2 | a | 3  |     2
+        3 | b | 2  |     2
+        4 | a | 6  |     4
+        5 | b | 5  |     4
+        6 | a | 9  |     6
+        """
+    )
+    result = pw.temporal.deduplicate_within(t, t.k, time_expr=t.t, horizon=5)
+    expected = T(
+        """
+          | k | t | __time__ | __diff__
+        1 | a | 1 |     2    |     1
+        3 | b | 2 |     2    |     1
+        4 | a | 6 |     4    |     1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_deduplicate_within_all_columns():
+    t = T(
+        """
+          | k | v | t | __time__
+        1 | a | 1 | 1 |     2
+        2 | a | 2 | 2 |     2
+        3 | a | 1 | 3 |     4
+        """
+    )
+    result = pw.temporal.deduplicate_within(
+        t.select(pw.this.k, pw.this.v), time_expr=t.t, horizon=5
+    )
+    expected = T(
+        """
+          | k | v | __time__ | __diff__
+        1 | a | 1 |     2    |     1
+        2 | a | 2 |     2    |     1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_deduplicate_within_ignores_late_and_deleted_rows():
+    t = T(
+        """
+          | k | t  | __time__ | __diff__
+        1 | a | 10 |     2    |     1
+        2 | b | 12 |     2    |     1
+        1 | a | 10 |     4    |    -1
+        3 | c | 20 |     4    |     1
+        4 | a | 11 |     6    |     1
+        5 | d | 14 |     6    |     1
+        """
+    )
+    result = pw.temporal.deduplicate_within(t, t.k, time_expr=t.t, horizon=5)
+    expected = T(
+        """
+          | k | t  | __time__ | __diff__
+        1 | a | 10 |     2    |     1
+        2 | b | 12 |     2    |     1
+        3 | c | 20 |     4    |     1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_deduplicate_within_datetimes():
+    t = T(
+        """
+          | k | t                   | __time__
+        1 | a | 2024-01-01T10:00:00 |     2
+        2 | a | 2024-01-01T10:00:30 |     4
+        3 | a | 2024-01-01T10:01:30 |     6
+        """
+    ).with_columns(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"))
+    result = pw.temporal.deduplicate_within(
+        t, t.k, time_expr=t.t, horizon=datetime.timedelta(minutes=1)
+    ).select(pw.this.k)
+    expected = T(
+        """
+          | k | __time__ | __diff__
+        1 | a |     2    |     1
+        3 | a |     6    |     1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_deduplicate_within_non_positive_horizon():
+    t = T(
+        """
+        k | t
+        a | 1
+        """
+    )
+    with pytest.raises(ValueError, match="horizon has to be positive"):
+        pw.temporal.deduplicate_within(t, t.k, time_expr=t.t, horizon=0)
@@ -0,0 +1,118 @@
+# Copyright © 2024 Pathway
+
+import datetime
+
+import pytest
+
+import pathway as pw
+from pathway.tests.utils import T, assert_stream_equality_wo_index
+
+
+def test_split_late():
+    t = T(
+        """
+          | t  | v | __time__ | __diff__
+        1 | 10 | a |     2    |     1
+        2 | 4  | b |     4    |     1
+        3 | 8  | c |     4    |     1
+        4 | 14 | d |     6    |     1
+        4 | 14 | d |     8    |    -1
+        5 | 12 | e |     8    |     1
+        3 | 8  | c |    10    |    -1
+        """
+    )
+    on_time, late = pw.temporal.split_late(t, t.t, allowed_lateness=3)
+    expected_on_time = T(
+        """
+          | t  | v | __time__ | __diff__
+        1 | 10 | a |     2    |     1
+        3 | 8  | c |     4    |     1
+        4 | 14 | d |     6    |     1
+        4 | 14 | d |     8    |    -1
+        5 | 12 | e |     8    |     1
+        """
+    )
+    expected_late = T(
+        """
+          | t  | v | __time__ | __diff__
+        2 | 4  | b |     4    |     1
+        3 | 8  | c |    10    |    -1
+        """
+    )
+    assert_stream_equality_wo_index(on_time, expected_on_time)
+    assert_stream_equality_wo_index(late, expected_late)
+
+
+def test_split_late_datetimes():
+    t = T(
+        """
+          | t                   | __time__
+        1 | 2024-01-01T12:00:00 |     2
+        2 | 2024-01-01T11:00:00 |     4
+        3 | 2024-01-01T10:00:00 |     4
+        """
+    ).select(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"))
+    on_time, late = pw.temporal.split_late(
+        t, t.t, allowed_lateness=datetime.timedelta(hours=2)
+    )
+    expected_on_time = T(
+        """
+          | t                   | __time__
+        1 | 2024-01-01T12:00:00 |     2
+        2 | 2024-01-01T11:00:00 |     4
+        """
+    ).select(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"))
+    expected_late = T(
+        """
+          | t                   | __time__
+        3 | 2024-01-01T10:00:00 |     4
+        """
+    ).select(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"))
+    assert_stream_equality_wo_index(on_time, expected_on_time)
+    assert_stream_equality_wo_index(late, expected_late)
+
+
+def test_split_late_feeds_windowby():
+    t = T(
+        """
+          | t  | __time__
+        1 | 1  |     2
+        2 | 12 |     4
+        3 | 2  |     6
+        4 | 11 |     6
+        """
+    )
+    on_time, late = pw.temporal.split_late(t, t.t, allowed_lateness=5)
+    result = on_time.windowby(
+        on_time.t, window=pw.temporal.tumbling(duration=10)
+    ).reduce(pw.this._pw_window_start, n=pw.reducers.count())
+    expected = T(
+        """
+        _pw_window_start | n | __time__ | __diff__
+        0                | 1 |     2    |     1
+        10               | 1 |     4    |     1
+        10               | 1 |     6    |    -1
+        10               | 2 |     6    |     1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+    assert_stream_equality_wo_index(
+        late,
+        T(
+            """
+              | t | __time__
+            3 | 2 |     6
+            """
+        ),
+    )
+
+
+def test_split_late_negative_lateness():
+    t = T(
+        """
+          | t
+        1 | 1
+        """
+    )
+    with pytest.raises(ValueError, match="allowed_lateness has to be non-negative"):
+        pw.temporal.split_late(t, t.t, allowed_lateness=-1)
@@ -0,0 +1,154 @@
+# Copyright © 2024 Pathway
+
+import datetime
+
+import pytest
+
+import pathway as pw
+from pathway.tests.utils import (
+    T,
+    assert_stream_equality_wo_index,
+    assert_table_equality_wo_index,
+)
+
+
+def test_match_pattern_sequence():
+    t = T(
+        """
+          | t | kind
+        1 | 1 | a
+        2 | 2 | x
+        3 | 3 | b
+        4 | 4 | a
+        5 | 5 | c
+        6 | 6 | b
+    """
+    )
+    result = pw.temporal.match_pattern(t, t.t, "A B", A=t.kind == "a", B=t.kind == "b")
+    expected = T(
+        """
+        match_start | match_end
+        1           | 3
+        4           | 6
+    """
+    )
+    assert_table_equality_wo_index(
+        result.select(pw.this.match_start, pw.this.match_end), expected
+    )
+    captured = result.select(a=t.ix(result.A).t, b=t.ix(result.B).t)
+    assert_table_equality_wo_index(
+        captured,
+        T(
+            """
+            a | b
+            1 | 3
+            4 | 6
+        """
+        ),
+    )
+
+
+def test_match_pattern_forbidden_and_within():
+    t = T(
+        """
+          | user | t  | kind
+        1 | u    | 1  | a
+        2 | u    | 2  | c
+        3 | u    | 3  | b
+        4 | v    | 1  | a
+        5 | v    | 3  | b
+        6 | w    | 1  | a
+        7 | w    | 10 | b
+    """
+    )
+    result = pw.temporal.match_pattern(
+        t,
+        t.t,
+        "A !C B",
+        within=5,
+        instance=t.user,
+        A=t.kind == "a",
+        B=t.kind == "b",
+        C=t.kind == "c",
+    )
+    expected = T(
+        """
+        user | match_start | match_end
+        v    | 1           | 3
+    """
+    )
+    assert_table_equality_wo_index(
+        result.select(t.ix(result.A).user, pw.this.match_start, pw.this.match_end),
+        expected,
+    )
+
+
+def test_match_pattern_out_of_order():
+    t = T(
+        """
+          | t | kind | __time__
+        1 | 1 | a    |     2
+        2 | 5 | b    |     2
+        3 | 3 | c    |     4
+    """
+    )
+    result = pw.temporal.match_pattern(
+        t, t.t, "A !C B", A=t.kind == "a", B=t.kind == "b", C=t.kind == "c"
+    ).select(pw.this.match_start, pw.this.match_end)
+    expected = T(
+        """
+        match_start | match_end | __time__ | __diff__
+        1           | 5         |     2    |     1
+        1           | 5         |     4    |    -1
+    """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_match_pattern_datetimes():
+    t = T(
+        """
+          | t                   | kind
+        1 | 2024-01-01T10:00:00 | a
+        2 | 2024-01-01T10:04:00 | b
+        3 | 2024-01-01T11:00:00 | a
+        4 | 2024-01-01T11:06:00 | b
+    """
+    ).with_columns(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"))
+    result = pw.temporal.match_pattern(
+        t,
+        t.t,
+        "A B",
+        within=datetime.timedelta(minutes=5),
+        A=t.kind == "a",
+        B=t.kind == "b",
+    )
+    assert_table_equality_wo_index(
+        result.select(t=t.ix(result.A).kind),
+        T(
+            """
+            t
+            a
+        """
+        ),
+    )
+
+
+@pytest.mark.parametrize(
+    "pattern,message",
+    [
+        ("A D", "variable D used in the pattern is not defined"),
+        ("!B A", "cannot start with a negated variable"),
+        ("A !B", "cannot end with a negated variable"),
+        ("A B A", "have to be distinct"),
+    ],
+)
+def test_match_pattern_invalid(pattern, message):
+    t = T(
+        """
+        t | kind
+        1 | a
+    """
+    )
+    with pytest.raises(ValueError, match=message):
+        pw.temporal.match_pattern(t, t.t, pattern, A=t.kind == "a", B=t.kind == "b")
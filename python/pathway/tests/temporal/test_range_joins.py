@@ -0,0 +1,251 @@
+# Copyright © 2024 Pathway
+
+import datetime
+
+import pytest
+
+import pathway as pw
+from pathway.stdlib.temporal._range_join import _forget_expired_ranges
+from pathway.tests.utils import (
+    T,
+    assert_stream_equality_wo_index,
+    assert_table_equality_wo_index,
+)
+
+
+def events_and_prices() -> tuple[pw.Table, pw.Table]:
+    events = T(
+        """
+          | product | t
+        1 | A       | -1
+        2 | A       | 0
+        3 | A       | 9
+        4 | A       | 10
+        5 | A       | 25
+        6 | B       | 5
+        7 | C       | 5
+        """
+    )
+    prices = T(
+        """
+          | product | price | valid_from | valid_to
+        1 | A       | 10    | 0          | 10
+        2 | A       | 15    | 10         | 20
+        3 | B       | 7     | 0          | 20
+        4 | B       | 8     | 3          | 6
+        """
+    )
+    return events, prices
+
+
+def test_range_join_inner():
+    events, prices = events_and_prices()
+    result = events.range_join_inner(
+        prices,
+        events.t,
+        prices.valid_from,
+        prices.valid_to,
+        events.product == prices.product,
+        bucket_size=4,
+    ).select(events.product, events.t, prices.price)
+    expected = T(
+        """
+        product | t  | price
+        A       | 0  | 10
+        A       | 9  | 10
+        A       | 10 | 15
+        B       | 5  | 7
+        B       | 5  | 8
+        """
+    )
+    assert_table_equality_wo_index(result, expected)
+
+
+def test_range_join_left():
+    events, prices = events_and_prices()
+    result = events.range_join(
+        prices,
+        events.t,
+        prices.valid_from,
+        prices.valid_to,
+        events.product == prices.product,
+        bucket_size=4,
+        how=pw.JoinMode.LEFT,
+    ).select(pw.left.product, pw.left.t, pw.right.price)
+    expected = T(
+        """
+        product | t  | price
+        A       | -1 |
+        A       | 0  | 10
+        A       | 9  | 10
+        A       | 10 | 15
+        A       | 25 |
+        B       | 5  | 7
+        B       | 5  | 8
+        C       | 5  |
+        """
+    )
+    assert_table_equality_wo_index(result, expected)
+
+
+def test_range_join_datetimes():
+    events = T(
+        """
+          | t
+        1 | 2024-01-01T12:00:00
+        2 | 2024-01-02T12:00:00
+        """
+    ).select(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"))
+    ranges = T(
+        """
+          | name | start
+        1 | x    | 2024-01-01T00:00:00
+        """
+    ).select(
+        pw.this.name,
+        start=pw.this.start.dt.strptime("%Y-%m-%dT%H:%M:%S"),
+    )
+    ranges = ranges.with_columns(end=pw.this.start + datetime.timedelta(days=1))
+    result = events.range_join_left(
+        ranges,
+        events.t,
+        ranges.start,
+        ranges.end,
+        bucket_size=datetime.timedelta(hours=6),
+    ).select(events.t, ranges.name)
+    expected = T(
+        """
+          | t                   | name
+        1 | 2024-01-01T12:00:00 | x
+        2 | 2024-01-02T12:00:00 |
+        """
+    ).select(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"), name=pw.this.name)
+    assert_table_equality_wo_index(result, expected)
+
+
+def test_range_join_without_on():
+    events = T(
+        """
+          | t
+        1 | 1
+        2 | 7
+        3 | 12
+        """
+    )
+    ranges = T(
+        """
+          | name | start | end
+        1 | x    | 0     | 10
+        2 | y    | 5     | 8
+        3 | z    | 20    | 30
+        """
+    )
+    result = events.range_join_inner(
+        ranges, events.t, ranges.start, ranges.end, bucket_size=3
+    ).select(events.t, ranges.name)
+    expected = T(
+        """
+        t | name
+        1 | x
+        7 | x
+        7 | y
+        """
+    )
+    assert_table_equality_wo_index(result, expected)
+
+
+def test_range_join_ranges_do_not_advance_watermark():
+    events = T(
+        """
+          | t  | __time__
+        1 | 5  |     2
+        2 | 12 |     6
+        3 | 9  |     8
+        """
+    )
+    ranges = T(
+        """
+          | name | start | end | __time__
+        1 | x    | 0     | 10  |     2
+        2 | y    | 10    | 20  |     2
+        3 | z    | 30    | 40  |     4
+        """
+    )
+    result = events.range_join_inner(
+        ranges,
+        events.t,
+        ranges.start,
+        ranges.end,
+        bucket_size=10,
+        behavior=pw.temporal.common_behavior(cutoff=5),
+    ).select(events.t, ranges.name)
+    # z starts after x ends, but only events move the watermark,
+    # so x is still kept when the event at 9 arrives
+    expected = T(
+        """
+        t  | name
+        5  | x
+        12 | y
+        9  | x
+        """
+    )
+    assert_table_equality_wo_index(result, expected)
+
+
+def test_range_join_forgets_expired_ranges():
+    events = T(
+        """
+          | t  | __time__
+        1 | 12 |     6
+        2 | 16 |     8
+        """
+    ).with_columns(_pw_time=pw.this.t)
+    ranges = T(
+        """
+          | name | end | __time__
+        1 | x    | 10  |     2
+        2 | y    | 20  |     2
+        3 | z    | 40  |     4
+        4 | w    | 9   |    10
+        """
+    ).with_columns(_pw_end=pw.this.end)
+    behavior = pw.temporal.common_behavior(cutoff=5, keep_results=False)
+    result = _forget_expired_ranges(events, ranges, behavior).select(pw.this.name)
+    # x is forgotten once the watermark reaches 10 + 5, w is already expired
+    # when it arrives
+    expected = T(
+        """
+          | name | __time__ | __diff__
+        1 | x    |     2    |     1
+        2 | y    |     2    |     1
+        3 | z    |     4    |     1
+        1 | x    |     8    |    -1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_range_join_unsupported_mode():
+    events, prices = events_and_prices()
+    with pytest.raises(ValueError):
+        events.range_join(
+            prices,
+            events.t,
+            prices.valid_from,
+            prices.valid_to,
+            bucket_size=4,
+            how=pw.JoinMode.OUTER,
+        )
+
+
+@pytest.mark.parametrize("bucket_size", [0, -2])
+def test_range_join_non_positive_bucket_size(bucket_size):
+    events, prices = events_and_prices()
+    with pytest.raises(ValueError, match="bucket_size has to be positive"):
+        events.range_join(
+            prices,
+            events.t,
+            prices.valid_from,
+            prices.valid_to,
+            bucket_size=bucket_size,
+        )
@@ -0,0 +1,149 @@
+# Copyright © 2024 Pathway
+
+import datetime
+
+import pytest
+
+import pathway as pw
+from pathway.tests.utils import T, assert_stream_equality_wo_index
+
+
+def test_with_ttl_event_time():
+    t = T(
+        """
+          | t  | __time__
+        1 | 1  |     2
+        2 | 3  |     2
+        3 | 6  |     4
+        4 | 12 |     6
+        5 | 2  |     8
+        """
+    )
+    result = pw.temporal.with_ttl(t, 5, time_expr=t.t)
+    expected = T(
+        """
+          | t  | __time__ | __diff__
+        1 | 1  |     2    |     1
+        2 | 3  |     2    |     1
+        3 | 6  |     4    |     1
+        1 | 1  |     4    |    -1
+        4 | 12 |     6    |     1
+        2 | 3  |     6    |    -1
+        3 | 6  |     6    |    -1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_with_ttl_event_time_join():
+    clicks = T(
+        """
+          | user | t  | __time__
+        1 | a    | 1  |     2
+        2 | b    | 8  |     4
+        """
+    )
+    users = T(
+        """
+          | user | name
+        1 | a    | Alice
+        2 | b    | Bob
+        """
+    )
+    recent = pw.temporal.with_ttl(clicks, 5, time_expr=clicks.t)
+    result = recent.join(users, recent.user == users.user).select(
+        recent.t, users.name
+    )
+    expected = T(
+        """
+          | t | name  | __time__ | __diff__
+        1 | 1 | Alice |     2    |     1
+        2 | 8 | Bob   |     4    |     1
+        1 | 1 | Alice |     4    |    -1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_with_ttl_processing_time():
+    t = T(
+        """
+          | v | __time__ | __diff__
+        1 | a |     2    |     1
+        2 | b |     2    |     1
+        3 | c |     4    |     1
+        1 | a |     4    |    -1
+        1 | d |     4    |     1
+        2 | b |    10    |    -1
+        """
+    )
+    result = pw.temporal.with_ttl(t, datetime.timedelta(milliseconds=4))
+    expected = T(
+        """
+          | v | __time__ | __diff__
+        1 | a |     2    |     1
+        2 | b |     2    |     1
+        3 | c |     4    |     1
+        1 | a |     4    |    -1
+        1 | d |     4    |     1
+        2 | b |     6    |    -1
+        3 | c |     8    |    -1
+        1 | d |     8    |    -1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+def test_with_ttl_processing_time_groupby():
+    t = T(
+        """
+          | user | __time__
+        1 | a    |     2
+        2 | a    |     6
+        3 | b    |     6
+        """
+    )
+    result = (
+        pw.temporal.with_ttl(t, datetime.timedelta(milliseconds=6))
+        .groupby(pw.this.user)
+        .reduce(pw.this.user, n=pw.reducers.count())
+    )
+    expected = T(
+        """
+        user | n | __time__ | __diff__
+        a    | 1 |     2    |     1
+        a    | 1 |     6    |    -1
+        a    | 2 |     6    |     1
+        a    | 2 |     8    |    -1
+        a    | 1 |     8    |     1
+        a    | 1 |    12    |    -1
+        b    | 1 |     6    |     1
+        b    | 1 |    12    |    -1
+        """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+@pytest.mark.parametrize("ttl", [0, -2])
+def test_with_ttl_non_positive(ttl):
+    t = T(
+        """
+          | t
+        1 | 1
+        """
+    )
+    with pytest.raises(ValueError, match="ttl has to be positive"):
+        pw.temporal.with_ttl(t, ttl, time_expr=t.t)
+    with pytest.raises(ValueError, match="ttl has to be positive"):
+        pw.temporal.with_ttl(t, datetime.timedelta(milliseconds=ttl))
+
+
+def test_with_ttl_processing_time_requires_timedelta():
+    t = T(
+        """
+          | t
+        1 | 1
+        """
+    )
+    with pytest.raises(ValueError, match="datetime.timedelta"):
+        pw.temporal.with_ttl(t, 5)
@@ -1013,3 +1013,37 @@ def test_window_joins_typing_on():
             pw.temporal.sliding(hop=50, duration=100),
             left_table.col == right_table.col,
         )
+
+
+def test_window_join_sliding_calendar():
+    t1 = pw.debug.table_from_markdown(
+        """
+      | a |            t
+    1 | 1 | 2023-03-25T12:00:00+0000
+    """
+    ).with_columns(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S%z"))
+    t2 = pw.debug.table_from_markdown(
+        """
+      | b |            t
+    1 | 1 | 2023-03-26T12:00:00+0000
+    2 | 2 | 2023-03-28T12:00:00+0000
+    """
+    ).with_columns(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S%z"))
+
+    result = t1.window_join_outer(
+        t2,
+        t1.t,
+        t2.t,
+        pw.temporal.sliding(hop="day", ratio=2, timezone="Europe/Warsaw"),
+    ).select(pw.left.a, pw.right.b)
+    expected = T(
+        """
+      | a | b
+    1 | 1 | 1
+    2 | 1 |
+    3 |   | 1
+    4 |   | 2
+    5 |   | 2
+    """
+    )
+    assert_table_equality_wo_index(result, expected)
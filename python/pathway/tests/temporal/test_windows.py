@@ -11,7 +11,11 @@ import pytest
 import pathway as pw
 from pathway.internals import dtype as dt
 from pathway.internals.dtype import DATE_TIME_NAIVE, DATE_TIME_UTC
-from pathway.tests.utils import T, assert_table_equality_wo_index
+from pathway.tests.utils import (
+    T,
+    assert_stream_equality_wo_index,
+    assert_table_equality_wo_index,
+)
 
 
 def test_session_simple():
@@ -235,6 +239,77 @@ def test_session_max_gap_mixed():
     assert_table_equality_wo_index(result, res)
 
 
+def test_session_max_gap_late_and_retracted():
+    t = T(
+        """
+          | t | __time__ | __diff__
+        1 | 1 |     2    |     1
+        2 | 2 |     2    |     1
+        3 | 6 |     2    |     1
+        4 | 7 |     4    |     1
+        5 | 4 |     6    |     1
+        5 | 4 |     8    |    -1
+    """
+    )
+
+    gb = t.windowby(t.t, window=pw.temporal.session(max_gap=3))
+    result = gb.reduce(
+        pw.this._pw_window_start,
+        pw.this._pw_window_end,
+        count=pw.reducers.count(),
+    )
+    expected = T(
+        """
+          | _pw_window_start | _pw_window_end | count | __time__ | __diff__
+        1 |        1         |       2        |   2   |     2    |     1
+        2 |        6         |       6        |   1   |     2    |     1
+        2 |        6         |       6        |   1   |     4    |    -1
+        3 |        6         |       7        |   2   |     4    |     1
+        1 |        1         |       2        |   2   |     6    |    -1
+        3 |        6         |       7        |   2   |     6    |    -1
+        4 |        1         |       7        |   5   |     6    |     1
+        4 |        1         |       7        |   5   |     8    |    -1
+        1 |        1         |       2        |   2   |     8    |     1
+        3 |        6         |       7        |   2   |     8    |     1
+    """
+    )
+    assert_stream_equality_wo_index(result, expected)
+
+
+@pytest.mark.parametrize(
+    "max_gap", [datetime.timedelta(minutes=10), pd.Timedelta(minutes=10)]
+)
+def test_session_max_gap_timedelta(max_gap):
+    t = pw.debug.table_from_markdown(
+        """
+      | k |          t          | a
+    1 | 1 | 2023-05-15T10:13:00 | 1
+    2 | 1 | 2023-05-15T10:20:00 | 2
+    3 | 1 | 2023-05-15T10:30:00 | 3
+    4 | 2 | 2023-05-15T10:25:00 | 4
+    5 | 2 | 2023-05-15T10:34:59 | 5
+    """
+    ).with_columns(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S"))
+
+    result = t.windowby(
+        pw.this.t, window=pw.temporal.session(max_gap=max_gap), instance=pw.this.k
+    ).reduce(
+        pw.this._pw_instance,
+        min_a=pw.reducers.min(pw.this.a),
+        max_a=pw.reducers.max(pw.this.a),
+        count=pw.reducers.count(),
+    )
+    expected = T(
+        """
+    _pw_instance | min_a | max_a | count
+          1      |   1   |   2   |   2
+          1      |   3   |   3   |   1
+          2      |   4   |   5   |   2
+    """
+    )
+    assert_table_equality_wo_index(result, expected)
+
+
 def test_session_window_creation():
     with pytest.raises(ValueError):
         pw.temporal.session()
@@ -677,6 +752,47 @@ def test_sliding_floats():
     assert res_pd["count"].sum() == 3 * n
 
 
+def test_sliding_calendar_in_timezone():
+    # midnight of 2023-03-12 doesn't exist in Havana, so this day starts at 01:00
+    t = pw.debug.table_from_markdown(
+        """
+      |            t
+    1 | 2023-03-11T12:00:00+0000
+    2 | 2023-03-12T12:00:00+0000
+    3 | 2023-03-13T12:00:00+0000
+    """
+    ).select(t=pw.this.t.dt.strptime("%Y-%m-%dT%H:%M:%S%z"))
+
+    gb = t.windowby(
+        t.t,
+        window=pw.temporal.sliding(hop="day", ratio=2, timezone="America/Havana"),
+    )
+    result = gb.reduce(
+        start=pw.this._pw_window_start.dt.strftime("%Y-%m-%dT%H:%M"),
+        end=pw.this._pw_window_end.dt.strftime("%Y-%m-%dT%H:%M"),
+        count=pw.reducers.count(),
+    )
+    res = T(
+        """
+        start            | end              | count
+        2023-03-10T05:00 | 2023-03-12T05:00 | 1
+        2023-03-11T05:00 | 2023-03-13T04:00 | 2
+        2023-03-12T05:00 | 2023-03-14T04:00 | 2
+        2023-03-13T04:00 | 2023-03-15T04:00 | 1
+    """
+    )
+    assert_table_equality_wo_index(result, res)
+
+
+def test_calendar_window_errors():
+    with pytest.raises(ValueError):
+        pw.temporal.tumbling(duration="week")
+    with pytest.raises(ValueError):
+        pw.temporal.sliding(hop="day")
+    with pytest.raises(ValueError):
+        pw.temporal.tumbling(duration=2, timezone="Europe/Warsaw")
+
+
 @pytest.mark.parametrize(
     "w",
     [
@@ -803,12 +919,11 @@ def test_windows_with_utc_datetimes(w):
         expected = T(
             """
          | min_a | max_a
-       1 |   1   |   2
-       2 |   3   |   3
-       3 |   4   |   5
-       4 |   6   |   6
+       1 |   1   |   3
+       2 |   4   |   5
+       3 |   6   |   6
         """
-        )  # FIXME merge rows 1, 2 when sorting is fixed
+        )
 
     elif w == pw.temporal.tumbling(duration=datetime.timedelta(minutes=30)):
         expected = T(
@@ -867,12 +982,11 @@ def test_windows_with_datetimes(w):
         expected = T(
             """
          | min_a | max_a
-       1 |   1   |   2
-       2 |   3   |   3
-       3 |   4   |   5
-       4 |   6   |   6
+       1 |   1   |   3
+       2 |   4   |   5
+       3 |   6   |   6
         """
-        )  # FIXME merge rows 1, 2 when sorting is fixed
+        )
 
     elif w == pw.temporal.tumbling(duration=datetime.timedelta(minutes=30)):
         expected = T(
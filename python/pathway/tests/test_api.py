@@ -1214,3 +1214,66 @@ def test_value_type_via_python(event_loop, value):
     assert_equal_tables_wo_index(
         table, new_table
     )  # wo_index knows how to compare arrays
+
+
+def test_arrays_passed_to_python_share_memory(event_loop):
+    value = np.array([[1.0, 2.0], [3.0, 4.0]])
+    expected = np.array([[1.0, 3.0], [2.0, 4.0]])
+
+    def build(s):
+        key = api.ref_scalar()
+        universe = s.static_universe([key])
+        properties = api.ColumnProperties(dtype=api.PathwayType.ARRAY)
+        column = s.static_column(universe, [(key, value)], properties=properties)
+        expected_column = s.static_column(
+            universe, [(key, expected)], properties=properties
+        )
+        table = s.table(universe, [column])
+
+        def fun(values):
+            [inner] = values
+            assert not inner.flags.writeable
+            with pytest.raises(ValueError):
+                inner[0, 0] = 5.0
+            # a view of the array is not the array itself
+            return inner.T
+
+        new_column = s.map_column(table, fun, properties)
+        new_table = s.table(universe, [new_column])
+        expected_table = s.table(universe, [expected_column])
+
+        return convert_tables(s, new_table, expected_table)
+
+    new_table, expected_table = api.run_with_new_graph(build, event_loop)
+
+    assert_equal_tables_wo_index(new_table, expected_table)
+
+
+def test_table_properties_introspection(event_loop):
+    properties = []
+
+    def build(s):
+        df = _markdown_to_pandas(
+            """
+                | a | b
+                1 | 1 | x
+                2 | 2 | y
+            """
+        )
+        table = static_table_from_pandas(s, df, legacy=False)
+        properties.extend(s.table_properties(table).columns())
+        return [(table, [column_path.ColumnPath((0,)), column_path.ColumnPath((1,))])]
+
+    api.run_with_new_graph(build, event_loop)
+
+    assert [path for path, _ in properties] == [(0,), (1,)]
+    assert [column.dtype for _, column in properties] == [
+        api.PathwayType.INT,
+        api.PathwayType.STRING,
+    ]
+    assert not any(column.append_only for _, column in properties)
+
+
+def test_key_bits():
+    # the keys are displayed in base32, after a caret
+    assert len(str(api.ref_scalar(42))) == 1 + -(-api.KEY_BITS // 5)
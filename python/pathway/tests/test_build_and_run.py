@@ -1,5 +1,7 @@
 # Copyright © 2024 Pathway
 
+import json
+import logging
 import os
 import pathlib
 
@@ -16,11 +18,11 @@ from pathway import (
     transformer,
 )
 from pathway.debug import _markdown_to_pandas
-from pathway.internals import column, datasink, datasource, graph_runner
+from pathway.internals import api, column, datasink, datasource, graph_runner
 from pathway.internals.decorators import table_from_datasource
 from pathway.internals.graph_runner.state import ScopeState
 from pathway.internals.graph_runner.storage_graph import OperatorStorageGraph
-from pathway.internals.monitoring import MonitoringLevel
+from pathway.internals.monitoring import JsonFormatter, LoggingConfig, MonitoringLevel
 from pathway.internals.parse_graph import G
 from pathway.internals.schema import Schema, schema_from_pandas
 from pathway.io import csv
@@ -364,3 +366,140 @@ def test_bounded_inputs():
     assert gr.has_bounded_input(t_csv_bounded)
     assert gr.has_bounded_input(t_markdown)
     assert gr.has_bounded_input(t_empty)
+
+
+def test_explain(tmp_path: pathlib.Path):
+    input_path = tmp_path / "input.csv"
+    input_path.write_text("a,b\n1,x\n")
+
+    class InputSchema(Schema):
+        a: int
+        b: str
+
+    input = csv.read(
+        input_path, schema=InputSchema, mode="static", persistent_id="words"
+    )
+    result = input.select(c=input.a + 1)
+    csv.write(result, tmp_path / "output.csv")
+
+    plan = json.loads(G.explain())
+    operators = {operator["id"]: operator for operator in plan["operators"]}
+    [reader] = [op for op in operators.values() if op["kind"] == "InputOperator"]
+    [select] = [op for op in operators.values() if op["label"] == "select"]
+    [writer] = [op for op in operators.values() if op["kind"] == "OutputOperator"]
+
+    assert reader["persistent_id"] == "words"
+    assert reader["outputs"][0]["schema"] == {"a": "INT", "b": "STR"}
+    assert select["outputs"][0]["schema"] == {"c": "INT"}
+    assert select["trace"].startswith(__file__)
+    assert writer["outputs"] == []
+    assert {"from": reader["id"], "to": select["id"]} in plan["edges"]
+    assert {"from": select["id"], "to": writer["id"]} in plan["edges"]
+
+    dot = G.explain(format="dot")
+    assert dot.startswith("digraph {")
+    assert f"n{reader['id']} -> n{select['id']};" in dot
+    assert "persistent_id: words" in dot
+
+
+def test_operator_state_limit_raise():
+    input = T(
+        """
+          | a
+        1 | foo
+        2 | bar
+        """
+    )
+    result = input.select(b=input.a + "baz")
+
+    with pytest.raises(api.EngineError, match="exceeding the limit of 100 bytes"):
+        graph_runner.GraphRunner(
+            G,
+            monitoring_level=MonitoringLevel.NONE,
+            operator_state_limit=100,
+            on_operator_state_limit="raise",
+        ).run_tables(result)
+
+
+def test_operator_state_limit_warn(caplog):
+    input = T(
+        """
+          | a
+        1 | foo
+        2 | bar
+        """
+    )
+    result = input.select(b=input.a + "baz")
+
+    [captured] = graph_runner.GraphRunner(
+        G,
+        monitoring_level=MonitoringLevel.NONE,
+        operator_state_limit=100,
+    ).run_tables(result)
+    assert len(captured) == 2
+    assert "exceeding the limit of 100 bytes" in caplog.text
+
+
+def test_engine_logs_carry_context(caplog):
+    input = T(
+        """
+          | a
+        1 | foo
+        2 | bar
+        """
+    )
+    result = input.select(b=input.a + "baz")
+
+    graph_runner.GraphRunner(
+        G,
+        monitoring_level=MonitoringLevel.NONE,
+        operator_state_limit=100,
+    ).run_tables(result)
+    [record] = [
+        record
+        for record in caplog.records
+        if "exceeding the limit of 100 bytes" in record.getMessage()
+    ]
+    assert record.worker_id == 0
+    assert isinstance(record.operator_id, int)
+
+    entry = json.loads(JsonFormatter().format(record))
+    assert entry["level"] == "WARNING"
+    assert entry["worker_id"] == 0
+    assert entry["operator_id"] == record.operator_id
+    assert "exceeding the limit of 100 bytes" in entry["message"]
+
+
+def test_engine_logs_per_subsystem(caplog):
+    input = T(
+        """
+          | a
+        1 | foo
+        2 | bar
+        """
+    )
+    result = input.select(b=input.a + "baz")
+
+    def run(logging_config=None):
+        caplog.clear()
+        graph_runner.GraphRunner(
+            G,
+            monitoring_level=MonitoringLevel.NONE,
+            operator_state_limit=100,
+            logging_config=logging_config,
+        ).run_tables(result)
+        return [
+            record
+            for record in caplog.records
+            if "exceeding the limit of 100 bytes" in record.getMessage()
+        ]
+
+    [record] = run()
+    assert record.name == "pathway.engine.dataflow"
+
+    assert run(LoggingConfig(engine_levels={"dataflow": "ERROR"})) == []
+    assert logging.getLogger("pathway.engine.dataflow").level == logging.NOTSET
+    assert len(run()) == 1
+
+    with pytest.raises(ValueError, match="unknown subsystem of the engine"):
+        LoggingConfig(engine_levels={"kafka": "DEBUG"})
@@ -16,9 +16,9 @@ import pytest
 import pathway as pw
 import pathway.internals.shadows.operator as operator
 from pathway.debug import table_from_pandas, table_to_pandas
-from pathway.internals import dtype as dt
+from pathway.internals import api, dtype as dt
 from pathway.internals.decorators import empty_from_schema
-from pathway.internals.expression import NumbaApplyExpression
+from pathway.internals.expression import ApplyExpression, NumbaApplyExpression
 from pathway.tests.utils import (
     T,
     assert_table_equality,
@@ -988,6 +988,63 @@ def test_concat_errors_on_intersecting_universes():
         run_all()
 
 
+def test_changelog():
+    t = T(
+        """
+          | pet | age | __time__ | __diff__
+        1 | Dog | 2   | 2        | 1
+        7 | Cat | 5   | 2        | 1
+        1 | Dog | 2   | 4        | -1
+        1 | Dog | 3   | 4        | 1
+        7 | Cat | 5   | 6        | -1
+        """
+    )
+    expected = T(
+        """
+        pet | age | time | diff
+        Dog | 2   | 2    | 1
+        Cat | 5   | 2    | 1
+        Dog | 2   | 4    | -1
+        Dog | 3   | 4    | 1
+        Cat | 5   | 6    | -1
+        """
+    )
+    assert_table_equality_wo_index(t.changelog(), expected)
+
+
+def test_changelog_of_computed_table():
+    t = T(
+        """
+          | owner | pet | __time__ | __diff__
+        1 | Alice | dog | 2        | 1
+        2 | Bob   | cat | 2        | 1
+        3 | Alice | cat | 4        | 1
+        """
+    )
+    counts = t.groupby(pw.this.owner).reduce(pw.this.owner, count=pw.reducers.count())
+    expected = T(
+        """
+        owner | count | time | diff
+        Alice | 1     | 2    | 1
+        Bob   | 1     | 2    | 1
+        Alice | 1     | 4    | -1
+        Alice | 2     | 4    | 1
+        """
+    )
+    assert_table_equality_wo_index(counts.changelog(), expected)
+
+
+def test_changelog_column_name_conflict():
+    t = T(
+        """
+        time | value
+        1    | 2
+        """
+    )
+    with pytest.raises(ValueError):
+        t.changelog()
+
+
 @pytest.mark.parametrize("dtype", [np.int64, np.float64])
 def test_flatten(dtype: Any):
     df = pd.DataFrame(
@@ -1044,6 +1101,67 @@ def test_flatten_multidimensional(dtype: Any):
         assert found_equal
 
 
+def test_table_from_pandas_in_bulk_same_as_by_rows():
+    df = pd.DataFrame(
+        {
+            "int": [1, 2, 3],
+            "float": [1.5, None, 3.0],
+            "str": ["x", None, "z"],
+            "bool": [True, False, True],
+            "datetime": pd.to_datetime(["2024-01-01", "2024-01-02", None]),
+            "duration": pd.to_timedelta([1, 2, 3], unit="s"),
+        }
+    )
+
+    in_bulk = table_to_pandas(table_from_pandas(df))
+    with mock.patch.object(api, "_arrow_array_from_pandas", return_value=None):
+        by_rows = table_to_pandas(table_from_pandas(df))
+
+    pd.testing.assert_frame_equal(in_bulk, by_rows)
+
+
+def test_table_to_arrow():
+    t = T(
+        """
+            | a | b
+        1   | 1 | x
+        2   | 2 | y
+        """
+    )
+
+    arrow_table = pw.debug.table_to_arrow(t, include_id=False)
+
+    assert arrow_table.schema.names == ["a", "b"]
+    assert sorted(arrow_table.column("a").to_pylist()) == [1, 2]
+    assert sorted(arrow_table.column("b").to_pylist()) == ["x", "y"]
+    with_ids = pw.debug.table_to_arrow(t)
+    assert with_ids.schema.names == ["id", "a", "b"]
+    assert len(set(with_ids.column("id").to_pylist())) == 2
+
+
+def test_estimated_size():
+    t = T(
+        """
+            | a | b
+        1   | 1 | x
+        2   | 2 | xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx
+        """
+    )
+
+    sizes = t.select(
+        a=pw.debug.estimated_size(t.a),
+        b=pw.debug.estimated_size(t.b),
+        row=pw.debug.estimated_size(pw.make_tuple(t.a, t.b)),
+    )
+
+    rows = sorted(pw.debug.table_to_pandas(sizes).itertuples(index=False))
+    (a_1, b_1, row_1), (a_2, b_2, row_2) = rows
+    assert a_1 == a_2 > 0
+    assert b_2 == b_1 + 65
+    assert row_1 > a_1 + b_1
+    assert row_2 == row_1 + 65
+
+
 def test_flatten_string():
     df = pd.DataFrame({"string": ["abc", "defoimkm", "xyz"], "other": [0, 1, 2]})
     t1 = pw.debug.table_from_pandas(df)
@@ -1586,6 +1704,76 @@ def test_iterate_with_limit(limit):
     assert_table_equality(ret, expected_ret)
 
 
+def test_iterate_with_limit_raise_converged():
+    def iteration_step(iterated):
+        iterated = iterated.select(
+            foo=pw.if_else(iterated.foo < 3, iterated.foo + 1, iterated.foo)
+        )
+        return iterated
+
+    ret = pw.iterate(
+        iteration_step,
+        iteration_limit=10,
+        on_iteration_limit="raise",
+        iterated=T(
+            """
+                | foo
+            1   | 0
+            """
+        ),
+    )
+
+    expected_ret = T(
+        """
+            | foo
+        1   | 3
+        """
+    )
+
+    assert_table_equality(ret, expected_ret)
+
+
+def test_iterate_with_limit_raise_not_converged():
+    def iteration_step(iterated):
+        iterated = iterated.select(foo=iterated.foo + 1)
+        return iterated
+
+    ret = pw.iterate(
+        iteration_step,
+        iteration_limit=10,
+        on_iteration_limit="raise",
+        iterated=T(
+            """
+                | foo
+            1   | 0
+            """
+        ),
+    )
+
+    with pytest.raises(
+        api.EngineError, match="fixed point not reached within the limit of 10"
+    ):
+        pw.debug.compute_and_print(ret)
+
+
+def test_iterate_raise_requires_limit():
+    def iteration_step(iterated):
+        iterated = iterated.select(foo=iterated.foo + 1)
+        return iterated
+
+    with pytest.raises(ValueError):
+        pw.iterate(
+            iteration_step,
+            on_iteration_limit="raise",
+            iterated=T(
+                """
+                    | foo
+                1   | 0
+                """
+            ),
+        )
+
+
 def test_iterate_with_same_universe_outside():
     t = T(
         """
@@ -1955,6 +2143,103 @@ def test_apply_wrong_number_of_args():
         a.select(ret=pw.apply(add))
 
 
+def _typed_expression(table: pw.Table, name: str) -> pw.ColumnExpression:
+    column = table._columns[name]
+    return column.context.expression_with_type(column.expression)
+
+
+def test_apply_translated_to_native_expression():
+    t = T(
+        """
+          | a  | b   | s
+        1 | 7  | 1.5 | Alice
+        2 | -3 | 2.0 | bob
+        3 | 0  | 0.5 | Carol
+        """
+    )
+    threshold = 2
+
+    def describe(a: int, s: str) -> str:
+        """Describes the row."""
+        return s.upper() if a > threshold else s.lower() + "!"
+
+    result = t.select(
+        x=pw.apply(lambda a, b: a * 2 + b / 4, t.a, t.b),
+        y=pw.apply(lambda a: a**2, t.a),
+        z=pw.apply(lambda a: a % 3 - abs(a), t.a),
+        w=pw.apply(describe, t.a, t.s),
+        v=pw.apply_with_type(
+            lambda s, a: s.startswith("A") or len(s) > 3 and not 0 < a < 5,
+            bool,
+            s=t.s,
+            a=t.a,
+        ),
+    )
+
+    for name in ["x", "z", "w", "v"]:
+        assert not isinstance(_typed_expression(result, name), ApplyExpression)
+    # the power isn't translated, as a negative exponent fails in the engine
+    assert isinstance(_typed_expression(result, "y"), ApplyExpression)
+    assert result.schema._dtypes() == {
+        "x": dt.ANY,
+        "y": dt.ANY,
+        "z": dt.ANY,
+        "w": dt.STR,
+        "v": dt.BOOL,
+    }
+    assert_table_equality_wo_types(
+        result,
+        T(
+            """
+              | x      | y  | z  | w      | v
+            1 | 14.375 | 49 | -6 | ALICE  | True
+            2 | -5.5   | 9  | -3 | bob!   | False
+            3 | 0.125  | 0  | 0  | carol! | True
+            """
+        ),
+    )
+
+
+def test_register_serializer():
+    class Counter:
+        def __init__(self, value: int) -> None:
+            self.value = value
+
+    serialized = []
+
+    def serialize(counter: Counter) -> bytes:
+        serialized.append(counter.value)
+        return str(counter.value).encode()
+
+    pw.register_serializer(
+        Counter, serialize, lambda data: Counter(int(data)), name="test_counter"
+    )
+
+    t = T(
+        """
+          | a
+        1 | 1
+        2 | 2
+        """
+    )
+    counters = t.select(counter=pw.apply(Counter, t.a))
+    result = counters.select(
+        value=pw.apply_with_type(lambda c: c.value * 10, int, counters.counter)
+    )
+
+    assert_table_equality(
+        result,
+        T(
+            """
+              | value
+            1 | 10
+            2 | 20
+            """
+        ),
+    )
+    assert sorted(serialized) == [1, 2]
+
+
 def test_apply_async():
     import asyncio
 
@@ -3278,6 +3563,36 @@ def test_difference():
     )
 
 
+def test_reshard_by():
+    t = T(
+        """
+            | owner | pet
+        1   | Alice | dog
+        2   | Bob   | cat
+        3   | Alice | cat
+        4   | Carol | fish
+        """
+    )
+    resharded = t.reshard_by(t.owner, pw.this.pet.str.len(), parallelism=2)
+    assert_table_equality(resharded, t)
+    assert_table_equality(
+        resharded.select(resharded.owner, pet=t.pet),
+        t,
+    )
+    assert_table_equality(t.reshard_by(), t)
+
+
+def test_reshard_by_non_positive_parallelism():
+    t = T(
+        """
+            | owner
+        1   | Alice
+        """
+    )
+    with pytest.raises(ValueError, match="parallelism has to be positive"):
+        t.reshard_by(t.owner, parallelism=0)
+
+
 def test_intersect():
     t1 = T(
         """
@@ -13,3 +13,11 @@ def test_identities():
     assert dt.Optional(dt.ANY) is dt.ANY
     assert dt.Optional(dt.Optional(dt.INT)) is dt.Optional(dt.INT)
     assert dt.Array(2, dt.Array(2, dt.INT)) is dt.Array(4, dt.INT)
+
+
+def test_is_nullable():
+    assert dt.Optional(dt.INT).is_nullable()
+    assert dt.ANY.is_nullable()
+    assert dt.NONE.is_nullable()
+    assert not dt.INT.is_nullable()
+    assert not dt.Tuple(dt.INT, dt.Optional(dt.STR)).is_nullable()
@@ -3,6 +3,7 @@
 import contextlib
 import os
 import re
+import traceback
 
 import pandas as pd
 import pytest
@@ -260,6 +261,31 @@ def test_traceback_rust_expression():
         run_all(runtime_typechecking=False)
 
 
+def test_udf_exception_keeps_traceback_and_row():
+    input = T(
+        """
+        foo
+        1
+        2
+        """
+    )
+
+    def check_positive(x: int) -> int:
+        if x > 1:
+            raise ValueError("value too large")
+        return x
+
+    input.select(ret=pw.apply(check_positive, pw.this.foo))
+
+    with pytest.raises(ValueError, match="value too large") as e:
+        run_all()
+    frames = traceback.extract_tb(e.value.__traceback__)
+    assert any(frame.name == "check_positive" for frame in frames)
+    assert any(
+        note.startswith("Occurred for the row with key") for note in e.value.__notes__
+    )
+
+
 @pytest.mark.xfail
 def test_traceback_async_apply():
     input = T(
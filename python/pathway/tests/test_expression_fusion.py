@@ -0,0 +1,117 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import pathway as pw
+from pathway.internals import dtype as dt
+from pathway.internals.expression_fusion import MAX_FUSED_EXPRESSION_SIZE
+from pathway.tests.utils import T, assert_table_equality
+
+
+def test_fused_chain():
+    t1 = T(
+        """
+          | a
+        1 | 1
+        2 | 2
+        3 | 3
+        """
+    )
+    t2 = t1.select(b=t1.a + 1)
+    t3 = t2.select(c=t2.b * 10)
+    t4 = t3.filter(t3.c > 25).select(d=pw.this.c - 1)
+
+    dependencies = t3._columns["c"].column_dependencies()
+    assert t1._columns["a"] in dependencies
+    assert t2._columns["b"] not in dependencies
+
+    expected = T(
+        """
+          | d
+        2 | 29
+        3 | 39
+        """
+    )
+    assert_table_equality(t4, expected)
+
+
+def test_fused_columns_still_available():
+    t1 = T(
+        """
+          | a
+        1 | 1
+        2 | 2
+        """
+    )
+    t2 = t1.with_columns(b=t1.a * 2)
+    t3 = t2.with_columns(c=t2.b + t2.a)
+
+    expected = T(
+        """
+          | a | b | c
+        1 | 1 | 2 | 3
+        2 | 2 | 4 | 6
+        """
+    )
+    assert_table_equality(t3, expected)
+
+
+def test_apply_not_fused():
+    t1 = T(
+        """
+          | a
+        1 | 1
+        """
+    )
+    t2 = t1.select(b=pw.apply(lambda x: x + 1, t1.a))
+    t3 = t2.select(c=t2.b + 1)
+
+    dependencies = t3._columns["c"].column_dependencies()
+    assert t2._columns["b"] in dependencies
+    assert t1._columns["a"] not in dependencies
+
+
+def test_type_narrowing_not_fused():
+    t1 = T(
+        """
+          | a
+        1 | 1
+        2 |
+        """
+    )
+    t2 = t1.select(b=t1.a + 1)
+    t3 = t2.select(c=pw.if_else(t2.b.is_not_none(), t2.b, 0))
+
+    assert t2.schema._dtypes()["b"] == dt.Optional(dt.INT)
+    assert t3.schema._dtypes()["c"] == dt.INT
+    expected = T(
+        """
+          | c
+        1 | 2
+        2 | 0
+        """
+    ).update_types(c=int)
+    assert_table_equality(t3, expected)
+
+
+def test_large_expressions_not_fused():
+    t = T(
+        """
+          | a
+        1 | 1
+        """
+    )
+    for _ in range(MAX_FUSED_EXPRESSION_SIZE):
+        t = t.select(a=t.a + t.a)
+
+    result = t.select(a=t.a // 2**MAX_FUSED_EXPRESSION_SIZE)
+    assert_table_equality(
+        result,
+        T(
+            """
+              | a
+            1 | 1
+            """
+        ),
+    )
+    assert len(str(result._columns["a"].expression)) < 2000
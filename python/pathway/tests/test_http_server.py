@@ -2,6 +2,9 @@
 
 from __future__ import annotations
 
+import datetime
+import itertools
+import json
 import os
 import time
 import urllib
@@ -15,14 +18,21 @@ from pathway.tests.utils import T
 
 
 @pytest.mark.xdist_group(name="http_server_tests")
-def http_server_status(_, max_retries: int = 1) -> int:
+def http_server_status(
+    _, max_retries: int = 1, path: str = "/status", headers: dict | None = None
+) -> int:
     port = os.environ.get("PATHWAY_MONITORING_HTTP_PORT", "20000")
+    request = urllib.request.Request(
+        f"http://localhost:{port}{path}", headers=headers or {}
+    )
 
     for n_attempt in range(max_retries):
         time.sleep(2**n_attempt * 0.1)
         try:
-            with urllib.request.urlopen(f"http://localhost:{port}/status") as response:
+            with urllib.request.urlopen(request) as response:
                 return response.status
+        except urllib.error.HTTPError as e:
+            return e.code
         except urllib.error.URLError:
             continue
     return -1
@@ -64,3 +74,277 @@ def test_http_server_doesnt_run_when_disabled():
         G, with_http_server=False, monitoring_level=pw.MonitoringLevel.NONE
     ).run_tables(response_code)[0]
     assert updates_stream[0].values[0] == -1
+
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_reports_health():
+    table = T(
+        """
+            | foo
+        1   | 42
+        """
+    )
+
+    response_code = table.select(
+        response_code=pw.apply_async(
+            http_server_status, table.foo, max_retries=4, path="/healthz"
+        )
+    )
+
+    updates_stream = graph_runner.GraphRunner(
+        G, with_http_server=True, monitoring_level=pw.MonitoringLevel.NONE
+    ).run_tables(response_code)[0]
+    assert updates_stream[0].values[0] == 200
+
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_not_ready_before_first_time_is_output():
+    table = T(
+        """
+            | foo
+        1   | 42
+        """
+    )
+
+    response_code = table.select(
+        response_code=pw.apply_async(
+            http_server_status, table.foo, max_retries=4, path="/ready"
+        )
+    )
+
+    updates_stream = graph_runner.GraphRunner(
+        G, with_http_server=True, monitoring_level=pw.MonitoringLevel.NONE
+    ).run_tables(response_code)[0]
+    assert updates_stream[0].values[0] == 503
+
+
+def http_server_get(path: str, max_retries: int = 4) -> str:
+    port = os.environ.get("PATHWAY_MONITORING_HTTP_PORT", "20000")
+
+    for n_attempt in range(max_retries):
+        time.sleep(2**n_attempt * 0.1)
+        try:
+            with urllib.request.urlopen(f"http://localhost:{port}{path}") as response:
+                return response.read().decode()
+        except urllib.error.URLError:
+            continue
+    return ""
+
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_serves_table():
+    pets = T(
+        """
+            | owner | pet
+        1   | Alice | dog
+        2   | Bob   | cat
+        """
+    )
+    pw.io.http.serve_table(pets, "pets")
+
+    def lookup_by_id(id) -> str:
+        return http_server_get(f"/tables/pets/{id}")
+
+    def lookup_by_owner(owner: str) -> str:
+        return http_server_get(f"/tables/pets?owner={owner}")
+
+    responses = pets.select(
+        pets.pet,
+        by_id=pw.apply_async(lookup_by_id, pets.id),
+        by_owner=pw.apply_async(lookup_by_owner, pets.owner),
+    )
+
+    rows = []
+    pw.io.subscribe(
+        responses, on_change=lambda key, row, time, is_addition: rows.append(row)
+    )
+    pw.run(with_http_server=True, monitoring_level=pw.MonitoringLevel.NONE)
+
+    assert len(rows) == 2
+    for row in rows:
+        assert json.loads(row["by_id"])["pet"] == row["pet"]
+        [served_row] = json.loads(row["by_owner"])
+        assert served_row["pet"] == row["pet"]
+
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_serves_topology():
+    table = T(
+        """
+            | foo
+        1   | 42
+        """
+    )
+    doubled = table.select(foo=table.foo * 2)
+
+    topology = doubled.select(
+        topology=pw.apply_async(lambda _: http_server_get("/topology"), doubled.foo)
+    )
+
+    updates_stream = graph_runner.GraphRunner(
+        G, with_http_server=True, monitoring_level=pw.MonitoringLevel.NONE
+    ).run_tables(topology)[0]
+    operators = json.loads(updates_stream[0].values[0])["operators"]
+    operators_by_id = {operator["id"]: operator for operator in operators}
+    assert len(operators_by_id) == len(operators)
+    for operator in operators:
+        assert all(input in operators_by_id for input in operator["inputs"])
+    assert any(operator["inputs"] for operator in operators)
+
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_reports_allocator_stats():
+    table = T(
+        """
+            | foo
+        1   | 42
+        """
+    )
+
+    stats = table.select(
+        stats=pw.apply_async(lambda _: http_server_get("/allocator"), table.foo)
+    )
+
+    updates_stream = graph_runner.GraphRunner(
+        G, with_http_server=True, monitoring_level=pw.MonitoringLevel.NONE
+    ).run_tables(stats)[0]
+    stats = json.loads(updates_stream[0].values[0])
+    assert 0 < stats["allocated"] <= stats["active"] <= stats["mapped"]
+
+
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_captures_profile():
+    table = T(
+        """
+            | foo
+        1   | 42
+        """
+    )
+
+    response_code = table.select(
+        response_code=pw.apply_async(
+            http_server_status,
+            table.foo,
+            max_retries=4,
+            path="/profile?seconds=1&format=pprof",
+        )
+    )
+
+    updates_stream = graph_runner.GraphRunner(
+        G, with_http_server=True, monitoring_level=pw.MonitoringLevel.NONE
+    ).run_tables(response_code)[0]
+    assert updates_stream[0].values[0] == 200
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_reports_alerts():
+    table = T(
+        """
+            | foo
+        1   | 42
+        """
+    )
+
+    def wait_for_alerts(_) -> str:
+        # The alerts are checked once a second
+        for _attempt in range(10):
+            health = json.loads(http_server_get("/healthz") or "{}")
+            if health.get("alerts"):
+                break
+            time.sleep(0.5)
+        return json.dumps(health.get("alerts"))
+
+    alerts = table.select(alerts=pw.apply_async(wait_for_alerts, table.foo))
+
+    updates_stream = graph_runner.GraphRunner(
+        G,
+        with_http_server=True,
+        monitoring_level=pw.MonitoringLevel.NONE,
+        alert_thresholds=pw.AlertThresholds(
+            max_output_staleness=datetime.timedelta(0)
+        ),
+    ).run_tables(alerts)[0]
+    [alert] = json.loads(updates_stream[0].values[0])
+    assert alert["name"] == "output_staleness"
+    assert alert["value"] > alert["threshold"] == 0
+
+
+def http_server_post(path: str, max_retries: int = 4) -> int:
+    port = os.environ.get("PATHWAY_MONITORING_HTTP_PORT", "20000")
+    request = urllib.request.Request(f"http://localhost:{port}{path}", method="POST")
+
+    for n_attempt in range(max_retries):
+        time.sleep(2**n_attempt * 0.1)
+        try:
+            with urllib.request.urlopen(request) as response:
+                return response.status
+        except urllib.error.URLError:
+            continue
+    return -1
+
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_shuts_down_gracefully():
+    class InfiniteSubject(pw.io.python.ConnectorSubject):
+        def run(self):
+            for i in itertools.count():
+                self.next(value=i)
+                if i == 10:
+                    assert http_server_post("/shutdown") == 202
+                time.sleep(0.01)
+
+    class InputSchema(pw.Schema):
+        value: int
+
+    table = pw.io.python.read(
+        InfiniteSubject(), schema=InputSchema, autocommit_duration_ms=10
+    )
+    values = []
+    pw.io.subscribe(
+        table,
+        on_change=lambda key, row, time, is_addition: values.append(row["value"]),
+    )
+    pw.run(with_http_server=True, monitoring_level=pw.MonitoringLevel.NONE)
+
+    # The rows read before the shutdown are all processed
+    assert sorted(values) == list(range(len(values)))
+
+
+@pytest.mark.xdist_group(name="http_server_tests")
+def test_http_server_authenticates_clients(monkeypatch):
+    monkeypatch.setenv("PATHWAY_MONITORING_HTTP_TOKENS", "admin:secret")
+    monkeypatch.setenv("PATHWAY_MONITORING_HTTP_AUTHORIZATION", "/ready=*")
+    table = T(
+        """
+            | foo
+        1   | 42
+        """
+    )
+
+    response_codes = table.select(
+        without_token=pw.apply_async(
+            http_server_status, table.foo, max_retries=4, path="/metrics"
+        ),
+        with_token=pw.apply_async(
+            http_server_status,
+            table.foo,
+            max_retries=4,
+            path="/metrics",
+            headers={"Authorization": "Bearer secret"},
+        ),
+        with_wrong_token=pw.apply_async(
+            http_server_status,
+            table.foo,
+            max_retries=4,
+            path="/metrics",
+            headers={"Authorization": "Bearer guess"},
+        ),
+        open_route=pw.apply_async(
+            http_server_status, table.foo, max_retries=4, path="/ready"
+        ),
+    )
+
+    updates_stream = graph_runner.GraphRunner(
+        G, with_http_server=True, monitoring_level=pw.MonitoringLevel.NONE
+    ).run_tables(response_codes)[0]
+    assert list(updates_stream[0].values) == [401, 200, 401, 503]
@@ -664,6 +664,133 @@ def test_subscribe():
     )
 
 
+def test_subscribe_on_commit():
+    table = T(
+        """
+        value | __time__
+        1     | 2
+        2     | 2
+        3     | 4
+        """
+    )
+
+    events: list[tuple] = []
+
+    def on_change(key, row, time, is_addition):
+        events.append(("change", time, row["value"]))
+
+    def on_commit(time):
+        events.append(("commit", time))
+
+    pw.io.subscribe(table, on_change=on_change, on_commit=on_commit)
+
+    run_all()
+
+    commits = [event[1] for event in events if event[0] == "commit"]
+    assert commits
+    assert commits == sorted(set(commits))
+    for index, event in enumerate(events):
+        if event[0] == "commit":
+            # no changes at the times before a commit come after it
+            assert all(
+                later[1] >= event[1]
+                for later in events[index + 1 :]
+                if later[0] == "change"
+            )
+
+
+class _EndlessSubject(pw.io.python.ConnectorSubject):
+    def run(self):
+        for value in range(3):
+            self.next_json({"value": value})
+        self.commit()
+        while True:
+            time.sleep(0.1)
+
+    def _is_finite(self) -> bool:
+        return False
+
+
+def _subscribe_until_received(table: pw.Table, count: int) -> threading.Event:
+    received = threading.Event()
+    rows: list[int] = []
+
+    def on_change(key, row, time, is_addition):
+        rows.append(row["value"])
+        if len(rows) == count:
+            received.set()
+
+    pw.io.subscribe(table, on_change=on_change)
+    return received
+
+
+def test_run_context_cancelled_by_keyboard_interrupt():
+    class InputSchema(pw.Schema):
+        value: int
+
+    table = pw.io.python.read(_EndlessSubject(), schema=InputSchema)
+    received = _subscribe_until_received(table, 3)
+
+    with pw.run_context(monitoring_level=pw.MonitoringLevel.NONE) as running:
+        assert received.wait(timeout=30)
+        raise KeyboardInterrupt
+
+    assert running.wait()
+    assert running.stats is not None
+    assert running.stats.cancelled
+    assert sum(running.stats.input_messages.values()) == 3
+    assert sum(running.stats.output_rows.values()) == 3
+
+
+def test_run_context_error_in_block():
+    class InputSchema(pw.Schema):
+        value: int
+
+    table = pw.io.python.read(_EndlessSubject(), schema=InputSchema)
+    received = _subscribe_until_received(table, 3)
+
+    with pytest.raises(ValueError, match="failure in the block"):
+        with pw.run_context(monitoring_level=pw.MonitoringLevel.NONE) as running:
+            assert received.wait(timeout=30)
+            raise ValueError("failure in the block")
+
+    assert running.stats is not None
+    assert running.stats.cancelled
+
+
+def test_consistent_outputs():
+    class TestSubject(pw.io.python.ConnectorSubject):
+        def run(self):
+            for value in ["foo", "bar", "baz"]:
+                self.next_str(value)
+                self.commit()
+
+    table = pw.io.python.read(TestSubject(), format="raw")
+    counts = table.reduce(count=pw.reducers.count())
+
+    rows: dict[int, list[str]] = {}
+    totals: dict[int, int] = {}
+
+    def on_row(key, row, time, is_addition):
+        rows.setdefault(time, []).append(row["data"])
+
+    def on_count(key, row, time, is_addition):
+        if is_addition:
+            totals[time] = row["count"]
+
+    pw.io.subscribe(table, on_change=on_row)
+    pw.io.subscribe(counts, on_change=on_count)
+
+    run(consistent_outputs=True)
+
+    assert sorted(value for values in rows.values() for value in values) == [
+        "bar",
+        "baz",
+        "foo",
+    ]
+    assert max(totals.items())[1] == 3
+    assert rows.keys() == totals.keys()
+
 def test_async_transformer(monkeypatch):
     if os.environ.get("PATHWAY_PERSISTENT_STORAGE"):
         monkeypatch.delenv("PATHWAY_PERSISTENT_STORAGE")
@@ -1286,6 +1413,71 @@ def test_python_connector_persistence(tmp_path: pathlib.Path):
     assert set(result["data"]) == {"three", "four"}
 
 
+def test_python_connector_source_persistence(tmp_path: pathlib.Path):
+    persistent_storage_path = tmp_path / "PStorage"
+
+    class TestSource(pw.io.python.ConnectorSource):
+        def __init__(self, items):
+            super().__init__()
+            self.items = items
+            self.position = 0
+            self.sought = []
+            self.persisted = []
+
+        def read(self):
+            if self.position == len(self.items):
+                return None
+            self.position += 1
+            return {"data": self.items[self.position - 1]}, self.position
+
+        def seek(self, cursor):
+            self.sought.append(cursor)
+            self.position = cursor
+
+        def on_persisted(self, cursor):
+            self.persisted.append(cursor)
+
+    class TestSink(pw.io.python.ConnectorSink):
+        def __init__(self):
+            self.rows = []
+            self.flushes = 0
+
+        def write(self, row):
+            self.rows.append(row)
+
+        def flush(self):
+            self.flushes += 1
+
+    class InputSchema(pw.Schema):
+        data: str
+
+    def run_computation(items):
+        G.clear()
+        source = TestSource(items)
+        sink = TestSink()
+        table = pw.io.python.read(source, schema=InputSchema, persistent_id="1")
+        pw.io.python.write(table, sink)
+        run(
+            persistence_config=pw.persistence.Config.simple_config(
+                pw.persistence.Backend.filesystem(persistent_storage_path),
+            )
+        )
+        return source, sink
+
+    source, sink = run_computation(["one", "two"])
+    assert source.sought == []
+    assert source.persisted[-1] == 2
+    assert sorted(row["data"] for row in sink.rows) == ["one", "two"]
+    assert all(row["diff"] == 1 for row in sink.rows)
+    assert sink.flushes > 0
+
+    # The source continues from the persisted cursor, so only the new items are read
+    source, sink = run_computation(["one", "two", "three", "four"])
+    assert source.sought == [2]
+    assert source.persisted[-1] == 4
+    assert sorted(row["data"] for row in sink.rows) == ["four", "three"]
+
+
 def test_no_pstorage(tmp_path: pathlib.Path):
     input_path = tmp_path / "input.txt"
     output_path = tmp_path / "input.txt"
@@ -1931,6 +2123,7 @@ def test_replay(tmp_path: pathlib.Path):
         generate_rows=0,
         continue_after_replay=True,
         snapshot_access=api.SnapshotAccess.FULL,
+        replay_until=None,
     ):
         G.clear()
 
@@ -1957,6 +2150,7 @@ def test_replay(tmp_path: pathlib.Path):
                 persistence_mode=persistence_mode,
                 continue_after_replay=continue_after_replay,
                 snapshot_access=snapshot_access,
+                replay_until=replay_until,
             )
         )
 
@@ -1971,6 +2165,15 @@ def test_replay(tmp_path: pathlib.Path):
 
     run_graph(api.PersistenceMode.SPEEDRUN_REPLAY, expected)
 
+    # Replaying up to a time after the last one gives all the persisted rows
+    run_graph(
+        api.PersistenceMode.SPEEDRUN_REPLAY,
+        expected,
+        continue_after_replay=False,
+        snapshot_access=api.SnapshotAccess.REPLAY,
+        replay_until=2**63,
+    )
+
     # With continue_after_replay=False, we should not generate new rows
     run_graph(
         api.PersistenceMode.SPEEDRUN_REPLAY,
@@ -2015,6 +2218,352 @@ def test_replay(tmp_path: pathlib.Path):
     run_graph(api.PersistenceMode.SPEEDRUN_REPLAY, expected)
 
 
+@pytest.mark.parametrize(
+    "snapshot_compression,snapshot_compression_level",
+    [(None, None), ("zstd", None), ("zstd", 19), ("lz4", None)],
+)
+def test_persistence_snapshot_compression(
+    tmp_path: pathlib.Path, snapshot_compression, snapshot_compression_level
+):
+    input_path = tmp_path / "input"
+    os.makedirs(input_path)
+    write_lines(input_path / "1.csv", ["k,v", "1,foo", "2,bar"])
+
+    def run_graph(
+        expected: list[str], compression: str | None, compression_level: int | None
+    ):
+        G.clear()
+        table = pw.io.csv.read(
+            input_path,
+            schema=pw.schema_from_types(k=int, v=str),
+            mode="static",
+            persistent_id="1",
+        )
+        callback = CollectValuesCallback(expected, "v")
+        pw.io.subscribe(table, callback, callback.on_end)
+        run(
+            persistence_config=pw.persistence.Config.simple_config(
+                pw.persistence.Backend.filesystem(tmp_path / "storage"),
+                snapshot_compression=compression,
+                snapshot_compression_level=compression_level,
+            )
+        )
+
+    run_graph(["foo", "bar"], snapshot_compression, snapshot_compression_level)
+    write_lines(input_path / "2.csv", ["k,v", "3,baz"])
+    # The data persisted with any compression can be read with any setting
+    run_graph(["baz"], "zstd", 6)
+    write_lines(input_path / "3.csv", ["k,v", "4,qux"])
+    run_graph(["qux"], "lz4", None)
+    write_lines(input_path / "4.csv", ["k,v", "5,quux"])
+    run_graph(["quux"], None, None)
+    run_graph([], snapshot_compression, snapshot_compression_level)
+
+
+def test_persistence_snapshot_compression_validated(tmp_path: pathlib.Path):
+    backend = pw.persistence.Backend.filesystem(tmp_path)
+    with pytest.raises(ValueError, match="snapshot_compression"):
+        pw.persistence.Config.simple_config(backend, snapshot_compression="deflate")
+    with pytest.raises(ValueError, match="snapshot_compression_level"):
+        pw.persistence.Config.simple_config(
+            backend, snapshot_compression="zstd", snapshot_compression_level=23
+        )
+    with pytest.raises(ValueError, match="snapshot_compression_level"):
+        pw.persistence.Config.simple_config(
+            backend, snapshot_compression="lz4", snapshot_compression_level=1
+        )
+
+
+def test_persistence_encryption(tmp_path: pathlib.Path):
+    input_path = tmp_path / "input"
+    storage_path = tmp_path / "storage"
+    os.makedirs(input_path)
+    write_lines(input_path / "1.csv", ["k,v", "1,foo", "2,bar"])
+    encryption_key = bytes(range(32))
+
+    def run_graph(expected: list[str]):
+        G.clear()
+        table = pw.io.csv.read(
+            input_path,
+            schema=pw.schema_from_types(k=int, v=str),
+            mode="static",
+            persistent_id="1",
+        )
+        callback = CollectValuesCallback(expected, "v")
+        pw.io.subscribe(table, callback, callback.on_end)
+        run(
+            persistence_config=pw.persistence.Config.simple_config(
+                pw.persistence.Backend.filesystem(storage_path),
+                encryption_key=encryption_key,
+            )
+        )
+
+    run_graph(["foo", "bar"])
+    write_lines(input_path / "2.csv", ["k,v", "3,baz"])
+    run_graph(["baz"])
+
+    for path in storage_path.rglob("*"):
+        if path.is_file():
+            assert b"foo" not in path.read_bytes()
+            assert b"baz" not in path.read_bytes()
+
+
+def test_persistence_encryption_migration(tmp_path: pathlib.Path):
+    input_path = tmp_path / "input"
+    storage_path = tmp_path / "storage"
+    os.makedirs(input_path)
+    write_lines(input_path / "1.csv", ["k,v", "1,foo", "2,bar"])
+    encryption_key = bytes(range(32))
+
+    def run_graph(
+        expected: list[str],
+        encryption_key: bytes | None,
+        migrate_unencrypted_data: bool = False,
+    ):
+        G.clear()
+        table = pw.io.csv.read(
+            input_path,
+            schema=pw.schema_from_types(k=int, v=str),
+            mode="static",
+            persistent_id="1",
+        )
+        callback = CollectValuesCallback(expected, "v")
+        pw.io.subscribe(table, callback, callback.on_end)
+        run(
+            persistence_config=pw.persistence.Config.simple_config(
+                pw.persistence.Backend.filesystem(storage_path),
+                encryption_key=encryption_key,
+                migrate_unencrypted_data=migrate_unencrypted_data,
+            )
+        )
+
+    run_graph(["foo", "bar"], None)
+    write_lines(input_path / "2.csv", ["k,v", "3,baz"])
+    # The data persisted without encryption is only read by the migration
+    with pytest.raises(api.EngineError, match="isn't encrypted"):
+        run_graph(["baz"], encryption_key)
+    run_graph(["baz"], encryption_key, migrate_unencrypted_data=True)
+
+    for path in storage_path.rglob("*"):
+        if path.is_file():
+            assert b"foo" not in path.read_bytes()
+    write_lines(input_path / "3.csv", ["k,v", "4,qux"])
+    run_graph(["qux"], encryption_key)
+
+
+def test_persistence_encryption_key_validated(tmp_path: pathlib.Path):
+    backend = pw.persistence.Backend.filesystem(tmp_path)
+    with pytest.raises(ValueError, match="encryption_key"):
+        pw.persistence.Config.simple_config(backend, encryption_key=b"too short")
+    with pytest.raises(ValueError, match="migrate_unencrypted_data"):
+        pw.persistence.Config.simple_config(backend, migrate_unencrypted_data=True)
+
+
+def test_persistence_gcs_backend():
+    with mock.patch.object(
+        pw.persistence, "AwsS3Settings", wraps=pw.persistence.AwsS3Settings
+    ) as settings:
+        backend = pw.persistence.Backend.gcs(
+            "root", bucket_name="bucket", hmac_access_id="id", hmac_secret="secret"
+        )
+
+    # The XML API of Google Cloud Storage is accessed like S3 with path-style requests
+    settings.assert_called_once_with(
+        bucket_name="bucket",
+        access_key="id",
+        secret_access_key="secret",
+        with_path_style=True,
+        region="auto",
+        endpoint="https://storage.googleapis.com",
+    )
+    assert isinstance(backend.engine_data_storage, api.DataStorage)
+
+
+def test_persistence_gcs_backend_service_account(
+    tmp_path: pathlib.Path, monkeypatch: pytest.MonkeyPatch
+):
+    credentials_file = tmp_path / "credentials.json"
+    credentials_file.write_text('{"type": "service_account"}')
+    monkeypatch.setenv("GOOGLE_APPLICATION_CREDENTIALS", os.fspath(credentials_file))
+    with mock.patch.object(api, "GcsSettings", wraps=api.GcsSettings) as settings:
+        backend = pw.persistence.Backend.gcs("root", bucket_name="bucket")
+
+    # The application default credentials are used, with the JSON API of the storage
+    settings.assert_called_once_with(
+        bucket_name="bucket",
+        credentials_json='{"type": "service_account"}',
+        endpoint=None,
+    )
+    assert backend.engine_data_storage.storage_type == "gcs"
+
+    with pytest.raises(ValueError, match="HMAC key"):
+        pw.persistence.Backend.gcs(
+            "root",
+            bucket_name="bucket",
+            credentials_file=credentials_file,
+            hmac_access_id="id",
+            hmac_secret="secret",
+        )
+
+
+def test_persistence_azure_backend(monkeypatch: pytest.MonkeyPatch):
+    monkeypatch.delenv("AZURE_STORAGE_CONNECTION_STRING", raising=False)
+    monkeypatch.delenv("AZURE_STORAGE_ACCOUNT", raising=False)
+    connection_string = (
+        "DefaultEndpointsProtocol=https;AccountName=account;AccountKey=a2V5;"
+        "EndpointSuffix=core.chinacloudapi.cn"
+    )
+    with mock.patch.object(
+        api, "AzureBlobStorageSettings", wraps=api.AzureBlobStorageSettings
+    ) as settings:
+        backend = pw.persistence.Backend.azure(
+            "root", container_name="container", connection_string=connection_string
+        )
+        monkeypatch.setenv("AZURE_STORAGE_ACCOUNT", "other")
+        monkeypatch.setenv("AZURE_STORAGE_SAS_TOKEN", "sv=2021-08-06&sig=abc")
+        pw.persistence.Backend.azure("root", container_name="container")
+
+    assert settings.call_args_list == [
+        mock.call(
+            account_name="account",
+            container_name="container",
+            account_key="a2V5",
+            sas_token=None,
+            endpoint="https://account.blob.core.chinacloudapi.cn",
+        ),
+        mock.call(
+            account_name="other",
+            container_name="container",
+            account_key=None,
+            sas_token="sv=2021-08-06&sig=abc",
+            endpoint=None,
+        ),
+    ]
+    assert backend.engine_data_storage.storage_type == "azure"
+
+    monkeypatch.delenv("AZURE_STORAGE_SAS_TOKEN")
+    monkeypatch.delenv("AZURE_STORAGE_KEY", raising=False)
+    with pytest.raises(ValueError, match="shared access signature"):
+        pw.persistence.Backend.azure("root", container_name="container")
+
+
+def test_persistence_savepoint(tmp_path: pathlib.Path):
+    input_path = tmp_path / "input"
+    os.makedirs(input_path)
+    write_lines(input_path / "1.csv", ["k,v", "1,foo", "2,bar"])
+    persistence_config = pw.persistence.Config.simple_config(
+        pw.persistence.Backend.filesystem(tmp_path / "storage"),
+    )
+
+    def run_graph(expected: list[str], savepoint: str | None = None):
+        G.clear()
+        table = pw.io.csv.read(
+            input_path,
+            schema=pw.schema_from_types(k=int, v=str),
+            mode="static",
+            persistent_id="1",
+        )
+        callback = CollectValuesCallback(expected, "v")
+        pw.io.subscribe(table, callback, callback.on_end)
+        if savepoint is not None:
+            persistence_config.savepoint(savepoint)
+        run(persistence_config=persistence_config)
+
+    run_graph(["foo", "bar"], savepoint="first")
+    write_lines(input_path / "2.csv", ["k,v", "3,baz"])
+    run_graph(["baz"])
+    run_graph([])
+
+    # The run after the savepoint is repeated
+    pw.persistence.restore_savepoint(persistence_config, "first")
+    run_graph(["baz"])
+    run_graph([])
+
+    with pytest.raises(api.EngineError, match="savepoint"):
+        pw.persistence.restore_savepoint(persistence_config, "unknown")
+    with pytest.raises(ValueError, match="savepoint name"):
+        persistence_config.savepoint("not/allowed")
+
+
+def test_persistence_schema_evolution(tmp_path: pathlib.Path):
+    input_path = tmp_path / "input"
+    os.makedirs(input_path)
+    write_lines(input_path / "1.csv", ["k,v", "1,foo", "2,bar"])
+    backend = pw.persistence.Backend.filesystem(tmp_path / "storage")
+
+    class InputSchema(pw.Schema):
+        k: int
+        v: str
+
+    class EvolvedInputSchema(pw.Schema):
+        k: float
+        name: str
+        extra: int = pw.column_definition(default_value=5)
+
+    def run_graph(schema, expected_names, expected_totals, schema_migrations=None):
+        G.clear()
+        table = pw.io.csv.read(
+            input_path,
+            schema=schema,
+            mode="static",
+            persistent_id="1",
+        )
+        if "name" not in schema.column_names():
+            table = table.select(pw.this.k, name=pw.this.v, extra=5)
+        result = table.reduce(
+            names=pw.reducers.sorted_tuple(pw.this.name),
+            total=pw.reducers.sum(pw.this.k * pw.this.extra),
+        )
+        names_callback = CollectValuesCallback(expected_names, "names")
+        totals_callback = CollectValuesCallback(expected_totals, "total")
+        pw.io.subscribe(result, names_callback, names_callback.on_end)
+        pw.io.subscribe(result, totals_callback, totals_callback.on_end)
+        run(
+            persistence_config=pw.persistence.Config.simple_config(
+                backend, schema_migrations=schema_migrations
+            )
+        )
+
+    run_graph(InputSchema, [("bar", "foo")], [15])
+    write_lines(input_path / "2.csv", ["k,name,extra", "3,baz,7"])
+    # The persisted rows get the renamed column, the default of the added one and
+    # the widened type of k
+    run_graph(
+        EvolvedInputSchema,
+        [("bar", "foo"), ("bar", "baz", "foo")],
+        [15.0, 36.0],
+        schema_migrations={
+            "1": {"name": pw.persistence.ColumnMigration(renamed_from="v")}
+        },
+    )
+
+
+def test_snapshot_interval_rows_has_to_be_positive(tmp_path: pathlib.Path):
+    backend = pw.persistence.Backend.filesystem(tmp_path)
+    with pytest.raises(ValueError, match="snapshot_interval_rows"):
+        pw.persistence.Config.simple_config(backend, snapshot_interval_rows=0)
+    with pytest.raises(ValueError, match="snapshot_interval_bytes"):
+        pw.persistence.Config.simple_config(backend, snapshot_interval_bytes=-1)
+
+
+def test_checkpoint_retention_has_to_be_positive(tmp_path: pathlib.Path):
+    backend = pw.persistence.Backend.filesystem(tmp_path)
+    with pytest.raises(ValueError, match="keep_last_checkpoints"):
+        pw.persistence.Config.simple_config(backend, keep_last_checkpoints=0)
+    with pytest.raises(ValueError, match="keep_checkpoints_days"):
+        pw.persistence.Config.simple_config(backend, keep_checkpoints_days=-1.5)
+
+
+def test_replay_until_requires_replay_only(tmp_path: pathlib.Path):
+    backend = pw.persistence.Backend.filesystem(tmp_path)
+    with pytest.raises(ValueError, match="replay_until"):
+        pw.persistence.Config.simple_config(backend, replay_until=0)
+    with pytest.raises(ValueError, match="replay_until"):
+        pw.persistence.Config.simple_config(
+            backend, snapshot_access=api.SnapshotAccess.REPLAY, replay_until=0
+        )
+
+
 def test_replay_timestamps(tmp_path: pathlib.Path):
     replay_dir = tmp_path / "test_replay_timestamps"
 
@@ -2466,6 +3015,39 @@ def test_stream_generator_two_tables_multiple_workers(monkeypatch: pytest.Monkey
     assert on_change.call_count == 3
 
 
+def test_step_by_step_run():
+    stream_generator = pw.debug.StreamGenerator()
+    t1 = stream_generator.table_from_markdown(
+        """
+       | colA | _time | _diff
+    1  | 1    | 2     | 1
+    2  | 2    | 2     | 1
+    1  | 1    | 4     | -1
+    3  | 4    | 6     | 1
+    """
+    )
+    t2 = t1.reduce(sum=pw.reducers.sum(pw.this.colA))
+
+    states = []
+    with pw.debug.StepByStepRun(
+        t1, t2, persistence_config=stream_generator.persistence_config()
+    ) as run:
+        while (time := run.step()) is not None:
+            states.append(
+                (
+                    time,
+                    sorted(run.table_state(t1, include_id=False)["colA"]),
+                    run.table_state(t2, include_id=False)["sum"].tolist(),
+                )
+            )
+
+    assert states == [
+        (2, [1, 2], [3]),
+        (4, [2], [2]),
+        (6, [2, 4], [6]),
+    ]
+
+
 def test_python_connector_upsert_raw(tmp_path: pathlib.Path):
     class TestSubject(pw.io.python.ConnectorSubject):
         @property
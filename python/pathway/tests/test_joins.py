@@ -1091,6 +1091,98 @@ def test_outer_join_desugaring_02():
     assert_table_equality_wo_index(res, expected)
 
 
+
+def test_outer_join_defaults():
+    t1 = T(
+        """
+            | a  | b
+          1 | 11 | 111
+          2 | 12 | 112
+          3 | 13 | 113
+        """
+    )
+
+    t2 = T(
+        """
+            | c  | d
+          1 | 11 | 211
+          2 | 14 | 214
+        """
+    )
+
+    expected = T(
+        """
+        a  | b   | d   | side
+        11 | 111 | 211 | both
+        12 | 112 | -1  | left_only
+        13 | 113 | -1  | left_only
+           | 0   | 214 | right_only
+        """
+    ).update_types(a=Optional[int])
+
+    res = t1.join_outer(
+        t2, t1.a == t2.c, defaults={t1.b: 0, t2.d: -1}, indicator="side"
+    ).select(t1.a, t1.b, t2.d, pw.this.side)
+    assert_table_equality_wo_index(res, expected)
+
+
+def test_left_join_defaults_keep_none():
+    t1 = T(
+        """
+            | a
+          1 | 11
+          2 | 12
+          3 | 13
+        """
+    )
+
+    t2 = T(
+        """
+            | c  | d
+          1 | 11 | 211
+          2 | 12 |
+        """
+    )
+
+    expected = T(
+        """
+        a  | d   | side
+        11 | 211 | both
+        12 |     | both
+        13 | 0   | left_only
+        """
+    ).update_types(d=Optional[int])
+
+    res = t1.join_left(
+        t2, t1.a == t2.c, defaults={t2.d: 0}, indicator="side"
+    ).select(t1.a, t2.d, pw.this.side)
+    assert_table_equality_wo_index(res, expected)
+
+
+def test_join_defaults_wrong_side():
+    t1 = T(
+        """
+            | a
+          1 | 11
+        """
+    )
+
+    t2 = T(
+        """
+            | c
+          1 | 11
+        """
+    )
+
+    with pytest.raises(ValueError):
+        t1.join_left(t2, t1.a == t2.c, defaults={t1.a: 0})
+
+    with pytest.raises(ValueError):
+        t1.join(t2, t1.a == t2.c, indicator="side")
+
+    with pytest.raises(ValueError):
+        t1.join_outer(t2, t1.a == t2.c, indicator="a")
+
 def test_outer_join_desugaring_03():
     # ID-s pf t1 and t2 overlap, but are not equal
     # - equal sets of input ID could make test false positive,
@@ -1511,3 +1603,11 @@ def test_joins_typing_on():
     right_table = pw.Table.empty(col=str)
     with pytest.raises(expected_exception=TypeError):
         left_table.join(right_table, left_table.col == right_table.col)
+
+
+def test_joins_keys_of_different_types():
+    left_table = pw.Table.empty(col=int)
+    right_table = pw.Table.empty(col=float)
+    result = left_table.join(right_table, left_table.col == right_table.col).select()
+    with pytest.raises(TypeError, match="join key type mismatch"):
+        pw.debug.compute_and_print(result)
@@ -1056,12 +1056,15 @@ def run_matrix_multiplcation(
     for res_i, exp_i in zip(res_pd, expected):
         if dtype == float:
             assert np.isclose(res_i, exp_i, rtol=1e-15, atol=0.0).all()
+        elif dtype == np.float32:
+            assert res_i.dtype == np.float32
+            assert np.isclose(res_i, exp_i, rtol=1e-6, atol=1e-6).all()
         else:
             assert (res_i == exp_i).all()
         assert res_i.shape == exp_i.shape
 
 
-@pytest.mark.parametrize("dtype", [int, float])
+@pytest.mark.parametrize("dtype", [int, float, np.float32])
 def test_matrix_multiplication_2d_by_2d(dtype: type) -> None:
     np.random.seed(42)
     r = np.random.randn
@@ -1079,7 +1082,7 @@ def test_matrix_multiplication_2d_by_2d(dtype: type) -> None:
     run_matrix_multiplcation(pairs, dtype)
 
 
-@pytest.mark.parametrize("dtype", [int, float])
+@pytest.mark.parametrize("dtype", [int, float, np.float32])
 def test_matrix_multiplication_2d_by_1d(dtype: type) -> None:
     np.random.seed(42)
     r = np.random.randn
@@ -1095,7 +1098,7 @@ def test_matrix_multiplication_2d_by_1d(dtype: type) -> None:
     run_matrix_multiplcation(pairs, dtype)
 
 
-@pytest.mark.parametrize("dtype", [int, float])
+@pytest.mark.parametrize("dtype", [int, float, np.float32])
 def test_matrix_multiplication_1d_by_2d(dtype: type) -> None:
     np.random.seed(42)
     r = np.random.randn
@@ -1111,7 +1114,7 @@ def test_matrix_multiplication_1d_by_2d(dtype: type) -> None:
     run_matrix_multiplcation(pairs, dtype)
 
 
-@pytest.mark.parametrize("dtype", [int, float])
+@pytest.mark.parametrize("dtype", [int, float, np.float32])
 def test_matrix_multiplication_1d_by_1d(dtype: type) -> None:
     pairs: list[tuple[np.ndarray, np.ndarray]] = [
         (np.ones(2), np.ones(2)),
@@ -1165,6 +1168,73 @@ def test_matrix_multiplication_errors_on_shapes_mismatch(a, b) -> None:
         run_all()
 
 
+def test_matrix_multiplication_mixed_float_precisions() -> None:
+    a = np.array([[1.5, 2.0], [3.0, 4.0]], dtype=np.float32)
+    b = np.array([0.1, 0.2])
+    t = table_from_pandas(pd.DataFrame({"a": [a], "b": [b]}))
+    res = table_to_pandas(t.select(c=t.a @ t.b, d=t.b @ t.a))
+    assert res["c"].iloc[0].dtype == np.float64
+    assert np.isclose(res["c"].iloc[0], a @ b, rtol=1e-15, atol=0.0).all()
+    assert np.isclose(res["d"].iloc[0], b @ a, rtol=1e-15, atol=0.0).all()
+
+
+def test_matrix_multiplication_int_accumulated_exactly() -> None:
+    a = np.array([[2**62, 2**62, -(2**62)]])
+    b = np.array([1, 1, 1])
+    t = table_from_pandas(pd.DataFrame({"a": [a], "b": [b]}))
+    res = table_to_pandas(t.select(c=t.a @ t.b))
+    assert (res["c"].iloc[0] == np.array([2**62])).all()
+
+
+def test_matrix_multiplication_errors_on_int_overflow() -> None:
+    a = np.array([[2**62, 2**62]])
+    b = np.array([1, 1])
+    t = table_from_pandas(pd.DataFrame({"a": [a], "b": [b]}))
+    t.select(c=t.a @ t.b)
+    with pytest.raises(ValueError):
+        run_all()
+
+
+def test_matrix_multiplication_sparse() -> None:
+    sparse = pytest.importorskip("scipy.sparse")
+    np.random.seed(42)
+    a = sparse.random(4, 6, density=0.3, format="csr")
+    b = sparse.random(6, 3, density=0.3, format="csc")
+    x = np.random.randn(6)
+    y = np.random.randn(5, 4).astype(np.float32)
+    t = table_from_pandas(
+        pd.DataFrame({"a": [a], "b": [b], "x": [x], "y": [y]}),
+        schema=pw.schema_from_types(
+            a=np.ndarray, b=np.ndarray, x=np.ndarray, y=np.ndarray
+        ),
+    )
+    res = table_to_pandas(
+        t.select(a=t.a, ab=t.a @ t.b, ax=t.a @ t.x, ya=t.y @ t.a)
+    ).iloc[0]
+    assert sparse.issparse(res["a"])
+    assert (res["a"] != a).nnz == 0
+    assert sparse.issparse(res["ab"])
+    assert np.isclose(
+        res["ab"].toarray(), (a @ b).toarray(), rtol=1e-15, atol=0.0
+    ).all()
+    assert isinstance(res["ax"], np.ndarray)
+    assert res["ax"].shape == (4,)
+    assert np.isclose(res["ax"], a @ x, rtol=1e-15, atol=0.0).all()
+    assert isinstance(res["ya"], np.ndarray)
+    assert np.isclose(res["ya"], y @ a, rtol=1e-15, atol=0.0).all()
+
+
+def test_matrix_multiplication_sparse_errors_on_shapes_mismatch() -> None:
+    sparse = pytest.importorskip("scipy.sparse")
+    t = table_from_pandas(
+        pd.DataFrame({"a": [sparse.eye(3, format="csr")], "b": [np.zeros(4)]}),
+        schema=pw.schema_from_types(a=np.ndarray, b=np.ndarray),
+    )
+    t.select(c=t.a @ t.b)
+    with pytest.raises(ValueError):
+        run_all()
+
+
 def test_optional_int_vs_float():
     table = T(
         """
@@ -394,3 +394,56 @@ def test_stateful_many_binary():
             id_from=["pet"],
         ),
     )
+
+
+def test_retractable_reducer():
+    calls = {"init": 0, "add": 0, "retract": 0}
+
+    def init():
+        calls["init"] += 1
+        return (0, 0)
+
+    def add(state, owner, age):
+        calls["add"] += 1
+        return (state[0] + len(owner) * age, state[1] + 1)
+
+    def retract(state, owner, age):
+        calls["retract"] += 1
+        return (state[0] - len(owner) * age, state[1] - 1)
+
+    def extract(state) -> int:
+        return state[0]
+
+    lens = pw.reducers.retractable_reducer(
+        init=init, add=add, retract=retract, extract=extract
+    )
+
+    left = T(
+        """
+            pet  |  owner  | age | __time__ | __diff__
+            dog  | Alice   | 10  | 0        | 1
+            dog  | Bob     | 9   | 0        | 1
+            cat  | Alice   | 8   | 0        | 1
+            dog  | Bob     | 7   | 0        | 1
+            dog  | Bob     | 7   | 2        | -1
+            cat  | Alice   | 8   | 4        | -1
+            fish | Bob     | 1   | 4        | 1
+        """
+    )
+
+    left_res = left.groupby(left.pet).reduce(left.pet, lens=lens(left.owner, left.age))
+
+    assert_table_equality(
+        left_res,
+        T(
+            """
+                pet  | lens
+                dog  | 77
+                fish | 3
+            """,
+            id_from=["pet"],
+        ),
+    )
+    # The removed rows are retracted instead of recomputing the groups,
+    # and the state of the emptied group is dropped without a retraction
+    assert calls == {"init": 3, "add": 5, "retract": 1}
@@ -3,14 +3,16 @@
 from __future__ import annotations
 
 import csv
+import dataclasses
 import importlib
 import pathlib
 import sys
-from typing import Any
+from typing import Any, TypedDict
 
 import pytest
 
 import pathway as pw
+from pathway.internals import dtype as dt
 from pathway.internals.schema import Schema
 from pathway.io._utils import _compat_schema
 from pathway.tests.utils import write_csv
@@ -338,3 +340,56 @@ def test_schema_properties():
         pass
 
     assert D.universe_properties.append_only is True
+
+
+@dataclasses.dataclass
+class _Point:
+    x: int
+    y: float = 0.0
+
+
+class _Label(TypedDict):
+    text: str
+    weight: int | None
+
+
+def test_schema_from_row_type():
+    assert_same_schema(
+        pw.schema_from_row_type(_Point),
+        pw.schema_builder(
+            {
+                "x": pw.column_definition(dtype=int),
+                "y": pw.column_definition(dtype=float, default_value=0.0),
+            },
+            name="_Point",
+        ),
+    )
+    assert pw.schema_from_row_type(_Label)._dtypes() == {
+        "text": dt.STR,
+        "weight": dt.Optional(dt.INT),
+    }
+    with pytest.raises(TypeError, match="neither a dataclass nor a TypedDict"):
+        pw.schema_from_row_type(int)
+
+
+def test_row_converter():
+    schema = pw.schema_from_types(y=float, x=int)
+    converter = pw.RowConverter(_Point, schema)
+    assert converter.from_values([1.5, 2]) == _Point(x=2, y=1.5)
+    assert converter.from_dict({"x": 2, "y": 1.5}) == _Point(x=2, y=1.5)
+    values = converter.to_values(_Point(x=3, y=4))
+    assert values == [4.0, 3] and isinstance(values[0], float)
+    with pytest.raises(TypeError, match="field 'x'"):
+        converter.to_values(_Point(x="3", y=4.0))  # type: ignore[arg-type]
+
+    label_converter = pw.RowConverter(_Label)
+    label = label_converter.from_values(["a", None])
+    assert label == {"text": "a", "weight": None}
+    assert label_converter.to_values(label) == ["a", None]
+
+
+def test_row_converter_schema_mismatch():
+    with pytest.raises(ValueError, match="don't match the fields"):
+        pw.RowConverter(_Point, pw.schema_from_types(x=int))
+    with pytest.raises(TypeError, match="of the column 'x'"):
+        pw.RowConverter(_Point, pw.schema_from_types(x=str, y=float))
@@ -2,6 +2,9 @@
 
 from __future__ import annotations
 
+import pytest
+
+import pathway as pw
 from pathway import (
     ClassArg,
     Table,
@@ -20,7 +23,12 @@ from pathway.stdlib.indexing.sorting import (
     filter_smallest_k,
     prefix_sum_oracle,
 )
-from pathway.tests.utils import T, assert_table_equality, assert_table_equality_wo_index
+from pathway.tests.utils import (
+    T,
+    assert_stream_equality,
+    assert_table_equality,
+    assert_table_equality_wo_index,
+)
 
 
 def test_argmin():
@@ -638,3 +646,67 @@ def test_prevnext_many_instance():
             next=nodes.pointer_from(this.next, optional=True),
         ),
     )
+
+
+def test_top_n():
+    t = T(
+        """
+          | name  | score | __time__ | __diff__
+        1 | Alice |   80  |     2    |     1
+        2 | Bob   |   90  |     2    |     1
+        3 | Carol |   70  |     2    |     1
+        4 | David |   85  |     4    |     1
+        2 | Bob   |   90  |     6    |    -1
+        """
+    )
+    result = t.top_n(t.score, 2, descending=True, with_position=True)
+    expected = T(
+        """
+          | name  | score | position | __time__ | __diff__
+        1 | Alice |   80  |     1    |     2    |     1
+        2 | Bob   |   90  |     0    |     2    |     1
+        1 | Alice |   80  |     1    |     4    |    -1
+        4 | David |   85  |     1    |     4    |     1
+        2 | Bob   |   90  |     0    |     6    |    -1
+        4 | David |   85  |     1    |     6    |    -1
+        4 | David |   85  |     0    |     6    |     1
+        1 | Alice |   80  |     1    |     6    |     1
+        """
+    )
+    assert_stream_equality(result, expected)
+
+
+def test_top_n_with_instance():
+    t = T(
+        """
+          | team | score
+        1 | A    |   5
+        2 | A    |   3
+        3 | A    |   3
+        4 | B    |   7
+        5 | A    |   9
+        """
+    )
+    result = t.top_n(pw.this.score, 2, instance=pw.this.team)
+    assert_table_equality(
+        result,
+        T(
+            """
+              | team | score
+            2 | A    |   3
+            3 | A    |   3
+            4 | B    |   7
+            """
+        ),
+    )
+
+
+def test_top_n_non_positive():
+    t = T(
+        """
+          | score
+        1 |   5
+        """
+    )
+    with pytest.raises(ValueError, match="n has to be positive"):
+        t.top_n(t.score, 0)
@@ -3,11 +3,15 @@
 from __future__ import annotations
 
 import asyncio
+import datetime
 import os
 import pathlib
 from unittest import mock
 
+import numpy as np
+
 import pathway as pw
+from pathway.internals import common
 from pathway.tests.utils import T, assert_table_equality
 
 
@@ -74,6 +78,45 @@ def test_udf_class():
     )
 
 
+def test_udf_async_capacity():
+    running = 0
+    max_running = 0
+
+    @pw.udf_async(capacity=2)
+    async def inc(x: int) -> int:
+        nonlocal running, max_running
+        running += 1
+        max_running = max(max_running, running)
+        await asyncio.sleep(0.05)
+        running -= 1
+        return x + 1
+
+    input = T(
+        """
+        foo
+        1
+        2
+        3
+        4
+        5
+        """
+    )
+    result = input.select(ret=inc(pw.this.foo))
+    expected = T(
+        """
+        ret
+        2
+        3
+        4
+        5
+        6
+        """
+    )
+
+    assert_table_equality(result, expected)
+    assert max_running == 2
+
+
 def test_udf_async_options(tmp_path: pathlib.Path):
     cache_dir = tmp_path / "test_cache"
 
@@ -151,6 +194,122 @@ def test_udf_async():
     )
 
 
+def test_udf_with_async_function():
+    in_flight = 0
+    max_in_flight = 0
+
+    @pw.udf
+    async def inc(a: int) -> int:
+        nonlocal in_flight, max_in_flight
+        in_flight += 1
+        max_in_flight = max(max_in_flight, in_flight)
+        await asyncio.sleep(0.1)
+        in_flight -= 1
+        return a + 3
+
+    input = pw.debug.table_from_markdown(
+        """
+        a
+        1
+        2
+        3
+        4
+        5
+        """
+    )
+
+    with mock.patch.object(common, "ASYNC_APPLY_CAPACITY", 2):
+        result = input.select(ret=inc(pw.this.a))
+
+    assert_table_equality(
+        result,
+        T(
+            """
+            ret
+            4
+            5
+            6
+            7
+            8
+            """,
+        ),
+    )
+    # The coroutines are awaited concurrently instead of blocking the engine one by one,
+    # but no more of them than the capacity
+    assert max_in_flight == 2
+
+
+def test_udf_batch():
+    batch_sizes = []
+
+    @pw.udf_batch(return_type=float)
+    def scale(a, b):
+        assert isinstance(a, np.ndarray)
+        assert isinstance(b, np.ndarray)
+        batch_sizes.append(len(a))
+        return a * b
+
+    input = pw.debug.table_from_markdown(
+        """
+        a | b
+        1 | 0.5
+        2 | 1.5
+        3 | 2.0
+        4 | 0.25
+        """
+    )
+
+    result = input.select(ret=scale(pw.this.a, b=pw.this.b))
+
+    assert_table_equality(
+        result,
+        T(
+            """
+            ret
+            0.5
+            3.0
+            6.0
+            1.0
+            """,
+        ),
+    )
+    assert sum(batch_sizes) == 4
+    assert len(batch_sizes) < 4
+
+
+def test_udf_batch_max_batch_size():
+    batch_sizes = []
+
+    @pw.udf_batch(return_type=str, max_batch_size=2)
+    def concat(a, b):
+        batch_sizes.append(len(a))
+        return [x + y for x, y in zip(a, b)]
+
+    input = pw.debug.table_from_markdown(
+        """
+        a | b
+        x | 1
+        y | 2
+        z | 3
+        """
+    )
+
+    result = input.select(ret=concat(pw.this.a, pw.this.b.to_string()))
+
+    assert_table_equality(
+        result,
+        T(
+            """
+            ret
+            x1
+            y2
+            z3
+            """,
+        ),
+    )
+    assert max(batch_sizes) <= 2
+
+
 def test_udf_async_class():
     class Inc(pw.UDFAsync):
         def __init__(self, inc, **kwargs) -> None:
@@ -184,3 +343,40 @@ def test_udf_async_class():
             """,
         ),
     )
+
+
+def test_in_memory_cache():
+    counter = mock.Mock()
+
+    async def inc(x: int) -> int:
+        counter()
+        return x + 1
+
+    cache = pw.asynchronous.InMemoryCache(max_size=2)
+
+    async def run():
+        return [await cache.invoke(inc, x) for x in [1, 2, 1, 3, 1]]
+
+    assert asyncio.run(run()) == [2, 3, 2, 4, 2]
+    # 1 is the most recently used entry when 3 is added, so 2 is evicted instead
+    assert counter.call_count == 3
+
+
+def test_in_memory_cache_ttl():
+    counter = mock.Mock()
+
+    async def inc(x: int) -> int:
+        counter()
+        return x + 1
+
+    cache = pw.asynchronous.InMemoryCache(ttl=datetime.timedelta(milliseconds=50))
+
+    async def run():
+        first = await cache.invoke(inc, 1)
+        cached = await cache.invoke(inc, 1)
+        await asyncio.sleep(0.1)
+        expired = await cache.invoke(inc, 1)
+        return [first, cached, expired]
+
+    assert asyncio.run(run()) == [2, 2, 2]
+    assert counter.call_count == 2
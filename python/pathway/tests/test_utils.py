@@ -2,6 +2,8 @@
 
 from __future__ import annotations
 
+from unittest import mock
+
 import pandas as pd
 import pytest
 
@@ -13,6 +15,8 @@ from pathway.stdlib.utils.col import (
     unpack_col,
 )
 from pathway.stdlib.utils.filtering import argmax_rows, argmin_rows
+from pathway.stdlib.utils.lookup import lookup_join
+from pathway.stdlib.utils.sampling import bernoulli_sample, reservoir_sample
 from pathway.tests.utils import (
     T,
     assert_table_equality,
@@ -626,3 +630,162 @@ def test_table_from_rows():
 
     table = pw.debug.table_from_rows(schema=TestSchema, rows=rows, is_stream=False)
     assert_table_equality(table, expected)
+
+
+class PriceSchema(pw.Schema):
+    price: int
+    currency: str
+
+
+PRICES = {
+    "apple": {"price": 3, "currency": "EUR"},
+    "pear": {"price": 5, "currency": "USD"},
+}
+
+
+def test_lookup_join_left():
+    t = T(
+        """
+          | product
+        1 | apple
+        2 | pear
+        3 | plum
+        4 | apple
+    """
+    )
+    counter = mock.Mock()
+
+    def fetch(product):
+        counter(product)
+        return PRICES.get(product)
+
+    result = lookup_join(t, t.product, fetch, PriceSchema)
+    expected = T(
+        """
+          | product | price | currency
+        1 | apple   | 3     | EUR
+        2 | pear    | 5     | USD
+        3 | plum    |       |
+        4 | apple   | 3     | EUR
+    """
+    )
+    assert_table_equality(result, expected)
+    assert counter.call_count == 3
+
+
+def test_lookup_join_inner_async():
+    t = T(
+        """
+          | product
+        1 | apple
+        2 | plum
+    """
+    )
+
+    async def fetch(product):
+        return PRICES.get(product)
+
+    result = lookup_join(t, t.product, fetch, PriceSchema, how="inner", capacity=1)
+    expected = T(
+        """
+          | product | price | currency
+        1 | apple   | 3     | EUR
+    """
+    )
+    assert_table_equality(result, expected)
+
+
+def test_lookup_join_ignore_errors():
+    t = T(
+        """
+          | product
+        1 | apple
+        2 | broken
+    """
+    )
+
+    def fetch(product):
+        if product == "broken":
+            raise ConnectionError("store unavailable")
+        return PRICES.get(product)
+
+    result = lookup_join(
+        t, t.product, fetch, PriceSchema, how="inner", on_error="ignore"
+    )
+    expected = T(
+        """
+          | product | price | currency
+        1 | apple   | 3     | EUR
+    """
+    )
+    assert_table_equality(result, expected)
+
+
+def _numbers(n: int) -> pw.Table:
+    return pw.debug.table_from_pandas(
+        pd.DataFrame({"value": range(n), "group": [i % 3 for i in range(n)]})
+    )
+
+
+def test_bernoulli_sample():
+    sample = bernoulli_sample(_numbers(1000), 0.3, seed=7)
+    [size] = pw.debug.table_to_pandas(sample.reduce(size=pw.reducers.count()))["size"]
+    assert 200 < size < 400
+
+
+def test_bernoulli_sample_consistent():
+    table = _numbers(1000)
+    small = bernoulli_sample(table, 0.1, seed=7)
+    large = bernoulli_sample(table, 0.5, seed=7)
+    small_ids = set(pw.debug.table_to_pandas(small).index)
+    large_ids = set(pw.debug.table_to_pandas(large).index)
+    assert small_ids and small_ids < large_ids
+
+
+def test_bernoulli_sample_wrong_fraction():
+    with pytest.raises(ValueError):
+        bernoulli_sample(_numbers(10), 1.5)
+
+
+def test_reservoir_sample():
+    sample = reservoir_sample(_numbers(100), 5, instance=pw.this.group, seed=3)
+    sizes = sample.groupby(pw.this.group).reduce(
+        pw.this.group, size=pw.reducers.count()
+    )
+    assert_table_equality_wo_index(
+        sizes,
+        T(
+            """
+            group | size
+            0     | 5
+            1     | 5
+            2     | 5
+            """
+        ),
+    )
+
+
+def test_reservoir_sample_replaces_deleted_rows():
+    table = T(
+        """
+          | value | __time__ | __diff__
+        1 | 1     | 2        | 1
+        2 | 2     | 2        | 1
+        3 | 3     | 2        | 1
+        4 | 4     | 2        | 1
+        1 | 1     | 4        | -1
+        2 | 2     | 4        | -1
+        3 | 3     | 4        | -1
+        """
+    )
+    sample = reservoir_sample(table, 2)
+    assert_table_equality_wo_index(
+        sample,
+        T(
+            """
+            value
+            4
+            """
+        ),
+    )
+
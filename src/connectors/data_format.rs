@@ -14,7 +14,7 @@ use crate::connectors::metadata::SourceMetadata;
 use crate::connectors::ReaderContext::{Diff, KeyValue, PreparedEvent, RawBytes, TokenizedEntries};
 use crate::connectors::{DataEventType, Offset, ReaderContext, SessionType, SnapshotEvent};
 use crate::engine::error::DynError;
-use crate::engine::{Key, Result, Type, Value};
+use crate::engine::{Key, Result, SharedBytes, Type, Value};
 
 use itertools::Itertools;
 use log::error;
@@ -579,11 +579,11 @@ impl IdentityParser {
         }
     }
 
-    fn prepare_bytes(&self, bytes: &[u8]) -> Result<Value, ParseError> {
+    fn prepare_bytes(&self, bytes: &SharedBytes) -> Result<Value, ParseError> {
         if self.parse_utf8 {
             Ok(Value::String(prepare_plaintext_string(bytes)?.into()))
         } else {
-            Ok(Value::Bytes(bytes.into()))
+            Ok(Value::Bytes(bytes.clone()))
         }
     }
 }
@@ -778,7 +778,7 @@ fn parse_value_from_json(value: &JsonValue) -> Option<Value> {
     }
 }
 
-fn serialize_value_to_json(value: &Value) -> Result<JsonValue, FormatterError> {
+pub fn serialize_value_to_json(value: &Value) -> Result<JsonValue, FormatterError> {
     match value {
         Value::None => Ok(JsonValue::Null),
         Value::Int(i) => Ok(json!(i)),
@@ -814,10 +814,30 @@ fn serialize_value_to_json(value: &Value) -> Result<JsonValue, FormatterError> {
             }
             Ok(JsonValue::Array(items))
         }
+        Value::Float32Array(a) => {
+            let mut items = Vec::with_capacity(a.len());
+            for item in a.iter() {
+                items.push(json!(item));
+            }
+            Ok(JsonValue::Array(items))
+        }
+        Value::SparseMatrix(m) => Ok(json!({
+            "shape": [m.shape().0, m.shape().1],
+            "indptr": m.indptr(),
+            "indices": m.indices(),
+            "data": m.data(),
+        })),
         Value::DateTimeNaive(dt) => Ok(json!(dt.to_string())),
         Value::DateTimeUtc(dt) => Ok(json!(dt.to_string())),
         Value::Duration(d) => Ok(json!(d.nanoseconds())),
         Value::Json(j) => Ok((**j).clone()),
+        Value::SerializedObject(o) => {
+            let mut items = Vec::with_capacity(o.bytes.len());
+            for item in o.bytes.iter() {
+                items.push(json!(item));
+            }
+            Ok(JsonValue::Array(items))
+        }
     }
 }
 
@@ -10,7 +10,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::env;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
 use std::fs::DirEntry;
 use std::fs::File;
 use std::io;
@@ -23,29 +23,33 @@ use std::mem::take;
 use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
 use std::str::{from_utf8, Utf8Error};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::thread::sleep;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use chrono::{DateTime, FixedOffset};
-use log::{error, warn};
+use log::{error, info, warn};
 use postgres::types::ToSql;
 use tempfile::{tempdir, TempDir};
 use xxhash_rust::xxh3::Xxh3 as Hasher;
 
 use crate::connectors::data_format::FormatterContext;
 use crate::connectors::metadata::SourceMetadata;
+use crate::connectors::monitoring::{SharedSourceLag, SourceLag, SourceLagTracker};
 use crate::connectors::offset::EMPTY_OFFSET;
 use crate::connectors::{Offset, OffsetKey, OffsetValue, ParsedEvent};
 use crate::deepcopy::DeepCopy;
-use crate::engine::Value;
+use crate::engine::{SharedBytes, Value};
 use crate::fs_helpers::ensure_directory;
 use crate::persistence::frontier::OffsetAntichain;
+use crate::persistence::metadata_backends::Error as MetadataBackendError;
+use crate::persistence::object_storage::Error as ObjectStorageError;
+use crate::persistence::prepared_output::PreparedOutputStorage;
 use crate::persistence::{ExternalPersistentId, PersistentId};
 use crate::python_api::threads::PythonThreadState;
 use crate::python_api::with_gil_and_pool;
-use crate::python_api::PythonSubject;
+use crate::python_api::{PythonSink, PythonSubject};
 use crate::timestamp::current_unix_timestamp_secs;
 
 use bincode::ErrorKind as BincodeError;
@@ -53,13 +57,16 @@ use elasticsearch::{BulkParts, Elasticsearch};
 use glob::Pattern as GlobPattern;
 use glob::PatternError as GlobPatternError;
 use pipe::PipeReader;
-use postgres::Client as PsqlClient;
+use postgres::{Client as PsqlClient, GenericClient};
 use pyo3::prelude::*;
-use rdkafka::consumer::{BaseConsumer, Consumer, DefaultConsumerContext};
+use pyo3::types::PyBytes;
+use rdkafka::consumer::{
+    BaseConsumer, CommitMode, Consumer, ConsumerGroupMetadata, DefaultConsumerContext,
+};
 use rdkafka::error::{KafkaError, RDKafkaErrorCode};
 use rdkafka::producer::{BaseRecord, DefaultProducerContext, Producer, ThreadedProducer};
-use rdkafka::topic_partition_list::Offset as KafkaOffset;
-use rdkafka::Message;
+use rdkafka::topic_partition_list::{Offset as KafkaOffset, TopicPartitionList};
+use rdkafka::{ClientConfig, Message};
 use rusqlite::types::ValueRef as SqliteValue;
 use rusqlite::types::{
     FromSql as FromSqlite, FromSqlError as FromSqliteError, FromSqlResult as FromSqliteResult,
@@ -128,6 +135,7 @@ pub enum S3CommandName {
     InitiateMultipartUpload,
     PutMultipartChunk,
     CompleteMultipartUpload,
+    AbortMultipartUpload,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Copy)]
@@ -139,16 +147,22 @@ pub enum DataEventType {
 
 #[derive(PartialEq, Eq, Debug)]
 pub enum ReaderContext {
-    RawBytes(DataEventType, Vec<u8>),
+    RawBytes(DataEventType, SharedBytes),
     TokenizedEntries(DataEventType, Vec<String>),
-    KeyValue((Option<Vec<u8>>, Option<Vec<u8>>)),
+    KeyValue((Option<Vec<u8>>, Option<SharedBytes>)),
     Diff((DataEventType, Option<Value>, Vec<u8>, Option<Vec<u8>>)),
     PreparedEvent(ParsedEvent),
 }
 
 impl ReaderContext {
-    pub fn from_raw_bytes(event: DataEventType, raw_bytes: Vec<u8>) -> ReaderContext {
-        ReaderContext::RawBytes(event, raw_bytes)
+    /// The bytes become the values of the `Bytes` columns without being copied, so
+    /// a reader passing an owned buffer, or a slice of a buffer shared with other messages,
+    /// avoids copying the payload.
+    pub fn from_raw_bytes(
+        event: DataEventType,
+        raw_bytes: impl Into<SharedBytes>,
+    ) -> ReaderContext {
+        ReaderContext::RawBytes(event, raw_bytes.into())
     }
 
     pub fn from_diff(
@@ -167,7 +181,7 @@ impl ReaderContext {
         ReaderContext::TokenizedEntries(event, tokenized_entries)
     }
 
-    pub fn from_key_value(key: Option<Vec<u8>>, value: Option<Vec<u8>>) -> ReaderContext {
+    pub fn from_key_value(key: Option<Vec<u8>>, value: Option<SharedBytes>) -> ReaderContext {
         ReaderContext::KeyValue((key, value))
     }
 }
@@ -201,6 +215,9 @@ pub enum ReadError {
     #[error("failed to perform S3 operation {0:?} reason: {1:?}")]
     S3(S3CommandName, S3Error),
 
+    #[error(transparent)]
+    ObjectStorage(#[from] ObjectStorageError),
+
     #[error("failed to perform Sqlite request: {0}")]
     Sqlite(#[from] SqliteError),
 
@@ -282,6 +299,11 @@ pub trait Reader {
                             result.advance_offset(offset_key.clone(), other_value.clone());
                         }
                     }
+                    // The cursors can't be compared, but the Python connectors are read by
+                    // a single worker, so the other frontier is the more recent one
+                    (OffsetValue::PythonCursor(_), OffsetValue::PythonCursor(_)) => {
+                        result.advance_offset(offset_key.clone(), other_value.clone());
+                    }
                     (
                         OffsetValue::FilePosition {
                             total_entries_read: offset_line_idx,
@@ -316,9 +338,35 @@ pub trait Reader {
         result
     }
 
+    /// Whether the entries read after seeking, which are already covered by the
+    /// frontier, are skipped. The readers producing such entries on purpose, e.g. to
+    /// pass the header of a CSV file to the parser again, have to disable it.
+    fn skips_entries_covered_by_frontier(&self) -> bool {
+        true
+    }
+
+    /// The acknowledger of the data read to the source, if the source keeps its own
+    /// position, like the committed offsets of a Kafka consumer group.
+    fn source_acknowledger(&self) -> Option<Box<dyn SourceAcknowledger>> {
+        None
+    }
+
+    /// The tracker of the lag of the reader behind its source, if it can be measured.
+    fn source_lag_tracker(&self) -> Option<Box<dyn SourceLagTracker>> {
+        None
+    }
+
     fn storage_type(&self) -> StorageType;
 }
 
+/// Acknowledges the data read to its source. It is called only once a checkpoint of
+/// the persisted state covering the data succeeds, so that after a recovery the position
+/// kept by the source and the persisted state are consistent.
+pub trait SourceAcknowledger: Send {
+    #[allow(clippy::missing_errors_doc)]
+    fn acknowledge(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError>;
+}
+
 pub trait ReaderBuilder: Send + 'static {
     fn build(self: Box<Self>) -> Result<Box<dyn Reader>, ReadError>;
 
@@ -343,6 +391,10 @@ pub trait ReaderBuilder: Send + 'static {
     fn persistent_id(&self) -> Option<PersistentId>;
     fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>);
 
+    fn source_acknowledger(&self) -> Option<Box<dyn SourceAcknowledger>> {
+        None
+    }
+
     fn storage_type(&self) -> StorageType;
 }
 
@@ -362,6 +414,10 @@ where
         Reader::update_persistent_id(self, persistent_id);
     }
 
+    fn source_acknowledger(&self) -> Option<Box<dyn SourceAcknowledger>> {
+        Reader::source_acknowledger(self)
+    }
+
     fn storage_type(&self) -> StorageType {
         Reader::storage_type(self)
     }
@@ -379,6 +435,9 @@ pub enum WriteError {
     #[error("failed to perform S3 operation {0:?} reason: {1:?}")]
     S3(S3CommandName, S3Error),
 
+    #[error(transparent)]
+    ObjectStorage(#[from] ObjectStorageError),
+
     #[error("failed to perform write in postgres: {0}")]
     Postgres(#[from] postgres::Error),
 
@@ -399,8 +458,22 @@ pub enum WriteError {
 
     #[error("elasticsearch client error: {0:?}")]
     Elasticsearch(elasticsearch::Error),
+
+    #[error("malformed prepared transaction name {0:?}")]
+    MalformedPreparedTransaction(String),
+
+    #[error("failed to access the prepared output: {0}")]
+    PreparedOutput(#[from] MetadataBackendError),
+
+    #[error("kafka consumer of group {0:?} has no group metadata")]
+    KafkaNoGroupMetadata(String),
+
+    #[error(transparent)]
+    Py(#[from] PyErr),
 }
 
+pub type SharedWriter = Arc<Mutex<Box<dyn Writer>>>;
+
 pub trait Writer: Send {
     fn write(&mut self, data: FormatterContext) -> Result<(), WriteError>;
 
@@ -412,6 +485,46 @@ pub trait Writer: Send {
         true
     }
 
+    /// Whether the writer takes part in the epoch commit protocol. A transactional
+    /// writer doesn't expose the data until `commit_prepared` is called for it.
+    fn transactional(&self) -> bool {
+        false
+    }
+
+    /// Called when the output for all times before `time` is complete. The data written
+    /// since the previous call forms the epoch `time`, which has to be made durable but
+    /// not yet visible.
+    fn prepare_commit(&mut self, _time: u64) -> Result<(), WriteError> {
+        Ok(())
+    }
+
+    /// Makes the prepared epochs up to `time` visible. With persistence enabled, it is
+    /// called only after the checkpoint containing these epochs is saved, so that
+    /// they are not emitted again after a restart.
+    fn commit_prepared(&mut self, _time: u64) -> Result<(), WriteError> {
+        Ok(())
+    }
+
+    /// The name under which the writer keeps its prepared epochs in the persisted state,
+    /// if the external system can't keep them until they are committed. It has to
+    /// identify the sink between the runs.
+    fn prepared_output_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Called once before any output with the time of the last saved checkpoint and,
+    /// if the writer has a `prepared_output_name` and persistence is enabled, the
+    /// storage of its prepared epochs. Epochs prepared by a previous run are committed
+    /// if the checkpoint covers them and rolled back otherwise, as their data will be
+    /// produced again.
+    fn resolve_prepared(
+        &mut self,
+        _last_finalized_time: u64,
+        _prepared_output: Option<PreparedOutputStorage>,
+    ) -> Result<(), WriteError> {
+        Ok(())
+    }
+
     fn short_description(&self) -> Cow<'static, str> {
         type_name::<Self>().into()
     }
@@ -591,6 +704,10 @@ impl Reader for FilesystemReader {
         self.persistent_id = persistent_id;
     }
 
+    fn source_lag_tracker(&self) -> Option<Box<dyn SourceLagTracker>> {
+        Some(self.filesystem_scanner.backlog_tracker())
+    }
+
     fn storage_type(&self) -> StorageType {
         StorageType::FileSystem
     }
@@ -611,11 +728,50 @@ impl Writer for FileWriter {
     }
 }
 
+/// The topics a Kafka reader is subscribed to: a single one or, if the name starts with `^`,
+/// as in librdkafka, all the ones matching the regex, including the ones created while
+/// reading, which librdkafka finds on each refresh of the metadata of the cluster.
+#[derive(Debug)]
+enum KafkaTopics {
+    Name(String),
+    Pattern(String),
+}
+
+impl KafkaTopics {
+    fn new(topic: String) -> Self {
+        if topic.starts_with('^') {
+            Self::Pattern(topic)
+        } else {
+            Self::Name(topic)
+        }
+    }
+
+    /// Whether the offsets in the topic are the reader's. The topics in the frontier of
+    /// a regex subscription are all taken as matching it, as they were read by the reader and
+    /// librdkafka's regexes can't be matched here.
+    fn is_subscribed(&self, topic: &str) -> bool {
+        match self {
+            Self::Name(name) => name == topic,
+            Self::Pattern(_) => true,
+        }
+    }
+}
+
+impl Display for KafkaTopics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "{name}"),
+            Self::Pattern(pattern) => write!(f, "topics matching {pattern}"),
+        }
+    }
+}
+
 pub struct KafkaReader {
-    consumer: BaseConsumer<DefaultConsumerContext>,
+    consumer: Arc<BaseConsumer<DefaultConsumerContext>>,
     persistent_id: Option<PersistentId>,
-    topic: Arc<String>,
-    positions_for_seek: HashMap<i32, i64>,
+    topics: Arc<KafkaTopics>,
+    topic_names: HashMap<String, Arc<String>>,
+    positions_for_seek: HashMap<(Arc<String>, i32), i64>,
 }
 
 impl Reader for KafkaReader {
@@ -626,10 +782,14 @@ impl Reader for KafkaReader {
                 .poll(Timeout::Never)
                 .expect("poll should never timeout")?;
             let message_key = kafka_message.key().map(<[u8]>::to_vec);
-            let message_payload = kafka_message.payload().map(<[u8]>::to_vec);
-
-            if let Some(last_read_offset) = self.positions_for_seek.get(&kafka_message.partition())
-            {
+            // The payload is borrowed from the message, so it's copied once here
+            let message_payload = kafka_message.payload().map(SharedBytes::from);
+            let topic_partition = (
+                Self::topic_name(&mut self.topic_names, kafka_message.topic()),
+                kafka_message.partition(),
+            );
+
+            if let Some(last_read_offset) = self.positions_for_seek.get(&topic_partition) {
                 if last_read_offset >= &kafka_message.offset() {
                     if let Err(e) = self.consumer.seek(
                         kafka_message.topic(),
@@ -646,11 +806,12 @@ impl Reader for KafkaReader {
                     }
                     continue;
                 }
-                self.positions_for_seek.remove(&kafka_message.partition());
+                self.positions_for_seek.remove(&topic_partition);
             }
 
             let offset = {
-                let offset_key = OffsetKey::Kafka(self.topic.clone(), kafka_message.partition());
+                let (topic, partition) = topic_partition;
+                let offset_key = OffsetKey::Kafka(topic, partition);
                 let offset_value = OffsetValue::KafkaOffset(kafka_message.offset());
                 (offset_key, offset_value)
             };
@@ -668,10 +829,10 @@ impl Reader for KafkaReader {
                 continue;
             };
             if let OffsetKey::Kafka(topic, partition) = offset_key {
-                if self.topic != *topic {
+                if !self.topics.is_subscribed(topic) {
                     warn!(
                         "Unexpected topic name. Expected: {}, Got: {topic}",
-                        *self.topic
+                        self.topics
                     );
                     continue;
                 }
@@ -684,7 +845,8 @@ impl Reader for KafkaReader {
                     to be done on behalf of rdkafka client, taking account of other
                     members in its' consumer group.
                 */
-                self.positions_for_seek.insert(*partition, *position);
+                self.positions_for_seek
+                    .insert((topic.clone(), *partition), *position);
             } else {
                 error!("Unexpected offset in Kafka frontier: ({offset_key:?}, {offset_value:?})");
             }
@@ -701,6 +863,19 @@ impl Reader for KafkaReader {
         self.persistent_id = persistent_id;
     }
 
+    fn source_acknowledger(&self) -> Option<Box<dyn SourceAcknowledger>> {
+        Some(Box::new(KafkaOffsetCommitter {
+            consumer: self.consumer.clone(),
+            topics: self.topics.clone(),
+        }))
+    }
+
+    fn source_lag_tracker(&self) -> Option<Box<dyn SourceLagTracker>> {
+        Some(Box::new(KafkaLagTracker {
+            consumer: self.consumer.clone(),
+        }))
+    }
+
     fn storage_type(&self) -> StorageType {
         StorageType::Kafka
     }
@@ -713,12 +888,127 @@ impl KafkaReader {
         persistent_id: Option<PersistentId>,
     ) -> KafkaReader {
         KafkaReader {
-            consumer,
+            consumer: Arc::new(consumer),
             persistent_id,
-            topic: Arc::new(topic),
+            topics: Arc::new(KafkaTopics::new(topic)),
+            topic_names: HashMap::new(),
             positions_for_seek: HashMap::new(),
         }
     }
+
+    /// Returns the name of the topic shared by the offsets of its messages, so that it isn't
+    /// allocated for each of them.
+    fn topic_name(topic_names: &mut HashMap<String, Arc<String>>, topic: &str) -> Arc<String> {
+        if let Some(name) = topic_names.get(topic) {
+            return name.clone();
+        }
+        let name = Arc::new(topic.to_string());
+        topic_names.insert(topic.to_string(), name.clone());
+        name
+    }
+}
+
+const KAFKA_LAG_QUERY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Measures the lag of the consumer as the end offsets of the assigned partitions, of all
+/// the subscribed topics, minus the positions of the consumer in them, or the committed
+/// offsets if nothing was read from a partition yet.
+pub struct KafkaLagTracker {
+    consumer: Arc<BaseConsumer<DefaultConsumerContext>>,
+}
+
+impl KafkaLagTracker {
+    fn partition_offsets(list: &TopicPartitionList) -> HashMap<(String, i32), i64> {
+        list.elements()
+            .into_iter()
+            .filter_map(|element| match element.offset() {
+                KafkaOffset::Offset(offset) => {
+                    Some(((element.topic().to_string(), element.partition()), offset))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl SourceLagTracker for KafkaLagTracker {
+    fn lag(&mut self) -> Option<SourceLag> {
+        let assignment = self.consumer.assignment().ok()?;
+        let positions = self
+            .consumer
+            .position()
+            .map(|positions| Self::partition_offsets(&positions))
+            .unwrap_or_default();
+        let mut committed = None;
+        let mut messages = 0;
+        for element in assignment.elements() {
+            let topic_partition = (element.topic().to_string(), element.partition());
+            let (topic, partition) = &topic_partition;
+            let end_offset = match self.consumer.fetch_watermarks(
+                topic,
+                *partition,
+                KAFKA_LAG_QUERY_TIMEOUT,
+            ) {
+                Ok((_low, high)) => high,
+                Err(e) => {
+                    warn!(
+                        "Failed to fetch the end offset of the partition {partition} of {topic}: {e}"
+                    );
+                    return None;
+                }
+            };
+            let position = positions.get(&topic_partition).copied().or_else(|| {
+                committed
+                    .get_or_insert_with(|| {
+                        self.consumer
+                            .committed(KAFKA_LAG_QUERY_TIMEOUT)
+                            .map(|committed| Self::partition_offsets(&committed))
+                            .unwrap_or_default()
+                    })
+                    .get(&topic_partition)
+                    .copied()
+            });
+            // Without a position or a committed offset, the whole partition is unread
+            let unread = end_offset - position.unwrap_or(0);
+            messages += u64::try_from(unread).unwrap_or(0);
+        }
+        Some(SourceLag {
+            messages: Some(messages),
+            ..SourceLag::default()
+        })
+    }
+}
+
+/// Commits the offsets of the consumer group up to the persisted frontier.
+pub struct KafkaOffsetCommitter {
+    consumer: Arc<BaseConsumer<DefaultConsumerContext>>,
+    topics: Arc<KafkaTopics>,
+}
+
+impl SourceAcknowledger for KafkaOffsetCommitter {
+    fn acknowledge(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let mut offsets = TopicPartitionList::new();
+        for (offset_key, offset_value) in frontier {
+            if let (
+                OffsetKey::Kafka(topic, partition),
+                OffsetValue::KafkaOffset(last_read_offset),
+            ) = (offset_key, offset_value)
+            {
+                if self.topics.is_subscribed(topic) {
+                    // The committed offset is the one of the next message to read
+                    offsets.add_partition_offset(
+                        topic,
+                        *partition,
+                        KafkaOffset::Offset(*last_read_offset + 1),
+                    )?;
+                }
+            }
+        }
+        if offsets.count() > 0 {
+            self.consumer.commit(&offsets, CommitMode::Sync)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -744,6 +1034,7 @@ struct FilesystemScanner {
     object_pattern: GlobPattern,
     next_file_for_insertion: Option<PathBuf>,
     cached_metadata: HashMap<PathBuf, Option<SourceMetadata>>,
+    backlog: SharedSourceLag,
 
     // Storage is deleted on object destruction, so we need to store it
     // for the connector's life time
@@ -805,6 +1096,7 @@ impl FilesystemScanner {
             object_pattern: GlobPattern::new(object_pattern)?,
             next_file_for_insertion: None,
             cached_metadata: HashMap::new(),
+            backlog: SharedSourceLag::default(),
             _connector_tmp_storage: connector_tmp_storage,
         })
     }
@@ -813,6 +1105,19 @@ impl FilesystemScanner {
         self.next_file_for_insertion.is_some()
     }
 
+    /// The files not read yet are counted whenever the directory is scanned.
+    fn backlog_tracker(&self) -> Box<dyn SourceLagTracker> {
+        Box::new(FilesystemBacklogTracker(self.backlog.clone()))
+    }
+
+    fn update_backlog(&self, files: u64, bytes: u64) {
+        *self.backlog.lock().unwrap() = Some(SourceLag {
+            files: Some(files),
+            bytes: Some(bytes),
+            ..SourceLag::default()
+        });
+    }
+
     fn is_polling_enabled(&self) -> bool {
         self.streaming_mode.is_polling_enabled()
     }
@@ -1029,6 +1334,8 @@ impl FilesystemScanner {
 
     fn next_insertion_entry(&mut self) -> io::Result<Option<ReadResult>> {
         let mut selected_file: Option<(PathBuf, SystemTime)> = None;
+        let mut unread_files = 0;
+        let mut unread_bytes = 0;
         if self.is_directory {
             let files_in_directory = std::fs::read_dir(self.path.as_path())?;
 
@@ -1060,6 +1367,8 @@ impl FilesystemScanner {
                         if self.known_files.contains_key(&(*current_path)) {
                             continue;
                         }
+                        unread_files += 1;
+                        unread_bytes += entry.metadata().map_or(0, |metadata| metadata.len());
                         match &selected_file {
                             Some((currently_selected_name, selected_file_created_at)) => {
                                 if (selected_file_created_at, currently_selected_name)
@@ -1076,10 +1385,14 @@ impl FilesystemScanner {
         } else {
             let is_existing_file = self.path.exists() && self.path.is_file();
             if !self.known_files.is_empty() || !is_existing_file {
+                self.update_backlog(0, 0);
                 return Ok(None);
             }
             selected_file = Some((self.path.clone(), SystemTime::now()));
+            unread_files = 1;
+            unread_bytes = std::fs::metadata(&self.path).map_or(0, |metadata| metadata.len());
         }
+        self.update_backlog(unread_files, unread_bytes);
 
         match selected_file {
             Some((new_file_name, _)) => Ok(Some(self.initiate_file_insertion(&new_file_name)?)),
@@ -1122,6 +1435,14 @@ impl FilesystemScanner {
     }
 }
 
+struct FilesystemBacklogTracker(SharedSourceLag);
+
+impl SourceLagTracker for FilesystemBacklogTracker {
+    fn lag(&mut self) -> Option<SourceLag> {
+        *self.0.lock().unwrap()
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ConnectorMode {
     Static,
@@ -1314,6 +1635,15 @@ impl Reader for CsvFilesystemReader {
         self.persistent_id = persistent_id;
     }
 
+    fn skips_entries_covered_by_frontier(&self) -> bool {
+        // The header is read again with the offset of the frontier after seeking
+        false
+    }
+
+    fn source_lag_tracker(&self) -> Option<Box<dyn SourceLagTracker>> {
+        Some(self.filesystem_scanner.backlog_tracker())
+    }
+
     fn storage_type(&self) -> StorageType {
         StorageType::CsvFilesystem
     }
@@ -1374,14 +1704,46 @@ impl ReaderBuilder for PythonReaderBuilder {
         self.persistent_id = persistent_id;
     }
 
+    fn source_acknowledger(&self) -> Option<Box<dyn SourceAcknowledger>> {
+        let on_persisted = self.subject.get().on_persisted.clone()?;
+        Some(Box::new(PythonCursorAcknowledger { on_persisted }))
+    }
+
     fn storage_type(&self) -> StorageType {
         StorageType::Python
     }
 }
 
+/// Passes the persisted cursor of a Python connector to the subject, e.g. for it to
+/// acknowledge the messages to its source.
+struct PythonCursorAcknowledger {
+    on_persisted: Py<PyAny>,
+}
+
+impl SourceAcknowledger for PythonCursorAcknowledger {
+    fn acknowledge(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        if let Some(OffsetValue::PythonCursor(cursor)) = frontier.get_offset(&OffsetKey::Empty) {
+            with_gil_and_pool(|py| self.on_persisted.call1(py, (cursor.clone(),)))?;
+        }
+        Ok(())
+    }
+}
+
 impl Reader for PythonReader {
     fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
         let offset_value = frontier.get_offset(&OffsetKey::Empty);
+        if let Some(OffsetValue::PythonCursor(cursor)) = offset_value {
+            // The subject resumes from the cursor itself, so nothing is skipped
+            return match &self.subject.get().seek {
+                Some(seek) => {
+                    with_gil_and_pool(|py| seek.call1(py, (cursor.clone(),)))?;
+                    Ok(())
+                }
+                None => Err(ReadError::Py(PyValueError::new_err(
+                    "the Python connector reported cursors, but it can't seek to them",
+                ))),
+            };
+        }
         let Some(OffsetValue::PythonEntrySequentialId(offset_value)) = offset_value else {
             if offset_value.is_some() {
                 warn!("Incorrect type of offset value in Python frontier: {offset_value:?}");
@@ -1389,7 +1751,11 @@ impl Reader for PythonReader {
             return Ok(());
         };
 
-        self.total_entries_read = *offset_value;
+        // The subject reading from the beginning again produces the messages with the
+        // same sequential ids, so that the ones read before are skipped by the connector
+        if !self.subject.get().replays_from_beginning {
+            self.total_entries_read = *offset_value;
+        }
 
         Ok(())
     }
@@ -1404,11 +1770,12 @@ impl Reader for PythonReader {
         }
 
         with_gil_and_pool(|py| {
-            let (event, key, values, metadata): (
+            let (event, key, values, metadata, cursor): (
                 DataEventType,
                 Option<Value>,
                 Vec<u8>,
                 Option<Vec<u8>>,
+                Option<Value>,
             ) = self
                 .subject
                 .borrow(py)
@@ -1432,11 +1799,14 @@ impl Reader for PythonReader {
                 // by default.
                 //
                 // If it's changed, add worker_id to the offset.
+                //
+                // The subjects keeping their own position report it as a cursor instead.
                 self.total_entries_read += 1;
-                let offset = (
-                    OffsetKey::Empty,
-                    OffsetValue::PythonEntrySequentialId(self.total_entries_read),
-                );
+                let offset_value = match cursor {
+                    Some(cursor) => OffsetValue::PythonCursor(cursor),
+                    None => OffsetValue::PythonEntrySequentialId(self.total_entries_read),
+                };
+                let offset = (OffsetKey::Empty, offset_value);
 
                 Ok(ReadResult::Data(
                     ReaderContext::from_diff(event, key, values, metadata),
@@ -1459,10 +1829,39 @@ impl Reader for PythonReader {
     }
 }
 
+pub struct PythonWriter {
+    sink: Py<PythonSink>,
+}
+
+impl PythonWriter {
+    pub fn new(sink: Py<PythonSink>) -> Self {
+        Self { sink }
+    }
+}
+
+impl Writer for PythonWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        with_gil_and_pool(|py| {
+            let write = &self.sink.get().write;
+            for payload in data.payloads {
+                write.call1(py, (PyBytes::new(py, &payload),))?;
+            }
+            Ok(())
+        })
+    }
+
+    fn flush(&mut self) -> Result<(), WriteError> {
+        with_gil_and_pool(|py| self.sink.get().flush.call0(py))?;
+        Ok(())
+    }
+}
+
 pub struct PsqlWriter {
     client: PsqlClient,
     max_batch_size: Option<usize>,
     buffer: Vec<FormatterContext>,
+    transaction_id: Option<String>,
+    prepared_epochs: Vec<u64>,
 }
 
 impl PsqlWriter {
@@ -1471,7 +1870,49 @@ impl PsqlWriter {
             client,
             max_batch_size,
             buffer: Vec::new(),
+            transaction_id: None,
+            prepared_epochs: Vec::new(),
+        }
+    }
+
+    /// Creates a writer that outputs each epoch in a separate prepared transaction
+    /// (requires `max_prepared_transactions` to be positive on the server). Prepared
+    /// transactions are named `<transaction_id>-<epoch>`, so `transaction_id` has to
+    /// be unique among the writers using the same database and stable between runs.
+    pub fn new_transactional(client: PsqlClient, transaction_id: String) -> PsqlWriter {
+        PsqlWriter {
+            transaction_id: Some(transaction_id),
+            ..Self::new(client, None)
+        }
+    }
+
+    fn prepared_transaction_name(transaction_id: &str, epoch: u64) -> String {
+        format!("{transaction_id}-{epoch}").replace('\'', "''")
+    }
+
+    fn execute_buffered(
+        client: &mut impl GenericClient,
+        buffer: &mut Vec<FormatterContext>,
+    ) -> Result<(), WriteError> {
+        for data in buffer.drain(..) {
+            let params: Vec<_> = data
+                .values
+                .iter()
+                .map(|v| v as &(dyn ToSql + Sync))
+                .collect();
+
+            for payload in &data.payloads {
+                let query = from_utf8(payload)?;
+
+                client.execute(query, params.as_slice()).map_err(|error| {
+                    WriteError::PsqlQueryFailed {
+                        query: query.to_string(),
+                        error,
+                    }
+                })?;
+            }
         }
+        Ok(())
     }
 }
 
@@ -1552,8 +1993,10 @@ mod to_sql {
                     try_forward!(&[Value], &t[..]);
                     "tuple"
                 }
-                Self::IntArray(_) => "int array",     // TODO
-                Self::FloatArray(_) => "float array", // TODO
+                Self::IntArray(_) => "int array",         // TODO
+                Self::FloatArray(_) => "float array",     // TODO
+                Self::Float32Array(_) => "float32 array", // TODO
+                Self::SparseMatrix(_) => "sparse matrix", // TODO
                 Self::DateTimeNaive(dt) => {
                     try_forward!(NaiveDateTime, dt.as_chrono_datetime());
                     "naive date/time"
@@ -1567,6 +2010,10 @@ mod to_sql {
                     try_forward!(&serde_json::Value, &**j);
                     "JSON"
                 }
+                Self::SerializedObject(o) => {
+                    try_forward!(&[u8], &o.bytes[..]);
+                    "serialized object"
+                }
             };
             Err(Box::new(WrongPathwayType {
                 pathway_type: pathway_type.to_owned(),
@@ -1594,37 +2041,95 @@ impl Writer for PsqlWriter {
     }
 
     fn flush(&mut self) -> Result<(), WriteError> {
-        if self.buffer.is_empty() {
+        if self.buffer.is_empty() || self.transaction_id.is_some() {
+            // In the transactional mode the data is kept until the epoch is prepared
             return Ok(());
         }
         let mut transaction = self.client.transaction()?;
+        Self::execute_buffered(&mut transaction, &mut self.buffer)?;
+        transaction.commit()?;
 
-        for data in self.buffer.drain(..) {
-            let params: Vec<_> = data
-                .values
-                .iter()
-                .map(|v| v as &(dyn ToSql + Sync))
-                .collect();
+        Ok(())
+    }
 
-            for payload in &data.payloads {
-                let query = from_utf8(payload)?;
+    fn single_threaded(&self) -> bool {
+        // Prepared transactions of the different workers would share names
+        self.transaction_id.is_some()
+    }
 
-                transaction
-                    .execute(query, params.as_slice())
-                    .map_err(|error| WriteError::PsqlQueryFailed {
-                        query: query.to_string(),
-                        error,
-                    })?;
-            }
-        }
+    fn transactional(&self) -> bool {
+        self.transaction_id.is_some()
+    }
 
+    fn prepare_commit(&mut self, time: u64) -> Result<(), WriteError> {
+        let Some(transaction_id) = &self.transaction_id else {
+            return Ok(());
+        };
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let mut transaction = self.client.transaction()?;
+        Self::execute_buffered(&mut transaction, &mut self.buffer)?;
+        transaction.batch_execute(&format!(
+            "PREPARE TRANSACTION '{}'",
+            Self::prepared_transaction_name(transaction_id, time)
+        ))?;
+        // The transaction is already closed by PREPARE TRANSACTION, committing it
+        // only releases the handle
         transaction.commit()?;
+        self.prepared_epochs.push(time);
+        Ok(())
+    }
 
+    fn commit_prepared(&mut self, time: u64) -> Result<(), WriteError> {
+        let Some(transaction_id) = &self.transaction_id else {
+            return Ok(());
+        };
+        while let Some(&epoch) = self.prepared_epochs.first() {
+            if epoch > time {
+                break;
+            }
+            self.client.batch_execute(&format!(
+                "COMMIT PREPARED '{}'",
+                Self::prepared_transaction_name(transaction_id, epoch)
+            ))?;
+            self.prepared_epochs.remove(0);
+        }
         Ok(())
     }
 
-    fn single_threaded(&self) -> bool {
-        false
+    fn resolve_prepared(
+        &mut self,
+        last_finalized_time: u64,
+        _prepared_output: Option<PreparedOutputStorage>,
+    ) -> Result<(), WriteError> {
+        let Some(transaction_id) = &self.transaction_id else {
+            return Ok(());
+        };
+        let prefix = format!("{transaction_id}-");
+        let prepared = self.client.query(
+            "SELECT gid FROM pg_prepared_xacts WHERE database = current_database()",
+            &[],
+        )?;
+        for row in prepared {
+            let name: String = row.get(0);
+            let Some(epoch) = name.strip_prefix(&prefix) else {
+                continue;
+            };
+            let epoch: u64 = epoch
+                .parse()
+                .map_err(|_| WriteError::MalformedPreparedTransaction(name.clone()))?;
+            let action = if epoch <= last_finalized_time {
+                "COMMIT PREPARED"
+            } else {
+                "ROLLBACK PREPARED"
+            };
+            self.client.batch_execute(&format!(
+                "{action} '{}'",
+                Self::prepared_transaction_name(transaction_id, epoch)
+            ))?;
+        }
+        Ok(())
     }
 }
 
@@ -1967,6 +2472,11 @@ impl Reader for S3CsvReader {
         }
     }
 
+    fn skips_entries_covered_by_frontier(&self) -> bool {
+        // The header is read again with the offset of the frontier after seeking
+        false
+    }
+
     fn storage_type(&self) -> StorageType {
         StorageType::S3Csv
     }
@@ -1980,14 +2490,202 @@ impl Reader for S3CsvReader {
     }
 }
 
+const KAFKA_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The messages of an epoch of a transactional `KafkaWriter`, as their keys and payloads.
+type KafkaMessages = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// The state of a `KafkaWriter` sending the output in transactions.
+///
+/// Each transaction also commits, for the consumer group `<transactional.id>-epochs`,
+/// the offset of partition 0 of the topic set to the last epoch sent in it. This way
+/// the last epoch committed to Kafka is known after a restart.
+struct KafkaTransactions {
+    transactional_id: String,
+    epochs_consumer: BaseConsumer,
+    group_metadata: ConsumerGroupMetadata,
+    unprepared: KafkaMessages,
+    prepared: VecDeque<(u64, KafkaMessages)>,
+    prepared_output: Option<PreparedOutputStorage>,
+}
+
 pub struct KafkaWriter {
     producer: ThreadedProducer<DefaultProducerContext>,
     topic: String,
+    transactions: Option<KafkaTransactions>,
 }
 
 impl KafkaWriter {
     pub fn new(producer: ThreadedProducer<DefaultProducerContext>, topic: String) -> KafkaWriter {
-        KafkaWriter { producer, topic }
+        KafkaWriter {
+            producer,
+            topic,
+            transactions: None,
+        }
+    }
+
+    /// Creates a writer sending the output in Kafka transactions. The producer has
+    /// to be created from `client_config`, with `transactional.id` set. Initializing
+    /// it aborts the transactions left open by a previous producer with the same id.
+    ///
+    /// Kafka doesn't allow resuming a transaction after a restart, so with persistence
+    /// enabled the prepared epochs are saved in the persisted state, and sent in
+    /// a transaction only after the checkpoint containing them is saved. The epochs
+    /// covered by the last checkpoint and not committed to Kafka are sent again after
+    /// a restart.
+    pub fn new_transactional(
+        producer: ThreadedProducer<DefaultProducerContext>,
+        topic: String,
+        client_config: &ClientConfig,
+    ) -> Result<KafkaWriter, WriteError> {
+        let transactional_id = client_config
+            .get("transactional.id")
+            .expect("transactional writer requires transactional.id")
+            .to_string();
+        let group_id = format!("{transactional_id}-epochs");
+        let mut consumer_config = client_config.clone();
+        consumer_config
+            .remove("transactional.id")
+            .set("group.id", &group_id)
+            .set("enable.auto.commit", "false")
+            .set("isolation.level", "read_committed");
+        let epochs_consumer: BaseConsumer = consumer_config.create()?;
+        let group_metadata = epochs_consumer
+            .group_metadata()
+            .ok_or(WriteError::KafkaNoGroupMetadata(group_id))?;
+
+        producer.init_transactions(KAFKA_TRANSACTION_TIMEOUT)?;
+        let mut writer = Self::new(producer, topic);
+        writer.transactions = Some(KafkaTransactions {
+            transactional_id,
+            epochs_consumer,
+            group_metadata,
+            unprepared: Vec::new(),
+            prepared: VecDeque::new(),
+            prepared_output: None,
+        });
+        Ok(writer)
+    }
+
+    fn send(&self, key: &[u8], payload: &[u8]) -> Result<(), WriteError> {
+        let mut entry = BaseRecord::<[u8], [u8]>::to(&self.topic)
+            .payload(payload)
+            .key(key);
+        loop {
+            match self.producer.send(entry) {
+                Ok(()) => return Ok(()),
+                Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), unsent_entry)) => {
+                    self.producer.poll(Duration::from_millis(10));
+                    entry = unsent_entry;
+                }
+                Err((e, _unsent_entry)) => return Err(WriteError::Kafka(e)),
+            }
+        }
+    }
+
+    /// The offsets marking `epoch` as the last one committed to Kafka.
+    fn epoch_offsets(&self, epoch: u64) -> Result<TopicPartitionList, WriteError> {
+        let mut offsets = TopicPartitionList::new();
+        offsets.add_partition_offset(
+            &self.topic,
+            0,
+            KafkaOffset::Offset(i64::try_from(epoch).unwrap_or(i64::MAX)),
+        )?;
+        Ok(offsets)
+    }
+
+    fn last_committed_epoch(
+        &self,
+        epochs_consumer: &BaseConsumer,
+    ) -> Result<Option<u64>, WriteError> {
+        let mut partitions = TopicPartitionList::new();
+        partitions.add_partition(&self.topic, 0);
+        let committed = epochs_consumer.committed_offsets(partitions, KAFKA_TRANSACTION_TIMEOUT)?;
+        let epoch = committed
+            .find_partition(&self.topic, 0)
+            .and_then(|partition| match partition.offset() {
+                KafkaOffset::Offset(offset) => u64::try_from(offset).ok(),
+                _ => None,
+            });
+        Ok(epoch)
+    }
+
+    fn send_in_transaction(
+        &self,
+        epochs: &[(u64, KafkaMessages)],
+        group_metadata: &ConsumerGroupMetadata,
+    ) -> Result<(), WriteError> {
+        let Some((last_epoch, _messages)) = epochs.last() else {
+            return Ok(());
+        };
+        self.producer.begin_transaction()?;
+        for (_epoch, messages) in epochs {
+            for (key, payload) in messages {
+                self.send(key, payload)?;
+            }
+        }
+        self.producer.send_offsets_to_transaction(
+            &self.epoch_offsets(*last_epoch)?,
+            group_metadata,
+            KAFKA_TRANSACTION_TIMEOUT,
+        )?;
+        self.producer
+            .commit_transaction(KAFKA_TRANSACTION_TIMEOUT)?;
+        Ok(())
+    }
+
+    /// Sends the prepared epochs up to `time` in a transaction and removes them from
+    /// the persisted state once it's committed. If it fails, the epochs are kept, so
+    /// that the next commit retries them.
+    fn commit_epochs(
+        &self,
+        transactions: &mut KafkaTransactions,
+        time: u64,
+    ) -> Result<(), WriteError> {
+        let committed_count = transactions
+            .prepared
+            .iter()
+            .take_while(|(epoch, _messages)| *epoch <= time)
+            .count();
+        if committed_count == 0 {
+            return Ok(());
+        }
+        let committed: Vec<_> = transactions.prepared.drain(..committed_count).collect();
+        if let Err(e) = self.send_in_transaction(&committed, &transactions.group_metadata) {
+            for epoch in committed.into_iter().rev() {
+                transactions.prepared.push_front(epoch);
+            }
+            self.producer.abort_transaction(KAFKA_TRANSACTION_TIMEOUT)?;
+            return Err(e);
+        }
+        if let Some(prepared_output) = &mut transactions.prepared_output {
+            for (epoch, _messages) in &committed {
+                // A leftover epoch is not newer than the one committed to Kafka, so
+                // it's removed after a restart anyway
+                if let Err(e) = prepared_output.remove(*epoch) {
+                    warn!("Failed to remove the prepared output of epoch {epoch}: {e}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Commits the epochs prepared by the previous run which the last checkpoint covers
+    /// and which weren't committed to Kafka before it stopped.
+    fn recover_epochs(
+        &self,
+        transactions: &mut KafkaTransactions,
+        mut prepared_output: PreparedOutputStorage,
+        last_finalized_time: u64,
+    ) -> Result<(), WriteError> {
+        let last_committed_epoch = self.last_committed_epoch(&transactions.epochs_consumer)?;
+        for (epoch, data) in prepared_output.recover(last_finalized_time, last_committed_epoch)? {
+            info!("Committing the output of epoch {epoch} prepared by the previous run");
+            let messages = bincode::deserialize(&data).map_err(|e| WriteError::Bincode(*e))?;
+            transactions.prepared.push_back((epoch, messages));
+        }
+        transactions.prepared_output = Some(prepared_output);
+        self.commit_epochs(transactions, last_finalized_time)
     }
 }
 
@@ -2000,30 +2698,76 @@ impl Drop for KafkaWriter {
 impl Writer for KafkaWriter {
     fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
         let key_as_bytes = data.key.0.to_le_bytes().to_vec();
-        for payload in &data.payloads {
-            let mut entry = BaseRecord::<Vec<u8>, Vec<u8>>::to(&self.topic)
-                .payload(payload)
-                .key(&key_as_bytes);
-            loop {
-                match self.producer.send(entry) {
-                    Ok(()) => break,
-                    Err((
-                        KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
-                        unsent_entry,
-                    )) => {
-                        self.producer.poll(Duration::from_millis(10));
-                        entry = unsent_entry;
-                        continue;
-                    }
-                    Err((e, _unsent_entry)) => return Err(WriteError::Kafka(e)),
-                }
+        if let Some(transactions) = &mut self.transactions {
+            for payload in data.payloads {
+                transactions
+                    .unprepared
+                    .push((key_as_bytes.clone(), payload));
             }
+            return Ok(());
+        }
+        for payload in &data.payloads {
+            self.send(&key_as_bytes, payload)?;
         }
         Ok(())
     }
 
     fn single_threaded(&self) -> bool {
-        false
+        // A transactional id can be used by only one producer at a time
+        self.transactions.is_some()
+    }
+
+    fn transactional(&self) -> bool {
+        self.transactions.is_some()
+    }
+
+    fn prepared_output_name(&self) -> Option<&str> {
+        self.transactions
+            .as_ref()
+            .map(|transactions| transactions.transactional_id.as_str())
+    }
+
+    fn prepare_commit(&mut self, time: u64) -> Result<(), WriteError> {
+        let Some(transactions) = &mut self.transactions else {
+            return Ok(());
+        };
+        if transactions.unprepared.is_empty() {
+            return Ok(());
+        }
+        let messages = take(&mut transactions.unprepared);
+        if let Some(prepared_output) = &mut transactions.prepared_output {
+            // Saved before the checkpoint covering the epoch can be committed
+            let data = bincode::serialize(&messages).map_err(|e| WriteError::Bincode(*e))?;
+            prepared_output.save(time, &data)?;
+        }
+        transactions.prepared.push_back((time, messages));
+        Ok(())
+    }
+
+    fn commit_prepared(&mut self, time: u64) -> Result<(), WriteError> {
+        let Some(mut transactions) = self.transactions.take() else {
+            return Ok(());
+        };
+        let result = self.commit_epochs(&mut transactions, time);
+        self.transactions = Some(transactions);
+        result
+    }
+
+    fn resolve_prepared(
+        &mut self,
+        last_finalized_time: u64,
+        prepared_output: Option<PreparedOutputStorage>,
+    ) -> Result<(), WriteError> {
+        let Some(prepared_output) = prepared_output else {
+            // Without persistence there is nothing to recover
+            return Ok(());
+        };
+        let Some(mut transactions) = self.transactions.take() else {
+            return Ok(());
+        };
+        let result = self.recover_epochs(&mut transactions, prepared_output, last_finalized_time);
+        self.transactions = Some(transactions);
+        result
     }
 }
 
@@ -6,12 +6,12 @@ use std::collections::HashMap;
 use std::env;
 use std::ops::ControlFlow;
 use std::rc::Rc;
-use std::sync::mpsc::{self, Sender, TryRecvError};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::thread::Thread;
 use std::time::{Duration, SystemTime};
 
+use crossbeam_channel::{Sender, TryRecvError};
 use scopeguard::guard;
 use timely::dataflow::operators::probe::Handle;
 use timely::progress::Timestamp as TimelyTimestamp;
@@ -24,24 +24,31 @@ pub mod monitoring;
 pub mod offset;
 pub mod snapshot;
 
-use crate::connectors::monitoring::ConnectorMonitor;
+use crate::connectors::monitoring::{start_source_lag_tracking, ConnectorMonitor, SharedSourceLag};
+use crate::engine::log_context::{self, LogContext};
 use crate::engine::report_error::{ReportError, SpawnWithReporter};
+use crate::engine::shutdown::SharedShutdownRequest;
 use crate::engine::{Key, Value};
 
 use crate::connectors::adaptors::InputAdaptor;
 use crate::connectors::snapshot::Event as SnapshotEvent;
 use crate::engine::Error as EngineError;
 use crate::persistence::frontier::OffsetAntichain;
+use crate::persistence::schema::{
+    InputSchema, PersistedColumn, SchemaMigrationError, SchemaMigrator,
+};
 use crate::persistence::tracker::SingleWorkerPersistentStorage;
 use crate::persistence::{ExternalPersistentId, PersistentId, SharedSnapshotWriter};
 use crate::timestamp::current_unix_timestamp_ms;
 
 use data_format::{ParseResult, ParsedEvent, Parser};
-use data_storage::{DataEventType, ReadResult, Reader, ReaderBuilder, ReaderContext, WriteError};
+use data_storage::{
+    DataEventType, ReadError, ReadResult, Reader, ReaderBuilder, ReaderContext, WriteError,
+};
 
 pub use adaptors::SessionType;
 pub use data_storage::StorageType;
-pub use offset::{Offset, OffsetKey, OffsetValue};
+pub use offset::{Offset, OffsetDeduplicator, OffsetKey, OffsetValue};
 
 pub const ARTIFICIAL_TIME_ON_REWIND_START: u64 = 0;
 
@@ -89,6 +96,9 @@ pub struct Connector<Timestamp> {
     commit_duration: Option<Duration>,
     current_timestamp: Timestamp,
     num_columns: usize,
+    input_schema: Option<InputSchema>,
+    // The schema is saved in the snapshot before the first event written in this run
+    unsaved_schema: Option<Vec<PersistedColumn>>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -136,6 +146,20 @@ impl PersistenceMode {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum RewindError {
+    #[error("the snapshot of {persistent_id} is corrupted after {entries_read} entries, the rest of its data can't be restored: {error}")]
+    CorruptedSnapshot {
+        persistent_id: PersistentId,
+        entries_read: usize,
+        #[source]
+        error: ReadError,
+    },
+
+    #[error(transparent)]
+    SchemaMigration(#[from] SchemaMigrationError),
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum SnapshotAccess {
     Replay,
@@ -168,11 +192,18 @@ where
         The implementation for pull model of data acquisition: we explicitly inquiry the source about the newly
         arrived data.
     */
-    pub fn new(commit_duration: Option<Duration>, num_columns: usize) -> Self {
+    pub fn new(
+        commit_duration: Option<Duration>,
+        num_columns: usize,
+        input_schema: Option<InputSchema>,
+    ) -> Self {
+        let unsaved_schema = input_schema.as_ref().map(InputSchema::persisted_columns);
         Connector {
             commit_duration,
             current_timestamp: Default::default(), // default is 0 now. If changing, make sure it is even (required for alt-neu).
             num_columns,
+            input_schema,
+            unsaved_schema,
         }
     }
 
@@ -194,12 +225,16 @@ where
         self.current_timestamp.clone().into()
     }
 
+    /// Sends the persisted events of the input to the connector. If `input_schema` is
+    /// given, the persisted rows are converted to it. A corrupted snapshot fails the
+    /// rewind, as the frontier of the input covers the data that can't be read.
     pub fn rewind_from_disk_snapshot(
         persistent_id: PersistentId,
         persistent_storage: &Arc<Mutex<SingleWorkerPersistentStorage>>,
         sender: &Sender<Entry>,
         persistence_mode: PersistenceMode,
-    ) {
+        input_schema: Option<&InputSchema>,
+    ) -> Result<(), RewindError> {
         let snapshot_readers = persistent_storage
             .lock()
             .unwrap()
@@ -208,18 +243,34 @@ where
         if let Ok(snapshot_readers) = snapshot_readers {
             for mut snapshot_reader in snapshot_readers {
                 let mut entries_read = 0;
+                let mut migrator = input_schema.map(InputSchema::migrator);
                 loop {
-                    let entry_read = snapshot_reader.read();
-                    let Ok(entry_read) = entry_read else { break };
+                    let entry_read =
+                        snapshot_reader
+                            .read()
+                            .map_err(|error| RewindError::CorruptedSnapshot {
+                                persistent_id,
+                                entries_read,
+                                error,
+                            })?;
                     match entry_read {
                         SnapshotEvent::Finished => {
                             info!("Reached the end of the snapshot. Exiting the rewind after {entries_read} entries");
                             break;
                         }
+                        SnapshotEvent::Schema(persisted_columns) => {
+                            if let Some(ref mut migrator) = migrator {
+                                migrator.set_persisted_schema(&persisted_columns)?;
+                            }
+                        }
                         SnapshotEvent::Insert(_, _)
                         | SnapshotEvent::Delete(_, _)
                         | SnapshotEvent::Upsert(_, _) => {
                             entries_read += 1;
+                            let entry_read = match migrator {
+                                Some(ref migrator) => Self::migrate_event(migrator, entry_read)?,
+                                None => entry_read,
+                            };
                             let send_res = sender.send(Entry::Snapshot(entry_read));
                             if let Err(e) = send_res {
                                 error!("Failed to send rewind entry: {e}");
@@ -232,6 +283,26 @@ where
                 }
             }
         }
+        Ok(())
+    }
+
+    fn migrate_event(
+        migrator: &SchemaMigrator,
+        event: SnapshotEvent,
+    ) -> Result<SnapshotEvent, SchemaMigrationError> {
+        Ok(match event {
+            SnapshotEvent::Insert(key, values) => {
+                SnapshotEvent::Insert(key, migrator.migrate(values)?)
+            }
+            SnapshotEvent::Delete(key, values) => {
+                SnapshotEvent::Delete(key, migrator.migrate(values)?)
+            }
+            SnapshotEvent::Upsert(key, values) => SnapshotEvent::Upsert(
+                key,
+                values.map(|values| migrator.migrate(values)).transpose()?,
+            ),
+            event => event,
+        })
     }
 
     pub fn read_realtime_updates(
@@ -239,6 +310,7 @@ where
         sender: &Sender<Entry>,
         main_thread: &Thread,
         error_reporter: &(impl ReportError + 'static),
+        mut deduplicator: OffsetDeduplicator,
     ) {
         let use_rare_wakeup = env::var("PATHWAY_YOLO_RARE_WAKEUPS") == Ok("1".to_string());
         let mut amt_send = 0;
@@ -247,6 +319,9 @@ where
             let finished = matches!(row_read_result, Ok(ReadResult::Finished));
 
             match row_read_result {
+                Ok(ReadResult::Data(_, offset)) if deduplicator.is_duplicate(&offset) => {
+                    continue;
+                }
                 Ok(read_result) => {
                     let send_res = sender.send(Entry::Realtime(read_result));
                     if send_res.is_err() {
@@ -280,7 +355,9 @@ where
         sender: &Sender<Entry>,
         persistence_mode: PersistenceMode,
         snapshot_access: SnapshotAccess,
-    ) {
+        input_schema: Option<&InputSchema>,
+    ) -> Result<OffsetDeduplicator, RewindError> {
+        let mut deduplicator = OffsetDeduplicator::default();
         if snapshot_access.is_replay_allowed() {
             persistence_mode.on_before_reading_snapshot(sender);
             // Rewind the data source
@@ -291,7 +368,8 @@ where
                         persistent_storage,
                         sender,
                         persistence_mode,
-                    );
+                        input_schema,
+                    )?;
 
                     let frontier = persistent_storage
                         .lock()
@@ -302,6 +380,10 @@ where
                     if let Err(e) = reader.seek(&frontier) {
                         error!("Failed to seek to frontier: {e}");
                     }
+                    if reader.skips_entries_covered_by_frontier() {
+                        // The entries that the reader can't skip by seeking are skipped when read
+                        deduplicator = OffsetDeduplicator::new(frontier);
+                    }
                 }
             }
         }
@@ -310,6 +392,7 @@ where
         if let Err(e) = send_res {
             panic!("Failed to switch from persisted to realtime: {e}");
         }
+        Ok(deduplicator)
     }
 
     pub fn snapshot_writer(
@@ -356,12 +439,13 @@ where
         external_persistent_id: Option<&ExternalPersistentId>,
         persistence_mode: PersistenceMode,
         snapshot_access: SnapshotAccess,
+        shutdown_request: SharedShutdownRequest,
         error_reporter: impl ReportError + 'static,
     ) -> Result<StartedConnectorState<Timestamp>, EngineError> {
         assert_eq!(self.num_columns, parser.column_count());
 
         let main_thread = thread::current();
-        let (sender, receiver) = mpsc::channel();
+        let (sender, receiver) = crossbeam_channel::unbounded();
 
         let thread_name = format!(
             "pathway:connector-{}-{}",
@@ -376,6 +460,17 @@ where
             snapshot_access,
         )
         .map_err(EngineError::SnapshotWriterError)?;
+        let input_schema = self.input_schema.clone();
+        let persistent_id = reader.persistent_id();
+        let tracked_persistent_storage = persistent_storage.clone();
+        let source_lag = SharedSourceLag::default();
+        let tracked_source_lag = source_lag.clone();
+        let tracked_reader_name = reader_name.clone();
+        let log_connector: Arc<str> = reader_name.as_str().into();
+        let reader_log_context = LogContext {
+            connector: Some(log_connector.clone()),
+            ..log_context::current()
+        };
 
         let input_thread_handle = thread::Builder::new()
             .name(thread_name)
@@ -386,17 +481,33 @@ where
                     drop(sender);
                     main_thread.unpark();
                 });
+                log_context::set(reader_log_context);
 
                 let mut reader = reader.build()?;
-                Self::read_snapshot(
+                if let Some(source_lag_tracker) = reader.source_lag_tracker() {
+                    start_source_lag_tracking(
+                        &tracked_reader_name,
+                        source_lag_tracker,
+                        &tracked_source_lag,
+                    );
+                }
+                drop(tracked_source_lag);
+                let deduplicator = Self::read_snapshot(
                     &mut *reader,
                     persistent_storage.as_ref(),
                     &sender,
                     persistence_mode,
                     snapshot_access,
-                );
+                    input_schema.as_ref(),
+                )?;
                 if realtime_reader_needed {
-                    Self::read_realtime_updates(&mut *reader, &sender, &main_thread, reporter);
+                    Self::read_realtime_updates(
+                        &mut *reader,
+                        &sender,
+                        &main_thread,
+                        reporter,
+                        deduplicator,
+                    );
                 }
 
                 Ok(())
@@ -409,10 +520,12 @@ where
         let mut next_commit_at = self.commit_duration.map(|x| SystemTime::now() + x);
         let mut backfilling_finished = false;
 
-        let connector_monitor = Rc::new(RefCell::new(ConnectorMonitor::new(reader_name)));
+        let connector_monitor =
+            Rc::new(RefCell::new(ConnectorMonitor::new(reader_name, source_lag)));
         let cloned_connector_monitor = connector_monitor.clone();
         let mut commit_allowed = true;
         let poller = Box::new(move || {
+            let _log_context = log_context::enter_connector(&log_connector);
             let iteration_start = SystemTime::now();
             if matches!(persistence_mode, PersistenceMode::SpeedrunReplay)
                 && !backfilling_finished
@@ -421,6 +534,13 @@ where
                 return ControlFlow::Continue(Some(iteration_start));
             }
 
+            if shutdown_request.is_requested() && backfilling_finished && commit_allowed {
+                // Finishing the input, so that the computation drains. The entries not
+                // received yet aren't committed, so they're read again after a restart.
+                (*connector_monitor).borrow_mut().finish();
+                return ControlFlow::Break(());
+            }
+
             if let Some(next_commit_at_timestamp) = next_commit_at {
                 if next_commit_at_timestamp <= iteration_start {
                     if backfilling_finished && commit_allowed {
@@ -445,6 +565,9 @@ where
                 }
             }
 
+            (*connector_monitor)
+                .borrow_mut()
+                .set_queue_depth(receiver.len());
             loop {
                 match receiver.try_recv() {
                     Ok(Entry::Realtime(ReadResult::Finished)) => {
@@ -461,6 +584,7 @@ where
                         return ControlFlow::Continue(Some(iteration_start));
                     }
                     Ok(entry) => {
+                        let rewind_finished = entry == Entry::RewindFinishSentinel;
                         self.handle_input_entry(
                             entry,
                             &mut backfilling_finished,
@@ -472,6 +596,16 @@ where
                             &mut Some(&mut *connector_monitor.borrow_mut()),
                             &mut commit_allowed,
                         );
+                        if rewind_finished {
+                            if let (Some(persistent_storage), Some(persistent_id)) =
+                                (&tracked_persistent_storage, persistent_id)
+                            {
+                                persistent_storage.lock().unwrap().accept_rewind_finished(
+                                    persistent_id,
+                                    self.current_timestamp.clone().into(),
+                                );
+                            }
+                        }
                     }
                     Err(TryRecvError::Empty) => return ControlFlow::Continue(next_commit_at),
                     Err(TryRecvError::Disconnected) => {
@@ -592,7 +726,9 @@ where
                     SnapshotEvent::Upsert(key, value) => {
                         Self::on_upsert(key, value, input_session);
                     }
-                    SnapshotEvent::AdvanceTime(_) | SnapshotEvent::Finished => {
+                    SnapshotEvent::AdvanceTime(_)
+                    | SnapshotEvent::Finished
+                    | SnapshotEvent::Schema(_) => {
                         unreachable!()
                     }
                 };
@@ -658,6 +794,19 @@ where
         }
     }
 
+    fn write_to_snapshot(
+        &mut self,
+        snapshot_writer: &SharedSnapshotWriter,
+        event: &SnapshotEvent,
+    ) -> Result<(), WriteError> {
+        let mut snapshot_writer = snapshot_writer.lock().unwrap();
+        if let Some(persisted_columns) = &self.unsaved_schema {
+            snapshot_writer.write(&SnapshotEvent::Schema(persisted_columns.clone()))?;
+            self.unsaved_schema = None;
+        }
+        snapshot_writer.write(event)
+    }
+
     fn on_insert(key: Key, values: Vec<Value>, input_session: &mut dyn InputAdaptor<Timestamp>) {
         input_session.insert(key, Value::Tuple(values.into()));
     }
@@ -699,7 +848,7 @@ where
                     let snapshot_event = entry
                         .snapshot_event(key)
                         .expect("Snapshot event not constructed");
-                    if let Err(e) = snapshot_writer.lock().unwrap().write(&snapshot_event) {
+                    if let Err(e) = self.write_to_snapshot(snapshot_writer, &snapshot_event) {
                         error!("Failed to save row ({entry:?}) in persistent buffer. Error: {e}");
                     }
                 }
@@ -724,16 +873,16 @@ where
                     Self::on_remove(key.expect("No key"), values, input_session);
                 }
                 ParsedEvent::AdvanceTime => {
+                    let batch_time = self.current_timestamp.clone().into();
                     let time_advanced = self.advance_time(input_session);
                     if let Some(ref mut connector_monitor) = connector_monitor {
-                        connector_monitor.commit();
+                        connector_monitor.commit(batch_time);
                     }
                     if let Some(snapshot_writer) = snapshot_writer {
-                        if let Err(e) = snapshot_writer
-                            .lock()
-                            .unwrap()
-                            .write(&SnapshotEvent::AdvanceTime(time_advanced))
-                        {
+                        if let Err(e) = self.write_to_snapshot(
+                            snapshot_writer,
+                            &SnapshotEvent::AdvanceTime(time_advanced),
+                        ) {
                             error!("Failed to save time advancement ({time_advanced}) in persistent buffer. Error: {e}");
                         }
                     }
@@ -1,11 +1,18 @@
 // Copyright © 2024 Pathway
 
 use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::{Duration, Instant};
 
 use log::{info, warn};
+use opentelemetry::global::BoxedSpan;
+use opentelemetry::trace::{Span, Tracer};
+use opentelemetry::KeyValue;
 use pyo3::pyclass;
 
+use crate::engine::telemetry::{epoch_attribute, tracer};
+
 #[derive(Debug, Clone, Copy)]
 #[pyclass]
 pub struct ConnectorStats {
@@ -17,6 +24,62 @@ pub struct ConnectorStats {
     pub num_messages_recently_committed: usize,
     #[pyo3(get, set)]
     pub finished: bool,
+    /// Number of the messages in the source not read yet.
+    #[pyo3(get, set)]
+    pub lag_messages: Option<u64>,
+    /// Number of the files in the source not read yet.
+    #[pyo3(get, set)]
+    pub backlog_files: Option<u64>,
+    /// Total size of the files in the source not read yet.
+    #[pyo3(get, set)]
+    pub backlog_bytes: Option<u64>,
+    /// Number of the entries read from the source and waiting to be sent to the engine.
+    #[pyo3(get, set)]
+    pub queue_depth: usize,
+}
+
+/// How far a reader is behind its source. The measures not applicable to the source
+/// are `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLag {
+    /// Number of the messages not read yet, e.g. the end offsets of the partitions of
+    /// a Kafka topic minus the positions of the consumer.
+    pub messages: Option<u64>,
+    /// Number of the files not read yet, including the ones not discovered before.
+    pub files: Option<u64>,
+    /// Total size of the files not read yet.
+    pub bytes: Option<u64>,
+}
+
+pub type SharedSourceLag = Arc<Mutex<Option<SourceLag>>>;
+
+/// Measures the lag of a reader behind its source. It's called periodically
+/// in a separate thread, so it may query the source.
+pub trait SourceLagTracker: Send {
+    fn lag(&mut self) -> Option<SourceLag>;
+}
+
+const SOURCE_LAG_REFRESH_PERIOD: Duration = Duration::from_secs(5);
+
+/// Refreshes the lag of a reader periodically, for as long as its monitor is alive.
+pub fn start_source_lag_tracking(
+    name: &str,
+    mut tracker: Box<dyn SourceLagTracker>,
+    source_lag: &SharedSourceLag,
+) {
+    let source_lag = Arc::downgrade(source_lag);
+    thread::Builder::new()
+        .name(format!("pathway:source_lag-{name}"))
+        .spawn(move || loop {
+            let lag = tracker.lag();
+            let Some(source_lag) = source_lag.upgrade() else {
+                break;
+            };
+            *source_lag.lock().unwrap() = lag;
+            drop(source_lag);
+            thread::sleep(SOURCE_LAG_REFRESH_PERIOD);
+        })
+        .expect("source lag tracking thread creation failed");
 }
 
 struct ConnectorLogger {
@@ -89,10 +152,12 @@ pub struct ConnectorMonitor {
     last_minute_queue: VecDeque<(usize, Instant)>,
     current_num_messages: usize,
     logger: ConnectorLogger,
+    batch_span: Option<BoxedSpan>,
+    source_lag: SharedSourceLag,
 }
 
 impl ConnectorMonitor {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String, source_lag: SharedSourceLag) -> Self {
         ConnectorMonitor {
             name: name.clone(),
             stats: ConnectorStats {
@@ -100,25 +165,58 @@ impl ConnectorMonitor {
                 num_messages_in_last_minute: 0,
                 num_messages_recently_committed: 0,
                 finished: false,
+                lag_messages: None,
+                backlog_files: None,
+                backlog_bytes: None,
+                queue_depth: 0,
             },
             last_minute_queue: VecDeque::new(),
             current_num_messages: 0,
             logger: ConnectorLogger::new(name),
+            batch_span: None,
+            source_lag,
         }
     }
 
     pub fn increment(&mut self) {
+        if self.batch_span.is_none() {
+            let tracer = tracer();
+            self.batch_span = Some(
+                tracer
+                    .span_builder("connector_batch")
+                    .with_attributes(vec![KeyValue::new("pathway.connector", self.name.clone())])
+                    .start(&tracer),
+            );
+        }
         self.current_num_messages += 1;
     }
 
+    /// Ends the span of the current batch. The epoch is unknown for the batch
+    /// closed by finishing the source.
+    fn end_batch_span(&mut self, time: Option<u64>) {
+        if let Some(mut span) = self.batch_span.take() {
+            if let Some(time) = time {
+                span.set_attribute(epoch_attribute(time));
+            }
+            span.set_attribute(KeyValue::new(
+                "pathway.rows",
+                i64::try_from(self.current_num_messages).unwrap_or(i64::MAX),
+            ));
+            span.end();
+        }
+    }
+
     pub fn finish(&mut self) {
+        self.end_batch_span(None);
         self.stats.finished = true;
         self.logger
             .on_commit(Instant::now(), self.current_num_messages);
         self.logger.on_finished();
     }
 
-    pub fn commit(&mut self) {
+    /// Closes the batch of the messages sent at `time`.
+    pub fn commit(&mut self, time: u64) {
+        self.end_batch_span(Some(time));
         self.stats.num_messages_recently_committed = self.current_num_messages;
         let now = Instant::now();
         while let Some(elem) = self.last_minute_queue.front() {
@@ -136,12 +234,22 @@ impl ConnectorMonitor {
         self.current_num_messages = 0;
     }
 
+    pub fn set_queue_depth(&mut self, queue_depth: usize) {
+        self.stats.queue_depth = queue_depth;
+    }
+
     pub fn get_name(&self) -> String {
         self.name.clone()
     }
 
     pub fn get_stats(&self) -> ConnectorStats {
-        self.stats
+        let source_lag = self.source_lag.lock().unwrap().unwrap_or_default();
+        ConnectorStats {
+            lag_messages: source_lag.messages,
+            backlog_files: source_lag.files,
+            backlog_bytes: source_lag.bytes,
+            ..self.stats
+        }
     }
 }
 
@@ -1,13 +1,17 @@
 // Copyright © 2024 Pathway
 
+use std::collections::HashMap;
 use std::os::unix::ffi::OsStrExt;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
-use xxhash_rust::xxh3::Xxh3 as Hasher;
 
-use crate::engine::value::HashInto;
+use log::info;
+
+use crate::engine::value::{HashInto, Hasher};
+use crate::engine::Value;
+use crate::persistence::frontier::OffsetAntichain;
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, Ord, PartialOrd)]
@@ -44,6 +48,9 @@ pub enum OffsetValue {
     },
     PythonEntrySequentialId(u64),
     Empty,
+    /// The position in the source reported by a Python connector, passed back to it
+    /// to resume from.
+    PythonCursor(Value),
 }
 
 impl HashInto for OffsetValue {
@@ -66,11 +73,94 @@ impl HashInto for OffsetValue {
                 sequential_id.hash_into(hasher);
             }
             OffsetValue::Empty => {}
+            OffsetValue::PythonCursor(cursor) => cursor.hash_into(hasher),
         };
     }
 }
 
+impl OffsetValue {
+    /// Whether the entry with this offset was read before the entry with the offset
+    /// `other` or is that entry. The offsets that can't be compared, like positions in
+    /// different files, are never covered.
+    pub fn is_covered_by(&self, other: &OffsetValue) -> bool {
+        match (self, other) {
+            (OffsetValue::KafkaOffset(offset), OffsetValue::KafkaOffset(other_offset)) => {
+                offset <= other_offset
+            }
+            (
+                OffsetValue::FilePosition {
+                    path, bytes_offset, ..
+                },
+                OffsetValue::FilePosition {
+                    path: other_path,
+                    bytes_offset: other_bytes_offset,
+                    ..
+                },
+            ) => path == other_path && bytes_offset <= other_bytes_offset,
+            (
+                OffsetValue::S3ObjectPosition {
+                    path, bytes_offset, ..
+                },
+                OffsetValue::S3ObjectPosition {
+                    path: other_path,
+                    bytes_offset: other_bytes_offset,
+                    ..
+                },
+            ) => path == other_path && bytes_offset <= other_bytes_offset,
+            (
+                OffsetValue::PythonEntrySequentialId(sequential_id),
+                OffsetValue::PythonEntrySequentialId(other_sequential_id),
+            ) => sequential_id <= other_sequential_id,
+            _ => false,
+        }
+    }
+}
+
 pub type Offset = (OffsetKey, OffsetValue);
 
 /// Empty offset for connectors that don't support persistence
 pub const EMPTY_OFFSET: Offset = (OffsetKey::Empty, OffsetValue::Empty);
+
+/// Skips the entries that a reader produces again after a restart, although they are
+/// covered by the persisted frontier, e.g. because its source can only be read from the
+/// beginning.
+///
+/// The entries of each offset key are skipped until the first one that isn't covered,
+/// as from then on the reader is past the persisted position.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Default)]
+pub struct OffsetDeduplicator {
+    // The persisted offsets and the numbers of the entries skipped for them
+    persisted_offsets: HashMap<OffsetKey, (OffsetValue, usize)>,
+}
+
+impl OffsetDeduplicator {
+    pub fn new(persisted_frontier: OffsetAntichain) -> Self {
+        Self {
+            persisted_offsets: persisted_frontier
+                .into_iter()
+                .map(|(offset_key, offset_value)| (offset_key, (offset_value, 0)))
+                .collect(),
+        }
+    }
+
+    pub fn is_duplicate(&mut self, offset: &Offset) -> bool {
+        let (offset_key, offset_value) = offset;
+        let Some((persisted_offset_value, skipped_entries)) =
+            self.persisted_offsets.get_mut(offset_key)
+        else {
+            return false;
+        };
+        if offset_value.is_covered_by(persisted_offset_value) {
+            *skipped_entries += 1;
+            return true;
+        }
+        if *skipped_entries > 0 {
+            info!(
+                "Skipped {skipped_entries} entries for {offset_key:?}, which were read before the restart"
+            );
+        }
+        self.persisted_offsets.remove(offset_key);
+        false
+    }
+}
@@ -1,10 +1,14 @@
 // Copyright © 2024 Pathway
 
 use log::{error, info, warn};
+use std::borrow::Cow;
 use std::fs;
 use std::fs::File;
 use std::fs::OpenOptions;
-use std::io::{BufReader, BufWriter, ErrorKind as IoErrorKind, Seek, Write};
+use std::io::{
+    BufRead, BufReader, BufWriter, Cursor, Error as IoError, ErrorKind as IoErrorKind, Read, Seek,
+    Write,
+};
 use std::mem::take;
 use std::path::Path;
 use std::path::PathBuf;
@@ -12,7 +16,10 @@ use std::sync::mpsc;
 use std::sync::mpsc::Sender;
 use std::thread;
 
-use bincode::{deserialize_from, serialize, serialize_into, ErrorKind as BincodeError};
+use bincode::{
+    deserialize_from, serialize, serialize_into, serialized_size, ErrorKind as BincodeError,
+    Result as BincodeResult,
+};
 use futures::channel::oneshot;
 use futures::channel::oneshot::Receiver as OneShotReceiver;
 use futures::channel::oneshot::Sender as OneShotSender;
@@ -20,24 +27,124 @@ use pipe::PipeReader;
 use s3::bucket::Bucket as S3Bucket;
 use s3::error::S3Error;
 use s3::serde_types::Part as S3Part;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::connectors::data_storage::S3CommandName;
 use crate::connectors::data_storage::{
     CurrentlyProcessedS3Object, ReadError, S3Scanner, WriteError,
 };
 use crate::deepcopy::DeepCopy;
-use crate::engine::{Key, Value};
+use crate::engine::value::LegacyValue;
+use crate::engine::{value_encoding, Key, Value};
 use crate::fs_helpers::ensure_directory;
+use crate::persistence::encryption::EncryptionKey;
+use crate::persistence::object_storage::SharedObjectStorage;
+use crate::persistence::schema::PersistedColumn;
+use crate::persistence::sync::SharedUnpersistedVolume;
 use crate::timestamp::current_unix_timestamp_ms;
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Event {
     Insert(Key, Vec<Value>),
     Delete(Key, Vec<Value>),
     Upsert(Key, Option<Vec<Value>>),
     AdvanceTime(u64),
     Finished,
+    // The schema of the rows persisted after this event.
+    Schema(Vec<PersistedColumn>),
+}
+
+/// The values of a row, stored together in the compact encoding, so that they share its
+/// version and dictionary of strings.
+struct EncodedRow<'a>(Cow<'a, [Value]>);
+
+impl Serialize for EncodedRow<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&value_encoding::encode(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedRow<'_> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        value_encoding::deserialize(deserializer).map(|values| Self(Cow::Owned(values)))
+    }
+}
+
+/// The form in which the events are stored in the snapshots.
+///
+/// The rows of the events stored before the compact encoding of the values are read as
+/// they were stored then. The new variants go last, so that the indices of the older ones,
+/// which are the first bytes of the stored events, stay the same.
+#[derive(Serialize, Deserialize)]
+enum StoredEvent<'a> {
+    LegacyInsert(Key, Vec<LegacyValue>),
+    LegacyDelete(Key, Vec<LegacyValue>),
+    LegacyUpsert(Key, Option<Vec<LegacyValue>>),
+    AdvanceTime(u64),
+    Finished,
+    Schema(Cow<'a, [PersistedColumn]>),
+    Insert(Key, EncodedRow<'a>),
+    Delete(Key, EncodedRow<'a>),
+    Upsert(Key, Option<EncodedRow<'a>>),
+}
+
+impl<'a> From<&'a Event> for StoredEvent<'a> {
+    fn from(event: &'a Event) -> Self {
+        let row = |values: &'a Vec<Value>| EncodedRow(Cow::Borrowed(values));
+        match event {
+            Event::Insert(key, values) => Self::Insert(*key, row(values)),
+            Event::Delete(key, values) => Self::Delete(*key, row(values)),
+            Event::Upsert(key, values) => Self::Upsert(*key, values.as_ref().map(row)),
+            Event::AdvanceTime(time) => Self::AdvanceTime(*time),
+            Event::Finished => Self::Finished,
+            Event::Schema(columns) => Self::Schema(Cow::Borrowed(columns)),
+        }
+    }
+}
+
+impl From<StoredEvent<'_>> for Event {
+    fn from(event: StoredEvent<'_>) -> Self {
+        let legacy_row = |values: Vec<LegacyValue>| -> Vec<Value> {
+            values.into_iter().map(Value::from).collect()
+        };
+        match event {
+            StoredEvent::LegacyInsert(key, values) => Self::Insert(key, legacy_row(values)),
+            StoredEvent::LegacyDelete(key, values) => Self::Delete(key, legacy_row(values)),
+            StoredEvent::LegacyUpsert(key, values) => Self::Upsert(key, values.map(legacy_row)),
+            StoredEvent::AdvanceTime(time) => Self::AdvanceTime(time),
+            StoredEvent::Finished => Self::Finished,
+            StoredEvent::Schema(columns) => Self::Schema(columns.into_owned()),
+            StoredEvent::Insert(key, values) => Self::Insert(key, values.0.into_owned()),
+            StoredEvent::Delete(key, values) => Self::Delete(key, values.0.into_owned()),
+            StoredEvent::Upsert(key, values) => {
+                Self::Upsert(key, values.map(|values| values.0.into_owned()))
+            }
+        }
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        StoredEvent::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        StoredEvent::deserialize(deserializer).map(Self::from)
+    }
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -79,15 +186,450 @@ pub trait SnapshotWriter: Send {
     fn flush(&mut self) -> OneShotReceiver<Result<(), WriteError>>;
 }
 
+// A block of events starts with one of these bytes, which are never the first byte of
+// a serialized event, as they are the indices of `Event` variants. The blocks written
+// before the checksums were added start with the first one
+const BLOCK_MARKER: u8 = 0xff;
+const CHECKSUMMED_BLOCK_MARKER: u8 = 0xfe;
+// The marker, the codec, the encryption and the length of the payload as little-endian
+// `u64`
+const BLOCK_HEADER_LEN: usize = 11;
+// Followed by the CRC32 of the rest of the header and of the payload as little-endian
+// `u32`
+const CHECKSUMMED_BLOCK_HEADER_LEN: usize = BLOCK_HEADER_LEN + 4;
+const MAX_UNCOMPRESSED_BLOCK_LEN: usize = 1 << 20;
+
+const NO_CODEC: u8 = 0;
+const ZSTD_CODEC: u8 = 1;
+const LZ4_CODEC: u8 = 2;
+const NO_ENCRYPTION: u8 = 0;
+const AES_256_GCM_ENCRYPTION: u8 = 1;
+
+/// The algorithm compressing the blocks of events in a snapshot.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SnapshotCompression {
+    Zstd { level: i32 },
+    Lz4,
+}
+
+impl SnapshotCompression {
+    /// Parses the compression named `codec`, either `zstd` or `lz4`. The level can
+    /// only be set for zstd and defaults to its default level.
+    pub fn new(codec: &str, level: Option<i32>) -> Result<Self, String> {
+        match (codec, level) {
+            ("zstd", level) => {
+                let level = level.unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL);
+                let levels = zstd::compression_level_range();
+                if !levels.contains(&level) {
+                    return Err(format!(
+                        "zstd compression level has to be between {} and {}",
+                        levels.start(),
+                        levels.end()
+                    ));
+                }
+                Ok(Self::Zstd { level })
+            }
+            ("lz4", None) => Ok(Self::Lz4),
+            ("lz4", Some(_)) => Err("lz4 compression has no levels".to_string()),
+            (codec, _) => Err(format!("unknown snapshot compression: {codec}")),
+        }
+    }
+
+    fn codec(self) -> u8 {
+        match self {
+            Self::Zstd { .. } => ZSTD_CODEC,
+            Self::Lz4 => LZ4_CODEC,
+        }
+    }
+
+    fn compress(self, events: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Zstd { level } => {
+                zstd::bulk::compress(events, level).expect("compressing to memory should not fail")
+            }
+            Self::Lz4 => lz4_flex::compress_prepend_size(events),
+        }
+    }
+}
+
+/// The way the events are stored in a snapshot.
+#[derive(Clone, Debug, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub struct SnapshotEncoding {
+    /// If set, the events are compressed in blocks with this algorithm.
+    pub compression: Option<SnapshotCompression>,
+    /// If set, the events are encrypted in blocks with this key.
+    pub encryption_key: Option<EncryptionKey>,
+}
+
+/// The name of a snapshot chunk stored at the path with `components`, binding its
+/// encrypted blocks to it: the chunk and the two directories above it, the worker and
+/// the input, which stay the same wherever the whole storage is moved.
+fn chunk_name<S: AsRef<str>>(components: impl DoubleEndedIterator<Item = S>) -> String {
+    let mut name: Vec<S> = components.rev().take(3).collect();
+    name.reverse();
+    name.iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+fn local_chunk_name(path: &Path) -> String {
+    chunk_name(path.iter().map(|component| component.to_string_lossy()))
+}
+
+fn object_chunk_name(key: &str) -> String {
+    chunk_name(key.split('/'))
+}
+
+/// The data authenticated together with an encrypted block: its header without the
+/// length of the payload, which is authenticated by the encryption itself, the index of
+/// the block in the chunk and the name of the chunk. A block moved elsewhere, or with a
+/// modified header, can't be decrypted.
+fn block_aad(header: &[u8], chunk_name: &str, block_index: u64) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(3 + 8 + chunk_name.len());
+    aad.extend_from_slice(&header[..3]);
+    aad.extend_from_slice(&block_index.to_le_bytes());
+    aad.extend_from_slice(chunk_name.as_bytes());
+    aad
+}
+
+/// The checksum of a block, covering its header without the checksum and its payload.
+fn block_checksum(header: &[u8], payload: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&header[..BLOCK_HEADER_LEN]);
+    hasher.update(payload);
+    hasher.finalize()
+}
+
+/// Stores serialized events as the `encoding` requires, as the block with
+/// `block_index` in the chunk `chunk_name`. The events are compressed first and then
+/// encrypted, and the header of the block records how, along with the checksum of the
+/// stored data.
+fn encode_block(
+    events: &[u8],
+    encoding: &SnapshotEncoding,
+    chunk_name: &str,
+    block_index: u64,
+) -> Vec<u8> {
+    let mut header = vec![
+        CHECKSUMMED_BLOCK_MARKER,
+        encoding
+            .compression
+            .map_or(NO_CODEC, SnapshotCompression::codec),
+        if encoding.encryption_key.is_some() {
+            AES_256_GCM_ENCRYPTION
+        } else {
+            NO_ENCRYPTION
+        },
+    ];
+    let compressed = encoding
+        .compression
+        .map(|compression| compression.compress(events));
+    let payload = compressed.as_deref().unwrap_or(events);
+    let encrypted = encoding
+        .encryption_key
+        .as_ref()
+        .map(|key| key.encrypt(payload, &block_aad(&header, chunk_name, block_index)));
+    let payload = encrypted.as_deref().unwrap_or(payload);
+
+    let payload_len = u64::try_from(payload.len()).expect("block length should fit in 64 bits");
+    header.reserve(CHECKSUMMED_BLOCK_HEADER_LEN - header.len() + payload.len());
+    header.extend_from_slice(&payload_len.to_le_bytes());
+    let checksum = block_checksum(&header, payload);
+    header.extend_from_slice(&checksum.to_le_bytes());
+    header.extend_from_slice(payload);
+    header
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> IoError {
+    IoError::new(IoErrorKind::InvalidData, error)
+}
+
+/// Reads events stored in blocks, as well as one by one, as the snapshots written without
+/// compression and encryption before the blocks were checksummed store them.
+///
+/// If the encryption key is set, only encrypted blocks are accepted, unless the chunk is
+/// read to be migrated.
+struct EventReader<R: BufRead> {
+    reader: R,
+    encryption_key: Option<EncryptionKey>,
+    chunk_name: String,
+    block: Option<Cursor<Vec<u8>>>,
+    block_len: u64,
+    block_index: u64,
+    block_encoding: SnapshotEncoding,
+    accept_unencrypted: bool,
+    read_unencrypted: bool,
+}
+
+impl<R: BufRead> EventReader<R> {
+    fn new(reader: R, encryption_key: Option<EncryptionKey>, chunk_name: String) -> Self {
+        Self {
+            reader,
+            encryption_key,
+            chunk_name,
+            block: None,
+            block_len: 0,
+            block_index: 0,
+            block_encoding: SnapshotEncoding::default(),
+            accept_unencrypted: false,
+            read_unencrypted: false,
+        }
+    }
+
+    /// Creates a reader of a chunk written before the encryption was enabled, which
+    /// accepts the unencrypted data to migrate it.
+    fn new_migrated(reader: R, encryption_key: EncryptionKey, chunk_name: String) -> Self {
+        Self {
+            accept_unencrypted: true,
+            ..Self::new(reader, Some(encryption_key), chunk_name)
+        }
+    }
+
+    fn check_unencrypted_allowed(&mut self) -> Result<(), IoError> {
+        if self.encryption_key.is_some() && !self.accept_unencrypted {
+            return Err(invalid_data(format!(
+                "the snapshot chunk {} isn't encrypted, it can be encrypted by a single run \
+                with the migration of unencrypted data enabled",
+                self.chunk_name
+            )));
+        }
+        self.read_unencrypted = true;
+        Ok(())
+    }
+
+    fn read(&mut self) -> BincodeResult<Event> {
+        loop {
+            if let Some(block) = &mut self.block {
+                if block.position() < block.get_ref().len() as u64 {
+                    return deserialize_from(block);
+                }
+                self.block = None;
+            }
+            match self.reader.fill_buf()?.first() {
+                Some(&(BLOCK_MARKER | CHECKSUMMED_BLOCK_MARKER)) => {}
+                Some(_) => {
+                    self.check_unencrypted_allowed()
+                        .map_err(|e| Box::new(BincodeError::Io(e)))?;
+                    return deserialize_from(&mut self.reader);
+                }
+                None => return deserialize_from(&mut self.reader),
+            }
+            let events = self
+                .read_block()
+                .map_err(|e| Box::new(BincodeError::Io(e)))?;
+            self.block = Some(Cursor::new(events));
+        }
+    }
+
+    /// Reads the next block, returning the events stored in it. A block with a checksum
+    /// not matching its data is rejected, as the rest of the chunk can't be trusted.
+    fn read_block(&mut self) -> Result<Vec<u8>, IoError> {
+        let mut header = [0; CHECKSUMMED_BLOCK_HEADER_LEN];
+        self.reader.read_exact(&mut header[..BLOCK_HEADER_LEN])?;
+        let [marker, codec, encryption, ..] = header;
+        let header_len = if marker == CHECKSUMMED_BLOCK_MARKER {
+            CHECKSUMMED_BLOCK_HEADER_LEN
+        } else {
+            BLOCK_HEADER_LEN
+        };
+        self.reader
+            .read_exact(&mut header[BLOCK_HEADER_LEN..header_len])?;
+        let header = &header[..header_len];
+        let payload_len = u64::from_le_bytes(header[3..BLOCK_HEADER_LEN].try_into().unwrap());
+        let mut payload = Vec::new();
+        (&mut self.reader)
+            .take(payload_len)
+            .read_to_end(&mut payload)?;
+        if (payload.len() as u64) < payload_len {
+            return Err(IoErrorKind::UnexpectedEof.into());
+        }
+        self.block_len = header_len as u64 + payload_len;
+        self.block_encoding = SnapshotEncoding::default();
+        let block_index = self.block_index;
+        self.block_index += 1;
+
+        if header_len == CHECKSUMMED_BLOCK_HEADER_LEN {
+            let checksum = u32::from_le_bytes(header[BLOCK_HEADER_LEN..].try_into().unwrap());
+            if block_checksum(header, &payload) != checksum {
+                return Err(invalid_data(format!(
+                    "the block {block_index} of the snapshot chunk {} is corrupted, \
+                    its checksum doesn't match its data",
+                    self.chunk_name
+                )));
+            }
+        }
+
+        match encryption {
+            NO_ENCRYPTION => self.check_unencrypted_allowed()?,
+            AES_256_GCM_ENCRYPTION => {
+                let Some(key) = &self.encryption_key else {
+                    return Err(invalid_data(
+                        "the snapshot is encrypted, but no encryption key is set",
+                    ));
+                };
+                let aad = block_aad(header, &self.chunk_name, block_index);
+                payload = key.decrypt(&payload, &aad).map_err(invalid_data)?;
+                self.block_encoding.encryption_key = Some(key.clone());
+            }
+            encryption => {
+                return Err(invalid_data(format!(
+                    "unknown encryption of a snapshot block: {encryption}"
+                )))
+            }
+        }
+
+        // The level isn't stored, as the blocks written again after a truncation are
+        // small, so the default one is used for them
+        let (events, compression) = match codec {
+            NO_CODEC => (payload, None),
+            ZSTD_CODEC => (
+                zstd::stream::decode_all(payload.as_slice())?,
+                Some(SnapshotCompression::Zstd {
+                    level: zstd::DEFAULT_COMPRESSION_LEVEL,
+                }),
+            ),
+            LZ4_CODEC => (
+                lz4_flex::decompress_size_prepended(&payload).map_err(invalid_data)?,
+                Some(SnapshotCompression::Lz4),
+            ),
+            codec => {
+                return Err(invalid_data(format!(
+                    "unknown compression of a snapshot block: {codec}"
+                )))
+            }
+        };
+        self.block_encoding.compression = compression;
+        Ok(events)
+    }
+
+    /// If only a part of the current block has been read, returns the length of the
+    /// block and the read events, stored the same way as the block.
+    fn encode_partially_read_block(&self) -> Option<(u64, Vec<u8>)> {
+        let block = self.block.as_ref()?;
+        let position = usize::try_from(block.position()).unwrap();
+        // The read part replaces the block, so it gets the same index
+        (position < block.get_ref().len()).then(|| {
+            (
+                self.block_len,
+                encode_block(
+                    &block.get_ref()[..position],
+                    &self.block_encoding,
+                    &self.chunk_name,
+                    self.block_index - 1,
+                ),
+            )
+        })
+    }
+}
+
+/// Reads a chunk written before the encryption was enabled and stores its events again
+/// with `encoding`, passing the blocks to `write`. A chunk stores all its events the
+/// same way, so if the first of them is encrypted, nothing is written and `false` is
+/// returned.
+fn migrate_chunk<R: BufRead>(
+    mut reader: EventReader<R>,
+    encoding: &SnapshotEncoding,
+    mut write: impl FnMut(Vec<u8>) -> Result<(), ReadError>,
+) -> Result<bool, ReadError> {
+    let mut block = Vec::new();
+    let mut block_index = 0;
+    loop {
+        let event = match reader.read() {
+            Ok(event) => event,
+            Err(e) => match *e {
+                // A partially written event at the end of the chunk is dropped, as when
+                // the chunk is read
+                BincodeError::Io(e) if e.kind() == IoErrorKind::UnexpectedEof => break,
+                BincodeError::Io(e) => return Err(ReadError::Io(e)),
+                _ => return Err(ReadError::Bincode(*e)),
+            },
+        };
+        if !reader.read_unencrypted {
+            return Ok(false);
+        }
+        serialize_into(&mut block, &event).map_err(|e| ReadError::Bincode(*e))?;
+        if block.len() >= MAX_UNCOMPRESSED_BLOCK_LEN {
+            write(encode_block(
+                &take(&mut block),
+                encoding,
+                &reader.chunk_name,
+                block_index,
+            ))?;
+            block_index += 1;
+        }
+    }
+    if !block.is_empty() {
+        write(encode_block(
+            &block,
+            encoding,
+            &reader.chunk_name,
+            block_index,
+        ))?;
+    }
+    Ok(reader.read_unencrypted)
+}
+
+/// Encrypts the chunks of the snapshot in the directory `root_path` written before the
+/// encryption was enabled, replacing each of them with a copy stored with `encoding`.
+pub fn encrypt_local_snapshot(
+    root_path: &Path,
+    encoding: &SnapshotEncoding,
+) -> Result<(), ReadError> {
+    let Some(key) = &encoding.encryption_key else {
+        return Ok(());
+    };
+    for entry in fs::read_dir(root_path)? {
+        let path = entry?.path();
+        if !path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.parse::<u64>().is_ok())
+        {
+            continue;
+        }
+        let reader = EventReader::new_migrated(
+            BufReader::new(File::open(&path)?),
+            key.clone(),
+            local_chunk_name(&path),
+        );
+        // The copy replaces the chunk only once it's complete
+        let migrated_path = path.with_extension("migrated");
+        let mut migrated_writer = None;
+        let migrated = migrate_chunk(reader, encoding, |block| {
+            let writer = match &mut migrated_writer {
+                Some(writer) => writer,
+                None => migrated_writer.insert(BufWriter::new(File::create(&migrated_path)?)),
+            };
+            writer.write_all(&block)?;
+            Ok(())
+        })?;
+        if let Some(mut writer) = migrated_writer {
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        if migrated {
+            info!("Encrypted the snapshot chunk {path:?} written without encryption");
+            fs::rename(&migrated_path, &path)?;
+        }
+    }
+    Ok(())
+}
+
 pub struct LocalBinarySnapshotReader {
     root_path: PathBuf,
-    reader: Option<BufReader<std::fs::File>>,
+    reader: Option<EventReader<BufReader<std::fs::File>>>,
     next_file_idx: usize,
     times_advanced: Vec<u64>,
+    encryption_key: Option<EncryptionKey>,
 }
 
 impl LocalBinarySnapshotReader {
-    pub fn new(root_path: PathBuf) -> Result<LocalBinarySnapshotReader, ReadError> {
+    pub fn new(
+        root_path: PathBuf,
+        encryption_key: Option<EncryptionKey>,
+    ) -> Result<LocalBinarySnapshotReader, ReadError> {
         let mut times_advanced = Vec::new();
         let entries = fs::read_dir(&root_path).map_err(ReadError::Io)?;
         for entry in entries {
@@ -109,6 +651,7 @@ impl LocalBinarySnapshotReader {
             reader: None,
             next_file_idx: 0,
             times_advanced,
+            encryption_key,
         })
     }
 }
@@ -117,7 +660,7 @@ impl SnapshotReaderImpl for LocalBinarySnapshotReader {
     fn read(&mut self) -> Result<Event, ReadError> {
         loop {
             match &mut self.reader {
-                Some(reader) => match deserialize_from(reader) {
+                Some(reader) => match reader.read() {
                     Ok(entry) => return Ok(entry),
                     Err(e) => match *e {
                         BincodeError::Io(e) => {
@@ -136,8 +679,10 @@ impl SnapshotReaderImpl for LocalBinarySnapshotReader {
                     }
                     let current_file_path = Path::new(&self.root_path)
                         .join(format!("{}", self.times_advanced[self.next_file_idx]));
-                    self.reader = Some(BufReader::new(
-                        File::open(current_file_path).map_err(ReadError::Io)?,
+                    self.reader = Some(EventReader::new(
+                        BufReader::new(File::open(&current_file_path).map_err(ReadError::Io)?),
+                        self.encryption_key.clone(),
+                        local_chunk_name(&current_file_path),
                     ));
                     self.next_file_idx += 1;
                 }
@@ -148,14 +693,27 @@ impl SnapshotReaderImpl for LocalBinarySnapshotReader {
 
     fn truncate(&mut self) -> Result<(), ReadError> {
         if let Some(ref mut reader) = &mut self.reader {
-            let stable_position = reader.stream_position()?;
+            let mut stable_position = reader.reader.stream_position()?;
             let file_path = Path::new(&self.root_path)
                 .join(format!("{}", self.times_advanced[self.next_file_idx - 1]));
 
+            // The read part of a block is stored again in place of the block
+            let read_part_of_block =
+                reader
+                    .encode_partially_read_block()
+                    .map(|(block_len, encoded_events)| {
+                        stable_position -= block_len;
+                        encoded_events
+                    });
+
             info!("Truncate: Shrink {file_path:?} to {stable_position} bytes");
 
-            let file = OpenOptions::new().write(true).open(file_path)?;
+            let mut file = OpenOptions::new().write(true).open(file_path)?;
             file.set_len(stable_position)?;
+            if let Some(block) = read_part_of_block {
+                file.seek(std::io::SeekFrom::End(0))?;
+                file.write_all(&block)?;
+            }
         }
 
         for unreachable_part in &self.times_advanced[self.next_file_idx..] {
@@ -172,46 +730,87 @@ impl SnapshotReaderImpl for LocalBinarySnapshotReader {
 pub struct LocalBinarySnapshotWriter {
     root_path: PathBuf,
     lazy_writer: Option<BufWriter<std::fs::File>>,
+    chunk_name: String,
+    encoding: SnapshotEncoding,
+    block: Vec<u8>,
+    next_block_index: u64,
 }
 
 impl LocalBinarySnapshotWriter {
-    pub fn new(path: &Path) -> Result<LocalBinarySnapshotWriter, WriteError> {
+    /// Creates a writer saving the events in the directory `path` with the given
+    /// `encoding`.
+    pub fn new(
+        path: &Path,
+        encoding: SnapshotEncoding,
+    ) -> Result<LocalBinarySnapshotWriter, WriteError> {
         ensure_directory(path)?;
 
         Ok(Self {
             root_path: path.to_owned(),
             lazy_writer: None,
+            chunk_name: String::new(),
+            encoding,
+            block: Vec::new(),
+            next_block_index: 0,
         })
     }
+
+    fn writer(&mut self) -> Result<&mut BufWriter<std::fs::File>, WriteError> {
+        if self.lazy_writer.is_none() {
+            let current_timestamp = current_unix_timestamp_ms();
+            let path = self.root_path.join(format!("{current_timestamp}"));
+
+            self.lazy_writer = Some(BufWriter::new(File::create(&path)?));
+            self.chunk_name = local_chunk_name(&path);
+        }
+        Ok(self.lazy_writer.as_mut().unwrap())
+    }
+
+    fn write_block(&mut self) -> Result<(), WriteError> {
+        if self.block.is_empty() {
+            return Ok(());
+        }
+        // The file is created first, as its name is a part of the encrypted block
+        self.writer()?;
+        let block = encode_block(
+            &take(&mut self.block),
+            &self.encoding,
+            &self.chunk_name,
+            self.next_block_index,
+        );
+        self.next_block_index += 1;
+        self.writer()?.write_all(&block)?;
+        Ok(())
+    }
+}
+
+impl Drop for LocalBinarySnapshotWriter {
+    fn drop(&mut self) {
+        // The buffered writer saves the rest of the data on drop, and so do we
+        if let Err(e) = self.write_block() {
+            error!("Failed to write the last block of the snapshot: {e}");
+        }
+    }
 }
 
 impl SnapshotWriter for LocalBinarySnapshotWriter {
     fn write(&mut self, event: &Event) -> Result<(), WriteError> {
-        let writer = {
-            if let Some(lazy_writer) = &mut self.lazy_writer {
-                lazy_writer
-            } else {
-                let current_timestamp = current_unix_timestamp_ms();
-                let path = self.root_path.join(format!("{current_timestamp}"));
-
-                self.lazy_writer = Some(BufWriter::new(File::create(path)?));
-                self.lazy_writer.as_mut().unwrap()
-            }
-        };
-
-        serialize_into(writer, &event).map_err(|e| match *e {
-            BincodeError::Io(io_error) => WriteError::Io(*Box::new(io_error)),
-            _ => WriteError::Bincode(*e),
-        })
+        serialize_into(&mut self.block, &event).map_err(|e| WriteError::Bincode(*e))?;
+        if self.block.len() >= MAX_UNCOMPRESSED_BLOCK_LEN {
+            self.write_block()?;
+        }
+        Ok(())
     }
 
     fn flush(&mut self) -> OneShotReceiver<Result<(), WriteError>> {
         let (sender, receiver) = oneshot::channel();
 
-        let internal_flush_result: Result<(), WriteError> = match &mut self.lazy_writer {
-            Some(ref mut writer) => writer.flush().map_err(WriteError::Io),
-            None => Ok(()),
-        };
+        let internal_flush_result: Result<(), WriteError> =
+            self.write_block()
+                .and_then(|()| match &mut self.lazy_writer {
+                    Some(ref mut writer) => writer.flush().map_err(WriteError::Io),
+                    None => Ok(()),
+                });
 
         let send_result = sender.send(internal_flush_result);
         if let Err(unsent_flush_result) = send_result {
@@ -224,16 +823,26 @@ impl SnapshotWriter for LocalBinarySnapshotWriter {
 
 const SNAPSHOT_CONTENT_TYPE: &str = "application/octet-stream";
 const MAX_CHUNK_LEN: usize = 1_000_000;
+// S3 rejects the parts of a multipart upload smaller than 5 MiB, except for the last one
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
 
 pub struct S3Writer {
     bucket: S3Bucket,
     key: String,
     upload_id: String,
     upload_parts: Vec<S3Part>,
+    pending_part: Vec<u8>,
+    chunk_name: String,
+    encoding: SnapshotEncoding,
+    next_block_index: u64,
 }
 
 impl S3Writer {
-    pub fn new(bucket: S3Bucket, path: &str) -> Result<Self, (S3CommandName, S3Error)> {
+    pub fn new(
+        bucket: S3Bucket,
+        path: &str,
+        encoding: SnapshotEncoding,
+    ) -> Result<Self, (S3CommandName, S3Error)> {
         let start_upload_response = bucket
             .initiate_multipart_upload(path, SNAPSHOT_CONTENT_TYPE)
             .map_err(|e| (S3CommandName::InitiateMultipartUpload, e))?;
@@ -243,19 +852,40 @@ impl S3Writer {
             key: start_upload_response.key,
             upload_id: start_upload_response.upload_id,
             upload_parts: Vec::new(),
+            pending_part: Vec::new(),
+            chunk_name: object_chunk_name(path),
+            encoding,
+            next_block_index: 0,
         })
     }
 
     pub fn put_chunk(&mut self, buffer: Vec<Event>) -> Result<(), (S3CommandName, S3Error)> {
-        let chunk = {
-            let mut chunk = Vec::new();
-            for entry in buffer {
-                let mut entry_serialized = serialize(&entry).expect("unable to serialize an entry");
-                chunk.append(&mut entry_serialized);
-            }
-            chunk
-        };
+        let mut chunk = Vec::new();
+        for entry in buffer {
+            let mut entry_serialized = serialize(&entry).expect("unable to serialize an entry");
+            chunk.append(&mut entry_serialized);
+        }
+        let block = encode_block(
+            &chunk,
+            &self.encoding,
+            &self.chunk_name,
+            self.next_block_index,
+        );
+        self.next_block_index += 1;
+        self.put_encoded(block)
+    }
 
+    /// Appends data already stored as the snapshot requires.
+    fn put_encoded(&mut self, mut chunk: Vec<u8>) -> Result<(), (S3CommandName, S3Error)> {
+        self.pending_part.append(&mut chunk);
+        if self.pending_part.len() >= MIN_PART_SIZE {
+            self.upload_pending_part()?;
+        }
+        Ok(())
+    }
+
+    fn upload_pending_part(&mut self) -> Result<(), (S3CommandName, S3Error)> {
+        let chunk = take(&mut self.pending_part);
         let part_number =
             u32::try_from(self.upload_parts.len()).expect("too many upload parts") + 1;
         let part = self
@@ -273,7 +903,18 @@ impl S3Writer {
         Ok(())
     }
 
-    pub fn finalize(self) -> Result<(), (S3CommandName, S3Error)> {
+    pub fn finalize(mut self) -> Result<(), (S3CommandName, S3Error)> {
+        if !self.pending_part.is_empty() {
+            self.upload_pending_part()?;
+        }
+        if self.upload_parts.is_empty() {
+            // An upload can't be completed without parts, and there is nothing to save
+            self.bucket
+                .abort_upload(&self.key, &self.upload_id)
+                .map_err(|e| (S3CommandName::AbortMultipartUpload, e))?;
+            return Ok(());
+        }
+
         let response = self
             .bucket
             .complete_multipart_upload(&self.key, &self.upload_id, self.upload_parts)
@@ -291,17 +932,22 @@ impl S3Writer {
 
 pub struct S3SnapshotReader {
     root_path: String,
-    reader: Option<PipeReader>,
+    reader: Option<EventReader<BufReader<PipeReader>>>,
     next_object_idx: usize,
     times_advanced: Vec<u64>,
 
     bucket: S3Bucket,
     current_state: Option<CurrentlyProcessedS3Object>,
     current_chunk_len: usize,
+    encryption_key: Option<EncryptionKey>,
 }
 
 impl S3SnapshotReader {
-    pub fn new(bucket: S3Bucket, path: &str) -> Result<S3SnapshotReader, ReadError> {
+    pub fn new(
+        bucket: S3Bucket,
+        path: &str,
+        encryption_key: Option<EncryptionKey>,
+    ) -> Result<S3SnapshotReader, ReadError> {
         let mut times_advanced = Vec::new();
 
         let object_lists = bucket
@@ -337,6 +983,7 @@ impl S3SnapshotReader {
             times_advanced,
             current_state: None,
             current_chunk_len: 0,
+            encryption_key,
         })
     }
 }
@@ -345,7 +992,7 @@ impl SnapshotReaderImpl for S3SnapshotReader {
     fn read(&mut self) -> Result<Event, ReadError> {
         loop {
             match &mut self.reader {
-                Some(reader) => match deserialize_from::<&mut PipeReader, Event>(reader) {
+                Some(reader) => match reader.read() {
                     Ok(entry) => {
                         self.current_chunk_len += 1;
                         return Ok(entry);
@@ -381,7 +1028,11 @@ impl SnapshotReaderImpl for S3SnapshotReader {
 
                     self.current_chunk_len = 0;
                     self.current_state = Some(new_current_state);
-                    self.reader = Some(pipe_reader);
+                    self.reader = Some(EventReader::new(
+                        BufReader::new(pipe_reader),
+                        self.encryption_key.clone(),
+                        object_chunk_name(&current_file_path),
+                    ));
                     self.next_object_idx += 1;
                 }
             }
@@ -401,18 +1052,28 @@ impl SnapshotReaderImpl for S3SnapshotReader {
             let object_after_truncation =
                 format!("{}/{}", self.root_path, current_unix_timestamp_ms());
 
-            let (_new_current_state, mut pipe_reader) =
-                S3Scanner::stream_object_from_path_and_bucket(
-                    &object_for_truncation,
-                    self.bucket.deep_copy(),
-                );
-            let mut writer = S3Writer::new(self.bucket.deep_copy(), &object_after_truncation)
-                .map_err(|(command, error)| ReadError::S3(command, error))?;
+            let (_new_current_state, pipe_reader) = S3Scanner::stream_object_from_path_and_bucket(
+                &object_for_truncation,
+                self.bucket.deep_copy(),
+            );
+            let mut event_reader = EventReader::new(
+                BufReader::new(pipe_reader),
+                self.encryption_key.clone(),
+                object_chunk_name(&object_for_truncation),
+            );
+            // The events kept are written uncompressed, but stay encrypted if they were
+            let encoding = SnapshotEncoding {
+                compression: None,
+                encryption_key: self.encryption_key.clone(),
+            };
+            let mut writer =
+                S3Writer::new(self.bucket.deep_copy(), &object_after_truncation, encoding)
+                    .map_err(|(command, error)| ReadError::S3(command, error))?;
 
             let mut n_entries_processed = 0;
             let mut current_chunk = Vec::new();
             while n_entries_processed < self.current_chunk_len {
-                let maybe_entry_read = deserialize_from::<&mut PipeReader, Event>(&mut pipe_reader);
+                let maybe_entry_read = event_reader.read();
                 if let Ok(entry) = maybe_entry_read {
                     current_chunk.push(entry);
                 } else {
@@ -450,6 +1111,66 @@ impl SnapshotReaderImpl for S3SnapshotReader {
     }
 }
 
+/// Encrypts the chunks of the snapshot in `root_path` in the bucket written before the
+/// encryption was enabled, replacing each of them with a copy stored with `encoding`.
+pub fn encrypt_s3_snapshot(
+    bucket: &S3Bucket,
+    root_path: &str,
+    encoding: &SnapshotEncoding,
+) -> Result<(), ReadError> {
+    let Some(key) = &encoding.encryption_key else {
+        return Ok(());
+    };
+    let object_lists = bucket
+        .list(root_path.to_string(), None)
+        .map_err(|e| ReadError::S3(S3CommandName::ListObjectsV2, e))?;
+    for object in object_lists.iter().flat_map(|list| &list.contents) {
+        if !Path::new(&object.key)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.parse::<u64>().is_ok())
+        {
+            continue;
+        }
+        let (current_state, pipe_reader) =
+            S3Scanner::stream_object_from_path_and_bucket(&object.key, bucket.deep_copy());
+        let reader = EventReader::new_migrated(
+            BufReader::new(pipe_reader),
+            key.clone(),
+            object_chunk_name(&object.key),
+        );
+        // The upload replaces the object only once it's completed
+        let mut migrated_writer = None;
+        let migrated = migrate_chunk(reader, encoding, |block| {
+            let writer = match &mut migrated_writer {
+                Some(writer) => writer,
+                None => migrated_writer.insert(
+                    S3Writer::new(bucket.deep_copy(), &object.key, encoding.clone())
+                        .map_err(|(command, error)| ReadError::S3(command, error))?,
+                ),
+            };
+            writer
+                .put_encoded(block)
+                .map_err(|(command, error)| ReadError::S3(command, error))
+        })?;
+        if !migrated {
+            // The rest of an encrypted object isn't read, so its download is dropped
+            continue;
+        }
+        current_state.finalize()?;
+        if let Some(writer) = migrated_writer {
+            writer
+                .finalize()
+                .map_err(|(command, error)| ReadError::S3(command, error))?;
+        }
+        info!(
+            "Encrypted the snapshot chunk {} written without encryption",
+            object.key
+        );
+    }
+    Ok(())
+}
+
 enum S3SnapshotWriterEvent {
     Chunk(Vec<Event>),
     Flush(OneShotSender<Result<(), WriteError>>),
@@ -477,14 +1198,14 @@ impl Drop for S3SnapshotWriter {
 }
 
 impl S3SnapshotWriter {
-    pub fn new(bucket: S3Bucket, chunks_root_path: &str) -> Self {
+    pub fn new(bucket: S3Bucket, chunks_root_path: &str, encoding: SnapshotEncoding) -> Self {
         let (chunk_events_sender, chunk_events_receiver) = mpsc::channel();
 
         let inner_chunks_root_path = chunks_root_path.to_string();
         let uploader_thread = thread::Builder::new()
             .name("pathway:s3_snapshot-bg-writer".to_string())
             .spawn(move || {
-                let mut s3_writer = S3Writer::new(bucket.deep_copy(), &format!("{}/{}", inner_chunks_root_path, current_unix_timestamp_ms())).expect("failed to construct s3 writer");
+                let mut s3_writer = S3Writer::new(bucket.deep_copy(), &format!("{}/{}", inner_chunks_root_path, current_unix_timestamp_ms()), encoding.clone()).expect("failed to construct s3 writer");
                 loop {
                     let event = chunk_events_receiver.recv().expect("unexpected termination for s3 events sender");
                     match event {
@@ -493,7 +1214,7 @@ impl S3SnapshotWriter {
                         }
                         S3SnapshotWriterEvent::Flush(sender) => {
                             let flush_result = s3_writer.finalize().map_err(|(command, s3_error)| WriteError::S3(command, s3_error));
-                            s3_writer = S3Writer::new(bucket.deep_copy(), &format!("{}/{}", inner_chunks_root_path, current_unix_timestamp_ms())).expect("failed to construct s3 writer");
+                            s3_writer = S3Writer::new(bucket.deep_copy(), &format!("{}/{}", inner_chunks_root_path, current_unix_timestamp_ms()), encoding.clone()).expect("failed to construct s3 writer");
                             if let Err(unsent_flush_result) = sender.send(flush_result) {
                                 error!("The receiver no longer waits for the result of this flush: {unsent_flush_result:?}");
                             }
@@ -539,6 +1260,321 @@ impl SnapshotWriter for S3SnapshotWriter {
     }
 }
 
+/// Counts the rows and the bytes written to a snapshot since the last commit of the
+/// frontiers.
+pub struct VolumeTrackingSnapshotWriter {
+    writer: Box<dyn SnapshotWriter>,
+    unpersisted_volume: SharedUnpersistedVolume,
+}
+
+impl VolumeTrackingSnapshotWriter {
+    pub fn new(
+        writer: Box<dyn SnapshotWriter>,
+        unpersisted_volume: SharedUnpersistedVolume,
+    ) -> Self {
+        Self {
+            writer,
+            unpersisted_volume,
+        }
+    }
+}
+
+impl SnapshotWriter for VolumeTrackingSnapshotWriter {
+    fn write(&mut self, event: &Event) -> Result<(), WriteError> {
+        self.writer.write(event)?;
+        if matches!(
+            event,
+            Event::Insert(_, _) | Event::Delete(_, _) | Event::Upsert(_, _)
+        ) {
+            let bytes = serialized_size(event).map_err(|e| WriteError::Bincode(*e))?;
+            self.unpersisted_volume.add(1, bytes);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> OneShotReceiver<Result<(), WriteError>> {
+        self.writer.flush()
+    }
+}
+
+/// The times of the chunks of the snapshot in `root_path` in the object storage.
+fn object_chunk_times(
+    storage: &SharedObjectStorage,
+    root_path: &str,
+) -> Result<Vec<u64>, ReadError> {
+    let prefix = format!("{root_path}/");
+    let mut times_advanced = Vec::new();
+    for key in storage.list(&prefix)? {
+        let Some(name) = key.strip_prefix(&prefix) else {
+            continue;
+        };
+        if let Ok(timestamp) = name.parse() {
+            times_advanced.push(timestamp);
+        } else {
+            error!("Unparsable timestamp. Full path: {key}");
+        }
+    }
+    times_advanced.sort_unstable();
+    Ok(times_advanced)
+}
+
+/// Reads a snapshot stored in an object storage, downloading its chunks one by one.
+#[allow(clippy::module_name_repetitions)]
+pub struct ObjectStorageSnapshotReader {
+    storage: SharedObjectStorage,
+    root_path: String,
+    reader: Option<EventReader<Cursor<Vec<u8>>>>,
+    next_object_idx: usize,
+    times_advanced: Vec<u64>,
+    encryption_key: Option<EncryptionKey>,
+}
+
+impl ObjectStorageSnapshotReader {
+    pub fn new(
+        storage: SharedObjectStorage,
+        path: &str,
+        encryption_key: Option<EncryptionKey>,
+    ) -> Result<Self, ReadError> {
+        Ok(Self {
+            times_advanced: object_chunk_times(&storage, path)?,
+            storage,
+            root_path: path.to_string(),
+            reader: None,
+            next_object_idx: 0,
+            encryption_key,
+        })
+    }
+
+    fn object_key(&self, index: usize) -> String {
+        format!("{}/{}", self.root_path, self.times_advanced[index])
+    }
+}
+
+impl SnapshotReaderImpl for ObjectStorageSnapshotReader {
+    fn read(&mut self) -> Result<Event, ReadError> {
+        loop {
+            match &mut self.reader {
+                Some(reader) => match reader.read() {
+                    Ok(entry) => return Ok(entry),
+                    Err(e) => match *e {
+                        BincodeError::Io(e) => {
+                            if !matches!(e.kind(), IoErrorKind::UnexpectedEof) {
+                                return Err(ReadError::Io(e));
+                            }
+                            self.reader = None;
+                            continue;
+                        }
+                        _ => return Err(ReadError::Bincode(*e)),
+                    },
+                },
+                None => {
+                    if self.next_object_idx >= self.times_advanced.len() {
+                        break;
+                    }
+                    let key = self.object_key(self.next_object_idx);
+                    self.reader = Some(EventReader::new(
+                        Cursor::new(self.storage.get(&key)?),
+                        self.encryption_key.clone(),
+                        object_chunk_name(&key),
+                    ));
+                    self.next_object_idx += 1;
+                }
+            }
+        }
+        Ok(Event::Finished)
+    }
+
+    fn truncate(&mut self) -> Result<(), ReadError> {
+        if let Some(reader) = &mut self.reader {
+            let key = format!(
+                "{}/{}",
+                self.root_path,
+                self.times_advanced[self.next_object_idx - 1]
+            );
+            let mut stable_position = usize::try_from(reader.reader.position()).unwrap();
+
+            // The read part of a block is stored again in place of the block
+            let read_part_of_block =
+                reader
+                    .encode_partially_read_block()
+                    .map(|(block_len, encoded_events)| {
+                        stable_position -= usize::try_from(block_len).unwrap();
+                        encoded_events
+                    });
+
+            let mut object = take(reader.reader.get_mut());
+            if stable_position < object.len() {
+                info!("Truncate: Shrink {key} to {stable_position} bytes");
+                object.truncate(stable_position);
+                object.extend(read_part_of_block.unwrap_or_default());
+                // The object is replaced as a whole, so it's never partially truncated
+                self.storage.put(&key, object)?;
+            }
+            self.reader = None;
+        }
+
+        for index in self.next_object_idx..self.times_advanced.len() {
+            let key = self.object_key(index);
+            info!("Truncate: Remove {key}");
+            self.storage.delete(&key)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An object of a snapshot, uploaded in the background. The result of the upload is
+/// sent to `sender`.
+struct ObjectUpload {
+    key: String,
+    data: Vec<u8>,
+    sender: OneShotSender<Result<(), WriteError>>,
+}
+
+/// Writes a snapshot to an object storage, uploading the events written until each
+/// flush as a new chunk.
+#[allow(clippy::module_name_repetitions)]
+pub struct ObjectStorageSnapshotWriter {
+    root_path: String,
+    encoding: SnapshotEncoding,
+    block: Vec<u8>,
+    chunk: Vec<u8>,
+    chunk_key: Option<String>,
+    next_block_index: u64,
+    last_chunk_time: u128,
+    uploads_sender: Option<Sender<ObjectUpload>>,
+    uploader_thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl ObjectStorageSnapshotWriter {
+    pub fn new(storage: SharedObjectStorage, root_path: &str, encoding: SnapshotEncoding) -> Self {
+        let (uploads_sender, uploads_receiver) = mpsc::channel::<ObjectUpload>();
+        let uploader_thread = thread::Builder::new()
+            .name("pathway:object_snapshot-bg-writer".to_string())
+            .spawn(move || {
+                for upload in uploads_receiver {
+                    let result = storage
+                        .put(&upload.key, upload.data)
+                        .map_err(WriteError::from);
+                    if let Err(unsent_flush_result) = upload.sender.send(result) {
+                        error!("The receiver no longer waits for the result of this flush: {unsent_flush_result:?}");
+                    }
+                }
+            })
+            .expect("object storage thread creation failed");
+
+        Self {
+            root_path: root_path.to_string(),
+            encoding,
+            block: Vec::new(),
+            chunk: Vec::new(),
+            chunk_key: None,
+            next_block_index: 0,
+            last_chunk_time: 0,
+            uploads_sender: Some(uploads_sender),
+            uploader_thread: Some(uploader_thread),
+        }
+    }
+
+    fn write_block(&mut self) {
+        if self.block.is_empty() {
+            return;
+        }
+        // The name of the chunk is a part of its encrypted blocks, so it's chosen first.
+        // The chunks are ordered by their names, so they never get the same one
+        let chunk_key = self.chunk_key.get_or_insert_with(|| {
+            self.last_chunk_time = current_unix_timestamp_ms().max(self.last_chunk_time + 1);
+            format!("{}/{}", self.root_path, self.last_chunk_time)
+        });
+        let block = encode_block(
+            &take(&mut self.block),
+            &self.encoding,
+            &object_chunk_name(chunk_key),
+            self.next_block_index,
+        );
+        self.next_block_index += 1;
+        self.chunk.extend(block);
+    }
+}
+
+impl Drop for ObjectStorageSnapshotWriter {
+    fn drop(&mut self) {
+        // The flushed chunks finish uploading, while the events written after the last
+        // flush are dropped, as by the S3 writer
+        drop(self.uploads_sender.take());
+        if let Some(uploader_thread) = self.uploader_thread.take() {
+            if let Err(e) = uploader_thread.join() {
+                error!("Failed to join object storage snapshot uploader thread: {e:?}");
+            }
+        }
+    }
+}
+
+impl SnapshotWriter for ObjectStorageSnapshotWriter {
+    fn write(&mut self, event: &Event) -> Result<(), WriteError> {
+        serialize_into(&mut self.block, &event).map_err(|e| WriteError::Bincode(*e))?;
+        if self.block.len() >= MAX_UNCOMPRESSED_BLOCK_LEN {
+            self.write_block();
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> OneShotReceiver<Result<(), WriteError>> {
+        let (sender, receiver) = oneshot::channel();
+        self.write_block();
+        let Some(key) = self.chunk_key.take() else {
+            // Nothing was written since the last flush
+            sender
+                .send(Ok(()))
+                .expect("the receiver should still be alive");
+            return receiver;
+        };
+        self.next_block_index = 0;
+        self.uploads_sender
+            .as_ref()
+            .unwrap()
+            .send(ObjectUpload {
+                key,
+                data: take(&mut self.chunk),
+                sender,
+            })
+            .expect("upload submission should not fail");
+        receiver
+    }
+}
+
+/// Encrypts the chunks of the snapshot in `root_path` in the object storage written
+/// before the encryption was enabled, replacing each of them with a copy stored with
+/// `encoding`.
+pub fn encrypt_object_storage_snapshot(
+    storage: &SharedObjectStorage,
+    root_path: &str,
+    encoding: &SnapshotEncoding,
+) -> Result<(), ReadError> {
+    let Some(key) = &encoding.encryption_key else {
+        return Ok(());
+    };
+    for time in object_chunk_times(storage, root_path)? {
+        let object_key = format!("{root_path}/{time}");
+        let reader = EventReader::new_migrated(
+            Cursor::new(storage.get(&object_key)?),
+            key.clone(),
+            object_chunk_name(&object_key),
+        );
+        let mut migrated_object = Vec::new();
+        let migrated = migrate_chunk(reader, encoding, |block| {
+            migrated_object.extend(block);
+            Ok(())
+        })?;
+        if migrated {
+            // The object is replaced as a whole, once it's complete
+            storage.put(&object_key, migrated_object)?;
+            info!("Encrypted the snapshot chunk {object_key} written without encryption");
+        }
+    }
+    Ok(())
+}
+
 pub struct MockSnapshotReader {
     events: Box<dyn Iterator<Item = Event>>,
 }
@@ -573,40 +1609,62 @@ impl SnapshotReaderImpl for MockSnapshotReader {
 pub struct SnapshotReader {
     reader_impl: Box<dyn SnapshotReaderImpl>,
     threshold_time: u64,
+    replay_until: Option<u64>,
     entries_read: usize,
 }
 
 impl SnapshotReader {
+    /// Creates a reader returning the events persisted for the times before
+    /// `threshold_time`, truncating the rest of the snapshot.
+    ///
+    /// If `replay_until` is set, the reader stops after the events of the times up
+    /// to `replay_until` and the snapshot is never truncated.
     pub fn new(
         mut reader_impl: Box<dyn SnapshotReaderImpl>,
         threshold_time: u64,
+        replay_until: Option<u64>,
     ) -> Result<Self, ReadError> {
         if threshold_time == 0 {
             info!("No time has been advanced in the previous run, therefore no data read from the snapshot");
-            if let Err(e) = reader_impl.truncate() {
-                error!("Failed to truncate the snapshot, the next re-run may provide incorrect results: {e}");
-                return Err(e);
+            if replay_until.is_none() {
+                if let Err(e) = reader_impl.truncate() {
+                    error!("Failed to truncate the snapshot, the next re-run may provide incorrect results: {e}");
+                    return Err(e);
+                }
             }
         }
 
         Ok(Self {
             reader_impl,
             threshold_time,
+            replay_until,
             entries_read: 0,
         })
     }
 
     pub fn read(&mut self) -> Result<Event, ReadError> {
+        if self.replay_until.is_some() && self.threshold_time == 0 {
+            return Ok(Event::Finished);
+        }
         let event = self.reader_impl.read()?;
         if let Event::AdvanceTime(new_time) = event {
             if self.reader_impl.check_threshold_from_metadata() && new_time >= self.threshold_time {
-                if let Err(e) = self.reader_impl.truncate() {
-                    error!("Failed to truncate the snapshot, the next re-run may provide incorrect results: {e}");
-                    return Err(e);
+                if self.replay_until.is_none() {
+                    if let Err(e) = self.reader_impl.truncate() {
+                        error!("Failed to truncate the snapshot, the next re-run may provide incorrect results: {e}");
+                        return Err(e);
+                    }
                 }
                 info!("Reached the greater logical time than preserved ({new_time}). Exiting the rewind after reading {} entries", self.entries_read);
                 return Ok(Event::Finished);
             }
+            if self
+                .replay_until
+                .is_some_and(|replay_until| new_time > replay_until)
+            {
+                info!("Reached the greater logical time than requested ({new_time}). Exiting the rewind after reading {} entries", self.entries_read);
+                return Ok(Event::Finished);
+            }
         }
         self.entries_read += 1;
         Ok(event)
@@ -0,0 +1,280 @@
+// Copyright © 2024 Pathway
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+use arc_swap::ArcSwapOption;
+use log::{info, warn};
+use serde::Serialize;
+
+use super::{Graph, ProberStats};
+
+const ALERT_CHECK_PERIOD: Duration = Duration::from_secs(1);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The limits of the health of the computation. An alert is raised when any of them
+/// is exceeded and resolved once it's not.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlertThresholds {
+    /// The maximal latency of the output, i.e. how far behind the wall clock the time
+    /// the output has advanced to may be.
+    pub max_output_staleness: Option<Duration>,
+    /// The maximal number of the messages (or the files, for the file sources) not read
+    /// yet by a connector.
+    pub max_source_lag: Option<u64>,
+    /// The maximal time since the last checkpoint of the persisted state.
+    pub max_checkpoint_age: Option<Duration>,
+    /// The address the alerts are posted to as JSON, when raised and when resolved.
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Alert {
+    pub name: &'static str,
+    /// The connector the alert concerns, if any.
+    pub connector: Option<String>,
+    pub value: u64,
+    pub threshold: u64,
+}
+
+impl Alert {
+    fn key(&self) -> (&'static str, Option<String>) {
+        (self.name, self.connector.clone())
+    }
+}
+
+/// The alerts of a process: the ones active now and the numbers of the times each alert
+/// was raised. They are exposed by the http server.
+#[derive(Debug, Default)]
+pub struct AlertsState {
+    pub active: Vec<Alert>,
+    pub raised: HashMap<&'static str, u64>,
+}
+
+pub type SharedAlerts = Arc<Mutex<AlertsState>>;
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+impl AlertThresholds {
+    /// Returns the alerts for the current statistics of the computation.
+    fn check(&self, stats: &ProberStats, now: SystemTime) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        if let Some(max_output_staleness) = self.max_output_staleness {
+            let threshold = duration_ms(max_output_staleness);
+            let staleness = stats
+                .output_stats
+                .latency(now)
+                .filter(|staleness| *staleness > threshold && !stats.output_stats.done);
+            if let Some(staleness) = staleness {
+                alerts.push(Alert {
+                    name: "output_staleness",
+                    connector: None,
+                    value: staleness,
+                    threshold,
+                });
+            }
+        }
+        if let Some(threshold) = self.max_source_lag {
+            for (name, connector_stats) in &stats.connector_stats {
+                let lag = connector_stats
+                    .lag_messages
+                    .or(connector_stats.backlog_files)
+                    .filter(|lag| *lag > threshold && !connector_stats.finished);
+                if let Some(lag) = lag {
+                    alerts.push(Alert {
+                        name: "source_lag",
+                        connector: Some(name.clone()),
+                        value: lag,
+                        threshold,
+                    });
+                }
+            }
+        }
+        if let Some(max_checkpoint_age) = self.max_checkpoint_age {
+            let threshold = duration_ms(max_checkpoint_age);
+            let age = stats
+                .persistence_stats
+                .and_then(|persistence_stats| persistence_stats.last_checkpoint_age(now))
+                .filter(|age| *age > threshold);
+            if let Some(age) = age {
+                alerts.push(Alert {
+                    name: "checkpoint_age",
+                    connector: None,
+                    value: age,
+                    threshold,
+                });
+            }
+        }
+        alerts
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct AlertEvent<'a> {
+    state: &'static str,
+    #[serde(flatten)]
+    alert: &'a Alert,
+}
+
+/// Posts the events of the alerts to the webhook. A failure is only logged, as the alerts
+/// are logged anyway.
+struct Webhook {
+    url: String,
+    client: reqwest::Client,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl Webhook {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            client: reqwest::Client::new(),
+            runtime: tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+        }
+    }
+
+    fn post(&self, event: &AlertEvent) {
+        let request = self
+            .client
+            .post(&self.url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .json(event);
+        let result = self.runtime.block_on(async {
+            request
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+        });
+        if let Err(e) = result {
+            warn!("Failed to post the alert to the webhook: {e}");
+        }
+    }
+}
+
+/// Raises and resolves the alerts, logging them and posting them to the webhook.
+struct AlertMonitor {
+    thresholds: AlertThresholds,
+    alerts: SharedAlerts,
+    webhook: Option<Webhook>,
+}
+
+impl AlertMonitor {
+    fn update(&mut self, stats: &ProberStats) {
+        let current = self.thresholds.check(stats, SystemTime::now());
+        let previous = {
+            let mut alerts = self.alerts.lock().unwrap();
+            for alert in &current {
+                if !alerts
+                    .active
+                    .iter()
+                    .any(|active| active.key() == alert.key())
+                {
+                    *alerts.raised.entry(alert.name).or_default() += 1;
+                }
+            }
+            std::mem::replace(&mut alerts.active, current.clone())
+        };
+        // Emitting without the lock, as posting to the webhook may take a while
+        for alert in &current {
+            if !previous.iter().any(|active| active.key() == alert.key()) {
+                self.emit("raised", alert);
+            }
+        }
+        for alert in &previous {
+            if !current.iter().any(|active| active.key() == alert.key()) {
+                self.emit("resolved", alert);
+            }
+        }
+    }
+
+    fn emit(&self, state: &'static str, alert: &Alert) {
+        let event = AlertEvent { state, alert };
+        let description = serde_json::to_string(&event).unwrap();
+        if state == "raised" {
+            warn!("Alert raised: {description}");
+        } else {
+            info!("Alert resolved: {description}");
+        }
+        if let Some(webhook) = &self.webhook {
+            webhook.post(&event);
+        }
+    }
+}
+
+/// Checks the alerts periodically in a separate thread, for as long as it's alive.
+pub struct Runner {
+    should_finish: Arc<AtomicBool>,
+    alerting_thread_handle: Option<JoinHandle<()>>,
+}
+
+impl Runner {
+    fn run(stats: &Arc<ArcSwapOption<ProberStats>>, mut monitor: AlertMonitor) -> Runner {
+        let should_finish = Arc::new(AtomicBool::new(false));
+        let thread_handle = {
+            let should_finish = Arc::clone(&should_finish);
+            let stats = Arc::clone(stats);
+            thread::Builder::new()
+                .name("pathway:alerting".to_owned())
+                .spawn(move || {
+                    while !should_finish.load(Ordering::Relaxed) {
+                        if let Some(ref stats) = *stats.load() {
+                            monitor.update(stats);
+                        }
+                        thread::park_timeout(ALERT_CHECK_PERIOD);
+                    }
+                })
+                .expect("alerting thread creation failed")
+        };
+        Runner {
+            should_finish,
+            alerting_thread_handle: Some(thread_handle),
+        }
+    }
+}
+
+impl Drop for Runner {
+    fn drop(&mut self) {
+        self.should_finish.store(true, Ordering::Relaxed);
+        let alerting_thread_handle = self.alerting_thread_handle.take().unwrap();
+        alerting_thread_handle.thread().unpark();
+        alerting_thread_handle
+            .join()
+            .expect("alerting thread failed");
+    }
+}
+
+pub fn maybe_run_alerting(
+    thresholds: Option<&AlertThresholds>,
+    graph: &dyn Graph,
+    alerts: SharedAlerts,
+) -> Option<Runner> {
+    let thresholds = thresholds?;
+    if graph.worker_index() != 0 {
+        return None;
+    }
+    let stats_shared = Arc::new(ArcSwapOption::from(None));
+    let monitor = AlertMonitor {
+        thresholds: thresholds.clone(),
+        alerts,
+        webhook: thresholds.webhook_url.clone().map(Webhook::new),
+    };
+    let alerting_runner = Runner::run(&stats_shared, monitor);
+
+    graph
+        .attach_prober(
+            Box::new(move |prober_stats| stats_shared.store(Some(Arc::new(prober_stats)))),
+            false,
+            true,
+        )
+        .expect("Failed to start alerting");
+
+    Some(alerting_runner)
+}
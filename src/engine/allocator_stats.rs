@@ -0,0 +1,186 @@
+// Copyright © 2024 Pathway
+
+use std::ffi::CString;
+use std::mem::size_of;
+use std::ptr;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{Builder, JoinHandle};
+use std::time::Duration;
+
+use log::info;
+
+/// Statistics of the memory allocated by jemalloc, in bytes. See the `stats.*` entries
+/// of the jemalloc manual for the details.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct AllocatorStats {
+    /// Memory allocated by the application.
+    pub allocated: u64,
+    /// Memory in the pages with allocations, including the unused parts of the pages.
+    pub active: u64,
+    /// Memory used by jemalloc for its own structures.
+    pub metadata: u64,
+    /// Memory in the physically resident pages mapped by jemalloc.
+    pub resident: u64,
+    /// Memory in the chunks mapped by jemalloc.
+    pub mapped: u64,
+    /// Memory retained by jemalloc instead of being returned to the system.
+    pub retained: u64,
+    pub arenas: Vec<ArenaStats>,
+}
+
+/// Statistics of an arena of jemalloc, in bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaStats {
+    pub index: u32,
+    pub allocated: u64,
+    pub active: u64,
+    /// Memory in the unused pages not purged yet.
+    pub dirty: u64,
+    /// Memory in the unused pages purged lazily.
+    pub muzzy: u64,
+}
+
+/// The part of the active memory not allocated by the application.
+#[allow(clippy::cast_precision_loss)]
+fn fragmentation(allocated: u64, active: u64) -> f64 {
+    if active == 0 {
+        0.0
+    } else {
+        active.saturating_sub(allocated) as f64 / active as f64
+    }
+}
+
+impl AllocatorStats {
+    pub fn fragmentation(&self) -> f64 {
+        fragmentation(self.allocated, self.active)
+    }
+}
+
+impl ArenaStats {
+    pub fn fragmentation(&self) -> f64 {
+        fragmentation(self.allocated, self.active)
+    }
+}
+
+fn read_value<T: Copy + Default>(name: &str) -> Option<T> {
+    let name = CString::new(name).ok()?;
+    let mut value = T::default();
+    let mut length = size_of::<T>();
+    // SAFETY: `value` has the size passed in `length` and nothing is written to jemalloc
+    let result = unsafe {
+        jemalloc_sys::mallctl(
+            name.as_ptr(),
+            ptr::addr_of_mut!(value).cast(),
+            &mut length,
+            ptr::null_mut(),
+            0,
+        )
+    };
+    (result == 0 && length == size_of::<T>()).then_some(value)
+}
+
+fn read_size(name: &str) -> Option<u64> {
+    read_value::<usize>(name).and_then(|size| size.try_into().ok())
+}
+
+/// jemalloc caches the statistics, they are refreshed by advancing its epoch.
+fn refresh() -> Option<()> {
+    let name = CString::new("epoch").unwrap();
+    let mut epoch: u64 = 1;
+    let mut length = size_of::<u64>();
+    // SAFETY: `epoch` has the size passed in `length` both for reading and writing
+    let result = unsafe {
+        jemalloc_sys::mallctl(
+            name.as_ptr(),
+            ptr::addr_of_mut!(epoch).cast(),
+            &mut length,
+            ptr::addr_of_mut!(epoch).cast(),
+            size_of::<u64>(),
+        )
+    };
+    (result == 0).then_some(())
+}
+
+fn read_arena(index: u32, page_size: u64) -> Option<ArenaStats> {
+    let prefix = format!("stats.arenas.{index}");
+    let small_allocated = read_size(&format!("{prefix}.small.allocated"))?;
+    let large_allocated = read_size(&format!("{prefix}.large.allocated"))?;
+    Some(ArenaStats {
+        index,
+        allocated: small_allocated + large_allocated,
+        active: read_size(&format!("{prefix}.pactive"))? * page_size,
+        dirty: read_size(&format!("{prefix}.pdirty"))? * page_size,
+        muzzy: read_size(&format!("{prefix}.pmuzzy"))? * page_size,
+    })
+}
+
+/// Reads the current statistics of the allocator. They are not available with the standard
+/// allocator.
+pub fn read() -> Option<AllocatorStats> {
+    if cfg!(feature = "standard-allocator") {
+        return None;
+    }
+    refresh()?;
+    let page_size = read_size("arenas.page")?;
+    let arenas_count: u32 = read_value("arenas.narenas")?;
+    // The arenas not initialized yet have no statistics
+    let arenas = (0..arenas_count)
+        .filter_map(|index| read_arena(index, page_size))
+        .filter(|arena| arena.active > 0)
+        .collect();
+    Some(AllocatorStats {
+        allocated: read_size("stats.allocated")?,
+        active: read_size("stats.active")?,
+        metadata: read_size("stats.metadata")?,
+        resident: read_size("stats.resident")?,
+        mapped: read_size("stats.mapped")?,
+        retained: read_size("stats.retained")?,
+        arenas,
+    })
+}
+
+/// Logs the statistics of the allocator periodically, for as long as it's alive.
+#[allow(clippy::module_name_repetitions)]
+pub struct AllocatorStatsLogger {
+    logging_thread_handle: Option<JoinHandle<()>>,
+    stop_transmitter: Option<Sender<()>>,
+}
+
+impl AllocatorStatsLogger {
+    pub fn start(period: Duration) -> Self {
+        let (stop_transmitter, stop_receiver) = mpsc::channel();
+        let logging_thread_handle = Builder::new()
+            .name("pathway:allocator_stats".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stop_receiver.recv_timeout(period) {
+                    if let Some(stats) = read() {
+                        info!(
+                            "Memory: {} B allocated, {} B active, {} B resident, {} B metadata, {:.1}% fragmentation",
+                            stats.allocated,
+                            stats.active,
+                            stats.resident,
+                            stats.metadata,
+                            stats.fragmentation() * 100.0,
+                        );
+                    }
+                }
+            })
+            .expect("allocator stats thread creation failed");
+        Self {
+            logging_thread_handle: Some(logging_thread_handle),
+            stop_transmitter: Some(stop_transmitter),
+        }
+    }
+}
+
+impl Drop for AllocatorStatsLogger {
+    fn drop(&mut self) {
+        drop(self.stop_transmitter.take());
+        self.logging_thread_handle
+            .take()
+            .unwrap()
+            .join()
+            .expect("allocator stats thread failed");
+    }
+}
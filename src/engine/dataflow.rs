@@ -10,7 +10,7 @@ pub mod shard;
 
 use crate::connectors::adaptors::{GenericValues, ValuesSessionAdaptor};
 use crate::connectors::data_format::{Formatter, Parser};
-use crate::connectors::data_storage::{ReaderBuilder, Writer};
+use crate::connectors::data_storage::{ReaderBuilder, SharedWriter, Writer};
 use crate::connectors::monitoring::{ConnectorMonitor, ConnectorStats, OutputConnectorStats};
 use crate::connectors::ARTIFICIAL_TIME_ON_REWIND_START;
 use crate::connectors::{Connector, PersistenceMode, SnapshotAccess};
@@ -19,29 +19,31 @@ use crate::engine::dataflow::operators::time_column::{
     Epsilon, TimeColumnForget, TimeColumnFreeze,
 };
 
-use crate::engine::value::HashInto;
+use crate::engine::value::{HashInto, Hasher};
 use crate::persistence::config::{PersistenceManagerConfig, PersistenceManagerOuterConfig};
-use crate::persistence::sync::SharedWorkersPersistenceCoordinator;
+use crate::persistence::schema::{InputSchema, SchemaColumn};
+use crate::persistence::sync::{SharedPersistenceStats, SharedWorkersPersistenceCoordinator};
 use crate::persistence::tracker::SingleWorkerPersistentStorage;
 use crate::persistence::{ExternalPersistentId, IntoPersistentId};
 
 use std::any::type_name;
 use std::borrow::{Borrow, Cow};
-use std::cell::RefCell;
-use std::cmp::min;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::iter::once;
 use std::marker::PhantomData;
+use std::mem::size_of;
 use std::ops::{ControlFlow, Deref};
 use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Mutex;
 use std::sync::{mpsc, Arc};
-use std::thread::{Builder, JoinHandle};
+use std::sync::{Mutex, RwLock};
+use std::thread::{self, Builder, JoinHandle};
 use std::time::{Duration, SystemTime};
 use std::{env, slice};
 
@@ -60,45 +62,66 @@ use differential_dataflow::{AsCollection as _, Data};
 use futures::future::BoxFuture;
 use id_arena::Arena;
 use itertools::{process_results, Itertools};
-use log::{info, warn};
+use log::{debug, info, warn};
 use ndarray::ArrayD;
 use once_cell::unsync::{Lazy, OnceCell};
 use pyo3::PyObject;
 use serde::{Deserialize, Serialize};
+use timely::dataflow::operators::feedback::Handle as FeedbackHandle;
 use timely::dataflow::operators::probe::Handle as ProbeHandle;
 use timely::dataflow::operators::ToStream as _;
-use timely::dataflow::operators::{Filter, Inspect, Probe};
+use timely::dataflow::operators::{
+    Concatenate, ConnectLoop, Exchange, Feedback, Filter, Inspect, Map, Probe,
+};
 use timely::dataflow::scopes::Child;
+use timely::dataflow::Stream;
 use timely::order::{Product, TotalOrder};
 use timely::progress::timestamp::Refines;
 use timely::progress::{PathSummary, Timestamp};
+use timely::worker::AsWorker as _;
 use timely::{execute, CommunicationConfig, Config, WorkerConfig};
-use xxhash_rust::xxh3::Xxh3 as Hasher;
 
 use self::complex_columns::complex_columns;
 use self::maybe_total::{MaybeTotalScope, MaybeTotalTimestamp, NotTotal, Total};
-use self::operators::output::{ConsolidateForOutput, OutputBatch};
+use self::operators::cumulative::Cumulative;
+use self::operators::deduplicate::DeduplicateWithin;
+use self::operators::expire::ExpireAfter;
+use self::operators::iteration::{InspectIterations, IterationEvent};
+use self::operators::output::{ConsolidateForOutput, HoldUntil, OutputBatch};
 use self::operators::prev_next::add_prev_next_pointers;
+use self::operators::rank::Rank;
+use self::operators::session_window::{within_gap, SessionWindows};
 use self::operators::stateful_reduce::StatefulReduce;
 use self::operators::time_column::{MaxTimestamp, SelfCompactionTime, TimeColumnBuffer};
+use self::operators::top_n::TopN;
 use self::operators::{ArrangeWithTypes, MapWrapped};
 use self::operators::{MaybeTotal, Reshard};
 use self::shard::Shard;
+use super::alerts::{maybe_run_alerting, AlertThresholds, SharedAlerts};
+use super::allocator_stats::AllocatorStatsLogger;
 use super::error::{DynError, DynResult, Trace};
+use super::expression::columnar::{ColumnarBatch, RowBuffer, COLUMNAR_BATCH_SIZE};
 use super::expression::AnyExpression;
-use super::graph::{DataRow, SubscribeCallbacks};
+use super::graph::{DataRow, LatencyHistogram, SharedLatencyHistogram, SubscribeCallbacks};
 use super::http_server::maybe_run_http_server_thread;
+use super::log_context;
+use super::pending_work::{PendingWorkTracker, SharedPendingRecords};
 use super::progress_reporter::{maybe_run_reporter, MonitoringLevel};
 use super::reduce::{
     AnyReducer, ArgMaxReducer, ArgMinReducer, ArraySumReducer, CountReducer, FloatSumReducer,
-    IntSumReducer, MaxReducer, MinReducer, ReducerImpl, SemigroupReducerImpl, SortedTupleReducer,
-    StatefulReducer, TupleReducer, UniqueReducer,
+    IntSumReducer, MaxReducer, MinReducer, ReducerImpl, RetractableReducer, SemigroupReducerImpl,
+    SortedTupleReducer, StatefulReducer, TupleReducer, UniqueReducer,
 };
 use super::report_error::{ReportError, ReportErrorExt, SpawnWithReporter, UnwrapWithReporter};
+use super::served_table::{served_table_callbacks, ServedTable, SharedServedTables};
+use super::shutdown::SharedShutdownRequest;
+use super::telemetry::{EpochTracer, Telemetry};
 use super::{
-    BatchWrapper, ColumnHandle, ColumnPath, ColumnProperties, ComplexColumn, Error, Expression,
-    ExpressionData, Graph, IterationLogic, IxKeyPolicy, JoinType, Key, LegacyTable, OperatorStats,
-    ProberStats, Reducer, ReducerData, Result, TableHandle, TableProperties, UniverseHandle, Value,
+    BatchWrapper, ColumnHandle, ColumnPath, ColumnProperties, ComplexColumn, EdgeStats, Error,
+    Expression, ExpressionData, Graph, IterationLimit, IterationLogic, IxKeyPolicy, JoinType, Key,
+    KeyHashing, LegacyTable, OperatorNode, OperatorStateLimit, OperatorStats, PersistenceStats,
+    ProberStats, Reducer, ReducerData, Result, TableHandle, TableProperties, Type, UniverseHandle,
+    Value, KEY_BITS,
 };
 
 pub type WakeupReceiver = Receiver<Box<dyn FnOnce() -> DynResult<()> + Send + Sync + 'static>>;
@@ -496,6 +519,58 @@ impl<S: MaybeTotalScope> Table<S> {
     }
 }
 
+/// Approximate size of the state of an operator in a worker, with the number of the row
+/// changes it has computed.
+///
+/// Stateful operators keep their inputs arranged, so the state of the whole computation
+/// is approximated by summing the sizes of the rows of the tables computed by each
+/// operator.
+#[derive(Debug, Default)]
+struct OperatorStateSize {
+    bytes: Cell<isize>,
+    limit_exceeded: Cell<bool>,
+    rows_processed: Cell<u64>,
+}
+
+impl OperatorStateSize {
+    fn get(&self) -> u64 {
+        self.bytes.get().try_into().unwrap_or(0)
+    }
+
+    fn rows_processed(&self) -> u64 {
+        self.rows_processed.get()
+    }
+
+    fn update(
+        &self,
+        delta: isize,
+        operator_id: usize,
+        limit: Option<OperatorStateLimit>,
+        error_reporter: &ErrorReporter,
+    ) {
+        self.rows_processed.set(self.rows_processed.get() + 1);
+        self.bytes.set(self.bytes.get() + delta);
+        let Some(limit) = limit else {
+            return;
+        };
+        let size = self.get();
+        if size <= limit.bytes {
+            self.limit_exceeded.set(false);
+        } else if !self.limit_exceeded.replace(true) {
+            let error = Error::OperatorStateLimitExceeded {
+                operator_id,
+                size,
+                limit: limit.bytes,
+            };
+            if limit.fail {
+                error_reporter.report_and_panic(error);
+            }
+            let _log_context = log_context::enter_operator(operator_id);
+            warn!("{error}");
+        }
+    }
+}
+
 struct Prober {
     input_time: Option<u64>,
     input_time_changed: Option<SystemTime>,
@@ -504,6 +579,9 @@ struct Prober {
     intermediate_probes_required: bool,
     run_callback_every_time: bool,
     stats: HashMap<usize, OperatorStats>,
+    persistence_stats: Option<PersistenceStats>,
+    output_latencies: Vec<(String, LatencyHistogram)>,
+    edges_stats: Vec<EdgeStats>,
     callback: Box<dyn FnMut(ProberStats)>,
 }
 
@@ -521,12 +599,22 @@ impl Prober {
             intermediate_probes_required,
             run_callback_every_time,
             stats: HashMap::new(),
+            persistence_stats: None,
+            output_latencies: Vec::new(),
+            edges_stats: Vec::new(),
             callback,
         }
     }
 
     #[allow(clippy::cast_possible_truncation)]
-    fn create_stats(probe: &ProbeHandle<u64>, input_time: Option<u64>) -> OperatorStats {
+    fn create_stats(
+        probe: &ProbeHandle<u64>,
+        input_time: Option<u64>,
+        state_size: Option<&OperatorStateSize>,
+        pending_records: Option<u64>,
+    ) -> OperatorStats {
+        let rows_processed = state_size.map_or(0, OperatorStateSize::rows_processed);
+        let state_size = state_size.map(OperatorStateSize::get);
         let frontier = probe.with_frontier(|frontier| frontier.as_option().copied());
         if let Some(timestamp) = frontier {
             OperatorStats {
@@ -539,23 +627,35 @@ impl Prober {
                     }
                 }),
                 done: false,
+                state_size,
+                pending_records,
+                rows_processed,
+                last_activity: None,
             }
         } else {
             OperatorStats {
                 time: None,
                 lag: None,
                 done: true,
+                state_size,
+                pending_records,
+                rows_processed,
+                last_activity: None,
             }
         }
     }
 
-    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_possible_truncation, clippy::too_many_arguments)]
     fn update(
         &mut self,
         input_probe: &ProbeHandle<u64>,
         output_probe: &ProbeHandle<u64>,
         intermediate_probes: &HashMap<usize, ProbeHandle<u64>>,
+        state_sizes: &HashMap<usize, Rc<OperatorStateSize>>,
         connector_monitors: &[Rc<RefCell<ConnectorMonitor>>],
+        persistence_stats: Option<&SharedPersistenceStats>,
+        output_latencies: &[(String, SharedLatencyHistogram)],
+        pending_work: Option<&PendingWorkTracker>,
     ) {
         let now = Lazy::new(SystemTime::now);
 
@@ -578,9 +678,15 @@ impl Prober {
         if self.intermediate_probes_required {
             for (id, probe) in intermediate_probes {
                 let new_time = probe.with_frontier(|frontier| frontier.as_option().copied());
+                let new_state_size = state_sizes.get(id).map(|size| size.get());
+                let new_rows_processed =
+                    state_sizes.get(id).map_or(0, |size| size.rows_processed());
                 let stat = self.stats.get(id);
                 if let Some(stat) = stat {
-                    if new_time != stat.time {
+                    if new_time != stat.time
+                        || new_state_size != stat.state_size
+                        || new_rows_processed != stat.rows_processed
+                    {
                         changed = true;
                     }
                 } else {
@@ -589,6 +695,27 @@ impl Prober {
             }
         }
 
+        let new_persistence_stats = persistence_stats.map(|stats| *stats.lock().unwrap());
+        if new_persistence_stats != self.persistence_stats {
+            self.persistence_stats = new_persistence_stats;
+            changed = true;
+        }
+
+        let new_output_latencies: Vec<(String, LatencyHistogram)> = output_latencies
+            .iter()
+            .map(|(name, histogram)| (name.clone(), histogram.lock().unwrap().clone()))
+            .collect();
+        if new_output_latencies != self.output_latencies {
+            self.output_latencies = new_output_latencies;
+            changed = true;
+        }
+
+        let new_edges_stats = pending_work.map_or_else(Vec::new, PendingWorkTracker::edges_stats);
+        if new_edges_stats != self.edges_stats {
+            self.edges_stats = new_edges_stats;
+            changed = true;
+        }
+
         let connector_stats: Vec<(String, ConnectorStats)> = connector_monitors
             .iter()
             .map(|connector_monitor| {
@@ -599,17 +726,43 @@ impl Prober {
 
         if changed || self.run_callback_every_time {
             if self.intermediate_probes_required {
+                let now_ms = now
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .ok()
+                    .and_then(|duration| u64::try_from(duration.as_millis()).ok());
                 for (id, probe) in intermediate_probes {
-                    self.stats
-                        .insert(*id, Self::create_stats(probe, self.input_time));
+                    let state_size = state_sizes.get(id).map(Rc::as_ref);
+                    let pending_records = pending_work.map(|_| {
+                        self.edges_stats
+                            .iter()
+                            .filter(|edge| edge.target_operator == Some(*id))
+                            .map(|edge| edge.pending_records)
+                            .sum()
+                    });
+                    let mut stats =
+                        Self::create_stats(probe, self.input_time, state_size, pending_records);
+                    stats.last_activity = match self.stats.get(id) {
+                        Some(previous)
+                            if previous.time == stats.time
+                                && previous.rows_processed == stats.rows_processed =>
+                        {
+                            previous.last_activity
+                        }
+                        _ if stats.time.is_none() && stats.rows_processed == 0 => None,
+                        _ => now_ms,
+                    };
+                    self.stats.insert(*id, stats);
                 }
             }
 
             let prober_stats = ProberStats {
-                input_stats: Self::create_stats(input_probe, self.input_time),
-                output_stats: Self::create_stats(output_probe, self.input_time),
+                input_stats: Self::create_stats(input_probe, self.input_time, None, None),
+                output_stats: Self::create_stats(output_probe, self.input_time, None, None),
                 operators_stats: self.stats.clone(),
                 connector_stats,
+                persistence_stats: self.persistence_stats,
+                output_latencies: self.output_latencies.clone(),
+                edges_stats: self.edges_stats.clone(),
             };
 
             (self.callback)(prober_stats);
@@ -649,10 +802,26 @@ struct DataflowGraphInner<S: MaybeTotalScope> {
     output_probe: ProbeHandle<S::Timestamp>,
     probers: Vec<Prober>,
     probes: HashMap<usize, ProbeHandle<S::Timestamp>>,
+    state_sizes: HashMap<usize, Rc<OperatorStateSize>>,
     ignore_asserts: bool,
     persistence_config: Option<PersistenceManagerConfig>,
     worker_persistent_storage: WorkerPersistentStorage,
     global_persistent_storage: GlobalPersistentStorage,
+    output_barrier: Option<OutputBarrier<S>>,
+    served_tables: SharedServedTables,
+    shutdown_request: SharedShutdownRequest,
+    output_latencies: Vec<(String, SharedLatencyHistogram)>,
+    pending_work: Option<Rc<PendingWorkTracker>>,
+}
+
+/// Frontier shared by all outputs of the graph.
+///
+/// The barrier stream gets its frontier from the inputs of all outputs, which are only
+/// known once the whole graph is built, so it is closed with [`FeedbackHandle`].
+struct OutputBarrier<S: MaybeTotalScope> {
+    handle: FeedbackHandle<S, ()>,
+    barrier: Stream<S, ()>,
+    inputs: Vec<Stream<S, ()>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -745,6 +914,48 @@ impl<S: MaybeTotalScope> TupleCollection<S> {
         }
     }
 
+    /// Maps batches of at most `max_batch_size` rows at once. The function returns
+    /// the results for the values of the rows in their order.
+    #[track_caller]
+    fn map_batched_named<D: Data>(
+        &self,
+        name: &str,
+        max_batch_size: usize,
+        mut logic: impl FnMut(&[&[Value]]) -> Vec<D> + 'static,
+    ) -> Collection<S, (Key, D)> {
+        fn with_keys<T: AsValueSlice, D>(
+            batch: &[(Key, T)],
+            logic: &mut impl FnMut(&[&[Value]]) -> Vec<D>,
+        ) -> Vec<(Key, D)> {
+            let rows: Vec<_> = batch
+                .iter()
+                .map(|(_key, values)| values.as_value_slice())
+                .collect();
+            let results = logic(&rows);
+            batch
+                .iter()
+                .map(|(key, _values)| *key)
+                .zip(results)
+                .collect()
+        }
+
+        match self {
+            Self::Zero(c) => c.map_named_batched(name, Some(max_batch_size), move |keys| {
+                let rows: Vec<&[Value]> = vec![&[]; keys.len()];
+                keys.into_iter().zip(logic(&rows)).collect()
+            }),
+            Self::One(c) => c.map_named_batched(name, Some(max_batch_size), move |batch| {
+                with_keys(&batch, &mut logic)
+            }),
+            Self::Two(c) => c.map_named_batched(name, Some(max_batch_size), move |batch| {
+                with_keys(&batch, &mut logic)
+            }),
+            Self::More(c) => c.map_named_batched(name, Some(max_batch_size), move |batch| {
+                with_keys(&batch, &mut logic)
+            }),
+        }
+    }
+
     #[track_caller]
     fn as_collection(&self) -> Collection<S, (Key, Tuple)> {
         match self {
@@ -764,6 +975,105 @@ impl<S: MaybeTotalScope> TupleCollection<S> {
     }
 }
 
+fn check_operand_type(operation: &str, expected: Type, actual: Type) -> Result<()> {
+    if actual == Type::Any || actual == expected {
+        Ok(())
+    } else {
+        Err(Error::OperandTypeMismatch {
+            operation: operation.to_string(),
+            expected,
+            actual,
+        })
+    }
+}
+
+fn check_operand_not_nullable(
+    operation: &str,
+    expected: Type,
+    properties: Option<&ColumnProperties>,
+) -> Result<()> {
+    match properties {
+        Some(properties) if properties.nullable => Err(Error::NullableOperand {
+            operation: operation.to_string(),
+            expected,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Marks the columns of the side of the result of a join that is None for the rows without
+/// a match as possibly None. The values of the result are the key and the values of the left
+/// row followed by the key and the values of the right row.
+fn outer_join_properties(
+    table_properties: Arc<TableProperties>,
+    join_type: JoinType,
+) -> Arc<TableProperties> {
+    if !join_type.keeps_unmatched_left() && !join_type.keeps_unmatched_right() {
+        return table_properties;
+    }
+    let TableProperties::Table(inner) = table_properties.as_ref() else {
+        return table_properties;
+    };
+    let properties = inner
+        .iter()
+        .enumerate()
+        .map(|(index, properties)| {
+            let missing = if index < 2 {
+                join_type.keeps_unmatched_right()
+            } else {
+                join_type.keeps_unmatched_left()
+            };
+            if missing {
+                properties.with_nullable()
+            } else {
+                properties.clone()
+            }
+        })
+        .collect();
+    Arc::new(TableProperties::Table(properties))
+}
+
+/// Evaluates the expressions for the rows of arguments of a batch of the rows of a table,
+/// returning the tuples of their results.
+fn evaluate_columnar_batch(
+    batch: &[(Key, Value)],
+    rows: &[&[Value]],
+    expressions: &[ExpressionData],
+    error_reporter: &ErrorReporter,
+) -> Vec<(Key, Value)> {
+    let columnar_batch = ColumnarBatch::new(rows);
+    let mut results: Vec<_> = expressions
+        .iter()
+        .map(|expression_data| {
+            expression_data
+                .expression
+                .eval_batch(&columnar_batch)
+                .into_iter()
+        })
+        .collect();
+    batch
+        .iter()
+        .zip(rows)
+        .map(|((key, _values), args)| {
+            let new_values =
+                results
+                    .iter_mut()
+                    .zip(expressions)
+                    .map(|(results, expression_data)| {
+                        results
+                            .next()
+                            .unwrap()
+                            .map_err(|error| Error::with_row(error, *key, args))
+                            .unwrap_with_reporter_and_trace(
+                                error_reporter,
+                                expression_data.properties.trace(),
+                            )
+                    });
+            (*key, Value::Tuple(new_values.collect()))
+        })
+        .collect()
+}
+
 #[derive(Derivative, Debug, Clone, Serialize, Deserialize)]
 #[derivative(PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct KeyWith<T>(
@@ -783,14 +1093,39 @@ impl<T> Shard for KeyWith<T> {
     }
 }
 
+fn cumulative_step(
+    reducer: &Reducer,
+    accumulator: Option<&Value>,
+    value: &Value,
+) -> DynResult<Value> {
+    let result = match (reducer, accumulator) {
+        (Reducer::Count, None) => Value::Int(1),
+        (Reducer::Count, Some(count)) => Value::Int(count.as_int()? + 1),
+        (Reducer::IntSum, None) => Value::Int(value.as_int()?),
+        (Reducer::IntSum, Some(sum)) => Value::Int(sum.as_int()? + value.as_int()?),
+        (Reducer::FloatSum, None) => Value::from(value.as_float()?),
+        (Reducer::FloatSum, Some(sum)) => Value::from(sum.as_float()? + value.as_float()?),
+        (Reducer::Min | Reducer::Max, None) => value.clone(),
+        (Reducer::Min, Some(min)) => min.min(value).clone(),
+        (Reducer::Max, Some(max)) => max.max(value).clone(),
+        _ => unreachable!("reducer not supported in cumulative aggregations"),
+    };
+    Ok(result)
+}
+
 #[allow(clippy::unnecessary_wraps)] // we want to always return Result for symmetry
 impl<S: MaybeTotalScope> DataflowGraphInner<S> {
+    #[allow(clippy::too_many_arguments)]
     fn new(
-        scope: S,
+        mut scope: S,
         error_reporter: ErrorReporter,
         ignore_asserts: bool,
         persistence_config: Option<PersistenceManagerConfig>,
         global_persistent_storage: Option<SharedWorkersPersistenceCoordinator>,
+        consistent_outputs: bool,
+        served_tables: SharedServedTables,
+        shutdown_request: SharedShutdownRequest,
+        pending_work: Option<Rc<PendingWorkTracker>>,
     ) -> Result<Self> {
         let worker_persistent_storage = {
             if let Some(persistence_config) = &persistence_config {
@@ -809,6 +1144,14 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
                 None
             }
         };
+        let output_barrier = consistent_outputs.then(|| {
+            let (handle, barrier) = scope.feedback(Default::default());
+            OutputBarrier {
+                handle,
+                barrier,
+                inputs: Vec::new(),
+            }
+        });
 
         Ok(Self {
             scope,
@@ -823,13 +1166,48 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             output_probe: ProbeHandle::new(),
             probers: Vec::new(),
             probes: HashMap::new(),
+            state_sizes: HashMap::new(),
             ignore_asserts,
             persistence_config,
             worker_persistent_storage,
             global_persistent_storage,
+            output_barrier,
+            served_tables,
+            shutdown_request,
+            output_latencies: Vec::new(),
+            pending_work,
         })
     }
 
+    /// Attributes the timely operators built since the previous call to the Pathway operator,
+    /// so that the records pending between them can be reported.
+    fn attribute_operators(&self, operator_id: Option<usize>) {
+        if let Some(pending_work) = &self.pending_work {
+            pending_work.attribute_operators(self.scope.logging(), operator_id);
+        }
+    }
+
+    fn hold_for_output_barrier<D: Data, R: Data>(
+        &mut self,
+        output: &Stream<S, OutputBatch<S::Timestamp, D, R>>,
+    ) -> Stream<S, OutputBatch<S::Timestamp, D, R>> {
+        let Some(output_barrier) = self.output_barrier.as_mut() else {
+            return output.clone();
+        };
+        output_barrier
+            .inputs
+            .push(output.flat_map(|_batch| None::<()>));
+        output.hold_until(&output_barrier.barrier)
+    }
+
+    fn close_output_barrier(&mut self) {
+        if let Some(output_barrier) = self.output_barrier.take() {
+            self.scope
+                .concatenate(output_barrier.inputs)
+                .connect_loop(output_barrier.handle);
+        }
+    }
+
     fn worker_index(&self) -> usize {
         self.scope.index()
     }
@@ -1075,14 +1453,23 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
         }
         let error_reporter = self.error_reporter.clone();
         let name = format!("Expression {wrapper:?} {expression:?}");
-        let new_values = self
-            .tuples(universe_handle, column_handles)?
-            .map_wrapped_named(&name, wrapper, move |key, values| {
+        let tuples = self.tuples(universe_handle, column_handles)?;
+        let new_values = if expression.is_columnar() {
+            tuples.map_batched_named(&name, COLUMNAR_BATCH_SIZE, move |rows| {
+                expression
+                    .eval_batch(&ColumnarBatch::new(rows))
+                    .into_iter()
+                    .map(|result| result.unwrap_with_reporter_and_trace(&error_reporter, &trace))
+                    .collect()
+            })
+        } else {
+            tuples.map_wrapped_named(&name, wrapper, move |key, values| {
                 let result = expression
                     .eval(values)
                     .unwrap_with_reporter_and_trace(&error_reporter, &trace);
                 (key, result)
-            });
+            })
+        };
 
         let new_column_handle = self.columns.alloc(
             Column::from_collection(universe_handle, new_values)
@@ -1103,27 +1490,90 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             .get(table_handle)
             .ok_or(Error::InvalidTableHandle)?;
 
+        let argument_types: Vec<_> = column_paths
+            .iter()
+            .map(|path| path.extract_type(&table.properties))
+            .collect();
+        for expression_data in &expressions {
+            let expression = &expression_data.expression;
+            expression
+                .check_types(&argument_types)
+                .and_then(|()| expression.check_nullability())
+                .map_err(|error| {
+                    Error::with_trace(error, expression_data.properties.trace().clone())
+                })?;
+        }
+        let nullable_arguments: Vec<_> = column_paths
+            .iter()
+            .map(|path| path.extract_nullable(&table.properties))
+            .collect();
+
         let properties: Vec<_> = expressions
             .iter()
-            .map(|expression_data| expression_data.properties.as_ref().clone())
+            .map(
+                |expression_data| match expression_data.properties.as_ref() {
+                    TableProperties::Column(properties)
+                        if properties.nullable
+                            && !expression_data.expression.is_nullable(&nullable_arguments) =>
+                    {
+                        TableProperties::Column(Arc::new(ColumnProperties {
+                            nullable: false,
+                            ..properties.as_ref().clone()
+                        }))
+                    }
+                    properties => properties.clone(),
+                },
+            )
             .collect();
         let properties = TableProperties::Table(properties.as_slice().into());
 
         let error_reporter = self.error_reporter.clone();
 
+        if expressions
+            .iter()
+            .all(|expression_data| expression_data.expression.is_columnar())
+        {
+            let mut row_buffer = RowBuffer::default();
+            let new_values = table.values_consolidated().map_named_batched(
+                "expression_table::evaluate_expression_columnar",
+                Some(COLUMNAR_BATCH_SIZE),
+                move |batch| {
+                    for (key, values) in &batch {
+                        row_buffer.push_row(column_paths.iter().map(|path| {
+                            path.extract(key, values)
+                                .unwrap_with_reporter(&error_reporter)
+                        }));
+                    }
+                    let new_values = evaluate_columnar_batch(
+                        &batch,
+                        &row_buffer.rows(),
+                        &expressions,
+                        &error_reporter,
+                    );
+                    row_buffer.clear();
+                    new_values
+                },
+            );
+            return Ok(self
+                .tables
+                .alloc(Table::from_collection(new_values).with_properties(Arc::new(properties))));
+        }
+
+        let mut args = Vec::with_capacity(column_paths.len());
         let new_values = table.values_consolidated().map_wrapped_named(
             "expression_table::evaluate_expression",
             wrapper,
             move |(key, values)| {
-                let args: Vec<Value> = column_paths
-                    .iter()
-                    .map(|path| path.extract(&key, &values))
-                    .collect::<Result<_>>()
-                    .unwrap_with_reporter(&error_reporter);
+                args.clear();
+                args.extend(column_paths.iter().map(|path| {
+                    path.extract(&key, &values)
+                        .unwrap_with_reporter(&error_reporter)
+                }));
                 let new_values = expressions.iter().map(|expression_data| {
                     let result = expression_data
                         .expression
                         .eval(&args)
+                        .map_err(|error| Error::with_row(error, key, &args))
                         .unwrap_with_reporter_and_trace(
                             &error_reporter,
                             expression_data.properties.trace(),
@@ -1276,6 +1726,7 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
         column_paths: Vec<ColumnPath>,
         table_properties: Arc<TableProperties>,
         trace: Trace,
+        max_in_flight: Option<usize>,
     ) -> Result<TableHandle> {
         let table = self
             .tables
@@ -1285,6 +1736,7 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
         let trace = Arc::new(trace);
         let new_values = table.values().map_named_async(
             "expression_column::apply_async",
+            max_in_flight,
             move |(key, values)| {
                 let args: Vec<Value> = column_paths
                     .iter()
@@ -1298,6 +1750,7 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
                     let value = async {
                         function(key, &args)
                             .await
+                            .map_err(|error| Error::with_row(error, key, &args))
                             .unwrap_with_reporter_and_trace(&error_reporter, &trace)
                     }
                     .await;
@@ -1311,6 +1764,55 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             .alloc(Table::from_collection(new_values).with_properties(table_properties)))
     }
 
+    fn batch_apply_table(
+        &mut self,
+        function: Arc<dyn Fn(Vec<Vec<Value>>) -> DynResult<Vec<Value>> + Send + Sync>,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        table_properties: Arc<TableProperties>,
+        trace: Trace,
+        max_batch_size: Option<usize>,
+    ) -> Result<TableHandle> {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+        let error_reporter = self.error_reporter.clone();
+        let new_values = table.values().map_named_batched(
+            "expression_column::apply_batched",
+            max_batch_size,
+            move |rows| {
+                let mut keys = Vec::with_capacity(rows.len());
+                let mut columns = vec![Vec::with_capacity(rows.len()); column_paths.len()];
+                for (key, values) in rows {
+                    for (column, path) in columns.iter_mut().zip(&column_paths) {
+                        column.push(
+                            path.extract(&key, &values)
+                                .unwrap_with_reporter_and_trace(&error_reporter, &trace),
+                        );
+                    }
+                    keys.push(key);
+                }
+                let results = function(columns)
+                    .and_then(|results| {
+                        if results.len() == keys.len() {
+                            Ok(results)
+                        } else {
+                            Err(Error::LengthMismatch.into())
+                        }
+                    })
+                    .unwrap_with_reporter_and_trace(&error_reporter, &trace);
+                keys.into_iter()
+                    .zip(results)
+                    .map(|(key, value)| (key, Value::from([value].as_slice())))
+                    .collect()
+            },
+        );
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_values).with_properties(table_properties)))
+    }
+
     fn filter_table(
         &mut self,
         table_handle: TableHandle,
@@ -1322,6 +1824,17 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             .get(table_handle)
             .ok_or(Error::InvalidTableHandle)?;
 
+        check_operand_type(
+            "filter",
+            Type::Bool,
+            filtering_column_path.extract_type(&table.properties),
+        )?;
+        check_operand_not_nullable(
+            "filter",
+            Type::Bool,
+            filtering_column_path.extract_column_properties(&table.properties),
+        )?;
+
         let error_reporter = self.error_reporter.clone();
 
         let new_table = table.values().flat_map(move |(key, values)| {
@@ -1371,6 +1884,80 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             .alloc(Table::from_collection(on_time).with_properties(table_properties)))
     }
 
+    fn freeze_late(
+        &mut self,
+        table_handle: TableHandle,
+        threshold_time_column_path: ColumnPath,
+        current_time_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>
+    where
+        <S as MaybeTotalScope>::MaybeTotalTimestamp: Timestamp<Summary = <S as MaybeTotalScope>::MaybeTotalTimestamp>
+            + PathSummary<<S as MaybeTotalScope>::MaybeTotalTimestamp>
+            + Epsilon,
+    {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+
+        let (_on_time, late) = table.values().freeze(
+            move |val| threshold_time_column_path.extract_from_value(val).unwrap(),
+            move |val| current_time_column_path.extract_from_value(val).unwrap(),
+        );
+
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(late).with_properties(table_properties)))
+    }
+
+    fn reshard_table(
+        &mut self,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        parallelism: Option<usize>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+
+        if parallelism == Some(0) {
+            return Err(Error::ValueError(
+                "parallelism has to be positive".to_owned(),
+            ));
+        }
+        let peers = self.scope.peers();
+        let workers = parallelism.map_or(peers, |parallelism| min(parallelism, peers)) as u64;
+        let error_reporter = self.error_reporter.clone();
+        let new_values = table
+            .values()
+            .inner
+            .exchange(move |((key, values), _time, _diff)| {
+                let shard = if column_paths.is_empty() {
+                    key.shard()
+                } else {
+                    let reshard_values: Vec<Value> = column_paths
+                        .iter()
+                        .map(|path| {
+                            path.extract(key, values)
+                                .unwrap_with_reporter(&error_reporter)
+                        })
+                        .collect();
+                    Key::for_values(&reshard_values).shard()
+                };
+                // Exchange sends a record to the worker equal to the value modulo
+                // the number of workers, so this picks one of the first `workers`
+                shard % workers
+            })
+            .as_collection();
+
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_values).with_properties(table_properties)))
+    }
+
     fn buffer(
         &mut self,
         table_handle: TableHandle,
@@ -1628,6 +2215,7 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             let wrapped = match value {
                 Value::IntArray(array) => Ok(flatten_ndarray(&array)),
                 Value::FloatArray(array) => Ok(flatten_ndarray(&array)),
+                Value::Float32Array(array) => Ok(flatten_ndarray(&array)),
                 Value::Tuple(array) => Ok((*array).to_vec()),
                 Value::String(s) => Ok((*s)
                     .chars()
@@ -1724,41 +2312,316 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             .alloc(Table::from_collection(new_values).with_properties(table_properties)))
     }
 
-    fn update_rows_arrange(
+    fn session_window_table(
         &mut self,
         table_handle: TableHandle,
-        update_handle: TableHandle,
-    ) -> Result<ArrangedByKey<S, Key, (bool, Value)>> {
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        max_gap: Value,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
         let table = self
             .tables
             .get(table_handle)
             .ok_or(Error::InvalidTableHandle)?;
-        let update = self
-            .tables
-            .get(update_handle)
-            .ok_or(Error::InvalidTableHandle)?;
 
-        Ok(table
+        let error_reporter = self.error_reporter.clone();
+
+        let sessions: ArrangedByKey<S, Key, [Value; 3]> = table
             .values()
-            .map_named("update_rows_arrange::table", |(k, v)| (k, (false, v)))
-            .concat(
-                &update
-                    .values()
-                    .map_named("update_rows_arrange::update", |(k, v)| (k, (true, v))),
+            .map_named("session_window_table::instance_key_id", {
+                let error_reporter = error_reporter.clone();
+                move |(id, values)| {
+                    let instance = instance_column_path
+                        .extract(&id, &values)
+                        .unwrap_with_reporter(&error_reporter);
+                    let key = key_column_path
+                        .extract(&id, &values)
+                        .unwrap_with_reporter(&error_reporter);
+                    (instance, (key, id))
+                }
+            })
+            .session_windows(move |prev: &Value, next: &Value| {
+                within_gap(prev, next, &max_gap).unwrap_with_reporter(&error_reporter)
+            })
+            .map_named(
+                "session_window_table::sessions",
+                |(instance, (id, (start, end)))| {
+                    let window =
+                        Value::Pointer(Key::for_values(&[instance, start.clone(), end.clone()]));
+                    (id, [window, start, end])
+                },
             )
-            .arrange_named("update_rows_arrange::both"))
+            .arrange();
+
+        let new_values = table
+            .values_arranged()
+            .join_core(&sessions, |key, values, session| {
+                once((
+                    *key,
+                    Value::Tuple(
+                        [values.clone()]
+                            .into_iter()
+                            .chain(session.clone())
+                            .collect(),
+                    ),
+                ))
+            });
+
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_values).with_properties(table_properties)))
     }
 
-    fn update_rows_table(
+    fn top_n_table(
         &mut self,
         table_handle: TableHandle,
-        update_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        n: usize,
+        descending: bool,
         table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle> {
-        let both_arranged = self.update_rows_arrange(table_handle, update_handle)?;
-
-        let updated_values = both_arranged.reduce_abelian(
-            "update_rows_table::updated",
+        if n == 0 {
+            return Err(Error::ValueError("n has to be positive".to_string()));
+        }
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+
+        let error_reporter = self.error_reporter.clone();
+
+        let positions: ArrangedByKey<S, Key, Value> = table
+            .values()
+            .map_named("top_n_table::instance_key_id", move |(id, values)| {
+                let instance = instance_column_path
+                    .extract(&id, &values)
+                    .unwrap_with_reporter(&error_reporter);
+                let key = key_column_path
+                    .extract(&id, &values)
+                    .unwrap_with_reporter(&error_reporter);
+                (instance, (key, id))
+            })
+            .top_n(n, descending)
+            .map_named(
+                "top_n_table::positions",
+                |(_instance, ((_key, id), position))| {
+                    (id, Value::Int(i64::try_from(position).unwrap()))
+                },
+            )
+            .arrange();
+
+        let new_values = table
+            .values_arranged()
+            .join_core(&positions, |key, values, position| {
+                once((
+                    *key,
+                    Value::Tuple([values.clone(), position.clone()].into_iter().collect()),
+                ))
+            });
+
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_values).with_properties(table_properties)))
+    }
+
+    fn rank_table(
+        &mut self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+
+        let error_reporter = self.error_reporter.clone();
+
+        let ranks: ArrangedByKey<S, Key, [Value; 3]> = table
+            .values()
+            .map_named("rank_table::instance_key_id", move |(id, values)| {
+                let instance = instance_column_path
+                    .extract(&id, &values)
+                    .unwrap_with_reporter(&error_reporter);
+                let key = key_column_path
+                    .extract(&id, &values)
+                    .unwrap_with_reporter(&error_reporter);
+                (instance, (key, id))
+            })
+            .rank(descending)
+            .map_named("rank_table::ranks", |(_instance, (id, ranks))| {
+                let as_value = |rank: usize| Value::Int(i64::try_from(rank).unwrap());
+                (
+                    id,
+                    [
+                        as_value(ranks.row_number),
+                        as_value(ranks.rank),
+                        as_value(ranks.dense_rank),
+                    ],
+                )
+            })
+            .arrange();
+
+        let new_values = table
+            .values_arranged()
+            .join_core(&ranks, |key, values, ranks| {
+                once((
+                    *key,
+                    Value::Tuple([values.clone()].into_iter().chain(ranks.clone()).collect()),
+                ))
+            });
+
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_values).with_properties(table_properties)))
+    }
+
+    fn cumulative_table(
+        &mut self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        reducers: Vec<ReducerData>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+
+        for data in &reducers {
+            let arity = match data.reducer {
+                Reducer::Count => 0,
+                Reducer::IntSum | Reducer::FloatSum | Reducer::Min | Reducer::Max => 1,
+                _ => {
+                    return Err(Error::ValueError(
+                        "only count, sum, min and max can be computed cumulatively".to_string(),
+                    ))
+                }
+            };
+            if data.column_paths.len() != arity {
+                return Err(Error::ValueError(format!(
+                    "cumulative reducer expects {arity} argument(s), got {}",
+                    data.column_paths.len()
+                )));
+            }
+        }
+
+        let error_reporter = self.error_reporter.clone();
+        let paths: Vec<Option<ColumnPath>> = reducers
+            .iter()
+            .map(|data| data.column_paths.first().cloned())
+            .collect();
+        let step_reducers: Vec<Reducer> = reducers.into_iter().map(|data| data.reducer).collect();
+        let step_error_reporter = self.error_reporter.clone();
+
+        let aggregates: ArrangedByKey<S, Key, Vec<Value>> = table
+            .values()
+            .map_named("cumulative_table::instance_key_id", move |(id, values)| {
+                let instance = instance_column_path
+                    .extract(&id, &values)
+                    .unwrap_with_reporter(&error_reporter);
+                let key = key_column_path
+                    .extract(&id, &values)
+                    .unwrap_with_reporter(&error_reporter);
+                let arguments: Vec<Value> = paths
+                    .iter()
+                    .map(|path| {
+                        path.as_ref().map_or(Value::None, |path| {
+                            path.extract(&id, &values)
+                                .unwrap_with_reporter(&error_reporter)
+                        })
+                    })
+                    .collect();
+                (instance, ((key, id), arguments))
+            })
+            .cumulative(
+                Vec::<Option<Value>>::new(),
+                move |accumulators, arguments: &Vec<Value>| {
+                    step_reducers
+                        .iter()
+                        .zip(arguments)
+                        .enumerate()
+                        .map(|(index, (reducer, argument))| {
+                            Some(
+                                cumulative_step(
+                                    reducer,
+                                    accumulators.get(index).and_then(Option::as_ref),
+                                    argument,
+                                )
+                                .unwrap_with_reporter(&step_error_reporter),
+                            )
+                        })
+                        .collect()
+                },
+            )
+            .map_named(
+                "cumulative_table::aggregates",
+                |(_instance, (id, accumulators))| {
+                    (id, accumulators.into_iter().flatten().collect())
+                },
+            )
+            .arrange();
+
+        let new_values =
+            table
+                .values_arranged()
+                .join_core(&aggregates, |key, values, aggregates| {
+                    once((
+                        *key,
+                        Value::Tuple(
+                            [values.clone()]
+                                .into_iter()
+                                .chain(aggregates.iter().cloned())
+                                .collect(),
+                        ),
+                    ))
+                });
+
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_values).with_properties(table_properties)))
+    }
+
+    fn update_rows_arrange(
+        &mut self,
+        table_handle: TableHandle,
+        update_handle: TableHandle,
+    ) -> Result<ArrangedByKey<S, Key, (bool, Value)>> {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+        let update = self
+            .tables
+            .get(update_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+
+        Ok(table
+            .values()
+            .map_named("update_rows_arrange::table", |(k, v)| (k, (false, v)))
+            .concat(
+                &update
+                    .values()
+                    .map_named("update_rows_arrange::update", |(k, v)| (k, (true, v))),
+            )
+            .arrange_named("update_rows_arrange::both"))
+    }
+
+    fn update_rows_table(
+        &mut self,
+        table_handle: TableHandle,
+        update_handle: TableHandle,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        let both_arranged = self.update_rows_arrange(table_handle, update_handle)?;
+
+        let updated_values = both_arranged.reduce_abelian(
+            "update_rows_table::updated",
             move |_key, input, output| {
                 let ((_is_right, value), _count) = input.last().unwrap();
                 output.push((value.clone(), 1));
@@ -1887,6 +2750,20 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             .get(key_handle)
             .ok_or(Error::InvalidTableHandle)?;
 
+        check_operand_type(
+            "ix",
+            Type::Pointer,
+            key_column_path.extract_type(&key_table.properties),
+        )?;
+        if ix_key_policy == IxKeyPolicy::FailMissing {
+            check_operand_not_nullable(
+                "ix",
+                Type::Pointer,
+                key_column_path.extract_column_properties(&key_table.properties),
+            )?;
+        }
+        let key_nullable = key_column_path.extract_nullable(&key_table.properties);
+
         let error_reporter = self.error_reporter.clone();
 
         let key_table_extracted =
@@ -1900,24 +2777,27 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
                 });
 
         let error_reporter = self.error_reporter.clone();
-        let values_to_keys_arranged: ArrangedByKey<S, Key, (Key, Value)> = match ix_key_policy {
-            IxKeyPolicy::FailMissing => key_table_extracted.map_named(
-                "ix_table unwrapping pointers",
-                move |(key, (values, value))| {
-                    let pointer = value.as_pointer().unwrap_with_reporter(&error_reporter);
-                    (pointer, (key, values))
-                },
-            ),
-            _ => key_table_extracted.flat_map(move |(key, (values, value))| {
-                if value == Value::None {
-                    None
-                } else {
-                    let pointer = value.as_pointer().unwrap_with_reporter(&error_reporter);
-                    Some((pointer, (key, values)))
-                }
-            }),
-        }
-        .arrange();
+        // the keys that can't be None don't have to be compared with it
+        let values_to_keys_arranged: ArrangedByKey<S, Key, (Key, Value)> =
+            if ix_key_policy == IxKeyPolicy::FailMissing || !key_nullable {
+                key_table_extracted.map_named(
+                    "ix_table unwrapping pointers",
+                    move |(key, (values, value))| {
+                        let pointer = value.as_pointer().unwrap_with_reporter(&error_reporter);
+                        (pointer, (key, values))
+                    },
+                )
+            } else {
+                key_table_extracted.flat_map(move |(key, (values, value))| {
+                    if value == Value::None {
+                        None
+                    } else {
+                        let pointer = value.as_pointer().unwrap_with_reporter(&error_reporter);
+                        Some((pointer, (key, values)))
+                    }
+                })
+            }
+            .arrange();
         let new_table = match ix_key_policy {
             IxKeyPolicy::SkipMissing => values_to_keys_arranged.join_core(
                 to_ix_table.values_arranged(),
@@ -1974,6 +2854,15 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             .get(right_table_handle)
             .ok_or(Error::InvalidTableHandle)?;
 
+        for (left_path, right_path) in left_column_paths.iter().zip(&right_column_paths) {
+            let left = left_path.extract_type(&left_table.properties);
+            let right = right_path.extract_type(&right_table.properties);
+            // the keys of the values of different types never match
+            if left != Type::Any && right != Type::Any && left != right {
+                return Err(Error::JoinKeyTypeMismatch { left, right });
+            }
+        }
+
         let error_reporter_left = self.error_reporter.clone();
         let error_reporter_right = self.error_reporter.clone();
 
@@ -2132,8 +3021,8 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
             result_left_right
         };
 
-        let result_table =
-            Table::from_collection(result_left_right).with_properties(table_properties);
+        let result_table = Table::from_collection(result_left_right)
+            .with_properties(outer_join_properties(table_properties, join_type));
 
         match join_type {
             JoinType::LeftKeysFull => {
@@ -2192,14 +3081,31 @@ impl<S: MaybeTotalScope> DataflowGraphInner<S> {
         Ok(())
     }
 
-    fn probe_table(&mut self, table_handle: TableHandle, operator_id: usize) -> Result<()> {
+    fn probe_table(
+        &mut self,
+        table_handle: TableHandle,
+        operator_id: usize,
+        state_limit: Option<OperatorStateLimit>,
+    ) -> Result<()> {
         let table = self
             .tables
             .get(table_handle)
             .ok_or(Error::InvalidTableHandle)?;
+        let state_size = self.state_sizes.entry(operator_id).or_default().clone();
+        let error_reporter = self.error_reporter.clone();
         table
             .values()
+            .inspect(move |((_key, value), _time, diff)| {
+                let row_size = size_of::<(Key, S::Timestamp, isize)>() + value.estimated_size();
+                state_size.update(
+                    isize::try_from(row_size).unwrap() * diff,
+                    operator_id,
+                    state_limit,
+                    &error_reporter,
+                );
+            })
             .probe_with(self.probes.entry(operator_id).or_default());
+        self.attribute_operators(Some(operator_id));
         Ok(())
     }
 }
@@ -2296,6 +3202,23 @@ where
     }
 }
 
+impl<S: MaybeTotalScope> DataflowReducer<S> for RetractableReducer
+where
+    S::MaybeTotalTimestamp: TotalOrder,
+{
+    fn reduce(self: Rc<Self>, values: &Collection<S, (Key, Key, Vec<Value>)>) -> Values<S> {
+        values
+            .map_named(
+                "RetractableReducer::reduce::init",
+                |(_source_key, result_key, values)| (result_key, values),
+            )
+            .batched_stateful_reduce_named("RetractableReducer::reduce::reduce", move |groups| {
+                self.update(groups)
+            })
+            .into()
+    }
+}
+
 trait CreateDataflowReducer<S: MaybeTotalScope> {
     fn create_dataflow_reducer(reducer: &Reducer) -> Result<Rc<dyn DataflowReducer<S>>>;
 }
@@ -2319,7 +3242,9 @@ where
             Reducer::Tuple { skip_nones } => Rc::new(TupleReducer::new(*skip_nones)),
 
             Reducer::Any => Rc::new(AnyReducer),
-            Reducer::Stateful { .. } => return Err(Error::NotSupportedInIteration),
+            Reducer::Stateful { .. } | Reducer::Retractable { .. } => {
+                return Err(Error::NotSupportedInIteration)
+            }
         };
 
         Ok(res)
@@ -2334,6 +3259,7 @@ where
     fn create_dataflow_reducer(reducer: &Reducer) -> Result<Rc<dyn DataflowReducer<S>>> {
         let res: Rc<dyn DataflowReducer<S>> = match reducer {
             Reducer::Stateful { combine_fn } => Rc::new(StatefulReducer::new(combine_fn.clone())),
+            Reducer::Retractable { reducer } => Rc::new(RetractableReducer::new(reducer.clone())),
             other => NotTotal::create_dataflow_reducer(other)?,
         };
 
@@ -2517,6 +3443,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
         }
     }
 
+    #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
     fn connector_table(
         &mut self,
         mut reader: Box<dyn ReaderBuilder>,
@@ -2525,6 +3452,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
         parallel_readers: usize,
         table_properties: Arc<TableProperties>,
         external_persistent_id: Option<&ExternalPersistentId>,
+        schema: Vec<SchemaColumn>,
     ) -> Result<TableHandle> {
         let has_persistent_storage = self.worker_persistent_storage.is_some();
         if let Some(external_persistent_id) = external_persistent_id {
@@ -2553,6 +3481,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
         if realtime_reader_needed || persisted_table {
             let persistent_id = reader.persistent_id();
             let reader_storage_type = reader.storage_type();
+            let source_acknowledger = reader.source_acknowledger();
             let persistence_mode = self
                 .persistence_config
                 .as_ref()
@@ -2562,7 +3491,26 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
                 .as_ref()
                 .map_or(SnapshotAccess::Full, |config| config.snapshot_access);
 
-            let connector = Connector::<S::Timestamp>::new(commit_duration, parser.column_count());
+            // The schema is only known for the parsers producing a value for each field
+            let input_schema = (schema.len() == parser.column_count()).then(|| {
+                let migrations = self
+                    .persistence_config
+                    .as_ref()
+                    .zip(external_persistent_id)
+                    .and_then(|(config, external_persistent_id)| {
+                        config
+                            .schema_migrations
+                            .get(external_persistent_id)
+                            .cloned()
+                    })
+                    .unwrap_or_default();
+                InputSchema::new(schema, migrations)
+            });
+            let connector = Connector::<S::Timestamp>::new(
+                commit_duration,
+                parser.column_count(),
+                input_schema,
+            );
             let state = connector.run(
                 reader,
                 parser,
@@ -2593,6 +3541,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
                 effective_persistent_id.as_ref(),
                 persistence_mode,
                 snapshot_access,
+                self.shutdown_request.clone(),
                 self.error_reporter.clone(),
             )?;
 
@@ -2601,20 +3550,26 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
             if let Some(persistent_id) = persistent_id {
                 // If there is a persistent id, there's also a persistent storage
                 // It is checked in the beginning of the method
-                self.worker_persistent_storage
+                let mut worker_persistent_storage = self
+                    .worker_persistent_storage
                     .as_ref()
                     .unwrap()
                     .lock()
-                    .unwrap()
-                    .register_input_source(
-                        persistent_id,
-                        &reader_storage_type,
-                        state.offsets_by_time,
-                    );
+                    .unwrap();
+                worker_persistent_storage.register_input_source(
+                    persistent_id,
+                    &reader_storage_type,
+                    state.offsets_by_time,
+                );
+                if let Some(source_acknowledger) = source_acknowledger {
+                    worker_persistent_storage
+                        .register_source_acknowledger(persistent_id, source_acknowledger);
+                }
             }
             self.connector_monitors.push(state.connector_monitor);
         }
 
+        self.attribute_operators(None);
         Ok(self
             .tables
             .alloc(Table::from_collection(table_values).with_properties(table_properties)))
@@ -2702,15 +3657,185 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
             .alloc(Table::from_collection(new_table).with_properties(table_properties)))
     }
 
+    fn expire_after(
+        &mut self,
+        table_handle: TableHandle,
+        duration: u64,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+        // keep regular (even) times, odd times are reserved for forgetting
+        let duration = duration + duration % 2;
+        let new_table = table.values().expire_after(duration);
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_table).with_properties(table_properties)))
+    }
+
+    fn deduplicate_within(
+        &mut self,
+        table_handle: TableHandle,
+        fingerprint_column_path: ColumnPath,
+        time_column_path: ColumnPath,
+        expiration_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+
+        let error_reporter = self.error_reporter.clone();
+
+        let new_table = table
+            .values()
+            .map_named(
+                "deduplicate_within::fingerprint_times",
+                move |(id, values)| {
+                    let fingerprint = fingerprint_column_path
+                        .extract(&id, &values)
+                        .unwrap_with_reporter(&error_reporter)
+                        .as_pointer()
+                        .unwrap_with_reporter(&error_reporter);
+                    let time = time_column_path
+                        .extract(&id, &values)
+                        .unwrap_with_reporter(&error_reporter);
+                    let expires_at = expiration_column_path
+                        .extract(&id, &values)
+                        .unwrap_with_reporter(&error_reporter);
+                    (fingerprint, ((time, expires_at), (id, values)))
+                },
+            )
+            .deduplicate_within();
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_table).with_properties(table_properties)))
+    }
+
+    fn subscribe_named_table(
+        &mut self,
+        name: String,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        callbacks: SubscribeCallbacks,
+        skip_persisted_batch: bool,
+    ) -> Result<()> {
+        let worker_index = self.scope.index();
+
+        let sink_id = self
+            .worker_persistent_storage
+            .as_ref()
+            .map(|m| m.lock().unwrap().register_sink());
+        let global_persistent_storage = self.global_persistent_storage.clone();
+        let skip_initial_time = skip_persisted_batch && global_persistent_storage.is_some();
+
+        let error_reporter = self.error_reporter.clone();
+        let error_reporter_2 = self.error_reporter.clone();
+
+        let SubscribeCallbacks {
+            wrapper,
+            mut on_data,
+            mut on_time_end,
+            mut on_end,
+            mut on_commit,
+        } = callbacks;
+        let wrapper_2 = wrapper.clone();
+        let mut last_commit_time = 0;
+
+        let latency = SharedLatencyHistogram::default();
+        self.output_latencies.push((name, latency.clone()));
+
+        let output = self
+            .extract_columns(table_handle, column_paths)?
+            .as_collection()
+            .consolidate_for_output(true);
+        self.hold_for_output_barrier(&output)
+            .inspect(move |batch| {
+                if batch.time == ARTIFICIAL_TIME_ON_REWIND_START && skip_initial_time {
+                    return;
+                }
+                wrapper
+                    .run(|| -> DynResult<()> {
+                        if let Some(on_data) = on_data.as_mut() {
+                            for ((key, values), diff) in &batch.data {
+                                on_data(*key, values, batch.time, *diff)?;
+                            }
+                        }
+                        if let Some(on_time_end) = on_time_end.as_mut() {
+                            on_time_end(batch.time)?;
+                        }
+                        Ok(())
+                    })
+                    .unwrap_with_reporter(&error_reporter);
+                latency.lock().unwrap().observe(
+                    batch.time,
+                    u64::try_from(batch.data.len()).unwrap(),
+                    SystemTime::now(),
+                );
+            })
+            .inspect_core(move |event| {
+                // Another inspect, so we are looking at the first inspect's output fronitier,
+                // i.e., we are called after every worker has finished processing callbacks from
+                // the first inspect for this frontier.
+                if let Err(frontier) = event {
+                    if worker_index == 0 && frontier.is_empty() {
+                        if let Some(on_end) = on_end.as_mut() {
+                            wrapper_2
+                                .run(on_end)
+                                .unwrap_with_reporter(&error_reporter_2);
+                        }
+                    }
+                    if let (Some(on_commit), Some(&time)) = (on_commit.as_mut(), frontier.first()) {
+                        if worker_index == 0 && time > last_commit_time {
+                            wrapper_2
+                                .run(|| on_commit(time))
+                                .unwrap_with_reporter(&error_reporter_2);
+                            last_commit_time = time;
+                        }
+                    }
+
+                    assert!(frontier.len() <= 1);
+                    let time_processed = frontier.first().copied();
+                    if let Some(global_persistent_storage) = &global_persistent_storage {
+                        let output_commit = global_persistent_storage
+                            .lock()
+                            .unwrap()
+                            .accept_finalized_timestamp(
+                                worker_index,
+                                sink_id.expect("undefined sink_id while using persistent storage"),
+                                time_processed,
+                            );
+                        // The checkpoint may complete here, with the output of the other sinks
+                        if let Some(output_commit) = output_commit {
+                            output_commit
+                                .run()
+                                .map_err(DynError::from)
+                                .unwrap_with_reporter(&error_reporter_2);
+                        }
+                    }
+                }
+            })
+            .probe_with(&mut self.output_probe);
+        self.attribute_operators(None);
+
+        Ok(())
+    }
+
     fn output_batch(
         stats: &mut OutputConnectorStats,
+        latency: &SharedLatencyHistogram,
         batch: OutputBatch<u64, (Key, Tuple), isize>,
-        data_sink: &mut Box<dyn Writer>,
+        data_sink: &SharedWriter,
         data_formatter: &mut Box<dyn Formatter>,
         global_persistent_storage: &GlobalPersistentStorage,
     ) -> Result<(), DynError> {
+        let mut data_sink = data_sink.lock().unwrap();
         stats.on_batch_started();
         let time = batch.time;
+        let mut rows_written = 0;
         for ((key, values), diff) in batch.data {
             if time == ARTIFICIAL_TIME_ON_REWIND_START && global_persistent_storage.is_some() {
                 // Ignore entries, which had been written before
@@ -2721,9 +3846,14 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
                 .map_err(DynError::from)?;
             data_sink.write(formatted).map_err(DynError::from)?;
             stats.on_batch_entry_written();
+            rows_written += 1;
         }
         stats.on_batch_finished();
         data_sink.flush().map_err(DynError::from)?;
+        latency
+            .lock()
+            .unwrap()
+            .observe(time, rows_written, SystemTime::now());
 
         Ok(())
     }
@@ -2731,12 +3861,23 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
     fn commit_output_time(
         stats: &mut OutputConnectorStats,
         t: Option<u64>,
+        epoch: u64,
         worker_index: usize,
         sink_id: Option<usize>,
+        data_sink: &SharedWriter,
         global_persistent_storage: &GlobalPersistentStorage,
-    ) {
+    ) -> Result<(), DynError> {
+        {
+            let mut data_sink = data_sink.lock().unwrap();
+            data_sink.prepare_commit(epoch).map_err(DynError::from)?;
+            if global_persistent_storage.is_none() {
+                // Without persistence nothing is replayed, so the epoch can be exposed
+                // right away. Otherwise the coordinator commits it with the checkpoint.
+                data_sink.commit_prepared(epoch).map_err(DynError::from)?;
+            }
+        }
         if let Some(global_persistent_storage) = &global_persistent_storage {
-            global_persistent_storage
+            let output_commit = global_persistent_storage
                 .lock()
                 .unwrap()
                 .accept_finalized_timestamp(
@@ -2744,13 +3885,54 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
                     sink_id.expect("undefined sink_id while using persistent storage"),
                     t,
                 );
+            // Run after releasing the coordinator, as it waits for the external systems
+            if let Some(output_commit) = output_commit {
+                output_commit.run().map_err(DynError::from)?;
+            }
         }
         stats.on_time_committed(t);
+        Ok(())
+    }
+
+    fn share_output_sink(&self, mut data_sink: Box<dyn Writer>) -> Result<SharedWriter> {
+        let last_finalized_time = self
+            .worker_persistent_storage
+            .as_ref()
+            .map_or(0, |storage| {
+                storage.lock().unwrap().last_finalized_timestamp()
+            });
+        let prepared_output = match (
+            data_sink.prepared_output_name(),
+            &self.worker_persistent_storage,
+        ) {
+            (Some(name), Some(storage)) => Some(
+                storage
+                    .lock()
+                    .unwrap()
+                    .create_prepared_output_storage(name)
+                    .map_err(|e| Error::Other(e.into()))?,
+            ),
+            _ => None,
+        };
+        data_sink
+            .resolve_prepared(last_finalized_time, prepared_output)
+            .map_err(|e| Error::Other(e.into()))?;
+        let transactional = data_sink.transactional();
+        let data_sink: SharedWriter = Arc::new(Mutex::new(data_sink));
+        if let Some(global_persistent_storage) = &self.global_persistent_storage {
+            if transactional {
+                global_persistent_storage
+                    .lock()
+                    .unwrap()
+                    .register_transactional_sink(data_sink.clone());
+            }
+        }
+        Ok(data_sink)
     }
 
     fn output_table(
         &mut self,
-        mut data_sink: Box<dyn Writer>,
+        data_sink: Box<dyn Writer>,
         mut data_formatter: Box<dyn Formatter>,
         table_handle: TableHandle,
         column_paths: Vec<ColumnPath>,
@@ -2761,6 +3943,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
         let single_threaded = data_sink.single_threaded();
 
         let output = output_columns.consolidate_for_output(single_threaded);
+        let output = self.hold_for_output_barrier(&output);
 
         let worker_index = self.scope.index();
         let sender = if !single_threaded || worker_index == 0 {
@@ -2781,7 +3964,14 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
             // connector_threads vector contains both, input and output connector threads
             // connector_monitors vector contains monitors only for input connectors
             let output_connector_id = self.connector_threads.len() - self.connector_monitors.len();
-            let mut stats = OutputConnectorStats::new(data_sink.name(output_connector_id));
+            let output_name = data_sink.name(output_connector_id);
+            let mut stats = OutputConnectorStats::new(output_name.clone());
+            let latency = SharedLatencyHistogram::default();
+            self.output_latencies.push((output_name, latency.clone()));
+
+            let data_sink = self.share_output_sink(data_sink)?;
+            // Output for times before the epoch is complete once the epoch is prepared
+            let mut next_epoch = 0;
 
             let output_joiner_handle = Builder::new()
                 .name(thread_name)
@@ -2791,22 +3981,27 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
                         let receiver = error_reporter_with_receiver.get();
                         match receiver.recv() {
                             Ok(OutputEvent::Batch(batch)) => {
+                                next_epoch = max(next_epoch, batch.time + 1);
                                 Self::output_batch(
                                     &mut stats,
+                                    &latency,
                                     batch,
-                                    &mut data_sink,
+                                    &data_sink,
                                     &mut data_formatter,
                                     &global_persistent_storage,
                                 )?;
                             }
                             Ok(OutputEvent::Commit(t)) => {
+                                next_epoch = max(next_epoch, t.unwrap_or(0));
                                 Self::commit_output_time(
                                     &mut stats,
                                     t,
+                                    next_epoch,
                                     worker_index,
                                     sink_id,
+                                    &data_sink,
                                     &global_persistent_storage,
-                                );
+                                )?;
                                 if t.is_none() {
                                     break Ok(());
                                 }
@@ -2852,10 +4047,45 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
                 }
             })
             .probe_with(&mut self.output_probe);
+        self.attribute_operators(None);
 
         Ok(())
     }
 
+    fn changelog_table(
+        &mut self,
+        table_handle: TableHandle,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        let table = self
+            .tables
+            .get(table_handle)
+            .ok_or(Error::InvalidTableHandle)?;
+
+        let new_values = table
+            .values()
+            .consolidate()
+            .inner
+            .map(|((key, values), time, diff)| {
+                let time_value = Value::from(i64::try_from(time).unwrap());
+                let diff_value = Value::from(i64::try_from(diff).unwrap());
+                let new_key = Key::for_values(&[
+                    Value::from(key),
+                    values.clone(),
+                    time_value.clone(),
+                    diff_value.clone(),
+                ]);
+                let new_values =
+                    Value::Tuple([values, time_value, diff_value].into_iter().collect());
+                ((new_key, new_values), time, 1)
+            })
+            .as_collection();
+
+        Ok(self
+            .tables
+            .alloc(Table::from_collection(new_values).with_properties(table_properties)))
+    }
+
     fn subscribe_table(
         &mut self,
         table_handle: TableHandle,
@@ -2863,77 +4093,38 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
         callbacks: SubscribeCallbacks,
         skip_persisted_batch: bool,
     ) -> Result<()> {
-        let worker_index = self.scope.index();
-
-        let sink_id = self
-            .worker_persistent_storage
-            .as_ref()
-            .map(|m| m.lock().unwrap().register_sink());
-        let global_persistent_storage = self.global_persistent_storage.clone();
-        let skip_initial_time = skip_persisted_batch && global_persistent_storage.is_some();
-
-        let error_reporter = self.error_reporter.clone();
-        let error_reporter_2 = self.error_reporter.clone();
-
-        let SubscribeCallbacks {
-            wrapper,
-            mut on_data,
-            mut on_time_end,
-            mut on_end,
-        } = callbacks;
-        let wrapper_2 = wrapper.clone();
-
-        self.extract_columns(table_handle, column_paths)?
-            .as_collection()
-            .consolidate_for_output(true)
-            .inspect(move |batch| {
-                if batch.time == ARTIFICIAL_TIME_ON_REWIND_START && skip_initial_time {
-                    return;
-                }
-                wrapper
-                    .run(|| -> DynResult<()> {
-                        if let Some(on_data) = on_data.as_mut() {
-                            for ((key, values), diff) in &batch.data {
-                                on_data(*key, values, batch.time, *diff)?;
-                            }
-                        }
-                        if let Some(on_time_end) = on_time_end.as_mut() {
-                            on_time_end(batch.time)?;
-                        }
-                        Ok(())
-                    })
-                    .unwrap_with_reporter(&error_reporter);
-            })
-            .inspect_core(move |event| {
-                // Another inspect, so we are looking at the first inspect's output fronitier,
-                // i.e., we are called after every worker has finished processing callbacks from
-                // the first inspect for this frontier.
-                if let Err(frontier) = event {
-                    if worker_index == 0 && frontier.is_empty() {
-                        if let Some(on_end) = on_end.as_mut() {
-                            wrapper_2
-                                .run(on_end)
-                                .unwrap_with_reporter(&error_reporter_2);
-                        }
-                    }
-
-                    assert!(frontier.len() <= 1);
-                    let time_processed = frontier.first().copied();
-                    if let Some(global_persistent_storage) = &global_persistent_storage {
-                        global_persistent_storage
-                            .lock()
-                            .unwrap()
-                            .accept_finalized_timestamp(
-                                worker_index,
-                                sink_id.expect("undefined sink_id while using persistent storage"),
-                                time_processed,
-                            );
-                    }
-                }
-            })
-            .probe_with(&mut self.output_probe);
+        let name = format!("subscribe-{}", self.output_latencies.len());
+        self.subscribe_named_table(
+            name,
+            table_handle,
+            column_paths,
+            callbacks,
+            skip_persisted_batch,
+        )
+    }
 
-        Ok(())
+    fn serve_table(
+        &mut self,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        name: String,
+        column_names: Vec<String>,
+    ) -> Result<()> {
+        // The workers of a process share the table
+        let served_table = self
+            .served_tables
+            .lock()
+            .unwrap()
+            .entry(name.clone())
+            .or_insert_with(|| Arc::new(RwLock::new(ServedTable::new(column_names))))
+            .clone();
+        self.subscribe_named_table(
+            format!("served_table-{name}"),
+            table_handle,
+            column_paths,
+            served_table_callbacks(served_table),
+            false,
+        )
     }
 
     fn iterate<'a>(
@@ -2941,12 +4132,12 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
         iterated: Vec<LegacyTable>,
         iterated_with_universe: Vec<LegacyTable>,
         extra: Vec<LegacyTable>,
-        limit: Option<u32>,
+        limit: Option<IterationLimit>,
         logic: IterationLogic<'a>,
     ) -> Result<(Vec<LegacyTable>, Vec<LegacyTable>)> {
         let mut scope = self.scope.clone();
-        if let Some(v) = limit {
-            if v <= 1 {
+        if let Some(limit) = limit {
+            if limit.iterations <= 1 {
                 return Err(Error::IterationLimitTooSmall);
             }
         }
@@ -2995,6 +4186,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> DataflowGraphInner<S> {
                     inner_table.finish(&mut state, universe_handle, column_handles)
                 })
                 .collect::<Result<_>>()?;
+            state.inspect_iterations();
             Ok((result, result_with_universe))
         })
     }
@@ -3410,19 +4602,21 @@ where
 struct AfterIterate<'g, O: MaybeTotalScope, I: MaybeTotalScope> {
     outer: &'g mut DataflowGraphInner<O>,
     inner: &'g DataflowGraphInner<I>,
-    limit: Option<u32>,
+    limit: Option<IterationLimit>,
+    updates: RefCell<Vec<Stream<I, (I::Timestamp, usize)>>>,
 }
 
 impl<'g, 'c, S: MaybeTotalScope> AfterIterate<'g, S, Child<'c, S, Product<S::Timestamp, u32>>> {
     fn new(
         outer: &'g mut DataflowGraphInner<S>,
         inner: &'g DataflowGraphInner<Child<'c, S, Product<S::MaybeTotalTimestamp, u32>>>,
-        limit: Option<u32>,
+        limit: Option<IterationLimit>,
     ) -> Self {
         Self {
             outer,
             inner,
             limit,
+            updates: RefCell::new(Vec::new()),
         }
     }
 
@@ -3433,17 +4627,53 @@ impl<'g, 'c, S: MaybeTotalScope> AfterIterate<'g, S, Child<'c, S, Product<S::Tim
     where
         D: Data,
     {
+        self.updates.borrow_mut().push(
+            collection
+                .inner
+                .map(|(_data, time, diff)| (time, diff.unsigned_abs())),
+        );
         if let Some(limit) = self.limit {
+            let mut updates = collection.inner.clone();
+            if limit.fail {
+                let error_reporter = self.inner.error_reporter.clone();
+                updates = updates.inspect(move |(_data, time, _diff)| {
+                    if time.inner >= limit.iterations - 1 {
+                        error_reporter.report_and_panic(Error::IterationLimitExceeded {
+                            limit: limit.iterations,
+                        });
+                    }
+                });
+            }
             Cow::Owned(
-                collection
-                    .inner
-                    .filter(move |(_data, time, _diff)| time.inner < limit - 1)
+                updates
+                    .filter(move |(_data, time, _diff)| time.inner < limit.iterations - 1)
                     .as_collection(),
             )
         } else {
             Cow::Borrowed(collection)
         }
     }
+
+    fn inspect_iterations(&self) {
+        let updates = self.updates.take();
+        if updates.is_empty() {
+            return;
+        }
+        self.inner
+            .scope
+            .clone()
+            .concatenate(updates)
+            .inspect_iterations(|event| match event {
+                IterationEvent::Iteration {
+                    time,
+                    iteration,
+                    updates,
+                } => debug!("iteration {iteration} at time {time:?}: {updates} updates"),
+                IterationEvent::Converged { time, iterations } => {
+                    debug!("fixed point at time {time:?} reached after {iterations} iterations");
+                }
+            });
+    }
 }
 
 fn extract_handles<U, C>(
@@ -3483,6 +4713,10 @@ impl<S: MaybeTotalScope> InnerDataflowGraph<S> {
             ignore_asserts,
             None,
             global_persistent_storage,
+            false,
+            SharedServedTables::default(),
+            SharedShutdownRequest::default(),
+            None,
         )?)))
     }
 }
@@ -3617,6 +4851,7 @@ where
         column_paths: Vec<ColumnPath>,
         table_properties: Arc<TableProperties>,
         trace: Trace,
+        max_in_flight: Option<usize>,
     ) -> Result<TableHandle> {
         self.0.borrow_mut().async_apply_table(
             function,
@@ -3624,48 +4859,98 @@ where
             column_paths,
             table_properties,
             trace,
+            max_in_flight,
         )
     }
 
-    fn filter_table(
+    fn batch_apply_table(
+        &self,
+        function: Arc<dyn Fn(Vec<Vec<Value>>) -> DynResult<Vec<Value>> + Send + Sync>,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        table_properties: Arc<TableProperties>,
+        trace: Trace,
+        max_batch_size: Option<usize>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().batch_apply_table(
+            function,
+            table_handle,
+            column_paths,
+            table_properties,
+            trace,
+            max_batch_size,
+        )
+    }
+
+    fn filter_table(
+        &self,
+        table_handle: TableHandle,
+        filtering_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0
+            .borrow_mut()
+            .filter_table(table_handle, filtering_column_path, table_properties)
+    }
+
+    fn forget(
+        &self,
+        _table_handle: TableHandle,
+        _threshold_time_column_path: ColumnPath,
+        _current_time_column_path: ColumnPath,
+        _mark_forgetting_records: bool,
+        _table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        Err(Error::NotSupportedInIteration)
+    }
+
+    fn forget_immediately(
+        &self,
+        _table_handle: TableHandle,
+        _table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        Err(Error::NotSupportedInIteration)
+    }
+
+    fn filter_out_results_of_forgetting(
         &self,
-        table_handle: TableHandle,
-        filtering_column_path: ColumnPath,
-        table_properties: Arc<TableProperties>,
+        _table_handle: TableHandle,
+        _table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle> {
-        self.0
-            .borrow_mut()
-            .filter_table(table_handle, filtering_column_path, table_properties)
+        Err(Error::NotSupportedInIteration)
     }
 
-    fn forget(
+    fn expire_after(
         &self,
         _table_handle: TableHandle,
-        _threshold_time_column_path: ColumnPath,
-        _current_time_column_path: ColumnPath,
-        _mark_forgetting_records: bool,
+        _duration: u64,
         _table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle> {
         Err(Error::NotSupportedInIteration)
     }
 
-    fn forget_immediately(
+    fn deduplicate_within(
         &self,
         _table_handle: TableHandle,
+        _fingerprint_column_path: ColumnPath,
+        _time_column_path: ColumnPath,
+        _expiration_column_path: ColumnPath,
         _table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle> {
         Err(Error::NotSupportedInIteration)
     }
 
-    fn filter_out_results_of_forgetting(
+    fn freeze(
         &self,
         _table_handle: TableHandle,
+        _threshold_time_column_path: ColumnPath,
+        _current_time_column_path: ColumnPath,
         _table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle> {
         Err(Error::NotSupportedInIteration)
     }
 
-    fn freeze(
+    fn freeze_late(
         &self,
         _table_handle: TableHandle,
         _threshold_time_column_path: ColumnPath,
@@ -3675,6 +4960,16 @@ where
         Err(Error::NotSupportedInIteration)
     }
 
+    fn reshard_table(
+        &self,
+        _table_handle: TableHandle,
+        _column_paths: Vec<ColumnPath>,
+        _parallelism: Option<usize>,
+        _table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        Err(Error::NotSupportedInIteration)
+    }
+
     fn buffer(
         &self,
         _table_handle: TableHandle,
@@ -3753,6 +5048,14 @@ where
             .flatten_table(table_handle, flatten_column_path, table_properties)
     }
 
+    fn changelog_table(
+        &self,
+        _table_handle: TableHandle,
+        _table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        Err(Error::NotSupportedInIteration)
+    }
+
     fn sort_table(
         &self,
         table_handle: TableHandle,
@@ -3768,6 +5071,76 @@ where
         )
     }
 
+    fn session_window_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        max_gap: Value,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().session_window_table(
+            table_handle,
+            key_column_path,
+            instance_column_path,
+            max_gap,
+            table_properties,
+        )
+    }
+
+    fn top_n_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        n: usize,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().top_n_table(
+            table_handle,
+            key_column_path,
+            instance_column_path,
+            n,
+            descending,
+            table_properties,
+        )
+    }
+
+    fn rank_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().rank_table(
+            table_handle,
+            key_column_path,
+            instance_column_path,
+            descending,
+            table_properties,
+        )
+    }
+
+    fn cumulative_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        reducers: Vec<ReducerData>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().cumulative_table(
+            table_handle,
+            key_column_path,
+            instance_column_path,
+            reducers,
+            table_properties,
+        )
+    }
+
     fn reindex_table(
         &self,
         table_handle: TableHandle,
@@ -3884,7 +5257,7 @@ where
         _iterated: Vec<LegacyTable>,
         _iterated_with_universe: Vec<LegacyTable>,
         _extra: Vec<LegacyTable>,
-        _limit: Option<u32>,
+        _limit: Option<IterationLimit>,
         _logic: IterationLogic<'a>,
     ) -> Result<(Vec<LegacyTable>, Vec<LegacyTable>)> {
         Err(Error::IterationNotPossible)
@@ -3915,6 +5288,7 @@ where
         _parallel_readers: usize,
         _table_properties: Arc<TableProperties>,
         _external_persistent_id: Option<&ExternalPersistentId>,
+        _schema: Vec<SchemaColumn>,
     ) -> Result<TableHandle> {
         Err(Error::IoNotPossible)
     }
@@ -3939,6 +5313,16 @@ where
         Err(Error::IoNotPossible)
     }
 
+    fn serve_table(
+        &self,
+        _table_handle: TableHandle,
+        _column_paths: Vec<ColumnPath>,
+        _name: String,
+        _column_names: Vec<String>,
+    ) -> Result<()> {
+        Err(Error::IoNotPossible)
+    }
+
     fn attach_prober(
         &self,
         _logic: Box<dyn FnMut(ProberStats)>,
@@ -3948,8 +5332,15 @@ where
         Err(Error::IoNotPossible)
     }
 
-    fn probe_table(&self, table_handle: TableHandle, operator_id: usize) -> Result<()> {
-        self.0.borrow_mut().probe_table(table_handle, operator_id)
+    fn probe_table(
+        &self,
+        table_handle: TableHandle,
+        operator_id: usize,
+        state_limit: Option<OperatorStateLimit>,
+    ) -> Result<()> {
+        self.0
+            .borrow_mut()
+            .probe_table(table_handle, operator_id, state_limit)
     }
 }
 
@@ -3958,12 +5349,17 @@ struct OuterDataflowGraph<S: MaybeTotalScope<MaybeTotalTimestamp = u64>>(
 );
 
 impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> OuterDataflowGraph<S> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         scope: S,
         error_reporter: ErrorReporter,
         ignore_asserts: bool,
         persistence_config: Option<PersistenceManagerOuterConfig>,
         global_persistent_storage: Option<SharedWorkersPersistenceCoordinator>,
+        consistent_outputs: bool,
+        served_tables: SharedServedTables,
+        shutdown_request: SharedShutdownRequest,
+        pending_work: Option<Rc<PendingWorkTracker>>,
     ) -> Result<Self> {
         let worker_idx = scope.index();
         let total_workers = scope.peers();
@@ -3973,6 +5369,10 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> OuterDataflowGraph<S> {
             ignore_asserts,
             persistence_config.map(|cfg| cfg.into_inner(worker_idx, total_workers)),
             global_persistent_storage,
+            consistent_outputs,
+            served_tables,
+            shutdown_request,
+            pending_work,
         )?)))
     }
 }
@@ -4104,6 +5504,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
         column_paths: Vec<ColumnPath>,
         table_properties: Arc<TableProperties>,
         trace: Trace,
+        max_in_flight: Option<usize>,
     ) -> Result<TableHandle> {
         self.0.borrow_mut().async_apply_table(
             function,
@@ -4111,6 +5512,26 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
             column_paths,
             table_properties,
             trace,
+            max_in_flight,
+        )
+    }
+
+    fn batch_apply_table(
+        &self,
+        function: Arc<dyn Fn(Vec<Vec<Value>>) -> DynResult<Vec<Value>> + Send + Sync>,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        table_properties: Arc<TableProperties>,
+        trace: Trace,
+        max_batch_size: Option<usize>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().batch_apply_table(
+            function,
+            table_handle,
+            column_paths,
+            table_properties,
+            trace,
+            max_batch_size,
         )
     }
 
@@ -4177,6 +5598,34 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
             .filter_out_results_of_forgetting(table_handle, table_properties)
     }
 
+    fn expire_after(
+        &self,
+        table_handle: TableHandle,
+        duration: u64,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0
+            .borrow_mut()
+            .expire_after(table_handle, duration, table_properties)
+    }
+
+    fn deduplicate_within(
+        &self,
+        table_handle: TableHandle,
+        fingerprint_column_path: ColumnPath,
+        time_column_path: ColumnPath,
+        expiration_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().deduplicate_within(
+            table_handle,
+            fingerprint_column_path,
+            time_column_path,
+            expiration_column_path,
+            table_properties,
+        )
+    }
+
     fn freeze(
         &self,
         table_handle: TableHandle,
@@ -4192,6 +5641,33 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
         )
     }
 
+    fn freeze_late(
+        &self,
+        table_handle: TableHandle,
+        threshold_time_column_path: ColumnPath,
+        current_time_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().freeze_late(
+            table_handle,
+            threshold_time_column_path,
+            current_time_column_path,
+            table_properties,
+        )
+    }
+
+    fn reshard_table(
+        &self,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        parallelism: Option<usize>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0
+            .borrow_mut()
+            .reshard_table(table_handle, column_paths, parallelism, table_properties)
+    }
+
     fn buffer(
         &self,
         table_handle: TableHandle,
@@ -4275,6 +5751,16 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
             .flatten_table(table_handle, flatten_column_path, table_properties)
     }
 
+    fn changelog_table(
+        &self,
+        table_handle: TableHandle,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0
+            .borrow_mut()
+            .changelog_table(table_handle, table_properties)
+    }
+
     fn sort_table(
         &self,
         table_handle: TableHandle,
@@ -4290,6 +5776,76 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
         )
     }
 
+    fn session_window_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        max_gap: Value,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().session_window_table(
+            table_handle,
+            key_column_path,
+            instance_column_path,
+            max_gap,
+            table_properties,
+        )
+    }
+
+    fn top_n_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        n: usize,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().top_n_table(
+            table_handle,
+            key_column_path,
+            instance_column_path,
+            n,
+            descending,
+            table_properties,
+        )
+    }
+
+    fn rank_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().rank_table(
+            table_handle,
+            key_column_path,
+            instance_column_path,
+            descending,
+            table_properties,
+        )
+    }
+
+    fn cumulative_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        reducers: Vec<ReducerData>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.0.borrow_mut().cumulative_table(
+            table_handle,
+            key_column_path,
+            instance_column_path,
+            reducers,
+            table_properties,
+        )
+    }
+
     fn reindex_table(
         &self,
         table_handle: TableHandle,
@@ -4406,7 +5962,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
         iterated: Vec<LegacyTable>,
         iterated_with_universe: Vec<LegacyTable>,
         extra: Vec<LegacyTable>,
-        limit: Option<u32>,
+        limit: Option<IterationLimit>,
         logic: IterationLogic<'a>,
     ) -> Result<(Vec<LegacyTable>, Vec<LegacyTable>)> {
         self.0
@@ -4439,6 +5995,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
         parallel_readers: usize,
         table_properties: Arc<TableProperties>,
         external_persistent_id: Option<&ExternalPersistentId>,
+        schema: Vec<SchemaColumn>,
     ) -> Result<TableHandle> {
         self.0.borrow_mut().connector_table(
             reader,
@@ -4447,6 +6004,7 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
             parallel_readers,
             table_properties,
             external_persistent_id,
+            schema,
         )
     }
 
@@ -4462,6 +6020,18 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
             .output_table(data_sink, data_formatter, table_handle, column_paths)
     }
 
+    fn serve_table(
+        &self,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        name: String,
+        column_names: Vec<String>,
+    ) -> Result<()> {
+        self.0
+            .borrow_mut()
+            .serve_table(table_handle, column_paths, name, column_names)
+    }
+
     fn attach_prober(
         &self,
         logic: Box<dyn FnMut(ProberStats)>,
@@ -4475,8 +6045,15 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = u64>> Graph for OuterDataflowGraph
         )
     }
 
-    fn probe_table(&self, table_handle: TableHandle, operator_id: usize) -> Result<()> {
-        self.0.borrow_mut().probe_table(table_handle, operator_id)
+    fn probe_table(
+        &self,
+        table_handle: TableHandle,
+        operator_id: usize,
+        state_limit: Option<OperatorStateLimit>,
+    ) -> Result<()> {
+        self.0
+            .borrow_mut()
+            .probe_table(table_handle, operator_id, state_limit)
     }
 }
 
@@ -4504,15 +6081,45 @@ where
     parse_env_var(name)?.ok_or_else(|| format!("{name} is not set"))
 }
 
+/// Creates the timely configuration from environment variables.
+///
+/// Processes of a cluster communicate over TCP with the addresses listed in
+/// `PATHWAY_ADDRESSES`, separated by commas, where the `i`-th address belongs to the
+/// process with ID `i`, running workers `i * threads` to `(i + 1) * threads - 1`.
+/// Without `PATHWAY_ADDRESSES`, all processes run on the local machine and listen on
+/// consecutive ports starting at `PATHWAY_FIRST_PORT`. With persistence, the processes
+/// agree on the checkpoints through the metadata storage, so on multiple machines it
+/// has to be shared by them, like S3 or a filesystem mounted on all of them.
 pub fn config_from_env() -> Result<(Config, usize), String> {
     let mut threads: usize = parse_env_var("PATHWAY_THREADS")?.unwrap_or(1);
-    let mut processes: usize = parse_env_var("PATHWAY_PROCESSES")?.unwrap_or(1);
+    let addresses: Option<Vec<String>> =
+        parse_env_var::<String>("PATHWAY_ADDRESSES")?.map(|addresses| {
+            addresses
+                .split(',')
+                .map(|address| address.trim().to_string())
+                .collect()
+        });
+    let mut processes: usize = match (&addresses, parse_env_var("PATHWAY_PROCESSES")?) {
+        (Some(addresses), Some(processes)) if processes != addresses.len() => {
+            return Err(format!(
+                "PATHWAY_PROCESSES is {processes}, but PATHWAY_ADDRESSES lists {} addresses",
+                addresses.len()
+            ));
+        }
+        (Some(addresses), _) => addresses.len(),
+        (None, processes) => processes.unwrap_or(1),
+    };
     if threads == 0 {
         return Err("Can't run with 0 threads".to_string());
     }
     if processes == 0 {
         return Err("Can't run with 0 processes".to_string());
     }
+    if addresses.is_some() && processes > MAX_WORKERS {
+        return Err(format!(
+            "Can't run more than {MAX_WORKERS} processes, but PATHWAY_ADDRESSES lists {processes} addresses"
+        ));
+    }
     let workers = threads * processes;
     if workers > MAX_WORKERS {
         warn!("{workers} is greater than the the maximum allowed number of workers ({MAX_WORKERS}), reducing");
@@ -4529,10 +6136,14 @@ pub fn config_from_env() -> Result<(Config, usize), String> {
         if process_id >= processes {
             return Err(format!("Process ID {process_id} is too big"));
         }
-        let first_port: usize = parse_env_var_required("PATHWAY_FIRST_PORT")?;
-        let addresses = (0..processes)
-            .map(|id| format!("127.0.0.1:{}", first_port + id))
-            .collect();
+        let addresses = if let Some(addresses) = addresses {
+            addresses
+        } else {
+            let first_port: usize = parse_env_var_required("PATHWAY_FIRST_PORT")?;
+            (0..processes)
+                .map(|id| format!("127.0.0.1:{}", first_port + id))
+                .collect()
+        };
         Config {
             communication: CommunicationConfig::Cluster {
                 threads,
@@ -4564,6 +6175,11 @@ pub fn run_with_new_dataflow_graph<R, R2>(
     with_http_server: bool,
     persistence_config: Option<PersistenceManagerOuterConfig>,
     num_workers: usize,
+    consistent_outputs: bool,
+    otlp_endpoint: Option<String>,
+    shutdown_request: SharedShutdownRequest,
+    topology: Vec<OperatorNode>,
+    alert_thresholds: Option<AlertThresholds>,
 ) -> Result<Vec<R2>>
 where
     R: 'static,
@@ -4574,6 +6190,17 @@ where
     }
     if !YOLO.is_empty() {
         info!("Running in YOLO mode: {}", YOLO.iter().format(", "));
+        warn!(
+            "The keys have {KEY_BITS} bits, so the rows of the tables of more than about 2^{} \
+            rows are likely to get the same keys and be merged",
+            KEY_BITS / 2
+        );
+    }
+    if let Ok(KeyHashing::SipHash { per_run_seed, .. }) = KeyHashing::configured() {
+        info!("Deriving the keys with SipHash");
+        if per_run_seed && persistence_config.is_some() {
+            return Err(Error::PerRunKeyHashingSeedWithPersistence);
+        }
     }
     let (error_reporter, error_receiver) = ErrorReporter::create();
     let failed = Arc::new(AtomicBool::new(false));
@@ -4582,14 +6209,50 @@ where
         CommunicationConfig::Cluster { process, .. } => process,
         _ => 0,
     };
-    let global_persistent_storage = persistence_config.as_ref().map(|cfg| {
-        Arc::new(Mutex::new(
-            cfg.create_workers_persistence_coordinator(num_workers),
-        ))
-    });
+    let local_workers = match config.communication {
+        CommunicationConfig::Cluster {
+            threads, process, ..
+        } => process * threads..(process + 1) * threads,
+        _ => 0..num_workers,
+    };
+    let global_persistent_storage = persistence_config
+        .as_ref()
+        .map(|cfg| cfg.create_workers_persistence_coordinator(local_workers, num_workers))
+        .transpose()?
+        .map(|coordinator| Arc::new(Mutex::new(coordinator)));
+
+    let persistence_stats = persistence_config
+        .as_ref()
+        .map(|config| config.persistence_stats().clone());
+
+    // Dropped after the workers are joined, so that all spans are exported
+    let telemetry = otlp_endpoint
+        .as_deref()
+        .map(Telemetry::init)
+        .transpose()
+        .map_err(Error::TraceExportSetupFailed)?;
+    let tracing_enabled = telemetry.is_some();
+    let _allocator_stats_logger =
+        match parse_env_var::<u64>("PATHWAY_ALLOCATOR_STATS_LOG_PERIOD_SECS") {
+            Ok(Some(period_secs)) if period_secs > 0 => Some(AllocatorStatsLogger::start(
+                Duration::from_secs(period_secs),
+            )),
+            Ok(_) => None,
+            Err(message) => {
+                warn!("Not logging the statistics of the allocator: {message}");
+                None
+            }
+        };
+    let served_tables = SharedServedTables::default();
+    let pending_records = SharedPendingRecords::default();
+    let topology: Arc<[OperatorNode]> = topology.into();
+    let alerts = SharedAlerts::default();
 
     let guards = execute(config, move |worker| {
         catch_unwind(AssertUnwindSafe(|| {
+            shutdown_request.register_worker(thread::current());
+            log_context::set_worker(worker.index());
+
             if let Ok(addr) = env::var("DIFFERENTIAL_LOG_ADDR") {
                 if let Ok(stream) = std::net::TcpStream::connect(&addr) {
                     differential_dataflow::logging::enable(worker, stream);
@@ -4599,6 +6262,9 @@ where
                 }
             }
 
+            let pending_work = (monitoring_level == MonitoringLevel::All || with_http_server)
+                .then(|| Rc::new(PendingWorkTracker::install(worker, pending_records.clone())));
+
             let (
                 res,
                 mut pollers,
@@ -4607,9 +6273,12 @@ where
                 input_probe,
                 output_probe,
                 intermediate_probes,
+                state_sizes,
                 mut probers,
+                output_latencies,
                 progress_reporter_runner,
                 http_server_runner,
+                alerting_runner,
             ) = worker.dataflow::<u64, _, _>(|scope| {
                 let graph = OuterDataflowGraph::new(
                     scope.clone(),
@@ -4617,13 +6286,30 @@ where
                     ignore_asserts,
                     persistence_config.clone(),
                     global_persistent_storage.clone(),
+                    consistent_outputs,
+                    served_tables.clone(),
+                    shutdown_request.clone(),
+                    pending_work.clone(),
                 )
                 .unwrap_with_reporter(&error_reporter);
                 let res = logic(&graph).unwrap_with_reporter(&error_reporter);
+                graph.0.borrow_mut().close_output_barrier();
                 let progress_reporter_runner =
                     maybe_run_reporter(&monitoring_level, &graph, stats_monitor.clone());
-                let http_server_runner =
-                    maybe_run_http_server_thread(with_http_server, &graph, process_id);
+                let alerting_runner =
+                    maybe_run_alerting(alert_thresholds.as_ref(), &graph, alerts.clone());
+                let http_server_runner = maybe_run_http_server_thread(
+                    with_http_server,
+                    &graph,
+                    process_id,
+                    persistence_config
+                        .as_ref()
+                        .map(|config| config.savepoint_requests().clone()),
+                    served_tables.clone(),
+                    shutdown_request.clone(),
+                    topology.clone(),
+                    alerts.clone(),
+                );
                 let graph = graph.0.into_inner();
                 (
                     res,
@@ -4633,12 +6319,17 @@ where
                     graph.input_probe,
                     graph.output_probe,
                     graph.probes,
+                    graph.state_sizes,
                     graph.probers,
+                    graph.output_latencies,
                     progress_reporter_runner,
                     http_server_runner,
+                    alerting_runner,
                 )
             });
 
+            let mut epoch_tracer = tracing_enabled.then(|| EpochTracer::new(worker.index()));
+
             loop {
                 if failed.load(Ordering::SeqCst) {
                     resume_unwind(Box::new("other worker panicked"));
@@ -4649,10 +6340,21 @@ where
                         &input_probe,
                         &output_probe,
                         &intermediate_probes,
+                        &state_sizes,
                         &connector_monitors,
+                        persistence_stats.as_ref(),
+                        &output_latencies,
+                        pending_work.as_deref(),
                     );
                 }
 
+                if let Some(epoch_tracer) = &mut epoch_tracer {
+                    epoch_tracer.update(&input_probe, &output_probe, &intermediate_probes);
+                }
+                log_context::set_epoch(
+                    output_probe.with_frontier(|frontier| frontier.as_option().copied()),
+                );
+
                 let mut next_step_duration = None;
 
                 let iteration_start = SystemTime::now();
@@ -4688,10 +6390,16 @@ where
                     &input_probe,
                     &output_probe,
                     &intermediate_probes,
+                    &state_sizes,
                     &connector_monitors,
+                    persistence_stats.as_ref(),
+                    &output_latencies,
+                    pending_work.as_deref(),
                 );
             }
 
+            drop(epoch_tracer);
+            drop(alerting_runner);
             drop(http_server_runner);
             drop(progress_reporter_runner);
 
@@ -1,28 +1,43 @@
 // Copyright © 2024 Pathway
 
+pub mod batching;
+pub mod cumulative;
+pub mod deduplicate;
+pub mod expire;
 pub mod gradual_broadcast;
+pub mod iteration;
 pub mod output;
 pub mod prev_next;
+pub mod rank;
+pub mod session_window;
 pub mod stateful_reduce;
 pub mod time_column;
+pub mod top_n;
 mod utils;
 
 use std::any::type_name;
+use std::collections::VecDeque;
+use std::mem::take;
 use std::panic::Location;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::operators::arrange::{Arranged, TraceAgent};
 use differential_dataflow::trace::{Batch, Trace, TraceReader};
 use differential_dataflow::{AsCollection, Collection, Data, ExchangeData};
 use futures::stream::FuturesUnordered;
-use futures::StreamExt;
-use futures::{future, Future};
+use futures::task::{waker, Context as TaskContext, Poll};
+use futures::{Future, FutureExt, StreamExt};
+use pyo3::Python;
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
 use timely::dataflow::operators::Exchange as _;
 use timely::dataflow::operators::Operator;
 
 use crate::engine::BatchWrapper;
 
+use self::batching::AdaptiveBatchSize;
+
 use super::maybe_total::{MaybeTotalScope, MaybeTotalSwitch};
 use super::shard::Shard;
 use super::ArrangedBySelf;
@@ -209,20 +224,41 @@ where
         logic: impl FnMut(D) -> D2 + 'static,
     ) -> Collection<S, D2, R>;
 
-    fn map_named_async<F: Future>(
+    /// Maps the collection with an asynchronous function, running at most `max_in_flight`
+    /// futures at once.
+    ///
+    /// The worker doesn't wait for the futures, it is woken up when they make progress.
+    /// Futures can complete in any order, and the result of each of them is emitted
+    /// at the time of its input, which is held back until all its futures complete.
+    fn map_named_async<F: Future + 'static>(
         &self,
         name: &str,
+        max_in_flight: Option<usize>,
         logic: impl Fn(D) -> F + 'static,
     ) -> Collection<S, F::Output, R>
     where
         F::Output: Data;
 
-    fn map_async<F: Future>(&self, logic: impl Fn(D) -> F + 'static) -> Collection<S, F::Output, R>
+    fn map_async<F: Future + 'static>(
+        &self,
+        logic: impl Fn(D) -> F + 'static,
+    ) -> Collection<S, F::Output, R>
     where
         F::Output: Data,
     {
-        self.map_named_async("MapAsync", logic)
+        self.map_named_async("MapAsync", None, logic)
     }
+
+    /// Maps the collection with a function applied to whole batches of data, e.g. to call
+    /// Python once for many rows. The batches consist of all the data available in an
+    /// activation, split into batches of at most `max_batch_size` elements. The function
+    /// returns the results for a batch in the order of its data.
+    fn map_named_batched<D2: Data>(
+        &self,
+        name: &str,
+        max_batch_size: Option<usize>,
+        logic: impl FnMut(Vec<D>) -> Vec<D2> + 'static,
+    ) -> Collection<S, D2, R>;
 }
 
 impl<S, D, R> MapWrapped<S, D, R> for Collection<S, D, R>
@@ -240,6 +276,9 @@ where
     ) -> Collection<S, D2, R> {
         let caller = Location::caller();
         let name = format!("{name} at {caller}");
+        if let BatchWrapper::AdaptiveWithGil(latency_target) = wrapper {
+            return map_adaptive_with_gil(self, &name, latency_target, logic);
+        }
         let mut vector = Vec::new();
         self.inner
             .unary(Pipeline, &name, move |_, _| {
@@ -260,9 +299,10 @@ where
     }
 
     #[track_caller]
-    fn map_named_async<F: Future>(
+    fn map_named_async<F: Future + 'static>(
         &self,
         name: &str,
+        max_in_flight: Option<usize>,
         logic: impl Fn(D) -> F + 'static,
     ) -> Collection<S, F::Output, R>
     where
@@ -270,28 +310,95 @@ where
     {
         let caller = Location::caller();
         let name = format!("{name} at {caller}");
+        let max_in_flight = max_in_flight.unwrap_or(usize::MAX);
+        let scope = self.scope();
         let mut vector = Vec::new();
-        let mut result = Vec::new();
+        let mut pending = VecDeque::new();
+        let mut in_flight = FuturesUnordered::new();
         self.inner
-            .unary(Pipeline, &name, move |_, _| {
+            .unary(Pipeline, &name, move |_, info| {
+                let waker = waker(Arc::new(scope.sync_activator_for(&info.address[..])));
                 move |input, output| {
-                    while let Some((time, data)) = input.next() {
+                    input.for_each(|capability, data| {
                         data.swap(&mut vector);
+                        pending.push_back((capability.retain(), VecDeque::from(take(&mut vector))));
+                    });
 
-                        let futures: FuturesUnordered<_> = vector
-                            .drain(..)
-                            .map(|(data, time, diff)| async { (logic(data).await, time, diff) })
-                            .collect();
+                    let mut context = TaskContext::from_waker(&waker);
+                    loop {
+                        while in_flight.len() < max_in_flight {
+                            let Some((capability, rows)) = pending.front_mut() else {
+                                break;
+                            };
+                            let Some((data, time, diff)) = rows.pop_front() else {
+                                pending.pop_front();
+                                continue;
+                            };
+                            let capability = capability.clone();
+                            in_flight.push(
+                                logic(data).map(move |result| (capability, (result, time, diff))),
+                            );
+                        }
+                        let Poll::Ready(Some((capability, update))) =
+                            in_flight.poll_next_unpin(&mut context)
+                        else {
+                            break;
+                        };
+                        output.session(&capability).give(update);
+                    }
+                }
+            })
+            .as_collection()
+    }
 
-                        assert!(result.is_empty());
-                        result.reserve(futures.len());
+    #[track_caller]
+    fn map_named_batched<D2: Data>(
+        &self,
+        name: &str,
+        max_batch_size: Option<usize>,
+        mut logic: impl FnMut(Vec<D>) -> Vec<D2> + 'static,
+    ) -> Collection<S, D2, R> {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+        let max_batch_size = max_batch_size.unwrap_or(usize::MAX).max(1);
+        let mut vector = Vec::new();
+        self.inner
+            .unary(Pipeline, &name, move |_, _| {
+                move |input, output| {
+                    let mut capabilities = Vec::new();
+                    let mut data = Vec::new();
+                    let mut times = Vec::new();
+                    input.for_each(|capability, message| {
+                        message.swap(&mut vector);
+                        capabilities.push((capability.retain(), vector.len()));
+                        for (datum, time, diff) in vector.drain(..) {
+                            data.push(datum);
+                            times.push((time, diff));
+                        }
+                    });
 
-                        futures::executor::block_on(futures.for_each(|item| {
-                            result.push(item);
-                            future::ready(())
-                        }));
+                    let mut results = Vec::with_capacity(data.len());
+                    let mut data = data.into_iter();
+                    while results.len() < times.len() {
+                        let batch: Vec<D> = data.by_ref().take(max_batch_size).collect();
+                        let batch_len = batch.len();
+                        let batch_results = logic(batch);
+                        assert_eq!(
+                            batch_results.len(),
+                            batch_len,
+                            "batched function should return a result for each element"
+                        );
+                        results.extend(batch_results);
+                    }
 
-                        output.session(&time).give_vec(&mut result);
+                    let mut updates = results.into_iter().zip(times);
+                    for (capability, count) in capabilities {
+                        output.session(&capability).give_iterator(
+                            updates
+                                .by_ref()
+                                .take(count)
+                                .map(|(result, (time, diff))| (result, time, diff)),
+                        );
                     }
                 }
             })
@@ -299,6 +406,66 @@ where
     }
 }
 
+/// Maps the collection holding the GIL for batches of rows, releasing it in between.
+///
+/// Only one batch is processed per activation, so downstream operators and other
+/// Python threads can make progress between batches. The sizes of batches are chosen
+/// by [`AdaptiveBatchSize`] to meet the latency target.
+fn map_adaptive_with_gil<S, D, D2, R>(
+    collection: &Collection<S, D, R>,
+    name: &str,
+    latency_target: Duration,
+    mut logic: impl FnMut(D) -> D2 + 'static,
+) -> Collection<S, D2, R>
+where
+    S: MaybeTotalScope,
+    D: Data,
+    D2: Data,
+    R: Semigroup,
+{
+    let scope = collection.scope();
+    let mut vector = Vec::new();
+    let mut pending = VecDeque::new();
+    let mut batch_size = AdaptiveBatchSize::new(latency_target);
+    collection
+        .inner
+        .unary(Pipeline, name, move |_, info| {
+            let activator = scope.activator_for(&info.address[..]);
+            move |input, output| {
+                input.for_each(|capability, data| {
+                    data.swap(&mut vector);
+                    pending.push_back((capability.retain(), VecDeque::from(take(&mut vector))));
+                });
+
+                let limit = batch_size.batch_size();
+                let start = Instant::now();
+                let mut processed = 0;
+                Python::with_gil(|_| {
+                    while processed < limit {
+                        let Some((capability, rows)) = pending.front_mut() else {
+                            break;
+                        };
+                        let count = rows.len().min(limit - processed);
+                        output.session(capability).give_iterator(
+                            rows.drain(..count)
+                                .map(|(data, time, diff)| (logic(data), time, diff)),
+                        );
+                        processed += count;
+                        if rows.is_empty() {
+                            pending.pop_front();
+                        }
+                    }
+                });
+                batch_size.record(processed, start.elapsed());
+
+                if !pending.is_empty() {
+                    activator.activate();
+                }
+            }
+        })
+        .as_collection()
+}
+
 pub trait Reshard<S, D, R>
 where
     S: MaybeTotalScope,
@@ -0,0 +1,69 @@
+// Copyright © 2024 Pathway
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+const INITIAL_BATCH_SIZE: usize = 64;
+const MAX_BATCH_SIZE: usize = 1 << 20;
+const LATENCY_WINDOW: usize = 100;
+
+/// Chooses sizes of batches so that processing them meets a latency target.
+///
+/// Processing times of recent batches are kept, and their 99th percentile is compared
+/// with the target after every batch. If it exceeds the target, the batch size is
+/// halved. If it is below half of the target and the last batch was full, i.e. the
+/// input keeps up with processing, the batch size is doubled. The kept processing
+/// times are forgotten whenever the batch size changes, as they no longer describe
+/// batches of the current size.
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatchSize {
+    latency_target: Duration,
+    batch_size: usize,
+    latencies: VecDeque<Duration>,
+}
+
+impl AdaptiveBatchSize {
+    pub fn new(latency_target: Duration) -> Self {
+        Self {
+            latency_target,
+            batch_size: INITIAL_BATCH_SIZE,
+            latencies: VecDeque::with_capacity(LATENCY_WINDOW),
+        }
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    pub fn latency_p99(&self) -> Option<Duration> {
+        let mut latencies: Vec<_> = self.latencies.iter().copied().collect();
+        latencies.sort_unstable();
+        let index = (latencies.len() * 99).div_ceil(100).checked_sub(1)?;
+        Some(latencies[index])
+    }
+
+    pub fn record(&mut self, rows: usize, elapsed: Duration) {
+        if rows == 0 {
+            return;
+        }
+        if self.latencies.len() == LATENCY_WINDOW {
+            self.latencies.pop_front();
+        }
+        self.latencies.push_back(elapsed);
+        let p99 = self
+            .latency_p99()
+            .expect("latencies should not be empty after pushing one");
+
+        let new_batch_size = if p99 > self.latency_target {
+            (self.batch_size / 2).max(1)
+        } else if rows >= self.batch_size && p99 * 2 < self.latency_target {
+            (self.batch_size * 2).min(MAX_BATCH_SIZE)
+        } else {
+            self.batch_size
+        };
+        if new_batch_size != self.batch_size {
+            self.batch_size = new_batch_size;
+            self.latencies.clear();
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright © 2024 Pathway
+
+use std::hash::Hash;
+use std::panic::Location;
+
+use differential_dataflow::difference::Abelian;
+use differential_dataflow::operators::Reduce;
+use differential_dataflow::{Collection, ExchangeData};
+
+use crate::engine::dataflow::maybe_total::MaybeTotalScope;
+
+/// Folds the entries of every instance in the order of their values.
+///
+/// Entries are ordered by their value and then by their id. Every entry carries an
+/// argument, and `step` combines the accumulator of the preceding entries with it,
+/// starting from `init`. For every entry, the operator returns its instance, its id
+/// and the accumulator including the entry.
+///
+/// The accumulators of an instance are recomputed whenever any entry of this instance
+/// changes. An update changes the accumulators of all entries following it anyway, so
+/// the cost of an update is linear in the number of entries of the instance.
+pub trait Cumulative<S, I, V, K, X, R>
+where
+    S: MaybeTotalScope,
+    R: Abelian,
+{
+    #[track_caller]
+    fn cumulative<A>(
+        &self,
+        init: A,
+        step: impl Fn(&A, &X) -> A + 'static,
+    ) -> Collection<S, (I, (K, A)), R>
+    where
+        A: ExchangeData,
+    {
+        self.cumulative_named("Cumulative", init, step)
+    }
+
+    fn cumulative_named<A>(
+        &self,
+        name: &str,
+        init: A,
+        step: impl Fn(&A, &X) -> A + 'static,
+    ) -> Collection<S, (I, (K, A)), R>
+    where
+        A: ExchangeData;
+}
+
+impl<S, I, V, K, X, R> Cumulative<S, I, V, K, X, R> for Collection<S, (I, ((V, K), X)), R>
+where
+    S: MaybeTotalScope,
+    I: ExchangeData + Hash,
+    V: ExchangeData,
+    K: ExchangeData,
+    X: ExchangeData,
+    R: ExchangeData + Abelian,
+{
+    #[track_caller]
+    fn cumulative_named<A>(
+        &self,
+        name: &str,
+        init: A,
+        step: impl Fn(&A, &X) -> A + 'static,
+    ) -> Collection<S, (I, (K, A)), R>
+    where
+        A: ExchangeData,
+    {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+
+        self.reduce_named(
+            &name,
+            move |_instance, input: &[(&((V, K), X), R)], output| {
+                let mut accumulator = init.clone();
+                for (((_value, id), argument), count) in input {
+                    accumulator = step(&accumulator, argument);
+                    output.push(((id.clone(), accumulator.clone()), count.clone()));
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright © 2024 Pathway
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::panic::Location;
+
+use differential_dataflow::consolidation::consolidate;
+use differential_dataflow::{AsCollection, Collection, ExchangeData};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Capability, Operator};
+use timely::dataflow::Scope;
+
+use crate::engine::dataflow::shard::Shard;
+
+/// Drops entries whose fingerprint was accepted recently.
+///
+/// Every entry comes with a fingerprint, its event time and its expiration time.
+/// An entry is accepted if no entry with the same fingerprint was accepted with an
+/// expiration time larger than the event time of this entry. Only fingerprints and
+/// expiration times of accepted entries are stored, and they are pruned once the
+/// watermark, the largest event time seen, reaches their expiration time. Entries
+/// arriving with an expiration time not larger than the watermark are dropped, as
+/// their duplicates might have been pruned already. The watermark is maintained
+/// separately by every worker.
+///
+/// Entries are processed in time order once the input frontier passes their time,
+/// and entries of the same time are processed in the order of their event times.
+/// Deletions are ignored, so the output only grows.
+pub trait DeduplicateWithin<S, F, T, D>
+where
+    S: Scope<Timestamp = u64>,
+{
+    #[track_caller]
+    fn deduplicate_within(&self) -> Collection<S, D, isize> {
+        self.deduplicate_within_named("DeduplicateWithin")
+    }
+
+    fn deduplicate_within_named(&self, name: &str) -> Collection<S, D, isize>;
+}
+
+impl<S, F, T, D> DeduplicateWithin<S, F, T, D> for Collection<S, (F, ((T, T), D)), isize>
+where
+    S: Scope<Timestamp = u64>,
+    F: ExchangeData + Shard + Hash,
+    T: ExchangeData,
+    D: ExchangeData,
+{
+    #[track_caller]
+    fn deduplicate_within_named(&self, name: &str) -> Collection<S, D, isize> {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+
+        self.inner
+            .unary_frontier(
+                Exchange::new(
+                    |((fingerprint, _entry), _time, _diff): &((F, ((T, T), D)), u64, isize)| {
+                        fingerprint.shard()
+                    },
+                ),
+                &name,
+                move |_capability, _info| {
+                    let mut input_buffer = Vec::new();
+                    let mut pending: BTreeMap<u64, Vec<((F, ((T, T), D)), isize)>> =
+                        BTreeMap::new();
+                    let mut accepted: HashMap<F, T> = HashMap::new();
+                    let mut expirations: BTreeMap<T, Vec<F>> = BTreeMap::new();
+                    let mut watermark: Option<T> = None;
+                    let mut maybe_cap: Option<Capability<u64>> = None;
+
+                    move |input, output| {
+                        input.for_each(|capability, data| {
+                            data.swap(&mut input_buffer);
+                            for (record, time, diff) in input_buffer.drain(..) {
+                                if maybe_cap.as_ref().map_or(true, |cap| cap.time() > &time) {
+                                    maybe_cap = Some(capability.delayed(&time));
+                                }
+                                pending.entry(time).or_default().push((record, diff));
+                            }
+                        });
+
+                        let Some(cap) = maybe_cap.as_mut() else {
+                            return;
+                        };
+                        let frontier = input.frontier().frontier();
+                        while let Some(time) = pending
+                            .keys()
+                            .next()
+                            .copied()
+                            .filter(|time| !frontier.less_equal(time))
+                        {
+                            cap.downgrade(&time);
+                            let mut session = output.session(cap);
+
+                            let mut updates = pending.remove(&time).unwrap();
+                            consolidate(&mut updates);
+                            let mut insertions: Vec<_> = updates
+                                .into_iter()
+                                .filter_map(|(record, diff)| (diff > 0).then_some(record))
+                                .collect();
+                            insertions.sort_by(
+                                |(_, ((event_time_1, _), data_1)),
+                                 (_, ((event_time_2, _), data_2))| {
+                                    event_time_1.cmp(event_time_2).then(data_1.cmp(data_2))
+                                },
+                            );
+
+                            for (fingerprint, ((event_time, expires_at), data)) in insertions {
+                                if watermark.as_ref().is_some_and(|mark| expires_at <= *mark) {
+                                    continue;
+                                }
+                                if watermark.as_ref().map_or(true, |mark| *mark < event_time) {
+                                    watermark = Some(event_time.clone());
+                                }
+                                if accepted
+                                    .get(&fingerprint)
+                                    .is_some_and(|previous| *previous > event_time)
+                                {
+                                    continue;
+                                }
+                                accepted.insert(fingerprint.clone(), expires_at.clone());
+                                expirations.entry(expires_at).or_default().push(fingerprint);
+                                session.give((data, time, 1));
+                            }
+
+                            let Some(mark) = watermark.as_ref() else {
+                                continue;
+                            };
+                            while let Some(entry) = expirations.first_entry() {
+                                if entry.key() > mark {
+                                    break;
+                                }
+                                let (expires_at, fingerprints) = entry.remove_entry();
+                                for fingerprint in fingerprints {
+                                    if accepted.get(&fingerprint) == Some(&expires_at) {
+                                        accepted.remove(&fingerprint);
+                                    }
+                                }
+                            }
+                        }
+
+                        match pending.keys().next() {
+                            Some(time) => cap.downgrade(time),
+                            None => maybe_cap = None,
+                        }
+                    }
+                },
+            )
+            .as_collection()
+    }
+}
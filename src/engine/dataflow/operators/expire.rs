@@ -0,0 +1,140 @@
+// Copyright © 2024 Pathway
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::panic::Location;
+
+use differential_dataflow::consolidation::consolidate;
+use differential_dataflow::{AsCollection, Collection, ExchangeData};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Capability, Operator};
+use timely::dataflow::Scope;
+
+use crate::engine::dataflow::shard::Shard;
+
+struct AliveEntry {
+    count: isize,
+    expires_at: u64,
+}
+
+/// Retracts every entry `duration` after it was inserted.
+///
+/// The time is the time of the computation, not a time stored in the entries.
+/// An entry that is updated or inserted again gets a new expiration time. Deletions
+/// of entries that have already expired are ignored, so the output never contains
+/// negative counts. Entries are processed in time order once the input frontier
+/// passes their time, which delays the output until the frontier moves.
+pub trait ExpireAfter<S, K, V>
+where
+    S: Scope<Timestamp = u64>,
+{
+    #[track_caller]
+    fn expire_after(&self, duration: u64) -> Collection<S, (K, V), isize> {
+        self.expire_after_named("ExpireAfter", duration)
+    }
+
+    fn expire_after_named(&self, name: &str, duration: u64) -> Collection<S, (K, V), isize>;
+}
+
+impl<S, K, V> ExpireAfter<S, K, V> for Collection<S, (K, V), isize>
+where
+    S: Scope<Timestamp = u64>,
+    K: ExchangeData + Shard + Hash,
+    V: ExchangeData + Hash,
+{
+    #[track_caller]
+    fn expire_after_named(&self, name: &str, duration: u64) -> Collection<S, (K, V), isize> {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+
+        self.inner
+            .unary_frontier(
+                Exchange::new(|((key, _value), _time, _diff): &((K, V), u64, isize)| key.shard()),
+                &name,
+                move |_capability, _info| {
+                    let mut input_buffer = Vec::new();
+                    let mut pending: BTreeMap<u64, Vec<((K, V), isize)>> = BTreeMap::new();
+                    let mut expirations: BTreeMap<u64, Vec<(K, V)>> = BTreeMap::new();
+                    let mut alive: HashMap<(K, V), AliveEntry> = HashMap::new();
+                    let mut maybe_cap: Option<Capability<u64>> = None;
+
+                    move |input, output| {
+                        input.for_each(|capability, data| {
+                            data.swap(&mut input_buffer);
+                            for (record, time, diff) in input_buffer.drain(..) {
+                                if maybe_cap.as_ref().map_or(true, |cap| cap.time() > &time) {
+                                    maybe_cap = Some(capability.delayed(&time));
+                                }
+                                pending.entry(time).or_default().push((record, diff));
+                            }
+                        });
+
+                        let Some(cap) = maybe_cap.as_mut() else {
+                            return;
+                        };
+                        let frontier = input.frontier().frontier();
+                        loop {
+                            let next_time = [pending.keys().next(), expirations.keys().next()]
+                                .into_iter()
+                                .flatten()
+                                .min()
+                                .copied();
+                            let Some(time) = next_time.filter(|time| !frontier.less_equal(time))
+                            else {
+                                break;
+                            };
+                            cap.downgrade(&time);
+                            let mut session = output.session(cap);
+
+                            let mut updates = pending.remove(&time).unwrap_or_default();
+                            consolidate(&mut updates);
+                            for (data, diff) in updates {
+                                if diff > 0 {
+                                    let expires_at = time.saturating_add(duration);
+                                    let entry = alive.entry(data.clone()).or_insert(AliveEntry {
+                                        count: 0,
+                                        expires_at,
+                                    });
+                                    entry.count += diff;
+                                    entry.expires_at = expires_at;
+                                    expirations
+                                        .entry(expires_at)
+                                        .or_default()
+                                        .push(data.clone());
+                                    session.give((data, time, diff));
+                                } else if let Some(entry) = alive.get_mut(&data) {
+                                    let retracted = diff.max(-entry.count);
+                                    entry.count += retracted;
+                                    if entry.count == 0 {
+                                        alive.remove(&data);
+                                    }
+                                    session.give((data, time, retracted));
+                                }
+                            }
+
+                            for data in expirations.remove(&time).unwrap_or_default() {
+                                if alive
+                                    .get(&data)
+                                    .is_some_and(|entry| entry.expires_at == time)
+                                {
+                                    let entry = alive.remove(&data).unwrap();
+                                    session.give((data, time, -entry.count));
+                                }
+                            }
+                        }
+
+                        let next_time = [pending.keys().next(), expirations.keys().next()]
+                            .into_iter()
+                            .flatten()
+                            .min()
+                            .copied();
+                        match next_time {
+                            Some(time) => cap.downgrade(&time),
+                            None => maybe_cap = None,
+                        }
+                    }
+                },
+            )
+            .as_collection()
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright © 2024 Pathway
+
+use std::collections::{BTreeMap, HashMap};
+use std::panic::Location;
+
+use timely::dataflow::channels::pact::{Exchange, Pipeline};
+use timely::dataflow::operators::Operator;
+use timely::dataflow::{Scope, Stream};
+use timely::order::{PartialOrder, Product};
+use timely::progress::Timestamp;
+
+/// Progress of an iterative computation, as seen by [`InspectIterations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IterationEvent<T> {
+    /// All updates produced by the iteration number `iteration` (counting from 1) for
+    /// the outer time `time` were seen.
+    Iteration {
+        time: T,
+        iteration: u32,
+        updates: usize,
+    },
+    /// No more iterations will run for the outer time `time`. The last iteration that
+    /// changed anything was the iteration number `iterations`.
+    Converged { time: T, iterations: u32 },
+}
+
+/// Reports the number of updates produced by every round of an iterative computation.
+///
+/// The input contains the number of updates per time of the inner scope. Counts are
+/// summed in every worker and sent to the first worker, which calls `logic` once the
+/// input frontier passes a round and once all rounds for an outer time are finished.
+/// Rounds without updates are not reported.
+pub trait InspectIterations<S, T>
+where
+    S: Scope<Timestamp = Product<T, u32>>,
+    T: Timestamp,
+{
+    #[track_caller]
+    fn inspect_iterations(&self, logic: impl FnMut(IterationEvent<T>) + 'static) {
+        self.inspect_iterations_named("InspectIterations", logic);
+    }
+
+    fn inspect_iterations_named(&self, name: &str, logic: impl FnMut(IterationEvent<T>) + 'static);
+}
+
+impl<S, T> InspectIterations<S, T> for Stream<S, (Product<T, u32>, usize)>
+where
+    S: Scope<Timestamp = Product<T, u32>>,
+    T: Timestamp,
+{
+    #[track_caller]
+    fn inspect_iterations_named(
+        &self,
+        name: &str,
+        mut logic: impl FnMut(IterationEvent<T>) + 'static,
+    ) {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+
+        let mut input_buffer = Vec::new();
+        let counts = self.unary(
+            Pipeline,
+            &format!("{name}::count"),
+            move |_capability, _info| {
+                let mut counts: HashMap<Product<T, u32>, usize> = HashMap::new();
+                move |input, output| {
+                    input.for_each(|capability, data| {
+                        data.swap(&mut input_buffer);
+                        for (time, updates) in input_buffer.drain(..) {
+                            *counts.entry(time).or_default() += updates;
+                        }
+                        for (time, updates) in counts.drain() {
+                            output
+                                .session(&capability.delayed(&time))
+                                .give((time, updates));
+                        }
+                    });
+                }
+            },
+        );
+
+        let mut input_buffer = Vec::new();
+        let mut pending: BTreeMap<Product<T, u32>, usize> = BTreeMap::new();
+        let mut last_iterations: BTreeMap<T, u32> = BTreeMap::new();
+        counts.sink(
+            Exchange::new(|_: &(Product<T, u32>, usize)| 0),
+            &name,
+            move |input| {
+                input.for_each(|_capability, data| {
+                    data.swap(&mut input_buffer);
+                    for (time, updates) in input_buffer.drain(..) {
+                        *pending.entry(time).or_default() += updates;
+                    }
+                });
+
+                let frontier = input.frontier().frontier();
+                let finished: Vec<_> = pending
+                    .keys()
+                    .filter(|time| !frontier.less_equal(*time))
+                    .cloned()
+                    .collect();
+                for time in finished {
+                    let updates = pending.remove(&time).unwrap();
+                    if updates == 0 {
+                        continue;
+                    }
+                    let iteration = time.inner + 1;
+                    let last_iteration = last_iterations.entry(time.outer.clone()).or_default();
+                    *last_iteration = (*last_iteration).max(iteration);
+                    logic(IterationEvent::Iteration {
+                        time: time.outer,
+                        iteration,
+                        updates,
+                    });
+                }
+
+                let converged: Vec<_> = last_iterations
+                    .keys()
+                    .filter(|outer| {
+                        !frontier
+                            .iter()
+                            .any(|time| PartialOrder::less_equal(&time.outer, *outer))
+                    })
+                    .cloned()
+                    .collect();
+                for time in converged {
+                    let iterations = last_iterations.remove(&time).unwrap();
+                    logic(IterationEvent::Converged { time, iterations });
+                }
+            },
+        );
+    }
+}
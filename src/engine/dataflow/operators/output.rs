@@ -8,8 +8,8 @@ use differential_dataflow::trace::TraceReader;
 use differential_dataflow::{Collection, Data, ExchangeData};
 use itertools::partition;
 use timely::dataflow::channels::pact::Pipeline;
-use timely::dataflow::operators::Operator;
-use timely::dataflow::Stream;
+use timely::dataflow::operators::{Capability, Operator};
+use timely::dataflow::{Scope, Stream};
 
 use crate::engine::dataflow::maybe_total::MaybeTotalScope;
 use crate::engine::dataflow::shard::Shard;
@@ -118,3 +118,65 @@ where
         })
     }
 }
+
+/// Holds output batches back until the frontier of `barrier` passes their times.
+///
+/// If the barrier combines the frontiers of the inputs of several outputs, each of the
+/// outputs gets the batches for a time only once all of them have their data for this
+/// time complete, so none of them can get ahead of the others.
+pub trait HoldUntil<S, D, R>
+where
+    S: Scope,
+{
+    fn hold_until_named(
+        &self,
+        name: &str,
+        barrier: &Stream<S, ()>,
+    ) -> Stream<S, OutputBatch<S::Timestamp, D, R>>;
+
+    #[track_caller]
+    fn hold_until(&self, barrier: &Stream<S, ()>) -> Stream<S, OutputBatch<S::Timestamp, D, R>> {
+        self.hold_until_named("HoldUntil", barrier)
+    }
+}
+
+impl<S, D, R> HoldUntil<S, D, R> for Stream<S, OutputBatch<S::Timestamp, D, R>>
+where
+    S: Scope,
+    D: Data,
+    R: Data,
+{
+    #[track_caller]
+    fn hold_until_named(
+        &self,
+        name: &str,
+        barrier: &Stream<S, ()>,
+    ) -> Stream<S, OutputBatch<S::Timestamp, D, R>> {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+        self.binary_frontier(barrier, Pipeline, Pipeline, &name, move |_cap, _info| {
+            let mut input_buffer = Vec::new();
+            let mut held: Vec<(Capability<S::Timestamp>, OutputBatch<S::Timestamp, D, R>)> =
+                Vec::new();
+            move |input, barrier, output| {
+                input.for_each(|cap, data| {
+                    data.swap(&mut input_buffer);
+                    for batch in input_buffer.drain(..) {
+                        held.push((cap.delayed(&batch.time), batch));
+                    }
+                });
+                barrier.for_each(|_cap, _data| {});
+
+                let (ready, pending): (Vec<_>, Vec<_>) =
+                    held.drain(..).partition(|(_cap, batch)| {
+                        !input.frontier().less_equal(&batch.time)
+                            && !barrier.frontier().less_equal(&batch.time)
+                    });
+                held = pending;
+                for (cap, batch) in ready {
+                    output.session(&cap).give(batch);
+                }
+            }
+        })
+    }
+}
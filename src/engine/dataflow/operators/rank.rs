@@ -0,0 +1,80 @@
+// Copyright © 2024 Pathway
+
+use std::hash::Hash;
+use std::panic::Location;
+
+use differential_dataflow::difference::Abelian;
+use differential_dataflow::operators::Reduce;
+use differential_dataflow::{Collection, ExchangeData};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::dataflow::maybe_total::MaybeTotalScope;
+
+use super::top_n::first_n;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Ranks {
+    pub row_number: usize,
+    pub rank: usize,
+    pub dense_rank: usize,
+}
+
+/// Numbers entries of every instance in the order of their values.
+///
+/// Entries are ordered by their value and then by their id, ascending also when the values
+/// are in the descending order, like in [`TopN`](super::top_n::TopN). For every entry, the
+/// operator returns its instance, its id and its ranks, all starting from 1:
+/// - `row_number` is the position of the entry in the order,
+/// - `rank` is one more than the number of entries with smaller values,
+/// - `dense_rank` is one more than the number of distinct smaller values.
+///
+/// The ranks of an instance are recomputed whenever any entry of this instance
+/// changes. An update shifts the ranks of all entries following it anyway, so the
+/// cost of an update is linear in the number of entries of the instance.
+pub trait Rank<S, I, V, K, R>
+where
+    S: MaybeTotalScope,
+    R: Abelian,
+{
+    #[track_caller]
+    fn rank(&self, descending: bool) -> Collection<S, (I, (K, Ranks)), R> {
+        self.rank_named("Rank", descending)
+    }
+
+    fn rank_named(&self, name: &str, descending: bool) -> Collection<S, (I, (K, Ranks)), R>;
+}
+
+impl<S, I, V, K, R> Rank<S, I, V, K, R> for Collection<S, (I, (V, K)), R>
+where
+    S: MaybeTotalScope,
+    I: ExchangeData + Hash,
+    V: ExchangeData,
+    K: ExchangeData,
+    R: ExchangeData + Abelian,
+{
+    #[track_caller]
+    fn rank_named(&self, name: &str, descending: bool) -> Collection<S, (I, (K, Ranks)), R> {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+
+        self.reduce_named(&name, move |_instance, input: &[(&(V, K), R)], output| {
+            // keeps ids of equal values in the ascending order, like `top_n`
+            let ordered = first_n(input, input.len(), descending);
+            let mut previous: Option<&V> = None;
+            let mut ranks = Ranks {
+                row_number: 0,
+                rank: 0,
+                dense_rank: 0,
+            };
+            for ((value, id), count) in ordered {
+                ranks.row_number += 1;
+                if previous != Some(value) {
+                    ranks.rank = ranks.row_number;
+                    ranks.dense_rank += 1;
+                    previous = Some(value);
+                }
+                output.push(((id.clone(), ranks), count.clone()));
+            }
+        })
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright © 2024 Pathway
+
+use std::panic::Location;
+
+use differential_dataflow::difference::{Abelian, Semigroup};
+use differential_dataflow::hashable::Hashable;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::operators::Reduce;
+use differential_dataflow::{Collection, ExchangeData};
+use timely::dataflow::Scope;
+
+use crate::engine::{Error, Result, Value};
+
+/// Groups entries of every instance into sessions.
+///
+/// Entries of an instance are ordered by their time (and id). Two consecutive
+/// entries belong to the same session if `same_session(previous_time, next_time)`
+/// holds. For every entry, the operator returns its instance, its id and the time
+/// of the first and the last entry of its session.
+///
+/// The sessions of an instance are recomputed whenever any entry of this instance
+/// changes, so the output is retraction-correct: a late entry that fills a gap
+/// between two sessions retracts both of them and produces a merged session.
+/// The cost of an update is linear in the number of entries of the instance, and
+/// all entries of an instance are processed by a single worker.
+pub trait SessionWindows<S, K, T, I, R>
+where
+    S: Scope,
+    R: Semigroup,
+{
+    #[track_caller]
+    fn session_windows(
+        &self,
+        same_session: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Collection<S, (K, (I, (T, T))), R> {
+        self.session_windows_named("SessionWindows", same_session)
+    }
+
+    fn session_windows_named(
+        &self,
+        name: &str,
+        same_session: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Collection<S, (K, (I, (T, T))), R>;
+}
+
+impl<S, K, T, I, R> SessionWindows<S, K, T, I, R> for Collection<S, (K, (T, I)), R>
+where
+    S: Scope,
+    S::Timestamp: Lattice + Ord,
+    K: ExchangeData + Hashable,
+    T: ExchangeData,
+    I: ExchangeData,
+    R: ExchangeData + Abelian,
+{
+    #[track_caller]
+    fn session_windows_named(
+        &self,
+        name: &str,
+        same_session: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Collection<S, (K, (I, (T, T))), R> {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+
+        self.reduce_named(&name, move |_instance, input, output| {
+            // input is sorted by (time, id), so sessions are contiguous
+            let mut session: Vec<(&I, R)> = Vec::new();
+            let mut session_start: Option<&T> = None;
+            let mut session_end: Option<&T> = None;
+            for ((time, id), count) in input {
+                if let Some(end) = session_end {
+                    if !same_session(end, time) {
+                        let start = session_start.unwrap();
+                        output.extend(session.drain(..).map(|(id, count)| {
+                            ((id.clone(), (start.clone(), end.clone())), count)
+                        }));
+                        session_start = None;
+                    }
+                }
+                session_start.get_or_insert(time);
+                session_end = Some(time);
+                session.push((id, count.clone()));
+            }
+            if let (Some(start), Some(end)) = (session_start, session_end) {
+                output.extend(
+                    session
+                        .into_iter()
+                        .map(|(id, count)| ((id.clone(), (start.clone(), end.clone())), count)),
+                );
+            }
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(i) => Some(*i as f64),
+        Value::Float(f) => Some(f.into_inner()),
+        _ => None,
+    }
+}
+
+/// Checks if `next - prev < max_gap` for time-like values.
+pub fn within_gap(prev: &Value, next: &Value, max_gap: &Value) -> Result<bool> {
+    match (prev, next, max_gap) {
+        (Value::Int(prev), Value::Int(next), Value::Int(max_gap)) => {
+            Ok(i128::from(*next) - i128::from(*prev) < i128::from(*max_gap))
+        }
+        (
+            Value::Int(_) | Value::Float(_),
+            Value::Int(_) | Value::Float(_),
+            Value::Int(_) | Value::Float(_),
+        ) => Ok(as_f64(next).unwrap() - as_f64(prev).unwrap() < as_f64(max_gap).unwrap()),
+        (Value::DateTimeNaive(prev), Value::DateTimeNaive(next), Value::Duration(max_gap)) => {
+            Ok(next
+                .checked_duration_since(*prev)
+                .is_ok_and(|gap| gap < *max_gap))
+        }
+        (Value::DateTimeUtc(prev), Value::DateTimeUtc(next), Value::Duration(max_gap)) => Ok(next
+            .checked_duration_since(*prev)
+            .is_ok_and(|gap| gap < *max_gap)),
+        (Value::Duration(prev), Value::Duration(next), Value::Duration(max_gap)) => {
+            Ok(next.checked_sub(*prev).is_ok_and(|gap| gap < *max_gap))
+        }
+        _ => Err(Error::ValueError(format!(
+            "cannot compare the distance between {prev} and {next} with {max_gap}"
+        ))),
+    }
+}
@@ -36,6 +36,16 @@ where
         name: &str,
         logic: impl FnMut(Option<&V2>, &[(V, R)]) -> Option<V2> + 'static,
     ) -> Collection<S, (K, V2), R>;
+
+    /// Like `stateful_reduce_named`, but the keys changed at a time are passed to `logic`
+    /// together. The states are updated by `logic` in place and never leave the operator,
+    /// so they don't have to be data. `logic` returns the values of the keys, `None` for
+    /// the keys without a value.
+    fn batched_stateful_reduce_named<St: 'static, V2: Data>(
+        &self,
+        name: &str,
+        logic: impl FnMut(&mut [(Option<St>, Vec<(V, R)>)]) -> Vec<Option<V2>> + 'static,
+    ) -> Collection<S, (K, V2), R>;
 }
 
 impl<S, K, V, R> StatefulReduce<S, K, V, R> for Collection<S, (K, V), R>
@@ -55,6 +65,16 @@ where
         let arranged: ArrangedByKey<S, K, V, R> = self.arrange_named(&format!("Arrange: {name}"));
         arranged.stateful_reduce_named(name, logic)
     }
+
+    #[track_caller]
+    fn batched_stateful_reduce_named<St: 'static, V2: Data>(
+        &self,
+        name: &str,
+        logic: impl FnMut(&mut [(Option<St>, Vec<(V, R)>)]) -> Vec<Option<V2>> + 'static,
+    ) -> Collection<S, (K, V2), R> {
+        let arranged: ArrangedByKey<S, K, V, R> = self.arrange_named(&format!("Arrange: {name}"));
+        arranged.batched_stateful_reduce_named(name, logic)
+    }
 }
 
 impl<S, Tr> StatefulReduce<S, Tr::Key, Tr::Val, Tr::R> for Arranged<S, Tr>
@@ -127,4 +147,84 @@ where
             })
             .as_collection()
     }
+
+    #[track_caller]
+    fn batched_stateful_reduce_named<St: 'static, V2: Data>(
+        &self,
+        name: &str,
+        mut logic: impl FnMut(&mut [(Option<St>, Vec<(Tr::Val, Tr::R)>)]) -> Vec<Option<V2>> + 'static,
+    ) -> Collection<S, (Tr::Key, V2), Tr::R> {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+
+        // The states of the keys with their current values
+        let mut state_by_key: HashMap<Tr::Key, (Option<St>, Option<V2>)> = HashMap::new();
+        self.stream
+            .unary(Pipeline, &name, move |_, _| {
+                move |input, output| {
+                    input.for_each(|cap, data| {
+                        let mut session = output.session(&cap);
+                        for batch in data.iter() {
+                            let mut data_by_time: BTreeMap<_, Vec<_>> = BTreeMap::new();
+                            let mut cursor = batch.cursor();
+                            while let Some(key) = cursor.get_key(batch) {
+                                let mut key_data_by_time: BTreeMap<_, Vec<_>> = BTreeMap::new();
+                                while let Some(val) = cursor.get_val(batch) {
+                                    cursor.map_times(batch, |time, diff| {
+                                        key_data_by_time
+                                            .entry(time.clone())
+                                            .or_default()
+                                            .push((val.clone(), diff.clone()));
+                                    });
+                                    cursor.step_val(batch);
+                                }
+                                for (time, data) in key_data_by_time {
+                                    data_by_time
+                                        .entry(time)
+                                        .or_default()
+                                        .push((key.clone(), data));
+                                }
+                                cursor.step_key(batch);
+                            }
+                            for (time, key_data) in data_by_time {
+                                let (keys, mut groups): (Vec<_>, Vec<_>) = key_data
+                                    .into_iter()
+                                    .map(|(key, data)| {
+                                        let state = state_by_key
+                                            .get_mut(&key)
+                                            .and_then(|(state, _value)| state.take());
+                                        (key, (state, data))
+                                    })
+                                    .unzip();
+                                let values = logic(&mut groups);
+                                for ((key, (state, _data)), value) in
+                                    keys.into_iter().zip(groups).zip(values)
+                                {
+                                    let previous_value =
+                                        state_by_key.remove(&key).and_then(|(_state, value)| value);
+                                    if let Some(previous_value) = previous_value {
+                                        session.give((
+                                            (key.clone(), previous_value),
+                                            time.clone(),
+                                            Tr::R::from(-1),
+                                        ));
+                                    }
+                                    if let Some(value) = value.clone() {
+                                        session.give((
+                                            (key.clone(), value),
+                                            time.clone(),
+                                            Tr::R::from(1),
+                                        ));
+                                    }
+                                    if state.is_some() || value.is_some() {
+                                        state_by_key.insert(key, (state, value));
+                                    }
+                                }
+                            }
+                        }
+                    });
+                }
+            })
+            .as_collection()
+    }
 }
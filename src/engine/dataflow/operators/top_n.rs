@@ -0,0 +1,125 @@
+// Copyright © 2024 Pathway
+
+use std::hash::Hash;
+use std::panic::Location;
+
+use differential_dataflow::difference::Abelian;
+use differential_dataflow::operators::Reduce;
+use differential_dataflow::{Collection, ExchangeData};
+
+use crate::engine::dataflow::maybe_total::MaybeTotalScope;
+use crate::engine::dataflow::shard::Shard;
+
+use super::MapWrapped;
+
+const BUCKET_BITS: u32 = 4;
+const BUCKET_LEVELS: u32 = 6;
+
+/// Returns the first `n` entries of the input, which is sorted by value and then by id,
+/// in the order of the operator: by value, ascending or descending, and then by id,
+/// always ascending, so that the entries with equal values come in the same order as
+/// in [`Rank`](super::rank::Rank).
+pub(super) fn first_n<'a, V, K, R>(
+    input: &'a [(&'a (V, K), R)],
+    n: usize,
+    descending: bool,
+) -> Vec<&'a (&'a (V, K), R)>
+where
+    V: Eq,
+{
+    if !descending {
+        return input.iter().take(n).collect();
+    }
+    let mut first = Vec::with_capacity(n.min(input.len()));
+    let mut end = input.len();
+    while end > 0 && first.len() < n {
+        let value = &input[end - 1].0 .0;
+        let start = input[..end]
+            .iter()
+            .rposition(|((other, _id), _count)| other != value)
+            .map_or(0, |position| position + 1);
+        first.extend(input[start..end].iter().take(n - first.len()));
+        end = start;
+    }
+    first
+}
+
+/// Maintains the first `n` entries of every instance.
+///
+/// Entries of an instance are ordered by their value and then by their id, ascending also
+/// when the values are in the descending order, like in [`Rank`](super::rank::Rank).
+/// For every entry among the first `n`, the operator returns its instance, its value and
+/// id, and its position in the order, starting from 0.
+///
+/// Candidates are selected hierarchically: entries are first split into buckets by
+/// the hash of their id, every bucket keeps its first `n` entries, and the buckets are
+/// merged level by level. An update recomputes only the buckets it belongs to, so its
+/// cost depends on `n` and not on the number of entries of the instance.
+pub trait TopN<S, I, V, K, R>
+where
+    S: MaybeTotalScope,
+    R: Abelian,
+{
+    #[track_caller]
+    fn top_n(&self, n: usize, descending: bool) -> Collection<S, (I, ((V, K), usize)), R> {
+        self.top_n_named("TopN", n, descending)
+    }
+
+    fn top_n_named(
+        &self,
+        name: &str,
+        n: usize,
+        descending: bool,
+    ) -> Collection<S, (I, ((V, K), usize)), R>;
+}
+
+impl<S, I, V, K, R> TopN<S, I, V, K, R> for Collection<S, (I, (V, K)), R>
+where
+    S: MaybeTotalScope,
+    I: ExchangeData + Hash,
+    V: ExchangeData,
+    K: ExchangeData + Shard,
+    R: ExchangeData + Abelian,
+{
+    #[track_caller]
+    fn top_n_named(
+        &self,
+        name: &str,
+        n: usize,
+        descending: bool,
+    ) -> Collection<S, (I, ((V, K), usize)), R> {
+        let caller = Location::caller();
+        let name = format!("{name} at {caller}");
+
+        let mut candidates = self.map_named("TopN::buckets", |(instance, (value, key))| {
+            let bucket = key.shard() & ((1 << (BUCKET_BITS * BUCKET_LEVELS)) - 1);
+            ((instance, bucket), (value, key))
+        });
+        for level in 0..BUCKET_LEVELS {
+            candidates = candidates
+                .reduce_named(
+                    &format!("{name} [level {level}]"),
+                    move |_bucket, input: &[(&(V, K), R)], output| {
+                        output.extend(
+                            first_n(input, n, descending)
+                                .into_iter()
+                                .map(|(entry, count)| ((*entry).clone(), count.clone())),
+                        );
+                    },
+                )
+                .map_named("TopN::merge_buckets", |((instance, bucket), entry)| {
+                    ((instance, bucket >> BUCKET_BITS), entry)
+                });
+        }
+
+        candidates
+            .map_named("TopN::instances", |((instance, _bucket), entry)| {
+                (instance, entry)
+            })
+            .reduce_named(&name, move |_instance, input: &[(&(V, K), R)], output| {
+                output.extend(first_n(input, n, descending).into_iter().enumerate().map(
+                    |(position, (entry, count))| (((*entry).clone(), position), count.clone()),
+                ));
+            })
+    }
+}
@@ -5,7 +5,11 @@ use std::error;
 use std::fmt;
 use std::result;
 
-use super::{Key, Value};
+use itertools::Itertools;
+use opentelemetry::trace::TraceError;
+use pyo3::PyErr;
+
+use super::{Key, Type, Value};
 use crate::persistence::metadata_backends::Error as MetadataBackendError;
 
 use crate::connectors::data_storage::{ReadError, WriteError};
@@ -15,12 +19,19 @@ use crate::persistence::ExternalPersistentId;
 pub type DynError = Box<dyn error::Error + Send + Sync>;
 pub type DynResult<T> = result::Result<T, DynError>;
 
+/// The maximal length of the description of the values of a row attached to an error,
+/// longer descriptions are truncated.
+const MAX_ROW_DESCRIPTION_LENGTH: usize = 1000;
+
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum Error {
     #[error("iteration limit too small")]
     IterationLimitTooSmall,
 
+    #[error("fixed point not reached within the limit of {limit} iterations")]
+    IterationLimitExceeded { limit: u32 },
+
     #[error("invalid universe handle")]
     InvalidUniverseHandle,
 
@@ -102,6 +113,19 @@ pub enum Error {
         actual: &'static str,
     },
 
+    #[error("operand type mismatch in {operation}: expected {expected:?}, got {actual:?}")]
+    OperandTypeMismatch {
+        operation: String,
+        expected: Type,
+        actual: Type,
+    },
+
+    #[error("join key type mismatch: {left:?} on the left side, {right:?} on the right side")]
+    JoinKeyTypeMismatch { left: Type, right: Type },
+
+    #[error("operand of {operation} can be None, expected {expected:?}")]
+    NullableOperand { operation: String, expected: Type },
+
     #[error("key missing in universe: {0}")]
     KeyMissingInUniverse(Key),
 
@@ -132,6 +156,9 @@ pub enum Error {
     #[error("date time conversion error")]
     DateTimeConversionError,
 
+    #[error("overflow in date time arithmetic")]
+    DateTimeOverflow,
+
     #[error("value error: {0}")]
     ValueError(String),
 
@@ -148,6 +175,21 @@ pub enum Error {
         trace: Trace,
     },
 
+    #[error("{inner}\nOccurred for the row with key {key} and values {values}")]
+    WithRow {
+        #[source]
+        inner: DynError,
+        key: Key,
+        values: String,
+    },
+
+    #[error("{traceback}")]
+    PythonException {
+        #[source]
+        error: PyErr,
+        traceback: String,
+    },
+
     #[error("persistent id {0} is assigned, but no persistent storage is configured")]
     NoPersistentStorage(ExternalPersistentId),
 
@@ -156,6 +198,18 @@ pub enum Error {
 
     #[error("exception in Python subject: {0}")]
     ReaderFailed(#[source] ReadError),
+
+    #[error("failed to set up the export of traces: {0}")]
+    TraceExportSetupFailed(#[source] TraceError),
+
+    #[error("state of operator {operator_id} takes approximately {size} bytes, exceeding the limit of {limit} bytes")]
+    OperatorStateLimitExceeded {
+        operator_id: usize,
+        size: u64,
+        limit: u64,
+    },
+    #[error("the keys are hashed with a seed generated for this run, so they can't be persisted, PATHWAY_KEY_HASHING_SEED has to be set")]
+    PerRunKeyHashingSeedWithPersistence,
 }
 
 impl Error {
@@ -186,6 +240,26 @@ impl Error {
             trace,
         }
     }
+
+    /// Attaches the row for which the error occurred, with the description of its values
+    /// truncated to `MAX_ROW_DESCRIPTION_LENGTH` characters.
+    pub fn with_row(error: impl Into<DynError>, key: Key, values: &[Value]) -> Self {
+        let description = format!("({})", values.iter().format(", "));
+        let values = if description.chars().count() > MAX_ROW_DESCRIPTION_LENGTH {
+            let truncated: String = description
+                .chars()
+                .take(MAX_ROW_DESCRIPTION_LENGTH)
+                .collect();
+            format!("{truncated}...")
+        } else {
+            description
+        };
+        Self::WithRow {
+            inner: error.into(),
+            key,
+            values,
+        }
+    }
 }
 
 impl From<DynError> for Error {
@@ -4,7 +4,7 @@
 
 use arcstr::ArcStr;
 use log::warn;
-use ndarray::{ArrayD, Axis, LinalgScalar};
+use ndarray::{ArrayD, Axis};
 use num_integer::Integer;
 use ordered_float::OrderedFloat;
 use std::cmp::Ordering;
@@ -18,8 +18,13 @@ use smallvec::SmallVec;
 use super::error::{DynError, DynResult};
 use super::time::{DateTime, DateTimeNaive, DateTimeUtc, Duration};
 use super::value::SimpleType;
-use super::{Error, Key, Type, Value};
-use crate::mat_mul::mat_mul;
+use super::{Error, Key, SparseMatrix, Type, Value};
+use crate::mat_mul::{
+    dense_sparse_mat_mul, mat_mul, sparse_dense_mat_mul, sparse_mat_mul, MatMulElement, MatMulError,
+};
+
+pub mod columnar;
+mod type_check;
 
 #[derive(Debug)]
 pub enum Expressions {
@@ -207,6 +212,10 @@ pub enum IntExpression {
     DateTimeNaiveYear(Arc<Expression>),
     DateTimeNaiveTimestampNs(Arc<Expression>),
     DateTimeNaiveWeekday(Arc<Expression>),
+    DateTimeNaiveIsoWeek(Arc<Expression>),
+    DateTimeNaiveIsoYear(Arc<Expression>),
+    DateTimeNaiveQuarter(Arc<Expression>),
+    DateTimeNaiveDayOfYear(Arc<Expression>),
     DateTimeUtcNanosecond(Arc<Expression>),
     DateTimeUtcMicrosecond(Arc<Expression>),
     DateTimeUtcMillisecond(Arc<Expression>),
@@ -218,6 +227,11 @@ pub enum IntExpression {
     DateTimeUtcYear(Arc<Expression>),
     DateTimeUtcTimestampNs(Arc<Expression>),
     DateTimeUtcWeekday(Arc<Expression>),
+    DateTimeUtcIsoWeek(Arc<Expression>),
+    DateTimeUtcIsoYear(Arc<Expression>),
+    DateTimeUtcQuarter(Arc<Expression>),
+    DateTimeUtcDayOfYear(Arc<Expression>),
+    DateTimeUtcOffsetSeconds(Arc<Expression>, Arc<Expression>),
     DurationFloorDiv(Arc<Expression>, Arc<Expression>),
     DurationNanoseconds(Arc<Expression>),
     DurationMicroseconds(Arc<Expression>),
@@ -230,6 +244,7 @@ pub enum IntExpression {
     CastFromBool(Arc<Expression>),
     CastFromFloat(Arc<Expression>),
     CastFromString(Arc<Expression>),
+    EstimatedSize(Arc<Expression>),
 }
 
 #[derive(Debug)]
@@ -248,9 +263,13 @@ pub enum FloatExpression {
     DurationTrueDiv(Arc<Expression>, Arc<Expression>),
     DateTimeNaiveTimestamp(Arc<Expression>, Arc<Expression>),
     DateTimeUtcTimestamp(Arc<Expression>, Arc<Expression>),
+    DateTimeNaiveExcelSerial(Arc<Expression>),
+    DateTimeNaiveJulianDay(Arc<Expression>),
+    DateTimeUtcJulianDay(Arc<Expression>),
     CastFromBool(Arc<Expression>),
     CastFromInt(Arc<Expression>),
     CastFromString(Arc<Expression>),
+    PointerSampleRank(Arc<Expression>, Arc<Expression>),
 }
 
 #[derive(Debug)]
@@ -262,6 +281,7 @@ pub enum StringExpression {
     CastFromInt(Arc<Expression>),
     DateTimeNaiveStrftime(Arc<Expression>, Arc<Expression>),
     DateTimeUtcStrftime(Arc<Expression>, Arc<Expression>),
+    DateTimeUtcStrftimeInTimezone(Arc<Expression>, Arc<Expression>, Arc<Expression>),
     ToString(Arc<Expression>),
 }
 
@@ -280,6 +300,11 @@ pub enum DateTimeNaiveExpression {
     Floor(Arc<Expression>, Arc<Expression>),
     FromTimestamp(Arc<Expression>, Arc<Expression>),
     FromFloatTimestamp(Arc<Expression>, Arc<Expression>),
+    FromExcelSerial(Arc<Expression>),
+    FromJulianDay(Arc<Expression>),
+    FloorToCalendarUnit(Arc<Expression>, Arc<Expression>),
+    RoundToCalendarUnit(Arc<Expression>, Arc<Expression>),
+    AddCalendarUnits(Arc<Expression>, Arc<Expression>, Arc<Expression>),
 }
 
 #[derive(Debug)]
@@ -287,9 +312,22 @@ pub enum DateTimeUtcExpression {
     AddDuration(Arc<Expression>, Arc<Expression>),
     SubDuration(Arc<Expression>, Arc<Expression>),
     Strptime(Arc<Expression>, Arc<Expression>),
+    ParseRfc3339(Arc<Expression>),
+    ParseRfc2822(Arc<Expression>),
     FromNaive(Arc<Expression>, Arc<Expression>),
     Round(Arc<Expression>, Arc<Expression>),
     Floor(Arc<Expression>, Arc<Expression>),
+    FloorToCalendarUnit(Arc<Expression>, Arc<Expression>),
+    RoundToCalendarUnit(Arc<Expression>, Arc<Expression>),
+    AddCalendarUnits(Arc<Expression>, Arc<Expression>, Arc<Expression>),
+    FloorToCalendarUnitInTimezone(Arc<Expression>, Arc<Expression>, Arc<Expression>),
+    RoundToCalendarUnitInTimezone(Arc<Expression>, Arc<Expression>, Arc<Expression>),
+    AddCalendarUnitsInTimezone(
+        Arc<Expression>,
+        Arc<Expression>,
+        Arc<Expression>,
+        Arc<Expression>,
+    ),
 }
 
 #[derive(Debug)]
@@ -361,6 +399,7 @@ fn get_array_element(
     match value {
         Value::IntArray(array) => get_ndarray_element(&array, index),
         Value::FloatArray(array) => get_ndarray_element(&array, index),
+        Value::Float32Array(array) => get_ndarray_element(&array, index),
         Value::Tuple(tuple) => get_tuple_element(&tuple, index),
         _ => Err(DynError::from(Error::ValueError(format!(
             "Can't get element at index {index} out of {value:?}"
@@ -393,18 +432,45 @@ fn get_json_item(
 
 fn mat_mul_wrapper<T>(lhs: &ArrayD<T>, rhs: &ArrayD<T>) -> DynResult<Value>
 where
-    T: LinalgScalar,
+    T: MatMulElement,
     Value: From<ArrayD<T>>,
 {
-    if let Some(result) = mat_mul(&lhs.view(), &rhs.view()) {
-        Ok(result.into())
-    } else {
-        let msg = format!(
-            "can't multiply arrays of shapes {:?} and {:?}",
-            lhs.shape(),
-            rhs.shape()
-        );
-        Err(DynError::from(Error::ValueError(msg)))
+    mat_mul(&lhs.view(), &rhs.view())
+        .map(Value::from)
+        .map_err(|error| mat_mul_error(error, lhs.shape(), rhs.shape()))
+}
+
+fn sparse_dense_mat_mul_wrapper(lhs: &SparseMatrix, rhs: &ArrayD<f64>) -> DynResult<Value> {
+    sparse_dense_mat_mul(lhs, &rhs.view())
+        .map(Value::from)
+        .map_err(|error| mat_mul_error(error, &sparse_shape(lhs), rhs.shape()))
+}
+
+fn dense_sparse_mat_mul_wrapper(lhs: &ArrayD<f64>, rhs: &SparseMatrix) -> DynResult<Value> {
+    dense_sparse_mat_mul(&lhs.view(), rhs)
+        .map(Value::from)
+        .map_err(|error| mat_mul_error(error, lhs.shape(), &sparse_shape(rhs)))
+}
+
+fn sparse_mat_mul_wrapper(lhs: &SparseMatrix, rhs: &SparseMatrix) -> DynResult<Value> {
+    sparse_mat_mul(lhs, rhs)
+        .map(Value::from)
+        .map_err(|error| mat_mul_error(error, &sparse_shape(lhs), &sparse_shape(rhs)))
+}
+
+fn sparse_shape(matrix: &SparseMatrix) -> [usize; 2] {
+    let (rows, columns) = matrix.shape();
+    [rows, columns]
+}
+
+fn mat_mul_error(error: MatMulError, lhs_shape: &[usize], rhs_shape: &[usize]) -> DynError {
+    match error {
+        MatMulError::ShapeMismatch => DynError::from(Error::ValueError(format!(
+            "can't multiply arrays of shapes {lhs_shape:?} and {rhs_shape:?}"
+        ))),
+        MatMulError::Overflow => DynError::from(Error::ValueError(String::from(
+            "integer overflow in matrix multiplication",
+        ))),
     }
 }
 
@@ -452,6 +518,8 @@ fn compare_tuples(lhs: &Arc<[Value]>, rhs: &Arc<[Value]>) -> DynResult<Ordering>
                     SimpleType::Json,
                     SimpleType::IntArray,
                     SimpleType::FloatArray,
+                    SimpleType::Float32Array,
+                    SimpleType::SparseMatrix,
                 ]
                 .contains(&type_l);
                 if type_l != type_r || is_incomparable_type {
@@ -613,7 +681,33 @@ impl AnyExpression {
                 let rhs_val = rhs.eval(values)?;
                 match (lhs_val, rhs_val) {
                     (Value::FloatArray(lhs), Value::FloatArray(rhs)) => mat_mul_wrapper(&lhs, &rhs),
+                    (Value::Float32Array(lhs), Value::Float32Array(rhs)) => {
+                        mat_mul_wrapper(&lhs, &rhs)
+                    }
+                    // mixed precisions are multiplied in the wider one, as in NumPy
+                    (Value::FloatArray(lhs), Value::Float32Array(rhs)) => {
+                        mat_mul_wrapper(&lhs, &rhs.mapv(f64::from))
+                    }
+                    (Value::Float32Array(lhs), Value::FloatArray(rhs)) => {
+                        mat_mul_wrapper(&lhs.mapv(f64::from), &rhs)
+                    }
                     (Value::IntArray(lhs), Value::IntArray(rhs)) => mat_mul_wrapper(&lhs, &rhs),
+                    // the products with sparse matrices are dense, unless both are sparse
+                    (Value::SparseMatrix(lhs), Value::FloatArray(rhs)) => {
+                        sparse_dense_mat_mul_wrapper(&lhs, &rhs)
+                    }
+                    (Value::SparseMatrix(lhs), Value::Float32Array(rhs)) => {
+                        sparse_dense_mat_mul_wrapper(&lhs, &rhs.mapv(f64::from))
+                    }
+                    (Value::FloatArray(lhs), Value::SparseMatrix(rhs)) => {
+                        dense_sparse_mat_mul_wrapper(&lhs, &rhs)
+                    }
+                    (Value::Float32Array(lhs), Value::SparseMatrix(rhs)) => {
+                        dense_sparse_mat_mul_wrapper(&lhs.mapv(f64::from), &rhs)
+                    }
+                    (Value::SparseMatrix(lhs), Value::SparseMatrix(rhs)) => {
+                        sparse_mat_mul_wrapper(&lhs, &rhs)
+                    }
                     (lhs_val, rhs_val) => {
                         let lhs_type = lhs_val.simple_type();
                         let rhs_type = rhs_val.simple_type();
@@ -845,6 +939,10 @@ impl IntExpression {
             Self::DateTimeNaiveYear(e) => Ok(e.eval_as_date_time_naive(values)?.year()),
             Self::DateTimeNaiveTimestampNs(e) => Ok(e.eval_as_date_time_naive(values)?.timestamp()),
             Self::DateTimeNaiveWeekday(e) => Ok(e.eval_as_date_time_naive(values)?.weekday()),
+            Self::DateTimeNaiveIsoWeek(e) => Ok(e.eval_as_date_time_naive(values)?.iso_week()),
+            Self::DateTimeNaiveIsoYear(e) => Ok(e.eval_as_date_time_naive(values)?.iso_year()),
+            Self::DateTimeNaiveQuarter(e) => Ok(e.eval_as_date_time_naive(values)?.quarter()),
+            Self::DateTimeNaiveDayOfYear(e) => Ok(e.eval_as_date_time_naive(values)?.day_of_year()),
             Self::DateTimeUtcNanosecond(e) => Ok(e.eval_as_date_time_utc(values)?.nanosecond()),
             Self::DateTimeUtcMicrosecond(e) => Ok(e.eval_as_date_time_utc(values)?.microsecond()),
             Self::DateTimeUtcMillisecond(e) => Ok(e.eval_as_date_time_utc(values)?.millisecond()),
@@ -856,6 +954,13 @@ impl IntExpression {
             Self::DateTimeUtcYear(e) => Ok(e.eval_as_date_time_utc(values)?.year()),
             Self::DateTimeUtcTimestampNs(e) => Ok(e.eval_as_date_time_utc(values)?.timestamp()),
             Self::DateTimeUtcWeekday(e) => Ok(e.eval_as_date_time_utc(values)?.weekday()),
+            Self::DateTimeUtcIsoWeek(e) => Ok(e.eval_as_date_time_utc(values)?.iso_week()),
+            Self::DateTimeUtcIsoYear(e) => Ok(e.eval_as_date_time_utc(values)?.iso_year()),
+            Self::DateTimeUtcQuarter(e) => Ok(e.eval_as_date_time_utc(values)?.quarter()),
+            Self::DateTimeUtcDayOfYear(e) => Ok(e.eval_as_date_time_utc(values)?.day_of_year()),
+            Self::DateTimeUtcOffsetSeconds(e, timezone) => Ok(e
+                .eval_as_date_time_utc(values)?
+                .offset_seconds(&timezone.eval_as_string(values)?)?),
             Self::DurationFloorDiv(lhs, rhs) => {
                 Ok((lhs.eval_as_duration(values)? / rhs.eval_as_duration(values)?)?)
             }
@@ -879,10 +984,27 @@ impl IntExpression {
                     )))
                 })
             }
+            Self::EstimatedSize(e) => {
+                Ok(i64::try_from(e.eval(values)?.estimated_size()).unwrap_or(i64::MAX))
+            }
         }
     }
 }
 
+fn python_float_mod(lhs: f64, rhs: f64) -> f64 {
+    /*
+    Implementation the same as the one in Cpython
+    https://github.com/python/cpython/blob/main/Objects/floatobject.c#L640
+    */
+    let mut modulo = lhs % rhs;
+    if modulo == 0.0f64 {
+        modulo = modulo.copysign(rhs);
+    } else if (rhs < 0.0f64) != (modulo < 0.0f64) {
+        modulo += rhs;
+    }
+    modulo
+}
+
 impl FloatExpression {
     pub fn eval(&self, values: &[Value]) -> DynResult<f64> {
         match self {
@@ -909,22 +1031,12 @@ impl FloatExpression {
                 }
             }
             Self::Mod(lhs, rhs) => {
-                /*
-                Implementation the same as the one in Cpython
-                https://github.com/python/cpython/blob/main/Objects/floatobject.c#L640
-                */
                 let lhs_val = lhs.eval_as_float(values)?;
                 let rhs_val = rhs.eval_as_float(values)?;
                 if rhs_val == 0.0f64 {
                     return Err(DynError::from(Error::DivisionByZero));
                 }
-                let mut modulo = lhs_val % rhs_val;
-                if modulo == 0.0f64 {
-                    modulo = modulo.copysign(rhs_val);
-                } else if (rhs_val < 0.0f64) != (modulo < 0.0f64) {
-                    modulo += rhs_val;
-                }
-                Ok(modulo)
+                Ok(python_float_mod(lhs_val, rhs_val))
             }
             Self::Pow(lhs, rhs) => {
                 let result = lhs.eval_as_float(values)?.powf(rhs.eval_as_float(values)?);
@@ -948,9 +1060,17 @@ impl FloatExpression {
             Self::DateTimeUtcTimestamp(e, unit) => Ok(e
                 .eval_as_date_time_utc(values)?
                 .timestamp_in_unit(&unit.eval_as_string(values)?)?),
+            Self::DateTimeNaiveExcelSerial(e) => {
+                Ok(e.eval_as_date_time_naive(values)?.excel_serial())
+            }
+            Self::DateTimeNaiveJulianDay(e) => Ok(e.eval_as_date_time_naive(values)?.julian_day()),
+            Self::DateTimeUtcJulianDay(e) => Ok(e.eval_as_date_time_utc(values)?.julian_day()),
             Self::DurationTrueDiv(lhs, rhs) => Ok(lhs
                 .eval_as_duration(values)?
                 .true_div(rhs.eval_as_duration(values)?)?),
+            Self::PointerSampleRank(pointer, seed) => Ok(pointer
+                .eval_as_pointer(values)?
+                .sample_rank(seed.eval_as_int(values)?)),
             Self::CastFromBool(e) => Ok(if e.eval_as_bool(values)? { 1.0 } else { 0.0 }),
             #[allow(clippy::cast_precision_loss)]
             Self::CastFromInt(e) => Ok(e.eval_as_int(values)? as f64),
@@ -1014,6 +1134,12 @@ impl StringExpression {
                 e.eval_as_date_time_utc(values)?
                     .strftime(&fmt.eval_as_string(values)?),
             )),
+            Self::DateTimeUtcStrftimeInTimezone(e, fmt, timezone) => Ok(ArcStr::from(
+                e.eval_as_date_time_utc(values)?.strftime_in_timezone(
+                    &fmt.eval_as_string(values)?,
+                    &timezone.eval_as_string(values)?,
+                )?,
+            )),
             Self::ToString(e) => {
                 let val = e.eval(values)?;
                 Ok(match val {
@@ -1028,12 +1154,12 @@ impl StringExpression {
 impl DateTimeNaiveExpression {
     pub fn eval(&self, values: &[Value]) -> DynResult<DateTimeNaive> {
         match self {
-            Self::AddDuration(lhs, rhs) => {
-                Ok(lhs.eval_as_date_time_naive(values)? + rhs.eval_as_duration(values)?)
-            }
-            Self::SubDuration(lhs, rhs) => {
-                Ok(lhs.eval_as_date_time_naive(values)? - rhs.eval_as_duration(values)?)
-            }
+            Self::AddDuration(lhs, rhs) => Ok(lhs
+                .eval_as_date_time_naive(values)?
+                .checked_add(rhs.eval_as_duration(values)?)?),
+            Self::SubDuration(lhs, rhs) => Ok(lhs
+                .eval_as_date_time_naive(values)?
+                .checked_sub(rhs.eval_as_duration(values)?)?),
             Self::Strptime(e, fmt) => Ok(DateTimeNaive::strptime(
                 &e.eval_as_string(values)?,
                 &fmt.eval_as_string(values)?,
@@ -1043,10 +1169,10 @@ impl DateTimeNaiveExpression {
                 .to_naive_in_timezone(&timezone.eval_as_string(values)?)?),
             Self::Round(expr, duration) => Ok(expr
                 .eval_as_date_time_naive(values)?
-                .round(duration.eval_as_duration(values)?)),
+                .round(duration.eval_as_duration(values)?)?),
             Self::Floor(expr, duration) => Ok(expr
                 .eval_as_date_time_naive(values)?
-                .truncate(duration.eval_as_duration(values)?)),
+                .truncate(duration.eval_as_duration(values)?)?),
             Self::FromTimestamp(expr, unit) => Ok(DateTimeNaive::from_timestamp(
                 expr.eval_as_int(values)?,
                 &unit.eval_as_string(values)?,
@@ -1055,6 +1181,24 @@ impl DateTimeNaiveExpression {
                 expr.eval_as_float(values)?,
                 &unit.eval_as_string(values)?,
             )?),
+            Self::FromExcelSerial(expr) => Ok(DateTimeNaive::from_excel_serial(
+                expr.eval_as_float(values)?,
+            )?),
+            Self::FromJulianDay(expr) => {
+                Ok(DateTimeNaive::from_julian_day(expr.eval_as_float(values)?)?)
+            }
+            Self::FloorToCalendarUnit(expr, unit) => Ok(expr
+                .eval_as_date_time_naive(values)?
+                .truncate_to_calendar_unit(unit.eval_as_string(values)?.parse()?)?),
+            Self::RoundToCalendarUnit(expr, unit) => Ok(expr
+                .eval_as_date_time_naive(values)?
+                .round_to_calendar_unit(unit.eval_as_string(values)?.parse()?)?),
+            Self::AddCalendarUnits(expr, unit, count) => {
+                Ok(expr.eval_as_date_time_naive(values)?.add_calendar_units(
+                    unit.eval_as_string(values)?.parse()?,
+                    count.eval_as_int(values)?,
+                )?)
+            }
         }
     }
 }
@@ -1062,25 +1206,58 @@ impl DateTimeNaiveExpression {
 impl DateTimeUtcExpression {
     pub fn eval(&self, values: &[Value]) -> DynResult<DateTimeUtc> {
         match self {
-            Self::AddDuration(lhs, rhs) => {
-                Ok(lhs.eval_as_date_time_utc(values)? + rhs.eval_as_duration(values)?)
-            }
-            Self::SubDuration(lhs, rhs) => {
-                Ok(lhs.eval_as_date_time_utc(values)? - rhs.eval_as_duration(values)?)
-            }
+            Self::AddDuration(lhs, rhs) => Ok(lhs
+                .eval_as_date_time_utc(values)?
+                .checked_add(rhs.eval_as_duration(values)?)?),
+            Self::SubDuration(lhs, rhs) => Ok(lhs
+                .eval_as_date_time_utc(values)?
+                .checked_sub(rhs.eval_as_duration(values)?)?),
             Self::Strptime(e, fmt) => Ok(DateTimeUtc::strptime(
                 &e.eval_as_string(values)?,
                 &fmt.eval_as_string(values)?,
             )?),
+            Self::ParseRfc3339(e) => Ok(DateTimeUtc::parse_rfc3339(&e.eval_as_string(values)?)?),
+            Self::ParseRfc2822(e) => Ok(DateTimeUtc::parse_rfc2822(&e.eval_as_string(values)?)?),
             Self::FromNaive(expr, from_timezone) => Ok(expr
                 .eval_as_date_time_naive(values)?
                 .to_utc_from_timezone(&from_timezone.eval_as_string(values)?)?),
             Self::Round(expr, duration) => Ok(expr
                 .eval_as_date_time_utc(values)?
-                .round(duration.eval_as_duration(values)?)),
+                .round(duration.eval_as_duration(values)?)?),
             Self::Floor(expr, duration) => Ok(expr
                 .eval_as_date_time_utc(values)?
-                .truncate(duration.eval_as_duration(values)?)),
+                .truncate(duration.eval_as_duration(values)?)?),
+            Self::FloorToCalendarUnit(expr, unit) => Ok(expr
+                .eval_as_date_time_utc(values)?
+                .truncate_to_calendar_unit(unit.eval_as_string(values)?.parse()?)?),
+            Self::RoundToCalendarUnit(expr, unit) => Ok(expr
+                .eval_as_date_time_utc(values)?
+                .round_to_calendar_unit(unit.eval_as_string(values)?.parse()?)?),
+            Self::AddCalendarUnits(expr, unit, count) => {
+                Ok(expr.eval_as_date_time_utc(values)?.add_calendar_units(
+                    unit.eval_as_string(values)?.parse()?,
+                    count.eval_as_int(values)?,
+                )?)
+            }
+            Self::FloorToCalendarUnitInTimezone(expr, unit, timezone) => Ok(expr
+                .eval_as_date_time_utc(values)?
+                .truncate_to_calendar_unit_in_timezone(
+                    unit.eval_as_string(values)?.parse()?,
+                    &timezone.eval_as_string(values)?,
+                )?),
+            Self::RoundToCalendarUnitInTimezone(expr, unit, timezone) => Ok(expr
+                .eval_as_date_time_utc(values)?
+                .round_to_calendar_unit_in_timezone(
+                    unit.eval_as_string(values)?.parse()?,
+                    &timezone.eval_as_string(values)?,
+                )?),
+            Self::AddCalendarUnitsInTimezone(expr, unit, count, timezone) => Ok(expr
+                .eval_as_date_time_utc(values)?
+                .add_calendar_units_in_timezone(
+                    unit.eval_as_string(values)?.parse()?,
+                    count.eval_as_int(values)?,
+                    &timezone.eval_as_string(values)?,
+                )?),
         }
     }
 }
@@ -1088,37 +1265,37 @@ impl DateTimeUtcExpression {
 impl DurationExpression {
     pub fn eval(&self, values: &[Value]) -> DynResult<Duration> {
         match self {
-            Self::Neg(e) => Ok(-e.eval_as_duration(values)?),
-            Self::Add(lhs, rhs) => {
-                Ok(lhs.eval_as_duration(values)? + rhs.eval_as_duration(values)?)
-            }
-            Self::Sub(lhs, rhs) => {
-                Ok(lhs.eval_as_duration(values)? - rhs.eval_as_duration(values)?)
-            }
-            Self::MulByInt(lhs, rhs) => {
-                Ok(lhs.eval_as_duration(values)? * rhs.eval_as_int(values)?)
-            }
+            Self::Neg(e) => Ok(e.eval_as_duration(values)?.checked_neg()?),
+            Self::Add(lhs, rhs) => Ok(lhs
+                .eval_as_duration(values)?
+                .checked_add(rhs.eval_as_duration(values)?)?),
+            Self::Sub(lhs, rhs) => Ok(lhs
+                .eval_as_duration(values)?
+                .checked_sub(rhs.eval_as_duration(values)?)?),
+            Self::MulByInt(lhs, rhs) => Ok(lhs
+                .eval_as_duration(values)?
+                .checked_mul(rhs.eval_as_int(values)?)?),
             Self::DivByInt(lhs, rhs) => {
                 Ok((lhs.eval_as_duration(values)? / rhs.eval_as_int(values)?)?)
             }
             Self::TrueDivByInt(lhs, rhs) => Ok(lhs
                 .eval_as_duration(values)?
                 .true_div_by_i64(rhs.eval_as_int(values)?)?),
-            Self::MulByFloat(lhs, rhs) => {
-                Ok(lhs.eval_as_duration(values)? * rhs.eval_as_float(values)?)
-            }
+            Self::MulByFloat(lhs, rhs) => Ok(lhs
+                .eval_as_duration(values)?
+                .checked_mul_f64(rhs.eval_as_float(values)?)?),
             Self::DivByFloat(lhs, rhs) => {
                 Ok((lhs.eval_as_duration(values)? / rhs.eval_as_float(values)?)?)
             }
             Self::Mod(lhs, rhs) => {
                 Ok((lhs.eval_as_duration(values)? % rhs.eval_as_duration(values)?)?)
             }
-            Self::DateTimeNaiveSub(lhs, rhs) => {
-                Ok(lhs.eval_as_date_time_naive(values)? - rhs.eval_as_date_time_naive(values)?)
-            }
-            Self::DateTimeUtcSub(lhs, rhs) => {
-                Ok(lhs.eval_as_date_time_utc(values)? - rhs.eval_as_date_time_utc(values)?)
-            }
+            Self::DateTimeNaiveSub(lhs, rhs) => Ok(lhs
+                .eval_as_date_time_naive(values)?
+                .checked_duration_since(rhs.eval_as_date_time_naive(values)?)?),
+            Self::DateTimeUtcSub(lhs, rhs) => Ok(lhs
+                .eval_as_date_time_utc(values)?
+                .checked_duration_since(rhs.eval_as_date_time_utc(values)?)?),
         }
     }
 }
@@ -0,0 +1,527 @@
+// Copyright © 2024 Pathway
+
+//! Evaluation of expressions for batches of rows in the columnar layout.
+//!
+//! The arguments of the rows of a batch are transposed into columns, kept in contiguous
+//! buffers of `bool`, `i64` or `f64` when all their values are of that type, and the
+//! expression is evaluated for the whole batch by loops over these buffers, with no dispatch
+//! on the variants of [`Value`] nor allocations for each row. Only the arithmetic,
+//! the comparisons and the logical operators on numbers and booleans are evaluated this way.
+//! If the evaluation of a batch fails, e.g. because of a division by zero or an argument of
+//! another type, the batch is evaluated row by row instead, so the results and the errors
+//! are the same as without batching.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use num_integer::Integer;
+use once_cell::unsync::OnceCell;
+
+use super::{
+    python_float_mod, AnyExpression, BoolExpression, Expression, FloatExpression, IntExpression,
+};
+use crate::engine::error::DynResult;
+use crate::engine::Value;
+
+#[cfg(feature = "simd")]
+mod simd;
+
+/// The number of rows evaluated at once, small enough for the buffers to stay in the cache.
+pub const COLUMNAR_BATCH_SIZE: usize = 1024;
+
+#[derive(Debug)]
+enum ColumnBuffer {
+    Bool(Vec<bool>),
+    Int(Vec<i64>),
+    Float(Vec<f64>),
+}
+
+impl ColumnBuffer {
+    /// Gathers the values at `index` of the rows, if they are all of the same supported type.
+    fn transpose(rows: &[&[Value]], index: usize) -> Option<Self> {
+        fn gather<T>(
+            rows: &[&[Value]],
+            index: usize,
+            typed: impl Fn(&Value) -> Option<T>,
+        ) -> Option<Vec<T>> {
+            rows.iter().map(|row| typed(row.get(index)?)).collect()
+        }
+
+        match rows.first()?.get(index)? {
+            Value::Bool(_) => gather(rows, index, |value| match value {
+                Value::Bool(b) => Some(*b),
+                _ => None,
+            })
+            .map(Self::Bool),
+            Value::Int(_) => gather(rows, index, |value| match value {
+                Value::Int(i) => Some(*i),
+                _ => None,
+            })
+            .map(Self::Int),
+            Value::Float(_) => gather(rows, index, |value| match value {
+                Value::Float(f) => Some(f.into_inner()),
+                _ => None,
+            })
+            .map(Self::Float),
+            _ => None,
+        }
+    }
+}
+
+/// A batch of rows of the arguments of expressions. The columns of the arguments are
+/// transposed when they are first used.
+pub struct ColumnarBatch<'a> {
+    rows: &'a [&'a [Value]],
+    columns: Vec<OnceCell<Option<ColumnBuffer>>>,
+}
+
+impl<'a> ColumnarBatch<'a> {
+    pub fn new(rows: &'a [&'a [Value]]) -> Self {
+        let width = rows.first().map_or(0, |row| row.len());
+        Self {
+            rows,
+            columns: (0..width).map(|_| OnceCell::new()).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn column(&self, index: usize) -> Option<&ColumnBuffer> {
+        self.columns
+            .get(index)?
+            .get_or_init(|| ColumnBuffer::transpose(self.rows, index))
+            .as_ref()
+    }
+}
+
+/// The arguments of the rows of a batch, kept in a single buffer reused for the following
+/// batches instead of a separate `Vec` allocated for each row.
+///
+/// Only the arguments are kept here, not the tuples of the results: a `Value` holds its
+/// strings, arrays and tuples in `Arc`s, which can't be placed in a bump arena released at
+/// the end of the batch, as the results outlive it in the states of the following
+/// operators, and the arguments cloned from the input rows only bump the counts of
+/// references of the values they share with them.
+#[derive(Debug, Default)]
+pub struct RowBuffer {
+    values: Vec<Value>,
+    ends: Vec<usize>,
+}
+
+impl RowBuffer {
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = Value>) {
+        self.values.extend(row);
+        self.ends.push(self.values.len());
+    }
+
+    pub fn rows(&self) -> Vec<&[Value]> {
+        let mut start = 0;
+        self.ends
+            .iter()
+            .map(|&end| {
+                let row = &self.values[start..end];
+                start = end;
+                row
+            })
+            .collect()
+    }
+
+    /// Drops the rows, keeping the memory for the next batch.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.ends.clear();
+    }
+}
+
+fn map<T: Copy, U>(values: &[T], op: impl Fn(T) -> U) -> Vec<U> {
+    values.iter().map(|value| op(*value)).collect()
+}
+
+fn zip_map<T: Copy, U: Copy, V>(lhs: &[T], rhs: &[U], op: impl Fn(T, U) -> V) -> Vec<V> {
+    lhs.iter()
+        .zip(rhs)
+        .map(|(lhs, rhs)| op(*lhs, *rhs))
+        .collect()
+}
+
+/// The arithmetic operators with kernels for the columns of floats.
+#[derive(Debug, Clone, Copy)]
+enum Arithmetic {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Arithmetic {
+    fn apply(self, lhs: f64, rhs: f64) -> f64 {
+        match self {
+            Self::Add => lhs + rhs,
+            Self::Sub => lhs - rhs,
+            Self::Mul => lhs * rhs,
+            Self::Div => lhs / rhs,
+        }
+    }
+}
+
+/// The comparison operators with kernels for the columns of ints and floats.
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn apply<T: PartialOrd + Copy>(self, lhs: T, rhs: T) -> bool {
+        match self {
+            Self::Eq => lhs == rhs,
+            Self::Ne => lhs != rhs,
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+        }
+    }
+}
+
+fn float_arithmetic(lhs: &[f64], rhs: &[f64], op: Arithmetic) -> Vec<f64> {
+    #[cfg(feature = "simd")]
+    if let Some(result) = simd::float_arithmetic(lhs, rhs, op) {
+        return result;
+    }
+    zip_map(lhs, rhs, |lhs, rhs| op.apply(lhs, rhs))
+}
+
+fn compare_floats(lhs: &[f64], rhs: &[f64], op: Comparison) -> Vec<bool> {
+    #[cfg(feature = "simd")]
+    if let Some(result) = simd::compare_floats(lhs, rhs, op) {
+        return result;
+    }
+    zip_map(lhs, rhs, |lhs, rhs| op.apply(lhs, rhs))
+}
+
+fn compare_ints(lhs: &[i64], rhs: &[i64], op: Comparison) -> Vec<bool> {
+    #[cfg(feature = "simd")]
+    if let Some(result) = simd::compare_ints(lhs, rhs, op) {
+        return result;
+    }
+    zip_map(lhs, rhs, |lhs, rhs| op.apply(lhs, rhs))
+}
+
+fn into_values<T>(values: Vec<T>) -> Vec<Value>
+where
+    Value: From<T>,
+{
+    values.into_iter().map(Value::from).collect()
+}
+
+impl Expression {
+    /// Whether the expression is evaluated in the columnar layout, unless it fails for some
+    /// rows of a batch.
+    pub fn is_columnar(&self) -> bool {
+        match self {
+            Self::Bool(expr) => expr.is_columnar(),
+            Self::Int(expr) => expr.is_columnar(),
+            Self::Float(expr) => expr.is_columnar(),
+            _ => false,
+        }
+    }
+
+    fn is_columnar_operand(&self) -> bool {
+        matches!(self, Self::Any(AnyExpression::Argument(_))) || self.is_columnar()
+    }
+
+    /// Evaluates the expression for all the rows of the batch in the columnar layout,
+    /// or returns `None` if it can't be evaluated this way.
+    pub fn eval_columnar(&self, batch: &ColumnarBatch) -> Option<Vec<Value>> {
+        match self {
+            Self::Bool(expr) => expr.eval_columnar(batch).map(into_values),
+            Self::Int(expr) => expr.eval_columnar(batch).map(into_values),
+            Self::Float(expr) => expr.eval_columnar(batch).map(into_values),
+            _ => None,
+        }
+    }
+
+    /// Evaluates the expression for all the rows of the batch, in the columnar layout
+    /// if possible and row by row otherwise.
+    pub fn eval_batch(&self, batch: &ColumnarBatch) -> Vec<DynResult<Value>> {
+        if let Some(values) = self.eval_columnar(batch) {
+            return values.into_iter().map(Ok).collect();
+        }
+        batch.rows.iter().map(|row| self.eval(row)).collect()
+    }
+
+    fn columnar_bool<'b>(&self, batch: &'b ColumnarBatch) -> Option<Cow<'b, [bool]>> {
+        match self {
+            Self::Bool(expr) => expr.eval_columnar(batch).map(Cow::Owned),
+            Self::Any(AnyExpression::Argument(index)) => match batch.column(*index)? {
+                ColumnBuffer::Bool(values) => Some(Cow::Borrowed(values)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn columnar_int<'b>(&self, batch: &'b ColumnarBatch) -> Option<Cow<'b, [i64]>> {
+        match self {
+            Self::Int(expr) => expr.eval_columnar(batch).map(Cow::Owned),
+            Self::Any(AnyExpression::Argument(index)) => match batch.column(*index)? {
+                ColumnBuffer::Int(values) => Some(Cow::Borrowed(values)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn columnar_float<'b>(&self, batch: &'b ColumnarBatch) -> Option<Cow<'b, [f64]>> {
+        match self {
+            Self::Float(expr) => expr.eval_columnar(batch).map(Cow::Owned),
+            Self::Any(AnyExpression::Argument(index)) => match batch.column(*index)? {
+                ColumnBuffer::Float(values) => Some(Cow::Borrowed(values)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl BoolExpression {
+    fn is_columnar(&self) -> bool {
+        match self {
+            Self::Const(_) => true,
+            Self::Not(e) | Self::CastFromInt(e) | Self::CastFromFloat(e) => e.is_columnar_operand(),
+            Self::And(lhs, rhs)
+            | Self::Or(lhs, rhs)
+            | Self::Xor(lhs, rhs)
+            | Self::IntEq(lhs, rhs)
+            | Self::IntNe(lhs, rhs)
+            | Self::IntLt(lhs, rhs)
+            | Self::IntLe(lhs, rhs)
+            | Self::IntGt(lhs, rhs)
+            | Self::IntGe(lhs, rhs)
+            | Self::FloatEq(lhs, rhs)
+            | Self::FloatNe(lhs, rhs)
+            | Self::FloatLt(lhs, rhs)
+            | Self::FloatLe(lhs, rhs)
+            | Self::FloatGt(lhs, rhs)
+            | Self::FloatGe(lhs, rhs)
+            | Self::BoolEq(lhs, rhs)
+            | Self::BoolNe(lhs, rhs)
+            | Self::BoolLe(lhs, rhs)
+            | Self::BoolLt(lhs, rhs)
+            | Self::BoolGe(lhs, rhs)
+            | Self::BoolGt(lhs, rhs) => lhs.is_columnar_operand() && rhs.is_columnar_operand(),
+            _ => false,
+        }
+    }
+
+    // Unlike row by row, both operands of `and` and `or` are always evaluated, but if the one
+    // that would be skipped fails, the batch is just evaluated row by row.
+    #[allow(clippy::float_cmp)]
+    fn eval_columnar(&self, batch: &ColumnarBatch) -> Option<Vec<bool>> {
+        let bools = |lhs: &Arc<Expression>, rhs: &Arc<Expression>, op: fn(bool, bool) -> bool| {
+            Some(zip_map(
+                &lhs.columnar_bool(batch)?,
+                &rhs.columnar_bool(batch)?,
+                op,
+            ))
+        };
+        let ints = |lhs: &Arc<Expression>, rhs: &Arc<Expression>, op: Comparison| {
+            Some(compare_ints(
+                &lhs.columnar_int(batch)?,
+                &rhs.columnar_int(batch)?,
+                op,
+            ))
+        };
+        let floats = |lhs: &Arc<Expression>, rhs: &Arc<Expression>, op: Comparison| {
+            Some(compare_floats(
+                &lhs.columnar_float(batch)?,
+                &rhs.columnar_float(batch)?,
+                op,
+            ))
+        };
+        match self {
+            Self::Const(c) => Some(vec![*c; batch.len()]),
+            Self::Not(e) => Some(map(&e.columnar_bool(batch)?, |value| !value)),
+            Self::And(lhs, rhs) => bools(lhs, rhs, |lhs, rhs| lhs && rhs),
+            Self::Or(lhs, rhs) => bools(lhs, rhs, |lhs, rhs| lhs || rhs),
+            Self::Xor(lhs, rhs) | Self::BoolNe(lhs, rhs) => bools(lhs, rhs, |lhs, rhs| lhs ^ rhs),
+            Self::BoolEq(lhs, rhs) => bools(lhs, rhs, |lhs, rhs| lhs == rhs),
+            Self::BoolLe(lhs, rhs) => bools(lhs, rhs, |lhs, rhs| lhs <= rhs),
+            Self::BoolLt(lhs, rhs) => bools(lhs, rhs, |lhs, rhs| !lhs & rhs),
+            Self::BoolGe(lhs, rhs) => bools(lhs, rhs, |lhs, rhs| lhs >= rhs),
+            Self::BoolGt(lhs, rhs) => bools(lhs, rhs, |lhs, rhs| lhs & !rhs),
+            Self::IntEq(lhs, rhs) => ints(lhs, rhs, Comparison::Eq),
+            Self::IntNe(lhs, rhs) => ints(lhs, rhs, Comparison::Ne),
+            Self::IntLt(lhs, rhs) => ints(lhs, rhs, Comparison::Lt),
+            Self::IntLe(lhs, rhs) => ints(lhs, rhs, Comparison::Le),
+            Self::IntGt(lhs, rhs) => ints(lhs, rhs, Comparison::Gt),
+            Self::IntGe(lhs, rhs) => ints(lhs, rhs, Comparison::Ge),
+            Self::FloatEq(lhs, rhs) => floats(lhs, rhs, Comparison::Eq),
+            Self::FloatNe(lhs, rhs) => floats(lhs, rhs, Comparison::Ne),
+            Self::FloatLt(lhs, rhs) => floats(lhs, rhs, Comparison::Lt),
+            Self::FloatLe(lhs, rhs) => floats(lhs, rhs, Comparison::Le),
+            Self::FloatGt(lhs, rhs) => floats(lhs, rhs, Comparison::Gt),
+            Self::FloatGe(lhs, rhs) => floats(lhs, rhs, Comparison::Ge),
+            Self::CastFromInt(e) => Some(map(&e.columnar_int(batch)?, |value| value != 0)),
+            Self::CastFromFloat(e) => Some(map(&e.columnar_float(batch)?, |value| value != 0.0)),
+            _ => None,
+        }
+    }
+}
+
+impl IntExpression {
+    fn is_columnar(&self) -> bool {
+        match self {
+            Self::Const(_) => true,
+            Self::Neg(e) | Self::Abs(e) | Self::CastFromBool(e) | Self::CastFromFloat(e) => {
+                e.is_columnar_operand()
+            }
+            Self::Add(lhs, rhs)
+            | Self::Sub(lhs, rhs)
+            | Self::Mul(lhs, rhs)
+            | Self::FloorDiv(lhs, rhs)
+            | Self::Mod(lhs, rhs)
+            | Self::Lshift(lhs, rhs)
+            | Self::Rshift(lhs, rhs)
+            | Self::And(lhs, rhs)
+            | Self::Or(lhs, rhs)
+            | Self::Xor(lhs, rhs) => lhs.is_columnar_operand() && rhs.is_columnar_operand(),
+            _ => false,
+        }
+    }
+
+    fn eval_columnar(&self, batch: &ColumnarBatch) -> Option<Vec<i64>> {
+        let ints = |lhs: &Arc<Expression>, rhs: &Arc<Expression>, op: fn(i64, i64) -> i64| {
+            Some(zip_map(
+                &lhs.columnar_int(batch)?,
+                &rhs.columnar_int(batch)?,
+                op,
+            ))
+        };
+        let nonzero_ints =
+            |lhs: &Arc<Expression>, rhs: &Arc<Expression>, op: fn(i64, i64) -> i64| {
+                let rhs = rhs.columnar_int(batch)?;
+                if rhs.contains(&0) {
+                    return None;
+                }
+                Some(zip_map(&lhs.columnar_int(batch)?, &rhs, op))
+            };
+        match self {
+            Self::Const(c) => Some(vec![*c; batch.len()]),
+            Self::Neg(e) => Some(map(&e.columnar_int(batch)?, |value| -value)),
+            Self::Abs(e) => Some(map(&e.columnar_int(batch)?, i64::abs)),
+            Self::Add(lhs, rhs) => ints(lhs, rhs, |lhs, rhs| lhs + rhs),
+            Self::Sub(lhs, rhs) => ints(lhs, rhs, |lhs, rhs| lhs - rhs),
+            Self::Mul(lhs, rhs) => ints(lhs, rhs, |lhs, rhs| lhs * rhs),
+            Self::FloorDiv(lhs, rhs) => {
+                nonzero_ints(lhs, rhs, |lhs, rhs| Integer::div_floor(&lhs, &rhs))
+            }
+            Self::Mod(lhs, rhs) => {
+                nonzero_ints(lhs, rhs, |lhs, rhs| Integer::mod_floor(&lhs, &rhs))
+            }
+            Self::Lshift(lhs, rhs) => ints(lhs, rhs, |lhs, rhs| lhs << rhs),
+            Self::Rshift(lhs, rhs) => ints(lhs, rhs, |lhs, rhs| lhs >> rhs),
+            Self::And(lhs, rhs) => ints(lhs, rhs, |lhs, rhs| lhs & rhs),
+            Self::Or(lhs, rhs) => ints(lhs, rhs, |lhs, rhs| lhs | rhs),
+            Self::Xor(lhs, rhs) => ints(lhs, rhs, |lhs, rhs| lhs ^ rhs),
+            Self::CastFromBool(e) => Some(map(&e.columnar_bool(batch)?, i64::from)),
+            #[allow(clippy::cast_possible_truncation)]
+            Self::CastFromFloat(e) => Some(map(&e.columnar_float(batch)?, |value| value as i64)),
+            _ => None,
+        }
+    }
+}
+
+impl FloatExpression {
+    fn is_columnar(&self) -> bool {
+        match self {
+            Self::Const(_) => true,
+            Self::Neg(e) | Self::Abs(e) | Self::CastFromBool(e) | Self::CastFromInt(e) => {
+                e.is_columnar_operand()
+            }
+            Self::Add(lhs, rhs)
+            | Self::Sub(lhs, rhs)
+            | Self::Mul(lhs, rhs)
+            | Self::FloorDiv(lhs, rhs)
+            | Self::TrueDiv(lhs, rhs)
+            | Self::IntTrueDiv(lhs, rhs)
+            | Self::Mod(lhs, rhs) => lhs.is_columnar_operand() && rhs.is_columnar_operand(),
+            _ => false,
+        }
+    }
+
+    fn eval_columnar(&self, batch: &ColumnarBatch) -> Option<Vec<f64>> {
+        let floats = |lhs: &Arc<Expression>, rhs: &Arc<Expression>, op: Arithmetic| {
+            Some(float_arithmetic(
+                &lhs.columnar_float(batch)?,
+                &rhs.columnar_float(batch)?,
+                op,
+            ))
+        };
+        let nonzero_floats =
+            |lhs: &Arc<Expression>, rhs: &Arc<Expression>, op: fn(f64, f64) -> f64| {
+                let rhs = rhs.columnar_float(batch)?;
+                if rhs.contains(&0.0) {
+                    return None;
+                }
+                Some(zip_map(&lhs.columnar_float(batch)?, &rhs, op))
+            };
+        match self {
+            Self::Const(c) => Some(vec![*c; batch.len()]),
+            Self::Neg(e) => Some(map(&e.columnar_float(batch)?, |value| -value)),
+            Self::Abs(e) => Some(map(&e.columnar_float(batch)?, f64::abs)),
+            Self::Add(lhs, rhs) => floats(lhs, rhs, Arithmetic::Add),
+            Self::Sub(lhs, rhs) => floats(lhs, rhs, Arithmetic::Sub),
+            Self::Mul(lhs, rhs) => floats(lhs, rhs, Arithmetic::Mul),
+            Self::FloorDiv(lhs, rhs) => nonzero_floats(lhs, rhs, |lhs, rhs| (lhs / rhs).floor()),
+            Self::TrueDiv(lhs, rhs) => {
+                let rhs = rhs.columnar_float(batch)?;
+                if rhs.contains(&0.0) {
+                    return None;
+                }
+                Some(float_arithmetic(
+                    &lhs.columnar_float(batch)?,
+                    &rhs,
+                    Arithmetic::Div,
+                ))
+            }
+            Self::Mod(lhs, rhs) => nonzero_floats(lhs, rhs, python_float_mod),
+            #[allow(clippy::cast_precision_loss)]
+            Self::IntTrueDiv(lhs, rhs) => {
+                let rhs = rhs.columnar_int(batch)?;
+                if rhs.contains(&0) {
+                    return None;
+                }
+                Some(zip_map(&lhs.columnar_int(batch)?, &rhs, |lhs, rhs| {
+                    lhs as f64 / rhs as f64
+                }))
+            }
+            Self::CastFromBool(e) => {
+                Some(map(
+                    &e.columnar_bool(batch)?,
+                    |value| {
+                        if value {
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    },
+                ))
+            }
+            #[allow(clippy::cast_precision_loss)]
+            Self::CastFromInt(e) => Some(map(&e.columnar_int(batch)?, |value| value as f64)),
+            _ => None,
+        }
+    }
+}
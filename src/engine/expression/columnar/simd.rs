@@ -0,0 +1,156 @@
+// Copyright © 2024 Pathway
+
+//! Kernels of the columnar expressions with explicit SIMD instructions, used when
+//! the CPU supports them. They return `None` otherwise, and the columns are then processed
+//! by the scalar loops. The results are exactly the same as of the scalar operators.
+
+use super::{Arithmetic, Comparison};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use std::arch::x86_64::{
+        __m256i, _mm256_add_pd, _mm256_castsi256_pd, _mm256_cmp_pd, _mm256_cmpeq_epi64,
+        _mm256_cmpgt_epi64, _mm256_div_pd, _mm256_loadu_pd, _mm256_loadu_si256, _mm256_movemask_pd,
+        _mm256_mul_pd, _mm256_storeu_pd, _mm256_sub_pd, _CMP_EQ_OQ, _CMP_GE_OQ, _CMP_GT_OQ,
+        _CMP_LE_OQ, _CMP_LT_OQ, _CMP_NEQ_UQ,
+    };
+
+    use super::{Arithmetic, Comparison};
+
+    /// The number of 64-bit values in a 256-bit register.
+    const LANES: usize = 4;
+
+    /// Pushes the lanes of the mask, with the highest bits of the lanes set if they are true.
+    fn push_mask(result: &mut Vec<bool>, mask: i32) {
+        result.extend((0..LANES).map(|lane| mask & (1 << lane) != 0));
+    }
+
+    /// # Safety
+    ///
+    /// The CPU has to support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn float_arithmetic(lhs: &[f64], rhs: &[f64], op: Arithmetic) -> Vec<f64> {
+        let len = lhs.len().min(rhs.len());
+        let vectorized_len = len - len % LANES;
+        let mut result: Vec<f64> = Vec::with_capacity(len);
+        for start in (0..vectorized_len).step_by(LANES) {
+            let lhs_lanes = _mm256_loadu_pd(lhs.as_ptr().add(start));
+            let rhs_lanes = _mm256_loadu_pd(rhs.as_ptr().add(start));
+            let result_lanes = match op {
+                Arithmetic::Add => _mm256_add_pd(lhs_lanes, rhs_lanes),
+                Arithmetic::Sub => _mm256_sub_pd(lhs_lanes, rhs_lanes),
+                Arithmetic::Mul => _mm256_mul_pd(lhs_lanes, rhs_lanes),
+                Arithmetic::Div => _mm256_div_pd(lhs_lanes, rhs_lanes),
+            };
+            _mm256_storeu_pd(result.as_mut_ptr().add(start), result_lanes);
+        }
+        result.set_len(vectorized_len);
+        result.extend(
+            lhs[vectorized_len..len]
+                .iter()
+                .zip(&rhs[vectorized_len..len])
+                .map(|(lhs, rhs)| op.apply(*lhs, *rhs)),
+        );
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The CPU has to support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn compare_floats(lhs: &[f64], rhs: &[f64], op: Comparison) -> Vec<bool> {
+        let len = lhs.len().min(rhs.len());
+        let vectorized_len = len - len % LANES;
+        let mut result = Vec::with_capacity(len);
+        for start in (0..vectorized_len).step_by(LANES) {
+            let lhs_lanes = _mm256_loadu_pd(lhs.as_ptr().add(start));
+            let rhs_lanes = _mm256_loadu_pd(rhs.as_ptr().add(start));
+            // the ordered predicates are false and the unordered ones true for NaNs,
+            // like the comparisons of Rust
+            let mask = match op {
+                Comparison::Eq => _mm256_cmp_pd::<_CMP_EQ_OQ>(lhs_lanes, rhs_lanes),
+                Comparison::Ne => _mm256_cmp_pd::<_CMP_NEQ_UQ>(lhs_lanes, rhs_lanes),
+                Comparison::Lt => _mm256_cmp_pd::<_CMP_LT_OQ>(lhs_lanes, rhs_lanes),
+                Comparison::Le => _mm256_cmp_pd::<_CMP_LE_OQ>(lhs_lanes, rhs_lanes),
+                Comparison::Gt => _mm256_cmp_pd::<_CMP_GT_OQ>(lhs_lanes, rhs_lanes),
+                Comparison::Ge => _mm256_cmp_pd::<_CMP_GE_OQ>(lhs_lanes, rhs_lanes),
+            };
+            push_mask(&mut result, _mm256_movemask_pd(mask));
+        }
+        result.extend(
+            lhs[vectorized_len..len]
+                .iter()
+                .zip(&rhs[vectorized_len..len])
+                .map(|(lhs, rhs)| op.apply(*lhs, *rhs)),
+        );
+        result
+    }
+
+    /// # Safety
+    ///
+    /// The CPU has to support AVX2.
+    #[target_feature(enable = "avx2")]
+    #[allow(clippy::cast_ptr_alignment)] // the loads are unaligned
+    pub(super) unsafe fn compare_ints(lhs: &[i64], rhs: &[i64], op: Comparison) -> Vec<bool> {
+        const ALL_LANES: i32 = (1 << LANES) - 1;
+
+        let len = lhs.len().min(rhs.len());
+        let vectorized_len = len - len % LANES;
+        let mut result = Vec::with_capacity(len);
+        for start in (0..vectorized_len).step_by(LANES) {
+            let lhs_lanes = _mm256_loadu_si256(lhs.as_ptr().add(start).cast::<__m256i>());
+            let rhs_lanes = _mm256_loadu_si256(rhs.as_ptr().add(start).cast::<__m256i>());
+            // only the equality and the greater-than comparisons are available
+            let (mask, negated) = match op {
+                Comparison::Eq => (_mm256_cmpeq_epi64(lhs_lanes, rhs_lanes), false),
+                Comparison::Ne => (_mm256_cmpeq_epi64(lhs_lanes, rhs_lanes), true),
+                Comparison::Lt => (_mm256_cmpgt_epi64(rhs_lanes, lhs_lanes), false),
+                Comparison::Le => (_mm256_cmpgt_epi64(lhs_lanes, rhs_lanes), true),
+                Comparison::Gt => (_mm256_cmpgt_epi64(lhs_lanes, rhs_lanes), false),
+                Comparison::Ge => (_mm256_cmpgt_epi64(rhs_lanes, lhs_lanes), true),
+            };
+            let mask = _mm256_movemask_pd(_mm256_castsi256_pd(mask));
+            push_mask(&mut result, if negated { mask ^ ALL_LANES } else { mask });
+        }
+        result.extend(
+            lhs[vectorized_len..len]
+                .iter()
+                .zip(&rhs[vectorized_len..len])
+                .map(|(lhs, rhs)| op.apply(*lhs, *rhs)),
+        );
+        result
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+pub(super) fn float_arithmetic(lhs: &[f64], rhs: &[f64], op: Arithmetic) -> Option<Vec<f64>> {
+    // SAFETY: the CPU supports AVX2
+    is_x86_feature_detected!("avx2").then(|| unsafe { avx2::float_arithmetic(lhs, rhs, op) })
+}
+
+#[cfg(target_arch = "x86_64")]
+pub(super) fn compare_floats(lhs: &[f64], rhs: &[f64], op: Comparison) -> Option<Vec<bool>> {
+    // SAFETY: the CPU supports AVX2
+    is_x86_feature_detected!("avx2").then(|| unsafe { avx2::compare_floats(lhs, rhs, op) })
+}
+
+#[cfg(target_arch = "x86_64")]
+pub(super) fn compare_ints(lhs: &[i64], rhs: &[i64], op: Comparison) -> Option<Vec<bool>> {
+    // SAFETY: the CPU supports AVX2
+    is_x86_feature_detected!("avx2").then(|| unsafe { avx2::compare_ints(lhs, rhs, op) })
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub(super) fn float_arithmetic(_lhs: &[f64], _rhs: &[f64], _op: Arithmetic) -> Option<Vec<f64>> {
+    None
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub(super) fn compare_floats(_lhs: &[f64], _rhs: &[f64], _op: Comparison) -> Option<Vec<bool>> {
+    None
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub(super) fn compare_ints(_lhs: &[i64], _rhs: &[i64], _op: Comparison) -> Option<Vec<bool>> {
+    None
+}
@@ -0,0 +1,579 @@
+// Copyright © 2024 Pathway
+
+//! Checking of the types of the operands of the expressions when the graph is built.
+//!
+//! The typed expressions evaluate their operands as values of fixed types, and an operand
+//! of another type makes every row fail. The types of the arguments come from the
+//! properties of the columns, so a mismatch is reported before the dataflow runs.
+//! The types that are not known, e.g. of optional columns, are [`Type::Any`] and are not
+//! checked, these operands are still checked for each row when they are evaluated.
+//!
+//! The nullability of the results is tracked as well. The operands of the typed expressions
+//! that are None for some rows independently of the values of the arguments are rejected.
+//! The nullability of the arguments is not enforced, as the types of the columns can be
+//! narrowed by the user, e.g. with `declare_type`, without the engine knowing it.
+
+use std::sync::Arc;
+
+use smallvec::{smallvec, SmallVec};
+
+use super::{
+    AnyExpression, BoolExpression, DateTimeNaiveExpression, DateTimeUtcExpression,
+    DurationExpression, Expression, Expressions, FloatExpression, IntExpression, PointerExpression,
+    StringExpression,
+};
+use crate::engine::{Error, Type, Value};
+
+type Operands<'a> = SmallVec<[(&'a Expression, Type); 4]>;
+
+fn value_type(value: &Value) -> Type {
+    match value {
+        Value::None | Value::SerializedObject(_) => Type::Any,
+        Value::Bool(_) => Type::Bool,
+        Value::Int(_) => Type::Int,
+        Value::Float(_) => Type::Float,
+        Value::Pointer(_) => Type::Pointer,
+        Value::String(_) => Type::String,
+        Value::Bytes(_) => Type::Bytes,
+        Value::Tuple(_) => Type::Tuple,
+        Value::IntArray(_)
+        | Value::FloatArray(_)
+        | Value::Float32Array(_)
+        | Value::SparseMatrix(_) => Type::Array,
+        Value::DateTimeNaive(_) => Type::DateTimeNaive,
+        Value::DateTimeUtc(_) => Type::DateTimeUtc,
+        Value::Duration(_) => Type::Duration,
+        Value::Json(_) => Type::Json,
+    }
+}
+
+fn unchecked(expressions: &Expressions) -> Operands<'_> {
+    match expressions {
+        Expressions::Explicit(expressions) => expressions
+            .iter()
+            .map(|expression| (expression.as_ref(), Type::Any))
+            .collect(),
+        Expressions::AllArguments | Expressions::Arguments(_) => SmallVec::new(),
+    }
+}
+
+fn same<'a>(operands: &[&'a Arc<Expression>], dtype: Type) -> Operands<'a> {
+    operands
+        .iter()
+        .map(|&operand| (operand.as_ref(), dtype))
+        .collect()
+}
+
+impl AnyExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::Argument(_) | Self::Const(_) => SmallVec::new(),
+            Self::Apply(_, args) | Self::OptionalPointerFrom(args) | Self::MakeTuple(args) => {
+                unchecked(args)
+            }
+            Self::IfElse(if_, then, else_) => {
+                smallvec![
+                    (if_.as_ref(), Type::Bool),
+                    (then.as_ref(), Type::Any),
+                    (else_.as_ref(), Type::Any)
+                ]
+            }
+            Self::TupleGetItemChecked(tuple, index, default)
+            | Self::JsonGetItem(tuple, index, default) => same(&[tuple, index, default], Type::Any),
+            Self::TupleGetItemUnchecked(lhs, rhs) | Self::MatMul(lhs, rhs) => {
+                same(&[lhs, rhs], Type::Any)
+            }
+            Self::JsonToOptional(e, _)
+            | Self::ParseStringToInt(e, _)
+            | Self::ParseStringToFloat(e, _)
+            | Self::ParseStringToBool(e, _, _, _)
+            | Self::Unwrap(e)
+            | Self::CastToOptionalIntFromOptionalFloat(e)
+            | Self::CastToOptionalFloatFromOptionalInt(e) => same(&[e], Type::Any),
+        }
+    }
+}
+
+impl BoolExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::Const(_) => SmallVec::new(),
+            Self::IsNone(e) => same(&[e], Type::Any),
+            Self::Not(e) => same(&[e], Type::Bool),
+            Self::CastFromInt(e) => same(&[e], Type::Int),
+            Self::CastFromFloat(e) => same(&[e], Type::Float),
+            Self::CastFromString(e) => same(&[e], Type::String),
+            Self::And(lhs, rhs)
+            | Self::Or(lhs, rhs)
+            | Self::Xor(lhs, rhs)
+            | Self::BoolEq(lhs, rhs)
+            | Self::BoolNe(lhs, rhs)
+            | Self::BoolLe(lhs, rhs)
+            | Self::BoolLt(lhs, rhs)
+            | Self::BoolGe(lhs, rhs)
+            | Self::BoolGt(lhs, rhs) => same(&[lhs, rhs], Type::Bool),
+            Self::IntEq(lhs, rhs)
+            | Self::IntNe(lhs, rhs)
+            | Self::IntLt(lhs, rhs)
+            | Self::IntLe(lhs, rhs)
+            | Self::IntGt(lhs, rhs)
+            | Self::IntGe(lhs, rhs) => same(&[lhs, rhs], Type::Int),
+            Self::FloatEq(lhs, rhs)
+            | Self::FloatNe(lhs, rhs)
+            | Self::FloatLt(lhs, rhs)
+            | Self::FloatLe(lhs, rhs)
+            | Self::FloatGt(lhs, rhs)
+            | Self::FloatGe(lhs, rhs) => same(&[lhs, rhs], Type::Float),
+            Self::StringEq(lhs, rhs)
+            | Self::StringNe(lhs, rhs)
+            | Self::StringLt(lhs, rhs)
+            | Self::StringLe(lhs, rhs)
+            | Self::StringGt(lhs, rhs)
+            | Self::StringGe(lhs, rhs) => same(&[lhs, rhs], Type::String),
+            Self::PtrEq(lhs, rhs)
+            | Self::PtrNe(lhs, rhs)
+            | Self::PtrLe(lhs, rhs)
+            | Self::PtrLt(lhs, rhs)
+            | Self::PtrGe(lhs, rhs)
+            | Self::PtrGt(lhs, rhs) => same(&[lhs, rhs], Type::Pointer),
+            Self::DateTimeNaiveEq(lhs, rhs)
+            | Self::DateTimeNaiveNe(lhs, rhs)
+            | Self::DateTimeNaiveLt(lhs, rhs)
+            | Self::DateTimeNaiveLe(lhs, rhs)
+            | Self::DateTimeNaiveGt(lhs, rhs)
+            | Self::DateTimeNaiveGe(lhs, rhs) => same(&[lhs, rhs], Type::DateTimeNaive),
+            Self::DateTimeUtcEq(lhs, rhs)
+            | Self::DateTimeUtcNe(lhs, rhs)
+            | Self::DateTimeUtcLt(lhs, rhs)
+            | Self::DateTimeUtcLe(lhs, rhs)
+            | Self::DateTimeUtcGt(lhs, rhs)
+            | Self::DateTimeUtcGe(lhs, rhs) => same(&[lhs, rhs], Type::DateTimeUtc),
+            Self::DurationEq(lhs, rhs)
+            | Self::DurationNe(lhs, rhs)
+            | Self::DurationLt(lhs, rhs)
+            | Self::DurationLe(lhs, rhs)
+            | Self::DurationGt(lhs, rhs)
+            | Self::DurationGe(lhs, rhs) => same(&[lhs, rhs], Type::Duration),
+            Self::TupleEq(lhs, rhs)
+            | Self::TupleNe(lhs, rhs)
+            | Self::TupleLe(lhs, rhs)
+            | Self::TupleLt(lhs, rhs)
+            | Self::TupleGe(lhs, rhs)
+            | Self::TupleGt(lhs, rhs) => same(&[lhs, rhs], Type::Tuple),
+            Self::Eq(lhs, rhs) | Self::Ne(lhs, rhs) => same(&[lhs, rhs], Type::Any),
+        }
+    }
+}
+
+impl IntExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::Const(_) => SmallVec::new(),
+            Self::Neg(e) | Self::Abs(e) => same(&[e], Type::Int),
+            Self::Add(lhs, rhs)
+            | Self::Sub(lhs, rhs)
+            | Self::Mul(lhs, rhs)
+            | Self::FloorDiv(lhs, rhs)
+            | Self::Mod(lhs, rhs)
+            | Self::Pow(lhs, rhs)
+            | Self::Lshift(lhs, rhs)
+            | Self::Rshift(lhs, rhs)
+            | Self::And(lhs, rhs)
+            | Self::Or(lhs, rhs)
+            | Self::Xor(lhs, rhs) => same(&[lhs, rhs], Type::Int),
+            Self::DateTimeNaiveNanosecond(e)
+            | Self::DateTimeNaiveMicrosecond(e)
+            | Self::DateTimeNaiveMillisecond(e)
+            | Self::DateTimeNaiveSecond(e)
+            | Self::DateTimeNaiveMinute(e)
+            | Self::DateTimeNaiveHour(e)
+            | Self::DateTimeNaiveDay(e)
+            | Self::DateTimeNaiveMonth(e)
+            | Self::DateTimeNaiveYear(e)
+            | Self::DateTimeNaiveTimestampNs(e)
+            | Self::DateTimeNaiveWeekday(e)
+            | Self::DateTimeNaiveIsoWeek(e)
+            | Self::DateTimeNaiveIsoYear(e)
+            | Self::DateTimeNaiveQuarter(e)
+            | Self::DateTimeNaiveDayOfYear(e) => same(&[e], Type::DateTimeNaive),
+            Self::DateTimeUtcNanosecond(e)
+            | Self::DateTimeUtcMicrosecond(e)
+            | Self::DateTimeUtcMillisecond(e)
+            | Self::DateTimeUtcSecond(e)
+            | Self::DateTimeUtcMinute(e)
+            | Self::DateTimeUtcHour(e)
+            | Self::DateTimeUtcDay(e)
+            | Self::DateTimeUtcMonth(e)
+            | Self::DateTimeUtcYear(e)
+            | Self::DateTimeUtcTimestampNs(e)
+            | Self::DateTimeUtcWeekday(e)
+            | Self::DateTimeUtcIsoWeek(e)
+            | Self::DateTimeUtcIsoYear(e)
+            | Self::DateTimeUtcQuarter(e)
+            | Self::DateTimeUtcDayOfYear(e) => same(&[e], Type::DateTimeUtc),
+            Self::DateTimeUtcOffsetSeconds(e, timezone) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeUtc),
+                    (timezone.as_ref(), Type::String)
+                ]
+            }
+            Self::DurationFloorDiv(lhs, rhs) => same(&[lhs, rhs], Type::Duration),
+            Self::DurationNanoseconds(e)
+            | Self::DurationMicroseconds(e)
+            | Self::DurationMilliseconds(e)
+            | Self::DurationSeconds(e)
+            | Self::DurationMinutes(e)
+            | Self::DurationHours(e)
+            | Self::DurationDays(e)
+            | Self::DurationWeeks(e) => same(&[e], Type::Duration),
+            Self::CastFromBool(e) => same(&[e], Type::Bool),
+            Self::CastFromFloat(e) => same(&[e], Type::Float),
+            Self::CastFromString(e) => same(&[e], Type::String),
+            Self::EstimatedSize(e) => same(&[e], Type::Any),
+        }
+    }
+}
+
+impl FloatExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::Const(_) => SmallVec::new(),
+            Self::Neg(e) | Self::Abs(e) => same(&[e], Type::Float),
+            Self::Add(lhs, rhs)
+            | Self::Sub(lhs, rhs)
+            | Self::Mul(lhs, rhs)
+            | Self::FloorDiv(lhs, rhs)
+            | Self::TrueDiv(lhs, rhs)
+            | Self::Mod(lhs, rhs)
+            | Self::Pow(lhs, rhs) => same(&[lhs, rhs], Type::Float),
+            Self::IntTrueDiv(lhs, rhs) => same(&[lhs, rhs], Type::Int),
+            Self::DurationTrueDiv(lhs, rhs) => same(&[lhs, rhs], Type::Duration),
+            Self::DateTimeNaiveTimestamp(e, unit) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeNaive),
+                    (unit.as_ref(), Type::String)
+                ]
+            }
+            Self::DateTimeUtcTimestamp(e, unit) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeUtc),
+                    (unit.as_ref(), Type::String)
+                ]
+            }
+            Self::DateTimeNaiveExcelSerial(e) | Self::DateTimeNaiveJulianDay(e) => {
+                same(&[e], Type::DateTimeNaive)
+            }
+            Self::DateTimeUtcJulianDay(e) => same(&[e], Type::DateTimeUtc),
+            Self::CastFromBool(e) => same(&[e], Type::Bool),
+            Self::CastFromInt(e) => same(&[e], Type::Int),
+            Self::CastFromString(e) => same(&[e], Type::String),
+            Self::PointerSampleRank(pointer, seed) => {
+                smallvec![
+                    (pointer.as_ref(), Type::Pointer),
+                    (seed.as_ref(), Type::Int)
+                ]
+            }
+        }
+    }
+}
+
+impl PointerExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::PointerFrom(args) => unchecked(args),
+        }
+    }
+}
+
+impl StringExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::Add(lhs, rhs) => same(&[lhs, rhs], Type::String),
+            Self::Mul(lhs, rhs) => {
+                smallvec![(lhs.as_ref(), Type::String), (rhs.as_ref(), Type::Int)]
+            }
+            Self::CastFromBool(e) => same(&[e], Type::Bool),
+            Self::CastFromFloat(e) => same(&[e], Type::Float),
+            Self::CastFromInt(e) => same(&[e], Type::Int),
+            Self::DateTimeNaiveStrftime(e, fmt) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeNaive),
+                    (fmt.as_ref(), Type::String)
+                ]
+            }
+            Self::DateTimeUtcStrftime(e, fmt) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeUtc),
+                    (fmt.as_ref(), Type::String)
+                ]
+            }
+            Self::DateTimeUtcStrftimeInTimezone(e, fmt, timezone) => smallvec![
+                (e.as_ref(), Type::DateTimeUtc),
+                (fmt.as_ref(), Type::String),
+                (timezone.as_ref(), Type::String)
+            ],
+            Self::ToString(e) => same(&[e], Type::Any),
+        }
+    }
+}
+
+impl DateTimeNaiveExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::AddDuration(lhs, rhs) | Self::SubDuration(lhs, rhs) => {
+                smallvec![
+                    (lhs.as_ref(), Type::DateTimeNaive),
+                    (rhs.as_ref(), Type::Duration)
+                ]
+            }
+            Self::Strptime(e, fmt) => same(&[e, fmt], Type::String),
+            Self::FromUtc(e, timezone) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeUtc),
+                    (timezone.as_ref(), Type::String)
+                ]
+            }
+            Self::Round(e, duration) | Self::Floor(e, duration) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeNaive),
+                    (duration.as_ref(), Type::Duration)
+                ]
+            }
+            Self::FromTimestamp(e, unit) => {
+                smallvec![(e.as_ref(), Type::Int), (unit.as_ref(), Type::String)]
+            }
+            Self::FromFloatTimestamp(e, unit) => {
+                smallvec![(e.as_ref(), Type::Float), (unit.as_ref(), Type::String)]
+            }
+            Self::FromExcelSerial(e) | Self::FromJulianDay(e) => same(&[e], Type::Float),
+            Self::FloorToCalendarUnit(e, unit) | Self::RoundToCalendarUnit(e, unit) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeNaive),
+                    (unit.as_ref(), Type::String)
+                ]
+            }
+            Self::AddCalendarUnits(e, unit, count) => smallvec![
+                (e.as_ref(), Type::DateTimeNaive),
+                (unit.as_ref(), Type::String),
+                (count.as_ref(), Type::Int)
+            ],
+        }
+    }
+}
+
+impl DateTimeUtcExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::AddDuration(lhs, rhs) | Self::SubDuration(lhs, rhs) => {
+                smallvec![
+                    (lhs.as_ref(), Type::DateTimeUtc),
+                    (rhs.as_ref(), Type::Duration)
+                ]
+            }
+            Self::Strptime(e, fmt) => same(&[e, fmt], Type::String),
+            Self::ParseRfc3339(e) | Self::ParseRfc2822(e) => same(&[e], Type::String),
+            Self::FromNaive(e, timezone) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeNaive),
+                    (timezone.as_ref(), Type::String)
+                ]
+            }
+            Self::Round(e, duration) | Self::Floor(e, duration) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeUtc),
+                    (duration.as_ref(), Type::Duration)
+                ]
+            }
+            Self::FloorToCalendarUnit(e, unit) | Self::RoundToCalendarUnit(e, unit) => {
+                smallvec![
+                    (e.as_ref(), Type::DateTimeUtc),
+                    (unit.as_ref(), Type::String)
+                ]
+            }
+            Self::AddCalendarUnits(e, unit, count) => smallvec![
+                (e.as_ref(), Type::DateTimeUtc),
+                (unit.as_ref(), Type::String),
+                (count.as_ref(), Type::Int)
+            ],
+            Self::FloorToCalendarUnitInTimezone(e, unit, timezone)
+            | Self::RoundToCalendarUnitInTimezone(e, unit, timezone) => smallvec![
+                (e.as_ref(), Type::DateTimeUtc),
+                (unit.as_ref(), Type::String),
+                (timezone.as_ref(), Type::String)
+            ],
+            Self::AddCalendarUnitsInTimezone(e, unit, count, timezone) => smallvec![
+                (e.as_ref(), Type::DateTimeUtc),
+                (unit.as_ref(), Type::String),
+                (count.as_ref(), Type::Int),
+                (timezone.as_ref(), Type::String)
+            ],
+        }
+    }
+}
+
+impl DurationExpression {
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::Neg(e) => same(&[e], Type::Duration),
+            Self::Add(lhs, rhs) | Self::Sub(lhs, rhs) | Self::Mod(lhs, rhs) => {
+                same(&[lhs, rhs], Type::Duration)
+            }
+            Self::MulByInt(lhs, rhs) | Self::DivByInt(lhs, rhs) | Self::TrueDivByInt(lhs, rhs) => {
+                smallvec![(lhs.as_ref(), Type::Duration), (rhs.as_ref(), Type::Int)]
+            }
+            Self::MulByFloat(lhs, rhs) | Self::DivByFloat(lhs, rhs) => {
+                smallvec![(lhs.as_ref(), Type::Duration), (rhs.as_ref(), Type::Float)]
+            }
+            Self::DateTimeNaiveSub(lhs, rhs) => same(&[lhs, rhs], Type::DateTimeNaive),
+            Self::DateTimeUtcSub(lhs, rhs) => same(&[lhs, rhs], Type::DateTimeUtc),
+        }
+    }
+}
+
+impl Expression {
+    /// Returns the type of the results of the expression for the arguments of the given
+    /// types, or [`Type::Any`] if it's not known before the expression is evaluated.
+    pub fn result_type(&self, argument_types: &[Type]) -> Type {
+        match self {
+            Self::Bool(_) => Type::Bool,
+            Self::Int(_) => Type::Int,
+            Self::Float(_) => Type::Float,
+            Self::Pointer(_) => Type::Pointer,
+            Self::String(_) => Type::String,
+            Self::DateTimeNaive(_) => Type::DateTimeNaive,
+            Self::DateTimeUtc(_) => Type::DateTimeUtc,
+            Self::Duration(_) => Type::Duration,
+            Self::Any(AnyExpression::Argument(index)) => {
+                argument_types.get(*index).copied().unwrap_or_default()
+            }
+            Self::Any(AnyExpression::Const(value)) => value_type(value),
+            Self::Any(AnyExpression::MakeTuple(_)) => Type::Tuple,
+            Self::Any(AnyExpression::IfElse(_, then, else_)) => {
+                let then_type = then.result_type(argument_types);
+                if then_type == else_.result_type(argument_types) {
+                    then_type
+                } else {
+                    Type::Any
+                }
+            }
+            Self::Any(_) => Type::Any,
+        }
+    }
+
+    /// Checks that the operands of the expression and of its subexpressions have the types
+    /// the operations expect, given the types of the arguments.
+    pub fn check_types(&self, argument_types: &[Type]) -> Result<(), Error> {
+        for (operand, expected) in self.operands() {
+            operand.check_types(argument_types)?;
+            let actual = operand.result_type(argument_types);
+            if expected != Type::Any && actual != Type::Any && actual != expected {
+                return Err(Error::OperandTypeMismatch {
+                    operation: self.operation_name(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the results of the expression can be None, given whether
+    /// the arguments can be None. The arguments not given are assumed to be possibly None.
+    pub fn is_nullable(&self, nullable_arguments: &[bool]) -> bool {
+        match self {
+            Self::Any(expression) => match expression {
+                AnyExpression::Argument(index) => {
+                    nullable_arguments.get(*index).copied().unwrap_or(true)
+                }
+                AnyExpression::Const(value) => *value == Value::None,
+                AnyExpression::IfElse(_, then, else_) => {
+                    then.is_nullable(nullable_arguments) || else_.is_nullable(nullable_arguments)
+                }
+                AnyExpression::CastToOptionalIntFromOptionalFloat(e)
+                | AnyExpression::CastToOptionalFloatFromOptionalInt(e) => {
+                    e.is_nullable(nullable_arguments)
+                }
+                AnyExpression::ParseStringToInt(_, optional)
+                | AnyExpression::ParseStringToFloat(_, optional)
+                | AnyExpression::ParseStringToBool(_, _, _, optional) => *optional,
+                AnyExpression::MakeTuple(_) | AnyExpression::Unwrap(_) => false,
+                _ => true,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns whether the expression is None for some rows independently of the values
+    /// of its arguments, i.e. whether None is one of the constants it can return.
+    fn produces_none(&self) -> bool {
+        match self {
+            Self::Any(AnyExpression::Const(value)) => *value == Value::None,
+            Self::Any(AnyExpression::IfElse(_, then, else_)) => {
+                then.produces_none() || else_.produces_none()
+            }
+            Self::Any(
+                AnyExpression::CastToOptionalIntFromOptionalFloat(e)
+                | AnyExpression::CastToOptionalFloatFromOptionalInt(e),
+            ) => e.produces_none(),
+            _ => false,
+        }
+    }
+
+    /// Checks that the operands of the expression and of its subexpressions that have to be
+    /// of a specific type can't be None independently of the values of the arguments.
+    pub fn check_nullability(&self) -> Result<(), Error> {
+        let operands = self.operands();
+        // the branches are evaluated only for some rows, e.g. for the rows in which
+        // the value checked by the condition is not None
+        let checked = match self {
+            Self::Any(AnyExpression::IfElse(..)) => &operands[..1],
+            _ => &operands[..],
+        };
+        for &(operand, expected) in checked {
+            operand.check_nullability()?;
+            if expected != Type::Any && operand.produces_none() {
+                return Err(Error::NullableOperand {
+                    operation: self.operation_name(),
+                    expected,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn operands(&self) -> Operands<'_> {
+        match self {
+            Self::Bool(expression) => expression.operands(),
+            Self::Int(expression) => expression.operands(),
+            Self::Float(expression) => expression.operands(),
+            Self::Pointer(expression) => expression.operands(),
+            Self::String(expression) => expression.operands(),
+            Self::DateTimeNaive(expression) => expression.operands(),
+            Self::DateTimeUtc(expression) => expression.operands(),
+            Self::Duration(expression) => expression.operands(),
+            Self::Any(expression) => expression.operands(),
+        }
+    }
+
+    #[cold]
+    fn operation_name(&self) -> String {
+        let (kind, description) = match self {
+            Self::Bool(expression) => ("BoolExpression", format!("{expression:?}")),
+            Self::Int(expression) => ("IntExpression", format!("{expression:?}")),
+            Self::Float(expression) => ("FloatExpression", format!("{expression:?}")),
+            Self::Pointer(expression) => ("PointerExpression", format!("{expression:?}")),
+            Self::String(expression) => ("StringExpression", format!("{expression:?}")),
+            Self::DateTimeNaive(expression) => {
+                ("DateTimeNaiveExpression", format!("{expression:?}"))
+            }
+            Self::DateTimeUtc(expression) => ("DateTimeUtcExpression", format!("{expression:?}")),
+            Self::Duration(expression) => ("DurationExpression", format!("{expression:?}")),
+            Self::Any(expression) => ("AnyExpression", format!("{expression:?}")),
+        };
+        let variant = description
+            .split(['(', ' ', '{'])
+            .next()
+            .unwrap_or_default();
+        format!("{kind}::{variant}")
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::cell::Cell;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 
 use futures::future::BoxFuture;
@@ -16,6 +16,7 @@ use scopeguard::defer;
 use crate::connectors::data_format::{Formatter, Parser};
 use crate::connectors::data_storage::{ReaderBuilder, Writer};
 use crate::connectors::monitoring::ConnectorStats;
+use crate::persistence::schema::SchemaColumn;
 use crate::persistence::ExternalPersistentId;
 
 use super::error::{DynResult, Trace};
@@ -164,6 +165,51 @@ impl ColumnPath {
             }
         }
     }
+
+    /// Returns the properties of the column at the path, if the properties of the table
+    /// include them.
+    pub fn extract_column_properties<'a>(
+        &self,
+        table_properties: &'a TableProperties,
+    ) -> Option<&'a ColumnProperties> {
+        let ColumnPath::ValuePath(path) = self else {
+            return None;
+        };
+        let mut table_properties = table_properties;
+        for i in path {
+            let TableProperties::Table(inner) = table_properties else {
+                return None;
+            };
+            table_properties = inner.get(*i)?;
+        }
+        match table_properties {
+            TableProperties::Column(properties) => Some(properties.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Returns the type of the values at the path, or [`Type::Any`] if the properties
+    /// of the table don't determine it.
+    pub fn extract_type(&self, table_properties: &TableProperties) -> Type {
+        match self {
+            ColumnPath::Key => Type::Pointer,
+            ColumnPath::ValuePath(_) => self
+                .extract_column_properties(table_properties)
+                .map_or(Type::Any, |properties| properties.dtype),
+        }
+    }
+
+    /// Returns whether the values at the path can be None, which is assumed if the properties
+    /// of the table don't determine it.
+    pub fn extract_nullable(&self, table_properties: &TableProperties) -> bool {
+        match self {
+            ColumnPath::Key => false,
+            ColumnPath::ValuePath(_) => match self.extract_column_properties(table_properties) {
+                Some(properties) => properties.nullable,
+                None => true,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -293,6 +339,7 @@ impl ComplexColumn {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ColumnProperties {
     pub dtype: Type,
+    pub nullable: bool,
     pub append_only: bool,
     pub trace: Trace,
 }
@@ -376,6 +423,20 @@ impl TableProperties {
             _ => &Trace::Empty,
         }
     }
+
+    /// Returns the same properties with all the columns marked as possibly None.
+    pub fn with_nullable(&self) -> Self {
+        match self {
+            Self::Table(inner) => Self::Table(inner.iter().map(Self::with_nullable).collect()),
+            Self::Column(properties) if !properties.nullable => {
+                Self::Column(Arc::new(ColumnProperties {
+                    nullable: true,
+                    ..properties.as_ref().clone()
+                }))
+            }
+            Self::Column(_) | Self::Empty => self.clone(),
+        }
+    }
 }
 
 pub type IterationLogic<'a> = Box<
@@ -392,13 +453,19 @@ pub type IterationLogic<'a> = Box<
 pub enum BatchWrapper {
     None,
     WithGil,
+    /// Like `WithGil`, but operators supporting it process their input in batches and
+    /// release the GIL in between. Batch sizes adapt so that the 99th percentile of
+    /// batch processing times stays below the given latency target.
+    AdaptiveWithGil(Duration),
 }
 
 impl BatchWrapper {
     pub fn run<R>(&self, logic: impl FnOnce() -> R) -> R {
         match self {
             BatchWrapper::None => logic(),
-            BatchWrapper::WithGil => Python::with_gil(|_| logic()),
+            BatchWrapper::WithGil | BatchWrapper::AdaptiveWithGil(_) => {
+                Python::with_gil(|_| logic())
+            }
         }
     }
 }
@@ -425,6 +492,18 @@ impl JoinType {
             _ => Err(Error::BadJoinType),
         }
     }
+
+    /// Whether the rows of the left table without a match are kept, with None values
+    /// in place of the right side.
+    pub fn keeps_unmatched_left(self) -> bool {
+        matches!(self, Self::LeftOuter | Self::FullOuter | Self::LeftKeysFull)
+    }
+
+    /// Whether the rows of the right table without a match are kept, with None values
+    /// in place of the left side.
+    pub fn keeps_unmatched_right(self) -> bool {
+        matches!(self, Self::RightOuter | Self::FullOuter)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -454,6 +533,20 @@ pub struct OperatorStats {
     pub lag: Option<u64>,
     #[pyo3(get, set)]
     pub done: bool,
+    /// Approximate number of bytes kept by the operator in this worker.
+    #[pyo3(get, set)]
+    pub state_size: Option<u64>,
+    /// Number of the records sent to the operator and not processed yet in this process,
+    /// if the pending work is tracked.
+    #[pyo3(get, set)]
+    pub pending_records: Option<u64>,
+    /// Number of the row changes computed by the operator in this worker.
+    #[pyo3(get, set)]
+    pub rows_processed: u64,
+    /// The wall clock time, in milliseconds since the Unix epoch, when the operator last
+    /// computed rows or advanced its time.
+    #[pyo3(get, set)]
+    pub last_activity: Option<u64>,
 }
 
 impl OperatorStats {
@@ -476,6 +569,167 @@ impl OperatorStats {
     }
 }
 
+/// An operator of the computation graph, with the operators whose tables it reads.
+/// The graph is built by the Python API, which passes its topology to the engine,
+/// so that it can be served by the http server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorNode {
+    pub id: usize,
+    pub name: String,
+    pub inputs: Vec<usize>,
+}
+
+/// Records sent from one operator to another and not processed yet in this process.
+/// The operators are `None` for the inputs and the outputs. The records buffered within
+/// an operator are reported with the same source and target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[pyclass]
+pub struct EdgeStats {
+    #[pyo3(get, set)]
+    pub source_operator: Option<usize>,
+    #[pyo3(get, set)]
+    pub target_operator: Option<usize>,
+    #[pyo3(get, set)]
+    pub pending_records: u64,
+}
+
+/// Statistics of the checkpoints of the persisted state, made in this process.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[pyclass]
+pub struct PersistenceStats {
+    #[pyo3(get, set)]
+    pub checkpoints: u64,
+    #[pyo3(get, set)]
+    pub failed_checkpoints: u64,
+    /// Time of the end of the last successful checkpoint in milliseconds since the epoch.
+    #[pyo3(get, set)]
+    pub last_checkpoint_time: Option<u64>,
+    #[pyo3(get, set)]
+    pub last_checkpoint_duration_ms: Option<u64>,
+    /// Number of the input rows persisted by the last successful checkpoint.
+    #[pyo3(get, set)]
+    pub last_checkpoint_rows: Option<u64>,
+    /// Approximate number of bytes persisted by the last successful checkpoint.
+    #[pyo3(get, set)]
+    pub last_checkpoint_bytes: Option<u64>,
+    /// Whether the last checkpoint failed.
+    #[pyo3(get, set)]
+    pub last_checkpoint_failed: bool,
+}
+
+impl PersistenceStats {
+    pub fn last_checkpoint_age(&self, now: SystemTime) -> Option<u64> {
+        let last_checkpoint_time = self.last_checkpoint_time?;
+        let now = u64::try_from(
+            now.duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_millis(),
+        )
+        .unwrap();
+        Some(now.saturating_sub(last_checkpoint_time))
+    }
+}
+
+/// Upper bounds of the buckets of [`LatencyHistogram`] in milliseconds. The last bucket
+/// is unbounded.
+pub const LATENCY_BUCKETS_MS: [u64; 14] = [
+    1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000, 30000, 60000,
+];
+
+/// Histogram of the latencies of the rows emitted by an output, measured from the time
+/// of the rows, i.e. the time they were ingested by the input connectors, to the time
+/// they were emitted by the output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[pyclass]
+pub struct LatencyHistogram {
+    /// Numbers of the rows in the buckets bounded by [`LATENCY_BUCKETS_MS`], with
+    /// the rows over the last bound at the end.
+    #[pyo3(get)]
+    pub bucket_counts: Vec<u64>,
+    #[pyo3(get)]
+    pub count: u64,
+    #[pyo3(get)]
+    pub sum_ms: u64,
+}
+
+pub type SharedLatencyHistogram = Arc<Mutex<LatencyHistogram>>;
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            bucket_counts: vec![0; LATENCY_BUCKETS_MS.len() + 1],
+            count: 0,
+            sum_ms: 0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    /// Records `rows` rows of the given time emitted `now`.
+    pub fn observe(&mut self, time: u64, rows: u64, now: SystemTime) {
+        if rows == 0 {
+            return;
+        }
+        let now = u64::try_from(
+            now.duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_millis(),
+        )
+        .unwrap();
+        let latency = now.saturating_sub(time);
+        let bucket = LATENCY_BUCKETS_MS.partition_point(|bound| *bound < latency);
+        self.bucket_counts[bucket] += rows;
+        self.count += rows;
+        self.sum_ms = self.sum_ms.saturating_add(latency.saturating_mul(rows));
+    }
+}
+
+#[pymethods]
+impl LatencyHistogram {
+    #[staticmethod]
+    fn bucket_bounds_ms() -> Vec<u64> {
+        LATENCY_BUCKETS_MS.to_vec()
+    }
+
+    /// Returns the upper bound of the bucket containing the given quantile, `None` if
+    /// no rows were recorded or the quantile is in the unbounded bucket.
+    pub fn quantile_bound(&self, quantile: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_precision_loss)]
+        #[allow(clippy::cast_sign_loss)]
+        let rank = ((self.count as f64) * quantile).ceil().max(1.0) as u64;
+        let mut rows = 0;
+        for (bucket, count) in self.bucket_counts.iter().enumerate() {
+            rows += count;
+            if rows >= rank {
+                return LATENCY_BUCKETS_MS.get(bucket).copied();
+            }
+        }
+        None
+    }
+}
+
+/// Limit of the number of iterations of a fixed point computation.
+#[derive(Debug, Clone, Copy)]
+pub struct IterationLimit {
+    pub iterations: u32,
+    /// Whether not reaching a fixed point within the limit stops the computation with
+    /// an error. Otherwise, the result of the last iteration is used.
+    pub fail: bool,
+}
+
+/// Limit of the approximate state size of a single operator in a single worker.
+#[derive(Debug, Clone, Copy)]
+pub struct OperatorStateLimit {
+    pub bytes: u64,
+    /// Whether exceeding the limit stops the computation with an error. Otherwise,
+    /// a warning is logged.
+    pub fail: bool,
+}
+
 #[derive(Debug, Clone)]
 #[pyclass]
 pub struct ProberStats {
@@ -487,17 +741,29 @@ pub struct ProberStats {
     pub operators_stats: HashMap<usize, OperatorStats>,
     #[pyo3(get, set)]
     pub connector_stats: Vec<(String, ConnectorStats)>,
+    #[pyo3(get, set)]
+    pub persistence_stats: Option<PersistenceStats>,
+    /// Latencies of the rows emitted by the outputs of this worker, by their names.
+    #[pyo3(get, set)]
+    pub output_latencies: Vec<(String, LatencyHistogram)>,
+    /// Pending records between the operators, empty unless the pending work is tracked.
+    #[pyo3(get, set)]
+    pub edges_stats: Vec<EdgeStats>,
 }
 
 pub type OnDataFn = Box<dyn FnMut(Key, &[Value], u64, isize) -> DynResult<()>>;
 pub type OnTimeEndFn = Box<dyn FnMut(u64) -> DynResult<()>>;
 pub type OnEndFn = Box<dyn FnMut() -> DynResult<()>>;
+pub type OnCommitFn = Box<dyn FnMut(u64) -> DynResult<()>>;
 
 pub struct SubscribeCallbacks {
     pub wrapper: BatchWrapper,
     pub on_data: Option<OnDataFn>,
     pub on_time_end: Option<OnTimeEndFn>,
     pub on_end: Option<OnEndFn>,
+    /// Called in the first worker with the new frontier of the output, once all the workers
+    /// have finished the callbacks for the times before it.
+    pub on_commit: Option<OnCommitFn>,
 }
 
 pub struct SubscribeCallbacksBuilder {
@@ -512,6 +778,7 @@ impl SubscribeCallbacksBuilder {
                 on_data: None,
                 on_time_end: None,
                 on_end: None,
+                on_commit: None,
             },
         }
     }
@@ -544,6 +811,12 @@ impl SubscribeCallbacksBuilder {
         self.inner.on_end = Some(on_end);
         self
     }
+
+    #[must_use]
+    pub fn on_commit(mut self, on_commit: OnCommitFn) -> Self {
+        self.inner.on_commit = Some(on_commit);
+        self
+    }
 }
 
 impl Default for SubscribeCallbacksBuilder {
@@ -630,6 +903,19 @@ pub trait Graph {
         column_paths: Vec<ColumnPath>,
         table_properties: Arc<TableProperties>,
         trace: Trace,
+        max_in_flight: Option<usize>,
+    ) -> Result<TableHandle>;
+
+    /// Applies the function to batches of rows at once. The function gets the values
+    /// of each column of a batch and returns one value for each row.
+    fn batch_apply_table(
+        &self,
+        function: Arc<dyn Fn(Vec<Vec<Value>>) -> DynResult<Vec<Value>> + Send + Sync>,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        table_properties: Arc<TableProperties>,
+        trace: Trace,
+        max_batch_size: Option<usize>,
     ) -> Result<TableHandle>;
 
     fn subscribe_table(
@@ -668,6 +954,27 @@ pub trait Graph {
         table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle>;
 
+    /// Retracts every row `duration` units of the computation time after it was
+    /// inserted. In streaming mode, the time is measured in milliseconds.
+    fn expire_after(
+        &self,
+        table_handle: TableHandle,
+        duration: u64,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
+    /// Keeps only rows whose fingerprint was not kept recently, i.e. with no earlier
+    /// kept row of the same fingerprint whose expiration time exceeds the time of the
+    /// row. Stored fingerprints are pruned by the watermark of the time column.
+    fn deduplicate_within(
+        &self,
+        table_handle: TableHandle,
+        fingerprint_column_path: ColumnPath,
+        time_column_path: ColumnPath,
+        expiration_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
     fn freeze(
         &self,
         table_handle: TableHandle,
@@ -676,6 +983,24 @@ pub trait Graph {
         table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle>;
 
+    fn freeze_late(
+        &self,
+        table_handle: TableHandle,
+        threshold_time_column_path: ColumnPath,
+        current_time_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
+    /// Sends the rows to workers according to the values at `column_paths` (or the
+    /// keys if empty), using at most `parallelism` workers.
+    fn reshard_table(
+        &self,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        parallelism: Option<usize>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
     fn buffer(
         &self,
         table_handle: TableHandle,
@@ -725,6 +1050,14 @@ pub trait Graph {
         table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle>;
 
+    /// Turns every update of the table into a row containing the values, the time
+    /// and the diff of the update.
+    fn changelog_table(
+        &self,
+        table_handle: TableHandle,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
     fn sort_table(
         &self,
         table_handle: TableHandle,
@@ -733,6 +1066,51 @@ pub trait Graph {
         table_properties: Arc<TableProperties>,
     ) -> Result<TableHandle>;
 
+    fn session_window_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        max_gap: Value,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
+    /// Keeps the `n` rows with the smallest (or the largest, if `descending`) keys
+    /// within every instance. Ties are broken by row ids. The position of a row in
+    /// the order, starting from 0, is appended to its values.
+    fn top_n_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        n: usize,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
+    /// Appends `row_number`, `rank` and `dense_rank` of every row within its instance,
+    /// in the order of keys, to the values of the row. All of them start from 1.
+    fn rank_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
+    /// Appends running aggregates of every row within its instance, in the order of
+    /// keys, to the values of the row. The aggregate of a row covers the row and all
+    /// rows preceding it. Only count, sum, min and max reducers are supported.
+    fn cumulative_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        reducers: Vec<ReducerData>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle>;
+
     fn reindex_table(
         &self,
         table_handle: TableHandle,
@@ -799,7 +1177,7 @@ pub trait Graph {
         iterated: Vec<LegacyTable>,
         iterated_with_universe: Vec<LegacyTable>,
         extra: Vec<LegacyTable>,
-        limit: Option<u32>,
+        limit: Option<IterationLimit>,
         logic: IterationLogic<'a>,
     ) -> Result<(Vec<LegacyTable>, Vec<LegacyTable>)>;
 
@@ -814,6 +1192,7 @@ pub trait Graph {
         column_path: ColumnPath,
     ) -> Result<()>;
 
+    #[allow(clippy::too_many_arguments)]
     fn connector_table(
         &self,
         reader: Box<dyn ReaderBuilder>,
@@ -822,6 +1201,7 @@ pub trait Graph {
         parallel_readers: usize,
         table_properties: Arc<TableProperties>,
         external_persistent_id: Option<&ExternalPersistentId>,
+        schema: Vec<SchemaColumn>,
     ) -> Result<TableHandle>;
 
     fn output_table(
@@ -832,6 +1212,14 @@ pub trait Graph {
         column_paths: Vec<ColumnPath>,
     ) -> Result<()>;
 
+    fn serve_table(
+        &self,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        name: String,
+        column_names: Vec<String>,
+    ) -> Result<()>;
+
     fn attach_prober(
         &self,
         logic: Box<dyn FnMut(ProberStats)>,
@@ -839,7 +1227,12 @@ pub trait Graph {
         run_callback_every_time: bool,
     ) -> Result<()>;
 
-    fn probe_table(&self, table_handle: TableHandle, operator_id: usize) -> Result<()>;
+    fn probe_table(
+        &self,
+        table_handle: TableHandle,
+        operator_id: usize,
+        state_limit: Option<OperatorStateLimit>,
+    ) -> Result<()>;
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -1001,6 +1394,7 @@ impl Graph for ScopedGraph {
         column_paths: Vec<ColumnPath>,
         table_properties: Arc<TableProperties>,
         trace: Trace,
+        max_in_flight: Option<usize>,
     ) -> Result<TableHandle> {
         self.try_with(|g| {
             g.async_apply_table(
@@ -1009,6 +1403,28 @@ impl Graph for ScopedGraph {
                 column_paths,
                 table_properties,
                 trace,
+                max_in_flight,
+            )
+        })
+    }
+
+    fn batch_apply_table(
+        &self,
+        function: Arc<dyn Fn(Vec<Vec<Value>>) -> DynResult<Vec<Value>> + Send + Sync>,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        table_properties: Arc<TableProperties>,
+        trace: Trace,
+        max_batch_size: Option<usize>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| {
+            g.batch_apply_table(
+                function,
+                table_handle,
+                column_paths,
+                table_properties,
+                trace,
+                max_batch_size,
             )
         })
     }
@@ -1090,6 +1506,34 @@ impl Graph for ScopedGraph {
         self.try_with(|g| g.filter_out_results_of_forgetting(table_handle, table_properties))
     }
 
+    fn expire_after(
+        &self,
+        table_handle: TableHandle,
+        duration: u64,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| g.expire_after(table_handle, duration, table_properties))
+    }
+
+    fn deduplicate_within(
+        &self,
+        table_handle: TableHandle,
+        fingerprint_column_path: ColumnPath,
+        time_column_path: ColumnPath,
+        expiration_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| {
+            g.deduplicate_within(
+                table_handle,
+                fingerprint_column_path,
+                time_column_path,
+                expiration_column_path,
+                table_properties,
+            )
+        })
+    }
+
     fn freeze(
         &self,
         table_handle: TableHandle,
@@ -1107,6 +1551,35 @@ impl Graph for ScopedGraph {
         })
     }
 
+    fn freeze_late(
+        &self,
+        table_handle: TableHandle,
+        threshold_time_column_path: ColumnPath,
+        current_time_column_path: ColumnPath,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| {
+            g.freeze_late(
+                table_handle,
+                threshold_time_column_path,
+                current_time_column_path,
+                table_properties,
+            )
+        })
+    }
+
+    fn reshard_table(
+        &self,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        parallelism: Option<usize>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| {
+            g.reshard_table(table_handle, column_paths, parallelism, table_properties)
+        })
+    }
+
     fn buffer(
         &self,
         table_handle: TableHandle,
@@ -1184,6 +1657,14 @@ impl Graph for ScopedGraph {
         self.try_with(|g| g.flatten_table(table_handle, flatten_column_path, table_properties))
     }
 
+    fn changelog_table(
+        &self,
+        table_handle: TableHandle,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| g.changelog_table(table_handle, table_properties))
+    }
+
     fn sort_table(
         &self,
         table_handle: TableHandle,
@@ -1201,6 +1682,84 @@ impl Graph for ScopedGraph {
         })
     }
 
+    fn session_window_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        max_gap: Value,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| {
+            g.session_window_table(
+                table_handle,
+                key_column_path,
+                instance_column_path,
+                max_gap,
+                table_properties,
+            )
+        })
+    }
+
+    fn top_n_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        n: usize,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| {
+            g.top_n_table(
+                table_handle,
+                key_column_path,
+                instance_column_path,
+                n,
+                descending,
+                table_properties,
+            )
+        })
+    }
+
+    fn rank_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        descending: bool,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| {
+            g.rank_table(
+                table_handle,
+                key_column_path,
+                instance_column_path,
+                descending,
+                table_properties,
+            )
+        })
+    }
+
+    fn cumulative_table(
+        &self,
+        table_handle: TableHandle,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        reducers: Vec<ReducerData>,
+        table_properties: Arc<TableProperties>,
+    ) -> Result<TableHandle> {
+        self.try_with(|g| {
+            g.cumulative_table(
+                table_handle,
+                key_column_path,
+                instance_column_path,
+                reducers,
+                table_properties,
+            )
+        })
+    }
+
     fn reindex_table(
         &self,
         table_handle: TableHandle,
@@ -1302,7 +1861,7 @@ impl Graph for ScopedGraph {
         iterated: Vec<LegacyTable>,
         iterated_with_universe: Vec<LegacyTable>,
         extra: Vec<LegacyTable>,
-        limit: Option<u32>,
+        limit: Option<IterationLimit>,
         logic: IterationLogic<'a>,
     ) -> Result<(Vec<LegacyTable>, Vec<LegacyTable>)> {
         self.try_with(|g| g.iterate(iterated, iterated_with_universe, extra, limit, logic))
@@ -1333,6 +1892,7 @@ impl Graph for ScopedGraph {
         parallel_readers: usize,
         table_properties: Arc<TableProperties>,
         external_persistent_id: Option<&ExternalPersistentId>,
+        schema: Vec<SchemaColumn>,
     ) -> Result<TableHandle> {
         self.try_with(|g| {
             g.connector_table(
@@ -1342,6 +1902,7 @@ impl Graph for ScopedGraph {
                 parallel_readers,
                 table_properties,
                 external_persistent_id,
+                schema,
             )
         })
     }
@@ -1356,6 +1917,16 @@ impl Graph for ScopedGraph {
         self.try_with(|g| g.output_table(data_sink, data_formatter, table_handle, column_paths))
     }
 
+    fn serve_table(
+        &self,
+        table_handle: TableHandle,
+        column_paths: Vec<ColumnPath>,
+        name: String,
+        column_names: Vec<String>,
+    ) -> Result<()> {
+        self.try_with(|g| g.serve_table(table_handle, column_paths, name, column_names))
+    }
+
     fn attach_prober(
         &self,
         logic: Box<dyn FnMut(ProberStats)>,
@@ -1367,7 +1938,12 @@ impl Graph for ScopedGraph {
         })
     }
 
-    fn probe_table(&self, table_handle: TableHandle, operator_id: usize) -> Result<()> {
-        self.try_with(|g| g.probe_table(table_handle, operator_id))
+    fn probe_table(
+        &self,
+        table_handle: TableHandle,
+        operator_id: usize,
+        state_limit: Option<OperatorStateLimit>,
+    ) -> Result<()> {
+        self.try_with(|g| g.probe_table(table_handle, operator_id, state_limit))
     }
 }
@@ -0,0 +1,231 @@
+// Copyright © 2024 Pathway
+
+use std::env;
+
+use hyper::header::{HeaderMap, AUTHORIZATION};
+use openssl::error::ErrorStack;
+use openssl::memcmp;
+use openssl::nid::Nid;
+use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod, SslVerifyMode};
+use openssl::x509::X509Ref;
+
+/// Stands for anyone, including the clients that didn't authenticate, in the authorization
+/// rules, e.g. `/healthz=*` keeps the liveness probe open.
+const ANYONE: &str = "*";
+
+#[derive(Debug, thiserror::Error)]
+#[allow(clippy::module_name_repetitions)]
+pub enum HttpAuthConfigError {
+    #[error("invalid token {0:?}, expected NAME:TOKEN")]
+    InvalidToken(String),
+
+    #[error("invalid authorization rule {0:?}, expected /PATH_PREFIX=NAME,NAME,...")]
+    InvalidRule(String),
+
+    #[error(
+        "authorization rules require tokens or client certificates to authenticate the clients"
+    )]
+    RulesWithoutAuthentication,
+
+    #[error("both the certificate and the private key are required for TLS")]
+    IncompleteTls,
+
+    #[error("client certificates can only be verified with TLS")]
+    ClientCaWithoutTls,
+
+    #[error("failed to set up TLS: {0}")]
+    Tls(#[from] ErrorStack),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum HttpAuthFailure {
+    /// The client didn't authenticate or presented an unknown token.
+    Unauthenticated,
+
+    /// The client authenticated, but it's not allowed to use the route.
+    Forbidden,
+}
+
+/// Authentication and authorization of the requests to the http server.
+///
+/// A client authenticates with a bearer token, known under a name, or with a client
+/// certificate verified by TLS, in which case its name is the common name of the certificate.
+/// A token takes precedence over the certificate. Once any of them is configured, every
+/// request has to be authenticated, unless a rule allows the route to anyone.
+///
+/// The rules limit the routes under a path prefix to the listed names. A prefix matches
+/// whole path segments, so `/tables` covers `/tables` and `/tables/NAME`, but not
+/// `/tablespace`. The longest matching prefix applies and the routes not matching any rule
+/// are available to all authenticated clients.
+#[derive(Debug, Default)]
+pub struct HttpAuth {
+    tokens: Vec<(String, String)>,
+    rules: Vec<(String, Vec<String>)>,
+    client_certificates: bool,
+}
+
+impl HttpAuth {
+    /// Parses the tokens, given as comma-separated `NAME:TOKEN` pairs, and the rules, given
+    /// as semicolon-separated `/PATH_PREFIX=NAME,NAME,...` entries.
+    pub fn parse(
+        tokens: &str,
+        rules: &str,
+        client_certificates: bool,
+    ) -> Result<Self, HttpAuthConfigError> {
+        let tokens: Vec<_> = tokens
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.split_once(':') {
+                Some((name, token)) if !name.is_empty() && !token.is_empty() => {
+                    Ok((name.to_string(), token.to_string()))
+                }
+                _ => Err(HttpAuthConfigError::InvalidToken(entry.to_string())),
+            })
+            .collect::<Result<_, _>>()?;
+        let rules: Vec<_> = rules
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let Some((prefix, names)) = entry.split_once('=') else {
+                    return Err(HttpAuthConfigError::InvalidRule(entry.to_string()));
+                };
+                let names: Vec<String> = names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                if !prefix.starts_with('/') || names.is_empty() {
+                    return Err(HttpAuthConfigError::InvalidRule(entry.to_string()));
+                }
+                Ok((prefix.to_string(), names))
+            })
+            .collect::<Result<_, _>>()?;
+        let auth = Self {
+            tokens,
+            rules,
+            client_certificates,
+        };
+        if !auth.rules.is_empty() && !auth.is_enabled() {
+            return Err(HttpAuthConfigError::RulesWithoutAuthentication);
+        }
+        Ok(auth)
+    }
+
+    /// Reads the tokens from `PATHWAY_MONITORING_HTTP_TOKENS` and the rules from
+    /// `PATHWAY_MONITORING_HTTP_AUTHORIZATION`.
+    pub fn from_env(client_certificates: bool) -> Result<Self, HttpAuthConfigError> {
+        Self::parse(
+            &env::var("PATHWAY_MONITORING_HTTP_TOKENS").unwrap_or_default(),
+            &env::var("PATHWAY_MONITORING_HTTP_AUTHORIZATION").unwrap_or_default(),
+            client_certificates,
+        )
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.tokens.is_empty() || self.client_certificates
+    }
+
+    /// Checks whether the request for `path` is allowed. `client_name` is the name from
+    /// the verified client certificate, if there is one.
+    pub fn authorize(
+        &self,
+        path: &str,
+        headers: &HeaderMap,
+        client_name: Option<&str>,
+    ) -> Result<(), HttpAuthFailure> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let allowed_names = self
+            .rules
+            .iter()
+            .filter(|(prefix, _names)| is_under_prefix(path, prefix))
+            .max_by_key(|(prefix, _names)| prefix.trim_end_matches('/').len())
+            .map(|(_prefix, names)| names);
+        if allowed_names.is_some_and(|names| names.iter().any(|name| name == ANYONE)) {
+            return Ok(());
+        }
+        let name = match bearer_token(headers) {
+            Some(token) => Some(
+                self.token_owner(token)
+                    .ok_or(HttpAuthFailure::Unauthenticated)?,
+            ),
+            None => client_name,
+        };
+        let Some(name) = name else {
+            return Err(HttpAuthFailure::Unauthenticated);
+        };
+        match allowed_names {
+            Some(names) if !names.iter().any(|allowed| allowed == name) => {
+                Err(HttpAuthFailure::Forbidden)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn token_owner(&self, token: &str) -> Option<&str> {
+        // Comparing in constant time, not to reveal the tokens through the response times
+        self.tokens
+            .iter()
+            .find(|(_name, known_token)| {
+                known_token.len() == token.len()
+                    && memcmp::eq(known_token.as_bytes(), token.as_bytes())
+            })
+            .map(|(name, _known_token)| name.as_str())
+    }
+}
+
+/// Checks whether `path` is `prefix` or a path under it, comparing whole segments.
+fn is_under_prefix(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then_some(token.trim())
+}
+
+/// Sets up TLS if `PATHWAY_MONITORING_HTTP_TLS_CERT` and `PATHWAY_MONITORING_HTTP_TLS_KEY`
+/// point to the PEM files with the certificate chain and the private key of the server.
+/// If `PATHWAY_MONITORING_HTTP_TLS_CLIENT_CA` is set as well, the clients are required
+/// to present certificates signed by the authorities from this PEM file.
+pub fn tls_acceptor_from_env() -> Result<Option<SslAcceptor>, HttpAuthConfigError> {
+    let certificate = env::var("PATHWAY_MONITORING_HTTP_TLS_CERT").ok();
+    let private_key = env::var("PATHWAY_MONITORING_HTTP_TLS_KEY").ok();
+    let client_ca = env::var("PATHWAY_MONITORING_HTTP_TLS_CLIENT_CA").ok();
+    let (certificate, private_key) = match (certificate, private_key) {
+        (Some(certificate), Some(private_key)) => (certificate, private_key),
+        (None, None) if client_ca.is_some() => return Err(HttpAuthConfigError::ClientCaWithoutTls),
+        (None, None) => return Ok(None),
+        _ => return Err(HttpAuthConfigError::IncompleteTls),
+    };
+
+    let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls_server())?;
+    builder.set_certificate_chain_file(certificate)?;
+    builder.set_private_key_file(private_key, SslFiletype::PEM)?;
+    builder.check_private_key()?;
+    if let Some(client_ca) = client_ca {
+        builder.set_ca_file(client_ca)?;
+        builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    }
+    Ok(Some(builder.build()))
+}
+
+/// Returns the name of the client, i.e. the common name of its certificate.
+pub fn client_name(certificate: &X509Ref) -> Option<String> {
+    certificate
+        .subject_name()
+        .entries_by_nid(Nid::COMMONNAME)
+        .next()
+        .and_then(|entry| entry.data().as_utf8().ok())
+        .map(|name| name.to_string())
+}
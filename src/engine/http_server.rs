@@ -1,28 +1,68 @@
 // Copyright © 2024 Pathway
 
 use std::env;
+use std::future::Future;
+use std::iter::once;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::thread::{Builder, JoinHandle};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use arc_swap::ArcSwapOption;
+use futures::SinkExt;
+use hyper::server::conn::Http;
 use hyper::service::{make_service_fn, service_fn};
-use hyper::{header, Body, Method, Response, Server, StatusCode};
-use log::{error, info};
+use hyper::upgrade::Upgraded;
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use log::{error, info, warn};
+use openssl::ssl::{Ssl, SslAcceptor, SslVerifyMode};
 use prometheus_client::encoding::text::encode;
+use prometheus_client::encoding::{EncodeMetric, MetricEncoder};
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
 use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::MetricType;
 use prometheus_client::registry::Registry;
+use serde_json::{json, Value as JsonValue};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio::sync::oneshot::Sender;
+use tokio::sync::{mpsc, watch};
+use tokio_openssl::SslStream;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, Role};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
 
+use super::alerts::{AlertsState, SharedAlerts};
+use super::allocator_stats::{self, AllocatorStats};
+use super::error::DynError;
+use super::graph::LATENCY_BUCKETS_MS;
+use super::http_auth::{self, tls_acceptor_from_env, HttpAuth, HttpAuthFailure};
+use super::profiling::{self, ProfileFormat, ProfilingError};
+use super::served_table::SharedServedTables;
+use super::shutdown::SharedShutdownRequest;
+use super::EdgeStats;
 use super::Error;
 use super::Graph;
+use super::Key;
+use super::LatencyHistogram;
+use super::OperatorNode;
+use super::PersistenceStats;
 use super::ProberStats;
+use crate::connectors::monitoring::ConnectorStats;
+use crate::persistence::state::is_valid_savepoint_name;
+use crate::persistence::sync::SharedSavepointRequests;
 
 const DEFAULT_MONITORING_HTTP_PORT: u16 = 20000;
+const DEFAULT_MONITORING_HTTP_HOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
 
 /// Retrieves metrics from prober stats in the `OpenMetrics` format
 /// See <https://github.com/OpenObservability/OpenMetrics>
-fn metrics_from_stats(stats: &Arc<ArcSwapOption<ProberStats>>) -> String {
+fn metrics_from_stats(stats: &Arc<ArcSwapOption<ProberStats>>, alerts: &SharedAlerts) -> String {
     let stats_owned = stats.load().clone();
     let now = SystemTime::now();
     let mut metrics_text = String::new();
@@ -65,19 +105,911 @@ fn metrics_from_stats(stats: &Arc<ArcSwapOption<ProberStats>>) -> String {
             output_latency_ms,
         );
 
+        let operator_state_bytes = Family::<Vec<(String, String)>, Gauge>::default();
+        for (operator_id, operator_stats) in &stats_owned.operators_stats {
+            if let Some(state_size) = operator_stats.state_size {
+                operator_state_bytes
+                    .get_or_create(&vec![("operator_id".to_string(), operator_id.to_string())])
+                    .set(i64::try_from(state_size).unwrap_or(i64::MAX));
+            }
+        }
+        registry.register(
+            "operator_state_bytes",
+            "An approximate size of the state of an operator in bytes",
+            operator_state_bytes,
+        );
+
+        register_source_lag_metrics(&mut registry, &stats_owned.connector_stats);
+        register_pending_work_metrics(
+            &mut registry,
+            &stats_owned.connector_stats,
+            &stats_owned.edges_stats,
+        );
+
+        registry.register(
+            "output_row_latency_ms",
+            "A latency of the rows emitted by an output in milliseconds, measured from their ingestion",
+            OutputLatencyHistograms(stats_owned.output_latencies.clone()),
+        );
+
+        if let Some(persistence_stats) = stats_owned.persistence_stats {
+            register_persistence_metrics(&mut registry, &persistence_stats, now);
+        }
+
+        if let Some(allocator_stats) = allocator_stats::read() {
+            register_allocator_metrics(&mut registry, &allocator_stats);
+        }
+
+        register_alert_metrics(&mut registry, &alerts.lock().unwrap());
+
         encode(&mut metrics_text, &registry).unwrap();
     }
     metrics_text
 }
 
+/// Latency histograms of the outputs, labelled with the names of the outputs.
+#[derive(Debug)]
+struct OutputLatencyHistograms(Vec<(String, LatencyHistogram)>);
+
+impl EncodeMetric for OutputLatencyHistograms {
+    #[allow(clippy::cast_precision_loss)]
+    fn encode(&self, mut encoder: MetricEncoder) -> Result<(), std::fmt::Error> {
+        for (output, histogram) in &self.0 {
+            let buckets: Vec<(f64, u64)> = LATENCY_BUCKETS_MS
+                .iter()
+                .map(|bound| *bound as f64)
+                .chain(once(f64::MAX))
+                .zip(histogram.bucket_counts.iter().copied())
+                .collect();
+            encoder
+                .encode_family(&vec![("output".to_string(), output.clone())])?
+                .encode_histogram::<()>(histogram.sum_ms as f64, histogram.count, &buckets, None)?;
+        }
+        Ok(())
+    }
+
+    fn metric_type(&self) -> MetricType {
+        MetricType::Histogram
+    }
+}
+
+fn register_source_lag_metrics(
+    registry: &mut Registry,
+    connector_stats: &[(String, ConnectorStats)],
+) {
+    let lag_messages = Family::<Vec<(String, String)>, Gauge>::default();
+    let backlog_files = Family::<Vec<(String, String)>, Gauge>::default();
+    let backlog_bytes = Family::<Vec<(String, String)>, Gauge>::default();
+    for (name, stats) in connector_stats {
+        let labels = vec![("connector".to_string(), name.clone())];
+        for (family, value) in [
+            (&lag_messages, stats.lag_messages),
+            (&backlog_files, stats.backlog_files),
+            (&backlog_bytes, stats.backlog_bytes),
+        ] {
+            if let Some(value) = value {
+                family
+                    .get_or_create(&labels)
+                    .set(i64::try_from(value).unwrap_or(i64::MAX));
+            }
+        }
+    }
+    registry.register(
+        "source_lag_messages",
+        "A number of the messages in the source not read yet by the connector",
+        lag_messages,
+    );
+    registry.register(
+        "source_backlog_files",
+        "A number of the files in the source not read yet by the connector",
+        backlog_files,
+    );
+    registry.register(
+        "source_backlog_bytes",
+        "A total size of the files in the source not read yet by the connector",
+        backlog_bytes,
+    );
+}
+
+/// The edges from the inputs and to the outputs are labelled with `input` and `output`
+/// in place of the operator ids.
+fn register_pending_work_metrics(
+    registry: &mut Registry,
+    connector_stats: &[(String, ConnectorStats)],
+    edges_stats: &[EdgeStats],
+) {
+    let queue_depth = Family::<Vec<(String, String)>, Gauge>::default();
+    for (name, stats) in connector_stats {
+        queue_depth
+            .get_or_create(&vec![("connector".to_string(), name.clone())])
+            .set(i64::try_from(stats.queue_depth).unwrap_or(i64::MAX));
+    }
+    registry.register(
+        "connector_queue_depth",
+        "A number of the entries read by the connector and not sent to the engine yet",
+        queue_depth,
+    );
+
+    let pending_records = Family::<Vec<(String, String)>, Gauge>::default();
+    for edge in edges_stats {
+        let labels = vec![
+            (
+                "source_operator_id".to_string(),
+                edge.source_operator
+                    .map_or_else(|| "input".to_string(), |id| id.to_string()),
+            ),
+            (
+                "target_operator_id".to_string(),
+                edge.target_operator
+                    .map_or_else(|| "output".to_string(), |id| id.to_string()),
+            ),
+        ];
+        pending_records
+            .get_or_create(&labels)
+            .set(i64::try_from(edge.pending_records).unwrap_or(i64::MAX));
+    }
+    registry.register(
+        "edge_pending_records",
+        "A number of the records sent from one operator to another and not processed yet",
+        pending_records,
+    );
+}
+
+fn register_allocator_metrics(registry: &mut Registry, stats: &AllocatorStats) {
+    for (name, help, value) in [
+        (
+            "allocator_allocated_bytes",
+            "Memory allocated by the process in bytes",
+            stats.allocated,
+        ),
+        (
+            "allocator_active_bytes",
+            "Memory in the pages of the allocator with allocations in bytes",
+            stats.active,
+        ),
+        (
+            "allocator_resident_bytes",
+            "Memory in the physically resident pages of the allocator in bytes",
+            stats.resident,
+        ),
+        (
+            "allocator_metadata_bytes",
+            "Memory used by the allocator for its own structures in bytes",
+            stats.metadata,
+        ),
+        (
+            "allocator_retained_bytes",
+            "Memory retained by the allocator instead of being returned to the system in bytes",
+            stats.retained,
+        ),
+    ] {
+        registry.register(name, help, gauge_from(Some(value)));
+    }
+
+    let arena_fragmentation = Family::<Vec<(String, String)>, Gauge<f64, AtomicU64>>::default();
+    for arena in &stats.arenas {
+        arena_fragmentation
+            .get_or_create(&vec![("arena".to_string(), arena.index.to_string())])
+            .set(arena.fragmentation());
+    }
+    registry.register(
+        "allocator_arena_fragmentation_ratio",
+        "The part of the active memory of an arena of the allocator not allocated by the process",
+        arena_fragmentation,
+    );
+}
+
+/// Reports the statistics of the allocator as JSON, in bytes.
+fn allocator_stats_json() -> (StatusCode, String) {
+    let Some(stats) = allocator_stats::read() else {
+        return (
+            StatusCode::NOT_FOUND,
+            "allocator statistics are not available".to_string(),
+        );
+    };
+    let arenas: Vec<_> = stats
+        .arenas
+        .iter()
+        .map(|arena| {
+            json!({
+                "index": arena.index,
+                "allocated": arena.allocated,
+                "active": arena.active,
+                "dirty": arena.dirty,
+                "muzzy": arena.muzzy,
+                "fragmentation": arena.fragmentation(),
+            })
+        })
+        .collect();
+    let report = json!({
+        "allocated": stats.allocated,
+        "active": stats.active,
+        "metadata": stats.metadata,
+        "resident": stats.resident,
+        "mapped": stats.mapped,
+        "retained": stats.retained,
+        "fragmentation": stats.fragmentation(),
+        "arenas": arenas,
+    });
+    (StatusCode::OK, report.to_string())
+}
+
+/// The alerts not concerning a connector are labelled with an empty connector.
+fn register_alert_metrics(registry: &mut Registry, alerts: &AlertsState) {
+    let active = Family::<Vec<(String, String)>, Gauge>::default();
+    for alert in &alerts.active {
+        let labels = vec![
+            ("alert".to_string(), alert.name.to_string()),
+            (
+                "connector".to_string(),
+                alert.connector.clone().unwrap_or_default(),
+            ),
+        ];
+        active.get_or_create(&labels).set(1);
+    }
+    registry.register(
+        "alert_active",
+        "Whether an alert on the health of the computation is raised now",
+        active,
+    );
+
+    let raised = Family::<Vec<(String, String)>, Counter>::default();
+    for (name, count) in &alerts.raised {
+        raised
+            .get_or_create(&vec![("alert".to_string(), (*name).to_string())])
+            .inc_by(*count);
+    }
+    registry.register(
+        "alerts_raised",
+        "A number of the times an alert on the health of the computation was raised",
+        raised,
+    );
+}
+
+fn gauge_from(value: Option<u64>) -> Gauge {
+    let gauge: Gauge = Gauge::default();
+    gauge.set(value.map_or(-1, |value| i64::try_from(value).unwrap_or(i64::MAX)));
+    gauge
+}
+
+fn register_persistence_metrics(
+    registry: &mut Registry,
+    stats: &PersistenceStats,
+    now: SystemTime,
+) {
+    registry.register(
+        "persistence_checkpoints",
+        "A number of the checkpoints of the persisted state made",
+        gauge_from(Some(stats.checkpoints)),
+    );
+    registry.register(
+        "persistence_failed_checkpoints",
+        "A number of the checkpoints of the persisted state that failed",
+        gauge_from(Some(stats.failed_checkpoints)),
+    );
+    registry.register(
+        "persistence_last_checkpoint_duration_ms",
+        "A duration of the last successful checkpoint in milliseconds (-1 when none was made)",
+        gauge_from(stats.last_checkpoint_duration_ms),
+    );
+    registry.register(
+        "persistence_last_checkpoint_rows",
+        "A number of the input rows persisted by the last successful checkpoint (-1 when none was made)",
+        gauge_from(stats.last_checkpoint_rows),
+    );
+    registry.register(
+        "persistence_last_checkpoint_bytes",
+        "An approximate size of the input persisted by the last successful checkpoint in bytes (-1 when none was made)",
+        gauge_from(stats.last_checkpoint_bytes),
+    );
+    registry.register(
+        "persistence_last_checkpoint_age_ms",
+        "A time since the last successful checkpoint in milliseconds (-1 when none was made)",
+        gauge_from(stats.last_checkpoint_age(now)),
+    );
+}
+
+/// Reports the health of the computation as JSON: the state of the connectors, the times
+/// the input and the output have advanced to and the state of the persistence.
+/// The computation is unhealthy when the last checkpoint of the persisted state failed.
+/// The alerts raised now are listed, but they don't make the computation unhealthy.
+fn health_from_stats(
+    stats: &Arc<ArcSwapOption<ProberStats>>,
+    alerts: &SharedAlerts,
+) -> (StatusCode, String) {
+    let Some(stats_owned) = stats.load().clone() else {
+        return (StatusCode::OK, json!({"status": "starting"}).to_string());
+    };
+    let now = SystemTime::now();
+    let connectors: Vec<_> = stats_owned
+        .connector_stats
+        .iter()
+        .map(|(name, connector_stats)| {
+            json!({
+                "name": name,
+                "finished": connector_stats.finished,
+                "messages_in_last_minute": connector_stats.num_messages_in_last_minute,
+                "lag_messages": connector_stats.lag_messages,
+                "backlog_files": connector_stats.backlog_files,
+                "backlog_bytes": connector_stats.backlog_bytes,
+                "queue_depth": connector_stats.queue_depth,
+            })
+        })
+        .collect();
+    let persistence = stats_owned.persistence_stats.map(|persistence_stats| {
+        json!({
+            "checkpoints": persistence_stats.checkpoints,
+            "failed_checkpoints": persistence_stats.failed_checkpoints,
+            "last_checkpoint_failed": persistence_stats.last_checkpoint_failed,
+            "last_checkpoint_age_ms": persistence_stats.last_checkpoint_age(now),
+        })
+    });
+    let healthy = !stats_owned
+        .persistence_stats
+        .is_some_and(|persistence_stats| persistence_stats.last_checkpoint_failed);
+    let report = json!({
+        "status": if healthy { "ok" } else { "unhealthy" },
+        "input_time": stats_owned.input_stats.time,
+        "output_time": stats_owned.output_stats.time,
+        "output_latency_ms": stats_owned.output_stats.latency(now),
+        "finished": stats_owned.output_stats.done,
+        "connectors": connectors,
+        "persistence": persistence,
+        "alerts": alerts.lock().unwrap().active,
+    });
+    let status_code = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status_code, report.to_string())
+}
+
+/// Reports the topology of the computation graph as JSON, with the statistics of
+/// the operators in this worker and the records pending between them, if they are known.
+fn topology_from_stats(
+    stats: &Arc<ArcSwapOption<ProberStats>>,
+    topology: &[OperatorNode],
+) -> String {
+    let stats_owned = stats.load().clone();
+    let now = SystemTime::now();
+    let operators: Vec<_> = topology
+        .iter()
+        .map(|operator| {
+            let operator_stats = stats_owned
+                .as_ref()
+                .and_then(|stats| stats.operators_stats.get(&operator.id))
+                .map(|operator_stats| {
+                    json!({
+                        "time": operator_stats.time,
+                        "latency_ms": operator_stats.latency(now),
+                        "lag_ms": operator_stats.lag,
+                        "done": operator_stats.done,
+                        "rows_processed": operator_stats.rows_processed,
+                        "state_bytes": operator_stats.state_size,
+                        "pending_records": operator_stats.pending_records,
+                        "last_activity_ms": operator_stats.last_activity,
+                    })
+                });
+            json!({
+                "id": operator.id,
+                "name": operator.name,
+                "inputs": operator.inputs,
+                "stats": operator_stats,
+            })
+        })
+        .collect();
+    let edges: Vec<_> = stats_owned
+        .iter()
+        .flat_map(|stats| &stats.edges_stats)
+        .map(|edge| {
+            json!({
+                "source": edge.source_operator,
+                "target": edge.target_operator,
+                "pending_records": edge.pending_records,
+            })
+        })
+        .collect();
+    json!({
+        "operators": operators,
+        "edges": edges,
+    })
+    .to_string()
+}
+
+/// The computation is ready once the outputs have finished the first time, which includes
+/// the data restored from the persisted state.
+fn readiness_from_stats(stats: &Arc<ArcSwapOption<ProberStats>>) -> (StatusCode, &'static str) {
+    match stats.load().as_deref() {
+        Some(stats) if stats.output_stats.time.is_some() || stats.output_stats.done => {
+            (StatusCode::OK, "ready")
+        }
+        _ => (StatusCode::SERVICE_UNAVAILABLE, "not ready"),
+    }
+}
+
+/// Looks up the rows of a served table. `/tables/NAME/KEY` returns the row with the given
+/// key, with or without the leading `^`, and `/tables/NAME?COLUMN=VALUE&...` returns
+/// the rows whose columns are equal to the given values.
+fn lookup_in_served_table(
+    path: &str,
+    query: Option<&str>,
+    served_tables: &SharedServedTables,
+) -> (StatusCode, String) {
+    let path = path.strip_prefix("/tables/").unwrap();
+    let (name, key) = match path.split_once('/') {
+        Some((name, key)) => (name, Some(key)),
+        None => (path, None),
+    };
+    let Some(served_table) = served_tables.lock().unwrap().get(name).cloned() else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("table {name:?} is not served"),
+        );
+    };
+    let served_table = served_table.read().unwrap();
+    if let Some(key) = key {
+        let key_text = ["%5E", "%5e", "^"]
+            .iter()
+            .find_map(|prefix| key.strip_prefix(prefix))
+            .unwrap_or(key);
+        let Some(key) = Key::parse(&format!("^{key_text}")) else {
+            return (StatusCode::BAD_REQUEST, format!("invalid key: {key:?}"));
+        };
+        match served_table.lookup(key) {
+            Some(row) => (StatusCode::OK, row.to_string()),
+            None => (StatusCode::NOT_FOUND, format!("no row with key {key}")),
+        }
+    } else {
+        let conditions: Vec<(String, String)> =
+            form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+                .into_owned()
+                .collect();
+        match served_table.filter(&conditions) {
+            Ok(rows) => (StatusCode::OK, JsonValue::Array(rows).to_string()),
+            Err(message) => (StatusCode::BAD_REQUEST, message),
+        }
+    }
+}
+
+/// Upgrades the request for `/tables/NAME/changes` to a WebSocket streaming the changes of
+/// the served table. The current rows are sent first, as `insert` frames.
+fn stream_served_table_changes(
+    req: Request<Body>,
+    served_tables: &SharedServedTables,
+) -> Response<Body> {
+    let name = req
+        .uri()
+        .path()
+        .strip_prefix("/tables/")
+        .and_then(|path| path.strip_suffix("/changes"))
+        .unwrap_or_default();
+    let Some(served_table) = served_tables.lock().unwrap().get(name).cloned() else {
+        return plain_response(
+            StatusCode::NOT_FOUND,
+            format!("table {name:?} is not served"),
+        );
+    };
+    let is_websocket_upgrade = req
+        .headers()
+        .get(header::UPGRADE)
+        .and_then(|upgrade| upgrade.to_str().ok())
+        .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"));
+    let key = req.headers().get(header::SEC_WEBSOCKET_KEY);
+    let Some(key) = key.filter(|_| is_websocket_upgrade) else {
+        return plain_response(
+            StatusCode::BAD_REQUEST,
+            "a WebSocket connection is required".to_string(),
+        );
+    };
+    let accept_key = derive_accept_key(key.as_bytes());
+
+    // Subscribing before responding, so that no change is missed
+    let (snapshot, receiver) = served_table.read().unwrap().stream_changes();
+    tokio::spawn(async move {
+        match hyper::upgrade::on(req).await {
+            Ok(upgraded) => {
+                let websocket =
+                    WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+                send_changes(websocket, snapshot, receiver).await;
+            }
+            Err(e) => error!("Failed to upgrade to WebSocket: {e}"),
+        }
+    });
+
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONNECTION,
+        header::HeaderValue::from_static("upgrade"),
+    );
+    headers.insert(
+        header::UPGRADE,
+        header::HeaderValue::from_static("websocket"),
+    );
+    headers.insert(
+        header::SEC_WEBSOCKET_ACCEPT,
+        header::HeaderValue::from_str(&accept_key).unwrap(),
+    );
+    response
+}
+
+const DEFAULT_PROFILE_DURATION: Duration = Duration::from_secs(10);
+
+/// Captures a CPU profile of the workers for `seconds` given in the query, 10 by default,
+/// as a flamegraph or, with `format=pprof`, in the format of `pprof`. The sampling happens
+/// outside of the runtime, so that the other requests are served meanwhile.
+async fn capture_profile(query: Option<&str>) -> Response<Body> {
+    let mut duration = DEFAULT_PROFILE_DURATION;
+    let mut format = ProfileFormat::Flamegraph;
+    for (name, value) in form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+        match name.as_ref() {
+            "seconds" => match value.parse::<u64>() {
+                Ok(seconds) if seconds > 0 => duration = Duration::from_secs(seconds),
+                _ => {
+                    return plain_response(
+                        StatusCode::BAD_REQUEST,
+                        format!("invalid duration of the profile: {value:?}"),
+                    )
+                }
+            },
+            "format" => {
+                let Some(parsed_format) = ProfileFormat::parse(&value) else {
+                    return plain_response(
+                        StatusCode::BAD_REQUEST,
+                        format!("unknown format of the profile: {value:?}"),
+                    );
+                };
+                format = parsed_format;
+            }
+            _ => {}
+        }
+    }
+    if duration > profiling::MAX_PROFILE_DURATION {
+        return plain_response(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "the profile can take at most {} seconds",
+                profiling::MAX_PROFILE_DURATION.as_secs()
+            ),
+        );
+    }
+    let result = tokio::task::spawn_blocking(move || profiling::capture(duration, format))
+        .await
+        .expect("profiling task failed");
+    match result {
+        Ok(profile) => {
+            let mut response = Response::new(Body::from(profile));
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                header::HeaderValue::from_static(format.content_type()),
+            );
+            response
+        }
+        Err(e @ ProfilingError::AlreadyRunning) => {
+            plain_response(StatusCode::CONFLICT, e.to_string())
+        }
+        Err(e) => plain_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+fn plain_response(status_code: StatusCode, message: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(message));
+    *response.status_mut() = status_code;
+    response
+}
+
+/// Sends the changes until the client disconnects. A client lagging behind so much that
+/// the changes are no longer buffered is disconnected, as it couldn't get a consistent view.
+async fn send_changes(
+    mut websocket: WebSocketStream<Upgraded>,
+    snapshot: Vec<Arc<str>>,
+    mut receiver: broadcast::Receiver<Arc<str>>,
+) {
+    for frame in snapshot {
+        if websocket
+            .send(Message::Text(frame.to_string()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+    let close_frame = loop {
+        match receiver.recv().await {
+            Ok(frame) => {
+                if websocket
+                    .send(Message::Text(frame.to_string()))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            Err(RecvError::Lagged(_)) => {
+                break CloseFrame {
+                    code: CloseCode::Again,
+                    reason: "lagged behind the changes".into(),
+                };
+            }
+            Err(RecvError::Closed) => {
+                break CloseFrame {
+                    code: CloseCode::Away,
+                    reason: "the table is no longer served".into(),
+                };
+            }
+        }
+    };
+    // The client may be gone already
+    let _ = websocket.close(Some(close_frame)).await;
+}
+
+/// Requests a savepoint with the name given in the path `/savepoint/NAME`.
+fn request_savepoint(
+    path: &str,
+    savepoint_requests: Option<&SharedSavepointRequests>,
+) -> (StatusCode, &'static str) {
+    let Some(savepoint_requests) = savepoint_requests else {
+        return (StatusCode::NOT_FOUND, "persistence is not enabled");
+    };
+    let name = path.trim_start_matches("/savepoint/");
+    if !is_valid_savepoint_name(name) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "only letters, digits, '_' and '-' are allowed in savepoint names",
+        );
+    }
+    savepoint_requests.lock().unwrap().push(name.to_string());
+    (StatusCode::ACCEPTED, "savepoint requested")
+}
+
+#[derive(Clone)]
+struct ServerState {
+    stats: Arc<ArcSwapOption<ProberStats>>,
+    savepoint_requests: Option<SharedSavepointRequests>,
+    served_tables: SharedServedTables,
+    shutdown_request: SharedShutdownRequest,
+    topology: Arc<[OperatorNode]>,
+    alerts: SharedAlerts,
+    auth: Arc<HttpAuth>,
+}
+
+/// Handles a request of a client, named by its certificate if it presented one.
+async fn handle_request(
+    req: Request<Body>,
+    state: ServerState,
+    client_name: Option<Arc<str>>,
+) -> Result<Response<Body>, Error> {
+    if let Err(failure) =
+        state
+            .auth
+            .authorize(req.uri().path(), req.headers(), client_name.as_deref())
+    {
+        return Ok(auth_failure_response(failure));
+    }
+    if req.method() == Method::GET
+        && req.uri().path().starts_with("/tables/")
+        && req.uri().path().ends_with("/changes")
+    {
+        return Ok(stream_served_table_changes(req, &state.served_tables));
+    }
+    if req.method() == Method::GET && req.uri().path() == "/profile" {
+        return Ok(capture_profile(req.uri().query()).await);
+    }
+    let mut response = Response::new(Body::empty());
+    let stats = &state.stats;
+
+    match (req.method(), req.uri().path()) {
+        (&Method::GET, "/status") => {
+            let metrics_text = metrics_from_stats(stats, &state.alerts);
+            *response.body_mut() = Body::from(metrics_text);
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                header::HeaderValue::from_static("application/json"),
+            );
+        }
+        (&Method::GET, "/metrics") => {
+            let metrics_text = metrics_from_stats(stats, &state.alerts);
+            *response.body_mut() = Body::from(metrics_text);
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                header::HeaderValue::from_static(
+                    "application/openmetrics-text; version=1.0.0; charset=utf-8",
+                ),
+            );
+        }
+        (&Method::GET, "/healthz") => {
+            let (status_code, report) = health_from_stats(stats, &state.alerts);
+            *response.status_mut() = status_code;
+            *response.body_mut() = Body::from(report);
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                header::HeaderValue::from_static("application/json"),
+            );
+        }
+        (&Method::GET, "/topology") => {
+            *response.body_mut() = Body::from(topology_from_stats(stats, &state.topology));
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                header::HeaderValue::from_static("application/json"),
+            );
+        }
+        (&Method::GET, "/allocator") => {
+            let (status_code, body) = allocator_stats_json();
+            *response.status_mut() = status_code;
+            *response.body_mut() = Body::from(body);
+            if status_code == StatusCode::OK {
+                response.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    header::HeaderValue::from_static("application/json"),
+                );
+            }
+        }
+        (&Method::GET, "/ready") => {
+            let (status_code, message) = readiness_from_stats(stats);
+            *response.status_mut() = status_code;
+            *response.body_mut() = Body::from(message);
+        }
+        (&Method::GET, path) if path.starts_with("/tables/") => {
+            let (status_code, body) =
+                lookup_in_served_table(path, req.uri().query(), &state.served_tables);
+            *response.status_mut() = status_code;
+            *response.body_mut() = Body::from(body);
+            if status_code == StatusCode::OK {
+                response.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    header::HeaderValue::from_static("application/json"),
+                );
+            }
+        }
+        (&Method::POST, path) if path.starts_with("/savepoint/") => {
+            let (status_code, message) = request_savepoint(path, state.savepoint_requests.as_ref());
+            *response.status_mut() = status_code;
+            *response.body_mut() = Body::from(message);
+        }
+        (&Method::POST, "/shutdown") => {
+            state.shutdown_request.request();
+            *response.status_mut() = StatusCode::ACCEPTED;
+            *response.body_mut() = Body::from("shutdown requested");
+        }
+
+        _ => {
+            *response.status_mut() = StatusCode::NOT_FOUND;
+        }
+    };
+    Ok(response)
+}
+
+fn auth_failure_response(failure: HttpAuthFailure) -> Response<Body> {
+    match failure {
+        HttpAuthFailure::Unauthenticated => {
+            let mut response = plain_response(
+                StatusCode::UNAUTHORIZED,
+                "authentication required".to_string(),
+            );
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                header::HeaderValue::from_static("Bearer"),
+            );
+            response
+        }
+        HttpAuthFailure::Forbidden => plain_response(
+            StatusCode::FORBIDDEN,
+            "not allowed to use this route".to_string(),
+        ),
+    }
+}
+
+async fn accept_tls(
+    acceptor: &SslAcceptor,
+    stream: TcpStream,
+) -> Result<SslStream<TcpStream>, DynError> {
+    let mut stream = SslStream::new(Ssl::new(acceptor.context())?, stream)?;
+    Pin::new(&mut stream).accept().await?;
+    Ok(stream)
+}
+
+/// Serves the requests over TLS until `shutdown_signal` completes. The connections are
+/// handled by hyper once the TLS handshake, verifying the client certificate if required,
+/// succeeds. On shutdown, like the plain http server, it stops accepting connections and
+/// waits for the requests in progress to complete, closing the idle connections.
+async fn serve_tls(
+    addr: SocketAddr,
+    acceptor: SslAcceptor,
+    state: ServerState,
+    shutdown_signal: impl Future<Output = ()>,
+) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    info!("Metrics available at https://{addr}");
+    let acceptor = Arc::new(acceptor);
+    let (shutdown_sender, shutdown_receiver) = watch::channel(());
+    // Every connection holds a sender, so the channel is closed once all of them are done
+    let (connection_sender, mut connections_done) = mpsc::channel::<()>(1);
+    tokio::pin!(shutdown_signal);
+    loop {
+        let (stream, peer_addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("Failed to accept a connection to the http monitoring server: {e}");
+                    continue;
+                }
+            },
+            () = &mut shutdown_signal => break,
+        };
+        let acceptor = acceptor.clone();
+        let state = state.clone();
+        let mut shutdown_receiver = shutdown_receiver.clone();
+        let connection_sender = connection_sender.clone();
+        tokio::spawn(async move {
+            let _connection_sender = connection_sender;
+            let stream = tokio::select! {
+                stream = accept_tls(&acceptor, stream) => match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        warn!("TLS handshake with {peer_addr} failed: {e}");
+                        return;
+                    }
+                },
+                _ = shutdown_receiver.changed() => return,
+            };
+            let client_name: Option<Arc<str>> = stream
+                .ssl()
+                .peer_certificate()
+                .and_then(|certificate| http_auth::client_name(&certificate))
+                .map(Into::into);
+            let service =
+                service_fn(move |req| handle_request(req, state.clone(), client_name.clone()));
+            let connection = Http::new()
+                .serve_connection(stream, service)
+                .with_upgrades();
+            tokio::pin!(connection);
+            let result = tokio::select! {
+                result = &mut connection => result,
+                _ = shutdown_receiver.changed() => {
+                    connection.as_mut().graceful_shutdown();
+                    connection.await
+                }
+            };
+            if let Err(e) = result {
+                warn!("Failed to serve the connection from {peer_addr}: {e}");
+            }
+        });
+    }
+    drop(listener);
+    shutdown_sender.send_replace(());
+    drop(connection_sender);
+    connections_done.recv().await;
+    Ok(())
+}
+
 /// Starts a lightweight http server allowing monitoring.
 /// Available at: http://localhost:PORT/status
 /// where PORT is `PATHWAY_MONITORING_HTTP_PORT + process_id`
+/// and HOST, `localhost` by default, can be set with `PATHWAY_MONITORING_HTTP_HOST`.
 /// It uses tokio and hyper. The status is passed using arcswap to avoid mutexes.
+/// `/healthz` and `/ready` are meant for liveness and readiness probes.
+/// `/topology` describes the operators of the computation with their live statistics
+/// and `/allocator` reports the statistics of the memory allocator.
+/// `/profile?seconds=N` captures a CPU profile of the workers for N seconds
+/// and returns it as a flamegraph, or in the format of `pprof` with `format=pprof`.
+/// The rows of the served tables can be looked up with `GET /tables/NAME`
+/// and their changes streamed over a WebSocket at `/tables/NAME/changes`.
+/// If persistence is enabled, a savepoint can be requested with `POST /savepoint/NAME`.
+/// `POST /shutdown` stops the computation of the process gracefully.
+/// The server uses TLS and authenticates the clients if configured, see [`HttpAuth`]
+/// and [`tls_acceptor_from_env`]. It isn't started if the configuration is invalid.
+#[allow(clippy::too_many_arguments)]
 pub fn start_http_server_thread(
     process_id: u16,
     // monitoring_status: Arc<ArcSwap<String>>,
     stats: Arc<ArcSwapOption<ProberStats>>,
+    savepoint_requests: Option<SharedSavepointRequests>,
+    served_tables: SharedServedTables,
+    shutdown_request: SharedShutdownRequest,
+    topology: Arc<[OperatorNode]>,
+    alerts: SharedAlerts,
     http_terminate_receiver: tokio::sync::oneshot::Receiver<()>,
 ) -> JoinHandle<()> {
     let monitoring_http_port: u16 = env::var("PATHWAY_MONITORING_HTTP_PORT")
@@ -85,64 +1017,71 @@ pub fn start_http_server_thread(
         .unwrap_or_default()
         .parse::<u16>()
         .unwrap_or(DEFAULT_MONITORING_HTTP_PORT);
+    let monitoring_http_host: IpAddr = env::var("PATHWAY_MONITORING_HTTP_HOST")
+        .ok()
+        .unwrap_or_default()
+        .parse::<IpAddr>()
+        .unwrap_or(DEFAULT_MONITORING_HTTP_HOST);
 
     Builder::new()
         .name("pathway:http_monitoring".to_string())
         .spawn(move || {
-            let stats = stats.clone();
+            let security = tls_acceptor_from_env().and_then(|tls_acceptor| {
+                let client_certificates = tls_acceptor.as_ref().is_some_and(|acceptor| {
+                    acceptor.context().verify_mode().contains(SslVerifyMode::PEER)
+                });
+                Ok((tls_acceptor, HttpAuth::from_env(client_certificates)?))
+            });
+            let (tls_acceptor, auth) = match security {
+                Ok(security) => security,
+                Err(e) => {
+                    error!("http monitoring server for process {process_id} not started: {e}");
+                    // Waiting for the termination anyway, as the runner expects
+                    let _ = http_terminate_receiver.blocking_recv();
+                    return;
+                }
+            };
+            if !auth.is_enabled() && !monitoring_http_host.is_loopback() {
+                warn!("The http monitoring server is exposed at {monitoring_http_host} without authentication");
+            }
+            let state = ServerState {
+                stats,
+                savepoint_requests,
+                served_tables,
+                shutdown_request,
+                topology,
+                alerts,
+                auth: Arc::new(auth),
+            };
             tokio::runtime::Builder::new_current_thread()
                 .enable_io()
                 .build()
                 .unwrap()
                 .block_on(async {
-                    let addr = ([127, 0, 0, 1], monitoring_http_port + process_id).into();
-                    let make_service = make_service_fn(move |_| {
-                        let stats = stats.clone();
-                        async move {
-                            Ok::<_, Error>(service_fn(move |req| {
-                                let stats = stats.clone();
-
-                                async move {
-                                    let mut response = Response::new(Body::empty());
-                                    let stats = stats.clone();
-
-                                    let metrics_text = metrics_from_stats(&stats);
-                                    match (req.method(), req.uri().path()) {
-                                        (&Method::GET, "/status") => {
-                                            *response.body_mut() = Body::from(metrics_text);
-                                            response.headers_mut().insert(
-                                                header::CONTENT_TYPE,
-                                                header::HeaderValue::from_static(
-                                                    "application/json",
-                                                ),
-                                            );
-                                        }
-                                        (&Method::GET, "/metrics") => {                              
-                                            *response.body_mut() = Body::from(metrics_text);
-                                            response.headers_mut().insert(
-                                                header::CONTENT_TYPE,
-                                                header::HeaderValue::from_static(
-                                                    "application/openmetrics-text; version=1.0.0; charset=utf-8",
-                                                ),
-                                            );
-                                        }
-
-                                        _ => {
-                                            *response.status_mut() = StatusCode::NOT_FOUND;
-                                        }
-                                    };
-                                    Ok::<_, Error>(response)
-                                }
-                            }))
-                        }
-                    });
-                    let server = Server::bind(&addr).serve(make_service);
+                    let addr =
+                        SocketAddr::new(monitoring_http_host, monitoring_http_port + process_id);
                     let shutdown_signal = async move {
                         http_terminate_receiver.await.unwrap();
                     };
-                    let graceful = server.with_graceful_shutdown(shutdown_signal);
-                    info!("Metrics available at http://{addr}");
-                    if let Err(e) = graceful.await {
+                    let result = if let Some(tls_acceptor) = tls_acceptor {
+                        serve_tls(addr, tls_acceptor, state, shutdown_signal)
+                            .await
+                            .map_err(DynError::from)
+                    } else {
+                        let make_service = make_service_fn(move |_| {
+                            let state = state.clone();
+                            async move {
+                                Ok::<_, Error>(service_fn(move |req| {
+                                    handle_request(req, state.clone(), None)
+                                }))
+                            }
+                        });
+                        let server = Server::bind(&addr).serve(make_service);
+                        let graceful = server.with_graceful_shutdown(shutdown_signal);
+                        info!("Metrics available at http://{addr}");
+                        graceful.await.map_err(DynError::from)
+                    };
+                    if let Err(e) = result {
                         error!(
                             "http monitoring server error for process {process_id}: {e}"
                         );
@@ -158,7 +1097,15 @@ pub struct Runner {
 }
 
 impl Runner {
-    fn run(stats: &Arc<ArcSwapOption<ProberStats>>, process_id: usize) -> Runner {
+    fn run(
+        stats: &Arc<ArcSwapOption<ProberStats>>,
+        savepoint_requests: Option<SharedSavepointRequests>,
+        served_tables: SharedServedTables,
+        shutdown_request: SharedShutdownRequest,
+        topology: Arc<[OperatorNode]>,
+        alerts: SharedAlerts,
+        process_id: usize,
+    ) -> Runner {
         let (http_terminate_transmitter, http_terminate_receiver) =
             tokio::sync::oneshot::channel::<()>();
         let http_server_thread_handle = {
@@ -166,6 +1113,11 @@ impl Runner {
             start_http_server_thread(
                 u16::try_from(process_id).unwrap(),
                 stats,
+                savepoint_requests,
+                served_tables,
+                shutdown_request,
+                topology,
+                alerts,
                 http_terminate_receiver,
             )
         };
@@ -191,19 +1143,33 @@ impl Drop for Runner {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn maybe_run_http_server_thread(
     with_http_server: bool,
     graph: &dyn Graph,
     process_id: usize,
+    savepoint_requests: Option<SharedSavepointRequests>,
+    served_tables: SharedServedTables,
+    shutdown_request: SharedShutdownRequest,
+    topology: Arc<[OperatorNode]>,
+    alerts: SharedAlerts,
 ) -> Option<Runner> {
     if with_http_server && graph.worker_index() == 0 {
         let stats_shared = Arc::new(ArcSwapOption::from(None));
-        let http_server_runner = Runner::run(&stats_shared, process_id);
+        let http_server_runner = Runner::run(
+            &stats_shared,
+            savepoint_requests,
+            served_tables,
+            shutdown_request,
+            topology,
+            alerts,
+            process_id,
+        );
 
         graph
             .attach_prober(
                 Box::new(move |prober_stats| stats_shared.store(Some(Arc::new(prober_stats)))),
-                false,
+                true,
                 false,
             )
             .expect("Failed to start http monitoring server");
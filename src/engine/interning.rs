@@ -0,0 +1,65 @@
+// Copyright © 2024 Pathway
+
+//! Interning of short strings, so that the values of the same string, e.g. in a column
+//! of statuses or country codes, share a single allocation instead of each of them having
+//! its own copy in the states of the operators.
+//!
+//! Each worker thread has its own pool, so interning takes no locks. The strings are
+//! reference counted, so they are still passed freely between the threads, and a string is
+//! allocated at most once per thread, as long as the pool keeps it.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use arcstr::ArcStr;
+
+/// The longer strings are unlikely to repeat often and are not interned.
+pub const MAX_INTERNED_LENGTH: usize = 32;
+
+/// The size of the pool of a thread at which the strings used only by the pool are first
+/// dropped from it.
+const INITIAL_SWEEP_SIZE: usize = 1 << 16;
+
+struct StringPool {
+    strings: HashSet<ArcStr>,
+    next_sweep_size: usize,
+}
+
+impl StringPool {
+    fn new() -> Self {
+        Self {
+            strings: HashSet::new(),
+            next_sweep_size: INITIAL_SWEEP_SIZE,
+        }
+    }
+
+    fn intern(&mut self, string: &str) -> ArcStr {
+        if let Some(interned) = self.strings.get(string) {
+            return interned.clone();
+        }
+        if self.strings.len() >= self.next_sweep_size {
+            self.strings
+                .retain(|interned| ArcStr::strong_count(interned) != Some(1));
+            // the pool grows if most of its strings are still used, so that the sweeps
+            // take amortized constant time
+            self.next_sweep_size = INITIAL_SWEEP_SIZE.max(2 * self.strings.len());
+        }
+        let interned = ArcStr::from(string);
+        self.strings.insert(interned.clone());
+        interned
+    }
+}
+
+thread_local! {
+    static POOL: RefCell<StringPool> = RefCell::new(StringPool::new());
+}
+
+/// Returns the string, sharing its allocation with the earlier strings equal to it
+/// interned by the same thread, unless it's longer than [`MAX_INTERNED_LENGTH`].
+pub fn intern(string: &str) -> ArcStr {
+    if string.len() > MAX_INTERNED_LENGTH {
+        return ArcStr::from(string);
+    }
+    POOL.try_with(|pool| pool.borrow_mut().intern(string))
+        .unwrap_or_else(|_destroyed| ArcStr::from(string))
+}
@@ -0,0 +1,76 @@
+// Copyright © 2024 Pathway
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// The context of the log records made by a thread: the worker it belongs to, the epoch
+/// the worker processes, and the connector and the operator the thread is working for.
+/// It's attached to the records, so that the logs can be filtered and aggregated by it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogContext {
+    pub worker_id: Option<usize>,
+    pub epoch: Option<u64>,
+    pub connector: Option<Arc<str>>,
+    pub operator_id: Option<usize>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<LogContext> = RefCell::new(LogContext::default());
+}
+
+pub fn current() -> LogContext {
+    CURRENT.with(|context| context.borrow().clone())
+}
+
+/// Sets the whole context of the current thread, e.g. a thread spawned by a worker
+/// can inherit the context of the worker.
+pub fn set(new_context: LogContext) {
+    CURRENT.with(|context| *context.borrow_mut() = new_context);
+}
+
+/// Sets the worker of the current thread, for the lifetime of the thread.
+pub fn set_worker(worker_id: usize) {
+    CURRENT.with(|context| context.borrow_mut().worker_id = Some(worker_id));
+}
+
+pub fn set_epoch(epoch: Option<u64>) {
+    CURRENT.with(|context| context.borrow_mut().epoch = epoch);
+}
+
+/// Restores the previous context of the thread when dropped.
+#[must_use]
+#[allow(clippy::module_name_repetitions)]
+pub struct LogContextGuard {
+    previous: LogContext,
+}
+
+impl Drop for LogContextGuard {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.previous);
+        CURRENT.with(|context| {
+            let mut context = context.borrow_mut();
+            context.connector = previous.connector;
+            context.operator_id = previous.operator_id;
+        });
+    }
+}
+
+/// Attributes the records made until the guard is dropped to the connector.
+pub fn enter_connector(connector: &Arc<str>) -> LogContextGuard {
+    CURRENT.with(|context| {
+        let mut context = context.borrow_mut();
+        let previous = context.clone();
+        context.connector = Some(connector.clone());
+        LogContextGuard { previous }
+    })
+}
+
+/// Attributes the records made until the guard is dropped to the operator.
+pub fn enter_operator(operator_id: usize) -> LogContextGuard {
+    CURRENT.with(|context| {
+        let mut context = context.borrow_mut();
+        let previous = context.clone();
+        context.operator_id = Some(operator_id);
+        LogContextGuard { previous }
+    })
+}
@@ -9,7 +9,7 @@ pub use self::error::{Error, Result};
 pub mod report_error;
 
 pub mod value;
-pub use self::value::{Key, KeyImpl, Type, Value};
+pub use self::value::{Key, KeyHashing, KeyImpl, SerializedObject, Type, Value, KEY_BITS};
 
 pub mod reduce;
 pub use reduce::Reducer;
@@ -17,13 +17,22 @@ pub use reduce::Reducer;
 pub mod graph;
 pub use graph::{
     BatchWrapper, ColumnHandle, ColumnPath, ColumnProperties, ComplexColumn, Computer,
-    ConcatHandle, Context, DataRow, ExpressionData, Graph, IterationLogic, IxKeyPolicy, IxerHandle,
-    JoinType, LegacyTable, OperatorStats, ProberStats, ReducerData, ScopedGraph, TableHandle,
-    TableProperties, UniverseHandle,
+    ConcatHandle, Context, DataRow, EdgeStats, ExpressionData, Graph, IterationLimit,
+    IterationLogic, IxKeyPolicy, IxerHandle, JoinType, LatencyHistogram, LegacyTable, OperatorNode,
+    OperatorStateLimit, OperatorStats, PersistenceStats, ProberStats, ReducerData, ScopedGraph,
+    TableHandle, TableProperties, UniverseHandle,
 };
 
+pub mod alerts;
+pub mod allocator_stats;
+pub mod http_auth;
 pub mod http_server;
 pub use http_server::maybe_run_http_server_thread;
+pub mod interning;
+pub mod shared_bytes;
+pub use shared_bytes::SharedBytes;
+pub mod sparse_matrix;
+pub use sparse_matrix::SparseMatrix;
 
 pub mod dataflow;
 pub use dataflow::{run_with_new_dataflow_graph, WakeupReceiver};
@@ -35,6 +44,13 @@ pub use expression::{
     StringExpression,
 };
 
+pub mod log_context;
+pub mod pending_work;
+pub mod profiling;
 pub mod progress_reporter;
+pub mod served_table;
+pub mod shutdown;
+pub mod telemetry;
 pub mod time;
 pub use time::{DateTimeNaive, DateTimeUtc, Duration};
+pub mod value_encoding;
@@ -0,0 +1,143 @@
+// Copyright © 2024 Pathway
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use timely::communication::Allocate;
+use timely::logging::{TimelyEvent, TimelyLogger};
+use timely::worker::Worker;
+
+use super::EdgeStats;
+
+/// Numbers of the records sent over the channels between timely operators and not received
+/// yet, by the identifiers of the channels. They are shared by the workers of a process,
+/// as a record exchanged between workers is sent by one of them and received by another.
+/// The records exchanged with other processes are counted only on one side, so with
+/// multiple processes the numbers are approximate.
+pub type SharedPendingRecords = Arc<Mutex<HashMap<usize, i64>>>;
+
+/// The timely operators and channels of a worker, with the Pathway operators they belong to.
+#[derive(Debug, Default)]
+struct Attribution {
+    operators_by_address: HashMap<Vec<usize>, usize>,
+    unattributed_operators: Vec<usize>,
+    pathway_operators: HashMap<usize, usize>,
+    channels: HashMap<usize, (Vec<usize>, Vec<usize>)>,
+}
+
+impl Attribution {
+    fn pathway_operator(&self, address: &[usize]) -> Option<usize> {
+        let timely_operator = self.operators_by_address.get(address)?;
+        self.pathway_operators.get(timely_operator).copied()
+    }
+}
+
+/// Tracks the records buffered between the operators of a worker, using the timely logging
+/// of the operators, the channels and the messages sent over them. The timely operators
+/// are attributed to a Pathway operator once it's built, so all the timely operators created
+/// since the previous Pathway operator belong to it.
+pub struct PendingWorkTracker {
+    attribution: Rc<RefCell<Attribution>>,
+    pending_records: SharedPendingRecords,
+}
+
+impl PendingWorkTracker {
+    /// Starts tracking the pending work of the worker. It has to be done before the dataflow
+    /// is built, as the operators and the channels are logged when they are created.
+    pub fn install<A: Allocate>(
+        worker: &mut Worker<A>,
+        pending_records: SharedPendingRecords,
+    ) -> Self {
+        let attribution = Rc::new(RefCell::new(Attribution::default()));
+        let logged_attribution = attribution.clone();
+        let logged_pending_records = pending_records.clone();
+        worker
+            .log_register()
+            .insert::<TimelyEvent, _>("timely", move |_time, events| {
+                let mut attribution = logged_attribution.borrow_mut();
+                let mut pending_records = logged_pending_records.lock().unwrap();
+                for (_time, _worker, event) in events.drain(..) {
+                    match event {
+                        TimelyEvent::Operates(operator) => {
+                            attribution
+                                .operators_by_address
+                                .insert(operator.addr, operator.id);
+                            attribution.unattributed_operators.push(operator.id);
+                        }
+                        TimelyEvent::Channels(channel) => {
+                            // Index 0 stands for the boundary of the scope, i.e. its operator
+                            let address = |index: usize| {
+                                let mut address = channel.scope_addr.clone();
+                                if index > 0 {
+                                    address.push(index);
+                                }
+                                address
+                            };
+                            let addresses = (address(channel.source.0), address(channel.target.0));
+                            attribution.channels.insert(channel.id, addresses);
+                        }
+                        TimelyEvent::Messages(messages) => {
+                            let length = i64::try_from(messages.length).unwrap();
+                            let pending = pending_records.entry(messages.channel).or_default();
+                            if messages.is_send {
+                                *pending += length;
+                            } else {
+                                *pending -= length;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            });
+        Self {
+            attribution,
+            pending_records,
+        }
+    }
+
+    /// Attributes the timely operators created since the previous call to the given Pathway
+    /// operator, `None` for the inputs and the outputs.
+    pub fn attribute_operators(&self, logger: Option<TimelyLogger>, operator_id: Option<usize>) {
+        if let Some(mut logger) = logger {
+            // Passing the buffered events to the tracker
+            logger.flush();
+        }
+        let mut attribution = self.attribution.borrow_mut();
+        let unattributed_operators = std::mem::take(&mut attribution.unattributed_operators);
+        if let Some(operator_id) = operator_id {
+            for timely_operator in unattributed_operators {
+                attribution
+                    .pathway_operators
+                    .insert(timely_operator, operator_id);
+            }
+        }
+    }
+
+    /// Returns the pending records by the pairs of the Pathway operators.
+    pub fn edges_stats(&self) -> Vec<EdgeStats> {
+        let attribution = self.attribution.borrow();
+        let pending_records = self.pending_records.lock().unwrap();
+        let mut edges: BTreeMap<(Option<usize>, Option<usize>), u64> = BTreeMap::new();
+        for (channel, (source, target)) in &attribution.channels {
+            let pending = pending_records.get(channel).copied().unwrap_or_default();
+            *edges
+                .entry((
+                    attribution.pathway_operator(source),
+                    attribution.pathway_operator(target),
+                ))
+                .or_default() += u64::try_from(pending).unwrap_or(0);
+        }
+        edges
+            .into_iter()
+            .map(
+                |((source_operator, target_operator), pending_records)| EdgeStats {
+                    source_operator,
+                    target_operator,
+                    pending_records,
+                },
+            )
+            .collect()
+    }
+}
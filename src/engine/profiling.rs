@@ -0,0 +1,81 @@
+// Copyright © 2024 Pathway
+
+use std::thread;
+use std::time::Duration;
+
+use pprof::protos::Message as _;
+use pprof::ProfilerGuardBuilder;
+
+/// The threads of the timely workers, named by the communication layer.
+const WORKER_THREAD_PREFIX: &str = "pathway:work-";
+
+const SAMPLING_FREQUENCY_HZ: i32 = 99;
+pub const MAX_PROFILE_DURATION: Duration = Duration::from_secs(300);
+
+/// The libraries in which unwinding the stack isn't safe, e.g. while in a signal handler.
+const BLOCKLIST: [&str; 4] = ["libc", "libgcc", "pthread", "vdso"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileFormat {
+    /// An interactive SVG flamegraph.
+    Flamegraph,
+    /// The protobuf format of `pprof`, e.g. for `go tool pprof`.
+    Pprof,
+}
+
+impl ProfileFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "flamegraph" | "svg" => Some(Self::Flamegraph),
+            "pprof" | "protobuf" => Some(Self::Pprof),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Flamegraph => "image/svg+xml",
+            Self::Pprof => "application/octet-stream",
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[allow(clippy::module_name_repetitions)]
+pub enum ProfilingError {
+    #[error("another profile is being captured")]
+    AlreadyRunning,
+
+    #[error("profiling failed: {0}")]
+    Profiler(#[from] pprof::Error),
+}
+
+/// Samples the stacks of the worker threads for `duration`, blocking the calling thread
+/// meanwhile. The samples are taken from the whole process with `SIGPROF`, so only
+/// one profile can be captured at a time, and the samples of the other threads are dropped.
+pub fn capture(duration: Duration, format: ProfileFormat) -> Result<Vec<u8>, ProfilingError> {
+    let guard = ProfilerGuardBuilder::default()
+        .frequency(SAMPLING_FREQUENCY_HZ)
+        .blocklist(&BLOCKLIST)
+        .build()
+        .map_err(|e| match e {
+            pprof::Error::Running => ProfilingError::AlreadyRunning,
+            e => ProfilingError::Profiler(e),
+        })?;
+    thread::sleep(duration.min(MAX_PROFILE_DURATION));
+    let mut report = guard.report().build()?;
+    drop(guard);
+    report
+        .data
+        .retain(|frames, _count| frames.thread_name.starts_with(WORKER_THREAD_PREFIX));
+
+    let profile = match format {
+        ProfileFormat::Flamegraph => {
+            let mut svg = Vec::new();
+            report.flamegraph(&mut svg)?;
+            svg
+        }
+        ProfileFormat::Pprof => report.pprof()?.encode_to_vec(),
+    };
+    Ok(profile)
+}
@@ -34,7 +34,10 @@ impl Runner {
                 .spawn(move || {
                     let thread_state = PythonThreadState::new();
 
-                    while !should_finish.load(Ordering::Relaxed) {
+                    loop {
+                        // Reporting once more when finishing, so that the final stats
+                        // of the computation are reported as well
+                        let finishing = should_finish.load(Ordering::Relaxed);
                         if let Some(ref stats) = *stats.load() {
                             let now = SystemTime::now();
                             let duration = u64::try_from(
@@ -53,6 +56,9 @@ impl Runner {
                                     .unwrap();
                             });
                         }
+                        if finishing {
+                            break;
+                        }
 
                         thread::park_timeout(printing_period);
                     }
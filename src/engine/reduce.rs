@@ -9,6 +9,7 @@ use differential_dataflow::{
 };
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::iter::repeat;
 use std::num::NonZeroUsize;
 use std::ops::Add;
@@ -19,6 +20,23 @@ use super::{Key, Value};
 pub type StatefulCombineFn =
     Arc<dyn Fn(Option<&Value>, &[(Vec<Value>, isize)]) -> Option<Value> + Send + Sync>;
 
+/// The state of a group of a `RetractableReducerImpl`, opaque to the engine.
+pub type RetractableState = Box<dyn Any + Send>;
+
+/// A reducer implemented outside of the engine, e.g. in Python, keeping a state per group.
+/// The rows inserted into a group are added to its state and the removed ones are
+/// retracted from it, so the state is never recomputed from all the rows of the group.
+pub trait RetractableReducerImpl: Send + Sync {
+    /// Applies the rows inserted (with positive diffs) and removed (with negative diffs) at
+    /// a time to the states of the groups, creating the states of the new groups, and
+    /// returns the current values of the groups. All the groups changed at a time are passed
+    /// at once, so that e.g. the GIL is acquired once for all of them.
+    fn update(
+        &self,
+        groups: &mut [(Option<RetractableState>, Vec<(Vec<Value>, isize)>)],
+    ) -> Vec<Value>;
+}
+
 #[derive(Clone)]
 pub enum Reducer {
     Count,
@@ -30,10 +48,19 @@ pub enum Reducer {
     ArgMin,
     Max,
     ArgMax,
-    SortedTuple { skip_nones: bool },
-    Tuple { skip_nones: bool },
+    SortedTuple {
+        skip_nones: bool,
+    },
+    Tuple {
+        skip_nones: bool,
+    },
     Any,
-    Stateful { combine_fn: StatefulCombineFn },
+    Stateful {
+        combine_fn: StatefulCombineFn,
+    },
+    Retractable {
+        reducer: Arc<dyn RetractableReducerImpl>,
+    },
 }
 
 pub trait SemigroupReducerImpl: 'static {
@@ -177,6 +204,7 @@ pub struct ArraySumReducer;
 enum ArraySumState<'a> {
     IntArray(CowArray<'a, i64, IxDyn>),
     FloatArray(CowArray<'a, f64, IxDyn>),
+    Float32Array(CowArray<'a, f32, IxDyn>),
 }
 
 impl<'a> ArraySumState<'a> {
@@ -198,6 +226,14 @@ impl<'a> ArraySumState<'a> {
                     Self::FloatArray(CowArray::from(&**array * cnt.get() as f64))
                 }
             }
+            #[allow(clippy::cast_precision_loss)]
+            Value::Float32Array(array) => {
+                if cnt.get() == 1 {
+                    Self::Float32Array(CowArray::from(&**array))
+                } else {
+                    Self::Float32Array(CowArray::from(&**array * cnt.get() as f32))
+                }
+            }
             _ => panic!("unsupported type for npsum"),
         }
     }
@@ -214,6 +250,9 @@ impl<'a> Add for ArraySumState<'a> {
             (Self::FloatArray(lhs), Self::FloatArray(rhs)) => {
                 Self::FloatArray(CowArray::from(lhs.into_owned() + &rhs))
             }
+            (Self::Float32Array(lhs), Self::Float32Array(rhs)) => {
+                Self::Float32Array(CowArray::from(lhs.into_owned() + &rhs))
+            }
             _ => panic!("mixing types in npsum is not allowed"),
         }
     }
@@ -224,6 +263,7 @@ impl<'a> From<ArraySumState<'a>> for Value {
         match state {
             ArraySumState::IntArray(a) => Self::from(a.into_owned()),
             ArraySumState::FloatArray(a) => Self::from(a.into_owned()),
+            ArraySumState::Float32Array(a) => Self::from(a.into_owned()),
         }
     }
 }
@@ -534,3 +574,58 @@ impl StatefulReducer {
         (self.combine_fn)(state, data)
     }
 }
+
+/// The state of a group of a `RetractableReducer` with the number of its rows, so that it
+/// is dropped once the group is empty.
+pub struct RetractableGroup {
+    rows: isize,
+    state: Option<RetractableState>,
+}
+
+#[derive(Clone)]
+pub struct RetractableReducer {
+    reducer: Arc<dyn RetractableReducerImpl>,
+}
+
+impl RetractableReducer {
+    pub fn new(reducer: Arc<dyn RetractableReducerImpl>) -> Self {
+        Self { reducer }
+    }
+
+    /// Updates the groups changed at a time and returns their values, `None` for the groups
+    /// that became empty.
+    pub fn update(
+        &self,
+        groups: &mut [(Option<RetractableGroup>, Vec<(Vec<Value>, isize)>)],
+    ) -> Vec<Option<Value>> {
+        let mut updated_indices = Vec::new();
+        let mut updates = Vec::new();
+        for (index, (group, rows)) in groups.iter_mut().enumerate() {
+            let group = group.get_or_insert_with(|| RetractableGroup {
+                rows: 0,
+                state: None,
+            });
+            group.rows += rows.iter().map(|(_values, diff)| diff).sum::<isize>();
+            if group.rows > 0 {
+                updated_indices.push(index);
+                updates.push((group.state.take(), std::mem::take(rows)));
+            }
+        }
+        let mut results = vec![None; groups.len()];
+        if updates.is_empty() {
+            return results;
+        }
+        let values = self.reducer.update(&mut updates);
+        for ((index, (state, _rows)), value) in updated_indices.into_iter().zip(updates).zip(values)
+        {
+            groups[index].0.as_mut().unwrap().state = state;
+            results[index] = Some(value);
+        }
+        for (group, _rows) in groups.iter_mut() {
+            if group.as_ref().is_some_and(|group| group.rows <= 0) {
+                *group = None;
+            }
+        }
+        results
+    }
+}
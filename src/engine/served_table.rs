@@ -0,0 +1,157 @@
+// Copyright © 2024 Pathway
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem::take;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+use tokio::sync::broadcast;
+
+use super::graph::{SubscribeCallbacks, SubscribeCallbacksBuilder};
+use super::{BatchWrapper, Key, Value};
+use crate::connectors::data_format::serialize_value_to_json;
+
+/// The number of changes buffered for the clients streaming them. A client lagging behind
+/// by more is disconnected.
+const CHANGES_CAPACITY: usize = 16384;
+
+/// An output table kept in memory, so that its rows can be looked up with the http server.
+/// The workers of a process update the rows of their shards of the table. The table is
+/// updated only when a time is finished, so the lookups never see a part of a time.
+/// The changes are also broadcast as JSON frames to the clients streaming them.
+pub struct ServedTable {
+    column_names: Vec<String>,
+    rows: HashMap<Key, Vec<Value>>,
+    last_time: Option<u64>,
+    changes: broadcast::Sender<Arc<str>>,
+}
+
+pub type SharedServedTable = Arc<RwLock<ServedTable>>;
+
+/// The tables served by the http server of a process, by their names.
+pub type SharedServedTables = Arc<Mutex<HashMap<String, SharedServedTable>>>;
+
+impl ServedTable {
+    pub fn new(column_names: Vec<String>) -> Self {
+        let (changes, _) = broadcast::channel(CHANGES_CAPACITY);
+        Self {
+            column_names,
+            rows: HashMap::new(),
+            last_time: None,
+            changes,
+        }
+    }
+
+    fn apply(&mut self, changes: Vec<(Key, Vec<Value>, isize)>, time: u64) {
+        // A row changed within a time is removed and inserted in any order
+        let (insertions, removals): (Vec<_>, Vec<_>) = changes
+            .into_iter()
+            .partition(|(_key, _values, diff)| *diff > 0);
+        for (key, values, _diff) in removals {
+            if self.rows.get(&key) == Some(&values) {
+                self.broadcast_change("delete", key, &values, time);
+                self.rows.remove(&key);
+            }
+        }
+        for (key, values, _diff) in insertions {
+            self.broadcast_change("insert", key, &values, time);
+            self.rows.insert(key, values);
+        }
+        self.last_time = self.last_time.max(Some(time));
+    }
+
+    fn change_frame(&self, change_type: &str, key: Key, values: &[Value], time: u64) -> Arc<str> {
+        let frame = json!({
+            "type": change_type,
+            "time": time,
+            "row": self.row_to_json(key, values),
+        });
+        frame.to_string().into()
+    }
+
+    fn broadcast_change(&self, change_type: &str, key: Key, values: &[Value], time: u64) {
+        if self.changes.receiver_count() > 0 {
+            // Fails only if all receivers were dropped meanwhile
+            let _ = self
+                .changes
+                .send(self.change_frame(change_type, key, values, time));
+        }
+    }
+
+    /// Returns the current rows as `insert` frames, followed by the frames of
+    /// the changes made later.
+    pub fn stream_changes(&self) -> (Vec<Arc<str>>, broadcast::Receiver<Arc<str>>) {
+        let time = self.last_time.unwrap_or_default();
+        let snapshot = self
+            .rows
+            .iter()
+            .map(|(key, values)| self.change_frame("insert", *key, values, time))
+            .collect();
+        (snapshot, self.changes.subscribe())
+    }
+
+    fn row_to_json(&self, key: Key, values: &[Value]) -> JsonValue {
+        let mut row = JsonMap::new();
+        row.insert("id".to_string(), JsonValue::String(key.to_string()));
+        for (name, value) in self.column_names.iter().zip(values) {
+            let value = serialize_value_to_json(value).unwrap_or(JsonValue::Null);
+            row.insert(name.clone(), value);
+        }
+        JsonValue::Object(row)
+    }
+
+    /// Returns the row with the given key as a JSON object with the columns and the `id`.
+    pub fn lookup(&self, key: Key) -> Option<JsonValue> {
+        self.rows
+            .get(&key)
+            .map(|values| self.row_to_json(key, values))
+    }
+
+    /// Returns the rows whose columns are equal to the given values. A value is
+    /// compared with the column serialized to JSON, and if it's not a valid JSON, with
+    /// the column being a string, e.g. both `10` and `Alice` match as expected.
+    pub fn filter(&self, conditions: &[(String, String)]) -> Result<Vec<JsonValue>, String> {
+        let mut column_conditions = Vec::with_capacity(conditions.len());
+        for (name, expected) in conditions {
+            let Some(index) = self.column_names.iter().position(|column| column == name) else {
+                return Err(format!("unknown column: {name}"));
+            };
+            let expected = serde_json::from_str(expected)
+                .unwrap_or_else(|_| JsonValue::String(expected.clone()));
+            column_conditions.push((index, expected));
+        }
+        let rows = self
+            .rows
+            .iter()
+            .filter(|(_key, values)| {
+                column_conditions.iter().all(|(index, expected)| {
+                    serialize_value_to_json(&values[*index]).is_ok_and(|value| value == *expected)
+                })
+            })
+            .map(|(key, values)| self.row_to_json(*key, values))
+            .collect();
+        Ok(rows)
+    }
+}
+
+/// The callbacks of the subscription to the table updating its served copy in a worker.
+pub fn served_table_callbacks(served_table: SharedServedTable) -> SubscribeCallbacks {
+    let pending_changes = Rc::new(RefCell::new(Vec::new()));
+    let pending_changes_2 = pending_changes.clone();
+    SubscribeCallbacksBuilder::new()
+        .wrapper(BatchWrapper::None)
+        .on_data(Box::new(move |key, values, _time, diff| {
+            pending_changes
+                .borrow_mut()
+                .push((key, values.to_vec(), diff));
+            Ok(())
+        }))
+        .on_time_end(Box::new(move |time| {
+            let changes = take(&mut *pending_changes_2.borrow_mut());
+            served_table.write().unwrap().apply(changes, time);
+            Ok(())
+        }))
+        .build()
+}
@@ -0,0 +1,46 @@
+// Copyright © 2024 Pathway
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::Thread;
+
+use log::info;
+
+/// A request to stop the computation gracefully. The input connectors stop reading
+/// once the data read so far is committed, so that the computation drains: the times
+/// in flight are finished, the outputs are flushed and, if persistence is enabled,
+/// the final checkpoint is made.
+#[derive(Debug, Default)]
+pub struct ShutdownRequest {
+    requested: AtomicBool,
+    workers: Mutex<Vec<Thread>>,
+}
+
+pub type SharedShutdownRequest = Arc<ShutdownRequest>;
+
+impl ShutdownRequest {
+    /// Registers a worker, so that it's woken up when the shutdown is requested.
+    pub fn register_worker(&self, worker: Thread) {
+        self.workers.lock().unwrap().push(worker);
+        if self.is_requested() {
+            self.wake_up_workers();
+        }
+    }
+
+    pub fn request(&self) {
+        if !self.requested.swap(true, Ordering::SeqCst) {
+            info!("Shutdown requested, finishing the data read so far");
+        }
+        self.wake_up_workers();
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    fn wake_up_workers(&self) {
+        for worker in self.workers.lock().unwrap().iter() {
+            worker.unpark();
+        }
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright © 2024 Pathway
+
+//! Matrices of floats stored in the compressed sparse row (CSR) format, for the
+//! high-dimensional features with few nonzero elements, which would take most of their
+//! memory, and of the time of their products, for the zeros if stored densely.
+
+use std::fmt::{self, Display};
+
+use ndarray::Array2;
+
+use super::{Error, Result};
+
+/// A matrix in the CSR format: the stored elements of the rows, one row after another, with
+/// their columns, in increasing order within each row, and the offsets at which the rows
+/// start. The elements that aren't stored are zeros.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix {
+    shape: (usize, usize),
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    data: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// Creates a matrix from its CSR parts, checking that they describe a matrix of
+    /// the shape. The columns of the elements of a row can be given in any order.
+    pub fn new(
+        shape: (usize, usize),
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<f64>,
+    ) -> Result<Self> {
+        let (rows, columns) = shape;
+        if indptr.len() != rows + 1 || indptr[0] != 0 {
+            return Err(Error::ValueError(format!(
+                "sparse matrix with {rows} rows needs {} row offsets starting with 0",
+                rows + 1
+            )));
+        }
+        if indptr.windows(2).any(|offsets| offsets[0] > offsets[1])
+            || indptr[rows] != indices.len()
+            || indices.len() != data.len()
+        {
+            return Err(Error::ValueError(
+                "row offsets of sparse matrix don't match its elements".to_string(),
+            ));
+        }
+        if let Some(column) = indices.iter().find(|&&column| column >= columns) {
+            return Err(Error::ValueError(format!(
+                "column {column} out of bounds of sparse matrix with {columns} columns"
+            )));
+        }
+        let mut matrix = Self {
+            shape,
+            indptr,
+            indices,
+            data,
+        };
+        matrix.sort_rows();
+        Ok(matrix)
+    }
+
+    /// Creates a matrix from the elements of its rows, given in the order of the rows.
+    pub(crate) fn from_rows(
+        shape: (usize, usize),
+        rows: impl IntoIterator<Item = impl IntoIterator<Item = (usize, f64)>>,
+    ) -> Self {
+        let mut indptr = Vec::with_capacity(shape.0 + 1);
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        indptr.push(0);
+        for row in rows {
+            for (column, value) in row {
+                indices.push(column);
+                data.push(value);
+            }
+            indptr.push(indices.len());
+        }
+        Self::new(shape, indptr, indices, data).expect("rows should fit in the shape")
+    }
+
+    fn sort_rows(&mut self) {
+        for row in 0..self.shape.0 {
+            let range = self.indptr[row]..self.indptr[row + 1];
+            let indices = &self.indices[range.clone()];
+            if indices.windows(2).all(|pair| pair[0] < pair[1]) {
+                continue;
+            }
+            let mut elements: Vec<_> = indices
+                .iter()
+                .copied()
+                .zip(self.data[range.clone()].iter().copied())
+                .collect();
+            elements.sort_by_key(|&(column, _value)| column);
+            for (position, (column, value)) in range.zip(elements) {
+                self.indices[position] = column;
+                self.data[position] = value;
+            }
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    /// The number of the stored elements.
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        &self.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// The stored elements of the row, with their columns.
+    pub fn row(&self, row: usize) -> impl ExactSizeIterator<Item = (usize, f64)> + '_ {
+        let range = self.indptr[row]..self.indptr[row + 1];
+        self.indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.data[range].iter().copied())
+    }
+
+    pub fn to_dense(&self) -> Array2<f64> {
+        let mut dense = Array2::zeros(self.shape);
+        for row in 0..self.shape.0 {
+            for (column, value) in self.row(row) {
+                dense[(row, column)] += value;
+            }
+        }
+        dense
+    }
+}
+
+impl Display for SparseMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rows, columns) = self.shape;
+        write!(
+            f,
+            "<sparse matrix of shape ({rows}, {columns}) with {} stored elements>",
+            self.nnz()
+        )
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright © 2024 Pathway
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::sync::mpsc;
+use std::thread::{Builder, JoinHandle};
+
+use log::{info, warn};
+use opentelemetry::global::{self, BoxedSpan, BoxedTracer};
+use opentelemetry::trace::{Span, TraceContextExt, TraceError, Tracer};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace as sdktrace, Resource};
+use timely::dataflow::operators::probe::Handle as ProbeHandle;
+use tokio::sync::oneshot::Sender;
+
+const TRACER_NAME: &str = "pathway";
+const DEFAULT_SERVICE_NAME: &str = "pathway";
+
+/// The attribute correlating the spans of connectors, operators and checkpoints
+/// concerning the same epoch.
+pub const EPOCH_ATTRIBUTE: &str = "pathway.epoch";
+
+/// Returns the tracer for the spans of the engine. It does nothing unless
+/// the trace export is set up with [`Telemetry::init`].
+pub fn tracer() -> BoxedTracer {
+    global::tracer(TRACER_NAME)
+}
+
+pub fn epoch_attribute(epoch: u64) -> KeyValue {
+    KeyValue::new(EPOCH_ATTRIBUTE, i64::try_from(epoch).unwrap_or(i64::MAX))
+}
+
+/// Exports the spans of the engine to an OTLP collector for as long as it's alive.
+/// The export runs in a separate thread with its own tokio runtime, as the http server does.
+pub struct Telemetry {
+    export_thread_handle: Option<JoinHandle<()>>,
+    export_terminate_transmitter: Option<Sender<()>>,
+}
+
+impl Telemetry {
+    /// Sets up the export of the spans to the OTLP (gRPC) collector at `otlp_endpoint`.
+    /// The service is named after `OTEL_SERVICE_NAME`, `pathway` by default.
+    pub fn init(otlp_endpoint: &str) -> Result<Self, TraceError> {
+        let service_name =
+            env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| DEFAULT_SERVICE_NAME.to_string());
+        let otlp_endpoint = otlp_endpoint.to_string();
+        let (init_result_transmitter, init_result_receiver) = mpsc::channel();
+        let (export_terminate_transmitter, export_terminate_receiver) =
+            tokio::sync::oneshot::channel::<()>();
+
+        let export_thread_handle = Builder::new()
+            .name("pathway:trace_export".to_string())
+            .spawn(move || {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .unwrap()
+                    .block_on(async {
+                        // The exporter and the batch processor need to be created within the
+                        // runtime that drives them
+                        let installed = opentelemetry_otlp::new_pipeline()
+                            .tracing()
+                            .with_exporter(
+                                opentelemetry_otlp::new_exporter()
+                                    .tonic()
+                                    .with_endpoint(otlp_endpoint.clone()),
+                            )
+                            .with_trace_config(sdktrace::config().with_resource(Resource::new(
+                                vec![KeyValue::new("service.name", service_name)],
+                            )))
+                            .install_batch(runtime::Tokio);
+                        let is_installed = installed.is_ok();
+                        init_result_transmitter
+                            .send(installed.map(|_| ()))
+                            .expect("trace export setup result should be received");
+                        if is_installed {
+                            info!("Exporting traces to {otlp_endpoint}");
+                            export_terminate_receiver.await.unwrap();
+                        }
+                    });
+            })
+            .expect("trace export thread creation failed");
+
+        match init_result_receiver
+            .recv()
+            .expect("trace export thread should report the setup result")
+        {
+            Ok(()) => {
+                if let Err(e) = global::set_error_handler(|e| warn!("Failed to export traces: {e}"))
+                {
+                    warn!("Failed to set the handler of trace export errors: {e}");
+                }
+                Ok(Self {
+                    export_thread_handle: Some(export_thread_handle),
+                    export_terminate_transmitter: Some(export_terminate_transmitter),
+                })
+            }
+            Err(e) => {
+                export_thread_handle
+                    .join()
+                    .expect("trace export thread failed");
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        // Flushes the spans, so it has to be done while the export runtime is still running
+        global::shutdown_tracer_provider();
+        self.export_terminate_transmitter
+            .take()
+            .unwrap()
+            .send(())
+            .expect("couldn't send terminate message to trace export thread");
+        self.export_thread_handle
+            .take()
+            .unwrap()
+            .join()
+            .expect("trace export thread failed");
+    }
+}
+
+struct EpochSpans {
+    context: Context,
+    operators: HashMap<usize, BoxedSpan>,
+}
+
+impl EpochSpans {
+    fn end(self) {
+        for (_operator_id, mut span) in self.operators {
+            span.end();
+        }
+        self.context.span().end();
+    }
+}
+
+/// Traces the processing of epochs within a worker. The span of an epoch starts when
+/// the input frontier passes it and ends when the output frontier does. The probed
+/// operators get child spans, ending when their frontiers pass the epoch.
+pub struct EpochTracer {
+    worker_index: usize,
+    input_time: Option<u64>,
+    epochs: BTreeMap<u64, EpochSpans>,
+}
+
+impl EpochTracer {
+    pub fn new(worker_index: usize) -> Self {
+        Self {
+            worker_index,
+            input_time: Some(0),
+            epochs: BTreeMap::new(),
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        input_probe: &ProbeHandle<u64>,
+        output_probe: &ProbeHandle<u64>,
+        intermediate_probes: &HashMap<usize, ProbeHandle<u64>>,
+    ) {
+        let new_input_time = input_probe.with_frontier(|frontier| frontier.as_option().copied());
+        if new_input_time != self.input_time {
+            if let Some(epoch) = self.input_time {
+                self.start_epoch(epoch, intermediate_probes);
+            }
+            self.input_time = new_input_time;
+        }
+
+        for (epoch, spans) in &mut self.epochs {
+            spans.operators.retain(|operator_id, span| {
+                let operator_done = intermediate_probes
+                    .get(operator_id)
+                    .map_or(true, |probe| !probe.less_equal(epoch));
+                if operator_done {
+                    span.end();
+                }
+                !operator_done
+            });
+        }
+
+        while let Some(entry) = self.epochs.first_entry() {
+            if output_probe.less_equal(entry.key()) {
+                break;
+            }
+            entry.remove().end();
+        }
+    }
+
+    fn start_epoch(&mut self, epoch: u64, intermediate_probes: &HashMap<usize, ProbeHandle<u64>>) {
+        let tracer = tracer();
+        let span = tracer
+            .span_builder("epoch")
+            .with_attributes(vec![
+                epoch_attribute(epoch),
+                KeyValue::new(
+                    "pathway.worker_index",
+                    i64::try_from(self.worker_index).unwrap(),
+                ),
+            ])
+            .start(&tracer);
+        let context = Context::current_with_span(span);
+        let operators = intermediate_probes
+            .keys()
+            .map(|operator_id| {
+                let span = tracer.build_with_context(
+                    tracer.span_builder("operator").with_attributes(vec![
+                        epoch_attribute(epoch),
+                        KeyValue::new("pathway.operator_id", i64::try_from(*operator_id).unwrap()),
+                    ]),
+                    &context,
+                );
+                (*operator_id, span)
+            })
+            .collect();
+        self.epochs.insert(epoch, EpochSpans { context, operators });
+    }
+}
+
+impl Drop for EpochTracer {
+    fn drop(&mut self) {
+        for (_epoch, spans) in std::mem::take(&mut self.epochs) {
+            spans.end();
+        }
+    }
+}
@@ -2,15 +2,28 @@
 
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
-use chrono::{self, DurationRound, LocalResult, TimeZone};
+use chrono::{self, DurationRound, LocalResult, Offset, TimeZone};
 use chrono::{Datelike, Timelike};
 use chrono_tz::Tz;
 use num_integer::Integer;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
 use super::{Error, Result};
 
+const NANOSECONDS_IN_DAY: i64 = 1_000_000_000 * 60 * 60 * 24;
+
+// Julian day number of 1970-01-01T00:00:00 UTC
+const UNIX_EPOCH_JULIAN_DAY: f64 = 2_440_587.5;
+
+// Excel serial number of 1970-01-01 (days since 1899-12-30)
+const UNIX_EPOCH_EXCEL_SERIAL: f64 = 25_569.0;
+
+// Excel treats 1900 as a leap year, so serial 60 is the non-existent 1900-02-29
+// and all earlier serials are shifted by one day.
+const EXCEL_FIRST_CORRECT_SERIAL: f64 = 61.0;
+
 #[allow(clippy::module_name_repetitions)]
 pub trait DateTime {
     fn timestamp(&self) -> i64;
@@ -69,20 +82,29 @@ pub trait DateTime {
 
     fn strftime(&self, format: &str) -> String;
 
-    fn get_rounded_timestamp(&self, duration: Duration) -> i64 {
-        self.as_chrono_datetime()
-            .duration_round(duration.as_chrono_duration())
-            .unwrap()
-            .timestamp_nanos_opt()
-            .unwrap()
+    fn get_rounded_timestamp(&self, duration: Duration) -> Result<i64> {
+        let span = positive_span(duration)?;
+        let timestamp = self.timestamp();
+        let remainder = timestamp.rem_euclid(span);
+        if remainder == 0 {
+            Ok(timestamp)
+        } else if span - remainder <= remainder {
+            timestamp
+                .checked_add(span - remainder)
+                .ok_or(Error::DateTimeOverflow)
+        } else {
+            timestamp
+                .checked_sub(remainder)
+                .ok_or(Error::DateTimeOverflow)
+        }
     }
 
-    fn get_truncated_timestamp(&self, duration: Duration) -> i64 {
-        self.as_chrono_datetime()
-            .duration_trunc(duration.as_chrono_duration())
-            .unwrap()
-            .timestamp_nanos_opt()
-            .unwrap()
+    fn get_truncated_timestamp(&self, duration: Duration) -> Result<i64> {
+        let span = positive_span(duration)?;
+        let timestamp = self.timestamp();
+        timestamp
+            .checked_sub(timestamp.rem_euclid(span))
+            .ok_or(Error::DateTimeOverflow)
     }
 
     fn sanitize_format_string(format: &str) -> Result<String> {
@@ -102,6 +124,190 @@ pub trait DateTime {
             .num_days_from_monday()
             .into()
     }
+
+    fn iso_week(&self) -> i64 {
+        self.as_chrono_datetime().iso_week().week().into()
+    }
+
+    fn iso_year(&self) -> i64 {
+        self.as_chrono_datetime().iso_week().year().into()
+    }
+
+    fn quarter(&self) -> i64 {
+        ((self.as_chrono_datetime().month0() / 3) + 1).into()
+    }
+
+    fn day_of_year(&self) -> i64 {
+        self.as_chrono_datetime().ordinal().into()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn julian_day(&self) -> f64 {
+        self.timestamp() as f64 / NANOSECONDS_IN_DAY as f64 + UNIX_EPOCH_JULIAN_DAY
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+fn days_since_epoch_to_timestamp(days: f64) -> Result<i64> {
+    let timestamp = (days * NANOSECONDS_IN_DAY as f64).round();
+    if timestamp.is_finite() && timestamp >= i64::MIN as f64 && timestamp < i64::MAX as f64 {
+        Ok(timestamp as i64)
+    } else {
+        Err(Error::DateTimeConversionError)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarUnit {
+    Hour,
+    Day,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl CalendarUnit {
+    fn months(self) -> Option<i64> {
+        match self {
+            Self::Hour | Self::Day => None,
+            Self::Month => Some(1),
+            Self::Quarter => Some(3),
+            Self::Year => Some(12),
+        }
+    }
+}
+
+impl FromStr for CalendarUnit {
+    type Err = Error;
+
+    fn from_str(unit: &str) -> Result<Self> {
+        match unit {
+            "hour" => Ok(Self::Hour),
+            "day" => Ok(Self::Day),
+            "month" => Ok(Self::Month),
+            "quarter" => Ok(Self::Quarter),
+            "year" => Ok(Self::Year),
+            _ => Err(Error::ValueError(format!(
+                "calendar unit has to be one of hour, day, month, quarter, year but is {unit}."
+            ))),
+        }
+    }
+}
+
+fn truncate_to_calendar_unit(
+    datetime: chrono::NaiveDateTime,
+    unit: CalendarUnit,
+) -> chrono::NaiveDateTime {
+    let date = match unit {
+        CalendarUnit::Hour => {
+            return datetime.date().and_hms_opt(datetime.hour(), 0, 0).unwrap();
+        }
+        CalendarUnit::Day => datetime.date(),
+        CalendarUnit::Month | CalendarUnit::Quarter | CalendarUnit::Year => {
+            let unit_months = unit.months().unwrap();
+            let months = i64::from(datetime.year()) * 12 + i64::from(datetime.month0());
+            let months = Integer::div_floor(&months, &unit_months) * unit_months;
+            let (year, month_index) = Integer::div_mod_floor(&months, &12);
+            chrono::NaiveDate::from_ymd_opt(
+                i32::try_from(year).unwrap(),
+                u32::try_from(month_index + 1).unwrap(),
+                1,
+            )
+            .unwrap()
+        }
+    };
+    date.and_hms_opt(0, 0, 0).unwrap()
+}
+
+fn add_calendar_units(
+    datetime: chrono::NaiveDateTime,
+    unit: CalendarUnit,
+    count: i64,
+) -> Result<chrono::NaiveDateTime> {
+    let shifted = match unit {
+        CalendarUnit::Hour | CalendarUnit::Day => {
+            let count = i32::try_from(count).map_err(|_| Error::DateTimeConversionError)?;
+            let duration = if unit == CalendarUnit::Hour {
+                chrono::Duration::hours(count.into())
+            } else {
+                chrono::Duration::days(count.into())
+            };
+            datetime.checked_add_signed(duration)
+        }
+        CalendarUnit::Month | CalendarUnit::Quarter | CalendarUnit::Year => {
+            let months = count
+                .checked_mul(unit.months().unwrap())
+                .and_then(|months| i32::try_from(months).ok())
+                .ok_or(Error::DateTimeConversionError)?;
+            if months >= 0 {
+                datetime.checked_add_months(chrono::Months::new(months.unsigned_abs()))
+            } else {
+                datetime.checked_sub_months(chrono::Months::new(months.unsigned_abs()))
+            }
+        }
+    };
+    shifted.ok_or(Error::DateTimeConversionError)
+}
+
+/// Localizes `local` in `tz`. If `local` doesn't exist in `tz` (e.g. at the beginning
+/// of DST), the first local time after the gap is localized instead.
+fn localize_skipping_gap(
+    tz: Tz,
+    local: chrono::NaiveDateTime,
+) -> LocalResult<chrono::DateTime<Tz>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::None => {
+            // gaps end at full hours
+            local
+                .checked_add_signed(chrono::Duration::minutes(30))
+                .and_then(|moved| moved.duration_round(chrono::Duration::hours(1)).ok())
+                .map_or(LocalResult::None, |rounded| {
+                    tz.from_local_datetime(&rounded)
+                })
+        }
+        localized => localized,
+    }
+}
+
+/// Returns the first instant at which the local time in `tz` is at least `local`.
+fn first_instant_at_local(tz: Tz, local: chrono::NaiveDateTime) -> Result<DateTimeUtc> {
+    match localize_skipping_gap(tz, local) {
+        LocalResult::Single(localized) | LocalResult::Ambiguous(localized, _) => {
+            Ok(localized.into())
+        }
+        LocalResult::None => Err(Error::DateTimeConversionError),
+    }
+}
+
+fn round_to_calendar_unit(
+    datetime: chrono::NaiveDateTime,
+    unit: CalendarUnit,
+) -> Result<chrono::NaiveDateTime> {
+    let floor = truncate_to_calendar_unit(datetime, unit);
+    let ceil = add_calendar_units(floor, unit, 1)?;
+    if datetime - floor < ceil - datetime {
+        Ok(floor)
+    } else {
+        Ok(ceil)
+    }
+}
+
+fn timestamp_from_chrono(value: chrono::NaiveDateTime) -> Result<i64> {
+    clamp_leap_second(value)
+        .timestamp_nanos_opt()
+        .ok_or(Error::DateTimeConversionError)
+}
+
+fn positive_span(duration: Duration) -> Result<i64> {
+    let span = duration.nanoseconds();
+    if span > 0 {
+        Ok(span)
+    } else {
+        Err(Error::ValueError(format!(
+            "duration has to be positive but is {span} ns."
+        )))
+    }
 }
 
 fn get_unit_multiplier(unit: &str) -> Result<i64, Error> {
@@ -147,24 +353,13 @@ impl DateTimeNaive {
 
     pub fn to_utc_from_timezone(&self, timezone: &str) -> Result<DateTimeUtc> {
         if let Ok(tz) = timezone.parse::<Tz>() {
-            let naive_local = self.as_chrono_datetime();
-            let localized = tz.from_local_datetime(&naive_local);
-            match localized {
+            // Ambiguous times are resolved to the later instant, unlike calendar
+            // boundaries, which start at the first instant of their local beginning.
+            match localize_skipping_gap(tz, self.as_chrono_datetime()) {
                 LocalResult::Single(localized) | LocalResult::Ambiguous(_, localized) => {
                     Ok(localized.into())
                 }
-                LocalResult::None => {
-                    // This NaiveDateTime doesn't exist in a given timezone.
-                    // We try getting a first date after this.
-                    let moved = naive_local + chrono::Duration::minutes(30);
-                    let rounded = moved.duration_round(chrono::Duration::hours(1)).unwrap();
-                    let localized = tz.from_local_datetime(&rounded);
-                    if let LocalResult::Single(localized) = localized {
-                        Ok(localized.into())
-                    } else {
-                        Err(Error::DateTimeConversionError)
-                    }
-                }
+                LocalResult::None => Err(Error::DateTimeConversionError),
             }
         } else {
             Err(Error::ParseError(format!(
@@ -173,33 +368,124 @@ impl DateTimeNaive {
         }
     }
 
+    pub fn round(&self, duration: Duration) -> Result<DateTimeNaive> {
+        Ok(Self::new(self.get_rounded_timestamp(duration)?))
+    }
+
+    pub fn truncate(&self, duration: Duration) -> Result<DateTimeNaive> {
+        Ok(Self::new(self.get_truncated_timestamp(duration)?))
+    }
+
+    pub fn truncate_to_calendar_unit(&self, unit: CalendarUnit) -> Result<DateTimeNaive> {
+        let truncated = truncate_to_calendar_unit(self.as_chrono_datetime(), unit);
+        Ok(Self::new(timestamp_from_chrono(truncated)?))
+    }
+
+    pub fn round_to_calendar_unit(&self, unit: CalendarUnit) -> Result<DateTimeNaive> {
+        let rounded = round_to_calendar_unit(self.as_chrono_datetime(), unit)?;
+        Ok(Self::new(timestamp_from_chrono(rounded)?))
+    }
+
+    pub fn add_calendar_units(&self, unit: CalendarUnit, count: i64) -> Result<DateTimeNaive> {
+        let shifted = add_calendar_units(self.as_chrono_datetime(), unit, count)?;
+        Ok(Self::new(timestamp_from_chrono(shifted)?))
+    }
+
+    pub fn checked_add(self, other: Duration) -> Result<Self> {
+        self.timestamp
+            .checked_add(other.duration)
+            .map(Self::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
+    pub fn checked_sub(self, other: Duration) -> Result<Self> {
+        self.timestamp
+            .checked_sub(other.duration)
+            .map(Self::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
+    pub fn checked_duration_since(self, other: Self) -> Result<Duration> {
+        self.timestamp
+            .checked_sub(other.timestamp)
+            .map(Duration::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
     #[must_use]
-    pub fn round(&self, duration: Duration) -> DateTimeNaive {
-        Self::new(self.get_rounded_timestamp(duration))
+    pub fn saturating_add(self, other: Duration) -> Self {
+        Self::new(self.timestamp.saturating_add(other.duration))
     }
 
     #[must_use]
-    pub fn truncate(&self, duration: Duration) -> DateTimeNaive {
-        Self::new(self.get_truncated_timestamp(duration))
+    pub fn saturating_sub(self, other: Duration) -> Self {
+        Self::new(self.timestamp.saturating_sub(other.duration))
     }
 
     pub fn from_timestamp(timestamp: i64, unit: &str) -> Result<Self> {
         let mult = get_unit_multiplier(unit)?;
-        Ok(Self::new(mult * timestamp))
+        let duration = Duration::new(mult).checked_mul(timestamp)?;
+        Ok(Self::new(duration.nanoseconds()))
     }
 
     #[allow(clippy::cast_precision_loss)]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn from_timestamp_f64(timestamp: f64, unit: &str) -> Result<Self> {
-        let mult = get_unit_multiplier(unit)? as f64;
-        Ok(Self::new((mult * timestamp) as i64))
+        let mult = get_unit_multiplier(unit)?;
+        let duration = Duration::new(mult).checked_mul_f64(timestamp)?;
+        Ok(Self::new(duration.nanoseconds()))
+    }
+
+    pub fn from_excel_serial(serial: f64) -> Result<Self> {
+        if serial < 0.0 {
+            return Err(Error::ValueError(format!(
+                "Excel serial date has to be non-negative but is {serial}."
+            )));
+        }
+        let days = if serial < EXCEL_FIRST_CORRECT_SERIAL - 1.0 {
+            serial - UNIX_EPOCH_EXCEL_SERIAL + 1.0
+        } else if serial < EXCEL_FIRST_CORRECT_SERIAL {
+            return Err(Error::ValueError(format!(
+                "Excel serial date {serial} refers to 1900-02-29 which does not exist."
+            )));
+        } else {
+            serial - UNIX_EPOCH_EXCEL_SERIAL
+        };
+        Ok(Self::new(days_since_epoch_to_timestamp(days)?))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn excel_serial(&self) -> f64 {
+        let serial = self.timestamp as f64 / NANOSECONDS_IN_DAY as f64 + UNIX_EPOCH_EXCEL_SERIAL;
+        if serial < EXCEL_FIRST_CORRECT_SERIAL {
+            serial - 1.0
+        } else {
+            serial
+        }
+    }
+
+    pub fn from_julian_day(julian_day: f64) -> Result<Self> {
+        Ok(Self::new(days_since_epoch_to_timestamp(
+            julian_day - UNIX_EPOCH_JULIAN_DAY,
+        )?))
+    }
+}
+
+// chrono represents a leap second (e.g. `23:59:60.5`) as a nanosecond value
+// exceeding one second, which would make it collide with the start of the next
+// minute. We clamp it to the last nanosecond of the preceding second instead,
+// so that the order of events around the leap second is preserved.
+fn clamp_leap_second(value: chrono::NaiveDateTime) -> chrono::NaiveDateTime {
+    if value.nanosecond() >= 1_000_000_000 {
+        value.with_nanosecond(999_999_999).unwrap()
+    } else {
+        value
     }
 }
 
 impl From<chrono::NaiveDateTime> for DateTimeNaive {
     fn from(value: chrono::NaiveDateTime) -> Self {
         Self {
-            timestamp: value.timestamp_nanos_opt().unwrap(),
+            timestamp: clamp_leap_second(value).timestamp_nanos_opt().unwrap(),
         }
     }
 }
@@ -270,12 +556,27 @@ impl DateTimeUtc {
         }
     }
 
-    pub fn to_naive_in_timezone(&self, timezone: &str) -> Result<DateTimeNaive> {
+    pub fn parse_rfc3339(date_string: &str) -> Result<Self> {
+        match chrono::DateTime::parse_from_rfc3339(date_string) {
+            Ok(datetime) => Ok(datetime.into()),
+            Err(_) => Err(Error::ParseError(format!(
+                "Cannot parse date: {date_string} as RFC 3339."
+            ))),
+        }
+    }
+
+    pub fn parse_rfc2822(date_string: &str) -> Result<Self> {
+        match chrono::DateTime::parse_from_rfc2822(date_string) {
+            Ok(datetime) => Ok(datetime.into()),
+            Err(_) => Err(Error::ParseError(format!(
+                "Cannot parse date: {date_string} as RFC 2822."
+            ))),
+        }
+    }
+
+    fn in_timezone(self, timezone: &str) -> Result<chrono::DateTime<Tz>> {
         if let Ok(tz) = timezone.parse::<Tz>() {
-            let naive_utc = self.as_chrono_datetime();
-            let localized = tz.from_utc_datetime(&naive_utc);
-            let naive_local = localized.naive_local();
-            Ok(naive_local.into())
+            Ok(tz.from_utc_datetime(&self.as_chrono_datetime()))
         } else {
             Err(Error::ParseError(format!(
                 "Cannot parse time zone: {timezone}."
@@ -283,21 +584,134 @@ impl DateTimeUtc {
         }
     }
 
+    pub fn to_naive_in_timezone(&self, timezone: &str) -> Result<DateTimeNaive> {
+        Ok(self.in_timezone(timezone)?.naive_local().into())
+    }
+
+    pub fn offset_seconds(&self, timezone: &str) -> Result<i64> {
+        let localized = self.in_timezone(timezone)?;
+        Ok(localized.offset().fix().local_minus_utc().into())
+    }
+
+    pub fn strftime_in_timezone(&self, format: &str, timezone: &str) -> Result<String> {
+        Ok(self.in_timezone(timezone)?.format(format).to_string())
+    }
+
+    pub fn round(&self, duration: Duration) -> Result<DateTimeUtc> {
+        Ok(Self::new(self.get_rounded_timestamp(duration)?))
+    }
+
+    pub fn truncate(&self, duration: Duration) -> Result<DateTimeUtc> {
+        Ok(Self::new(self.get_truncated_timestamp(duration)?))
+    }
+
+    pub fn truncate_to_calendar_unit(&self, unit: CalendarUnit) -> Result<DateTimeUtc> {
+        let truncated = truncate_to_calendar_unit(self.as_chrono_datetime(), unit);
+        Ok(Self::new(timestamp_from_chrono(truncated)?))
+    }
+
+    pub fn round_to_calendar_unit(&self, unit: CalendarUnit) -> Result<DateTimeUtc> {
+        let rounded = round_to_calendar_unit(self.as_chrono_datetime(), unit)?;
+        Ok(Self::new(timestamp_from_chrono(rounded)?))
+    }
+
+    pub fn add_calendar_units(&self, unit: CalendarUnit, count: i64) -> Result<DateTimeUtc> {
+        let shifted = add_calendar_units(self.as_chrono_datetime(), unit, count)?;
+        Ok(Self::new(timestamp_from_chrono(shifted)?))
+    }
+
+    /// Truncates to the beginning of a calendar unit as seen in a given timezone.
+    /// Hours are aligned to the local offset, so around DST changes an hour is never
+    /// shortened or lengthened. Days and longer units start at the first instant
+    /// of their local beginning, so they can be shorter or longer than usual.
+    pub fn truncate_to_calendar_unit_in_timezone(
+        self,
+        unit: CalendarUnit,
+        timezone: &str,
+    ) -> Result<DateTimeUtc> {
+        let localized = self.in_timezone(timezone)?;
+        let local = localized.naive_local();
+        let truncated = truncate_to_calendar_unit(local, unit);
+        if unit == CalendarUnit::Hour {
+            let offset = Duration::new(timestamp_from_chrono(local)? - self.timestamp);
+            Ok(DateTimeUtc::new(timestamp_from_chrono(truncated)?).checked_sub(offset)?)
+        } else {
+            first_instant_at_local(localized.timezone(), truncated)
+        }
+    }
+
+    /// Rounds to the nearest beginning of a calendar unit as seen in a given timezone.
+    /// The distances to both beginnings are measured in absolute time.
+    pub fn round_to_calendar_unit_in_timezone(
+        self,
+        unit: CalendarUnit,
+        timezone: &str,
+    ) -> Result<DateTimeUtc> {
+        let floor = self.truncate_to_calendar_unit_in_timezone(unit, timezone)?;
+        let ceil = floor
+            .add_calendar_units_in_timezone(unit, 1, timezone)?
+            .truncate_to_calendar_unit_in_timezone(unit, timezone)?;
+        if self.checked_duration_since(floor)? < ceil.checked_duration_since(self)? {
+            Ok(floor)
+        } else {
+            Ok(ceil)
+        }
+    }
+
+    /// Shifts by a number of calendar units as seen in a given timezone.
+    /// Hours are always absolute, longer units keep the local time unchanged.
+    pub fn add_calendar_units_in_timezone(
+        self,
+        unit: CalendarUnit,
+        count: i64,
+        timezone: &str,
+    ) -> Result<DateTimeUtc> {
+        let localized = self.in_timezone(timezone)?;
+        if unit == CalendarUnit::Hour {
+            return self.add_calendar_units(unit, count);
+        }
+        let shifted = add_calendar_units(localized.naive_local(), unit, count)?;
+        first_instant_at_local(localized.timezone(), shifted)
+    }
+
+    pub fn checked_add(self, other: Duration) -> Result<Self> {
+        self.timestamp
+            .checked_add(other.duration)
+            .map(Self::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
+    pub fn checked_sub(self, other: Duration) -> Result<Self> {
+        self.timestamp
+            .checked_sub(other.duration)
+            .map(Self::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
+    pub fn checked_duration_since(self, other: Self) -> Result<Duration> {
+        self.timestamp
+            .checked_sub(other.timestamp)
+            .map(Duration::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
     #[must_use]
-    pub fn round(&self, duration: Duration) -> DateTimeUtc {
-        Self::new(self.get_rounded_timestamp(duration))
+    pub fn saturating_add(self, other: Duration) -> Self {
+        Self::new(self.timestamp.saturating_add(other.duration))
     }
 
     #[must_use]
-    pub fn truncate(&self, duration: Duration) -> DateTimeUtc {
-        Self::new(self.get_truncated_timestamp(duration))
+    pub fn saturating_sub(self, other: Duration) -> Self {
+        Self::new(self.timestamp.saturating_sub(other.duration))
     }
 }
 
 impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for DateTimeUtc {
     fn from(value: chrono::DateTime<Tz>) -> Self {
         Self {
-            timestamp: value.naive_utc().timestamp_nanos_opt().unwrap(),
+            timestamp: clamp_leap_second(value.naive_utc())
+                .timestamp_nanos_opt()
+                .unwrap(),
         }
     }
 }
@@ -410,9 +824,70 @@ impl Duration {
         if other == 0 {
             Err(Error::DivisionByZero)
         } else {
-            Ok(Self::new(self.duration / other))
+            self.duration
+                .checked_div(other)
+                .map(Self::new)
+                .ok_or(Error::DateTimeOverflow)
         }
     }
+
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_precision_loss)]
+    fn from_f64_nanoseconds(duration: f64) -> Result<Self> {
+        if duration.is_finite() && duration >= i64::MIN as f64 && duration < i64::MAX as f64 {
+            Ok(Self::new(duration as i64))
+        } else {
+            Err(Error::DateTimeOverflow)
+        }
+    }
+
+    pub fn checked_neg(self) -> Result<Self> {
+        self.duration
+            .checked_neg()
+            .map(Self::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        self.duration
+            .checked_add(other.duration)
+            .map(Self::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        self.duration
+            .checked_sub(other.duration)
+            .map(Self::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
+    pub fn checked_mul(self, other: i64) -> Result<Self> {
+        self.duration
+            .checked_mul(other)
+            .map(Self::new)
+            .ok_or(Error::DateTimeOverflow)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn checked_mul_f64(self, other: f64) -> Result<Self> {
+        Self::from_f64_nanoseconds(self.duration as f64 * other)
+    }
+
+    #[must_use]
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self::new(self.duration.saturating_add(other.duration))
+    }
+
+    #[must_use]
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self::new(self.duration.saturating_sub(other.duration))
+    }
+
+    #[must_use]
+    pub fn saturating_mul(self, other: i64) -> Self {
+        Self::new(self.duration.saturating_mul(other))
+    }
 }
 
 impl Neg for Duration {
@@ -473,6 +948,8 @@ impl Div for Duration {
     fn div(self, other: Self) -> Self::Output {
         if other.duration == 0 {
             Err(Error::DivisionByZero)
+        } else if self.duration == i64::MIN && other.duration == -1 {
+            Err(Error::DateTimeOverflow)
         } else {
             Ok(Integer::div_floor(&self.duration, &other.duration))
         }
@@ -485,6 +962,8 @@ impl Div<i64> for Duration {
     fn div(self, other: i64) -> Self::Output {
         if other == 0 {
             Err(Error::DivisionByZero)
+        } else if self.duration == i64::MIN && other == -1 {
+            Err(Error::DateTimeOverflow)
         } else {
             Ok(Duration {
                 duration: Integer::div_floor(&self.duration, &other),
@@ -496,15 +975,12 @@ impl Div<i64> for Duration {
 impl Div<f64> for Duration {
     type Output = Result<Duration>;
 
-    #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_precision_loss)]
     fn div(self, other: f64) -> Self::Output {
         if other == 0.0 {
             Err(Error::DivisionByZero)
         } else {
-            Ok(Duration {
-                duration: (self.duration as f64 / other) as i64,
-            })
+            Duration::from_f64_nanoseconds(self.duration as f64 / other)
         }
     }
 }
@@ -515,6 +991,8 @@ impl Rem for Duration {
     fn rem(self, other: Self) -> Self::Output {
         if other.duration == 0 {
             Err(Error::DivisionByZero)
+        } else if other.duration == -1 {
+            Ok(Duration { duration: 0 })
         } else {
             Ok(Duration {
                 duration: Integer::mod_floor(&self.duration, &other.duration),
@@ -2,13 +2,20 @@
 
 #![allow(clippy::non_canonical_partial_ord_impl)] // False positive with Derivative
 
+use std::cmp::Ordering;
+use std::env;
 use std::fmt::{self, Debug, Display};
+use std::hash::Hasher as _;
 use std::mem::{align_of, size_of};
 use std::ops::Deref;
 use std::sync::Arc;
 
 use super::error::{DynError, DynResult};
+use super::interning::intern;
+use super::shared_bytes::SharedBytes;
+use super::sparse_matrix::SparseMatrix;
 use super::time::{DateTime, DateTimeNaive, DateTimeUtc, Duration};
+use super::value_encoding;
 use super::Error;
 
 use arcstr::ArcStr;
@@ -16,12 +23,14 @@ use cfg_if::cfg_if;
 use derivative::Derivative;
 use itertools::Itertools as _;
 use ndarray::ArrayD;
+use once_cell::sync::Lazy;
 use ordered_float::OrderedFloat;
 use rand::Rng;
 use serde::de::Visitor;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value as JsonValue;
-use xxhash_rust::xxh3::Xxh3 as Hasher;
+use siphasher::sip128::{Hasher128 as _, SipHasher24};
+use xxhash_rust::xxh3::{xxh3_64_with_seed, Xxh3};
 
 const BASE32_ALPHABET: base32::Alphabet = base32::Alphabet::Crockford;
 
@@ -35,13 +44,135 @@ cfg_if! {
     }
 }
 
+/// The width of the keys, set at build time with the `yolo-id64` and `yolo-id32` features.
+/// The keys are hashes, so a table of `n` rows has two rows with the same key, which are
+/// then merged, with the probability of about `n² / 2^(KEY_BITS + 1)`.
+pub const KEY_BITS: u32 = KeyImpl::BITS;
+
+/// The hash function the keys are derived with. It's selected for the whole process, with
+/// environment variables, as all the processes of a computation, and all its runs resumed
+/// from the persisted state, have to derive the same keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyHashing {
+    /// xxh3, the default. It's the fastest, but the inputs can be crafted to get the same
+    /// keys, merging their rows, or to make the states of the operators slow to update.
+    Xxh3,
+    /// SipHash-2-4 keyed with a seed, so that the inputs can't be crafted to get the same
+    /// keys without knowing it. It's a few times slower than xxh3.
+    SipHash {
+        seed: u128,
+        /// Whether the seed was generated for this run, so the keys differ between the runs.
+        per_run_seed: bool,
+    },
+}
+
+static KEY_HASHING: Lazy<Result<KeyHashing, String>> = Lazy::new(KeyHashing::from_env);
+
+impl KeyHashing {
+    fn from_env() -> Result<Self, String> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// Reads the hashing from the `PATHWAY_KEY_HASHING` variable, `xxh3` or `siphash`, and
+    /// the seed of SipHash from `PATHWAY_KEY_HASHING_SEED`, getting the variables with
+    /// `var`. The seed is secret, so it's never derived from the other settings, which may
+    /// be guessed. Without it, a seed is generated randomly if a single process runs the
+    /// computation, and the hashing is rejected otherwise, as the processes have to derive
+    /// the same keys.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let hashing = var("PATHWAY_KEY_HASHING").unwrap_or_default();
+        match hashing.to_lowercase().as_str() {
+            "" | "xxh3" => Ok(Self::Xxh3),
+            "siphash" => {
+                if let Some(seed) = var("PATHWAY_KEY_HASHING_SEED") {
+                    let seed = seed.parse().map_err(|err| {
+                        format!("Couldn't parse the value of PATHWAY_KEY_HASHING_SEED: {err}")
+                    })?;
+                    return Ok(Self::SipHash {
+                        seed,
+                        per_run_seed: false,
+                    });
+                }
+                if var("PATHWAY_PROCESSES").is_some_and(|processes| processes != "1") {
+                    return Err("PATHWAY_KEY_HASHING_SEED has to be set to the same secret \
+                        number for all the processes"
+                        .to_string());
+                }
+                Ok(Self::SipHash {
+                    seed: rand::thread_rng().gen(),
+                    per_run_seed: true,
+                })
+            }
+            _ => Err(format!(
+                "Unknown key hashing {hashing:?} in PATHWAY_KEY_HASHING, \
+                expected \"xxh3\" or \"siphash\""
+            )),
+        }
+    }
+
+    /// Returns the hashing the keys are derived with by this process.
+    pub fn configured() -> Result<Self, String> {
+        KEY_HASHING.clone()
+    }
+
+    fn current() -> Self {
+        match &*KEY_HASHING {
+            Ok(hashing) => *hashing,
+            Err(message) => panic!("{message}"),
+        }
+    }
+}
+
+// the hashers live on the stack only for the time of hashing a value
+#[allow(clippy::large_enum_variant)]
+enum HasherImpl {
+    Xxh3(Xxh3),
+    SipHash(SipHasher24),
+}
+
+/// The hasher deriving the keys with the hash function of [`KeyHashing`], by default
+/// the one configured for the process.
+pub struct Hasher(HasherImpl);
+
+impl Hasher {
+    pub fn new(hashing: KeyHashing) -> Self {
+        match hashing {
+            KeyHashing::Xxh3 => Self(HasherImpl::Xxh3(Xxh3::default())),
+            #[allow(clippy::cast_possible_truncation)]
+            KeyHashing::SipHash { seed, .. } => Self(HasherImpl::SipHash(
+                SipHasher24::new_with_keys(seed as u64, (seed >> 64) as u64),
+            )),
+        }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        match &mut self.0 {
+            HasherImpl::Xxh3(hasher) => hasher.update(bytes),
+            HasherImpl::SipHash(hasher) => hasher.write(bytes),
+        }
+    }
+
+    pub fn digest128(&self) -> u128 {
+        match &self.0 {
+            HasherImpl::Xxh3(hasher) => hasher.digest128(),
+            HasherImpl::SipHash(hasher) => hasher.finish128().as_u128(),
+        }
+    }
+}
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Self::new(KeyHashing::current())
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Key(pub KeyImpl);
 
 impl Key {
     const FOR_EMPTY_TUPLE: Self = Self(0x40_10_8D_33_B7); // PWSRT42
 
-    pub(crate) fn from_hasher(hasher: &Hasher) -> Self {
+    pub fn from_hasher(hasher: &Hasher) -> Self {
         cfg_if! {
             if #[cfg(feature="strong-hash")] {
                 let mut res = [0; KEY_BYTES];
@@ -76,6 +207,23 @@ impl Key {
     pub fn salted_with(self, seed: KeyImpl) -> Self {
         Self(self.0 ^ seed)
     }
+
+    /// Maps the key to a number in `[0, 1)`, uniformly distributed over keys.
+    /// Numbers computed with different seeds are independent.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn sample_rank(self, seed: i64) -> f64 {
+        let hash = xxh3_64_with_seed(
+            &self.0.to_le_bytes(),
+            u64::from_le_bytes(seed.to_le_bytes()),
+        );
+        (hash >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Parses a key in the form it's displayed in, e.g. `^X1MXHYYG4YM0DB900V28XN5T4W`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let bytes = base32::decode(BASE32_ALPHABET, text.strip_prefix('^')?)?;
+        Some(Self(KeyImpl::from_le_bytes(bytes.try_into().ok()?)))
+    }
 }
 
 impl Display for Key {
@@ -179,7 +327,56 @@ where
     d.deserialize_str(JsonVisitor)
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+struct InternedStrVisitor;
+
+impl<'de> Visitor<'de> for InternedStrVisitor {
+    type Value = ArcStr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(intern(v))
+    }
+}
+
+fn deserialize_interned<'de, D>(d: D) -> Result<ArcStr, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    d.deserialize_str(InternedStrVisitor)
+}
+
+/// A Python object carried by the engine in the form given by the serializer registered
+/// for its type in Python. It's deserialized, with the serializer of the same name, each
+/// time it's passed back to Python, so the serialized form has to stay stable for as long
+/// as it's persisted.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SerializedObject {
+    pub serializer: ArcStr,
+    pub bytes: Arc<[u8]>,
+}
+
+impl SerializedObject {
+    pub fn new(serializer: &str, bytes: &[u8]) -> Self {
+        Self {
+            serializer: serializer.into(),
+            bytes: bytes.into(),
+        }
+    }
+}
+
+impl Display for SerializedObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<object serialized with {:?}>", self.serializer.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Value {
     None,
     Bool(bool),
@@ -187,10 +384,55 @@ pub enum Value {
     Float(OrderedFloat<f64>),
     Pointer(Key),
     String(ArcStr),
-    Bytes(Arc<[u8]>),
+    Bytes(SharedBytes),
     Tuple(Arc<[Self]>),
     IntArray(Handle<ArrayD<i64>>),
     FloatArray(Handle<ArrayD<f64>>),
+    Float32Array(Handle<ArrayD<f32>>),
+    SparseMatrix(Handle<SparseMatrix>),
+    DateTimeNaive(DateTimeNaive),
+    DateTimeUtc(DateTimeUtc),
+    Duration(Duration),
+    Json(Handle<JsonValue>),
+    SerializedObject(Handle<SerializedObject>),
+}
+
+/// A value is serialized as bytes holding its compact encoding, without the version and
+/// the count of the values encoded together, so that a row exchanged between the workers
+/// as a tuple takes a single header of the serializer and a single dictionary of strings.
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&value_encoding::encode_value(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        value_encoding::deserialize_value(deserializer)
+    }
+}
+
+/// A value in the form it was serialized in before the compact encoding, kept to read
+/// the snapshots saved then.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum LegacyValue {
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(OrderedFloat<f64>),
+    Pointer(Key),
+    #[serde(deserialize_with = "deserialize_interned")]
+    String(ArcStr),
+    Bytes(SharedBytes),
+    Tuple(Vec<Self>),
+    IntArray(Handle<ArrayD<i64>>),
+    FloatArray(Handle<ArrayD<f64>>),
     DateTimeNaive(DateTimeNaive),
     DateTimeUtc(DateTimeUtc),
     Duration(Duration),
@@ -199,12 +441,169 @@ pub enum Value {
         deserialize_with = "deserialize_json"
     )]
     Json(Handle<JsonValue>),
+    SerializedObject(Handle<SerializedObject>),
+}
+
+impl From<LegacyValue> for Value {
+    fn from(value: LegacyValue) -> Self {
+        match value {
+            LegacyValue::None => Self::None,
+            LegacyValue::Bool(b) => Self::Bool(b),
+            LegacyValue::Int(i) => Self::Int(i),
+            LegacyValue::Float(f) => Self::Float(f),
+            LegacyValue::Pointer(key) => Self::Pointer(key),
+            LegacyValue::String(string) => Self::String(string),
+            LegacyValue::Bytes(bytes) => Self::Bytes(bytes),
+            LegacyValue::Tuple(values) => Self::Tuple(values.into_iter().map(Self::from).collect()),
+            LegacyValue::IntArray(array) => Self::IntArray(array),
+            LegacyValue::FloatArray(array) => Self::FloatArray(array),
+            LegacyValue::DateTimeNaive(date_time) => Self::DateTimeNaive(date_time),
+            LegacyValue::DateTimeUtc(date_time) => Self::DateTimeUtc(date_time),
+            LegacyValue::Duration(duration) => Self::Duration(duration),
+            LegacyValue::Json(json) => Self::Json(json),
+            LegacyValue::SerializedObject(object) => Self::SerializedObject(object),
+        }
+    }
+}
+
+fn estimated_json_size(json: &JsonValue) -> usize {
+    let pointed_to_size = match json {
+        JsonValue::String(string) => string.len(),
+        JsonValue::Array(values) => values.iter().map(estimated_json_size).sum(),
+        JsonValue::Object(object) => object
+            .iter()
+            .map(|(key, value)| key.len() + estimated_json_size(value))
+            .sum(),
+        JsonValue::Null | JsonValue::Bool(_) | JsonValue::Number(_) => 0,
+    };
+    size_of::<JsonValue>() + pointed_to_size
 }
 
 const _: () = assert!(align_of::<Value>() <= 16);
 const _: () = assert!(size_of::<Value>() <= 32);
 
+/// Compares the exact values of an int and a float, with `NaN` greater than all ints.
+fn compare_int_to_float(int: i64, float: f64) -> Ordering {
+    // 2^63, the first float above all ints
+    const INT_END: f64 = 9_223_372_036_854_775_808.0;
+    if float.is_nan() || float >= INT_END {
+        Ordering::Less
+    } else if float < -INT_END {
+        Ordering::Greater
+    } else {
+        // the floats in the range of ints are truncated exactly
+        #[allow(clippy::cast_possible_truncation)]
+        let truncated = float.trunc() as i64;
+        int.cmp(&truncated).then_with(|| {
+            if float > float.trunc() {
+                Ordering::Less
+            } else if float < float.trunc() {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+    }
+}
+
 impl Value {
+    /// The position of the type of the value in the order of the values of different types.
+    fn type_rank(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Bool(_) => 1,
+            Self::Int(_) | Self::Float(_) => 2,
+            Self::Pointer(_) => 3,
+            Self::String(_) => 4,
+            Self::Bytes(_) => 5,
+            Self::Tuple(_) => 6,
+            Self::IntArray(_) => 7,
+            Self::FloatArray(_) => 8,
+            Self::Float32Array(_) => 9,
+            Self::SparseMatrix(_) => 10,
+            Self::DateTimeNaive(_) => 11,
+            Self::DateTimeUtc(_) => 12,
+            Self::Duration(_) => 13,
+            Self::Json(_) => 14,
+            Self::SerializedObject(_) => 15,
+        }
+    }
+}
+
+/// The total order of the values, by which they are sorted and chosen by the `min`, `max`,
+/// `argmin` and `argmax` reducers, stable between the versions:
+/// - the values of different types are ordered by the type: `None`, `Bool`, the numbers,
+///   `Pointer`, `String`, `Bytes`, `Tuple`, `IntArray`, `FloatArray`, `Float32Array`,
+///   `SparseMatrix`, `DateTimeNaive`, `DateTimeUtc`, `Duration`, `Json` and
+///   `SerializedObject`,
+/// - the ints and the floats are ordered by their exact values, with an int before
+///   the float equal to it and `NaN` after all the other numbers,
+/// - the strings and the bytes are ordered lexicographically by their bytes, and the tuples
+///   by their elements, in this order, with a tuple before the longer ones it's a prefix of,
+/// - the arrays, the sparse matrices, the JSON values and the serialized objects are ordered by the hashes
+///   of their contents, which is deterministic, but unrelated to the contents.
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::None, Self::None) => Ordering::Equal,
+            (Self::Bool(lhs), Self::Bool(rhs)) => lhs.cmp(rhs),
+            (Self::Int(lhs), Self::Int(rhs)) => lhs.cmp(rhs),
+            (Self::Float(lhs), Self::Float(rhs)) => lhs.cmp(rhs),
+            (Self::Int(lhs), Self::Float(rhs)) => {
+                compare_int_to_float(*lhs, rhs.0).then(Ordering::Less)
+            }
+            (Self::Float(lhs), Self::Int(rhs)) => compare_int_to_float(*rhs, lhs.0)
+                .reverse()
+                .then(Ordering::Greater),
+            (Self::Pointer(lhs), Self::Pointer(rhs)) => lhs.cmp(rhs),
+            (Self::String(lhs), Self::String(rhs)) => lhs.cmp(rhs),
+            (Self::Bytes(lhs), Self::Bytes(rhs)) => lhs.cmp(rhs),
+            (Self::Tuple(lhs), Self::Tuple(rhs)) => lhs.cmp(rhs),
+            (Self::IntArray(lhs), Self::IntArray(rhs)) => lhs.cmp(rhs),
+            (Self::FloatArray(lhs), Self::FloatArray(rhs)) => lhs.cmp(rhs),
+            (Self::Float32Array(lhs), Self::Float32Array(rhs)) => lhs.cmp(rhs),
+            (Self::SparseMatrix(lhs), Self::SparseMatrix(rhs)) => lhs.cmp(rhs),
+            (Self::DateTimeNaive(lhs), Self::DateTimeNaive(rhs)) => lhs.cmp(rhs),
+            (Self::DateTimeUtc(lhs), Self::DateTimeUtc(rhs)) => lhs.cmp(rhs),
+            (Self::Duration(lhs), Self::Duration(rhs)) => lhs.cmp(rhs),
+            (Self::Json(lhs), Self::Json(rhs)) => lhs.cmp(rhs),
+            (Self::SerializedObject(lhs), Self::SerializedObject(rhs)) => lhs.cmp(rhs),
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Value {
+    /// Approximate number of bytes taken by the value, including the data it points to.
+    /// Data shared by multiple values is counted for each of them.
+    pub fn estimated_size(&self) -> usize {
+        let pointed_to_size = match self {
+            Self::String(string) => string.len(),
+            Self::Bytes(bytes) => bytes.len(),
+            Self::Tuple(values) => values.iter().map(Self::estimated_size).sum(),
+            Self::IntArray(array) => array.len() * size_of::<i64>(),
+            Self::FloatArray(array) => array.len() * size_of::<f64>(),
+            Self::Float32Array(array) => array.len() * size_of::<f32>(),
+            Self::SparseMatrix(matrix) => {
+                size_of::<SparseMatrix>()
+                    + matrix.indptr().len() * size_of::<usize>()
+                    + matrix.nnz() * (size_of::<usize>() + size_of::<f64>())
+            }
+            Self::Json(json) => estimated_json_size(json),
+            Self::SerializedObject(object) => {
+                size_of::<SerializedObject>() + object.serializer.len() + object.bytes.len()
+            }
+            _ => 0,
+        };
+        size_of::<Self>() + pointed_to_size
+    }
+
     pub fn from_isize(i: isize) -> Self {
         match i.try_into() {
             Ok(i) => Self::Int(i),
@@ -323,10 +722,13 @@ impl Display for Value {
             Self::Tuple(vals) => write!(fmt, "({})", vals.iter().format(", ")),
             Self::IntArray(array) => write!(fmt, "{array}"),
             Self::FloatArray(array) => write!(fmt, "{array}"),
+            Self::Float32Array(array) => write!(fmt, "{array}"),
+            Self::SparseMatrix(matrix) => write!(fmt, "{matrix}"),
             Self::DateTimeNaive(date_time) => write!(fmt, "{date_time}"),
             Self::DateTimeUtc(date_time) => write!(fmt, "{date_time}"),
             Self::Duration(duration) => write!(fmt, "{duration}"),
             Self::Json(json) => write!(fmt, "{json}"),
+            Self::SerializedObject(object) => write!(fmt, "{object}"),
         }
     }
 }
@@ -349,6 +751,12 @@ impl From<f64> for Value {
     }
 }
 
+impl From<f32> for Value {
+    fn from(f: f32) -> Self {
+        Self::Float(OrderedFloat(f.into()))
+    }
+}
+
 impl From<OrderedFloat<f64>> for Value {
     fn from(f: OrderedFloat<f64>) -> Self {
         Self::Float(f)
@@ -363,7 +771,7 @@ impl From<Key> for Value {
 
 impl From<&str> for Value {
     fn from(s: &str) -> Self {
-        Self::String(s.into())
+        Self::String(intern(s))
     }
 }
 
@@ -397,6 +805,18 @@ impl From<ArrayD<f64>> for Value {
     }
 }
 
+impl From<ArrayD<f32>> for Value {
+    fn from(a: ArrayD<f32>) -> Self {
+        Self::Float32Array(Handle::new(a))
+    }
+}
+
+impl From<SparseMatrix> for Value {
+    fn from(matrix: SparseMatrix) -> Self {
+        Self::SparseMatrix(Handle::new(matrix))
+    }
+}
+
 impl<T> From<Option<T>> for Value
 where
     T: Into<Value>,
@@ -433,6 +853,12 @@ impl From<JsonValue> for Value {
     }
 }
 
+impl From<SerializedObject> for Value {
+    fn from(object: SerializedObject) -> Self {
+        Self::SerializedObject(Handle::new(object))
+    }
+}
+
 // Please only append to this list, as the values here are used in hashing,
 // so changing them will result in changed IDs
 #[repr(u8)]
@@ -452,9 +878,12 @@ pub enum SimpleType {
     Duration,
     Bytes,
     Json,
+    SerializedObject,
+    Float32Array,
+    SparseMatrix,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Type {
     #[default]
     Any,
@@ -486,10 +915,13 @@ impl Value {
             Self::Tuple(_) => SimpleType::Tuple,
             Self::IntArray(_) => SimpleType::IntArray,
             Self::FloatArray(_) => SimpleType::FloatArray,
+            Self::Float32Array(_) => SimpleType::Float32Array,
+            Self::SparseMatrix(_) => SimpleType::SparseMatrix,
             Self::DateTimeNaive(_) => SimpleType::DateTimeNaive,
             Self::DateTimeUtc(_) => SimpleType::DateTimeUtc,
             Self::Duration(_) => SimpleType::Duration,
             Self::Json(_) => SimpleType::Json,
+            Self::SerializedObject(_) => SimpleType::SerializedObject,
         }
     }
 }
@@ -518,6 +950,12 @@ impl HashInto for f64 {
     }
 }
 
+impl HashInto for f32 {
+    fn hash_into(&self, hasher: &mut Hasher) {
+        f64::from(*self).hash_into(hasher);
+    }
+}
+
 impl HashInto for OrderedFloat<f64> {
     fn hash_into(&self, hasher: &mut Hasher) {
         self.0.hash_into(hasher);
@@ -596,6 +1034,17 @@ impl<T: HashInto> HashInto for ArrayD<T> {
     }
 }
 
+impl HashInto for SparseMatrix {
+    fn hash_into(&self, hasher: &mut Hasher) {
+        let (rows, columns) = self.shape();
+        rows.hash_into(hasher);
+        columns.hash_into(hasher);
+        self.indptr().hash_into(hasher);
+        self.indices().hash_into(hasher);
+        self.data().hash_into(hasher);
+    }
+}
+
 impl HashInto for DateTimeNaive {
     fn hash_into(&self, hasher: &mut Hasher) {
         self.timestamp().hash_into(hasher);
@@ -628,10 +1077,13 @@ impl HashInto for Value {
             Self::Tuple(vals) => vals.hash_into(hasher),
             Self::IntArray(handle) => handle.hash_into(hasher),
             Self::FloatArray(handle) => handle.hash_into(hasher),
+            Self::Float32Array(handle) => handle.hash_into(hasher),
+            Self::SparseMatrix(handle) => handle.hash_into(hasher),
             Self::DateTimeNaive(date_time) => date_time.hash_into(hasher),
             Self::DateTimeUtc(date_time) => date_time.hash_into(hasher),
             Self::Duration(duration) => duration.hash_into(hasher),
             Self::Json(json) => json.hash_into(hasher),
+            Self::SerializedObject(object) => object.hash_into(hasher),
         }
     }
 }
@@ -641,3 +1093,10 @@ impl HashInto for JsonValue {
         (*self).to_string().hash_into(hasher);
     }
 }
+
+impl HashInto for SerializedObject {
+    fn hash_into(&self, hasher: &mut Hasher) {
+        self.serializer.hash_into(hasher);
+        self.bytes.hash_into(hasher);
+    }
+}
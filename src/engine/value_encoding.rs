@@ -0,0 +1,509 @@
+// Copyright © 2024 Pathway
+
+//! The compact binary encoding of the values, in which they are stored in the snapshots and
+//! exchanged between the workers.
+//!
+//! The values encoded together, like the rows of the snapshots, start with the version of
+//! the format and their count. A single value, like the ones exchanged between the workers,
+//! is encoded without them, starting with its tag, so that it takes as little as it does in
+//! a row. The tags of the existing kinds of values stay the same across the versions, with
+//! the new kinds taking new tags.
+//!
+//! The integers, lengths and timestamps are varints, with the signed ones zigzag-encoded,
+//! so that the small ones take a byte or two, and the strings are dictionary-encoded: each
+//! distinct string is stored once, at its first occurrence, and referred to by its index
+//! later on, so that a row or a tuple repeating a string doesn't store it again.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::mem::size_of;
+use std::sync::Arc;
+
+use arcstr::ArcStr;
+use ndarray::{ArrayD, IxDyn};
+use ordered_float::OrderedFloat;
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::Deserializer;
+use serde_json::Value as JsonValue;
+
+use super::interning::intern;
+use super::sparse_matrix::SparseMatrix;
+use super::time::{DateTime, DateTimeNaive, DateTimeUtc, Duration};
+use super::value::{Key, KeyImpl, SerializedObject, Value};
+
+/// The version of the format. It has to be increased on every change of the encoding,
+/// keeping the decoding of the previous versions. The versions start at 1.
+pub const FORMAT_VERSION: u8 = 1;
+
+const TAG_NONE: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_FLOAT: u8 = 4;
+const TAG_POINTER: u8 = 5;
+const TAG_STRING: u8 = 6;
+const TAG_BYTES: u8 = 7;
+const TAG_TUPLE: u8 = 8;
+const TAG_INT_ARRAY: u8 = 9;
+const TAG_FLOAT_ARRAY: u8 = 10;
+const TAG_DATE_TIME_NAIVE: u8 = 11;
+const TAG_DATE_TIME_UTC: u8 = 12;
+const TAG_DURATION: u8 = 13;
+const TAG_JSON: u8 = 14;
+const TAG_SERIALIZED_OBJECT: u8 = 15;
+const TAG_FLOAT32_ARRAY: u8 = 16;
+const TAG_SPARSE_MATRIX: u8 = 17;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum DecodeError {
+    #[error("values encoded in format version {0}, out of the supported 1 to {FORMAT_VERSION}")]
+    UnsupportedVersion(u8),
+
+    #[error("encoded values end unexpectedly")]
+    UnexpectedEnd,
+
+    #[error("invalid tag {0} of an encoded value")]
+    InvalidTag(u8),
+
+    #[error("varint out of range")]
+    VarintOutOfRange,
+
+    #[error("reference to string {0} out of the dictionary")]
+    InvalidStringReference(u64),
+
+    #[error("encoded string is not valid UTF-8")]
+    InvalidUtf8,
+
+    #[error("invalid encoded JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error("shape of an encoded array doesn't match its elements")]
+    InvalidArrayShape,
+
+    #[error("column of an element of an encoded sparse matrix out of its bounds")]
+    InvalidSparseMatrix,
+
+    #[error("expected {expected} encoded values, got more")]
+    TrailingValues { expected: usize },
+}
+
+/// Encodes the values together, with their strings sharing a single dictionary.
+pub fn encode(values: &[Value]) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    encoder.bytes.push(FORMAT_VERSION);
+    encoder.varint(values.len() as u64);
+    for value in values {
+        encoder.value(value);
+    }
+    encoder.bytes
+}
+
+/// Encodes a single value, without the version and the count of [`encode`].
+pub fn encode_value(value: &Value) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    encoder.value(value);
+    encoder.bytes
+}
+
+/// Decodes the values encoded together with [`encode`].
+pub fn decode(bytes: &[u8]) -> Result<Vec<Value>, DecodeError> {
+    let mut decoder = Decoder::new(bytes);
+    let version = decoder.byte()?;
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+    let len = decoder.len()?;
+    let values = (0..len)
+        .map(|_| decoder.value())
+        .collect::<Result<Vec<_>, _>>()?;
+    decoder.finish(len)?;
+    Ok(values)
+}
+
+/// Decodes a single value encoded with [`encode_value`].
+pub fn decode_value(bytes: &[u8]) -> Result<Value, DecodeError> {
+    let mut decoder = Decoder::new(bytes);
+    let value = decoder.value()?;
+    decoder.finish(1)?;
+    Ok(value)
+}
+
+struct EncodedVisitor<F>(F);
+
+impl<'de, T, F> Visitor<'de> for EncodedVisitor<F>
+where
+    F: FnOnce(&[u8]) -> Result<T, DecodeError>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("bytes holding encoded values")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        (self.0)(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        (self.0)(&bytes).map_err(A::Error::custom)
+    }
+}
+
+/// Deserializes the values from the bytes holding their encoding, for the serialization
+/// that stores them with `serialize_bytes`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_bytes(EncodedVisitor(decode))
+}
+
+/// Deserializes a single value from the bytes holding its encoding by [`encode_value`].
+pub fn deserialize_value<'de, D>(deserializer: D) -> Result<Value, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_bytes(EncodedVisitor(decode_value))
+}
+
+#[derive(Default)]
+struct Encoder<'a> {
+    bytes: Vec<u8>,
+    strings: HashMap<&'a str, u64>,
+}
+
+impl<'a> Encoder<'a> {
+    #[allow(clippy::cast_possible_truncation)]
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn int(&mut self, value: i64) {
+        self.varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn len_prefixed(&mut self, bytes: &[u8]) {
+        self.varint(bytes.len() as u64);
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// A string seen before is stored as its index in the dictionary, with the lowest bit
+    /// set, and a new one as its length, with the lowest bit unset, followed by its bytes.
+    fn string(&mut self, string: &'a str) {
+        if let Some(&index) = self.strings.get(string) {
+            self.varint((index << 1) | 1);
+        } else {
+            self.strings.insert(string, self.strings.len() as u64);
+            self.varint((string.len() as u64) << 1);
+            self.bytes.extend_from_slice(string.as_bytes());
+        }
+    }
+
+    fn shape(&mut self, shape: &[usize]) {
+        self.varint(shape.len() as u64);
+        for &dimension in shape {
+            self.varint(dimension as u64);
+        }
+    }
+
+    /// A sparse matrix is stored as its shape and its rows, each as the number of its
+    /// elements followed by their columns and values.
+    fn sparse_matrix(&mut self, matrix: &SparseMatrix) {
+        let (rows, columns) = matrix.shape();
+        self.varint(rows as u64);
+        self.varint(columns as u64);
+        for row in 0..rows {
+            let elements = matrix.row(row);
+            self.varint(elements.len() as u64);
+            for (column, value) in elements {
+                self.varint(column as u64);
+                self.bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+
+    fn value(&mut self, value: &'a Value) {
+        match value {
+            Value::None => self.bytes.push(TAG_NONE),
+            Value::Bool(false) => self.bytes.push(TAG_FALSE),
+            Value::Bool(true) => self.bytes.push(TAG_TRUE),
+            Value::Int(i) => {
+                self.bytes.push(TAG_INT);
+                self.int(*i);
+            }
+            Value::Float(f) => {
+                self.bytes.push(TAG_FLOAT);
+                self.bytes.extend_from_slice(&f.to_le_bytes());
+            }
+            Value::Pointer(key) => {
+                self.bytes.push(TAG_POINTER);
+                self.bytes.extend_from_slice(&key.0.to_le_bytes());
+            }
+            Value::String(string) => {
+                self.bytes.push(TAG_STRING);
+                self.string(string);
+            }
+            Value::Bytes(bytes) => {
+                self.bytes.push(TAG_BYTES);
+                self.len_prefixed(bytes);
+            }
+            Value::Tuple(values) => {
+                self.bytes.push(TAG_TUPLE);
+                self.varint(values.len() as u64);
+                for value in values.iter() {
+                    self.value(value);
+                }
+            }
+            Value::IntArray(array) => {
+                self.bytes.push(TAG_INT_ARRAY);
+                self.shape(array.shape());
+                for &element in array.iter() {
+                    self.int(element);
+                }
+            }
+            Value::FloatArray(array) => {
+                self.bytes.push(TAG_FLOAT_ARRAY);
+                self.shape(array.shape());
+                for element in array.iter() {
+                    self.bytes.extend_from_slice(&element.to_le_bytes());
+                }
+            }
+            Value::Float32Array(array) => {
+                self.bytes.push(TAG_FLOAT32_ARRAY);
+                self.shape(array.shape());
+                for element in array.iter() {
+                    self.bytes.extend_from_slice(&element.to_le_bytes());
+                }
+            }
+            Value::SparseMatrix(matrix) => {
+                self.bytes.push(TAG_SPARSE_MATRIX);
+                self.sparse_matrix(matrix);
+            }
+            Value::DateTimeNaive(date_time) => {
+                self.bytes.push(TAG_DATE_TIME_NAIVE);
+                self.int(date_time.timestamp());
+            }
+            Value::DateTimeUtc(date_time) => {
+                self.bytes.push(TAG_DATE_TIME_UTC);
+                self.int(date_time.timestamp());
+            }
+            Value::Duration(duration) => {
+                self.bytes.push(TAG_DURATION);
+                self.int(duration.nanoseconds());
+            }
+            Value::Json(json) => {
+                self.bytes.push(TAG_JSON);
+                self.len_prefixed(json.to_string().as_bytes());
+            }
+            Value::SerializedObject(object) => {
+                self.bytes.push(TAG_SERIALIZED_OBJECT);
+                self.string(&object.serializer);
+                self.len_prefixed(&object.bytes);
+            }
+        }
+    }
+}
+
+struct Decoder<'a> {
+    input: &'a [u8],
+    strings: Vec<ArcStr>,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            strings: Vec::new(),
+        }
+    }
+
+    fn finish(&self, expected: usize) -> Result<(), DecodeError> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodeError::TrailingValues { expected })
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if len > self.input.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(taken)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            let bits = u64::from(byte & 0x7f);
+            if bits << shift >> shift != bits {
+                return Err(DecodeError::VarintOutOfRange);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::VarintOutOfRange)
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn int(&mut self) -> Result<i64, DecodeError> {
+        let zigzag = self.varint()?;
+        Ok((zigzag >> 1) as i64 ^ -((zigzag & 1) as i64))
+    }
+
+    /// Reads a length, which can't exceed the number of the remaining bytes, as each of
+    /// the counted bytes or values takes at least a byte.
+    fn len(&mut self) -> Result<usize, DecodeError> {
+        let len = self.varint()?;
+        match usize::try_from(len) {
+            Ok(len) if len <= self.input.len() => Ok(len),
+            _ => Err(DecodeError::UnexpectedEnd),
+        }
+    }
+
+    fn len_prefixed(&mut self) -> Result<&'a [u8], DecodeError> {
+        let len = self.len()?;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<ArcStr, DecodeError> {
+        let header = self.varint()?;
+        if header & 1 == 1 {
+            let index = header >> 1;
+            return usize::try_from(index)
+                .ok()
+                .and_then(|index| self.strings.get(index))
+                .cloned()
+                .ok_or(DecodeError::InvalidStringReference(index));
+        }
+        let len = usize::try_from(header >> 1).map_err(|_| DecodeError::UnexpectedEnd)?;
+        let bytes = self.take(len)?;
+        let string = intern(std::str::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8)?);
+        self.strings.push(string.clone());
+        Ok(string)
+    }
+
+    fn shape(&mut self) -> Result<Vec<usize>, DecodeError> {
+        let dimensions = self.len()?;
+        (0..dimensions)
+            .map(|_| usize::try_from(self.varint()?).map_err(|_| DecodeError::InvalidArrayShape))
+            .collect()
+    }
+
+    fn array<T>(
+        &mut self,
+        mut element: impl FnMut(&mut Self) -> Result<T, DecodeError>,
+    ) -> Result<ArrayD<T>, DecodeError> {
+        let shape = self.shape()?;
+        let len = shape
+            .iter()
+            .try_fold(1_usize, |len, &dimension| len.checked_mul(dimension))
+            .ok_or(DecodeError::InvalidArrayShape)?;
+        // each element takes at least a byte
+        if len > self.input.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let elements = (0..len)
+            .map(|_| element(self))
+            .collect::<Result<Vec<_>, _>>()?;
+        ArrayD::from_shape_vec(IxDyn(&shape), elements).map_err(|_| DecodeError::InvalidArrayShape)
+    }
+
+    fn sparse_matrix(&mut self) -> Result<SparseMatrix, DecodeError> {
+        let rows = self.len()?;
+        let columns =
+            usize::try_from(self.varint()?).map_err(|_| DecodeError::InvalidSparseMatrix)?;
+        let mut indptr = Vec::with_capacity(rows + 1);
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        indptr.push(0);
+        for _ in 0..rows {
+            let len = self.len()?;
+            for _ in 0..len {
+                let column = self.varint()?;
+                indices
+                    .push(usize::try_from(column).map_err(|_| DecodeError::InvalidSparseMatrix)?);
+                data.push(f64::from_le_bytes(self.take_array()?));
+            }
+            indptr.push(indices.len());
+        }
+        SparseMatrix::new((rows, columns), indptr, indices, data)
+            .map_err(|_| DecodeError::InvalidSparseMatrix)
+    }
+
+    fn value(&mut self) -> Result<Value, DecodeError> {
+        let value = match self.byte()? {
+            TAG_NONE => Value::None,
+            TAG_FALSE => Value::Bool(false),
+            TAG_TRUE => Value::Bool(true),
+            TAG_INT => Value::Int(self.int()?),
+            TAG_FLOAT => Value::Float(OrderedFloat(f64::from_le_bytes(self.take_array()?))),
+            TAG_POINTER => Value::Pointer(Key(KeyImpl::from_le_bytes(
+                self.take_array::<{ size_of::<KeyImpl>() }>()?,
+            ))),
+            TAG_STRING => Value::String(self.string()?),
+            TAG_BYTES => Value::Bytes(self.len_prefixed()?.into()),
+            TAG_TUPLE => {
+                let len = self.len()?;
+                let values = (0..len)
+                    .map(|_| self.value())
+                    .collect::<Result<Arc<[_]>, _>>()?;
+                Value::Tuple(values)
+            }
+            TAG_INT_ARRAY => Value::from(self.array(Self::int)?),
+            TAG_FLOAT_ARRAY => {
+                Value::from(self.array(|decoder| Ok(f64::from_le_bytes(decoder.take_array()?)))?)
+            }
+            TAG_FLOAT32_ARRAY => {
+                Value::from(self.array(|decoder| Ok(f32::from_le_bytes(decoder.take_array()?)))?)
+            }
+            TAG_SPARSE_MATRIX => Value::from(self.sparse_matrix()?),
+            TAG_DATE_TIME_NAIVE => Value::DateTimeNaive(DateTimeNaive::new(self.int()?)),
+            TAG_DATE_TIME_UTC => Value::DateTimeUtc(DateTimeUtc::new(self.int()?)),
+            TAG_DURATION => Value::Duration(Duration::new(self.int()?)),
+            TAG_JSON => {
+                let json: JsonValue = serde_json::from_slice(self.len_prefixed()?)?;
+                Value::from(json)
+            }
+            TAG_SERIALIZED_OBJECT => {
+                let serializer = self.string()?;
+                let bytes = self.len_prefixed()?;
+                Value::from(SerializedObject {
+                    serializer,
+                    bytes: bytes.into(),
+                })
+            }
+            tag => return Err(DecodeError::InvalidTag(tag)),
+        };
+        Ok(value)
+    }
+}
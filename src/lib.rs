@@ -11,14 +11,17 @@
 pub mod connectors;
 pub mod deepcopy;
 pub mod engine;
+pub mod mat_mul;
 pub mod persistence;
 pub mod python_api;
 
 mod fs_helpers;
-mod mat_mul;
 mod pipe;
 mod timestamp;
 
+#[cfg(feature = "blas")]
+extern crate blas_src; // links the BLAS implementation used by ndarray
+
 #[cfg(not(feature = "standard-allocator"))]
 mod jemalloc {
     use jemallocator::Jemalloc;
@@ -1,29 +1,246 @@
 // Copyright © 2024 Pathway
 
-use ndarray::{arr0, ArrayD, ArrayViewD, Ix1, Ix2, LinalgScalar};
+#![allow(clippy::module_name_repetitions)]
 
-pub fn mat_mul<T>(a: &ArrayViewD<T>, b: &ArrayViewD<T>) -> Option<ArrayD<T>>
+use ndarray::{arr0, Array2, ArrayD, ArrayView2, ArrayViewD, Axis, Ix1, Ix2, IxDyn, LinalgScalar};
+
+use crate::engine::SparseMatrix;
+
+#[cfg(not(feature = "blas"))]
+mod tiled;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatMulError {
+    /// The arrays aren't vectors or matrices, or their inner dimensions differ.
+    ShapeMismatch,
+    /// An element of the product of int arrays doesn't fit in `i64`.
+    Overflow,
+}
+
+/// The types of the elements of the arrays multiplied by [`mat_mul`], each with its own way
+/// of accumulating the products.
+pub trait MatMulElement: Sized {
+    fn mat_mul(a: &ArrayViewD<Self>, b: &ArrayViewD<Self>) -> Result<ArrayD<Self>, MatMulError>;
+}
+
+/// Multiplies vectors and matrices like `numpy.matmul`, with the vectors on the left treated
+/// as rows and the ones on the right as columns.
+pub fn mat_mul<T>(a: &ArrayViewD<T>, b: &ArrayViewD<T>) -> Result<ArrayD<T>, MatMulError>
+where
+    T: MatMulElement,
+{
+    T::mat_mul(a, b)
+}
+
+fn dot<T>(a: &ArrayViewD<T>, b: &ArrayViewD<T>) -> Result<ArrayD<T>, MatMulError>
 where
     T: LinalgScalar,
 {
     if a.ndim() < 1 || 2 < a.ndim() || b.ndim() < 1 || 2 < b.ndim() {
-        return None;
+        return Err(MatMulError::ShapeMismatch);
     } else if let Ok(a) = a.view().into_dimensionality::<Ix2>() {
         if a.shape()[1] != b.shape()[0] {
-            return None;
+            return Err(MatMulError::ShapeMismatch);
         } else if let Ok(b) = b.view().into_dimensionality::<Ix2>() {
-            return Some(a.dot(&b).into_dyn());
+            return Ok(a.dot(&b).into_dyn());
         } else if let Ok(b) = b.view().into_dimensionality::<Ix1>() {
-            return Some(a.dot(&b).into_dyn());
+            return Ok(a.dot(&b).into_dyn());
         }
     } else if let Ok(a) = a.view().into_dimensionality::<Ix1>() {
         if a.shape()[0] != b.shape()[0] {
-            return None;
+            return Err(MatMulError::ShapeMismatch);
         } else if let Ok(b) = b.view().into_dimensionality::<Ix2>() {
-            return Some(a.dot(&b).into_dyn());
+            return Ok(a.dot(&b).into_dyn());
         } else if let Ok(b) = b.view().into_dimensionality::<Ix1>() {
-            return Some(arr0(a.dot(&b)).into_dyn());
+            return Ok(arr0(a.dot(&b)).into_dyn());
+        }
+    }
+    Err(MatMulError::ShapeMismatch)
+}
+
+/// Views a vector as a matrix, with the axis added, and returns whether it was a vector.
+fn as_matrix<'a, T>(
+    array: &'a ArrayViewD<T>,
+    vector_axis: Axis,
+) -> Result<(ArrayView2<'a, T>, bool), MatMulError> {
+    match array.ndim() {
+        1 => array
+            .view()
+            .into_dimensionality::<Ix1>()
+            .map(|vector| (vector.insert_axis(vector_axis), true)),
+        2 => array
+            .view()
+            .into_dimensionality::<Ix2>()
+            .map(|matrix| (matrix, false)),
+        _ => return Err(MatMulError::ShapeMismatch),
+    }
+    .map_err(|_| MatMulError::ShapeMismatch)
+}
+
+/// Views the vectors as matrices and returns them with the shape of their product,
+/// without the dimensions added to the vectors.
+fn as_matrices<'a, 'b, T>(
+    a: &'a ArrayViewD<T>,
+    b: &'b ArrayViewD<T>,
+) -> Result<(ArrayView2<'a, T>, ArrayView2<'b, T>, Vec<usize>), MatMulError> {
+    let (a, a_is_vector) = as_matrix(a, Axis(0))?;
+    let (b, b_is_vector) = as_matrix(b, Axis(1))?;
+    if a.ncols() != b.nrows() {
+        return Err(MatMulError::ShapeMismatch);
+    }
+    let shape = product_shape(a.nrows(), a_is_vector, b.ncols(), b_is_vector);
+    Ok((a, b, shape))
+}
+
+fn product_shape(rows: usize, a_is_vector: bool, columns: usize, b_is_vector: bool) -> Vec<usize> {
+    let mut shape = Vec::with_capacity(2);
+    if !a_is_vector {
+        shape.push(rows);
+    }
+    if !b_is_vector {
+        shape.push(columns);
+    }
+    shape
+}
+
+/// The large products of `f64` arrays are computed by BLAS with the `blas` feature, which
+/// parallelizes them by itself, and by tiles computed in parallel otherwise.
+impl MatMulElement for f64 {
+    fn mat_mul(a: &ArrayViewD<Self>, b: &ArrayViewD<Self>) -> Result<ArrayD<Self>, MatMulError> {
+        #[cfg(not(feature = "blas"))]
+        {
+            let (a_matrix, b_matrix, shape) = as_matrices(a, b)?;
+            if let Some(product) = tiled::mat_mul(&a_matrix, &b_matrix) {
+                return Ok(product
+                    .into_shape(IxDyn(&shape))
+                    .expect("the product should have as many elements as its shape"));
+            }
+        }
+        dot(a, b)
+    }
+}
+
+/// The products of `f32` arrays are computed by `sgemm` of BLAS with the `blas` feature,
+/// accumulating them in `f32` like `numpy` does. Otherwise they are accumulated in `f64`
+/// and rounded once, so that the long sums don't lose the precision the arrays are stored
+/// with.
+impl MatMulElement for f32 {
+    #[cfg(feature = "blas")]
+    fn mat_mul(a: &ArrayViewD<Self>, b: &ArrayViewD<Self>) -> Result<ArrayD<Self>, MatMulError> {
+        dot(a, b)
+    }
+
+    #[cfg(not(feature = "blas"))]
+    #[allow(clippy::cast_possible_truncation)]
+    fn mat_mul(a: &ArrayViewD<Self>, b: &ArrayViewD<Self>) -> Result<ArrayD<Self>, MatMulError> {
+        let product = f64::mat_mul(&a.mapv(f64::from).view(), &b.mapv(f64::from).view())?;
+        Ok(product.mapv(|element| element as f32))
+    }
+}
+
+/// The products of `i64` arrays are accumulated exactly, in `i128`, and an element that
+/// doesn't fit in `i64` is an error instead of wrapping around.
+impl MatMulElement for i64 {
+    fn mat_mul(a: &ArrayViewD<Self>, b: &ArrayViewD<Self>) -> Result<ArrayD<Self>, MatMulError> {
+        let (a, b, shape) = as_matrices(a, b)?;
+        let mut product = Vec::with_capacity(a.nrows() * b.ncols());
+        for row in a.rows() {
+            for column in b.columns() {
+                let element = row
+                    .iter()
+                    .zip(column)
+                    .try_fold(0_i128, |sum, (&x, &y)| {
+                        sum.checked_add(i128::from(x) * i128::from(y))
+                    })
+                    .and_then(|sum| Self::try_from(sum).ok())
+                    .ok_or(MatMulError::Overflow)?;
+                product.push(element);
+            }
+        }
+        Ok(ArrayD::from_shape_vec(IxDyn(&shape), product)
+            .expect("the product should have as many elements as its shape"))
+    }
+}
+
+/// Multiplies a sparse matrix by a dense vector or matrix, giving a dense result.
+pub fn sparse_dense_mat_mul(
+    a: &SparseMatrix,
+    b: &ArrayViewD<f64>,
+) -> Result<ArrayD<f64>, MatMulError> {
+    let (rows, inner) = a.shape();
+    let (b, b_is_vector) = as_matrix(b, Axis(1))?;
+    if inner != b.nrows() {
+        return Err(MatMulError::ShapeMismatch);
+    }
+    let mut product = Array2::zeros((rows, b.ncols()));
+    for (row, mut product_row) in product.rows_mut().into_iter().enumerate() {
+        for (k, x) in a.row(row) {
+            product_row.scaled_add(x, &b.row(k));
+        }
+    }
+    let shape = product_shape(rows, false, b.ncols(), b_is_vector);
+    Ok(product
+        .into_shape(IxDyn(&shape))
+        .expect("the product should have as many elements as its shape"))
+}
+
+/// Multiplies a dense vector or matrix by a sparse matrix, giving a dense result.
+pub fn dense_sparse_mat_mul(
+    a: &ArrayViewD<f64>,
+    b: &SparseMatrix,
+) -> Result<ArrayD<f64>, MatMulError> {
+    let (inner, columns) = b.shape();
+    let (a, a_is_vector) = as_matrix(a, Axis(0))?;
+    if a.ncols() != inner {
+        return Err(MatMulError::ShapeMismatch);
+    }
+    let mut product = Array2::zeros((a.nrows(), columns));
+    for (a_row, mut product_row) in a.rows().into_iter().zip(product.rows_mut()) {
+        for (k, &x) in a_row.iter().enumerate() {
+            for (column, y) in b.row(k) {
+                product_row[column] += x * y;
+            }
+        }
+    }
+    let shape = product_shape(a.nrows(), a_is_vector, columns, false);
+    Ok(product
+        .into_shape(IxDyn(&shape))
+        .expect("the product should have as many elements as its shape"))
+}
+
+/// Multiplies sparse matrices row by row, accumulating each row of the product densely,
+/// over the columns it touches only, and keeping its nonzero elements.
+#[allow(clippy::float_cmp)]
+pub fn sparse_mat_mul(a: &SparseMatrix, b: &SparseMatrix) -> Result<SparseMatrix, MatMulError> {
+    let (rows, inner) = a.shape();
+    let (b_rows, columns) = b.shape();
+    if inner != b_rows {
+        return Err(MatMulError::ShapeMismatch);
+    }
+    let mut sums = vec![0.0; columns];
+    let mut touched = vec![false; columns];
+    let mut product_rows = Vec::with_capacity(rows);
+    for row in 0..rows {
+        let mut row_columns = Vec::new();
+        for (k, x) in a.row(row) {
+            for (column, y) in b.row(k) {
+                if !touched[column] {
+                    touched[column] = true;
+                    row_columns.push(column);
+                }
+                sums[column] += x * y;
+            }
         }
+        row_columns.sort_unstable();
+        let elements: Vec<_> = row_columns
+            .into_iter()
+            .filter_map(|column| {
+                touched[column] = false;
+                let sum = std::mem::take(&mut sums[column]);
+                (sum != 0.0).then_some((column, sum))
+            })
+            .collect();
+        product_rows.push(elements);
     }
-    None
+    Ok(SparseMatrix::from_rows((rows, columns), product_rows))
 }
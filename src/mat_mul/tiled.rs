@@ -0,0 +1,96 @@
+// Copyright © 2024 Pathway
+
+//! Multiplication of large matrices split into tiles of the product, computed in parallel.
+//!
+//! The rows of the product are split into bands, taken by the threads one by one, so that
+//! the threads stay busy until the end even if some of them are slowed down, and each band
+//! is computed tile by tile, reusing the rows of `a` it needs from the cache. The tiles
+//! themselves are computed by the cache-blocked kernel of `ndarray`.
+//!
+//! The thread multiplying the matrices takes part in the computation, helped by threads
+//! taken from a budget shared by all the workers of the process, so that the workers
+//! multiplying matrices at the same time don't start more threads than there are CPUs.
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use ndarray::linalg::general_mat_mul;
+use ndarray::{Array2, ArrayView2, Axis};
+use once_cell::sync::Lazy;
+
+/// The number of the multiplications of elements below which a product is computed by
+/// a single thread, as starting the threads would take longer than they save.
+const MIN_PARALLEL_WORK: usize = 1 << 22;
+
+/// The number of rows and columns of a tile of the product.
+const TILE_SIZE: usize = 128;
+
+/// The number of the threads that can still be started to help with the products, besides
+/// the threads multiplying the matrices.
+static SPARE_THREADS: Lazy<AtomicUsize> = Lazy::new(|| {
+    AtomicUsize::new(thread::available_parallelism().map_or(1, NonZeroUsize::get) - 1)
+});
+
+/// The threads taken from [`SPARE_THREADS`], given back when it's dropped.
+struct HelperThreads(usize);
+
+impl HelperThreads {
+    fn take(wanted: usize) -> Self {
+        let spare = SPARE_THREADS
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |spare| {
+                Some(spare.saturating_sub(wanted))
+            })
+            .unwrap_or_else(|spare| spare);
+        Self(spare.min(wanted))
+    }
+}
+
+impl Drop for HelperThreads {
+    fn drop(&mut self) {
+        SPARE_THREADS.fetch_add(self.0, Ordering::AcqRel);
+    }
+}
+
+/// Multiplies the matrices in parallel, if the product is large enough for it to pay off.
+pub fn mat_mul(a: &ArrayView2<f64>, b: &ArrayView2<f64>) -> Option<Array2<f64>> {
+    let work = a
+        .nrows()
+        .saturating_mul(a.ncols())
+        .saturating_mul(b.ncols());
+    let bands = a.nrows().div_ceil(TILE_SIZE);
+    if work < MIN_PARALLEL_WORK || bands < 2 {
+        return None;
+    }
+    let helpers = HelperThreads::take(bands - 1);
+    if helpers.0 == 0 {
+        return None;
+    }
+
+    let mut product = Array2::zeros((a.nrows(), b.ncols()));
+    let bands = Mutex::new(
+        product
+            .axis_chunks_iter_mut(Axis(0), TILE_SIZE)
+            .zip(a.axis_chunks_iter(Axis(0), TILE_SIZE)),
+    );
+    let compute_bands = || loop {
+        let band = bands.lock().unwrap().next();
+        let Some((mut product_band, a_band)) = band else {
+            break;
+        };
+        for (mut tile, b_band) in product_band
+            .axis_chunks_iter_mut(Axis(1), TILE_SIZE)
+            .zip(b.axis_chunks_iter(Axis(1), TILE_SIZE))
+        {
+            general_mat_mul(1.0, &a_band, &b_band, 0.0, &mut tile);
+        }
+    };
+    thread::scope(|scope| {
+        for _ in 0..helpers.0 {
+            scope.spawn(compute_bands);
+        }
+        compute_bands();
+    });
+    Some(product)
+}
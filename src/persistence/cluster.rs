@@ -0,0 +1,154 @@
+// Copyright © 2024 Pathway
+
+//! The agreement of the processes of a cluster on the checkpoints.
+//!
+//! The first process proposes the time of each checkpoint, all processes prepare their
+//! checkpoints at this time, and each of them commits its checkpoint only once all of
+//! them have prepared it. So the committed states of the processes differ by at most
+//! one checkpoint, and the next run starts from the last checkpoint committed by all
+//! of them, which all workers keep, as they rotate the last two checkpoints.
+//!
+//! The proposals and the prepared checkpoints are kept in the metadata storage, which
+//! has to be shared by the processes, like S3 or a filesystem mounted on all machines.
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::Duration;
+
+use log::error;
+
+use crate::persistence::metadata_backends::{Error, MetadataBackend};
+
+/// The prefix of the keys of the proposed and the prepared checkpoints in the metadata
+/// storage, which are not checkpoints.
+pub const CLUSTER_CHECKPOINT_KEY_PREFIX: &str = "cluster-checkpoint-";
+
+/// The time agreed for the checkpoint made after all output is finished, whose
+/// timestamp differs between the workers.
+pub const FINAL_CHECKPOINT_TIME: u64 = u64::MAX;
+
+const PROPOSED_KEY_PREFIX: &str = "cluster-checkpoint-proposed-";
+const PREPARED_KEY_PREFIX: &str = "cluster-checkpoint-prepared-";
+const PREPARED: &str = "prepared";
+const FAILED: &str = "failed";
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug)]
+pub struct ClusterCheckpoints {
+    backend: Box<dyn MetadataBackend>,
+    process_id: usize,
+    processes: usize,
+}
+
+impl ClusterCheckpoints {
+    /// Creates the agreement of the process `process_id` of `processes`. The first
+    /// process removes the proposals and the prepared checkpoints left by the previous
+    /// run. It's done before its workers start, and the outputs of the other processes
+    /// can't finish any time before that, so they never see them.
+    pub fn new(
+        mut backend: Box<dyn MetadataBackend>,
+        process_id: usize,
+        processes: usize,
+    ) -> Result<Self, Error> {
+        if process_id == 0 {
+            for key in backend.list_keys()? {
+                if key.starts_with(CLUSTER_CHECKPOINT_KEY_PREFIX) {
+                    backend.remove_key(&key)?;
+                }
+            }
+        }
+        Ok(Self {
+            backend,
+            process_id,
+            processes,
+        })
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.process_id == 0
+    }
+
+    /// Proposes the checkpoint at `agreed_time` to the other processes.
+    pub fn propose(&mut self, agreed_time: u64) -> Result<(), Error> {
+        self.backend
+            .put_value(&format!("{PROPOSED_KEY_PREFIX}{agreed_time}"), "")
+    }
+
+    /// Returns the earliest checkpoint proposed after `after`, if it isn't later than
+    /// `until`.
+    pub fn next_proposed(&self, after: Option<u64>, until: u64) -> Result<Option<u64>, Error> {
+        let next = self
+            .backend
+            .list_keys()?
+            .iter()
+            .filter_map(|key| key.strip_prefix(PROPOSED_KEY_PREFIX)?.parse::<u64>().ok())
+            .filter(|agreed_time| after.map_or(true, |after| *agreed_time > after))
+            .min();
+        Ok(next.filter(|agreed_time| *agreed_time <= until))
+    }
+
+    fn prepared_key(agreed_time: u64, process_id: usize) -> String {
+        format!("{PREPARED_KEY_PREFIX}{agreed_time}-{process_id}")
+    }
+
+    /// Records whether this process has prepared the checkpoint at `agreed_time`.
+    pub fn mark_prepared(&mut self, agreed_time: u64, is_prepared: bool) -> Result<(), Error> {
+        let key = Self::prepared_key(agreed_time, self.process_id);
+        self.backend
+            .put_value(&key, if is_prepared { PREPARED } else { FAILED })
+    }
+
+    /// Returns `Some(true)` once all processes have prepared the checkpoint at
+    /// `agreed_time`, `Some(false)` if any of them failed to, and `None` otherwise.
+    fn prepared_by_all(&self, agreed_time: u64) -> Result<Option<bool>, Error> {
+        let keys = self.backend.list_keys()?;
+        let mut prepared = 0;
+        for process_id in 0..self.processes {
+            let key = Self::prepared_key(agreed_time, process_id);
+            if !keys.contains(&key) {
+                continue;
+            }
+            if self.backend.get_value(&key)? == FAILED {
+                return Ok(Some(false));
+            }
+            prepared += 1;
+        }
+        Ok((prepared == self.processes).then_some(true))
+    }
+
+    /// Removes the checkpoints prepared by this process before `agreed_time` and, in the
+    /// first process, the proposals of them, which aren't needed once it's committed.
+    pub fn remove_before(&mut self, agreed_time: u64) -> Result<(), Error> {
+        let own_suffix = format!("-{}", self.process_id);
+        for key in self.backend.list_keys()? {
+            let earlier = if let Some(proposed) = key.strip_prefix(PROPOSED_KEY_PREFIX) {
+                self.is_leader() && proposed.parse::<u64>().is_ok_and(|time| time < agreed_time)
+            } else if let Some(prepared) = key.strip_prefix(PREPARED_KEY_PREFIX) {
+                prepared
+                    .strip_suffix(&own_suffix)
+                    .is_some_and(|time| time.parse::<u64>().is_ok_and(|time| time < agreed_time))
+            } else {
+                false
+            };
+            if earlier {
+                self.backend.remove_key(&key)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Waits until all processes have prepared the checkpoint at `agreed_time`. Returns
+/// whether they all succeeded. The lock is only held while the storage is checked.
+pub fn wait_until_prepared_by_all(cluster: &Mutex<ClusterCheckpoints>, agreed_time: u64) -> bool {
+    loop {
+        match cluster.lock().unwrap().prepared_by_all(agreed_time) {
+            Ok(Some(all_prepared)) => return all_prepared,
+            Ok(None) => {}
+            Err(e) => error!("Failed to check the checkpoints of the other processes: {e}"),
+        }
+        sleep(POLL_INTERVAL);
+    }
+}
@@ -6,6 +6,7 @@ use log::{error, info, warn};
 use std::collections::HashMap;
 use std::fs;
 use std::io::Error as IoError;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -15,23 +16,35 @@ use s3::bucket::Bucket as S3Bucket;
 use crate::connectors::data_storage::S3CommandName;
 use crate::connectors::data_storage::{ReadError, WriteError};
 use crate::connectors::snapshot::{
-    Event, LocalBinarySnapshotReader, LocalBinarySnapshotWriter, MockSnapshotReader,
-    S3SnapshotReader, S3SnapshotWriter, SnapshotReader, SnapshotReaderImpl,
+    encrypt_local_snapshot, encrypt_object_storage_snapshot, encrypt_s3_snapshot, Event,
+    LocalBinarySnapshotReader, LocalBinarySnapshotWriter, MockSnapshotReader,
+    ObjectStorageSnapshotReader, ObjectStorageSnapshotWriter, S3SnapshotReader, S3SnapshotWriter,
+    SnapshotCompression, SnapshotEncoding, SnapshotReader, SnapshotReaderImpl, SnapshotWriter,
+    VolumeTrackingSnapshotWriter,
 };
 use crate::connectors::{PersistenceMode, SnapshotAccess};
 use crate::deepcopy::DeepCopy;
 use crate::fs_helpers::ensure_directory;
+use crate::persistence::cluster::ClusterCheckpoints;
+use crate::persistence::encryption::EncryptionKey;
 use crate::persistence::metadata_backends::Error as MetadataBackendError;
 use crate::persistence::metadata_backends::{
-    FilesystemKVStorage, MetadataBackend, MockKVStorage, S3KVStorage,
+    EncryptedKVStorage, FilesystemKVStorage, MetadataBackend, MockKVStorage,
+    ObjectStorageKVStorage, S3KVStorage,
 };
-use crate::persistence::state::MetadataAccessor;
-use crate::persistence::sync::WorkersPersistenceCoordinator;
-use crate::persistence::{PersistentId, SharedSnapshotWriter};
+use crate::persistence::object_storage::SharedObjectStorage;
+use crate::persistence::schema::ColumnMigration;
+use crate::persistence::state::{MetadataAccessor, RetentionPolicy};
+use crate::persistence::sync::{
+    SharedCheckpointHooks, SharedPersistenceStats, SharedSavepointRequests,
+    SharedUnpersistedVolume, UnpersistedVolume, WorkersPersistenceCoordinator,
+};
+use crate::persistence::{ExternalPersistentId, PersistentId, SharedSnapshotWriter};
 
 const STREAMS_DIRECTORY_NAME: &str = "streams";
 
 pub type ConnectorWorkerPair = (PersistentId, usize);
+pub type SchemaMigrations = HashMap<ExternalPersistentId, HashMap<String, ColumnMigration>>;
 
 /// Metadata storage handles the frontier over all persisted data sources.
 /// When we restart the computation, it will start from the frontier stored
@@ -39,7 +52,14 @@ pub type ConnectorWorkerPair = (PersistentId, usize);
 #[derive(Debug, Clone)]
 pub enum MetadataStorageConfig {
     Filesystem(PathBuf),
-    S3 { bucket: S3Bucket, root_path: String },
+    S3 {
+        bucket: S3Bucket,
+        root_path: String,
+    },
+    ObjectStorage {
+        storage: SharedObjectStorage,
+        root_path: String,
+    },
     Mock,
 }
 
@@ -48,7 +68,14 @@ pub enum MetadataStorageConfig {
 #[derive(Debug, Clone)]
 pub enum StreamStorageConfig {
     Filesystem(PathBuf),
-    S3 { bucket: S3Bucket, root_path: String },
+    S3 {
+        bucket: S3Bucket,
+        root_path: String,
+    },
+    ObjectStorage {
+        storage: SharedObjectStorage,
+        root_path: String,
+    },
     Mock(HashMap<ConnectorWorkerPair, Vec<Event>>),
 }
 
@@ -60,29 +87,66 @@ pub enum StreamStorageConfig {
 #[derive(Debug, Clone)]
 pub struct PersistenceManagerOuterConfig {
     snapshot_interval: Duration,
+    unpersisted_volume: SharedUnpersistedVolume,
     metadata_storage: MetadataStorageConfig,
     stream_storage: StreamStorageConfig,
     snapshot_access: SnapshotAccess,
     persistence_mode: PersistenceMode,
     continue_after_replay: bool,
+    replay_until: Option<u64>,
+    snapshot_compression: Option<SnapshotCompression>,
+    encryption_key: Option<EncryptionKey>,
+    migrate_unencrypted: bool,
+    savepoint_requests: SharedSavepointRequests,
+    checkpoint_hooks: SharedCheckpointHooks,
+    schema_migrations: SchemaMigrations,
+    retention_policy: RetentionPolicy,
+    background_checkpoints: bool,
+    persistence_stats: SharedPersistenceStats,
 }
 
 impl PersistenceManagerOuterConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         snapshot_interval: Duration,
+        snapshot_interval_rows: Option<u64>,
+        snapshot_interval_bytes: Option<u64>,
         metadata_storage: MetadataStorageConfig,
         stream_storage: StreamStorageConfig,
         snapshot_access: SnapshotAccess,
         persistence_mode: PersistenceMode,
         continue_after_replay: bool,
+        replay_until: Option<u64>,
+        snapshot_compression: Option<SnapshotCompression>,
+        encryption_key: Option<EncryptionKey>,
+        migrate_unencrypted: bool,
+        savepoint_requests: SharedSavepointRequests,
+        checkpoint_hooks: SharedCheckpointHooks,
+        schema_migrations: SchemaMigrations,
+        retention_policy: RetentionPolicy,
+        background_checkpoints: bool,
     ) -> Self {
         Self {
             snapshot_interval,
+            unpersisted_volume: Arc::new(UnpersistedVolume::new(
+                snapshot_interval_rows,
+                snapshot_interval_bytes,
+            )),
             metadata_storage,
             stream_storage,
             snapshot_access,
             persistence_mode,
             continue_after_replay,
+            replay_until,
+            snapshot_compression,
+            encryption_key,
+            migrate_unencrypted,
+            savepoint_requests,
+            checkpoint_hooks,
+            schema_migrations,
+            retention_policy,
+            background_checkpoints,
+            persistence_stats: SharedPersistenceStats::default(),
         }
     }
 
@@ -90,11 +154,49 @@ impl PersistenceManagerOuterConfig {
         PersistenceManagerConfig::new(self, worker_id, total_workers)
     }
 
+    /// Creates the coordinator of the checkpoints of the workers `workers` of this
+    /// process, out of `total_workers`. If there are other processes, the metadata
+    /// storage is used for their agreement on the checkpoints, so it has to be shared.
     pub fn create_workers_persistence_coordinator(
         &self,
-        num_workers: usize,
-    ) -> WorkersPersistenceCoordinator {
-        WorkersPersistenceCoordinator::new(self.snapshot_interval, num_workers)
+        workers: Range<usize>,
+        total_workers: usize,
+    ) -> Result<WorkersPersistenceCoordinator, MetadataBackendError> {
+        let cluster = if workers.len() < total_workers {
+            let backend = self
+                .clone()
+                .into_inner(workers.start, total_workers)
+                .create_metadata_backend()?;
+            Some(ClusterCheckpoints::new(
+                backend,
+                workers.start / workers.len(),
+                total_workers / workers.len(),
+            )?)
+        } else {
+            None
+        };
+        Ok(WorkersPersistenceCoordinator::new(
+            self.snapshot_interval,
+            workers,
+            cluster,
+            self.savepoint_requests.clone(),
+            self.checkpoint_hooks.clone(),
+            self.unpersisted_volume.clone(),
+            self.persistence_stats.clone(),
+            self.background_checkpoints,
+        ))
+    }
+
+    pub fn savepoint_requests(&self) -> &SharedSavepointRequests {
+        &self.savepoint_requests
+    }
+
+    pub fn checkpoint_hooks(&self) -> &SharedCheckpointHooks {
+        &self.checkpoint_hooks
+    }
+
+    pub fn persistence_stats(&self) -> &SharedPersistenceStats {
+        &self.persistence_stats
     }
 }
 
@@ -107,6 +209,23 @@ pub struct PersistenceManagerConfig {
     pub snapshot_access: SnapshotAccess,
     pub persistence_mode: PersistenceMode,
     pub continue_after_replay: bool,
+    /// If set, only the data persisted for the times up to this one is replayed and
+    /// the persisted state is not modified.
+    pub replay_until: Option<u64>,
+    /// If set, new snapshot data is compressed with this algorithm.
+    pub snapshot_compression: Option<SnapshotCompression>,
+    /// If set, the snapshots and the metadata are encrypted with this key.
+    pub encryption_key: Option<EncryptionKey>,
+    /// If set, the persisted data written before the encryption was enabled is
+    /// encrypted, instead of being rejected.
+    pub migrate_unencrypted: bool,
+    /// The migrations of the columns of the inputs, whose schemas changed since their
+    /// state was persisted.
+    pub schema_migrations: SchemaMigrations,
+    /// Decides which checkpoints are kept. The snapshots of the inputs that no kept
+    /// checkpoint refers to are removed.
+    pub retention_policy: RetentionPolicy,
+    unpersisted_volume: SharedUnpersistedVolume,
     pub worker_id: usize,
     total_workers: usize,
 }
@@ -123,22 +242,49 @@ impl PersistenceManagerConfig {
             snapshot_access: outer_config.snapshot_access,
             persistence_mode: outer_config.persistence_mode,
             continue_after_replay: outer_config.continue_after_replay,
+            replay_until: outer_config.replay_until,
+            snapshot_compression: outer_config.snapshot_compression,
+            encryption_key: outer_config.encryption_key,
+            migrate_unencrypted: outer_config.migrate_unencrypted,
+            schema_migrations: outer_config.schema_migrations,
+            retention_policy: outer_config.retention_policy,
+            unpersisted_volume: outer_config.unpersisted_volume,
             worker_id,
             total_workers,
         }
     }
 
     pub fn create_metadata_storage(&self) -> Result<MetadataAccessor, MetadataBackendError> {
-        let backend: Box<dyn MetadataBackend> = match &self.metadata_storage {
+        MetadataAccessor::new(
+            self.create_metadata_backend()?,
+            self.worker_id,
+            self.retention_policy,
+        )
+    }
+
+    pub fn create_metadata_backend(
+        &self,
+    ) -> Result<Box<dyn MetadataBackend>, MetadataBackendError> {
+        let mut backend: Box<dyn MetadataBackend> = match &self.metadata_storage {
             MetadataStorageConfig::Filesystem(root_path) => {
                 Box::new(FilesystemKVStorage::new(root_path)?)
             }
             MetadataStorageConfig::S3 { bucket, root_path } => {
                 Box::new(S3KVStorage::new(bucket.deep_copy(), root_path))
             }
+            MetadataStorageConfig::ObjectStorage { storage, root_path } => {
+                Box::new(ObjectStorageKVStorage::new(storage.clone(), root_path))
+            }
             MetadataStorageConfig::Mock => Box::new(MockKVStorage {}),
         };
-        MetadataAccessor::new(backend, self.worker_id)
+        if let Some(key) = &self.encryption_key {
+            backend = Box::new(EncryptedKVStorage::new(
+                backend,
+                key.clone(),
+                self.migrate_unencrypted,
+            )?);
+        }
+        Ok(backend)
     }
 
     pub fn create_snapshot_readers(
@@ -153,7 +299,16 @@ impl PersistenceManagerConfig {
                 let assigned_snapshot_paths =
                     self.assigned_snapshot_paths(root_path, persistent_id)?;
                 for (worker_id, path) in assigned_snapshot_paths {
-                    reader_impls.insert(worker_id, Box::new(LocalBinarySnapshotReader::new(path)?));
+                    if self.migrate_unencrypted {
+                        encrypt_local_snapshot(&path, &self.snapshot_encoding())?;
+                    }
+                    reader_impls.insert(
+                        worker_id,
+                        Box::new(LocalBinarySnapshotReader::new(
+                            path,
+                            self.encryption_key.clone(),
+                        )?),
+                    );
                 }
                 reader_impls
             }
@@ -162,9 +317,35 @@ impl PersistenceManagerConfig {
                 let assigned_snapshot_paths =
                     self.assigned_s3_snapshot_paths(&bucket.deep_copy(), root_path, persistent_id)?;
                 for (worker_id, path) in assigned_snapshot_paths {
+                    if self.migrate_unencrypted {
+                        encrypt_s3_snapshot(bucket, &path, &self.snapshot_encoding())?;
+                    }
+                    reader_impls.insert(
+                        worker_id,
+                        Box::new(S3SnapshotReader::new(
+                            bucket.deep_copy(),
+                            &path,
+                            self.encryption_key.clone(),
+                        )?),
+                    );
+                }
+                reader_impls
+            }
+            StreamStorageConfig::ObjectStorage { storage, root_path } => {
+                let mut reader_impls = HashMap::<usize, Box<dyn SnapshotReaderImpl>>::new();
+                let assigned_snapshot_paths =
+                    self.assigned_object_snapshot_paths(storage, root_path, persistent_id)?;
+                for (worker_id, path) in assigned_snapshot_paths {
+                    if self.migrate_unencrypted {
+                        encrypt_object_storage_snapshot(storage, &path, &self.snapshot_encoding())?;
+                    }
                     reader_impls.insert(
                         worker_id,
-                        Box::new(S3SnapshotReader::new(bucket.deep_copy(), &path)?),
+                        Box::new(ObjectStorageSnapshotReader::new(
+                            storage.clone(),
+                            &path,
+                            self.encryption_key.clone(),
+                        )?),
                     );
                 }
                 reader_impls
@@ -184,10 +365,14 @@ impl PersistenceManagerConfig {
         for (worker_id, reader_impl) in reader_impls {
             let Some(threshold_time) = threshold_times.get(&worker_id) else {
                 // append the snapshot reader which would truncate snapshot straight away
-                result.push(SnapshotReader::new(reader_impl, 0)?);
+                result.push(SnapshotReader::new(reader_impl, 0, self.replay_until)?);
                 continue;
             };
-            result.push(SnapshotReader::new(reader_impl, *threshold_time)?);
+            result.push(SnapshotReader::new(
+                reader_impl,
+                *threshold_time,
+                self.replay_until,
+            )?);
         }
 
         Ok(result)
@@ -197,22 +382,136 @@ impl PersistenceManagerConfig {
         &mut self,
         persistent_id: PersistentId,
     ) -> Result<SharedSnapshotWriter, WriteError> {
-        match &self.stream_storage {
-            StreamStorageConfig::Filesystem(root_path) => {
-                Ok(Arc::new(Mutex::new(LocalBinarySnapshotWriter::new(
-                    &self.snapshot_writer_path(root_path, persistent_id)?,
-                )?)))
-            }
+        let writer: Box<dyn SnapshotWriter> = match &self.stream_storage {
+            StreamStorageConfig::Filesystem(root_path) => Box::new(LocalBinarySnapshotWriter::new(
+                &self.snapshot_writer_path(root_path, persistent_id)?,
+                self.snapshot_encoding(),
+            )?),
             StreamStorageConfig::S3 { bucket, root_path } => {
-                let snapshot_path = self.s3_snapshot_path(root_path, persistent_id);
-                Ok(Arc::new(Mutex::new(S3SnapshotWriter::new(
+                let snapshot_path = self.object_snapshot_path(root_path, persistent_id);
+                Box::new(S3SnapshotWriter::new(
                     bucket.deep_copy(),
                     &snapshot_path,
-                ))))
+                    self.snapshot_encoding(),
+                ))
+            }
+            StreamStorageConfig::ObjectStorage { storage, root_path } => {
+                let snapshot_path = self.object_snapshot_path(root_path, persistent_id);
+                Box::new(ObjectStorageSnapshotWriter::new(
+                    storage.clone(),
+                    &snapshot_path,
+                    self.snapshot_encoding(),
+                ))
             }
             StreamStorageConfig::Mock(_) => {
                 unreachable!()
             }
+        };
+        Ok(Arc::new(Mutex::new(VolumeTrackingSnapshotWriter::new(
+            writer,
+            self.unpersisted_volume.clone(),
+        ))))
+    }
+
+    /// Returns the inputs that have snapshots written by this worker.
+    pub fn snapshot_persistent_ids(&self) -> Result<Vec<PersistentId>, ReadError> {
+        let names = match &self.stream_storage {
+            StreamStorageConfig::Filesystem(root_path) => {
+                let worker_path = root_path
+                    .join(STREAMS_DIRECTORY_NAME)
+                    .join(self.worker_id.to_string());
+                if !worker_path.exists() {
+                    return Ok(Vec::new());
+                }
+                let mut names = Vec::new();
+                for entry in fs::read_dir(worker_path)? {
+                    names.push(entry?.file_name().to_string_lossy().to_string());
+                }
+                names
+            }
+            StreamStorageConfig::S3 { bucket, root_path } => {
+                let worker_path = format!("{}/", self.object_worker_snapshots_path(root_path));
+                let object_lists = bucket
+                    .list(worker_path.clone(), None)
+                    .map_err(|e| ReadError::S3(S3CommandName::ListObjectsV2, e))?;
+                object_lists
+                    .iter()
+                    .flat_map(|list| &list.contents)
+                    .filter_map(|object| {
+                        let snapshot_path_block = object.key.strip_prefix(&worker_path)?;
+                        let (name, _) = snapshot_path_block.split_once('/')?;
+                        Some(name.to_string())
+                    })
+                    .collect()
+            }
+            StreamStorageConfig::ObjectStorage { storage, root_path } => {
+                let worker_path = format!("{}/", self.object_worker_snapshots_path(root_path));
+                storage
+                    .list(&worker_path)?
+                    .iter()
+                    .filter_map(|key| {
+                        let snapshot_path_block = key.strip_prefix(&worker_path)?;
+                        let (name, _) = snapshot_path_block.split_once('/')?;
+                        Some(name.to_string())
+                    })
+                    .collect()
+            }
+            StreamStorageConfig::Mock(_) => Vec::new(),
+        };
+        let mut persistent_ids: Vec<PersistentId> = names
+            .iter()
+            .filter_map(|name| {
+                let persistent_id = name.parse().ok();
+                if persistent_id.is_none() {
+                    warn!("Unexpected object in snapshot directory: {name}");
+                }
+                persistent_id
+            })
+            .collect();
+        persistent_ids.sort_unstable();
+        persistent_ids.dedup();
+        Ok(persistent_ids)
+    }
+
+    /// Removes the snapshot of the input written by this worker.
+    pub fn remove_snapshot(&self, persistent_id: PersistentId) -> Result<(), ReadError> {
+        match &self.stream_storage {
+            StreamStorageConfig::Filesystem(root_path) => {
+                fs::remove_dir_all(
+                    root_path
+                        .join(STREAMS_DIRECTORY_NAME)
+                        .join(self.worker_id.to_string())
+                        .join(persistent_id.to_string()),
+                )?;
+            }
+            StreamStorageConfig::S3 { bucket, root_path } => {
+                let snapshot_path =
+                    format!("{}/", self.object_snapshot_path(root_path, persistent_id));
+                let object_lists = bucket
+                    .list(snapshot_path, None)
+                    .map_err(|e| ReadError::S3(S3CommandName::ListObjectsV2, e))?;
+                for object in object_lists.iter().flat_map(|list| &list.contents) {
+                    bucket
+                        .delete_object(&object.key)
+                        .map_err(|e| ReadError::S3(S3CommandName::DeleteObject, e))?;
+                }
+            }
+            StreamStorageConfig::ObjectStorage { storage, root_path } => {
+                let snapshot_path =
+                    format!("{}/", self.object_snapshot_path(root_path, persistent_id));
+                for key in storage.list(&snapshot_path)? {
+                    storage.delete(&key)?;
+                }
+            }
+            StreamStorageConfig::Mock(_) => {}
+        }
+        Ok(())
+    }
+
+    fn snapshot_encoding(&self) -> SnapshotEncoding {
+        SnapshotEncoding {
+            compression: self.snapshot_compression,
+            encryption_key: self.encryption_key.clone(),
         }
     }
 
@@ -229,11 +528,18 @@ impl PersistenceManagerConfig {
         Ok(worker_path.join(persistent_id.to_string()))
     }
 
-    fn s3_snapshot_path(&self, root_path: &str, persistent_id: PersistentId) -> String {
+    fn object_worker_snapshots_path(&self, root_path: &str) -> String {
         format!(
-            "{}/streams/{}/{}",
+            "{}/streams/{}",
             root_path.strip_suffix('/').unwrap_or(root_path),
             self.worker_id,
+        )
+    }
+
+    fn object_snapshot_path(&self, root_path: &str, persistent_id: PersistentId) -> String {
+        format!(
+            "{}/{}",
+            self.object_worker_snapshots_path(root_path),
             persistent_id
         )
     }
@@ -291,20 +597,55 @@ impl PersistenceManagerConfig {
         root_path: &str,
         persistent_id: PersistentId,
     ) -> Result<HashMap<usize, String>, ReadError> {
-        let snapshots_root_path = format!(
-            "{}/streams/",
-            root_path.strip_suffix('/').unwrap_or(root_path)
-        );
-        let prefix_len = snapshots_root_path.len();
+        let snapshots_root_path = Self::object_snapshots_root_path(root_path);
         let object_lists = bucket
             .list(snapshots_root_path.clone(), None)
             .map_err(|e| ReadError::S3(S3CommandName::ListObjectsV2, e))?;
+        Ok(self.assign_object_snapshot_paths(
+            &snapshots_root_path,
+            object_lists
+                .iter()
+                .flat_map(|list| &list.contents)
+                .map(|object| object.key.as_str()),
+            persistent_id,
+        ))
+    }
+
+    fn assigned_object_snapshot_paths(
+        &self,
+        storage: &SharedObjectStorage,
+        root_path: &str,
+        persistent_id: PersistentId,
+    ) -> Result<HashMap<usize, String>, ReadError> {
+        let snapshots_root_path = Self::object_snapshots_root_path(root_path);
+        let keys = storage.list(&snapshots_root_path)?;
+        Ok(self.assign_object_snapshot_paths(
+            &snapshots_root_path,
+            keys.iter().map(String::as_str),
+            persistent_id,
+        ))
+    }
 
+    fn object_snapshots_root_path(root_path: &str) -> String {
+        format!(
+            "{}/streams/",
+            root_path.strip_suffix('/').unwrap_or(root_path)
+        )
+    }
+
+    /// Assigns to this worker the snapshots of the input among the objects with `keys`
+    /// in `snapshots_root_path`.
+    fn assign_object_snapshot_paths<'a>(
+        &self,
+        snapshots_root_path: &str,
+        keys: impl Iterator<Item = &'a str>,
+        persistent_id: PersistentId,
+    ) -> HashMap<usize, String> {
+        let prefix_len = snapshots_root_path.len();
         let mut assigned_paths = HashMap::new();
 
-        for list in &object_lists {
-            for object in &list.contents {
-                let key: &str = &object.key;
+        {
+            for key in keys {
                 assert!(key.len() > prefix_len);
                 let snapshot_path_block = key[prefix_len..].to_string();
                 // snapshot_path_block has the form {worker_id}/{persistent_id}/{snapshot_block_id}
@@ -335,6 +676,6 @@ impl PersistenceManagerConfig {
             }
         }
 
-        Ok(assigned_paths)
+        assigned_paths
     }
 }
@@ -0,0 +1,173 @@
+// Copyright © 2024 Pathway
+
+use std::cmp::max;
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+
+use log::info;
+
+use crate::connectors::data_storage::{ReadError, WriteError};
+use crate::connectors::snapshot::Event;
+use crate::engine::{Key, Value};
+use crate::persistence::config::PersistenceManagerOuterConfig;
+use crate::persistence::metadata_backends::Error as MetadataBackendError;
+use crate::persistence::schema::{
+    InputSchema, PersistedColumn, SchemaColumn, SchemaMigrationError,
+};
+use crate::persistence::state::{MetadataAccessor, RetentionPolicy};
+use crate::persistence::PersistentId;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Metadata(#[from] MetadataBackendError),
+
+    #[error(transparent)]
+    Read(#[from] ReadError),
+
+    #[error(transparent)]
+    Write(#[from] WriteError),
+
+    #[error(transparent)]
+    SchemaMigration(#[from] SchemaMigrationError),
+
+    #[error("the schema of the input was not persisted, it is persisted by the next run of the computation")]
+    SchemaNotPersisted,
+
+    #[error("the snapshot writer stopped before flushing the data")]
+    FlushCanceled,
+}
+
+pub type PersistedRow = (Key, Vec<Value>);
+
+/// The rows of an input in its persisted state, with the values in the order of
+/// `columns`.
+#[derive(Debug, Clone)]
+pub struct PersistedRows {
+    pub columns: Vec<PersistedColumn>,
+    pub rows: Vec<PersistedRow>,
+}
+
+/// Reads the rows of the input with `persistent_id` in the last committed state, or as
+/// of `replay_until`, if it's set in the config. The persisted state is not modified.
+///
+/// The rows persisted with an older schema of the input are converted to the last
+/// persisted one.
+pub fn export_persisted_rows(
+    config: PersistenceManagerOuterConfig,
+    persistent_id: PersistentId,
+) -> Result<PersistedRows, Error> {
+    let mut config = config.into_inner(0, 1);
+    // The snapshot read as when replaying it is never truncated
+    config.replay_until = Some(config.replay_until.unwrap_or(u64::MAX));
+    let metadata = MetadataAccessor::new(
+        config.create_metadata_backend()?,
+        0,
+        RetentionPolicy::default(),
+    )?;
+
+    let mut schemas: Vec<Vec<PersistedColumn>> = Vec::new();
+    let mut rows_with_schema: HashMap<Key, (Option<usize>, Vec<Value>)> = HashMap::new();
+    for mut reader in
+        config.create_snapshot_readers(persistent_id, metadata.past_runs_threshold_times())?
+    {
+        let mut schema_index = None;
+        loop {
+            match reader.read()? {
+                Event::Insert(key, values) | Event::Upsert(key, Some(values)) => {
+                    rows_with_schema.insert(key, (schema_index, values));
+                }
+                Event::Delete(key, _) | Event::Upsert(key, None) => {
+                    rows_with_schema.remove(&key);
+                }
+                Event::Schema(columns) => {
+                    schemas.push(columns);
+                    schema_index = Some(schemas.len() - 1);
+                }
+                Event::AdvanceTime(_) => {}
+                Event::Finished => break,
+            }
+        }
+    }
+
+    let columns = schemas.last().ok_or(Error::SchemaNotPersisted)?.clone();
+    let input_schema = InputSchema::new(
+        columns
+            .iter()
+            .map(|column| SchemaColumn {
+                name: column.name.clone(),
+                type_: column.type_,
+                default: None,
+            })
+            .collect(),
+        HashMap::new(),
+    );
+    let mut migrators = Vec::with_capacity(schemas.len());
+    for persisted_columns in &schemas {
+        let mut migrator = input_schema.migrator();
+        migrator.set_persisted_schema(persisted_columns)?;
+        migrators.push(migrator);
+    }
+    let positional_migrator = input_schema.migrator();
+
+    let mut rows = Vec::with_capacity(rows_with_schema.len());
+    for (key, (schema_index, values)) in rows_with_schema {
+        let migrator = schema_index.map_or(&positional_migrator, |index| &migrators[index]);
+        rows.push((key, migrator.migrate(values)?));
+    }
+    rows.sort_by_key(|(key, _)| *key);
+    Ok(PersistedRows { columns, rows })
+}
+
+/// Replaces the persisted rows of the input with `persistent_id` with `rows`. The
+/// frontier of the input is kept, so the next run continues reading the source where
+/// the persisted state ends, or reads it from the beginning, if nothing was persisted.
+pub fn import_persisted_rows(
+    config: &PersistenceManagerOuterConfig,
+    persistent_id: PersistentId,
+    rows: PersistedRows,
+) -> Result<(), Error> {
+    let mut worker_config = config.clone().into_inner(0, 1);
+    let mut metadata = MetadataAccessor::new(
+        worker_config.create_metadata_backend()?,
+        0,
+        RetentionPolicy::default(),
+    )?;
+
+    // The snapshots of all workers are replaced with a single one of worker 0, as the
+    // rows are distributed among the workers anyway when they are read
+    let mut worker_ids: HashSet<usize> = metadata
+        .past_runs_threshold_times()
+        .keys()
+        .copied()
+        .collect();
+    worker_ids.insert(0);
+    for worker_id in worker_ids {
+        match config
+            .clone()
+            .into_inner(worker_id, worker_id + 1)
+            .remove_snapshot(persistent_id)
+        {
+            Err(ReadError::Io(e)) if e.kind() == ErrorKind::NotFound => {}
+            result => result?,
+        }
+    }
+
+    let snapshot_writer = worker_config.create_snapshot_writer(persistent_id)?;
+    let mut snapshot_writer = snapshot_writer.lock().unwrap();
+    snapshot_writer.write(&Event::Schema(rows.columns))?;
+    let row_count = rows.rows.len();
+    for (key, values) in rows.rows {
+        snapshot_writer.write(&Event::Insert(key, values))?;
+    }
+    futures::executor::block_on(snapshot_writer.flush()).map_err(|_| Error::FlushCanceled)??;
+    info!("Imported {row_count} rows of input {persistent_id}");
+
+    // The snapshot is only read if worker 0 has advanced the time
+    let threshold_time = metadata.past_runs_threshold_times().get(&0).copied();
+    if threshold_time.unwrap_or(0) == 0 {
+        metadata.accept_finalized_timestamp(max(metadata.last_advanced_timestamp(), 1));
+        metadata.save_current_state()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// Copyright © 2024 Pathway
+
+use std::fmt;
+
+use openssl::rand::rand_bytes;
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "failed to decrypt persisted data: the key is wrong, or the data is corrupted or was moved"
+)]
+pub struct DecryptionError;
+
+#[derive(Debug, thiserror::Error)]
+#[error("the encryption key has to be {KEY_LEN} bytes long, got {0}")]
+pub struct InvalidKeyLength(usize);
+
+/// A key for encrypting the persisted data with AES-256-GCM.
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct EncryptionKey([u8; KEY_LEN]);
+
+impl EncryptionKey {
+    pub fn new(key: &[u8]) -> Result<Self, InvalidKeyLength> {
+        Ok(Self(
+            key.try_into().map_err(|_| InvalidKeyLength(key.len()))?,
+        ))
+    }
+
+    /// Encrypts `plaintext` with a random nonce. The result contains the nonce, the
+    /// authentication tag and the ciphertext.
+    ///
+    /// The associated data `aad` isn't stored, but the same has to be given to decrypt
+    /// the result. It binds the ciphertext to the place where it's stored, so that it
+    /// can't be moved elsewhere unnoticed.
+    pub fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> Vec<u8> {
+        let mut nonce = [0; NONCE_LEN];
+        rand_bytes(&mut nonce).expect("generating a nonce should not fail");
+        let mut tag = [0; TAG_LEN];
+        let ciphertext = encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.0,
+            Some(&nonce),
+            aad,
+            plaintext,
+            &mut tag,
+        )
+        .expect("encryption with a valid key should not fail");
+        let mut result = Vec::with_capacity(NONCE_LEN + TAG_LEN + ciphertext.len());
+        result.extend_from_slice(&nonce);
+        result.extend_from_slice(&tag);
+        result.extend_from_slice(&ciphertext);
+        result
+    }
+
+    pub fn decrypt(&self, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, DecryptionError> {
+        if data.len() < NONCE_LEN + TAG_LEN {
+            return Err(DecryptionError);
+        }
+        let (nonce, rest) = data.split_at(NONCE_LEN);
+        let (tag, ciphertext) = rest.split_at(TAG_LEN);
+        decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.0,
+            Some(nonce),
+            aad,
+            ciphertext,
+            tag,
+        )
+        .map_err(|_| DecryptionError)
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("EncryptionKey(..)")
+    }
+}
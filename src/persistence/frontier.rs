@@ -1,6 +1,6 @@
 // Copyright © 2024 Pathway
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -129,6 +129,11 @@ impl OffsetAntichainCollection {
         }
     }
 
+    pub fn retain_storages(&mut self, persistent_ids: &HashSet<PersistentId>) {
+        self.antichains
+            .retain(|persistent_id, _| persistent_ids.contains(persistent_id));
+    }
+
     pub fn antichain_for_storage(&self, persistent_id: PersistentId) -> OffsetAntichain {
         match self.antichains.get(&persistent_id) {
             Some(data) => data.clone(),
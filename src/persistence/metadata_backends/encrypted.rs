@@ -0,0 +1,85 @@
+// Copyright © 2024 Pathway
+
+use log::info;
+
+use crate::persistence::encryption::EncryptionKey;
+use crate::persistence::metadata_backends::{Error, MetadataBackend};
+
+const BASE32_ALPHABET: base32::Alphabet = base32::Alphabet::Crockford;
+const ENCRYPTED_VALUE_PREFIX: &str = "encrypted:";
+
+/// Encrypts the values stored in another backend. Each value is bound to its key, so
+/// it can't be read under another one.
+///
+/// The values written before the encryption was enabled are rejected, unless
+/// `migrate_unencrypted` is set. Then they are encrypted when the storage is created,
+/// so that a single run with it migrates the metadata.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct EncryptedKVStorage {
+    backend: Box<dyn MetadataBackend>,
+    key: EncryptionKey,
+}
+
+impl EncryptedKVStorage {
+    pub fn new(
+        backend: Box<dyn MetadataBackend>,
+        key: EncryptionKey,
+        migrate_unencrypted: bool,
+    ) -> Result<Self, Error> {
+        let mut storage = Self { backend, key };
+        if migrate_unencrypted {
+            storage.encrypt_unencrypted_values()?;
+        }
+        Ok(storage)
+    }
+
+    fn encrypt_unencrypted_values(&mut self) -> Result<(), Error> {
+        for key in self.backend.list_keys()? {
+            let value = self.backend.get_value(&key)?;
+            if !value.starts_with(ENCRYPTED_VALUE_PREFIX) {
+                info!("Encrypting the metadata entry {key:?} written without encryption");
+                self.put_value(&key, &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encrypt(&self, key: &str, value: &str) -> String {
+        let encrypted = self.key.encrypt(value.as_bytes(), key.as_bytes());
+        format!(
+            "{ENCRYPTED_VALUE_PREFIX}{}",
+            base32::encode(BASE32_ALPHABET, &encrypted)
+        )
+    }
+}
+
+impl MetadataBackend for EncryptedKVStorage {
+    fn list_keys(&self) -> Result<Vec<String>, Error> {
+        self.backend.list_keys()
+    }
+
+    fn get_value(&self, key: &str) -> Result<String, Error> {
+        let value = self.backend.get_value(key)?;
+        let Some(encrypted) = value.strip_prefix(ENCRYPTED_VALUE_PREFIX) else {
+            return Err(Error::Unencrypted(key.to_string()));
+        };
+        let decrypted = base32::decode(BASE32_ALPHABET, encrypted)
+            .ok_or(Error::Decryption(key.to_string()))
+            .and_then(|encrypted| {
+                self.key
+                    .decrypt(&encrypted, key.as_bytes())
+                    .map_err(|_| Error::Decryption(key.to_string()))
+            })?;
+        String::from_utf8(decrypted).map_err(|e| Error::Utf8(e.utf8_error()))
+    }
+
+    fn put_value(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let encrypted = self.encrypt(key, value);
+        self.backend.put_value(key, &encrypted)
+    }
+
+    fn remove_key(&mut self, key: &str) -> Result<(), Error> {
+        self.backend.remove_key(key)
+    }
+}
@@ -7,11 +7,17 @@ use std::str::Utf8Error;
 use ::s3::error::S3Error;
 use serde_json::Error as ParseError;
 
+use crate::persistence::object_storage::Error as ObjectStorageError;
+
+pub mod encrypted;
 pub mod file;
 pub mod mock;
+pub mod object_storage;
 pub mod s3;
+pub use encrypted::EncryptedKVStorage;
 pub use file::FilesystemKVStorage;
 pub use mock::MockKVStorage;
+pub use object_storage::ObjectStorageKVStorage;
 pub use s3::S3KVStorage;
 
 #[derive(Debug, thiserror::Error)]
@@ -23,15 +29,37 @@ pub enum Error {
     #[error(transparent)]
     S3(#[from] S3Error),
 
+    #[error(transparent)]
+    ObjectStorage(#[from] ObjectStorageError),
+
     #[error(transparent)]
     Utf8(#[from] Utf8Error),
 
     #[error("metadata entry {0:?} incorrectly formatted: {1}")]
     IncorrectFormat(String, #[source] ParseError),
+
+    #[error("failed to decrypt metadata entry {0:?}")]
+    Decryption(String),
+
+    #[error("metadata entry {0:?} isn't encrypted, it can be encrypted by a single run with the migration of unencrypted data enabled")]
+    Unencrypted(String),
+
+    #[error("metadata entry {0:?} is corrupted")]
+    Corrupted(String),
+
+    #[error("metadata entry checksum mismatch")]
+    ChecksumMismatch,
+
+    #[error("metadata entry has format version {0}, while the newest supported one is {1}, it was likely saved by a newer version of Pathway")]
+    UnsupportedFormatVersion(u32, u32),
+
+    #[error("savepoint {0:?} not found")]
+    SavepointNotFound(String),
 }
 
 pub trait MetadataBackend: Send + Debug {
     fn list_keys(&self) -> Result<Vec<String>, Error>;
     fn get_value(&self, key: &str) -> Result<String, Error>;
     fn put_value(&mut self, key: &str, value: &str) -> Result<(), Error>;
+    fn remove_key(&mut self, key: &str) -> Result<(), Error>;
 }
@@ -0,0 +1,58 @@
+// Copyright © 2024 Pathway
+
+use crate::persistence::metadata_backends::{Error, MetadataBackend};
+use crate::persistence::object_storage::SharedObjectStorage;
+
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ObjectStorageKVStorage {
+    storage: SharedObjectStorage,
+    root_path: String,
+}
+
+impl ObjectStorageKVStorage {
+    pub fn new(storage: SharedObjectStorage, root_path: &str) -> Self {
+        let mut root_path_prepared = root_path.to_string();
+        if !root_path.ends_with('/') {
+            root_path_prepared += "/";
+        }
+        Self {
+            storage,
+            root_path: root_path_prepared,
+        }
+    }
+
+    fn full_key_path(&self, key: &str) -> String {
+        self.root_path.clone() + key
+    }
+}
+
+impl MetadataBackend for ObjectStorageKVStorage {
+    fn list_keys(&self) -> Result<Vec<String>, Error> {
+        // The snapshots are stored in the directories under the root, next to the keys
+        Ok(self
+            .storage
+            .list(&self.root_path)?
+            .iter()
+            .filter_map(|key| key.strip_prefix(&self.root_path))
+            .filter(|key| !key.is_empty() && !key.contains('/'))
+            .map(ToString::to_string)
+            .collect())
+    }
+
+    fn get_value(&self, key: &str) -> Result<String, Error> {
+        let value = self.storage.get(&self.full_key_path(key))?;
+        Ok(std::str::from_utf8(&value)?.to_string())
+    }
+
+    fn put_value(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        self.storage
+            .put(&self.full_key_path(key), value.as_bytes().to_vec())?;
+        Ok(())
+    }
+
+    fn remove_key(&mut self, key: &str) -> Result<(), Error> {
+        self.storage.delete(&self.full_key_path(key))?;
+        Ok(())
+    }
+}
@@ -6,9 +6,15 @@ use xxhash_rust::xxh3::Xxh3 as Hasher;
 
 use crate::connectors::snapshot::SnapshotWriter;
 
+pub mod cluster;
 pub mod config;
+pub mod dump;
+pub mod encryption;
 pub mod frontier;
 pub mod metadata_backends;
+pub mod object_storage;
+pub mod prepared_output;
+pub mod schema;
 pub mod state;
 pub mod sync;
 pub mod tracker;
@@ -0,0 +1,264 @@
+// Copyright © 2024 Pathway
+
+//! Azure Blob Storage, accessed with its REST API. The requests are signed with the
+//! access key of the storage account, or authorized with a shared access signature.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::Utc;
+use openssl::base64;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use reqwest::{Client, Method, RequestBuilder, Url};
+
+use super::{Error, HttpClient, ObjectStorage};
+
+const API_VERSION: &str = "2021-08-06";
+const CONTENT_TYPE: &str = "application/octet-stream";
+
+#[derive(Clone)]
+pub enum AzureCredentials {
+    /// The access key of the storage account, signing the requests with the Shared Key
+    /// scheme.
+    AccountKey(String),
+    /// A shared access signature, added to the query of the requests.
+    SasToken(String),
+}
+
+impl fmt::Debug for AzureCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AccountKey(_) => f.write_str("AccountKey(..)"),
+            Self::SasToken(_) => f.write_str("SasToken(..)"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureBlobStorage {
+    account_name: String,
+    container_name: String,
+    endpoint: Url,
+    credentials: AzureCredentials,
+    http: HttpClient,
+}
+
+impl AzureBlobStorage {
+    /// Creates a client of the container `container_name` of the storage account
+    /// `account_name`. The blob service of the account is at `endpoint`, which defaults
+    /// to the one in the Azure public cloud.
+    pub fn new(
+        account_name: String,
+        container_name: String,
+        endpoint: Option<&str>,
+        credentials: AzureCredentials,
+    ) -> Result<Self, Error> {
+        let endpoint = match endpoint {
+            Some(endpoint) => endpoint.to_string(),
+            None => format!("https://{account_name}.blob.core.windows.net"),
+        };
+        let endpoint = Url::parse(&endpoint)
+            .ok()
+            .filter(|endpoint| !endpoint.cannot_be_a_base())
+            .ok_or_else(|| {
+                Error::Authentication(format!("invalid blob service endpoint {endpoint:?}"))
+            })?;
+        if let AzureCredentials::AccountKey(key) = &credentials {
+            base64::decode_block(key).map_err(|_| {
+                Error::Authentication("the account key isn't encoded in base64".to_string())
+            })?;
+        }
+        Ok(Self {
+            account_name,
+            container_name,
+            endpoint,
+            credentials,
+            http: HttpClient::new(),
+        })
+    }
+
+    fn url(&self, key: Option<&str>) -> Url {
+        let mut url = self.endpoint.clone();
+        {
+            let mut segments = url.path_segments_mut().unwrap();
+            segments.pop_if_empty().push(&self.container_name);
+            if let Some(key) = key {
+                segments.extend(key.split('/'));
+            }
+        }
+        url
+    }
+
+    /// Makes a request authorized with the credentials, whose body is `body`.
+    fn request(
+        &self,
+        client: &Client,
+        method: &Method,
+        mut url: Url,
+        body: Option<&[u8]>,
+    ) -> Result<RequestBuilder, Error> {
+        let mut headers = vec![
+            (
+                "x-ms-date",
+                Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ),
+            ("x-ms-version", API_VERSION.to_string()),
+        ];
+        if body.is_some() {
+            headers.push(("x-ms-blob-type", "BlockBlob".to_string()));
+        }
+        let authorization = match &self.credentials {
+            AzureCredentials::AccountKey(key) => {
+                Some(self.shared_key_authorization(key, method, &url, &headers, body)?)
+            }
+            AzureCredentials::SasToken(token) => {
+                let token = token.strip_prefix('?').unwrap_or(token);
+                let query = match url.query() {
+                    Some(query) => format!("{query}&{token}"),
+                    None => token.to_string(),
+                };
+                url.set_query(Some(&query));
+                None
+            }
+        };
+
+        let mut request = client.request(method.clone(), url);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        if let Some(authorization) = authorization {
+            request = request.header("Authorization", authorization);
+        }
+        if let Some(body) = body {
+            request = request
+                .header("Content-Type", CONTENT_TYPE)
+                .body(body.to_vec());
+        }
+        Ok(request)
+    }
+
+    /// The authorization of a request with the Shared Key scheme: the HMAC of its
+    /// method, its standard headers, its `x-ms-` headers and its resource, keyed with the
+    /// access key of the account.
+    fn shared_key_authorization(
+        &self,
+        key: &str,
+        method: &Method,
+        url: &Url,
+        headers: &[(&str, String)],
+        body: Option<&[u8]>,
+    ) -> Result<String, Error> {
+        // The length is left empty for the requests without a body
+        let content_length = body
+            .map(<[u8]>::len)
+            .filter(|len| *len > 0)
+            .map(|len| len.to_string())
+            .unwrap_or_default();
+        let content_type = if body.is_some() { CONTENT_TYPE } else { "" };
+        // The encoding, the language, the length, the MD5, the type, the date, the
+        // conditions and the range of the content
+        let mut string_to_sign =
+            format!("{method}\n\n\n{content_length}\n\n{content_type}\n\n\n\n\n\n\n");
+
+        let mut headers = headers.to_vec();
+        headers.sort_unstable();
+        for (name, value) in headers {
+            string_to_sign += &format!("{name}:{value}\n");
+        }
+
+        string_to_sign += &format!("/{}{}", self.account_name, url.path());
+        let mut parameters = BTreeMap::<String, Vec<String>>::new();
+        for (name, value) in url.query_pairs() {
+            parameters
+                .entry(name.to_lowercase())
+                .or_default()
+                .push(value.into_owned());
+        }
+        for (name, mut values) in parameters {
+            values.sort_unstable();
+            string_to_sign += &format!("\n{name}:{}", values.join(","));
+        }
+
+        let key = PKey::hmac(&base64::decode_block(key)?)?;
+        let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+        signer.update(string_to_sign.as_bytes())?;
+        let signature = base64::encode_block(&signer.sign_to_vec()?);
+        Ok(format!("SharedKey {}:{signature}", self.account_name))
+    }
+}
+
+/// Returns the unescaped texts of the elements `tag` in an XML `document`, which has no
+/// nested elements with the same name.
+fn xml_elements<'a>(document: &'a str, tag: &str) -> impl Iterator<Item = String> + 'a {
+    let start_tag = format!("<{tag}>");
+    let end_tag = format!("</{tag}>");
+    document.split(&start_tag).skip(1).filter_map(move |part| {
+        let text = &part[..part.find(&end_tag)?];
+        Some(
+            text.replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&"),
+        )
+    })
+}
+
+impl ObjectStorage for AzureBlobStorage {
+    fn list(&self, prefix: &str) -> Result<Vec<String>, Error> {
+        let mut keys = Vec::new();
+        let mut marker = None;
+        loop {
+            let mut url = self.url(None);
+            url.query_pairs_mut()
+                .append_pair("restype", "container")
+                .append_pair("comp", "list")
+                .append_pair("prefix", prefix);
+            if let Some(marker) = &marker {
+                url.query_pairs_mut().append_pair("marker", marker);
+            }
+            let response = self.http.send("list", prefix, |client| {
+                self.request(client, &Method::GET, url.clone(), None)
+            })?;
+            let response =
+                String::from_utf8(response).map_err(|e| Error::MalformedResponse(e.to_string()))?;
+            let Some((blobs, next_marker)) = response.split_once("<NextMarker") else {
+                return Err(Error::MalformedResponse(format!(
+                    "no marker in the list of blobs: {response}"
+                )));
+            };
+            keys.extend(xml_elements(blobs, "Name"));
+            // The marker is an empty element after the last page
+            marker = xml_elements(&format!("<NextMarker{next_marker}"), "NextMarker")
+                .next()
+                .filter(|marker| !marker.is_empty());
+            if marker.is_none() {
+                return Ok(keys);
+            }
+        }
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        self.http.send("get", key, |client| {
+            self.request(client, &Method::GET, self.url(Some(key)), None)
+        })
+    }
+
+    fn put(&self, key: &str, data: Vec<u8>) -> Result<(), Error> {
+        self.http.send("put", key, |client| {
+            self.request(client, &Method::PUT, self.url(Some(key)), Some(&data))
+        })?;
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Error> {
+        match self.http.send("delete", key, |client| {
+            self.request(client, &Method::DELETE, self.url(Some(key)), None)
+        }) {
+            Err(e) if e.is_not_found() => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+// Copyright © 2024 Pathway
+
+//! Google Cloud Storage, accessed with its JSON API. The requests are authorized with
+//! OAuth 2.0 access tokens, obtained for a service account key, for the credentials of
+//! a user saved by `gcloud auth application-default login`, or from the metadata server
+//! of the machine running on Google Cloud.
+
+use std::env;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use openssl::base64;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::Deserialize;
+
+use super::{Error, HttpClient, ObjectStorage};
+
+const DEFAULT_ENDPOINT: &str = "https://storage.googleapis.com";
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+const DEFAULT_METADATA_HOST: &str = "metadata.google.internal";
+const SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_write";
+const JWT_BEARER_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+const ASSERTION_LIFETIME: Duration = Duration::from_secs(3600);
+// A token is refreshed a bit before it expires, so that it stays valid for the request
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// The credentials in the JSON format of the key files of service accounts and of the
+/// application default credentials.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CredentialsFile {
+    ServiceAccount {
+        client_email: String,
+        private_key: String,
+        #[serde(default = "default_token_uri")]
+        token_uri: String,
+    },
+    AuthorizedUser {
+        client_id: String,
+        client_secret: String,
+        refresh_token: String,
+    },
+}
+
+fn default_token_uri() -> String {
+    DEFAULT_TOKEN_URI.to_string()
+}
+
+#[derive(Clone)]
+pub enum GcsCredentials {
+    /// A service account key or the credentials of a user, as saved in their JSON
+    /// files.
+    Json(String),
+    /// The service account of the machine, whose tokens are given by the metadata
+    /// server of Compute Engine, Google Kubernetes Engine or Cloud Run.
+    MetadataServer,
+}
+
+impl fmt::Debug for GcsCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(_) => f.write_str("Json(..)"),
+            Self::MetadataServer => f.write_str("MetadataServer"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Deserialize)]
+struct ObjectList {
+    #[serde(default)]
+    items: Vec<ObjectListItem>,
+    #[serde(rename = "nextPageToken")]
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ObjectListItem {
+    name: String,
+}
+
+fn base64_url(data: &[u8]) -> String {
+    base64::encode_block(data)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Failed to get the current timestamp")
+        .as_secs()
+}
+
+pub struct GcsStorage {
+    bucket_name: String,
+    endpoint: Url,
+    credentials: Option<CredentialsFile>,
+    token: Mutex<Option<(String, Instant)>>,
+    http: HttpClient,
+}
+
+impl fmt::Debug for GcsStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GcsStorage")
+            .field("bucket_name", &self.bucket_name)
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GcsStorage {
+    /// Creates a client of the bucket `bucket_name`, served at `endpoint`, which
+    /// defaults to the one of Google Cloud.
+    pub fn new(
+        bucket_name: String,
+        endpoint: Option<&str>,
+        credentials: GcsCredentials,
+    ) -> Result<Self, Error> {
+        let endpoint = endpoint.unwrap_or(DEFAULT_ENDPOINT);
+        let endpoint = Url::parse(endpoint)
+            .ok()
+            .filter(|endpoint| !endpoint.cannot_be_a_base())
+            .ok_or_else(|| Error::Authentication(format!("invalid endpoint {endpoint:?}")))?;
+        let credentials = match credentials {
+            GcsCredentials::Json(json) => {
+                let credentials: CredentialsFile = serde_json::from_str(&json)
+                    .map_err(|e| Error::Authentication(format!("unsupported credentials: {e}")))?;
+                if let CredentialsFile::ServiceAccount { private_key, .. } = &credentials {
+                    PKey::private_key_from_pem(private_key.as_bytes())?;
+                }
+                Some(credentials)
+            }
+            GcsCredentials::MetadataServer => None,
+        };
+        Ok(Self {
+            bucket_name,
+            endpoint,
+            credentials,
+            token: Mutex::new(None),
+            http: HttpClient::new(),
+        })
+    }
+
+    /// Returns a valid access token, requesting a new one once the last one is about to
+    /// expire.
+    fn access_token(&self) -> Result<String, Error> {
+        let mut token = self.token.lock().unwrap();
+        if let Some((access_token, expires_at)) = &*token {
+            if Instant::now() + TOKEN_EXPIRY_MARGIN < *expires_at {
+                return Ok(access_token.clone());
+            }
+        }
+        let requested_at = Instant::now();
+        let response = self
+            .http
+            .send("authentication", "", |client| self.token_request(client))?;
+        let response: TokenResponse = serde_json::from_slice(&response)
+            .map_err(|e| Error::Authentication(format!("malformed token: {e}")))?;
+        *token = Some((
+            response.access_token.clone(),
+            requested_at + Duration::from_secs(response.expires_in),
+        ));
+        Ok(response.access_token)
+    }
+
+    fn token_request(&self, client: &Client) -> Result<RequestBuilder, Error> {
+        Ok(match &self.credentials {
+            Some(CredentialsFile::ServiceAccount {
+                client_email,
+                private_key,
+                token_uri,
+            }) => {
+                let assertion = Self::signed_assertion(client_email, private_key, token_uri)?;
+                client.post(token_uri).form(&[
+                    ("grant_type", JWT_BEARER_GRANT_TYPE),
+                    ("assertion", assertion.as_str()),
+                ])
+            }
+            Some(CredentialsFile::AuthorizedUser {
+                client_id,
+                client_secret,
+                refresh_token,
+            }) => client.post(DEFAULT_TOKEN_URI).form(&[
+                ("grant_type", "refresh_token"),
+                ("client_id", client_id.as_str()),
+                ("client_secret", client_secret.as_str()),
+                ("refresh_token", refresh_token.as_str()),
+            ]),
+            None => {
+                let host = env::var("GCE_METADATA_HOST")
+                    .unwrap_or_else(|_| DEFAULT_METADATA_HOST.to_string());
+                client
+                    .get(format!(
+                        "http://{host}/computeMetadata/v1/instance/service-accounts/default/token"
+                    ))
+                    .header("Metadata-Flavor", "Google")
+            }
+        })
+    }
+
+    /// The JSON Web Token asserting the identity of the service account, signed with
+    /// its private key, which is exchanged for an access token.
+    fn signed_assertion(
+        client_email: &str,
+        private_key: &str,
+        token_uri: &str,
+    ) -> Result<String, Error> {
+        let issued_at = unix_time();
+        let header = base64_url(br#"{"alg":"RS256","typ":"JWT"}"#);
+        let claims = serde_json::json!({
+            "iss": client_email,
+            "scope": SCOPE,
+            "aud": token_uri,
+            "iat": issued_at,
+            "exp": issued_at + ASSERTION_LIFETIME.as_secs(),
+        });
+        let claims = base64_url(claims.to_string().as_bytes());
+        let unsigned = format!("{header}.{claims}");
+
+        let key = PKey::private_key_from_pem(private_key.as_bytes())?;
+        let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+        signer.update(unsigned.as_bytes())?;
+        Ok(format!("{unsigned}.{}", base64_url(&signer.sign_to_vec()?)))
+    }
+
+    fn url(&self, path: &[&str]) -> Url {
+        let mut url = self.endpoint.clone();
+        url.path_segments_mut().unwrap().pop_if_empty().extend(path);
+        url
+    }
+
+    /// The URL of the object `key`, whose slashes are encoded, as it's a single segment.
+    fn object_url(&self, key: &str) -> Url {
+        self.url(&["storage", "v1", "b", &self.bucket_name, "o", key])
+    }
+}
+
+impl ObjectStorage for GcsStorage {
+    fn list(&self, prefix: &str) -> Result<Vec<String>, Error> {
+        let mut keys = Vec::new();
+        let mut page_token = None;
+        loop {
+            let mut url = self.url(&["storage", "v1", "b", &self.bucket_name, "o"]);
+            url.query_pairs_mut()
+                .append_pair("prefix", prefix)
+                .append_pair("fields", "items(name),nextPageToken");
+            if let Some(page_token) = &page_token {
+                url.query_pairs_mut().append_pair("pageToken", page_token);
+            }
+            let response = self.http.send("list", prefix, |client| {
+                Ok(client.get(url.clone()).bearer_auth(self.access_token()?))
+            })?;
+            let response: ObjectList = serde_json::from_slice(&response)
+                .map_err(|e| Error::MalformedResponse(e.to_string()))?;
+            keys.extend(response.items.into_iter().map(|item| item.name));
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                return Ok(keys);
+            }
+        }
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        let mut url = self.object_url(key);
+        url.query_pairs_mut().append_pair("alt", "media");
+        self.http.send("get", key, |client| {
+            Ok(client.get(url.clone()).bearer_auth(self.access_token()?))
+        })
+    }
+
+    fn put(&self, key: &str, data: Vec<u8>) -> Result<(), Error> {
+        let mut url = self.url(&["upload", "storage", "v1", "b", &self.bucket_name, "o"]);
+        url.query_pairs_mut()
+            .append_pair("uploadType", "media")
+            .append_pair("name", key);
+        self.http.send("put", key, |client| {
+            Ok(client
+                .post(url.clone())
+                .bearer_auth(self.access_token()?)
+                .header("Content-Type", "application/octet-stream")
+                .body(data.clone()))
+        })?;
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Error> {
+        let url = self.object_url(key);
+        match self.http.send("delete", key, |client| {
+            Ok(client.delete(url.clone()).bearer_auth(self.access_token()?))
+        }) {
+            Err(e) if e.is_not_found() => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright © 2024 Pathway
+
+//! The storages of objects without an S3-compatible API, accessed with their REST APIs:
+//! Azure Blob Storage and Google Cloud Storage. The metadata and the snapshots are kept
+//! in them with the same layout as in S3.
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::thread::sleep;
+use std::time::Duration;
+
+use log::warn;
+use openssl::error::ErrorStack;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use tokio::runtime::Runtime;
+
+pub mod azure;
+pub mod gcs;
+
+pub use azure::{AzureBlobStorage, AzureCredentials};
+pub use gcs::{GcsCredentials, GcsStorage};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_ATTEMPTS: u32 = 5;
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("request to the object storage failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("{method} of {key:?} failed with status {status}: {body}")]
+    Status {
+        method: &'static str,
+        key: String,
+        status: StatusCode,
+        body: String,
+    },
+
+    #[error("failed to authenticate to the object storage: {0}")]
+    Authentication(String),
+
+    #[error("malformed response of the object storage: {0}")]
+    MalformedResponse(String),
+
+    #[error(transparent)]
+    Openssl(#[from] ErrorStack),
+}
+
+impl Error {
+    fn is_not_found(&self) -> bool {
+        matches!(self, Self::Status { status, .. } if *status == StatusCode::NOT_FOUND)
+    }
+}
+
+/// A storage of objects, which are read and written as a whole.
+pub trait ObjectStorage: Send + Sync + Debug {
+    /// Returns the keys of all objects starting with `prefix`.
+    fn list(&self, prefix: &str) -> Result<Vec<String>, Error>;
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, Error>;
+
+    /// Stores `data` as the object `key`, replacing the previous one.
+    fn put(&self, key: &str, data: Vec<u8>) -> Result<(), Error>;
+
+    /// Removes the object `key`. A missing object isn't an error.
+    fn delete(&self, key: &str) -> Result<(), Error>;
+}
+
+pub type SharedObjectStorage = Arc<dyn ObjectStorage>;
+
+/// Sends the requests of a client of an object storage, blocking until they finish.
+#[derive(Debug)]
+struct HttpClient {
+    client: Client,
+    runtime: Runtime,
+}
+
+impl HttpClient {
+    fn new() -> Self {
+        Self {
+            client: Client::new(),
+            runtime: tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// Sends the request made by `request`, retrying it after the failures that may be
+    /// transient: the failed connections, the throttling and the errors of the server.
+    /// The request is made again for every attempt, so that its signature or its token
+    /// stay valid. Returns the body of the response if its status is a success.
+    fn send(
+        &self,
+        method: &'static str,
+        key: &str,
+        request: impl Fn(&Client) -> Result<RequestBuilder, Error>,
+    ) -> Result<Vec<u8>, Error> {
+        let mut delay = INITIAL_RETRY_DELAY;
+        let mut attempt = 1;
+        loop {
+            let request = request(&self.client)?.timeout(REQUEST_TIMEOUT);
+            let result = self.runtime.block_on(async {
+                let response = request.send().await?;
+                let status = response.status();
+                let body = response.bytes().await?;
+                if status.is_success() {
+                    Ok(body.to_vec())
+                } else {
+                    Err(Error::Status {
+                        method,
+                        key: key.to_string(),
+                        status,
+                        body: String::from_utf8_lossy(&body).into_owned(),
+                    })
+                }
+            });
+            let transient = match &result {
+                Ok(_) => false,
+                Err(Error::Request(e)) => e.is_connect() || e.is_timeout(),
+                Err(Error::Status { status, .. }) => {
+                    status.is_server_error()
+                        || *status == StatusCode::TOO_MANY_REQUESTS
+                        || *status == StatusCode::REQUEST_TIMEOUT
+                }
+                Err(_) => false,
+            };
+            if !transient || attempt == MAX_ATTEMPTS {
+                return result;
+            }
+            warn!(
+                "{method} of {key:?} failed, retrying in {delay:?}: {}",
+                result.unwrap_err()
+            );
+            sleep(delay);
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+}
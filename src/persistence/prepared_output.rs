@@ -0,0 +1,93 @@
+// Copyright © 2024 Pathway
+
+//! The output of the epochs prepared by the transactional sinks whose external system
+//! can't keep it until the checkpoint is saved, like Kafka, which doesn't allow resuming
+//! a transaction after a restart.
+//!
+//! An epoch is saved in the metadata storage before the checkpoint covering it is
+//! committed and removed once it's committed to the external system. After a crash in
+//! between, the next run finds it there and commits it instead of losing it.
+
+#![allow(clippy::module_name_repetitions)]
+
+use crate::persistence::metadata_backends::{Error, MetadataBackend};
+
+const BASE32_ALPHABET: base32::Alphabet = base32::Alphabet::Crockford;
+
+/// The prefix of the keys of the prepared epochs in the metadata storage, which are
+/// not checkpoints.
+pub const PREPARED_OUTPUT_KEY_PREFIX: &str = "prepared-output-";
+
+/// The epochs prepared by one sink, kept in the metadata storage.
+#[derive(Debug)]
+pub struct PreparedOutputStorage {
+    backend: Box<dyn MetadataBackend>,
+    key_prefix: String,
+}
+
+impl PreparedOutputStorage {
+    /// Creates the storage of the epochs of the sink `sink_name`, which has to identify
+    /// the sink between the runs.
+    pub fn new(backend: Box<dyn MetadataBackend>, sink_name: &str) -> Self {
+        // The name is encoded, as the keys become file names and object keys
+        let encoded_name = base32::encode(BASE32_ALPHABET, sink_name.as_bytes());
+        Self {
+            backend,
+            key_prefix: format!("{PREPARED_OUTPUT_KEY_PREFIX}{encoded_name}-"),
+        }
+    }
+
+    fn key(&self, epoch: u64) -> String {
+        format!("{}{epoch}", self.key_prefix)
+    }
+
+    pub fn save(&mut self, epoch: u64, data: &[u8]) -> Result<(), Error> {
+        let key = self.key(epoch);
+        self.backend
+            .put_value(&key, &base32::encode(BASE32_ALPHABET, data))
+    }
+
+    pub fn remove(&mut self, epoch: u64) -> Result<(), Error> {
+        let key = self.key(epoch);
+        self.backend.remove_key(&key)
+    }
+
+    /// Returns the epochs saved by the previous runs, in increasing order.
+    pub fn saved_epochs(&self) -> Result<Vec<u64>, Error> {
+        let mut epochs: Vec<u64> = self
+            .backend
+            .list_keys()?
+            .iter()
+            .filter_map(|key| key.strip_prefix(&self.key_prefix)?.parse().ok())
+            .collect();
+        epochs.sort_unstable();
+        Ok(epochs)
+    }
+
+    /// Returns the data of the epochs of the previous runs that have to be committed
+    /// after a restart: the ones covered by the last checkpoint, saved at
+    /// `last_finalized_time`, and not committed to the external system, which has
+    /// everything up to `last_committed_epoch`. The other epochs are removed, as they
+    /// are either already committed or produced again from the replayed data.
+    pub fn recover(
+        &mut self,
+        last_finalized_time: u64,
+        last_committed_epoch: Option<u64>,
+    ) -> Result<Vec<(u64, Vec<u8>)>, Error> {
+        let mut recovered = Vec::new();
+        for epoch in self.saved_epochs()? {
+            let committed = last_committed_epoch.is_some_and(|committed| epoch <= committed);
+            if committed || epoch > last_finalized_time {
+                self.remove(epoch)?;
+                continue;
+            }
+            let key = self.key(epoch);
+            let encoded = self.backend.get_value(&key)?;
+            // A checkpoint is committed only after its epochs are saved, so the data of
+            // a covered epoch is complete unless the storage is damaged
+            let data = base32::decode(BASE32_ALPHABET, &encoded).ok_or(Error::Corrupted(key))?;
+            recovered.push((epoch, data));
+        }
+        Ok(recovered)
+    }
+}
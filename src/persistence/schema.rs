@@ -0,0 +1,199 @@
+// Copyright © 2024 Pathway
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::collections::HashMap;
+
+use log::info;
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{Type, Value};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaMigrationError {
+    #[error("the type of column {0:?} changed from {1:?} to {2:?}, which is not a compatible change of the persisted state")]
+    IncompatibleType(String, Type, Type),
+
+    #[error("a persisted row has {0} values, while {1} were expected")]
+    RowLength(usize, usize),
+}
+
+/// A column of an input, as it is saved in the snapshot next to the rows.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistedColumn {
+    pub name: String,
+    pub type_: Type,
+}
+
+/// A column of the current schema of an input.
+#[derive(Clone, Debug)]
+pub struct SchemaColumn {
+    pub name: String,
+    pub type_: Type,
+    pub default: Option<Value>,
+}
+
+/// Describes how a column changed since its values were persisted.
+#[derive(Clone, Debug, Default)]
+pub struct ColumnMigration {
+    /// The name of the column in the persisted state, if the column was renamed.
+    pub renamed_from: Option<String>,
+
+    /// The value for the persisted rows that don't have the column. If not set, the
+    /// default value of the column is used, or `None` if it has no default.
+    pub fill_value: Option<Value>,
+}
+
+/// The current schema of an input together with the migrations of its columns.
+#[derive(Clone, Debug)]
+pub struct InputSchema {
+    columns: Vec<SchemaColumn>,
+    migrations: HashMap<String, ColumnMigration>,
+}
+
+impl InputSchema {
+    pub fn new(columns: Vec<SchemaColumn>, migrations: HashMap<String, ColumnMigration>) -> Self {
+        Self {
+            columns,
+            migrations,
+        }
+    }
+
+    pub fn persisted_columns(&self) -> Vec<PersistedColumn> {
+        self.columns
+            .iter()
+            .map(|column| PersistedColumn {
+                name: column.name.clone(),
+                type_: column.type_,
+            })
+            .collect()
+    }
+
+    pub fn migrator(&self) -> SchemaMigrator {
+        SchemaMigrator {
+            schema: self,
+            plan: None,
+        }
+    }
+
+    fn fill_value(&self, column: &SchemaColumn) -> Value {
+        self.migrations
+            .get(&column.name)
+            .and_then(|migration| migration.fill_value.clone())
+            .or_else(|| column.default.clone())
+            .unwrap_or(Value::None)
+    }
+
+    fn persisted_name<'a>(&'a self, column: &'a SchemaColumn) -> &'a str {
+        self.migrations
+            .get(&column.name)
+            .and_then(|migration| migration.renamed_from.as_deref())
+            .unwrap_or(&column.name)
+    }
+}
+
+#[derive(Debug)]
+enum ColumnSource {
+    Persisted(usize),
+    Filled(Value),
+}
+
+/// Converts the persisted rows of an input to its current schema.
+///
+/// The snapshot contains the schema of the rows persisted after it, so the rows are
+/// matched with the current columns by name. The rows persisted before the schemas
+/// were saved are matched by position, with the columns added at the end filled.
+pub struct SchemaMigrator<'a> {
+    schema: &'a InputSchema,
+    plan: Option<(Vec<ColumnSource>, usize)>,
+}
+
+impl<'a> SchemaMigrator<'a> {
+    /// Sets the schema of the rows that follow in the snapshot.
+    pub fn set_persisted_schema(
+        &mut self,
+        persisted_columns: &[PersistedColumn],
+    ) -> Result<(), SchemaMigrationError> {
+        let mut plan = Vec::with_capacity(self.schema.columns.len());
+        for column in &self.schema.columns {
+            let persisted_name = self.schema.persisted_name(column);
+            let persisted_column = persisted_columns
+                .iter()
+                .enumerate()
+                .find(|(_, persisted_column)| persisted_column.name == persisted_name);
+            if let Some((index, persisted_column)) = persisted_column {
+                if !is_compatible_type_change(persisted_column.type_, column.type_) {
+                    return Err(SchemaMigrationError::IncompatibleType(
+                        column.name.clone(),
+                        persisted_column.type_,
+                        column.type_,
+                    ));
+                }
+                plan.push(ColumnSource::Persisted(index));
+            } else {
+                let fill_value = self.schema.fill_value(column);
+                info!(
+                    "Column {:?} is not in the persisted state, using {fill_value:?} for it",
+                    column.name
+                );
+                plan.push(ColumnSource::Filled(fill_value));
+            }
+        }
+        self.plan = Some((plan, persisted_columns.len()));
+        Ok(())
+    }
+
+    pub fn migrate(&self, values: Vec<Value>) -> Result<Vec<Value>, SchemaMigrationError> {
+        let columns = &self.schema.columns;
+        let values = match &self.plan {
+            Some((plan, persisted_column_count)) => {
+                if values.len() != *persisted_column_count {
+                    return Err(SchemaMigrationError::RowLength(
+                        values.len(),
+                        *persisted_column_count,
+                    ));
+                }
+                plan.iter()
+                    .map(|source| match source {
+                        ColumnSource::Persisted(index) => values[*index].clone(),
+                        ColumnSource::Filled(value) => value.clone(),
+                    })
+                    .collect()
+            }
+            None => {
+                if values.len() > columns.len() {
+                    return Err(SchemaMigrationError::RowLength(values.len(), columns.len()));
+                }
+                let missing_columns = &columns[values.len()..];
+                let mut values = values;
+                values.extend(
+                    missing_columns
+                        .iter()
+                        .map(|column| self.schema.fill_value(column)),
+                );
+                values
+            }
+        };
+        Ok(values
+            .into_iter()
+            .zip(columns)
+            .map(|(value, column)| widen(value, column.type_))
+            .collect())
+    }
+}
+
+fn is_compatible_type_change(persisted_type: Type, current_type: Type) -> bool {
+    persisted_type == current_type
+        || persisted_type == Type::Any
+        || current_type == Type::Any
+        || (persisted_type == Type::Int && current_type == Type::Float)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn widen(value: Value, type_: Type) -> Value {
+    match (value, type_) {
+        (Value::Int(value), Type::Float) => Value::Float(OrderedFloat(value as f64)),
+        (value, _) => value,
+    }
+}
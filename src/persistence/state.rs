@@ -1,28 +1,94 @@
 // Copyright © 2024 Pathway
 
 use log::{error, info, warn};
-use std::cmp::max;
-use std::collections::HashMap;
+use std::cmp::{max, min, Reverse};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
-use std::mem::swap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::mem::{swap, take};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
 use crate::connectors::data_storage::StorageType;
 use crate::connectors::{OffsetKey, OffsetValue};
+use crate::persistence::cluster::CLUSTER_CHECKPOINT_KEY_PREFIX;
 use crate::persistence::frontier::OffsetAntichain;
 use crate::persistence::frontier::OffsetAntichainCollection;
 use crate::persistence::metadata_backends::{Error, MetadataBackend};
+use crate::persistence::prepared_output::PREPARED_OUTPUT_KEY_PREFIX;
 use crate::persistence::PersistentId;
+use crate::timestamp::current_unix_timestamp_ms;
 
 const EXPECTED_KEY_PARTS: usize = 3;
+const SAVEPOINT_KEY_PREFIX: &str = "savepoint-";
+// Separates the serialized state from its checksum. A serialized state never contains
+// a newline, as the newlines in strings are escaped.
+const CHECKSUM_SEPARATOR: &str = "\n#crc32:";
+const FORMAT_VERSION_FIELD: &str = "format_version";
+
+/// The version of the format of the stored metadata. It has to be increased on every
+/// change of `StoredMetadata` that the previous versions of the engine can't read,
+/// together with adding the conversion from the previous version to `upgrade_format`.
+///
+/// The versions are:
+/// - 0: the blocks saved before the format was versioned;
+/// - 1: the version is saved in the block.
+pub const METADATA_FORMAT_VERSION: u32 = 1;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StoredMetadata {
+    #[serde(default)]
+    format_version: u32,
     frontiers: OffsetAntichainCollection,
     storage_types: HashMap<PersistentId, StorageType>,
     last_advanced_timestamp: u64,
+    /// The time when the block was saved in milliseconds since the epoch. The blocks
+    /// saved by the older versions don't have it.
+    #[serde(default)]
+    saved_at: Option<u128>,
+    /// The time of the checkpoint agreed by the processes of a cluster. Only the blocks
+    /// saved in a cluster have it.
+    #[serde(default)]
+    agreed_time: Option<u64>,
+}
+
+/// Decides which checkpoints, that is the metadata blocks saved by the commits of the
+/// frontiers, are kept. Each new checkpoint contains the whole state, so the older ones
+/// are only needed to fall back to, if the newer ones are broken.
+///
+/// If neither of the limits is set, all checkpoints are kept. Otherwise, a checkpoint is
+/// kept if it is one of the last `keep_last_checkpoints` ones of its worker or if it was
+/// made within `keep_checkpoints_for`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetentionPolicy {
+    pub keep_last_checkpoints: Option<usize>,
+    pub keep_checkpoints_for: Option<Duration>,
+}
+
+impl RetentionPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.keep_last_checkpoints.is_some() || self.keep_checkpoints_for.is_some()
+    }
+
+    fn retains(&self, newer_checkpoints: usize, age: Duration) -> bool {
+        !self.is_enabled()
+            || self
+                .keep_last_checkpoints
+                .is_some_and(|keep_last_checkpoints| newer_checkpoints < keep_last_checkpoints)
+            || self
+                .keep_checkpoints_for
+                .is_some_and(|keep_checkpoints_for| age < keep_checkpoints_for)
+    }
+}
+
+/// A checkpoint of this worker saved in one of the previous runs.
+#[derive(Debug)]
+struct PastCheckpoint {
+    key: String,
+    saved_at: u128,
+    last_advanced_timestamp: u64,
+    persistent_ids: HashSet<PersistentId>,
 }
 
 #[derive(Debug)]
@@ -30,28 +96,95 @@ pub struct MetadataAccessor {
     backend: Box<dyn MetadataBackend>,
     internal_state: StoredMetadata,
     past_runs_threshold_times: HashMap<usize, u64>,
+    worker_id: usize,
 
     current_key_to_use: String,
     next_key_to_use: String,
+
+    retention_policy: RetentionPolicy,
+    // Sorted from the newest one
+    past_checkpoints: Vec<PastCheckpoint>,
+    // The inputs referenced by the checkpoints of the other workers and by the savepoints
+    external_references: HashSet<PersistentId>,
+    registered_inputs: HashSet<PersistentId>,
+    current_run_checkpoints: usize,
 }
 
 impl StoredMetadata {
     pub fn new() -> Self {
         Self {
+            format_version: METADATA_FORMAT_VERSION,
             frontiers: OffsetAntichainCollection::new(),
             storage_types: HashMap::new(),
             last_advanced_timestamp: 0,
+            saved_at: None,
+            agreed_time: None,
         }
     }
 
     pub fn parse(data: &str) -> Result<Self, Error> {
-        let result = serde_json::from_str::<StoredMetadata>(data.trim_end())
+        let data = data.trim_end();
+        let serialized_state = match data.rsplit_once(CHECKSUM_SEPARATOR) {
+            Some((serialized_state, checksum)) => {
+                let checksum = u32::from_str_radix(checksum, 16).ok();
+                if checksum != Some(crc32fast::hash(serialized_state.as_bytes())) {
+                    return Err(Error::ChecksumMismatch);
+                }
+                serialized_state
+            }
+            // The blocks saved before the checksums were introduced don't have them
+            None => data,
+        };
+        let state = serde_json::from_str::<JsonValue>(serialized_state)
+            .map_err(|e| Error::IncorrectFormat(data.to_string(), e))?;
+        let state = Self::upgrade_format(state)?;
+        let result = serde_json::from_value::<StoredMetadata>(state)
             .map_err(|e| Error::IncorrectFormat(data.to_string(), e))?;
         Ok(result)
     }
 
+    /// Converts the serialized state saved in an older format to the current one, one
+    /// version at a time.
+    fn upgrade_format(mut state: JsonValue) -> Result<JsonValue, Error> {
+        let format_version = state
+            .get(FORMAT_VERSION_FIELD)
+            .and_then(JsonValue::as_u64)
+            .map_or(0, |version| u32::try_from(version).unwrap_or(u32::MAX));
+        if format_version > METADATA_FORMAT_VERSION {
+            return Err(Error::UnsupportedFormatVersion(
+                format_version,
+                METADATA_FORMAT_VERSION,
+            ));
+        }
+        for version in format_version..METADATA_FORMAT_VERSION {
+            info!(
+                "Upgrade the metadata block from format version {version} to {}",
+                version + 1
+            );
+            // The conversions of the contents from the older versions go here. The
+            // version 1 didn't change anything except for saving the version itself.
+            if let Some(fields) = state.as_object_mut() {
+                fields.insert(FORMAT_VERSION_FIELD.to_string(), (version + 1).into());
+            }
+        }
+        Ok(state)
+    }
+
     pub fn serialize(&self) -> String {
-        serde_json::to_string(&self).unwrap()
+        let serialized_state = serde_json::to_string(&self).unwrap();
+        let checksum = crc32fast::hash(serialized_state.as_bytes());
+        format!("{serialized_state}{CHECKSUM_SEPARATOR}{checksum:08x}")
+    }
+
+    fn persistent_ids(&self) -> HashSet<PersistentId> {
+        self.storage_types.keys().copied().collect()
+    }
+
+    /// Forgets the state of the inputs other than `persistent_ids`.
+    fn retain_inputs(&mut self, persistent_ids: &HashSet<PersistentId>) {
+        self.storage_types
+            .retain(|persistent_id, _| persistent_ids.contains(persistent_id));
+        self.frontiers.retain_storages(persistent_ids);
     }
 
     pub fn merge(&mut self, other: StoredMetadata) {
@@ -118,14 +251,97 @@ impl Display for MetadataKey {
     }
 }
 
+fn savepoint_key(name: &str, worker_id: usize) -> String {
+    format!("{SAVEPOINT_KEY_PREFIX}{name}-{worker_id}")
+}
+
+/// Returns the name of the savepoint and the worker id stored in the key of a savepoint
+/// block.
+fn parse_savepoint_key(key: &str) -> Option<(&str, usize)> {
+    let (name, worker_id) = key.strip_prefix(SAVEPOINT_KEY_PREFIX)?.rsplit_once('-')?;
+    Some((name, worker_id.parse().ok()?))
+}
+
+/// Savepoint names become a part of metadata keys, so only the characters safe in file
+/// names and object keys are allowed.
+pub fn is_valid_savepoint_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Makes the state saved in the savepoint `name` the current one, so that the next run
+/// starts from it. The state saved after the savepoint is removed, while the
+/// savepoints themselves are kept.
+pub fn restore_savepoint(backend: &mut dyn MetadataBackend, name: &str) -> Result<(), Error> {
+    let keys = backend.list_keys()?;
+    let savepoint_blocks: Vec<_> = keys
+        .iter()
+        .filter_map(|key| {
+            let (savepoint_name, worker_id) = parse_savepoint_key(key)?;
+            (savepoint_name == name).then_some((worker_id, key))
+        })
+        .collect();
+    if savepoint_blocks.is_empty() {
+        return Err(Error::SavepointNotFound(name.to_string()));
+    }
+
+    // The blocks of the savepoint are copied before the current ones are removed, so
+    // that a failure in between leaves a state that can be restored again
+    let current_timestamp = current_unix_timestamp_ms();
+    let mut restored_keys = Vec::new();
+    for (worker_id, key) in savepoint_blocks {
+        let block = backend.get_value(key)?;
+        let restored_key =
+            MetadataKey::from_components(current_timestamp, worker_id, 0).to_string();
+        backend.put_value(&restored_key, &block)?;
+        restored_keys.push(restored_key);
+    }
+    for key in &keys {
+        if !key.starts_with(SAVEPOINT_KEY_PREFIX)
+            && !restored_keys.contains(key)
+            && MetadataKey::from_str(key).is_some()
+        {
+            info!("Remove the metadata block {key} saved after the savepoint {name:?}");
+            backend.remove_key(key)?;
+        }
+    }
+    Ok(())
+}
+
 impl MetadataAccessor {
-    pub fn new(backend: Box<dyn MetadataBackend>, worker_id: usize) -> Result<Self, Error> {
+    pub fn new(
+        backend: Box<dyn MetadataBackend>,
+        worker_id: usize,
+        retention_policy: RetentionPolicy,
+    ) -> Result<Self, Error> {
+        let mut past_checkpoints = Vec::new();
+        let mut external_references = HashSet::new();
         let (internal_state, past_runs_threshold_times) = {
             let mut internal_state = StoredMetadata::new();
             let mut past_runs_threshold_times = HashMap::new();
+            let mut broken_keys_by_worker: HashMap<usize, Vec<String>> = HashMap::new();
 
+            let mut blocks = Vec::new();
             let keys = backend.list_keys()?;
             for key in keys {
+                if key.starts_with(PREPARED_OUTPUT_KEY_PREFIX)
+                    || key.starts_with(CLUSTER_CHECKPOINT_KEY_PREFIX)
+                {
+                    // The output prepared by the transactional sinks is read by the sinks
+                    // and the agreement of the processes of a cluster only by themselves
+                    continue;
+                }
+                if key.starts_with(SAVEPOINT_KEY_PREFIX) {
+                    // The savepoints are only needed to know which snapshots they use
+                    if retention_policy.is_enabled() {
+                        if let Ok(block) = StoredMetadata::parse(&backend.get_value(&key)?) {
+                            external_references.extend(block.persistent_ids());
+                        }
+                    }
+                    continue;
+                }
                 let metadata_key = MetadataKey::from_str(&key);
                 let Some(metadata_key) = metadata_key else {
                     continue;
@@ -135,24 +351,75 @@ impl MetadataAccessor {
                 let raw_block = backend.get_value(&key)?;
                 let block_result = StoredMetadata::parse(&raw_block);
                 match block_result {
-                    Ok(block) => {
-                        past_runs_threshold_times
-                            .entry(other_worker_id)
-                            .and_modify(|timestamp: &mut u64| {
-                                *timestamp = max(*timestamp, block.last_advanced_timestamp);
-                            })
-                            .or_insert(block.last_advanced_timestamp);
-                        info!("Merge the current state with block: {block:?}");
-                        internal_state.merge(block);
+                    Ok(block) => blocks.push((key, metadata_key, block)),
+                    // Skipping the block would silently lose the state, as it can't be
+                    // read by this version of the engine at all
+                    Err(e @ Error::UnsupportedFormatVersion(_, _)) => {
+                        error!("Metadata block with key {key} can't be read: {e}");
+                        return Err(e);
                     }
                     Err(e) => {
                         warn!("Broken offsets block with key {key}. Error: {e}");
+                        if other_worker_id == worker_id {
+                            past_checkpoints.push(PastCheckpoint {
+                                key: key.clone(),
+                                saved_at: metadata_key.timestamp,
+                                last_advanced_timestamp: 0,
+                                persistent_ids: HashSet::new(),
+                            });
+                        }
+                        broken_keys_by_worker
+                            .entry(other_worker_id)
+                            .or_default()
+                            .push(key);
                     }
                 };
             }
 
+            let agreed_time = Self::last_agreed_time(&blocks);
+            for (key, metadata_key, block) in blocks {
+                let other_worker_id = metadata_key.worker_id;
+                if other_worker_id == worker_id {
+                    past_checkpoints.push(PastCheckpoint {
+                        key: key.clone(),
+                        saved_at: block.saved_at.unwrap_or(metadata_key.timestamp),
+                        last_advanced_timestamp: block.last_advanced_timestamp,
+                        persistent_ids: block.persistent_ids(),
+                    });
+                } else {
+                    external_references.extend(block.persistent_ids());
+                }
+                if block
+                    .agreed_time
+                    .is_some_and(|block_agreed_time| block_agreed_time > agreed_time)
+                {
+                    info!("Skip the block {key}, as not all processes of the cluster committed its checkpoint");
+                    continue;
+                }
+                past_runs_threshold_times
+                    .entry(other_worker_id)
+                    .and_modify(|timestamp: &mut u64| {
+                        *timestamp = max(*timestamp, block.last_advanced_timestamp);
+                    })
+                    .or_insert(block.last_advanced_timestamp);
+                info!("Merge the current state with block: {block:?}");
+                internal_state.merge(block);
+            }
+
+            // All workers read the same blocks, so one of them is enough to report it
+            if worker_id == 0 && !broken_keys_by_worker.is_empty() {
+                Self::report_fallback(
+                    &internal_state,
+                    &past_runs_threshold_times,
+                    &broken_keys_by_worker,
+                );
+            }
+
             (internal_state, past_runs_threshold_times)
         };
+        past_checkpoints.sort_by_key(|checkpoint| {
+            Reverse((checkpoint.saved_at, checkpoint.last_advanced_timestamp))
+        });
 
         let current_timestamp = {
             let now = SystemTime::now();
@@ -169,11 +436,60 @@ impl MetadataAccessor {
             backend,
             internal_state,
             past_runs_threshold_times,
+            worker_id,
             current_key_to_use,
             next_key_to_use,
+            retention_policy,
+            past_checkpoints,
+            external_references,
+            registered_inputs: HashSet::new(),
+            current_run_checkpoints: 0,
         })
     }
 
+    /// Returns the time of the last checkpoint committed by all processes of the cluster
+    /// that saved the blocks, which is the earliest of the last checkpoints of the
+    /// workers. The blocks saved outside of a cluster don't limit it.
+    fn last_agreed_time(blocks: &[(String, MetadataKey, StoredMetadata)]) -> u64 {
+        let mut last_agreed_times: HashMap<usize, u64> = HashMap::new();
+        for (_key, metadata_key, block) in blocks {
+            if let Some(agreed_time) = block.agreed_time {
+                last_agreed_times
+                    .entry(metadata_key.worker_id)
+                    .and_modify(|last_agreed_time| {
+                        *last_agreed_time = max(*last_agreed_time, agreed_time);
+                    })
+                    .or_insert(agreed_time);
+            }
+        }
+        last_agreed_times.into_values().min().unwrap_or(u64::MAX)
+    }
+
+    /// Reports the state used instead of the broken blocks: the state from the latest
+    /// valid block of each worker and the offsets from which each input is read again.
+    fn report_fallback(
+        internal_state: &StoredMetadata,
+        past_runs_threshold_times: &HashMap<usize, u64>,
+        broken_keys_by_worker: &HashMap<usize, Vec<String>>,
+    ) {
+        for (worker_id, broken_keys) in broken_keys_by_worker {
+            if let Some(time) = past_runs_threshold_times.get(worker_id) {
+                warn!("Skipped the corrupted persisted state of worker {worker_id} in blocks {broken_keys:?}. The worker falls back to its latest valid state, saved at time {time}");
+            } else {
+                warn!("Skipped the corrupted persisted state of worker {worker_id} in blocks {broken_keys:?}. The worker has no valid state and starts from scratch");
+            }
+        }
+        for persistent_id in internal_state.storage_types.keys() {
+            let frontier = internal_state
+                .frontiers
+                .antichain_for_storage(*persistent_id);
+            warn!(
+                "Input {persistent_id} is read again after the offsets {:?}",
+                frontier.as_vec()
+            );
+        }
+    }
+
     pub fn past_runs_threshold_times(&self) -> &HashMap<usize, u64> {
         &self.past_runs_threshold_times
     }
@@ -192,6 +508,7 @@ impl MetadataAccessor {
         self.internal_state
             .storage_types
             .insert(persistent_id, *storage_type);
+        self.registered_inputs.insert(persistent_id);
     }
 
     pub fn save_offset(
@@ -215,17 +532,96 @@ impl MetadataAccessor {
         self.internal_state.last_advanced_timestamp
     }
 
+    pub fn accept_agreed_time(&mut self, agreed_time: Option<u64>) {
+        self.internal_state.agreed_time = agreed_time;
+    }
+
+    fn serialize_state(&mut self) -> String {
+        if self.retention_policy.is_enabled() {
+            // The inputs removed from the computation are forgotten, so that their
+            // snapshots can be removed once no retained checkpoint refers to them
+            self.internal_state.retain_inputs(&self.registered_inputs);
+        }
+        self.internal_state.saved_at = Some(current_unix_timestamp_ms());
+        self.internal_state.serialize()
+    }
+
     pub fn save_current_state(&mut self) -> Result<(), Error> {
-        let serialized_state = self.internal_state.serialize();
+        let serialized_state = self.serialize_state();
         self.backend
             .put_value(&self.current_key_to_use, &serialized_state)?;
         swap(&mut self.current_key_to_use, &mut self.next_key_to_use);
+        // The checkpoints of this run rotate between two keys
+        self.current_run_checkpoints = min(self.current_run_checkpoints + 1, 2);
         Ok(())
     }
+
+    /// Saves the current state under the name of a savepoint, which can be restored later.
+    pub fn save_savepoint(&mut self, name: &str) -> Result<(), Error> {
+        let serialized_state = self.serialize_state();
+        self.backend
+            .put_value(&savepoint_key(name, self.worker_id), &serialized_state)
+    }
+
+    /// Removes the checkpoints of this worker saved in the previous runs, which are not
+    /// kept by the retention policy. It is only done after a checkpoint is saved in this
+    /// run, so that the state is never lost. Returns whether any checkpoint was removed.
+    /// In a cluster, the checkpoint committed last may not be committed by the other
+    /// processes yet, so the removal waits for the second one.
+    pub fn remove_expired_checkpoints(&mut self) -> bool {
+        let required_checkpoints = if self.internal_state.agreed_time.is_some() {
+            2
+        } else {
+            1
+        };
+        if !self.retention_policy.is_enabled()
+            || self.current_run_checkpoints < required_checkpoints
+        {
+            return false;
+        }
+        let now = current_unix_timestamp_ms();
+        let mut newer_checkpoints = self.current_run_checkpoints;
+        let mut any_removed = false;
+        for checkpoint in take(&mut self.past_checkpoints) {
+            let age = Duration::from_millis(
+                u64::try_from(now.saturating_sub(checkpoint.saved_at)).unwrap_or(u64::MAX),
+            );
+            let is_retained = self.retention_policy.retains(newer_checkpoints, age);
+            newer_checkpoints += 1;
+            if is_retained {
+                self.past_checkpoints.push(checkpoint);
+                continue;
+            }
+            info!("Remove the expired checkpoint {}", checkpoint.key);
+            if let Err(e) = self.backend.remove_key(&checkpoint.key) {
+                error!("Failed to remove the checkpoint {}: {e}", checkpoint.key);
+                self.past_checkpoints.push(checkpoint);
+                continue;
+            }
+            any_removed = true;
+        }
+        any_removed
+    }
+
+    /// Returns the inputs, whose snapshots are used by this run or by a retained
+    /// checkpoint or savepoint.
+    pub fn referenced_inputs(&self) -> HashSet<PersistentId> {
+        let mut referenced_inputs = self.registered_inputs.clone();
+        referenced_inputs.extend(&self.external_references);
+        for checkpoint in &self.past_checkpoints {
+            referenced_inputs.extend(&checkpoint.persistent_ids);
+        }
+        referenced_inputs
+    }
 }
 
 impl Drop for MetadataAccessor {
     fn drop(&mut self) {
+        // In a cluster, only the checkpoints agreed by the processes are saved, as this
+        // one would replace the previous checkpoint, which may be the last agreed one
+        if self.internal_state.agreed_time.is_some() {
+            return;
+        }
         if let Err(e) = self.save_current_state() {
             error!("Unsuccessful termination of metadata storage. Data may duplicate in the re-run. Error: {e}");
         }
@@ -1,38 +1,108 @@
 // Copyright © 2024 Pathway
 
-use log::error;
+use log::{error, info, warn};
+use opentelemetry::global::BoxedSpan;
+use opentelemetry::trace::{Span, Status, Tracer};
+use opentelemetry::KeyValue;
+use std::cmp::max;
+use std::fmt::Debug;
+use std::mem::take;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime};
+use std::thread::{self, sleep, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
 
-use crate::persistence::tracker::SingleWorkerPersistentStorage;
+use crate::connectors::data_storage::{SharedWriter, WriteError};
+use crate::engine::telemetry::{epoch_attribute, tracer};
+use crate::engine::PersistenceStats;
+use crate::persistence::cluster::{
+    wait_until_prepared_by_all, ClusterCheckpoints, FINAL_CHECKPOINT_TIME,
+};
+use crate::persistence::tracker::{FrontierCommitData, SingleWorkerPersistentStorage};
 
+const PROPOSAL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Coordinates the checkpoints of the workers of one process. In a cluster, the
+/// processes agree on the times of the checkpoints, see [`ClusterCheckpoints`].
 #[derive(Default)]
 pub struct WorkersPersistenceCoordinator {
     refresh_frequency: Duration,
     last_flush_at: Option<SystemTime>,
+    first_worker: usize,
     worker_persistence_managers: Vec<Option<Arc<Mutex<SingleWorkerPersistentStorage>>>>,
     last_timestamp_flushed: Option<u64>,
+    cluster: Option<Arc<Mutex<ClusterCheckpoints>>>,
+    last_agreed_time: Option<u64>,
+    transactional_sinks: Vec<SharedWriter>,
+    last_output_commit: Arc<Mutex<Option<u64>>>,
+    savepoint_requests: SharedSavepointRequests,
+    checkpoint_hooks: SharedCheckpointHooks,
+    unpersisted_volume: SharedUnpersistedVolume,
+    persistence_stats: SharedPersistenceStats,
+    background_checkpoints: bool,
+    checkpoint_in_progress: Option<JoinHandle<()>>,
+    restore_completed: bool,
 }
 
 impl WorkersPersistenceCoordinator {
-    pub fn new(refresh_frequency: Duration, num_workers: usize) -> Self {
+    /// Creates the coordinator of the workers `workers`, the ones of this process. In a
+    /// cluster, the checkpoints are always made in the background, as the workers of
+    /// the other processes may need the workers of this one to finish the time of the
+    /// checkpoint.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        refresh_frequency: Duration,
+        workers: Range<usize>,
+        cluster: Option<ClusterCheckpoints>,
+        savepoint_requests: SharedSavepointRequests,
+        checkpoint_hooks: SharedCheckpointHooks,
+        unpersisted_volume: SharedUnpersistedVolume,
+        persistence_stats: SharedPersistenceStats,
+        background_checkpoints: bool,
+    ) -> Self {
+        let background_checkpoints = background_checkpoints || cluster.is_some();
         Self {
             refresh_frequency,
             last_flush_at: None,
-            worker_persistence_managers: vec![None; num_workers],
+            first_worker: workers.start,
+            worker_persistence_managers: vec![None; workers.len()],
             last_timestamp_flushed: Some(0),
+            cluster: cluster.map(|cluster| Arc::new(Mutex::new(cluster))),
+            last_agreed_time: None,
+            transactional_sinks: Vec::new(),
+            last_output_commit: Arc::default(),
+            savepoint_requests,
+            checkpoint_hooks,
+            unpersisted_volume,
+            persistence_stats,
+            background_checkpoints,
+            checkpoint_in_progress: None,
+            restore_completed: false,
         }
     }
 
+    pub fn persistence_stats(&self) -> &SharedPersistenceStats {
+        &self.persistence_stats
+    }
+
+    /// Registers a sink whose prepared epochs are committed each time the frontiers
+    /// are committed. As the frontiers are committed first, a restart never replays
+    /// the data of the committed epochs, and the sink has to keep the prepared epochs
+    /// until they are committed, also over a restart.
+    pub fn register_transactional_sink(&mut self, sink: SharedWriter) {
+        self.transactional_sinks.push(sink);
+    }
+
     /// Record shared pointer to the particular worker's persistent storage in the storage.
     /// Maintain that the pointer to the storage of the
-    /// worker K occupies the K-th position in the array.
+    /// worker K occupies the (K - first worker of the process)-th position in the array.
     pub fn register_worker(
         &mut self,
         persistence_manager: Arc<Mutex<SingleWorkerPersistentStorage>>,
     ) {
         let worker_id = persistence_manager.lock().unwrap().worker_id();
-        self.worker_persistence_managers[worker_id] = Some(persistence_manager);
+        self.worker_persistence_managers[worker_id - self.first_worker] = Some(persistence_manager);
     }
 
     /// Handles the event of the timestamp update within a particular sink in a particular worker.
@@ -42,13 +112,22 @@ impl WorkersPersistenceCoordinator {
     /// The new frontiers for any particular time T are committed only when all workers finish the
     /// output for this time T. Synchronization is needed, because there is no guarantee that the
     /// worker which reads the entry will output it in case of multithreaded execution.
+    ///
+    /// In a cluster, the first process decides when to make a checkpoint and the other ones
+    /// make theirs at the times it proposes.
+    ///
+    /// With background checkpoints, waiting for the snapshots and committing the frontiers is
+    /// done in a separate thread, and the next checkpoint starts only after it's finished.
+    ///
+    /// Otherwise, the returned `OutputCommit` of a checkpoint has to be run by the caller,
+    /// after releasing the lock of the coordinator.
     pub fn accept_finalized_timestamp(
         &mut self,
         worker_id: usize,
         sink_id: usize,
         reported_timestamp: Option<u64>,
-    ) {
-        let worker_storage = self.worker_persistence_managers[worker_id]
+    ) -> Option<OutputCommit> {
+        let worker_storage = self.worker_persistence_managers[worker_id - self.first_worker]
             .as_ref()
             .unwrap();
         worker_storage
@@ -57,58 +136,223 @@ impl WorkersPersistenceCoordinator {
             .update_sink_finalized_time(sink_id, reported_timestamp);
 
         let global_finalized_timestamp = self.global_closed_timestamp();
+        if !self.restore_completed {
+            self.maybe_complete_restore(global_finalized_timestamp);
+        }
+
+        let is_leader = match &self.cluster {
+            Some(cluster) => cluster.lock().unwrap().is_leader(),
+            None => true,
+        };
+        if !is_leader {
+            return self.follow_proposed_checkpoints(global_finalized_timestamp);
+        }
+        // In a cluster, the workers of this process can't wait for the other processes
+        // until all output is finished
+        let finishing = if self.cluster.is_some() {
+            global_finalized_timestamp.is_none()
+        } else {
+            reported_timestamp.is_none()
+        };
 
         if global_finalized_timestamp != self.last_timestamp_flushed {
             let current_timestamp = SystemTime::now();
+            let savepoint_requested = !self.savepoint_requests.lock().unwrap().is_empty();
             let should_refresh = self.last_flush_at.map_or(true, |last_timestamp| {
                 current_timestamp.duration_since(last_timestamp).unwrap() >= self.refresh_frequency
-            }) || reported_timestamp.is_none();
-            if should_refresh {
-                self.last_flush_at = Some(current_timestamp);
-
-                self.last_timestamp_flushed = global_finalized_timestamp;
-                let mut worker_futures = Vec::new();
-
-                for persistence_manager in &self.worker_persistence_managers {
-                    let commit_data = persistence_manager
-                        .as_ref()
-                        .unwrap()
-                        .lock()
-                        .unwrap()
-                        .accept_globally_finalized_timestamp(global_finalized_timestamp);
-                    worker_futures.push(commit_data);
-                }
+            }) || reported_timestamp.is_none()
+                || savepoint_requested
+                || self.unpersisted_volume.exceeds_limits();
+            // The last checkpoint is always waited for, so that the output is committed
+            if should_refresh && self.finish_checkpoint_in_progress(finishing) {
+                let agreed_time = if let Some(cluster) = &self.cluster {
+                    let agreed_time = global_finalized_timestamp.unwrap_or(FINAL_CHECKPOINT_TIME);
+                    if let Err(e) = cluster.lock().unwrap().propose(agreed_time) {
+                        error!("Failed to propose the checkpoint to the other processes: {e}");
+                        return None;
+                    }
+                    Some(agreed_time)
+                } else {
+                    None
+                };
+                return self.start_checkpoint(global_finalized_timestamp, agreed_time, finishing);
+            }
+        }
+        None
+    }
 
-                // Ensure all snapshots are written to the required point
-                for (tracker, commit_data) in self
-                    .worker_persistence_managers
-                    .iter()
-                    .zip(worker_futures.iter_mut())
-                {
-                    let is_prepared = commit_data.prepare();
-                    if !is_prepared {
-                        error!(
-                            "Failed to prepare frontier commit for worker {}",
-                            tracker.as_ref().unwrap().lock().unwrap().worker_id()
-                        );
-                        return;
+    /// Makes the checkpoints proposed by the first process of the cluster, in order, once
+    /// the outputs of the workers of this process have finished their times. After all
+    /// output is finished, it waits for the remaining proposals, up to the final one.
+    fn follow_proposed_checkpoints(
+        &mut self,
+        global_finalized_timestamp: Option<u64>,
+    ) -> Option<OutputCommit> {
+        let finishing = global_finalized_timestamp.is_none();
+        let until = global_finalized_timestamp.unwrap_or(FINAL_CHECKPOINT_TIME);
+        let mut output_commit: Option<OutputCommit> = None;
+        loop {
+            if !self.finish_checkpoint_in_progress(finishing) {
+                return None;
+            }
+            let proposed = self
+                .cluster
+                .as_ref()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .next_proposed(self.last_agreed_time, until);
+            let agreed_time = match proposed {
+                Ok(Some(agreed_time)) => agreed_time,
+                Ok(None) if !finishing => return None,
+                Ok(None) => {
+                    sleep(PROPOSAL_POLL_INTERVAL);
+                    continue;
+                }
+                Err(e) => {
+                    error!("Failed to read the checkpoints proposed by the first process: {e}");
+                    if !finishing {
+                        return None;
                     }
+                    sleep(PROPOSAL_POLL_INTERVAL);
+                    continue;
                 }
+            };
+            // The checkpoints preceding the final one are completed here
+            if let Some(output_commit) = output_commit.take() {
+                if let Err(e) = output_commit.run() {
+                    error!("Failed to commit the prepared output: {e}");
+                }
+            }
+            let timestamp = (agreed_time != FINAL_CHECKPOINT_TIME).then_some(agreed_time);
+            output_commit = self.start_checkpoint(timestamp, Some(agreed_time), finishing);
+            if !finishing || agreed_time == FINAL_CHECKPOINT_TIME {
+                return output_commit;
+            }
+        }
+    }
 
-                // Then commit all frontiers
-                for (tracker, commit_data) in self
-                    .worker_persistence_managers
-                    .iter()
-                    .zip(worker_futures.iter_mut())
-                {
-                    tracker
-                        .as_ref()
-                        .unwrap()
-                        .lock()
-                        .unwrap()
-                        .commit_globally_finalized_timestamp(commit_data);
+    /// Starts the checkpoint of the state up to `timestamp`, agreed by the processes of a
+    /// cluster as `agreed_time`. Unless it's made in the background, the returned commit
+    /// of the output completes it.
+    fn start_checkpoint(
+        &mut self,
+        timestamp: Option<u64>,
+        agreed_time: Option<u64>,
+        finishing: bool,
+    ) -> Option<OutputCommit> {
+        self.last_flush_at = Some(SystemTime::now());
+        let started_at = Instant::now();
+        // The data written from now on is covered by the next commit
+        let (rows, bytes) = self.unpersisted_volume.take();
+
+        self.last_timestamp_flushed = timestamp;
+        self.last_agreed_time = agreed_time;
+        for hook in self.checkpoint_hooks.lock().unwrap().iter_mut() {
+            hook.on_checkpoint_start(timestamp);
+        }
+        let tracer = tracer();
+        let mut span_attributes = vec![
+            KeyValue::new("pathway.rows", i64::try_from(rows).unwrap_or(i64::MAX)),
+            KeyValue::new("pathway.bytes", i64::try_from(bytes).unwrap_or(i64::MAX)),
+        ];
+        if let Some(timestamp) = timestamp {
+            span_attributes.push(epoch_attribute(timestamp));
+        }
+        let span = tracer
+            .span_builder("checkpoint")
+            .with_attributes(span_attributes)
+            .start(&tracer);
+
+        let worker_persistence_managers: Vec<_> = self
+            .worker_persistence_managers
+            .iter()
+            .map(|persistence_manager| persistence_manager.clone().unwrap())
+            .collect();
+        let commit_data = worker_persistence_managers
+            .iter()
+            .map(|persistence_manager| {
+                persistence_manager
+                    .lock()
+                    .unwrap()
+                    .accept_globally_finalized_timestamp(timestamp, agreed_time)
+            })
+            .collect();
+
+        let checkpoint = PendingCheckpoint {
+            worker_persistence_managers,
+            commit_data,
+            timestamp,
+            agreement: self.cluster.clone().zip(agreed_time),
+            started_at,
+            rows,
+            bytes,
+            savepoint_requests: self.savepoint_requests.clone(),
+            checkpoint_hooks: self.checkpoint_hooks.clone(),
+            transactional_sinks: self.transactional_sinks.clone(),
+            last_output_commit: self.last_output_commit.clone(),
+            unpersisted_volume: self.unpersisted_volume.clone(),
+            persistence_stats: self.persistence_stats.clone(),
+            span,
+        };
+        if self.background_checkpoints && !finishing {
+            let checkpoint_thread = thread::Builder::new()
+                .name("pathway:checkpoint".to_string())
+                .spawn(move || {
+                    if let Some(output_commit) = checkpoint.complete() {
+                        // The epochs stay prepared, the next checkpoint retries them
+                        if let Err(e) = output_commit.run() {
+                            error!("Failed to commit the prepared output: {e}");
+                        }
+                    }
+                })
+                .expect("checkpoint thread creation failed");
+            self.checkpoint_in_progress = Some(checkpoint_thread);
+            None
+        } else {
+            checkpoint.complete()
+        }
+    }
+
+    /// Calls the restore hooks once the inputs of all workers have replayed their snapshots
+    /// and the outputs have finished the time of the replayed data.
+    fn maybe_complete_restore(&mut self, global_finalized_timestamp: Option<u64>) {
+        let mut restored_time = 0;
+        for worker_pm in &self.worker_persistence_managers {
+            let Some(worker_pm) = worker_pm else {
+                return;
+            };
+            let Some(worker_restored_time) = worker_pm.lock().unwrap().restored_time() else {
+                return;
+            };
+            restored_time = max(restored_time, worker_restored_time);
+        }
+        if global_finalized_timestamp.is_some_and(|timestamp| timestamp < restored_time) {
+            return;
+        }
+        self.restore_completed = true;
+        info!("The persisted state is restored at time {restored_time}");
+        for hook in self.checkpoint_hooks.lock().unwrap().iter_mut() {
+            hook.on_restore_complete(restored_time);
+        }
+    }
+
+    /// Returns whether no checkpoint is in progress. If `wait` is set, the checkpoint in
+    /// progress is waited for.
+    fn finish_checkpoint_in_progress(&mut self, wait: bool) -> bool {
+        match self.checkpoint_in_progress.take() {
+            Some(checkpoint_thread) if !wait && !checkpoint_thread.is_finished() => {
+                self.checkpoint_in_progress = Some(checkpoint_thread);
+                false
+            }
+            Some(checkpoint_thread) => {
+                if let Err(e) = checkpoint_thread.join() {
+                    // there is no formatter for std::any::Any
+                    error!("Failed to join the checkpoint thread: {e:?}");
                 }
+                true
             }
+            None => true,
         }
     }
 
@@ -135,4 +379,258 @@ impl WorkersPersistenceCoordinator {
     }
 }
 
+impl Drop for WorkersPersistenceCoordinator {
+    fn drop(&mut self) {
+        self.finish_checkpoint_in_progress(true);
+    }
+}
+
+/// The second phase of a checkpoint: waiting for the snapshots to be written and then
+/// committing the frontiers, the savepoints and the output of the transactional sinks.
+struct PendingCheckpoint {
+    worker_persistence_managers: Vec<Arc<Mutex<SingleWorkerPersistentStorage>>>,
+    commit_data: Vec<FrontierCommitData>,
+    timestamp: Option<u64>,
+    // The agreement of the processes of a cluster and the time agreed for the checkpoint
+    agreement: Option<(Arc<Mutex<ClusterCheckpoints>>, u64)>,
+    started_at: Instant,
+    rows: u64,
+    bytes: u64,
+    savepoint_requests: SharedSavepointRequests,
+    checkpoint_hooks: SharedCheckpointHooks,
+    transactional_sinks: Vec<SharedWriter>,
+    last_output_commit: Arc<Mutex<Option<u64>>>,
+    unpersisted_volume: SharedUnpersistedVolume,
+    persistence_stats: SharedPersistenceStats,
+    span: BoxedSpan,
+}
+
+impl PendingCheckpoint {
+    /// Commits the frontiers once the snapshots are written. Returns the commit of the
+    /// output completing the checkpoint, or `None` if the checkpoint failed.
+    fn complete(mut self) -> Option<OutputCommit> {
+        // Ensure all snapshots are written to the required point
+        let mut is_prepared = true;
+        for (tracker, commit_data) in self
+            .worker_persistence_managers
+            .iter()
+            .zip(self.commit_data.iter_mut())
+        {
+            if !commit_data.prepare() {
+                error!(
+                    "Failed to prepare frontier commit for worker {}",
+                    tracker.lock().unwrap().worker_id()
+                );
+                is_prepared = false;
+                break;
+            }
+        }
+        if let Some((cluster, agreed_time)) = &self.agreement {
+            // The other processes wait for this one also if it failed
+            if let Err(e) = cluster
+                .lock()
+                .unwrap()
+                .mark_prepared(*agreed_time, is_prepared)
+            {
+                error!("Failed to record the prepared checkpoint for the other processes: {e}");
+                is_prepared = false;
+            } else if is_prepared && !wait_until_prepared_by_all(cluster, *agreed_time) {
+                error!("Another process of the cluster failed to prepare the checkpoint");
+                is_prepared = false;
+            }
+        }
+        if !is_prepared {
+            self.unpersisted_volume.add(self.rows, self.bytes);
+            {
+                let mut stats = self.persistence_stats.lock().unwrap();
+                stats.failed_checkpoints += 1;
+                stats.last_checkpoint_failed = true;
+            }
+            self.span
+                .set_status(Status::error("failed to prepare frontier commit"));
+            self.span.end();
+            return None;
+        }
+
+        // Then commit all frontiers
+        for (tracker, commit_data) in self
+            .worker_persistence_managers
+            .iter()
+            .zip(self.commit_data.iter())
+        {
+            tracker
+                .lock()
+                .unwrap()
+                .commit_globally_finalized_timestamp(commit_data);
+        }
+        if let Some((cluster, agreed_time)) = &self.agreement {
+            if let Err(e) = cluster.lock().unwrap().remove_before(*agreed_time) {
+                warn!("Failed to remove the checkpoints agreed before {agreed_time}: {e}");
+            }
+        }
+
+        // The savepoints requested meanwhile save the state just committed, which is
+        // the same in all workers
+        for name in take(&mut *self.savepoint_requests.lock().unwrap()) {
+            for tracker in &self.worker_persistence_managers {
+                tracker.lock().unwrap().save_savepoint(&name);
+            }
+            info!("Savepoint {name:?} taken at time {:?}", self.timestamp);
+        }
+
+        Some(OutputCommit {
+            timestamp: self.timestamp,
+            started_at: self.started_at,
+            rows: self.rows,
+            bytes: self.bytes,
+            checkpoint_hooks: self.checkpoint_hooks,
+            transactional_sinks: self.transactional_sinks,
+            last_output_commit: self.last_output_commit,
+            persistence_stats: self.persistence_stats,
+            span: self.span,
+        })
+    }
+}
+
+/// The last phase of a checkpoint: exposing the output prepared by the transactional
+/// sinks for the epochs covered by the committed frontiers. It waits for the external
+/// systems, so it's run without holding the lock of the coordinator.
+pub struct OutputCommit {
+    timestamp: Option<u64>,
+    started_at: Instant,
+    rows: u64,
+    bytes: u64,
+    checkpoint_hooks: SharedCheckpointHooks,
+    transactional_sinks: Vec<SharedWriter>,
+    last_output_commit: Arc<Mutex<Option<u64>>>,
+    persistence_stats: SharedPersistenceStats,
+    span: BoxedSpan,
+}
+
+impl OutputCommit {
+    /// Commits the output of the sinks. If it fails, the checkpoint is reported as failed.
+    /// The frontiers are already committed then, so the sinks keep the epochs and the
+    /// next checkpoint or, after a restart, the recovery of the sinks commits them.
+    pub fn run(mut self) -> Result<(), WriteError> {
+        // After the output is finished, everything prepared is covered
+        let committed_timestamp = self.timestamp.unwrap_or(u64::MAX);
+        // The commits of the consecutive checkpoints may be run concurrently by the
+        // callers, so they are serialized and a commit overtaken by a later one is
+        // skipped, as the later one has committed its output
+        let mut last_output_commit = self.last_output_commit.lock().unwrap();
+        if last_output_commit.is_some_and(|last_timestamp| last_timestamp >= committed_timestamp) {
+            self.span.end();
+            return Ok(());
+        }
+        for sink in &self.transactional_sinks {
+            if let Err(e) = sink.lock().unwrap().commit_prepared(committed_timestamp) {
+                {
+                    let mut stats = self.persistence_stats.lock().unwrap();
+                    stats.failed_checkpoints += 1;
+                    stats.last_checkpoint_failed = true;
+                }
+                self.span
+                    .set_status(Status::error("failed to commit the prepared output"));
+                self.span.end();
+                return Err(e);
+            }
+        }
+        *last_output_commit = Some(committed_timestamp);
+        drop(last_output_commit);
+
+        self.record_checkpoint();
+        for hook in self.checkpoint_hooks.lock().unwrap().iter_mut() {
+            hook.on_checkpoint_complete(self.timestamp);
+        }
+        self.span.end();
+        Ok(())
+    }
+
+    fn record_checkpoint(&self) {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        let duration = self.started_at.elapsed();
+        let mut stats = self.persistence_stats.lock().unwrap();
+        stats.checkpoints += 1;
+        stats.last_checkpoint_time = Some(u64::try_from(now.as_millis()).unwrap());
+        stats.last_checkpoint_duration_ms = Some(u64::try_from(duration.as_millis()).unwrap());
+        stats.last_checkpoint_rows = Some(self.rows);
+        stats.last_checkpoint_bytes = Some(self.bytes);
+        stats.last_checkpoint_failed = false;
+    }
+}
+
 pub type SharedWorkersPersistenceCoordinator = Arc<Mutex<WorkersPersistenceCoordinator>>;
+
+/// The names of the savepoints requested by the user. They are taken at the next commit
+/// of the frontiers.
+pub type SharedSavepointRequests = Arc<Mutex<Vec<String>>>;
+
+/// The callbacks allowing to coordinate external systems with the checkpoints of the
+/// persisted state. The timestamp of a checkpoint is `None` for the final one, made
+/// after all output is finished.
+pub trait CheckpointHook: Send + Debug {
+    /// Called when a checkpoint of the state up to `timestamp` starts.
+    fn on_checkpoint_start(&mut self, _timestamp: Option<u64>) {}
+
+    /// Called after the checkpoint of the state up to `timestamp` is committed. It's not
+    /// called if the checkpoint fails.
+    fn on_checkpoint_complete(&mut self, _timestamp: Option<u64>) {}
+
+    /// Called once per run, when the persisted state is replayed and the outputs have
+    /// finished `timestamp`, the time of the replayed data.
+    fn on_restore_complete(&mut self, _timestamp: u64) {}
+}
+
+/// The hooks registered by the user. In a computation run in several processes, each
+/// of them calls its own hooks.
+pub type SharedCheckpointHooks = Arc<Mutex<Vec<Box<dyn CheckpointHook>>>>;
+
+/// The amount of the input data written to the snapshots since the last commit of the
+/// frontiers. The frontiers are committed earlier than after the refresh interval, if
+/// it exceeds one of the limits, so that less data is replayed after a crash.
+#[derive(Debug, Default)]
+pub struct UnpersistedVolume {
+    rows: AtomicU64,
+    bytes: AtomicU64,
+    max_rows: Option<u64>,
+    max_bytes: Option<u64>,
+}
+
+impl UnpersistedVolume {
+    pub fn new(max_rows: Option<u64>, max_bytes: Option<u64>) -> Self {
+        Self {
+            rows: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            max_rows,
+            max_bytes,
+        }
+    }
+
+    pub fn add(&self, rows: u64, bytes: u64) {
+        self.rows.fetch_add(rows, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn exceeds_limits(&self) -> bool {
+        self.max_rows
+            .is_some_and(|max_rows| self.rows.load(Ordering::Relaxed) >= max_rows)
+            || self
+                .max_bytes
+                .is_some_and(|max_bytes| self.bytes.load(Ordering::Relaxed) >= max_bytes)
+    }
+
+    /// Returns the numbers of the rows and the bytes written since the previous call.
+    fn take(&self) -> (u64, u64) {
+        (
+            self.rows.swap(0, Ordering::Relaxed),
+            self.bytes.swap(0, Ordering::Relaxed),
+        )
+    }
+}
+
+pub type SharedUnpersistedVolume = Arc<UnpersistedVolume>;
+
+/// The statistics of the commits of the frontiers, which are exposed by the prober.
+pub type SharedPersistenceStats = Arc<Mutex<PersistenceStats>>;
@@ -1,17 +1,19 @@
 // Copyright © 2024 Pathway
 
 use itertools::Itertools;
-use log::{error, info};
+use log::{error, info, warn};
+use std::cmp::max;
 use std::collections::HashMap;
 use std::mem::take;
 use std::sync::{Arc, Mutex};
 
-use crate::connectors::data_storage::{ReadError, StorageType, WriteError};
+use crate::connectors::data_storage::{ReadError, SourceAcknowledger, StorageType, WriteError};
 use crate::connectors::snapshot::{SnapshotReader, SnapshotWriterFlushFuture};
 use crate::connectors::PersistenceMode;
 use crate::persistence::config::PersistenceManagerConfig;
 use crate::persistence::frontier::OffsetAntichain;
 use crate::persistence::metadata_backends::Error as MetadataBackendError;
+use crate::persistence::prepared_output::PreparedOutputStorage;
 use crate::persistence::state::MetadataAccessor;
 use crate::persistence::{PersistentId, SharedSnapshotWriter};
 
@@ -27,6 +29,9 @@ pub struct SingleWorkerPersistentStorage {
     snapshot_writers: HashMap<PersistentId, SharedSnapshotWriter>,
     sink_threshold_times: Vec<Option<u64>>,
     input_sources: FrontierByTimeForInputSources,
+    source_acknowledgers: Vec<(PersistentId, Box<dyn SourceAcknowledger>)>,
+    rewind_finished_times: HashMap<PersistentId, u64>,
+    unreferenced_snapshots_removed: bool,
 }
 
 /// The information from the first phase of time finalization commit.
@@ -38,13 +43,21 @@ pub struct FrontierCommitData {
 
     // The timestamp which needs to be committed when saving is successful
     timestamp: u64,
+
+    // The time of the checkpoint agreed by the processes of a cluster
+    agreed_time: Option<u64>,
 }
 
 impl FrontierCommitData {
-    pub fn new(snapshot_futures: Vec<SnapshotWriterFlushFuture>, timestamp: u64) -> Self {
+    pub fn new(
+        snapshot_futures: Vec<SnapshotWriterFlushFuture>,
+        timestamp: u64,
+        agreed_time: Option<u64>,
+    ) -> Self {
         Self {
             snapshot_futures,
             timestamp,
+            agreed_time,
         }
     }
 
@@ -76,6 +89,9 @@ impl SingleWorkerPersistentStorage {
             snapshot_writers: HashMap::new(),
             sink_threshold_times: Vec::new(),
             input_sources: Vec::new(),
+            source_acknowledgers: Vec::new(),
+            rewind_finished_times: HashMap::new(),
+            unreferenced_snapshots_removed: false,
         })
     }
 
@@ -87,6 +103,18 @@ impl SingleWorkerPersistentStorage {
         self.metadata_storage.last_advanced_timestamp()
     }
 
+    /// Creates the storage in which a transactional sink keeps its prepared epochs
+    /// until they are committed.
+    pub fn create_prepared_output_storage(
+        &self,
+        sink_name: &str,
+    ) -> Result<PreparedOutputStorage, MetadataBackendError> {
+        Ok(PreparedOutputStorage::new(
+            self.config.create_metadata_backend()?,
+            sink_name,
+        ))
+    }
+
     pub fn register_input_source(
         &mut self,
         persistent_id: PersistentId,
@@ -106,6 +134,34 @@ impl SingleWorkerPersistentStorage {
             .register_input_source(persistent_id, storage_type);
     }
 
+    /// Registers the acknowledger of the data read by the input source, which is called
+    /// with the frontier of the source after each successful commit.
+    pub fn register_source_acknowledger(
+        &mut self,
+        persistent_id: PersistentId,
+        source_acknowledger: Box<dyn SourceAcknowledger>,
+    ) {
+        self.source_acknowledgers
+            .push((persistent_id, source_acknowledger));
+    }
+
+    /// Records that the input source has replayed its snapshot, and that the data read
+    /// further is sent with times starting from `timestamp`.
+    pub fn accept_rewind_finished(&mut self, persistent_id: PersistentId, timestamp: u64) {
+        self.rewind_finished_times.insert(persistent_id, timestamp);
+    }
+
+    /// Returns the time from which the data read after the snapshots is sent, once all
+    /// input sources have replayed their snapshots.
+    pub fn restored_time(&self) -> Option<u64> {
+        let mut restored_time = 0;
+        for (persistent_id, _) in &self.input_sources {
+            let rewind_finished_time = self.rewind_finished_times.get(persistent_id)?;
+            restored_time = max(restored_time, *rewind_finished_time);
+        }
+        Some(restored_time)
+    }
+
     pub fn frontier_for(&self, persistent_id: PersistentId) -> OffsetAntichain {
         self.metadata_storage.frontier_for(persistent_id)
     }
@@ -130,9 +186,11 @@ impl SingleWorkerPersistentStorage {
 
     /// This method is called when all workers have finished the processing of time `timestamp`.
     /// If `timestamp` is `None` it means that all output within all workers has finished.
+    /// In a cluster, `agreed_time` is the time of the checkpoint agreed by the processes.
     pub fn accept_globally_finalized_timestamp(
         &mut self,
         timestamp: Option<u64>,
+        agreed_time: Option<u64>,
     ) -> FrontierCommitData {
         /*
             Use the timestamp provided, or if it's None use the max timestamp across input sources
@@ -143,7 +201,7 @@ impl SingleWorkerPersistentStorage {
             error!("Time isn't in the increasing order. Got advancement to {finalized_timestamp} while last advanced timestamp was {}", self.last_finalized_timestamp());
 
             // Empty set of snapshot commit futures and non-changed timestamp
-            return FrontierCommitData::new(vec![], self.last_finalized_timestamp());
+            return FrontierCommitData::new(vec![], self.last_finalized_timestamp(), agreed_time);
         }
 
         for (persistent_id, input_source) in &mut self.input_sources {
@@ -202,15 +260,78 @@ impl SingleWorkerPersistentStorage {
             futures.push(flush_future);
         }
 
-        FrontierCommitData::new(futures, finalized_timestamp)
+        FrontierCommitData::new(futures, finalized_timestamp, agreed_time)
     }
 
     pub fn commit_globally_finalized_timestamp(&mut self, commit_data: &FrontierCommitData) {
+        if self.config.replay_until.is_some() {
+            // Replaying a past state must not move the persisted frontier
+            return;
+        }
         self.metadata_storage
             .accept_finalized_timestamp(commit_data.timestamp);
+        self.metadata_storage
+            .accept_agreed_time(commit_data.agreed_time);
 
         if let Err(e) = self.metadata_storage.save_current_state() {
             error!("Failed to save the current state, the data may duplicate in the re-run: {e}");
+            return;
+        }
+        self.acknowledge_sources();
+        self.collect_garbage();
+    }
+
+    /// Acknowledges to the sources the data covered by the frontiers just committed.
+    /// A failed acknowledgement is only logged, as the next one covers the same data.
+    fn acknowledge_sources(&mut self) {
+        for (persistent_id, source_acknowledger) in &mut self.source_acknowledgers {
+            let frontier = self.metadata_storage.frontier_for(*persistent_id);
+            if let Err(e) = source_acknowledger.acknowledge(&frontier) {
+                error!("Failed to acknowledge the data read by input {persistent_id}: {e}");
+            }
+        }
+    }
+
+    /// Removes the checkpoints that the retention policy doesn't keep and then the
+    /// snapshots that no checkpoint refers to. The snapshots are checked once per run
+    /// and after each removal of checkpoints, as only then they can become unreferenced.
+    fn collect_garbage(&mut self) {
+        if !self.config.retention_policy.is_enabled() {
+            return;
+        }
+        let checkpoints_removed = self.metadata_storage.remove_expired_checkpoints();
+        if self.unreferenced_snapshots_removed && !checkpoints_removed {
+            return;
+        }
+        self.unreferenced_snapshots_removed = true;
+
+        let referenced_inputs = self.metadata_storage.referenced_inputs();
+        let persistent_ids = match self.config.snapshot_persistent_ids() {
+            Ok(persistent_ids) => persistent_ids,
+            Err(e) => {
+                error!("Failed to list the snapshots: {e}");
+                return;
+            }
+        };
+        for persistent_id in persistent_ids {
+            if referenced_inputs.contains(&persistent_id) {
+                continue;
+            }
+            info!("Remove the snapshot of input {persistent_id}, which no checkpoint refers to");
+            if let Err(e) = self.config.remove_snapshot(persistent_id) {
+                error!("Failed to remove the snapshot of input {persistent_id}: {e}");
+            }
+        }
+    }
+
+    /// Saves the state committed last under the name of a savepoint.
+    pub fn save_savepoint(&mut self, name: &str) {
+        if self.config.replay_until.is_some() {
+            warn!("Savepoint {name:?} is not saved when replaying a past state");
+            return;
+        }
+        if let Err(e) = self.metadata_storage.save_savepoint(name) {
+            error!("Failed to save the savepoint {name:?}: {e}");
         }
     }
 
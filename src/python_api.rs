@@ -6,6 +6,8 @@
 #![allow(clippy::needless_pass_by_value)]
 
 use crate::engine::{graph::SubscribeCallbacksBuilder, Computer as EngineComputer, Expressions};
+use ::arrow::pyarrow::PyArrowType;
+use ::arrow::record_batch::RecordBatch;
 use csv::ReaderBuilder as CsvReaderBuilder;
 use elasticsearch::{
     auth::Credentials as ESCredentials,
@@ -16,19 +18,21 @@ use elasticsearch::{
     Elasticsearch,
 };
 use itertools::Itertools;
-use log::warn;
-use numpy::{PyArray, PyReadonlyArrayDyn};
+use log::{error, warn};
+use ndarray::ArrayD;
+use numpy::npyffi::flags::NPY_ARRAY_WRITEABLE;
+use numpy::{dtype, Element, PyArray, PyReadonlyArrayDyn, PyUntypedArray};
 use once_cell::sync::Lazy;
 use postgres::{Client, NoTls};
 use pyo3::exceptions::{
-    PyBaseException, PyException, PyIOError, PyIndexError, PyKeyError, PyRuntimeError, PyTypeError,
-    PyValueError, PyZeroDivisionError,
+    PyBaseException, PyException, PyIOError, PyIndexError, PyKeyError, PyOverflowError,
+    PyRuntimeError, PyTypeError, PyValueError, PyZeroDivisionError,
 };
 use pyo3::marker::Ungil;
 use pyo3::prelude::*;
 use pyo3::pyclass::CompareOp;
 use pyo3::sync::GILOnceCell;
-use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyString, PyTuple, PyType};
+use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple, PyType};
 use pyo3::{AsPyPointer, PyTypeInfo};
 use rdkafka::consumer::{BaseConsumer, Consumer};
 use rdkafka::producer::{DefaultProducerContext, ThreadedProducer};
@@ -42,14 +46,17 @@ use serde_json::Value as JsonValue;
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::ffi::c_char;
 use std::fs::File;
 use std::io::{BufWriter, Read};
-use std::mem::take;
+use std::iter::once;
+use std::mem::{size_of, take};
 use std::os::unix::prelude::*;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time;
 
+use self::arrow::{data_rows_from_arrow, data_rows_to_arrow, ArrowColumns};
 use self::threads::PythonThreadState;
 use crate::connectors::data_format::{
     DebeziumDBType, DebeziumMessageParser, DsvSettings, Formatter, IdentityParser,
@@ -59,22 +66,26 @@ use crate::connectors::data_format::{
 use crate::connectors::data_storage::{
     ConnectorMode, CsvFilesystemReader, DataEventType, ElasticSearchWriter, FileWriter,
     FilesystemReader, KafkaReader, KafkaWriter, NullWriter, PsqlWriter, PythonReaderBuilder,
-    ReadMethod, ReaderBuilder, S3CsvReader, S3GenericReader, SqliteReader, Writer,
+    PythonWriter, ReadMethod, ReaderBuilder, S3CsvReader, S3GenericReader, SqliteReader, Writer,
 };
 use crate::connectors::snapshot::Event as SnapshotEvent;
+use crate::connectors::snapshot::SnapshotCompression;
 use crate::connectors::{PersistenceMode, SessionType, SnapshotAccess};
+use crate::engine::alerts::AlertThresholds;
 use crate::engine::dataflow::config_from_env;
 use crate::engine::error::{DynError, DynResult, Trace as EngineTrace};
 use crate::engine::graph::ScopedContext;
 use crate::engine::progress_reporter::MonitoringLevel;
-use crate::engine::reduce::StatefulCombineFn;
+use crate::engine::reduce::{RetractableReducerImpl, RetractableState, StatefulCombineFn};
+use crate::engine::shutdown::SharedShutdownRequest;
 use crate::engine::time::DateTime;
 use crate::engine::ReducerData;
 use crate::engine::{
     run_with_new_dataflow_graph, BatchWrapper, ColumnHandle, ColumnPath,
     ColumnProperties as EngineColumnProperties, DataRow, DateTimeNaive, DateTimeUtc, Duration,
-    ExpressionData, IxKeyPolicy, JoinType, Key, KeyImpl, PointerExpression, Reducer, ScopedGraph,
-    TableHandle, TableProperties as EngineTableProperties, Type, UniverseHandle, Value,
+    ExpressionData, IterationLimit, IxKeyPolicy, JoinType, Key, KeyHashing, KeyImpl, OperatorNode,
+    OperatorStateLimit, PointerExpression, Reducer, ScopedGraph, SerializedObject, SparseMatrix,
+    TableHandle, TableProperties as EngineTableProperties, Type, UniverseHandle, Value, KEY_BITS,
 };
 use crate::engine::{AnyExpression, Context as EngineContext};
 use crate::engine::{BoolExpression, Error as EngineError};
@@ -86,10 +97,23 @@ use crate::engine::{LegacyTable as EngineLegacyTable, StringExpression};
 use crate::persistence::config::{
     ConnectorWorkerPair, MetadataStorageConfig, PersistenceManagerOuterConfig, StreamStorageConfig,
 };
+use crate::persistence::dump;
+use crate::persistence::encryption::EncryptionKey;
+use crate::persistence::object_storage::{
+    AzureBlobStorage, AzureCredentials, GcsCredentials, GcsStorage, SharedObjectStorage,
+};
+use crate::persistence::schema::{
+    ColumnMigration as EngineColumnMigration, PersistedColumn, SchemaColumn,
+};
+use crate::persistence::state::{
+    is_valid_savepoint_name, restore_savepoint as restore_persisted_savepoint, RetentionPolicy,
+};
+use crate::persistence::sync::{CheckpointHook, SharedCheckpointHooks, SharedSavepointRequests};
 use crate::persistence::{ExternalPersistentId, IntoPersistentId, PersistentId};
 use crate::pipe::{pipe, ReaderType, WriterType};
 use s3::creds::Credentials as AwsCredentials;
 
+mod arrow;
 mod logging;
 mod numba;
 pub mod threads;
@@ -100,6 +124,7 @@ pub fn with_gil_and_pool<R>(f: impl FnOnce(Python) -> R + Ungil) -> R {
 
 const S3_PATH_PREFIX: &str = "s3://";
 static CONVERT: GILOnceCell<PyObject> = GILOnceCell::new();
+static SERIALIZATION: GILOnceCell<PyObject> = GILOnceCell::new();
 
 fn get_convert_python_module(py: Python<'_>) -> &PyAny {
     CONVERT
@@ -111,6 +136,16 @@ fn get_convert_python_module(py: Python<'_>) -> &PyAny {
         .as_ref(py)
 }
 
+fn get_serialization_python_module(py: Python<'_>) -> &PyAny {
+    SERIALIZATION
+        .get_or_init(py, || {
+            PyModule::import(py, "pathway.internals.serialization")
+                .unwrap()
+                .to_object(py)
+        })
+        .as_ref(py)
+}
+
 #[allow(unused)] // XXX
 macro_rules! pytodo {
     () => {
@@ -179,6 +214,51 @@ fn value_from_pandas_timedelta(ob: &PyAny) -> PyResult<Value> {
     Ok(Value::Duration(Duration::new(duration)))
 }
 
+fn value_from_sparse_matrix(ob: &PyAny) -> PyResult<Value> {
+    let (shape, indptr, indices, data) = get_convert_python_module(ob.py())
+        .call_method1("_sparse_matrix_to_rust", (ob,))?
+        .extract::<(
+            (usize, usize),
+            PyReadonlyArrayDyn<i64>,
+            PyReadonlyArrayDyn<i64>,
+            PyReadonlyArrayDyn<f64>,
+        )>()?;
+    let to_usize = |array: PyReadonlyArrayDyn<i64>| {
+        array
+            .as_array()
+            .iter()
+            .map(|&i| {
+                usize::try_from(i)
+                    .map_err(|_| PyValueError::new_err("negative index in sparse matrix"))
+            })
+            .collect::<PyResult<Vec<_>>>()
+    };
+    let matrix = SparseMatrix::new(
+        shape,
+        to_usize(indptr)?,
+        to_usize(indices)?,
+        data.as_array().iter().copied().collect(),
+    )?;
+    Ok(Value::from(matrix))
+}
+
+fn sparse_matrix_to_py_object(py: Python<'_>, matrix: &SparseMatrix) -> PyObject {
+    let to_py_array =
+        |elements: &[usize]| PyArray::from_iter(py, elements.iter().map(|&i| i as u64));
+    get_convert_python_module(py)
+        .call_method1(
+            "_sparse_matrix_from_rust",
+            (
+                matrix.shape(),
+                to_py_array(matrix.indptr()),
+                to_py_array(matrix.indices()),
+                PyArray::from_slice(py, matrix.data()),
+            ),
+        )
+        .unwrap()
+        .into_py(py)
+}
+
 fn value_json_from_py_any(ob: &PyAny) -> PyResult<Value> {
     let py = ob.py();
     let json_str = get_convert_python_module(py)
@@ -234,6 +314,95 @@ impl IntoPy<PyObject> for Duration {
     }
 }
 
+/// Keeps the array of a [`Value`] alive for as long as the NumPy arrays sharing its memory.
+#[pyclass(module = "pathway.engine", frozen)]
+struct SharedArray(Value);
+
+/// Passes the array of the value to Python without copying it. The NumPy array is read-only,
+/// as the values are immutable and may be shared by many rows.
+fn shared_array_to_py<T: Element>(py: Python<'_>, array: &ArrayD<T>, value: &Value) -> PyObject {
+    let owner = PyCell::new(py, SharedArray(value.clone())).unwrap();
+    // SAFETY: the memory of the array is kept alive by `owner`, the base of the NumPy array
+    let py_array = unsafe { PyArray::borrow_from_array(array, owner.as_ref()) };
+    // SAFETY: the NumPy array was just created and isn't accessed by anything else
+    unsafe {
+        (*py_array.as_array_ptr()).flags &= !NPY_ARRAY_WRITEABLE;
+    }
+    py_array.into()
+}
+
+fn is_whole_shared_array<T: Element>(
+    py_array: &PyUntypedArray,
+    data: *const c_char,
+    array: &ArrayD<T>,
+) -> bool {
+    let element_size = isize::try_from(size_of::<T>()).unwrap();
+    py_array.dtype().is_equiv_to(dtype::<T>(py_array.py()))
+        && data.cast::<T>() == array.as_ptr()
+        && py_array.shape() == array.shape()
+        && py_array
+            .strides()
+            .iter()
+            .copied()
+            .eq(array.strides().iter().map(|stride| stride * element_size))
+}
+
+/// Returns the value whose array is shared by the NumPy array, if the NumPy array
+/// is the whole array and not a view of its part, so that it's passed back to the engine
+/// without copying it.
+fn value_of_shared_array(ob: &PyAny) -> Option<Value> {
+    let py_array = ob.downcast::<PyUntypedArray>().ok()?;
+    // SAFETY: the pointer is to a live NumPy array
+    let (base, data, flags) = unsafe {
+        let raw_array = &*py_array.as_array_ptr();
+        (raw_array.base, raw_array.data, raw_array.flags)
+    };
+    if base.is_null() || flags & NPY_ARRAY_WRITEABLE != 0 {
+        return None;
+    }
+    // SAFETY: the base is kept alive by the array
+    let base: &PyAny = unsafe { ob.py().from_borrowed_ptr(base) };
+    let value = base
+        .downcast::<PyCell<SharedArray>>()
+        .ok()?
+        .borrow()
+        .0
+        .clone();
+    let is_whole = match &value {
+        Value::IntArray(array) => is_whole_shared_array(py_array, data, array),
+        Value::FloatArray(array) => is_whole_shared_array(py_array, data, array),
+        Value::Float32Array(array) => is_whole_shared_array(py_array, data, array),
+        _ => false,
+    };
+    is_whole.then_some(value)
+}
+
+/// Converts a column of a batch to a NumPy array if all its values are integers or all are
+/// floats, and to a list otherwise.
+fn batch_column_to_py(py: Python<'_>, column: &[Value]) -> PyObject {
+    let ints: Option<Vec<i64>> = column
+        .iter()
+        .map(|value| match value {
+            Value::Int(i) => Some(*i),
+            _ => None,
+        })
+        .collect();
+    if let Some(ints) = ints {
+        return PyArray::from_vec(py, ints).into_py(py);
+    }
+    let floats: Option<Vec<f64>> = column
+        .iter()
+        .map(|value| match value {
+            Value::Float(f) => Some(f.into_inner()),
+            _ => None,
+        })
+        .collect();
+    if let Some(floats) = floats {
+        return PyArray::from_vec(py, floats).into_py(py);
+    }
+    PyList::new(py, column).into_py(py)
+}
+
 impl<'source> FromPyObject<'source> for Value {
     fn extract(ob: &'source PyAny) -> PyResult<Self> {
         if ob.is_none() {
@@ -275,6 +444,8 @@ impl<'source> FromPyObject<'source> for Value {
         } else if let Ok(b) = ob.extract::<&PyBool>() {
             // Fallback checks from now on
             Ok(Value::Bool(b.is_true()))
+        } else if let Some(value) = value_of_shared_array(ob) {
+            Ok(value)
         } else if let Ok(array) = ob.extract::<PyReadonlyArrayDyn<i64>>() {
             // single-element arrays convert to scalars, so we need to check for arrays first
             Ok(Value::from(array.as_array().to_owned()))
@@ -285,7 +456,7 @@ impl<'source> FromPyObject<'source> for Value {
         } else if let Ok(array) = ob.extract::<PyReadonlyArrayDyn<f64>>() {
             Ok(Value::from(array.as_array().to_owned()))
         } else if let Ok(array) = ob.extract::<PyReadonlyArrayDyn<f32>>() {
-            Ok(Value::from(array.as_array().mapv(f64::from)))
+            Ok(Value::from(array.as_array().to_owned()))
         } else if let Ok(i) = ob.extract::<i64>() {
             Ok(Value::Int(i))
         } else if let Ok(f) = ob.extract::<f64>() {
@@ -316,6 +487,15 @@ impl<'source> FromPyObject<'source> for Value {
                 return value_json_from_py_any(ob.getattr("value")?);
             }
 
+            let module: &str = ob.get_type().getattr("__module__")?.extract()?;
+            if module.starts_with("scipy.sparse") {
+                return value_from_sparse_matrix(ob);
+            }
+
+            if let Some(value) = serialized_object_from_py_any(ob)? {
+                return Ok(value);
+            }
+
             if let Ok(vec) = ob.extract::<Vec<&PyAny>>() {
                 // generate a nicer error message if the type of an element is the problem
                 for v in vec {
@@ -331,6 +511,24 @@ impl<'source> FromPyObject<'source> for Value {
     }
 }
 
+/// Serializes the object with the serializer registered for its type, if there is one.
+fn serialized_object_from_py_any(ob: &PyAny) -> PyResult<Option<Value>> {
+    let serialized = get_serialization_python_module(ob.py())
+        .call_method1("_serialize", (ob,))?
+        .extract::<Option<(&str, &[u8])>>()?;
+    Ok(serialized.map(|(serializer, bytes)| SerializedObject::new(serializer, bytes).into()))
+}
+
+fn serialized_object_to_py_object(py: Python<'_>, object: &SerializedObject) -> PyObject {
+    get_serialization_python_module(py)
+        .call_method1(
+            "_deserialize",
+            (object.serializer.as_str(), PyBytes::new(py, &object.bytes)),
+        )
+        .unwrap()
+        .into_py(py)
+}
+
 fn json_to_py_object(py: Python<'_>, json: &JsonValue) -> PyObject {
     get_convert_python_module(py)
         .call_method1("_parse_to_json", (json.to_string(),))
@@ -349,12 +547,15 @@ impl ToPyObject for Value {
             Self::String(s) => s.into_py(py),
             Self::Bytes(b) => PyBytes::new(py, b).into(),
             Self::Tuple(t) => PyTuple::new(py, t.iter()).into(),
-            Self::IntArray(a) => PyArray::from_array(py, a).into(),
-            Self::FloatArray(a) => PyArray::from_array(py, a).into(),
+            Self::IntArray(a) => shared_array_to_py(py, a, self),
+            Self::FloatArray(a) => shared_array_to_py(py, a, self),
+            Self::Float32Array(a) => shared_array_to_py(py, a, self),
+            Self::SparseMatrix(m) => sparse_matrix_to_py_object(py, m),
             Self::DateTimeNaive(dt) => dt.into_py(py),
             Self::DateTimeUtc(dt) => dt.into_py(py),
             Self::Duration(d) => d.into_py(py),
             Self::Json(j) => json_to_py_object(py, j),
+            Self::SerializedObject(o) => serialized_object_to_py_object(py, o),
         }
     }
 }
@@ -461,25 +662,65 @@ impl IntoPy<PyObject> for MonitoringLevel {
     }
 }
 
+/// Keeps the formatted traceback of an exception raised by a Python function along with it,
+/// so that it isn't flattened to the message of the exception in the errors of the engine.
+fn python_exception(py: Python<'_>, error: PyErr) -> EngineError {
+    let traceback = error
+        .traceback(py)
+        .and_then(|traceback| traceback.format().ok())
+        .unwrap_or_default();
+    EngineError::PythonException {
+        traceback: format!("{traceback}{error}"),
+        error,
+    }
+}
+
+/// Adds a note to the exception, like `BaseException.add_note` available from Python 3.11.
+fn add_note(py: Python<'_>, error: &PyErr, note: String) -> PyResult<()> {
+    let value = error.value(py);
+    if !value.hasattr("__notes__")? {
+        value.setattr("__notes__", PyList::empty(py))?;
+    }
+    value
+        .getattr("__notes__")?
+        .call_method1("append", (note,))?;
+    Ok(())
+}
+
 impl From<EngineError> for PyErr {
     fn from(mut error: EngineError) -> Self {
         match error.downcast::<PyErr>() {
             Ok(error) => return error,
             Err(other) => error = other,
         };
+        if let EngineError::PythonException { error, .. } = error {
+            return error;
+        }
         Python::with_gil(|py| {
+            if let EngineError::WithRow { inner, key, values } = error {
+                let inner = PyErr::from(EngineError::from(inner));
+                let note = format!("Occurred for the row with key {key} and values {values}");
+                if let Err(e) = add_note(py, &inner, note) {
+                    warn!("Failed to add the row to the exception: {e}");
+                }
+                return inner;
+            }
             if let EngineError::WithTrace { inner, trace } = error {
                 let inner = PyErr::from(EngineError::from(inner));
                 let args = (inner, trace);
                 return PyErr::from_type(ENGINE_ERROR_WITH_TRACE_TYPE.as_ref(py), args);
             }
             let exception_type = match error {
-                EngineError::TypeMismatch { .. } => PyTypeError::type_object(py),
+                EngineError::TypeMismatch { .. }
+                | EngineError::OperandTypeMismatch { .. }
+                | EngineError::JoinKeyTypeMismatch { .. }
+                | EngineError::NullableOperand { .. } => PyTypeError::type_object(py),
                 EngineError::DuplicateKey(_)
                 | EngineError::ValueMissing
                 | EngineError::KeyMissingInColumn(_)
                 | EngineError::KeyMissingInUniverse(_) => PyKeyError::type_object(py),
                 EngineError::DivisionByZero => PyZeroDivisionError::type_object(py),
+                EngineError::DateTimeOverflow => PyOverflowError::type_object(py),
                 EngineError::IterationLimitTooSmall
                 | EngineError::ValueError(_)
                 | EngineError::NoPersistentStorage(_)
@@ -634,6 +875,89 @@ impl PyReducer {
         });
         Reducer::Stateful { combine_fn }
     }
+
+    #[staticmethod]
+    fn retractable(
+        init: Py<PyAny>,
+        add: Py<PyAny>,
+        retract: Py<PyAny>,
+        extract: Py<PyAny>,
+    ) -> Reducer {
+        let reducer = PythonRetractableReducer {
+            init,
+            add,
+            retract,
+            extract,
+        };
+        Reducer::Retractable {
+            reducer: Arc::new(reducer),
+        }
+    }
+}
+
+/// A reducer keeping the state of a group as a Python object. `add` and `retract` are
+/// called with the state and the values of a row and return the new state.
+struct PythonRetractableReducer {
+    init: Py<PyAny>,
+    add: Py<PyAny>,
+    retract: Py<PyAny>,
+    extract: Py<PyAny>,
+}
+
+impl PythonRetractableReducer {
+    fn update_group(
+        &self,
+        py: Python,
+        state: Option<RetractableState>,
+        rows: &[(Vec<Value>, isize)],
+    ) -> PyResult<(PyObject, Value)> {
+        let mut state = match state {
+            Some(state) => *state
+                .downcast::<PyObject>()
+                .expect("state of a Python reducer should be a Python object"),
+            None => self.init.call0(py)?,
+        };
+        // Adding before retracting, so that a row is never retracted before it's added
+        let additions = rows.iter().filter(|(_values, diff)| *diff > 0);
+        let retractions = rows.iter().filter(|(_values, diff)| *diff < 0);
+        for (values, diff) in additions.chain(retractions) {
+            let callable = if *diff > 0 { &self.add } else { &self.retract };
+            for _ in 0..diff.unsigned_abs() {
+                let args: Vec<PyObject> = once(state.clone_ref(py))
+                    .chain(values.iter().map(|value| value.to_object(py)))
+                    .collect();
+                state = callable.call1(py, PyTuple::new(py, args))?;
+            }
+        }
+        let value = self
+            .extract
+            .call1(py, (state.clone_ref(py),))?
+            .extract(py)?;
+        Ok((state, value))
+    }
+}
+
+impl RetractableReducerImpl for PythonRetractableReducer {
+    fn update(
+        &self,
+        groups: &mut [(Option<RetractableState>, Vec<(Vec<Value>, isize)>)],
+    ) -> Vec<Value> {
+        with_gil_and_pool(|py| {
+            groups
+                .iter_mut()
+                .map(|(state, rows)| {
+                    let (new_state, value) = self
+                        .update_group(py, state.take(), rows)
+                        .unwrap_or_else(|e| {
+                            e.print(py);
+                            panic!("python error");
+                        });
+                    *state = Some(Box::new(new_state));
+                    value
+                })
+                .collect()
+        })
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -830,7 +1154,10 @@ impl PyExpression {
                 Box::new(move |input| {
                     with_gil_and_pool(|py| -> DynResult<Value> {
                         let args = PyTuple::new(py, input);
-                        Ok(function.call1(py, args)?.extract::<Value>(py)?)
+                        let result = function
+                            .call1(py, args)
+                            .map_err(|e| python_exception(py, e))?;
+                        Ok(result.extract::<Value>(py)?)
                     })
                 }),
                 args.into(),
@@ -1163,6 +1490,116 @@ impl PyExpression {
         )
     }
 
+    #[staticmethod]
+    fn date_time_naive_add_calendar_units(
+        expr: &PyExpression,
+        unit: &PyExpression,
+        count: &PyExpression,
+    ) -> Self {
+        Self::new(
+            Arc::new(Expression::DateTimeNaive(
+                DateTimeNaiveExpression::AddCalendarUnits(
+                    expr.inner.clone(),
+                    unit.inner.clone(),
+                    count.inner.clone(),
+                ),
+            )),
+            expr.gil || unit.gil || count.gil,
+        )
+    }
+
+    #[staticmethod]
+    fn date_time_utc_add_calendar_units(
+        expr: &PyExpression,
+        unit: &PyExpression,
+        count: &PyExpression,
+    ) -> Self {
+        Self::new(
+            Arc::new(Expression::DateTimeUtc(
+                DateTimeUtcExpression::AddCalendarUnits(
+                    expr.inner.clone(),
+                    unit.inner.clone(),
+                    count.inner.clone(),
+                ),
+            )),
+            expr.gil || unit.gil || count.gil,
+        )
+    }
+
+    #[staticmethod]
+    fn date_time_utc_floor_to_calendar_unit_in_timezone(
+        expr: &PyExpression,
+        unit: &PyExpression,
+        timezone: &PyExpression,
+    ) -> Self {
+        Self::new(
+            Arc::new(Expression::DateTimeUtc(
+                DateTimeUtcExpression::FloorToCalendarUnitInTimezone(
+                    expr.inner.clone(),
+                    unit.inner.clone(),
+                    timezone.inner.clone(),
+                ),
+            )),
+            expr.gil || unit.gil || timezone.gil,
+        )
+    }
+
+    #[staticmethod]
+    fn date_time_utc_round_to_calendar_unit_in_timezone(
+        expr: &PyExpression,
+        unit: &PyExpression,
+        timezone: &PyExpression,
+    ) -> Self {
+        Self::new(
+            Arc::new(Expression::DateTimeUtc(
+                DateTimeUtcExpression::RoundToCalendarUnitInTimezone(
+                    expr.inner.clone(),
+                    unit.inner.clone(),
+                    timezone.inner.clone(),
+                ),
+            )),
+            expr.gil || unit.gil || timezone.gil,
+        )
+    }
+
+    #[staticmethod]
+    fn date_time_utc_add_calendar_units_in_timezone(
+        expr: &PyExpression,
+        unit: &PyExpression,
+        count: &PyExpression,
+        timezone: &PyExpression,
+    ) -> Self {
+        Self::new(
+            Arc::new(Expression::DateTimeUtc(
+                DateTimeUtcExpression::AddCalendarUnitsInTimezone(
+                    expr.inner.clone(),
+                    unit.inner.clone(),
+                    count.inner.clone(),
+                    timezone.inner.clone(),
+                ),
+            )),
+            expr.gil || unit.gil || count.gil || timezone.gil,
+        )
+    }
+
+    #[staticmethod]
+    fn date_time_utc_strftime_in_timezone(
+        expr: &PyExpression,
+        fmt: &PyExpression,
+        timezone: &PyExpression,
+    ) -> Self {
+        Self::new(
+            Arc::new(Expression::String(
+                StringExpression::DateTimeUtcStrftimeInTimezone(
+                    expr.inner.clone(),
+                    fmt.inner.clone(),
+                    timezone.inner.clone(),
+                ),
+            )),
+            expr.gil || fmt.gil || timezone.gil,
+        )
+    }
+
     #[staticmethod]
     fn json_get_item_unchecked(expr: &PyExpression, index: &PyExpression) -> Self {
         Self::new(
@@ -1183,6 +1620,8 @@ binary_expr!(eq, BoolExpression::Eq);
 binary_expr!(ne, BoolExpression::Ne);
 unary_expr!(int_abs, IntExpression::Abs);
 unary_expr!(float_abs, FloatExpression::Abs);
+binary_expr!(pointer_sample_rank, FloatExpression::PointerSampleRank);
+unary_expr!(estimated_size, IntExpression::EstimatedSize);
 binary_expr!(
     sequence_get_item_unchecked,
     AnyExpression::TupleGetItemUnchecked
@@ -1214,6 +1653,19 @@ binary_expr!(
     FloatExpression::DateTimeNaiveTimestamp
 );
 unary_expr!(date_time_naive_weekday, IntExpression::DateTimeNaiveWeekday);
+unary_expr!(
+    date_time_naive_iso_week,
+    IntExpression::DateTimeNaiveIsoWeek
+);
+unary_expr!(
+    date_time_naive_iso_year,
+    IntExpression::DateTimeNaiveIsoYear
+);
+unary_expr!(date_time_naive_quarter, IntExpression::DateTimeNaiveQuarter);
+unary_expr!(
+    date_time_naive_day_of_year,
+    IntExpression::DateTimeNaiveDayOfYear
+);
 binary_expr!(date_time_naive_strptime, DateTimeNaiveExpression::Strptime);
 binary_expr!(
     date_time_naive_strftime,
@@ -1227,9 +1679,33 @@ binary_expr!(
     date_time_naive_from_float_timestamp,
     DateTimeNaiveExpression::FromFloatTimestamp
 );
+unary_expr!(
+    date_time_naive_from_excel_serial,
+    DateTimeNaiveExpression::FromExcelSerial
+);
+unary_expr!(
+    date_time_naive_from_julian_day,
+    DateTimeNaiveExpression::FromJulianDay
+);
+unary_expr!(
+    date_time_naive_excel_serial,
+    FloatExpression::DateTimeNaiveExcelSerial
+);
+unary_expr!(
+    date_time_naive_julian_day,
+    FloatExpression::DateTimeNaiveJulianDay
+);
 binary_expr!(date_time_naive_to_utc, DateTimeUtcExpression::FromNaive);
 binary_expr!(date_time_naive_round, DateTimeNaiveExpression::Round);
 binary_expr!(date_time_naive_floor, DateTimeNaiveExpression::Floor);
+binary_expr!(
+    date_time_naive_floor_to_calendar_unit,
+    DateTimeNaiveExpression::FloorToCalendarUnit
+);
+binary_expr!(
+    date_time_naive_round_to_calendar_unit,
+    DateTimeNaiveExpression::RoundToCalendarUnit
+);
 unary_expr!(
     date_time_utc_nanosecond,
     IntExpression::DateTimeUtcNanosecond
@@ -1257,14 +1733,45 @@ binary_expr!(
     FloatExpression::DateTimeUtcTimestamp
 );
 unary_expr!(date_time_utc_weekday, IntExpression::DateTimeUtcWeekday);
+unary_expr!(date_time_utc_iso_week, IntExpression::DateTimeUtcIsoWeek);
+unary_expr!(date_time_utc_iso_year, IntExpression::DateTimeUtcIsoYear);
+unary_expr!(date_time_utc_quarter, IntExpression::DateTimeUtcQuarter);
+unary_expr!(
+    date_time_utc_day_of_year,
+    IntExpression::DateTimeUtcDayOfYear
+);
+unary_expr!(
+    date_time_utc_julian_day,
+    FloatExpression::DateTimeUtcJulianDay
+);
 binary_expr!(date_time_utc_strptime, DateTimeUtcExpression::Strptime);
+unary_expr!(
+    date_time_utc_parse_rfc3339,
+    DateTimeUtcExpression::ParseRfc3339
+);
+unary_expr!(
+    date_time_utc_parse_rfc2822,
+    DateTimeUtcExpression::ParseRfc2822
+);
 binary_expr!(
     date_time_utc_strftime,
     StringExpression::DateTimeUtcStrftime
 );
+binary_expr!(
+    date_time_utc_offset_seconds,
+    IntExpression::DateTimeUtcOffsetSeconds
+);
 binary_expr!(date_time_utc_to_naive, DateTimeNaiveExpression::FromUtc);
 binary_expr!(date_time_utc_round, DateTimeUtcExpression::Round);
 binary_expr!(date_time_utc_floor, DateTimeUtcExpression::Floor);
+binary_expr!(
+    date_time_utc_floor_to_calendar_unit,
+    DateTimeUtcExpression::FloorToCalendarUnit
+);
+binary_expr!(
+    date_time_utc_round_to_calendar_unit,
+    DateTimeUtcExpression::RoundToCalendarUnit
+);
 unary_expr!(duration_nanoseconds, IntExpression::DurationNanoseconds);
 unary_expr!(duration_microseconds, IntExpression::DurationMicroseconds);
 unary_expr!(duration_milliseconds, IntExpression::DurationMilliseconds);
@@ -1316,6 +1823,10 @@ impl PathwayType {
     pub const TUPLE: Type = Type::Tuple;
     #[classattr]
     pub const BYTES: Type = Type::Bytes;
+
+    pub fn __eq__(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
 }
 
 #[pyclass(module = "pathway.engine", frozen, name = "ReadMethod")]
@@ -1394,6 +1905,53 @@ impl PyMonitoringLevel {
     pub const ALL: MonitoringLevel = MonitoringLevel::All;
 }
 
+/// Stops a running computation gracefully when requested, see
+/// [`ShutdownRequest`](crate::engine::shutdown::ShutdownRequest).
+#[pyclass(module = "pathway.engine", frozen, name = "ShutdownHandle")]
+pub struct PyShutdownHandle(SharedShutdownRequest);
+
+#[pymethods]
+impl PyShutdownHandle {
+    #[new]
+    fn new() -> Self {
+        Self(SharedShutdownRequest::default())
+    }
+
+    fn request(&self) {
+        self.0.request();
+    }
+}
+
+/// The thresholds of the alerts on the health of the computation, see
+/// [`AlertThresholds`](crate::engine::alerts::AlertThresholds).
+#[pyclass(module = "pathway.engine", frozen, name = "AlertThresholds")]
+pub struct PyAlertThresholds(AlertThresholds);
+
+#[pymethods]
+impl PyAlertThresholds {
+    #[new]
+    #[pyo3(signature = (
+        *,
+        max_output_staleness_ms = None,
+        max_source_lag = None,
+        max_checkpoint_age_ms = None,
+        webhook_url = None
+    ))]
+    fn new(
+        max_output_staleness_ms: Option<u64>,
+        max_source_lag: Option<u64>,
+        max_checkpoint_age_ms: Option<u64>,
+        webhook_url: Option<String>,
+    ) -> Self {
+        Self(AlertThresholds {
+            max_output_staleness: max_output_staleness_ms.map(time::Duration::from_millis),
+            max_source_lag,
+            max_checkpoint_age: max_checkpoint_age_ms.map(time::Duration::from_millis),
+            webhook_url,
+        })
+    }
+}
+
 #[pyclass(module = "pathway.engine", frozen)]
 pub struct Universe {
     scope: Py<Scope>,
@@ -1829,10 +2387,17 @@ pub struct Scope {
     tables: RefCell<HashMap<TableHandle, Py<Table>>>,
     persistent_ids: RefCell<HashSet<ExternalPersistentId>>,
     event_loop: PyObject,
+    batch_latency_target: Option<time::Duration>,
+    operator_state_limit: Option<OperatorStateLimit>,
 }
 
 impl Scope {
-    fn new(parent: Option<Py<Self>>, event_loop: PyObject) -> Self {
+    fn new(
+        parent: Option<Py<Self>>,
+        event_loop: PyObject,
+        batch_latency_target: Option<time::Duration>,
+        operator_state_limit: Option<OperatorStateLimit>,
+    ) -> Self {
         Scope {
             parent,
 
@@ -1842,6 +2407,8 @@ impl Scope {
             tables: RefCell::new(HashMap::new()),
             persistent_ids: RefCell::new(HashSet::new()),
             event_loop,
+            batch_latency_target,
+            operator_state_limit,
         }
     }
 
@@ -1902,6 +2469,41 @@ impl Scope {
         Table::new(self_, handle)
     }
 
+    /// Creates a static table from the columns of a record batch, converting them in bulk
+    /// instead of a Python object at a time.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (
+        batch,
+        properties,
+        value_columns,
+        key_columns,
+        unsafe_trusted_ids = false,
+        time_column = None,
+        diff_column = None,
+        shard_column = None
+    ))]
+    pub fn static_table_from_arrow(
+        self_: &PyCell<Self>,
+        batch: PyArrowType<RecordBatch>,
+        properties: ConnectorProperties,
+        value_columns: Vec<String>,
+        key_columns: Vec<String>,
+        unsafe_trusted_ids: bool,
+        time_column: Option<String>,
+        diff_column: Option<String>,
+        shard_column: Option<String>,
+    ) -> PyResult<Py<Table>> {
+        let columns = ArrowColumns {
+            values: &value_columns,
+            keys: &key_columns,
+            time: time_column.as_deref(),
+            diff: diff_column.as_deref(),
+            shard: shard_column.as_deref(),
+        };
+        let data = data_rows_from_arrow(&batch.0, &columns, unsafe_trusted_ids)?;
+        Self::static_table(self_, data, properties)
+    }
+
     pub fn connector_table(
         self_: &PyCell<Self>,
         data_source: &PyCell<DataStorage>,
@@ -1927,6 +2529,12 @@ impl Scope {
         let (reader_impl, parallel_readers) = data_source.borrow().construct_reader(py)?;
 
         let parser_impl = data_format.borrow().construct_parser(py)?;
+        let schema = data_format
+            .borrow()
+            .value_fields
+            .iter()
+            .map(|field| field.borrow(py).as_schema_column())
+            .collect();
 
         let column_properties = properties.column_properties();
 
@@ -1939,12 +2547,13 @@ impl Scope {
             parallel_readers,
             Arc::new(EngineTableProperties::flat(column_properties)),
             persistent_id.as_ref(),
+            schema,
         )?;
         Table::new(self_, table_handle)
     }
 
     #[allow(clippy::type_complexity)]
-    #[pyo3(signature = (iterated, iterated_with_universe, extra, logic, *, limit = None))]
+    #[pyo3(signature = (iterated, iterated_with_universe, extra, logic, *, limit = None, fail_on_limit = false))]
     pub fn iterate(
         self_: &PyCell<Self>,
         #[pyo3(from_py_with = "engine_tables_from_py_iterable")] iterated: Vec<EngineLegacyTable>,
@@ -1954,8 +2563,13 @@ impl Scope {
         #[pyo3(from_py_with = "engine_tables_from_py_iterable")] extra: Vec<EngineLegacyTable>,
         logic: &PyAny,
         limit: Option<u32>,
+        fail_on_limit: bool,
     ) -> PyResult<(Vec<Py<LegacyTable>>, Vec<Py<LegacyTable>>)> {
         let py = self_.py();
+        let limit = limit.map(|iterations| IterationLimit {
+            iterations,
+            fail: fail_on_limit,
+        });
         let (result, result_with_universe) = self_.borrow().graph.iterate(
             iterated,
             iterated_with_universe,
@@ -1964,7 +2578,12 @@ impl Scope {
             Box::new(|graph, iterated, iterated_with_universe, extra| {
                 let scope = PyCell::new(
                     py,
-                    Scope::new(Some(self_.into()), self_.borrow().event_loop.clone()),
+                    Scope::new(
+                        Some(self_.into()),
+                        self_.borrow().event_loop.clone(),
+                        self_.borrow().batch_latency_target,
+                        self_.borrow().operator_state_limit,
+                    ),
                 )?;
                 scope.borrow().graph.scoped(graph, || {
                     let iterated = iterated
@@ -2029,7 +2648,10 @@ impl Scope {
                     with_gil_and_pool(|py| -> DynResult<_> {
                         let inputs = PyTuple::new(py, input);
                         let args = PyTuple::new(py, [inputs]);
-                        Ok(function.call1(py, args)?.extract::<Value>(py)?)
+                        let result = function
+                            .call1(py, args)
+                            .map_err(|e| python_exception(py, e))?;
+                        Ok(result.extract::<Value>(py)?)
                     })
                 }),
                 Expressions::AllArguments,
@@ -2042,12 +2664,14 @@ impl Scope {
         Column::new(universe, handle)
     }
 
+    #[pyo3(signature = (table, column_paths, function, properties, max_in_flight = None))]
     pub fn async_apply_table(
         self_: &PyCell<Self>,
         table: PyRef<Table>,
         #[pyo3(from_py_with = "from_py_iterable")] column_paths: Vec<ColumnPath>,
         function: Py<PyAny>,
         properties: TableProperties,
+        max_in_flight: Option<usize>,
     ) -> PyResult<Py<Table>> {
         let event_loop = self_.borrow().event_loop.clone();
         let table_handle = self_.borrow().graph.async_apply_table(
@@ -2063,7 +2687,8 @@ impl Scope {
                 });
 
                 Box::pin(async {
-                    let result = future?.await?;
+                    let exception = |e| with_gil_and_pool(|py| python_exception(py, e));
+                    let result = future.map_err(exception)?.await.map_err(exception)?;
                     with_gil_and_pool(|py| result.extract::<Value>(py).map_err(DynError::from))
                 })
             }),
@@ -2071,6 +2696,43 @@ impl Scope {
             column_paths,
             properties.0,
             EngineTrace::Empty,
+            max_in_flight,
+        )?;
+        Table::new(self_, table_handle)
+    }
+
+    #[pyo3(signature = (table, column_paths, function, properties, max_batch_size = None))]
+    pub fn batch_apply_table(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        #[pyo3(from_py_with = "from_py_iterable")] column_paths: Vec<ColumnPath>,
+        function: Py<PyAny>,
+        properties: TableProperties,
+        max_batch_size: Option<usize>,
+    ) -> PyResult<Py<Table>> {
+        let table_handle = self_.borrow().graph.batch_apply_table(
+            Arc::new(move |columns| {
+                with_gil_and_pool(|py| -> DynResult<_> {
+                    let args: Vec<PyObject> = columns
+                        .iter()
+                        .map(|column| batch_column_to_py(py, column))
+                        .collect();
+                    let results = function
+                        .call1(py, PyTuple::new(py, args))
+                        .map_err(|e| python_exception(py, e))?;
+                    let results = results
+                        .as_ref(py)
+                        .iter()?
+                        .map(|result| result?.extract())
+                        .collect::<PyResult<_>>()?;
+                    Ok(results)
+                })
+            }),
+            table.handle,
+            column_paths,
+            properties.0,
+            EngineTrace::Empty,
+            max_batch_size,
         )?;
         Table::new(self_, table_handle)
     }
@@ -2087,10 +2749,10 @@ impl Scope {
         let gil = expressions
             .iter()
             .any(|(expression, _properties)| expression.gil);
-        let wrapper = if gil {
-            BatchWrapper::WithGil
-        } else {
-            BatchWrapper::None
+        let wrapper = match (gil, self_.borrow().batch_latency_target) {
+            (true, Some(latency_target)) => BatchWrapper::AdaptiveWithGil(latency_target),
+            (true, None) => BatchWrapper::WithGil,
+            (false, _) => BatchWrapper::None,
         };
         let expressions: Vec<ExpressionData> = expressions
             .into_iter()
@@ -2216,50 +2878,46 @@ impl Scope {
         Table::new(self_, new_table_handle)
     }
 
-    pub fn freeze(
+    pub fn expire_after(
         self_: &PyCell<Self>,
         table: PyRef<Table>,
-        threshold_column_path: ColumnPath,
-        current_time_column_path: ColumnPath,
+        duration: u64,
         table_properties: TableProperties,
     ) -> PyResult<Py<Table>> {
-        let new_table_handle = self_.borrow().graph.freeze(
-            table.handle,
-            threshold_column_path,
-            current_time_column_path,
-            table_properties.0,
-        )?;
+        let new_table_handle =
+            self_
+                .borrow()
+                .graph
+                .expire_after(table.handle, duration, table_properties.0)?;
         Table::new(self_, new_table_handle)
     }
 
-    pub fn gradual_broadcast(
+    pub fn deduplicate_within(
         self_: &PyCell<Self>,
-        input_table: PyRef<Table>,
-        threshold_table: PyRef<Table>,
-        lower_path: ColumnPath,
-        value_path: ColumnPath,
-        upper_path: ColumnPath,
+        table: PyRef<Table>,
+        fingerprint_column_path: ColumnPath,
+        time_column_path: ColumnPath,
+        expiration_column_path: ColumnPath,
         table_properties: TableProperties,
     ) -> PyResult<Py<Table>> {
-        let new_table_handle = self_.borrow().graph.gradual_broadcast(
-            input_table.handle,
-            threshold_table.handle,
-            lower_path,
-            value_path,
-            upper_path,
+        let new_table_handle = self_.borrow().graph.deduplicate_within(
+            table.handle,
+            fingerprint_column_path,
+            time_column_path,
+            expiration_column_path,
             table_properties.0,
         )?;
         Table::new(self_, new_table_handle)
     }
 
-    pub fn buffer(
+    pub fn freeze(
         self_: &PyCell<Self>,
         table: PyRef<Table>,
         threshold_column_path: ColumnPath,
         current_time_column_path: ColumnPath,
         table_properties: TableProperties,
     ) -> PyResult<Py<Table>> {
-        let new_table_handle = self_.borrow().graph.buffer(
+        let new_table_handle = self_.borrow().graph.freeze(
             table.handle,
             threshold_column_path,
             current_time_column_path,
@@ -2268,14 +2926,83 @@ impl Scope {
         Table::new(self_, new_table_handle)
     }
 
-    pub fn intersect_tables(
+    pub fn freeze_late(
         self_: &PyCell<Self>,
         table: PyRef<Table>,
-        #[pyo3(from_py_with = "from_py_iterable")] tables: Vec<PyRef<Table>>,
+        threshold_column_path: ColumnPath,
+        current_time_column_path: ColumnPath,
         table_properties: TableProperties,
     ) -> PyResult<Py<Table>> {
-        let table_handles = tables.into_iter().map(|table| table.handle).collect();
-        let result_table_handle = self_.borrow().graph.intersect_tables(
+        let new_table_handle = self_.borrow().graph.freeze_late(
+            table.handle,
+            threshold_column_path,
+            current_time_column_path,
+            table_properties.0,
+        )?;
+        Table::new(self_, new_table_handle)
+    }
+
+    #[pyo3(signature = (table, column_paths, table_properties, parallelism = None))]
+    pub fn reshard_table(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        #[pyo3(from_py_with = "from_py_iterable")] column_paths: Vec<ColumnPath>,
+        table_properties: TableProperties,
+        parallelism: Option<usize>,
+    ) -> PyResult<Py<Table>> {
+        let new_table_handle = self_.borrow().graph.reshard_table(
+            table.handle,
+            column_paths,
+            parallelism,
+            table_properties.0,
+        )?;
+        Table::new(self_, new_table_handle)
+    }
+
+    pub fn gradual_broadcast(
+        self_: &PyCell<Self>,
+        input_table: PyRef<Table>,
+        threshold_table: PyRef<Table>,
+        lower_path: ColumnPath,
+        value_path: ColumnPath,
+        upper_path: ColumnPath,
+        table_properties: TableProperties,
+    ) -> PyResult<Py<Table>> {
+        let new_table_handle = self_.borrow().graph.gradual_broadcast(
+            input_table.handle,
+            threshold_table.handle,
+            lower_path,
+            value_path,
+            upper_path,
+            table_properties.0,
+        )?;
+        Table::new(self_, new_table_handle)
+    }
+
+    pub fn buffer(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        threshold_column_path: ColumnPath,
+        current_time_column_path: ColumnPath,
+        table_properties: TableProperties,
+    ) -> PyResult<Py<Table>> {
+        let new_table_handle = self_.borrow().graph.buffer(
+            table.handle,
+            threshold_column_path,
+            current_time_column_path,
+            table_properties.0,
+        )?;
+        Table::new(self_, new_table_handle)
+    }
+
+    pub fn intersect_tables(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        #[pyo3(from_py_with = "from_py_iterable")] tables: Vec<PyRef<Table>>,
+        table_properties: TableProperties,
+    ) -> PyResult<Py<Table>> {
+        let table_handles = tables.into_iter().map(|table| table.handle).collect();
+        let result_table_handle = self_.borrow().graph.intersect_tables(
             table.handle,
             table_handles,
             table_properties.0,
@@ -2324,6 +3051,18 @@ impl Scope {
         Table::new(self_, new_table_handle)
     }
 
+    pub fn changelog_table(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        table_properties: TableProperties,
+    ) -> PyResult<Py<Table>> {
+        let new_table_handle = self_
+            .borrow()
+            .graph
+            .changelog_table(table.handle, table_properties.0)?;
+        Table::new(self_, new_table_handle)
+    }
+
     pub fn sort_table(
         self_: &PyCell<Self>,
         table: PyRef<Table>,
@@ -2340,6 +3079,84 @@ impl Scope {
         Table::new(self_, new_table_handle)
     }
 
+    pub fn session_window_table(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        max_gap: Value,
+        table_properties: TableProperties,
+    ) -> PyResult<Py<Table>> {
+        let new_table_handle = self_.borrow().graph.session_window_table(
+            table.handle,
+            key_column_path,
+            instance_column_path,
+            max_gap,
+            table_properties.0,
+        )?;
+        Table::new(self_, new_table_handle)
+    }
+
+    pub fn top_n_table(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        n: usize,
+        descending: bool,
+        table_properties: TableProperties,
+    ) -> PyResult<Py<Table>> {
+        let new_table_handle = self_.borrow().graph.top_n_table(
+            table.handle,
+            key_column_path,
+            instance_column_path,
+            n,
+            descending,
+            table_properties.0,
+        )?;
+        Table::new(self_, new_table_handle)
+    }
+
+    pub fn rank_table(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        descending: bool,
+        table_properties: TableProperties,
+    ) -> PyResult<Py<Table>> {
+        let new_table_handle = self_.borrow().graph.rank_table(
+            table.handle,
+            key_column_path,
+            instance_column_path,
+            descending,
+            table_properties.0,
+        )?;
+        Table::new(self_, new_table_handle)
+    }
+
+    pub fn cumulative_table(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        key_column_path: ColumnPath,
+        instance_column_path: ColumnPath,
+        #[pyo3(from_py_with = "from_py_iterable")] reducers: Vec<(Reducer, Vec<ColumnPath>)>,
+        table_properties: TableProperties,
+    ) -> PyResult<Py<Table>> {
+        let reducers = reducers
+            .into_iter()
+            .map(|(reducer, paths)| ReducerData::new(reducer, paths))
+            .collect();
+        let new_table_handle = self_.borrow().graph.cumulative_table(
+            table.handle,
+            key_column_path,
+            instance_column_path,
+            reducers,
+            table_properties.0,
+        )?;
+        Table::new(self_, new_table_handle)
+    }
+
     pub fn reindex_table(
         self_: &PyCell<Self>,
         table: PyRef<Table>,
@@ -2585,6 +3402,16 @@ impl Scope {
         Ok(())
     }
 
+    #[pyo3(signature = (
+        table,
+        column_paths,
+        skip_persisted_batch,
+        on_change,
+        on_time_end,
+        on_end,
+        on_commit = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
     pub fn subscribe_table(
         self_: &PyCell<Self>,
         table: PyRef<Table>,
@@ -2593,8 +3420,9 @@ impl Scope {
         on_change: Py<PyAny>,
         on_time_end: Py<PyAny>,
         on_end: Py<PyAny>,
+        on_commit: Option<Py<PyAny>>,
     ) -> PyResult<()> {
-        let callbacks = SubscribeCallbacksBuilder::new()
+        let mut callbacks = SubscribeCallbacksBuilder::new()
             .wrapper(BatchWrapper::WithGil)
             .on_data(Box::new(move |key, values, time, diff| {
                 with_gil_and_pool(|py| {
@@ -2613,26 +3441,47 @@ impl Scope {
                     on_end.call0(py)?;
                     Ok(())
                 })
-            }))
-            .build();
+            }));
+        if let Some(on_commit) = on_commit {
+            callbacks = callbacks.on_commit(Box::new(move |time| {
+                with_gil_and_pool(|py| {
+                    on_commit.call1(py, (time,))?;
+                    Ok(())
+                })
+            }));
+        }
         self_.borrow().graph.subscribe_table(
             table.handle,
             column_paths,
-            callbacks,
+            callbacks.build(),
             skip_persisted_batch,
         )?;
         Ok(())
     }
 
+    pub fn serve_table(
+        self_: &PyCell<Self>,
+        table: PyRef<Table>,
+        #[pyo3(from_py_with = "from_py_iterable")] column_paths: Vec<ColumnPath>,
+        name: String,
+        column_names: Vec<String>,
+    ) -> PyResult<()> {
+        self_
+            .borrow()
+            .graph
+            .serve_table(table.handle, column_paths, name, column_names)?;
+        Ok(())
+    }
+
     pub fn probe_table(
         self_: &PyCell<Self>,
         table: PyRef<Table>,
         operator_id: usize,
     ) -> PyResult<()> {
+        let self_ = self_.borrow();
         self_
-            .borrow()
             .graph
-            .probe_table(table.handle, operator_id)?;
+            .probe_table(table.handle, operator_id, self_.operator_state_limit)?;
         Ok(())
     }
 }
@@ -2674,6 +3523,7 @@ pub fn make_captured_table(table_data: Vec<CapturedTableData>) -> PyResult<Vec<D
 
 #[pyfunction]
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::fn_params_excessive_bools)]
 #[pyo3(signature = (
     logic,
     event_loop,
@@ -2681,7 +3531,15 @@ pub fn make_captured_table(table_data: Vec<CapturedTableData>) -> PyResult<Vec<D
     ignore_asserts = false,
     monitoring_level = MonitoringLevel::None,
     with_http_server = false,
-    persistence_config = None
+    persistence_config = None,
+    batch_latency_target_ms = None,
+    operator_state_limit = None,
+    fail_on_operator_state_limit = false,
+    consistent_outputs = false,
+    otlp_endpoint = None,
+    shutdown_handle = None,
+    topology = Vec::new(),
+    alert_thresholds = None
 ))]
 pub fn run_with_new_graph(
     py: Python,
@@ -2692,12 +3550,32 @@ pub fn run_with_new_graph(
     monitoring_level: MonitoringLevel,
     with_http_server: bool,
     persistence_config: Option<PersistenceConfig>,
+    batch_latency_target_ms: Option<u64>,
+    operator_state_limit: Option<u64>,
+    fail_on_operator_state_limit: bool,
+    consistent_outputs: bool,
+    otlp_endpoint: Option<String>,
+    shutdown_handle: Option<Py<PyShutdownHandle>>,
+    topology: Vec<(usize, String, Vec<usize>)>,
+    alert_thresholds: Option<Py<PyAlertThresholds>>,
 ) -> PyResult<Vec<Vec<DataRow>>> {
     defer! {
         log::logger().flush();
     }
     let (config, num_workers) =
         config_from_env().map_err(|msg| PyErr::from_type(ENGINE_ERROR_TYPE.as_ref(py), msg))?;
+    let operator_state_limit = operator_state_limit.map(|bytes| OperatorStateLimit {
+        bytes,
+        fail: fail_on_operator_state_limit,
+    });
+    let shutdown_request = shutdown_handle.map_or_else(SharedShutdownRequest::default, |handle| {
+        handle.borrow(py).0.clone()
+    });
+    let topology = topology
+        .into_iter()
+        .map(|(id, name, inputs)| OperatorNode { id, name, inputs })
+        .collect();
+    let alert_thresholds = alert_thresholds.map(|thresholds| thresholds.borrow(py).0.clone());
     let persistence_config = {
         if let Some(persistence_config) = persistence_config {
             Some(persistence_config.prepare(py)?)
@@ -2712,7 +3590,15 @@ pub fn run_with_new_graph(
                     let thread_state = PythonThreadState::new();
 
                     let captured_tables = Python::with_gil(|py| {
-                        let our_scope = PyCell::new(py, Scope::new(None, event_loop.clone()))?;
+                        let our_scope = PyCell::new(
+                            py,
+                            Scope::new(
+                                None,
+                                event_loop.clone(),
+                                batch_latency_target_ms.map(time::Duration::from_millis),
+                                operator_state_limit,
+                            ),
+                        )?;
                         let tables: Vec<(PyRef<Table>, Vec<ColumnPath>)> =
                             our_scope.borrow().graph.scoped(graph, || {
                                 let args = PyTuple::new(py, [our_scope]);
@@ -2735,6 +3621,11 @@ pub fn run_with_new_graph(
                 with_http_server,
                 persistence_config,
                 num_workers,
+                consistent_outputs,
+                otlp_endpoint,
+                shutdown_request,
+                topology,
+                alert_thresholds,
             )
         })
     })??;
@@ -2766,6 +3657,92 @@ pub fn unsafe_make_pointer(value: KeyImpl) -> Key {
     Key(value)
 }
 
+/// Makes the engine use the current levels of the Python loggers of its logs.
+#[pyfunction]
+#[pyo3(signature = (*, trace = false))]
+pub fn refresh_engine_logging(trace: bool) {
+    logging::refresh(trace);
+}
+
+/// Converts the captured updates of a table to a record batch with its current rows.
+#[pyfunction]
+#[pyo3(signature = (captured, column_names, include_id = true))]
+pub fn captured_table_to_arrow(
+    #[pyo3(from_py_with = "from_py_iterable")] captured: Vec<PyRef<DataRow>>,
+    column_names: Vec<String>,
+    include_id: bool,
+) -> PyResult<PyArrowType<RecordBatch>> {
+    let rows: Vec<&DataRow> = captured.iter().map(|row| &**row).collect();
+    data_rows_to_arrow(&rows, &column_names, include_id).map(PyArrowType)
+}
+
+#[pyfunction]
+pub fn restore_savepoint(
+    py: Python,
+    persistence_config: PersistenceConfig,
+    name: &str,
+) -> PyResult<()> {
+    let mut backend = persistence_config
+        .prepare(py)?
+        .into_inner(0, 1)
+        .create_metadata_backend()
+        .map_err(EngineError::from)?;
+    restore_persisted_savepoint(backend.as_mut(), name).map_err(EngineError::from)?;
+    Ok(())
+}
+
+#[pyfunction]
+pub fn export_persisted_rows(
+    py: Python,
+    persistence_config: PersistenceConfig,
+    persistent_id: ExternalPersistentId,
+) -> PyResult<(Vec<String>, Vec<dump::PersistedRow>)> {
+    let persisted_rows = dump::export_persisted_rows(
+        persistence_config.prepare(py)?,
+        persistent_id.into_persistent_id(),
+    )
+    .map_err(|e| PyIOError::new_err(format!("Failed to export the persisted rows: {e}")))?;
+    let columns = persisted_rows
+        .columns
+        .into_iter()
+        .map(|column| column.name)
+        .collect();
+    Ok((columns, persisted_rows.rows))
+}
+
+#[pyfunction]
+pub fn import_persisted_rows(
+    py: Python,
+    persistence_config: PersistenceConfig,
+    persistent_id: ExternalPersistentId,
+    columns: Vec<String>,
+    rows: Vec<dump::PersistedRow>,
+) -> PyResult<()> {
+    if let Some((_, values)) = rows
+        .iter()
+        .find(|(_, values)| values.len() != columns.len())
+    {
+        return Err(PyValueError::new_err(format!(
+            "a row has {} values, while there are {} columns",
+            values.len(),
+            columns.len()
+        )));
+    }
+    let columns = columns
+        .into_iter()
+        .map(|name| PersistedColumn {
+            name,
+            type_: Type::Any,
+        })
+        .collect();
+    dump::import_persisted_rows(
+        &persistence_config.prepare(py)?,
+        persistent_id.into_persistent_id(),
+        dump::PersistedRows { columns, rows },
+    )
+    .map_err(|e| PyIOError::new_err(format!("Failed to import the persisted rows: {e}")))
+}
+
 #[pyclass(module = "pathway.engine", frozen)]
 pub struct AwsS3Settings {
     bucket_name: Option<String>,
@@ -2935,6 +3912,110 @@ impl AwsS3Settings {
     }
 }
 
+#[pyclass(module = "pathway.engine", frozen)]
+pub struct AzureBlobStorageSettings {
+    account_name: String,
+    container_name: String,
+    account_key: Option<String>,
+    sas_token: Option<String>,
+    endpoint: Option<String>,
+}
+
+#[pymethods]
+impl AzureBlobStorageSettings {
+    #[new]
+    #[pyo3(signature = (
+        account_name,
+        container_name,
+        account_key = None,
+        sas_token = None,
+        endpoint = None,
+    ))]
+    fn new(
+        account_name: String,
+        container_name: String,
+        account_key: Option<String>,
+        sas_token: Option<String>,
+        endpoint: Option<String>,
+    ) -> PyResult<Self> {
+        if account_key.is_some() == sas_token.is_some() {
+            return Err(PyValueError::new_err(
+                "exactly one of account_key and sas_token must be specified",
+            ));
+        }
+        Ok(AzureBlobStorageSettings {
+            account_name,
+            container_name,
+            account_key,
+            sas_token,
+            endpoint,
+        })
+    }
+}
+
+impl AzureBlobStorageSettings {
+    fn construct_storage(&self) -> PyResult<SharedObjectStorage> {
+        let credentials = match (&self.account_key, &self.sas_token) {
+            (Some(account_key), _) => AzureCredentials::AccountKey(account_key.clone()),
+            (None, Some(sas_token)) => AzureCredentials::SasToken(sas_token.clone()),
+            (None, None) => unreachable!("the credentials are checked in the constructor"),
+        };
+        let storage = AzureBlobStorage::new(
+            self.account_name.clone(),
+            self.container_name.clone(),
+            self.endpoint.as_deref(),
+            credentials,
+        )
+        .map_err(|err| {
+            PyValueError::new_err(format!("Failed to connect to Azure Blob Storage: {err}"))
+        })?;
+        Ok(Arc::new(storage))
+    }
+}
+
+#[pyclass(module = "pathway.engine", frozen)]
+pub struct GcsSettings {
+    bucket_name: String,
+    credentials_json: Option<String>,
+    endpoint: Option<String>,
+}
+
+#[pymethods]
+impl GcsSettings {
+    #[new]
+    #[pyo3(signature = (bucket_name, credentials_json = None, endpoint = None))]
+    fn new(
+        bucket_name: String,
+        credentials_json: Option<String>,
+        endpoint: Option<String>,
+    ) -> Self {
+        GcsSettings {
+            bucket_name,
+            credentials_json,
+            endpoint,
+        }
+    }
+}
+
+impl GcsSettings {
+    fn construct_storage(&self) -> PyResult<SharedObjectStorage> {
+        // Without credentials, the ones of the machine are taken from the metadata server
+        let credentials = match &self.credentials_json {
+            Some(credentials_json) => GcsCredentials::Json(credentials_json.clone()),
+            None => GcsCredentials::MetadataServer,
+        };
+        let storage = GcsStorage::new(
+            self.bucket_name.clone(),
+            self.endpoint.as_deref(),
+            credentials,
+        )
+        .map_err(|err| {
+            PyValueError::new_err(format!("Failed to connect to Google Cloud Storage: {err}"))
+        })?;
+        Ok(Arc::new(storage))
+    }
+}
+
 #[pyclass(module = "pathway.engine", frozen)]
 pub struct ElasticSearchAuth {
     auth_type: String,
@@ -3075,6 +4156,10 @@ pub struct DataStorage {
     mock_events: Option<HashMap<(ExternalPersistentId, usize), Vec<SnapshotEvent>>>,
     table_name: Option<String>,
     column_names: Option<Vec<String>>,
+    transaction_id: Option<String>,
+    python_sink: Option<Py<PythonSink>>,
+    azure_blob_storage_settings: Option<Py<AzureBlobStorageSettings>>,
+    gcs_settings: Option<Py<GcsSettings>>,
 }
 
 #[pyclass(module = "pathway.engine", frozen, name = "PersistenceMode")]
@@ -3135,11 +4220,23 @@ impl IntoPy<PyObject> for SnapshotAccess {
 #[pyclass(module = "pathway.engine", frozen)]
 pub struct PersistenceConfig {
     snapshot_interval: ::std::time::Duration,
+    snapshot_interval_rows: Option<u64>,
+    snapshot_interval_bytes: Option<u64>,
     metadata_storage: DataStorage,
     stream_storage: DataStorage,
     snapshot_access: SnapshotAccess,
     persistence_mode: PersistenceMode,
     continue_after_replay: bool,
+    replay_until: Option<u64>,
+    snapshot_compression: Option<String>,
+    snapshot_compression_level: Option<i32>,
+    encryption_key: Option<Vec<u8>>,
+    migrate_unencrypted_data: bool,
+    savepoint_requests: SharedSavepointRequests,
+    checkpoint_hooks: SharedCheckpointHooks,
+    schema_migrations: HashMap<ExternalPersistentId, HashMap<String, ColumnMigration>>,
+    retention_policy: RetentionPolicy,
+    background_checkpoints: bool,
 }
 
 #[pymethods]
@@ -3153,7 +4250,19 @@ impl PersistenceConfig {
         snapshot_access = SnapshotAccess::Full,
         persistence_mode = PersistenceMode::Batch,
         continue_after_replay = true,
+        replay_until = None,
+        snapshot_compression = None,
+        snapshot_compression_level = None,
+        encryption_key = None,
+        migrate_unencrypted_data = false,
+        schema_migrations = HashMap::new(),
+        snapshot_interval_rows = None,
+        snapshot_interval_bytes = None,
+        keep_last_checkpoints = None,
+        keep_checkpoints_ms = None,
+        background_checkpoints = false,
     ))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         snapshot_interval_ms: u64,
         metadata_storage: DataStorage,
@@ -3161,32 +4270,171 @@ impl PersistenceConfig {
         snapshot_access: SnapshotAccess,
         persistence_mode: PersistenceMode,
         continue_after_replay: bool,
+        replay_until: Option<u64>,
+        snapshot_compression: Option<String>,
+        snapshot_compression_level: Option<i32>,
+        encryption_key: Option<Vec<u8>>,
+        migrate_unencrypted_data: bool,
+        schema_migrations: HashMap<ExternalPersistentId, HashMap<String, ColumnMigration>>,
+        snapshot_interval_rows: Option<u64>,
+        snapshot_interval_bytes: Option<u64>,
+        keep_last_checkpoints: Option<usize>,
+        keep_checkpoints_ms: Option<u64>,
+        background_checkpoints: bool,
     ) -> Self {
         Self {
             snapshot_interval: ::std::time::Duration::from_millis(snapshot_interval_ms),
+            snapshot_interval_rows,
+            snapshot_interval_bytes,
             metadata_storage,
             stream_storage,
             snapshot_access,
             persistence_mode,
             continue_after_replay,
+            replay_until,
+            snapshot_compression,
+            snapshot_compression_level,
+            encryption_key,
+            migrate_unencrypted_data,
+            savepoint_requests: SharedSavepointRequests::default(),
+            checkpoint_hooks: SharedCheckpointHooks::default(),
+            schema_migrations,
+            retention_policy: RetentionPolicy {
+                keep_last_checkpoints,
+                keep_checkpoints_for: keep_checkpoints_ms.map(::std::time::Duration::from_millis),
+            },
+            background_checkpoints,
+        }
+    }
+
+    fn request_savepoint(&self, name: String) -> PyResult<()> {
+        if !is_valid_savepoint_name(&name) {
+            return Err(PyValueError::new_err(format!(
+                "invalid savepoint name {name:?}, only letters, digits, '_' and '-' are allowed"
+            )));
         }
+        self.savepoint_requests.lock().unwrap().push(name);
+        Ok(())
+    }
+
+    #[pyo3(signature = (
+        *,
+        on_checkpoint_start = None,
+        on_checkpoint_complete = None,
+        on_restore_complete = None,
+    ))]
+    fn register_checkpoint_hooks(
+        &self,
+        on_checkpoint_start: Option<Py<PyAny>>,
+        on_checkpoint_complete: Option<Py<PyAny>>,
+        on_restore_complete: Option<Py<PyAny>>,
+    ) {
+        self.checkpoint_hooks
+            .lock()
+            .unwrap()
+            .push(Box::new(PyCheckpointHook {
+                checkpoint_start: on_checkpoint_start,
+                checkpoint_complete: on_checkpoint_complete,
+                restore_complete: on_restore_complete,
+            }));
     }
 }
 
 impl PersistenceConfig {
     fn prepare(self, py: pyo3::Python) -> PyResult<PersistenceManagerOuterConfig> {
+        let encryption_key = self
+            .encryption_key
+            .map(|key| EncryptionKey::new(&key))
+            .transpose()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let snapshot_compression = self
+            .snapshot_compression
+            .map(|codec| SnapshotCompression::new(&codec, self.snapshot_compression_level))
+            .transpose()
+            .map_err(PyValueError::new_err)?;
         Ok(PersistenceManagerOuterConfig::new(
             self.snapshot_interval,
+            self.snapshot_interval_rows,
+            self.snapshot_interval_bytes,
             self.metadata_storage
                 .construct_metadata_storage_config(py)?,
             self.stream_storage.construct_stream_storage_config(py)?,
             self.snapshot_access,
             self.persistence_mode,
             self.continue_after_replay,
+            self.replay_until,
+            snapshot_compression,
+            encryption_key,
+            self.migrate_unencrypted_data,
+            self.savepoint_requests,
+            self.checkpoint_hooks,
+            self.schema_migrations
+                .into_iter()
+                .map(|(persistent_id, migrations)| {
+                    let migrations = migrations
+                        .into_iter()
+                        .map(|(column, migration)| (column, migration.0))
+                        .collect();
+                    (persistent_id, migrations)
+                })
+                .collect(),
+            self.retention_policy,
+            self.background_checkpoints,
         ))
     }
 }
 
+#[derive(Debug)]
+struct PyCheckpointHook {
+    checkpoint_start: Option<Py<PyAny>>,
+    checkpoint_complete: Option<Py<PyAny>>,
+    restore_complete: Option<Py<PyAny>>,
+}
+
+impl PyCheckpointHook {
+    fn call(callback: Option<&Py<PyAny>>, timestamp: Option<u64>) {
+        let Some(callback) = callback else {
+            return;
+        };
+        with_gil_and_pool(|py| {
+            if let Err(e) = callback.call1(py, (timestamp,)) {
+                e.print(py);
+                error!("Checkpoint hook failed: {e}");
+            }
+        });
+    }
+}
+
+impl CheckpointHook for PyCheckpointHook {
+    fn on_checkpoint_start(&mut self, timestamp: Option<u64>) {
+        Self::call(self.checkpoint_start.as_ref(), timestamp);
+    }
+
+    fn on_checkpoint_complete(&mut self, timestamp: Option<u64>) {
+        Self::call(self.checkpoint_complete.as_ref(), timestamp);
+    }
+
+    fn on_restore_complete(&mut self, timestamp: u64) {
+        Self::call(self.restore_complete.as_ref(), Some(timestamp));
+    }
+}
+
+#[pyclass(module = "pathway.engine", frozen)]
+#[derive(Clone, Debug)]
+pub struct ColumnMigration(EngineColumnMigration);
+
+#[pymethods]
+impl ColumnMigration {
+    #[new]
+    #[pyo3(signature = (*, renamed_from = None, fill_value = None))]
+    fn new(renamed_from: Option<String>, fill_value: Option<Value>) -> Self {
+        Self(EngineColumnMigration {
+            renamed_from,
+            fill_value,
+        })
+    }
+}
+
 impl<'source> FromPyObject<'source> for SnapshotEvent {
     fn extract(ob: &'source PyAny) -> PyResult<Self> {
         Ok(ob.extract::<PyRef<PySnapshotEvent>>()?.0.clone())
@@ -3228,18 +4476,38 @@ pub struct PythonSubject {
     pub end: Py<PyAny>,
     pub is_internal: bool,
     pub deletions_enabled: bool,
+    /// Whether the subject produces its messages again from the beginning after a
+    /// restart. If so, the messages read before the restart are skipped.
+    pub replays_from_beginning: bool,
+    /// Called with the cursor persisted last, before reading, to resume from it.
+    pub seek: Option<Py<PyAny>>,
+    /// Called with a cursor once the persisted state covers the messages up to it.
+    pub on_persisted: Option<Py<PyAny>>,
 }
 
 #[pymethods]
 impl PythonSubject {
     #[new]
-    #[pyo3(signature = (start, read, end, is_internal, deletions_enabled))]
+    #[pyo3(signature = (
+        start,
+        read,
+        end,
+        is_internal,
+        deletions_enabled,
+        replays_from_beginning = true,
+        seek = None,
+        on_persisted = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         start: Py<PyAny>,
         read: Py<PyAny>,
         end: Py<PyAny>,
         is_internal: bool,
         deletions_enabled: bool,
+        replays_from_beginning: bool,
+        seek: Option<Py<PyAny>>,
+        on_persisted: Option<Py<PyAny>>,
     ) -> Self {
         Self {
             start,
@@ -3247,10 +4515,30 @@ impl PythonSubject {
             end,
             is_internal,
             deletions_enabled,
+            replays_from_beginning,
+            seek,
+            on_persisted,
         }
     }
 }
 
+#[pyclass(module = "pathway.engine", frozen)]
+#[derive(Clone)]
+pub struct PythonSink {
+    /// Called with each formatted update of the table.
+    pub write: Py<PyAny>,
+    /// Called once all the updates of a time are written.
+    pub flush: Py<PyAny>,
+}
+
+#[pymethods]
+impl PythonSink {
+    #[new]
+    fn new(write: Py<PyAny>, flush: Py<PyAny>) -> Self {
+        Self { write, flush }
+    }
+}
+
 #[pyclass(module = "pathway.engine")]
 #[derive(Clone)]
 pub struct ValueField {
@@ -3266,6 +4554,14 @@ impl ValueField {
     fn as_inner_schema_field(&self) -> InnerSchemaField {
         InnerSchemaField::new(self.type_, self.default.clone())
     }
+
+    fn as_schema_column(&self) -> SchemaColumn {
+        SchemaColumn {
+            name: self.name.clone(),
+            type_: self.type_,
+            default: self.default.clone(),
+        }
+    }
 }
 
 #[pymethods]
@@ -3320,6 +4616,10 @@ impl DataStorage {
         mock_events = None,
         table_name = None,
         column_names = None,
+        transaction_id = None,
+        python_sink = None,
+        azure_blob_storage_settings = None,
+        gcs_settings = None,
     ))]
     #[allow(clippy::too_many_arguments)]
     fn new(
@@ -3341,6 +4641,10 @@ impl DataStorage {
         mock_events: Option<HashMap<(ExternalPersistentId, usize), Vec<SnapshotEvent>>>,
         table_name: Option<String>,
         column_names: Option<Vec<String>>,
+        transaction_id: Option<String>,
+        python_sink: Option<Py<PythonSink>>,
+        azure_blob_storage_settings: Option<Py<AzureBlobStorageSettings>>,
+        gcs_settings: Option<Py<GcsSettings>>,
     ) -> Self {
         DataStorage {
             storage_type,
@@ -3361,6 +4665,10 @@ impl DataStorage {
             mock_events,
             table_name,
             column_names,
+            transaction_id,
+            python_sink,
+            azure_blob_storage_settings,
+            gcs_settings,
         }
     }
 }
@@ -3512,6 +4820,32 @@ impl DataStorage {
         Ok(bucket)
     }
 
+    fn object_storage(&self, py: pyo3::Python) -> PyResult<SharedObjectStorage> {
+        match self.storage_type.as_ref() {
+            "azure" => self
+                .azure_blob_storage_settings
+                .as_ref()
+                .ok_or_else(|| {
+                    PyValueError::new_err(
+                        "For Azure storage, azure_blob_storage_settings must be specified",
+                    )
+                })?
+                .borrow(py)
+                .construct_storage(),
+            "gcs" => self
+                .gcs_settings
+                .as_ref()
+                .ok_or_else(|| {
+                    PyValueError::new_err("For GCS storage, gcs_settings must be specified")
+                })?
+                .borrow(py)
+                .construct_storage(),
+            other => Err(PyValueError::new_err(format!(
+                "{other:?} isn't an object storage"
+            ))),
+        }
+    }
+
     fn kafka_client_config(&self) -> PyResult<ClientConfig> {
         let rdkafka_settings = self.rdkafka_settings.as_ref().ok_or_else(|| {
             PyValueError::new_err("For kafka input, rdkafka_settings must be specified")
@@ -3526,6 +4860,15 @@ impl DataStorage {
         Ok(client_config)
     }
 
+    fn kafka_consumer_config(&self) -> PyResult<ClientConfig> {
+        let mut client_config = self.kafka_client_config()?;
+        if self.persistent_id.is_some() {
+            // The offsets are committed together with the checkpoints of the persisted state
+            client_config.set("enable.auto.commit", "false");
+        }
+        Ok(client_config)
+    }
+
     fn kafka_topic(&self) -> PyResult<&str> {
         let topic = self
             .topic
@@ -3605,7 +4948,7 @@ impl DataStorage {
                 Ok((Box::new(reader), 1))
             }
             "kafka" => {
-                let client_config = self.kafka_client_config()?;
+                let client_config = self.kafka_consumer_config()?;
 
                 let consumer: BaseConsumer = client_config.create().map_err(|e| {
                     PyValueError::new_err(format!("Creating Kafka consumer failed: {e}"))
@@ -3670,6 +5013,10 @@ impl DataStorage {
                     root_path: path.into(),
                 })
             }
+            "azure" | "gcs" => Ok(StreamStorageConfig::ObjectStorage {
+                storage: self.object_storage(py)?,
+                root_path: self.path()?.into(),
+            }),
             "mock" => {
                 let mut events = HashMap::<ConnectorWorkerPair, Vec<SnapshotEvent>>::new();
                 for ((external_persistent_id, worker_id), es) in self.mock_events.as_ref().unwrap()
@@ -3700,6 +5047,10 @@ impl DataStorage {
                     root_path: path.into(),
                 })
             }
+            "azure" | "gcs" => Ok(MetadataStorageConfig::ObjectStorage {
+                storage: self.object_storage(py)?,
+                root_path: self.path()?.into(),
+            }),
             "mock" => Ok(MetadataStorageConfig::Mock),
             other => Err(PyValueError::new_err(format!(
                 "Unsupported metadata storage format: {other:?}"
@@ -3749,15 +5100,31 @@ impl DataStorage {
                         Err(_) => return Err(PyIOError::new_err("Producer creation failed")),
                     };
 
-                let topic = self.kafka_topic()?;
-                let writer = KafkaWriter::new(producer, topic.to_string());
+                let topic = self.kafka_topic()?.to_string();
+                let transactional = client_config.get("transactional.id").is_some();
+                let writer = if transactional {
+                    KafkaWriter::new_transactional(producer, topic, &client_config).map_err(
+                        |e| {
+                            PyIOError::new_err(format!(
+                                "Failed to initialize Kafka transactions: {e}"
+                            ))
+                        },
+                    )?
+                } else {
+                    KafkaWriter::new(producer, topic)
+                };
 
                 Ok(Box::new(writer))
             }
             "postgres" => {
                 let connection_string = self.connection_string()?;
                 let storage = match Client::connect(connection_string, NoTls) {
-                    Ok(client) => PsqlWriter::new(client, self.max_batch_size),
+                    Ok(client) => match &self.transaction_id {
+                        Some(transaction_id) => {
+                            PsqlWriter::new_transactional(client, transaction_id.clone())
+                        }
+                        None => PsqlWriter::new(client, self.max_batch_size),
+                    },
                     Err(e) => {
                         return Err(PyIOError::new_err(format!(
                             "Failed to establish PostgreSQL connection: {e:?}"
@@ -3775,6 +5142,12 @@ impl DataStorage {
                 let writer = ElasticSearchWriter::new(client, index_name, max_batch_size);
                 Ok(Box::new(writer))
             }
+            "python" => {
+                let sink = self.python_sink.clone().ok_or_else(|| {
+                    PyValueError::new_err("For Python output, python_sink should be specified")
+                })?;
+                Ok(Box::new(PythonWriter::new(sink)))
+            }
             "null" => Ok(Box::new(NullWriter::new())),
             other => Err(PyValueError::new_err(format!(
                 "Unknown data sink {other:?}"
@@ -3913,13 +5286,15 @@ impl ColumnProperties {
     #[pyo3(signature = (
         dtype,
         trace = None,
-        append_only = false
+        append_only = false,
+        nullable = true
     ))]
     fn new(
         py: Python,
         dtype: Py<PyAny>,
         trace: Option<Py<Trace>>,
         append_only: bool,
+        nullable: bool,
     ) -> PyResult<Py<Self>> {
         let trace = trace
             .clone()
@@ -3927,11 +5302,32 @@ impl ColumnProperties {
         let inner = Arc::new(EngineColumnProperties {
             append_only,
             dtype: dtype.extract(py)?,
+            nullable,
             trace,
         });
         let res = Py::new(py, Self(inner))?;
         Ok(res)
     }
+
+    #[getter]
+    fn dtype(&self) -> Type {
+        self.0.dtype
+    }
+
+    #[getter]
+    fn trace(&self) -> EngineTrace {
+        self.0.trace.clone()
+    }
+
+    #[getter]
+    fn append_only(&self) -> bool {
+        self.0.append_only
+    }
+
+    #[getter]
+    fn nullable(&self) -> bool {
+        self.0.nullable
+    }
 }
 
 #[pyclass(module = "pathway.engine", frozen, subclass)]
@@ -3977,6 +5373,36 @@ impl TableProperties {
 
         TableProperties::new(py, Arc::new(table_properties))
     }
+
+    /// Returns the properties of the columns with their paths, in the order of the paths.
+    fn columns(&self, py: Python) -> Vec<(Py<PyTuple>, ColumnProperties)> {
+        fn collect(
+            properties: &EngineTableProperties,
+            path: &mut Vec<usize>,
+            result: &mut Vec<(Vec<usize>, ColumnProperties)>,
+        ) {
+            match properties {
+                EngineTableProperties::Table(inner) => {
+                    for (index, properties) in inner.iter().enumerate() {
+                        path.push(index);
+                        collect(properties, path, result);
+                        path.pop();
+                    }
+                }
+                EngineTableProperties::Column(column_properties) => {
+                    result.push((path.clone(), ColumnProperties(column_properties.clone())));
+                }
+                EngineTableProperties::Empty => {}
+            }
+        }
+
+        let mut result = Vec::new();
+        collect(&self.0, &mut Vec::new(), &mut result);
+        result
+            .into_iter()
+            .map(|(path, column_properties)| (PyTuple::new(py, path).into(), column_properties))
+            .collect()
+    }
 }
 
 #[pyclass(module = "pathway.engine", frozen)]
@@ -4171,6 +5597,8 @@ fn run_with_wakeup_receiver<R>(
 #[pyo3(name = "engine")]
 fn module(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     logging::init();
+    // the keys can't be derived if the hashing is misconfigured
+    KeyHashing::configured().map_err(PyValueError::new_err)?;
 
     m.add_class::<Pointer>()?;
     m.add_class::<PyReducer>()?;
@@ -4194,6 +5622,8 @@ fn module(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<Context>()?;
 
     m.add_class::<AwsS3Settings>()?;
+    m.add_class::<AzureBlobStorageSettings>()?;
+    m.add_class::<GcsSettings>()?;
     m.add_class::<ElasticSearchParams>()?;
     m.add_class::<ElasticSearchAuth>()?;
     m.add_class::<CsvParserSettings>()?;
@@ -4201,7 +5631,9 @@ fn module(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<DataStorage>()?;
     m.add_class::<DataFormat>()?;
     m.add_class::<PersistenceConfig>()?;
+    m.add_class::<ColumnMigration>()?;
     m.add_class::<PythonSubject>()?;
+    m.add_class::<PythonSink>()?;
     m.add_class::<PyPersistenceMode>()?;
     m.add_class::<PySnapshotAccess>()?;
     m.add_class::<PySnapshotEvent>()?;
@@ -4210,15 +5642,23 @@ fn module(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<ColumnProperties>()?;
     m.add_class::<TableProperties>()?;
     m.add_class::<Trace>()?;
+    m.add_class::<PyShutdownHandle>()?;
+    m.add_class::<PyAlertThresholds>()?;
 
     m.add_function(wrap_pyfunction!(run_with_new_graph, m)?)?;
     m.add_function(wrap_pyfunction!(ref_scalar, m)?)?;
     #[allow(clippy::unsafe_removed_from_name)] // false positive
     m.add_function(wrap_pyfunction!(unsafe_make_pointer, m)?)?;
+    m.add_function(wrap_pyfunction!(captured_table_to_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(restore_savepoint, m)?)?;
+    m.add_function(wrap_pyfunction!(export_persisted_rows, m)?)?;
+    m.add_function(wrap_pyfunction!(import_persisted_rows, m)?)?;
+    m.add_function(wrap_pyfunction!(refresh_engine_logging, m)?)?;
 
     m.add("MissingValueError", &*MISSING_VALUE_ERROR_TYPE)?;
     m.add("EngineError", &*ENGINE_ERROR_TYPE)?;
     m.add("EngineErrorWithTrace", &*ENGINE_ERROR_WITH_TRACE_TYPE)?;
+    m.add("KEY_BITS", KEY_BITS)?;
 
     Ok(())
 }
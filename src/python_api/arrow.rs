@@ -0,0 +1,300 @@
+// Copyright © 2024 Pathway
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, BinaryArray, BooleanArray, DurationNanosecondArray, Float64Array,
+    Int64Array, NullArray, StringArray, TimestampNanosecondArray,
+};
+use arrow::compute::{cast_with_options, CastOptions};
+use arrow::datatypes::{
+    DataType, DurationNanosecondType, Field, Float64Type, Int64Type, Schema, TimeUnit,
+    TimestampNanosecondType,
+};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use pyo3::exceptions::{PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::engine::time::DateTime as _;
+use crate::engine::{DataRow, DateTimeNaive, DateTimeUtc, Duration, Key, KeyImpl, Value};
+
+fn cast(array: &dyn Array, data_type: &DataType) -> PyResult<ArrayRef> {
+    // Failing on the values that don't fit instead of replacing them with nulls
+    let options = CastOptions {
+        safe: false,
+        ..CastOptions::default()
+    };
+    cast_with_options(array, data_type, &options).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> PyResult<&'a ArrayRef> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| PyKeyError::new_err(format!("no column {name:?} in the record batch")))
+}
+
+/// Converts an Arrow array to values, the same way as the Python objects of the same types.
+fn values_from_arrow(array: &dyn Array) -> PyResult<Vec<Value>> {
+    let values = match array.data_type() {
+        DataType::Null => vec![Value::None; array.len()],
+        DataType::Boolean => array
+            .as_boolean()
+            .iter()
+            .map(|value| value.map_or(Value::None, Value::Bool))
+            .collect(),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => cast(array, &DataType::Int64)?
+            .as_primitive::<Int64Type>()
+            .iter()
+            .map(|value| value.map_or(Value::None, Value::Int))
+            .collect(),
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => {
+            cast(array, &DataType::Float64)?
+                .as_primitive::<Float64Type>()
+                .iter()
+                .map(|value| value.map_or(Value::None, |value| Value::Float(value.into())))
+                .collect()
+        }
+        DataType::Utf8 | DataType::LargeUtf8 => cast(array, &DataType::LargeUtf8)?
+            .as_string::<i64>()
+            .iter()
+            .map(|value| value.map_or(Value::None, Value::from))
+            .collect(),
+        DataType::Binary | DataType::LargeBinary => cast(array, &DataType::LargeBinary)?
+            .as_binary::<i64>()
+            .iter()
+            .map(|value| value.map_or(Value::None, Value::from))
+            .collect(),
+        DataType::Timestamp(_unit, timezone) => {
+            let with_timezone = timezone.is_some();
+            cast(
+                array,
+                &DataType::Timestamp(TimeUnit::Nanosecond, timezone.clone()),
+            )?
+            .as_primitive::<TimestampNanosecondType>()
+            .iter()
+            .map(|value| match value {
+                None => Value::None,
+                Some(timestamp) if with_timezone => Value::DateTimeUtc(DateTimeUtc::new(timestamp)),
+                Some(timestamp) => Value::DateTimeNaive(DateTimeNaive::new(timestamp)),
+            })
+            .collect()
+        }
+        DataType::Duration(_unit) => cast(array, &DataType::Duration(TimeUnit::Nanosecond))?
+            .as_primitive::<DurationNanosecondType>()
+            .iter()
+            .map(|value| value.map_or(Value::None, |value| Value::Duration(Duration::new(value))))
+            .collect(),
+        data_type => {
+            return Err(PyTypeError::new_err(format!(
+                "unsupported Arrow type: {data_type}"
+            )))
+        }
+    };
+    Ok(values)
+}
+
+fn int_column(batch: &RecordBatch, name: Option<&str>) -> PyResult<Option<Vec<Option<i64>>>> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let array = cast(column(batch, name)?.as_ref(), &DataType::Int64)?;
+    Ok(Some(array.as_primitive::<Int64Type>().iter().collect()))
+}
+
+fn key_for_values(values: &[Value], unsafe_trusted_ids: bool) -> PyResult<Key> {
+    if !unsafe_trusted_ids {
+        return Ok(Key::for_values(values));
+    }
+    match values {
+        [Value::Int(id)] => KeyImpl::try_from(*id)
+            .map(Key)
+            .map_err(|_| PyValueError::new_err(format!("invalid trusted id: {id}"))),
+        _ => Err(PyValueError::new_err(
+            "trusted ids have to be single integers",
+        )),
+    }
+}
+
+/// The columns of a record batch that make up a static table.
+pub struct ArrowColumns<'a> {
+    pub values: &'a [String],
+    pub keys: &'a [String],
+    pub time: Option<&'a str>,
+    pub diff: Option<&'a str>,
+    pub shard: Option<&'a str>,
+}
+
+/// Converts the record batch to the rows of a static table, column by column.
+/// The keys are computed from the key columns, like `ref_scalar` does, unless the ids
+/// are trusted, in which case the single key column contains them.
+pub fn data_rows_from_arrow(
+    batch: &RecordBatch,
+    columns: &ArrowColumns,
+    unsafe_trusted_ids: bool,
+) -> PyResult<Vec<DataRow>> {
+    let to_values = |names: &[String]| -> PyResult<Vec<_>> {
+        names
+            .iter()
+            .map(|name| Ok(values_from_arrow(column(batch, name)?.as_ref())?.into_iter()))
+            .collect()
+    };
+    let mut key_columns = to_values(columns.keys)?;
+    let mut value_columns = to_values(columns.values)?;
+    let times = int_column(batch, columns.time)?;
+    let diffs = int_column(batch, columns.diff)?;
+    let shards = int_column(batch, columns.shard)?;
+
+    let mut rows = Vec::with_capacity(batch.num_rows());
+    for i in 0..batch.num_rows() {
+        let key_values: Vec<_> = key_columns
+            .iter_mut()
+            .map(|column| column.next().unwrap())
+            .collect();
+        let key = key_for_values(&key_values, unsafe_trusted_ids)?;
+        let values = value_columns
+            .iter_mut()
+            .map(|column| column.next().unwrap())
+            .collect();
+        let time = match times.as_ref().and_then(|times| times[i]) {
+            Some(time) => u64::try_from(time)
+                .map_err(|_| PyValueError::new_err(format!("invalid time: {time}")))?,
+            None => 0,
+        };
+        let diff = match diffs.as_ref().and_then(|diffs| diffs[i]) {
+            Some(1) | None => 1,
+            Some(-1) => -1,
+            Some(_) => {
+                return Err(PyValueError::new_err(
+                    "the diff column can only contain 1 and -1",
+                ))
+            }
+        };
+        let shard = shards
+            .as_ref()
+            .and_then(|shards| shards[i])
+            .map(|shard| {
+                usize::try_from(shard)
+                    .map_err(|_| PyValueError::new_err(format!("invalid shard: {shard}")))
+            })
+            .transpose()?;
+        rows.push(DataRow {
+            key,
+            values,
+            time,
+            diff,
+            shard,
+        });
+    }
+    Ok(rows)
+}
+
+fn array_from_values(name: &str, values: &[&Value]) -> PyResult<ArrayRef> {
+    let mismatch = || {
+        PyTypeError::new_err(format!(
+            "column {name:?} contains values of different types"
+        ))
+    };
+    macro_rules! collect_array {
+        ($array_type:ty, $pattern:pat => $value:expr) => {
+            Arc::new(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        Value::None => Ok(None),
+                        $pattern => Ok(Some($value)),
+                        _ => Err(mismatch()),
+                    })
+                    .collect::<PyResult<$array_type>>()?,
+            )
+        };
+    }
+
+    let Some(first) = values.iter().find(|value| !matches!(value, Value::None)) else {
+        return Ok(Arc::new(NullArray::new(values.len())));
+    };
+    let array: ArrayRef = match first {
+        Value::Bool(_) => collect_array!(BooleanArray, Value::Bool(b) => *b),
+        Value::Int(_) => collect_array!(Int64Array, Value::Int(i) => *i),
+        Value::Float(_) => collect_array!(Float64Array, Value::Float(f) => f.into_inner()),
+        Value::String(_) => collect_array!(StringArray, Value::String(s) => s.as_str()),
+        Value::Bytes(_) => collect_array!(BinaryArray, Value::Bytes(b) => &**b),
+        Value::Pointer(_) => collect_array!(StringArray, Value::Pointer(p) => p.to_string()),
+        Value::Json(_) => collect_array!(StringArray, Value::Json(j) => j.to_string()),
+        Value::DateTimeNaive(_) => {
+            collect_array!(TimestampNanosecondArray, Value::DateTimeNaive(dt) => dt.timestamp())
+        }
+        Value::DateTimeUtc(_) => Arc::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::None => Ok(None),
+                    Value::DateTimeUtc(dt) => Ok(Some(dt.timestamp())),
+                    _ => Err(mismatch()),
+                })
+                .collect::<PyResult<TimestampNanosecondArray>>()?
+                .with_timezone("UTC"),
+        ),
+        Value::Duration(_) => {
+            collect_array!(DurationNanosecondArray, Value::Duration(d) => d.nanoseconds())
+        }
+        value => {
+            return Err(PyTypeError::new_err(format!(
+                "column {name:?} contains values not supported by Arrow: {value}"
+            )))
+        }
+    };
+    Ok(array)
+}
+
+/// Converts the squashed updates of a table to a record batch, column by column.
+/// The pointers, including the ids, are converted to strings and the JSON values
+/// to their text.
+pub fn data_rows_to_arrow(
+    rows: &[&DataRow],
+    column_names: &[String],
+    include_id: bool,
+) -> PyResult<RecordBatch> {
+    let mut sorted_rows = rows.to_vec();
+    sorted_rows.sort_by_key(|row| (row.time, row.diff));
+    let mut table: HashMap<Key, &[Value]> = HashMap::new();
+    for row in sorted_rows {
+        if row.diff > 0 {
+            table.insert(row.key, &row.values);
+        } else {
+            table.remove(&row.key);
+        }
+    }
+    let mut table: Vec<_> = table.into_iter().collect();
+    table.sort_by_key(|(key, _values)| *key);
+
+    let mut fields = Vec::new();
+    let mut arrays = Vec::new();
+    if include_id {
+        let ids: StringArray = table
+            .iter()
+            .map(|(key, _values)| Some(key.to_string()))
+            .collect();
+        fields.push(Field::new("id", DataType::Utf8, false));
+        arrays.push(Arc::new(ids) as ArrayRef);
+    }
+    for (index, name) in column_names.iter().enumerate() {
+        let values: Vec<_> = table
+            .iter()
+            .map(|(_key, values)| values.get(index).unwrap_or(&Value::None))
+            .collect();
+        let array = array_from_values(name, &values)?;
+        fields.push(Field::new(name, array.data_type().clone(), true));
+        arrays.push(array);
+    }
+    let options = RecordBatchOptions::new().with_row_count(Some(table.len()));
+    RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
@@ -6,56 +6,108 @@ use std::thread;
 
 use crossbeam_channel as channel;
 use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
-use pyo3::Python;
+use once_cell::sync::OnceCell;
+use pyo3::types::{IntoPyDict, PyDict, PyTuple};
+use pyo3::{PyResult, Python};
 use pyo3_log::{Logger as PyLogger, ResetHandle};
 
 use super::threads::PythonThreadState;
-
-struct OwnedMetadata {
-    level: Level,
-    target: String,
-}
-
-impl OwnedMetadata {
-    fn as_metadata(&self) -> Metadata {
-        Metadata::builder()
-            .level(self.level)
-            .target(&self.target)
-            .build()
-    }
-}
-
-impl From<&Metadata<'_>> for OwnedMetadata {
-    fn from(metadata: &Metadata<'_>) -> Self {
-        Self {
-            level: metadata.level(),
-            target: metadata.target().to_owned(),
-        }
-    }
+use crate::engine::log_context::{self, LogContext};
+
+/// The Python logger the logs of the engine are passed to, with a child per subsystem,
+/// e.g. `pathway.engine.connectors`, `pathway.engine.persistence` or
+/// `pathway.engine.dataflow`.
+const ENGINE_LOGGER: &str = "pathway.engine";
+
+/// The crates whose logs belong to the dataflow subsystem.
+const DATAFLOW_CRATES: [&str; 2] = ["timely", "differential_dataflow"];
+
+static RESET_HANDLE: OnceCell<ResetHandle> = OnceCell::new();
+
+/// Returns the name of the Python logger of the records of the target. The modules of
+/// the engine are put under [`ENGINE_LOGGER`], so that e.g. `pathway_engine::connectors::kafka`
+/// is logged by `pathway.engine.connectors.kafka`, and the logs of timely and differential
+/// under its `dataflow` subsystem. The other targets are only converted to the Python
+/// naming, as `pyo3_log` does.
+fn python_logger_name(target: &str) -> String {
+    let name = if let Some(path) = target
+        .strip_prefix("pathway_engine::engine::")
+        .or_else(|| target.strip_prefix("pathway_engine::"))
+    {
+        format!("{ENGINE_LOGGER}::{path}")
+    } else if target == "pathway_engine" {
+        ENGINE_LOGGER.to_owned()
+    } else if DATAFLOW_CRATES.iter().any(|name| {
+        target
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    }) {
+        format!("{ENGINE_LOGGER}::dataflow::{target}")
+    } else {
+        target.to_owned()
+    };
+    name.replace("::", ".")
 }
 
 struct OwnedRecord {
     msg: String,
-    metadata: OwnedMetadata,
-    module_path: Option<Cow<'static, str>>,
+    level: Level,
+    logger_name: String,
     file: Option<Cow<'static, str>>,
     line: Option<u32>,
+    context: LogContext,
 }
 
 impl OwnedRecord {
-    fn with<R>(&self, logic: impl FnOnce(Record) -> R) -> R {
-        let mut builder = Record::builder();
-        builder.metadata(self.metadata.as_metadata());
-        match &self.module_path {
-            Some(Cow::Borrowed(s)) => builder.module_path_static(Some(s)),
-            other => builder.module_path(other.as_deref()),
-        };
-        match &self.file {
-            Some(Cow::Borrowed(s)) => builder.file_static(Some(s)),
-            other => builder.file(other.as_deref()),
-        };
-        builder.line(self.line);
-        logic(builder.args(format_args!("{}", self.msg)).build())
+    /// Passes the record to the Python logger of its target.
+    /// The log context is attached to the Python record as its `worker_id`, `epoch`,
+    /// `connector` and `operator_id` attributes, if they are known.
+    fn log_to_python(&self, py: Python) -> PyResult<()> {
+        let logger = py
+            .import("logging")?
+            .call_method1("getLogger", (self.logger_name.as_str(),))?;
+        let level = python_level(self.level);
+        if !logger.call_method1("isEnabledFor", (level,))?.is_true()? {
+            return Ok(());
+        }
+        let extra = PyDict::new(py);
+        if let Some(worker_id) = self.context.worker_id {
+            extra.set_item("worker_id", worker_id)?;
+        }
+        if let Some(epoch) = self.context.epoch {
+            extra.set_item("epoch", epoch)?;
+        }
+        if let Some(connector) = &self.context.connector {
+            extra.set_item("connector", connector.as_ref())?;
+        }
+        if let Some(operator_id) = self.context.operator_id {
+            extra.set_item("operator_id", operator_id)?;
+        }
+        let record = logger.call_method(
+            "makeRecord",
+            (
+                self.logger_name.as_str(),
+                level,
+                self.file.as_deref().unwrap_or("<none>"),
+                self.line.unwrap_or_default(),
+                self.msg.as_str(),
+                PyTuple::empty(py),
+                py.None(),
+            ),
+            Some([("extra", extra)].into_py_dict(py)),
+        )?;
+        logger.call_method1("handle", (record,))?;
+        Ok(())
+    }
+}
+
+fn python_level(level: Level) -> u8 {
+    match level {
+        Level::Error => 40,
+        Level::Warn => 30,
+        Level::Info => 20,
+        Level::Debug => 10,
+        Level::Trace => 5,
     }
 }
 
@@ -76,13 +128,12 @@ impl From<&Record<'_>> for OwnedRecord {
         };
         Self {
             msg: record.args().to_string(),
-            metadata: record.metadata().into(),
-            module_path: record
-                .module_path_static()
-                .map(Cow::Borrowed)
-                .or_else(|| record.module_path().map(|s| Cow::Owned(s.to_owned()))),
-            file: file.or(Some(Cow::Borrowed("<none>"))),
+            level: record.level(),
+            logger_name: python_logger_name(record.target()),
+            file,
             line,
+            // Taken in the thread making the record, not in the one passing it to Python
+            context: log_context::current(),
         }
     }
 }
@@ -108,7 +159,11 @@ impl Logger {
                     let thread_state = PythonThreadState::new();
                     loop {
                         match receiver.recv() {
-                            Ok(Message::Record(record)) => record.with(|record| inner.log(&record)),
+                            Ok(Message::Record(record)) => Python::with_gil(|py| {
+                                if let Err(e) = record.log_to_python(py) {
+                                    e.print(py);
+                                }
+                            }),
                             Ok(Message::Flush(sender)) => {
                                 inner.flush();
                                 sender.send(()).unwrap_or(());
@@ -126,25 +181,34 @@ impl Logger {
     pub fn install(self) -> Result<ResetHandle, SetLoggerError> {
         let reset_handle = self.inner.reset_handle();
         log::set_boxed_logger(Box::new(self))?;
-        log::set_max_level(LevelFilter::Debug); // XXX: `pyo3_log::Logger` does not allow us to get the level
+        // XXX: `pyo3_log::Logger` does not allow us to get the level, the trace records
+        // are let through only once they are enabled with `refresh`
+        log::set_max_level(LevelFilter::Debug);
         Ok(reset_handle)
     }
 }
 
 impl Default for Logger {
     fn default() -> Self {
-        Self::new(Arc::new(PyLogger::default()))
+        Self::new(Arc::new(PyLogger::default().filter(LevelFilter::Trace)))
     }
 }
 
 impl Log for Logger {
-    fn enabled(&self, metadata: &log::Metadata) -> bool {
-        // `pyo3_log::Logger` does not take the GIL when running `enabled`
-        self.inner.enabled(metadata)
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // `pyo3_log::Logger` does not take the GIL when running `enabled`, apart from
+        // looking up the levels of the loggers not cached yet
+        let logger_name = python_logger_name(metadata.target());
+        self.inner.enabled(
+            &Metadata::builder()
+                .level(metadata.level())
+                .target(&logger_name)
+                .build(),
+        )
     }
 
     fn log(&self, record: &log::Record) {
-        if !self.inner.enabled(record.metadata()) {
+        if !self.enabled(record.metadata()) {
             return;
         }
         self.sender
@@ -165,7 +229,21 @@ impl Log for Logger {
 }
 
 pub fn init() {
-    Logger::default()
+    let reset_handle = Logger::default()
         .install()
         .expect("initializing the logger should not fail");
+    RESET_HANDLE.set(reset_handle).unwrap_or(());
+}
+
+/// Makes the engine use the current levels of the Python loggers, as they are cached
+/// once looked up. The trace records are passed to Python only if `trace` is set.
+pub fn refresh(trace: bool) {
+    log::set_max_level(if trace {
+        LevelFilter::Trace
+    } else {
+        LevelFilter::Debug
+    });
+    if let Some(reset_handle) = RESET_HANDLE.get() {
+        reset_handle.reset();
+    }
 }
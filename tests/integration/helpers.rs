@@ -11,7 +11,7 @@ use pathway_engine::persistence::config::{
     MetadataStorageConfig, PersistenceManagerOuterConfig, StreamStorageConfig,
 };
 use pathway_engine::persistence::metadata_backends::FilesystemKVStorage;
-use pathway_engine::persistence::state::MetadataAccessor;
+use pathway_engine::persistence::state::{MetadataAccessor, RetentionPolicy};
 use pathway_engine::persistence::tracker::SingleWorkerPersistentStorage;
 
 use pathway_engine::connectors::data_format::{ParsedEvent, Parser};
@@ -23,7 +23,7 @@ use pathway_engine::connectors::{Connector, Entry, PersistenceMode, SnapshotAcce
 use pathway_engine::engine::Key;
 use pathway_engine::persistence::frontier::OffsetAntichain;
 use pathway_engine::persistence::sync::{
-    SharedWorkersPersistenceCoordinator, WorkersPersistenceCoordinator,
+    SharedCheckpointHooks, SharedSavepointRequests, SharedWorkersPersistenceCoordinator,
 };
 
 #[derive(Debug)]
@@ -71,16 +71,24 @@ pub fn full_cycle_read(
     .unwrap();
 
     let mut reader = reader.build().expect("building the reader failed");
-    Connector::<u64>::read_snapshot(
+    let deduplicator = Connector::<u64>::read_snapshot(
         &mut *reader,
         persistent_storage,
         &sender,
         PersistenceMode::Batch,
         SnapshotAccess::Full,
-    );
+        None,
+    )
+    .expect("reading the snapshot failed");
 
     let reporter = PanicErrorReporter::default();
-    Connector::<u64>::read_realtime_updates(&mut *reader, &sender, &main_thread, &reporter);
+    Connector::<u64>::read_realtime_updates(
+        &mut *reader,
+        &sender,
+        &main_thread,
+        &reporter,
+        deduplicator,
+    );
     let result = get_entries_in_receiver(receiver);
 
     let has_persistent_storage = persistent_storage.is_some();
@@ -202,29 +210,95 @@ pub fn create_persistence_manager(
 ) -> (
     Arc<Mutex<SingleWorkerPersistentStorage>>,
     SharedWorkersPersistenceCoordinator,
+) {
+    create_persistence_manager_with_intervals(fs_path, recreate, Duration::ZERO, None)
+}
+
+pub fn create_persistence_manager_with_intervals(
+    fs_path: &Path,
+    recreate: bool,
+    snapshot_interval: Duration,
+    snapshot_interval_rows: Option<u64>,
+) -> (
+    Arc<Mutex<SingleWorkerPersistentStorage>>,
+    SharedWorkersPersistenceCoordinator,
+) {
+    create_persistence_manager_with_config(
+        fs_path,
+        recreate,
+        snapshot_interval,
+        snapshot_interval_rows,
+        RetentionPolicy::default(),
+        false,
+    )
+}
+
+pub fn create_persistence_manager_with_retention(
+    fs_path: &Path,
+    recreate: bool,
+    retention_policy: RetentionPolicy,
+) -> (
+    Arc<Mutex<SingleWorkerPersistentStorage>>,
+    SharedWorkersPersistenceCoordinator,
+) {
+    create_persistence_manager_with_config(
+        fs_path,
+        recreate,
+        Duration::ZERO,
+        None,
+        retention_policy,
+        false,
+    )
+}
+
+pub fn create_persistence_manager_with_background_checkpoints(
+    fs_path: &Path,
+    recreate: bool,
+) -> (
+    Arc<Mutex<SingleWorkerPersistentStorage>>,
+    SharedWorkersPersistenceCoordinator,
+) {
+    create_persistence_manager_with_config(
+        fs_path,
+        recreate,
+        Duration::ZERO,
+        None,
+        RetentionPolicy::default(),
+        true,
+    )
+}
+
+fn create_persistence_manager_with_config(
+    fs_path: &Path,
+    recreate: bool,
+    snapshot_interval: Duration,
+    snapshot_interval_rows: Option<u64>,
+    retention_policy: RetentionPolicy,
+    background_checkpoints: bool,
+) -> (
+    Arc<Mutex<SingleWorkerPersistentStorage>>,
+    SharedWorkersPersistenceCoordinator,
 ) {
     if recreate {
         let _ = std::fs::remove_dir_all(fs_path);
     }
 
-    let global_tracker = Arc::new(Mutex::new(WorkersPersistenceCoordinator::new(
-        Duration::ZERO,
-        1,
-    )));
+    let config = create_persistence_config_with_options(
+        fs_path,
+        snapshot_interval,
+        snapshot_interval_rows,
+        retention_policy,
+        background_checkpoints,
+    );
+    let global_tracker = Arc::new(Mutex::new(
+        config
+            .create_workers_persistence_coordinator(0..1, 1)
+            .expect("Failed to create the persistence coordinator"),
+    ));
 
     let tracker = Arc::new(Mutex::new(
-        SingleWorkerPersistentStorage::new(
-            PersistenceManagerOuterConfig::new(
-                Duration::ZERO,
-                MetadataStorageConfig::Filesystem(fs_path.to_path_buf()),
-                StreamStorageConfig::Filesystem(fs_path.to_path_buf()),
-                SnapshotAccess::Full,
-                PersistenceMode::Batch,
-                true,
-            )
-            .into_inner(0, 1),
-        )
-        .expect("Failed to create persistence manager"),
+        SingleWorkerPersistentStorage::new(config.into_inner(0, 1))
+            .expect("Failed to create persistence manager"),
     ));
     global_tracker
         .lock()
@@ -234,13 +308,51 @@ pub fn create_persistence_manager(
     (tracker, global_tracker)
 }
 
+pub fn create_persistence_config(fs_path: &Path) -> PersistenceManagerOuterConfig {
+    create_persistence_config_with_options(
+        fs_path,
+        Duration::ZERO,
+        None,
+        RetentionPolicy::default(),
+        false,
+    )
+}
+
+fn create_persistence_config_with_options(
+    fs_path: &Path,
+    snapshot_interval: Duration,
+    snapshot_interval_rows: Option<u64>,
+    retention_policy: RetentionPolicy,
+    background_checkpoints: bool,
+) -> PersistenceManagerOuterConfig {
+    PersistenceManagerOuterConfig::new(
+        snapshot_interval,
+        snapshot_interval_rows,
+        None,
+        MetadataStorageConfig::Filesystem(fs_path.to_path_buf()),
+        StreamStorageConfig::Filesystem(fs_path.to_path_buf()),
+        SnapshotAccess::Full,
+        PersistenceMode::Batch,
+        true,
+        None,
+        None,
+        None,
+        false,
+        SharedSavepointRequests::default(),
+        SharedCheckpointHooks::default(),
+        HashMap::new(),
+        retention_policy,
+        background_checkpoints,
+    )
+}
+
 pub fn create_metadata_storage(fs_path: &Path, recreate: bool) -> MetadataAccessor {
     if recreate {
         let _ = std::fs::remove_dir_all(fs_path);
     }
 
     let backend = Box::new(FilesystemKVStorage::new(fs_path).expect("Backend creation failed"));
-    MetadataAccessor::new(backend, 0).expect("Storage creation failed")
+    MetadataAccessor::new(backend, 0, RetentionPolicy::default()).expect("Storage creation failed")
 }
 
 pub fn get_entries_in_receiver<T>(receiver: Receiver<T>) -> Vec<T> {
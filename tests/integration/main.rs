@@ -3,27 +3,47 @@
 mod helpers;
 mod operator_test_utils;
 
+mod test_adaptive_batching;
+mod test_async_map;
 mod test_bytes;
+mod test_columnar;
 mod test_connector_field_defaults;
+mod test_cumulative;
 mod test_dd_distinct_total;
 mod test_debezium;
 mod test_dsv;
 mod test_dsv_dir;
 mod test_dsv_output;
 mod test_file_kv;
+mod test_http_auth;
+mod test_interning;
+mod test_iteration;
 mod test_json_output;
 mod test_jsonlines;
+mod test_key_hashing;
+mod test_latency_histogram;
+mod test_mat_mul;
 mod test_metadata;
 mod test_null_writer;
+mod test_object_storage;
 mod test_offsets_storage;
+mod test_output_barrier;
 mod test_parser_errors;
 mod test_prev_next;
 mod test_psql_output;
 mod test_psql_snapshot;
+mod test_rank;
 mod test_seek;
+mod test_session_window;
+mod test_sparse_matrix;
 mod test_sqlite;
 mod test_stream_snapshot;
 mod test_time;
 mod test_time_column;
+mod test_top_n;
+mod test_transactional_sink;
+mod test_type_check;
 mod test_upsert_session;
+mod test_value_encoding;
+mod test_value_order;
 mod test_value_to_sql;
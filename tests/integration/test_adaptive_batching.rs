@@ -0,0 +1,72 @@
+// Copyright © 2024 Pathway
+
+use std::time::Duration;
+
+use pathway_engine::engine::dataflow::operators::batching::AdaptiveBatchSize;
+
+#[test]
+fn test_grows_when_fast_and_full() {
+    let mut batching = AdaptiveBatchSize::new(Duration::from_millis(100));
+    let initial = batching.batch_size();
+
+    batching.record(initial, Duration::from_millis(1));
+    assert_eq!(batching.batch_size(), initial * 2);
+
+    batching.record(initial * 2, Duration::from_millis(1));
+    assert_eq!(batching.batch_size(), initial * 4);
+}
+
+#[test]
+fn test_does_not_grow_when_not_full() {
+    let mut batching = AdaptiveBatchSize::new(Duration::from_millis(100));
+    let initial = batching.batch_size();
+
+    batching.record(initial / 2, Duration::from_millis(1));
+    assert_eq!(batching.batch_size(), initial);
+}
+
+#[test]
+fn test_shrinks_when_slow() {
+    let mut batching = AdaptiveBatchSize::new(Duration::from_millis(10));
+    let initial = batching.batch_size();
+
+    batching.record(initial, Duration::from_millis(20));
+    assert_eq!(batching.batch_size(), initial / 2);
+
+    for _ in 0..100 {
+        batching.record(batching.batch_size(), Duration::from_millis(20));
+    }
+    assert_eq!(batching.batch_size(), 1);
+}
+
+#[test]
+fn test_stable_between_half_and_full_target() {
+    let mut batching = AdaptiveBatchSize::new(Duration::from_millis(10));
+    let initial = batching.batch_size();
+
+    for _ in 0..10 {
+        batching.record(initial, Duration::from_millis(7));
+    }
+    assert_eq!(batching.batch_size(), initial);
+}
+
+#[test]
+fn test_latency_p99() {
+    let mut batching = AdaptiveBatchSize::new(Duration::from_secs(1));
+    assert_eq!(batching.latency_p99(), None);
+
+    for i in 1..=100 {
+        batching.record(1, Duration::from_millis(i));
+    }
+    assert_eq!(batching.latency_p99(), Some(Duration::from_millis(99)));
+
+    batching.record(1, Duration::from_millis(200));
+    assert_eq!(batching.latency_p99(), Some(Duration::from_millis(100)));
+}
+
+#[test]
+fn test_ignores_empty_batches() {
+    let mut batching = AdaptiveBatchSize::new(Duration::from_millis(10));
+    batching.record(0, Duration::from_secs(1));
+    assert_eq!(batching.latency_p99(), None);
+}
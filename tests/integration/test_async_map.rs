@@ -0,0 +1,94 @@
+// Copyright © 2024 Pathway
+
+#![allow(clippy::disallowed_methods)]
+
+use super::operator_test_utils::run_test;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use differential_dataflow::operators::arrange::ArrangeByKey;
+
+use pathway_engine::engine::dataflow::operators::MapWrapped;
+
+/// Completes on the second poll, so that other futures are started in the meantime.
+struct YieldOnce<T> {
+    value: Option<T>,
+    yielded: bool,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl<T: Unpin> Future for YieldOnce<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        if self.yielded {
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Poll::Ready(self.value.take().unwrap())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn run_doubling(max_in_flight: Option<usize>) -> usize {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_seen = Arc::new(AtomicUsize::new(0));
+    let input = vec![
+        vec![
+            ((0, 1), 0, 1),
+            ((0, 2), 0, 1),
+            ((1, 3), 0, 1),
+            ((1, 4), 0, 1),
+            ((2, 5), 0, 1),
+        ],
+        vec![((2, 6), 1, 1), ((0, 1), 1, -1)],
+    ];
+    let expected = vec![
+        vec![
+            ((0, 2), 0, 1),
+            ((0, 4), 0, 1),
+            ((1, 6), 0, 1),
+            ((1, 8), 0, 1),
+            ((2, 10), 0, 1),
+        ],
+        vec![((2, 12), 1, 1), ((0, 2), 1, -1)],
+    ];
+    run_test(input, expected, {
+        let max_seen = max_seen.clone();
+        move |coll| {
+            let in_flight = in_flight.clone();
+            let max_seen = max_seen.clone();
+            coll.map_named_async(
+                "Doubling",
+                max_in_flight,
+                move |(key, value): (u32, u32)| {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_seen.fetch_max(current, Ordering::SeqCst);
+                    YieldOnce {
+                        value: Some((key, value * 2)),
+                        yielded: false,
+                        in_flight: in_flight.clone(),
+                    }
+                },
+            )
+            .arrange_by_key()
+        }
+    });
+    max_seen.load(Ordering::SeqCst)
+}
+
+#[test]
+fn test_unbounded() {
+    assert_eq!(run_doubling(None), 5);
+}
+
+#[test]
+fn test_max_in_flight() {
+    assert_eq!(run_doubling(Some(2)), 2);
+}
@@ -1,13 +1,14 @@
 // Copyright © 2024 Pathway
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use pathway_engine::connectors::data_format::{IdentityParser, ParseResult, ParsedEvent, Parser};
 use pathway_engine::connectors::data_storage::{
-    ConnectorMode, FilesystemReader, ReadMethod, ReadResult, Reader,
+    ConnectorMode, DataEventType, FilesystemReader, ReadMethod, ReadResult, Reader, ReaderContext,
 };
 use pathway_engine::connectors::SessionType;
-use pathway_engine::engine::Value;
+use pathway_engine::engine::{Key, SharedBytes, Value};
 
 fn read_bytes_from_path(path: &str) -> eyre::Result<Vec<ParsedEvent>> {
     let mut reader = FilesystemReader::new(
@@ -99,3 +100,43 @@ fn test_bytes_read_from_folder() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_values_share_buffer() -> eyre::Result<()> {
+    let buffer = Arc::new(b"first|second".to_vec());
+    let bytes = SharedBytes::from(buffer.clone());
+    let mut parser = IdentityParser::new(vec!["data".to_string()], false, SessionType::Native);
+
+    let mut values = Vec::new();
+    for range in [0..5, 6..12] {
+        let context = ReaderContext::from_raw_bytes(DataEventType::Insert, bytes.slice(range));
+        for event in parser.parse(&context)? {
+            let ParsedEvent::Insert((None, row)) = event else {
+                panic!("unexpected event {event:?}");
+            };
+            values.extend(row);
+        }
+    }
+    drop(bytes);
+
+    assert_eq!(
+        values,
+        [Value::from(&b"first"[..]), Value::from(&b"second"[..])]
+    );
+    assert_eq!(
+        Key::for_value(&values[1]),
+        Key::for_value(&Value::from(&b"second"[..]))
+    );
+    assert_eq!(Arc::strong_count(&buffer), 3);
+    drop(values);
+    assert_eq!(Arc::strong_count(&buffer), 1);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_slice_out_of_bounds() {
+    let bytes = SharedBytes::from(b"abc".to_vec());
+    let _ = bytes.slice(1..4);
+}
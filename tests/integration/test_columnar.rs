@@ -0,0 +1,176 @@
+// Copyright © 2024 Pathway
+
+use std::sync::Arc;
+
+use pathway_engine::engine::expression::columnar::{ColumnarBatch, RowBuffer};
+use pathway_engine::engine::{
+    AnyExpression, BoolExpression, Expression, FloatExpression, IntExpression, Value,
+};
+
+fn argument(index: usize) -> Arc<Expression> {
+    Arc::new(Expression::Any(AnyExpression::Argument(index)))
+}
+
+fn assert_same_as_row_by_row(expression: &Expression, rows: &[&[Value]]) {
+    let batch = ColumnarBatch::new(rows);
+    let results = expression.eval_batch(&batch);
+    assert_eq!(results.len(), rows.len());
+    for (result, row) in results.into_iter().zip(rows) {
+        match (result, expression.eval(row)) {
+            (Ok(batched), Ok(expected)) => assert_eq!(batched, expected),
+            (Err(_), Err(_)) => {}
+            (batched, expected) => panic!("got {batched:?} instead of {expected:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_int_arithmetic() {
+    let expression = Expression::Int(IntExpression::Mul(
+        Arc::new(Expression::Int(IntExpression::Add(
+            argument(0),
+            argument(1),
+        ))),
+        Arc::new(Expression::Int(IntExpression::Const(-2))),
+    ));
+    assert!(expression.is_columnar());
+    let rows: &[&[Value]] = &[
+        &[Value::Int(1), Value::Int(2)],
+        &[Value::Int(3), Value::Int(-4)],
+    ];
+    let batch = ColumnarBatch::new(rows);
+    assert_eq!(
+        expression.eval_columnar(&batch),
+        Some(vec![Value::Int(-6), Value::Int(2)])
+    );
+    assert_same_as_row_by_row(&expression, rows);
+}
+
+#[test]
+fn test_float_comparisons() {
+    let expression = Expression::Bool(BoolExpression::And(
+        Arc::new(Expression::Bool(BoolExpression::FloatLt(
+            Arc::new(Expression::Float(FloatExpression::CastFromInt(argument(0)))),
+            argument(1),
+        ))),
+        argument(2),
+    ));
+    assert!(expression.is_columnar());
+    let rows: &[&[Value]] = &[
+        &[Value::Int(1), Value::Float(1.5.into()), Value::Bool(true)],
+        &[Value::Int(2), Value::Float(1.5.into()), Value::Bool(true)],
+        &[Value::Int(1), Value::Float(1.5.into()), Value::Bool(false)],
+    ];
+    let batch = ColumnarBatch::new(rows);
+    assert_eq!(
+        expression.eval_columnar(&batch),
+        Some(vec![
+            Value::Bool(true),
+            Value::Bool(false),
+            Value::Bool(false)
+        ])
+    );
+    assert_same_as_row_by_row(&expression, rows);
+}
+
+#[test]
+fn test_falls_back_on_division_by_zero() {
+    let expression = Expression::Int(IntExpression::FloorDiv(argument(0), argument(1)));
+    let rows: &[&[Value]] = &[
+        &[Value::Int(7), Value::Int(2)],
+        &[Value::Int(7), Value::Int(0)],
+    ];
+    let batch = ColumnarBatch::new(rows);
+    assert_eq!(expression.eval_columnar(&batch), None);
+    let results = expression.eval_batch(&batch);
+    assert_eq!(results[0].as_ref().unwrap(), &Value::Int(3));
+    assert!(results[1].is_err());
+}
+
+#[test]
+fn test_falls_back_on_mixed_types() {
+    let expression = Expression::Float(FloatExpression::Add(argument(0), argument(0)));
+    let rows: &[&[Value]] = &[
+        &[Value::Float(1.0.into())],
+        &[Value::None],
+        &[Value::Int(1)],
+    ];
+    let batch = ColumnarBatch::new(rows);
+    assert_eq!(expression.eval_columnar(&batch), None);
+    assert_same_as_row_by_row(&expression, rows);
+}
+
+#[test]
+fn test_not_columnar() {
+    let expression = Expression::Any(AnyExpression::IfElse(argument(0), argument(1), argument(2)));
+    assert!(!expression.is_columnar());
+    let rows: &[&[Value]] = &[
+        &[Value::Bool(true), Value::from("a"), Value::from("b")],
+        &[Value::Bool(false), Value::from("a"), Value::from("b")],
+    ];
+    assert_same_as_row_by_row(&expression, rows);
+}
+
+#[test]
+fn test_long_batch() {
+    #[allow(clippy::cast_precision_loss)]
+    let values: Vec<[Value; 4]> = (0..1027_i64)
+        .map(|i| {
+            let x = if i % 100 == 0 {
+                f64::NAN
+            } else {
+                i as f64 * 0.37
+            };
+            [
+                Value::Int(i % 7),
+                Value::Int(i % 5),
+                Value::Float(x.into()),
+                Value::Float(((i % 11) as f64).into()),
+            ]
+        })
+        .collect();
+    let rows: Vec<&[Value]> = values.iter().map(|row| row.as_slice()).collect();
+    let float_sum = Arc::new(Expression::Float(FloatExpression::Add(
+        argument(2),
+        argument(3),
+    )));
+    let expressions = [
+        Expression::Bool(BoolExpression::IntLe(argument(0), argument(1))),
+        Expression::Bool(BoolExpression::IntNe(argument(0), argument(1))),
+        Expression::Bool(BoolExpression::FloatGe(float_sum.clone(), argument(3))),
+        Expression::Bool(BoolExpression::FloatNe(argument(2), argument(3))),
+        Expression::Float(FloatExpression::Mul(float_sum, argument(2))),
+    ];
+    for expression in &expressions {
+        assert!(expression.is_columnar());
+        assert!(expression
+            .eval_columnar(&ColumnarBatch::new(&rows))
+            .is_some());
+        assert_same_as_row_by_row(expression, &rows);
+    }
+}
+
+#[test]
+fn test_row_buffer() {
+    let mut row_buffer = RowBuffer::default();
+    row_buffer.push_row([Value::Int(1), Value::Int(2)]);
+    row_buffer.push_row([]);
+    row_buffer.push_row([Value::from("a")]);
+    assert_eq!(
+        row_buffer.rows(),
+        [
+            &[Value::Int(1), Value::Int(2)][..],
+            &[][..],
+            &[Value::from("a")][..]
+        ]
+    );
+
+    row_buffer.clear();
+    assert!(row_buffer.rows().is_empty());
+    row_buffer.push_row([Value::Int(3)]);
+    let expression = Expression::Int(IntExpression::Neg(argument(0)));
+    assert_eq!(
+        expression.eval_columnar(&ColumnarBatch::new(&row_buffer.rows())),
+        Some(vec![Value::Int(-3)])
+    );
+}
@@ -0,0 +1,65 @@
+// Copyright © 2024 Pathway
+
+#![allow(clippy::disallowed_methods)]
+
+use super::operator_test_utils::run_test;
+
+use differential_dataflow::operators::arrange::ArrangeByKey;
+
+use pathway_engine::engine::dataflow::operators::cumulative::Cumulative;
+
+#[test]
+fn test_running_sum() {
+    let input = vec![
+        vec![
+            ((0, ((1, 1), 10)), 0, 1),
+            ((0, ((3, 2), 30)), 0, 1),
+            ((1, ((2, 3), 5)), 0, 1),
+        ],
+        vec![((0, ((2, 4), 20)), 1, 1)],
+    ];
+    let expected = vec![
+        vec![
+            ((0, (1, 10)), 0, 1),
+            ((0, (2, 40)), 0, 1),
+            ((1, (3, 5)), 0, 1),
+        ],
+        vec![
+            ((0, (2, 40)), 1, -1),
+            ((0, (4, 30)), 1, 1),
+            ((0, (2, 60)), 1, 1),
+        ],
+    ];
+    run_test(input, expected, |coll| {
+        coll.cumulative(0, |sum, value| sum + value)
+            .arrange_by_key()
+    });
+}
+
+#[test]
+fn test_running_max_with_retraction() {
+    let input = vec![
+        vec![
+            ((0, ((1, 1), 7)), 0, 1),
+            ((0, ((2, 2), 3)), 0, 1),
+            ((0, ((3, 3), 9)), 0, 1),
+        ],
+        vec![((0, ((1, 1), 7)), 1, -1)],
+    ];
+    let expected = vec![
+        vec![
+            ((0, (1, 7)), 0, 1),
+            ((0, (2, 7)), 0, 1),
+            ((0, (3, 9)), 0, 1),
+        ],
+        vec![
+            ((0, (1, 7)), 1, -1),
+            ((0, (2, 7)), 1, -1),
+            ((0, (2, 3)), 1, 1),
+        ],
+    ];
+    run_test(input, expected, |coll| {
+        coll.cumulative(i32::MIN, |max, value| *max.max(value))
+            .arrange_by_key()
+    });
+}
@@ -1,9 +1,11 @@
 // Copyright © 2024 Pathway
 
+use assert_matches::assert_matches;
 use tempfile::tempdir;
 
+use pathway_engine::persistence::encryption::EncryptionKey;
 use pathway_engine::persistence::metadata_backends::file::FilesystemKVStorage;
-use pathway_engine::persistence::metadata_backends::MetadataBackend;
+use pathway_engine::persistence::metadata_backends::{EncryptedKVStorage, Error, MetadataBackend};
 
 #[test]
 fn test_simple_kv_operations() -> eyre::Result<()> {
@@ -28,3 +30,67 @@ fn test_simple_kv_operations() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_encrypted_kv_operations() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let mut plain_storage = FilesystemKVStorage::new(test_storage_path)?;
+    let key = EncryptionKey::new(&[7; 32])?;
+    let mut storage = EncryptedKVStorage::new(
+        Box::new(FilesystemKVStorage::new(test_storage_path)?),
+        key,
+        false,
+    )?;
+
+    storage.put_value("1", "one")?;
+    assert_eq!(storage.get_value("1")?, "one");
+    assert_ne!(plain_storage.get_value("1")?, "one");
+
+    let storage_with_wrong_key = EncryptedKVStorage::new(
+        Box::new(FilesystemKVStorage::new(test_storage_path)?),
+        EncryptionKey::new(&[8; 32])?,
+        false,
+    )?;
+    assert_matches!(
+        storage_with_wrong_key.get_value("1"),
+        Err(Error::Decryption(_))
+    );
+
+    // A value is bound to its key, so it can't be moved to another one
+    let encrypted = plain_storage.get_value("1")?;
+    plain_storage.put_value("2", &encrypted)?;
+    assert_matches!(storage.get_value("2"), Err(Error::Decryption(_)));
+
+    Ok(())
+}
+
+#[test]
+fn test_encrypted_kv_migration() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let mut plain_storage = FilesystemKVStorage::new(test_storage_path)?;
+    plain_storage.put_value("1", "one")?;
+
+    // The values written before the encryption was enabled are rejected
+    let key = EncryptionKey::new(&[7; 32])?;
+    let storage = EncryptedKVStorage::new(
+        Box::new(FilesystemKVStorage::new(test_storage_path)?),
+        key.clone(),
+        false,
+    )?;
+    assert_matches!(storage.get_value("1"), Err(Error::Unencrypted(_)));
+
+    // unless they are migrated, which encrypts them in place
+    EncryptedKVStorage::new(
+        Box::new(FilesystemKVStorage::new(test_storage_path)?),
+        key,
+        true,
+    )?;
+    assert_ne!(plain_storage.get_value("1")?, "one");
+    assert_eq!(storage.get_value("1")?, "one");
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+// Copyright © 2024 Pathway
+
+use assert_matches::assert_matches;
+use hyper::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+
+use pathway_engine::engine::http_auth::{HttpAuth, HttpAuthConfigError, HttpAuthFailure};
+
+fn bearer(token: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        AUTHORIZATION,
+        HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+    );
+    headers
+}
+
+#[test]
+fn test_everything_is_allowed_without_authentication() {
+    let auth = HttpAuth::parse("", "", false).unwrap();
+    assert!(!auth.is_enabled());
+    assert_eq!(auth.authorize("/shutdown", &HeaderMap::new(), None), Ok(()));
+}
+
+#[test]
+fn test_tokens_are_required() {
+    let auth = HttpAuth::parse("reader:secret-1, admin:secret-2", "", false).unwrap();
+    assert_eq!(
+        auth.authorize("/metrics", &bearer("secret-1"), None),
+        Ok(())
+    );
+    assert_eq!(
+        auth.authorize("/shutdown", &bearer("secret-2"), None),
+        Ok(())
+    );
+    assert_eq!(
+        auth.authorize("/metrics", &HeaderMap::new(), None),
+        Err(HttpAuthFailure::Unauthenticated)
+    );
+    assert_eq!(
+        auth.authorize("/metrics", &bearer("secret-3"), None),
+        Err(HttpAuthFailure::Unauthenticated)
+    );
+}
+
+#[test]
+fn test_routes_are_authorized_by_the_longest_prefix() {
+    let auth = HttpAuth::parse(
+        "reader:secret-1,admin:secret-2",
+        "/healthz=*; /tables/=reader,admin; /tables/private=admin; /shutdown=admin",
+        false,
+    )
+    .unwrap();
+    assert_eq!(auth.authorize("/healthz", &HeaderMap::new(), None), Ok(()));
+    assert_eq!(
+        auth.authorize("/tables/users", &bearer("secret-1"), None),
+        Ok(())
+    );
+    assert_eq!(
+        auth.authorize("/tables/private", &bearer("secret-1"), None),
+        Err(HttpAuthFailure::Forbidden)
+    );
+    assert_eq!(
+        auth.authorize("/tables/private", &bearer("secret-2"), None),
+        Ok(())
+    );
+    assert_eq!(
+        auth.authorize("/shutdown", &bearer("secret-1"), None),
+        Err(HttpAuthFailure::Forbidden)
+    );
+    assert_eq!(
+        auth.authorize("/metrics", &bearer("secret-1"), None),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_prefixes_match_whole_segments() {
+    let auth = HttpAuth::parse(
+        "reader:secret-1,admin:secret-2",
+        "/tables=admin; /ready/=*",
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        auth.authorize("/tables", &bearer("secret-1"), None),
+        Err(HttpAuthFailure::Forbidden)
+    );
+    assert_eq!(
+        auth.authorize("/tables/users", &bearer("secret-1"), None),
+        Err(HttpAuthFailure::Forbidden)
+    );
+    assert_eq!(
+        auth.authorize("/tablespace", &bearer("secret-1"), None),
+        Ok(())
+    );
+    assert_eq!(auth.authorize("/ready", &HeaderMap::new(), None), Ok(()));
+    assert_eq!(
+        auth.authorize("/readyz", &HeaderMap::new(), None),
+        Err(HttpAuthFailure::Unauthenticated)
+    );
+}
+
+#[test]
+fn test_client_certificates_name_the_clients() {
+    let auth = HttpAuth::parse("admin:secret", "/shutdown=admin,operator", true).unwrap();
+    assert_eq!(
+        auth.authorize("/shutdown", &HeaderMap::new(), Some("operator")),
+        Ok(())
+    );
+    assert_eq!(
+        auth.authorize("/shutdown", &HeaderMap::new(), Some("dashboard")),
+        Err(HttpAuthFailure::Forbidden)
+    );
+    // The token takes precedence over the certificate
+    assert_eq!(
+        auth.authorize("/shutdown", &bearer("secret"), Some("dashboard")),
+        Ok(())
+    );
+    assert_eq!(
+        auth.authorize("/metrics", &HeaderMap::new(), Some("dashboard")),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_invalid_configuration() {
+    assert_matches!(
+        HttpAuth::parse("secret", "", false),
+        Err(HttpAuthConfigError::InvalidToken(_))
+    );
+    assert_matches!(
+        HttpAuth::parse("admin:secret", "shutdown=admin", false),
+        Err(HttpAuthConfigError::InvalidRule(_))
+    );
+    assert_matches!(
+        HttpAuth::parse("admin:secret", "/shutdown=", false),
+        Err(HttpAuthConfigError::InvalidRule(_))
+    );
+    assert_matches!(
+        HttpAuth::parse("", "/shutdown=admin", false),
+        Err(HttpAuthConfigError::RulesWithoutAuthentication)
+    );
+}
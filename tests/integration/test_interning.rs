@@ -0,0 +1,44 @@
+// Copyright © 2024 Pathway
+
+use arcstr::ArcStr;
+
+use pathway_engine::engine::interning::{intern, MAX_INTERNED_LENGTH};
+use pathway_engine::engine::Value;
+
+fn as_arc_str(value: &Value) -> &ArcStr {
+    match value {
+        Value::String(string) => string,
+        value => panic!("expected a string, got {value:?}"),
+    }
+}
+
+#[test]
+fn test_short_strings_are_shared() {
+    let first = intern("PL");
+    let second = intern(&String::from("PL"));
+    assert!(ArcStr::ptr_eq(&first, &second));
+    assert!(!ArcStr::ptr_eq(&first, &intern("DE")));
+}
+
+#[test]
+fn test_long_strings_are_not_shared() {
+    let long = "x".repeat(MAX_INTERNED_LENGTH + 1);
+    assert!(!ArcStr::ptr_eq(&intern(&long), &intern(&long)));
+}
+
+#[test]
+fn test_values_share_strings() {
+    let first = Value::from("pending");
+    let second = Value::from("pending");
+    assert!(ArcStr::ptr_eq(as_arc_str(&first), as_arc_str(&second)));
+}
+
+#[test]
+fn test_deserialized_values_share_strings() -> eyre::Result<()> {
+    let serialized = bincode::serialize(&Value::from("delivered"))?;
+    let first: Value = bincode::deserialize(&serialized)?;
+    let second: Value = bincode::deserialize(&serialized)?;
+    assert_eq!(first, Value::from("delivered"));
+    assert!(ArcStr::ptr_eq(as_arc_str(&first), as_arc_str(&second)));
+    Ok(())
+}
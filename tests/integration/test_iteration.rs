@@ -0,0 +1,102 @@
+// Copyright © 2024 Pathway
+
+#![allow(clippy::disallowed_methods)]
+
+use std::sync::{Arc, Mutex};
+
+use differential_dataflow::input::InputSession;
+use differential_dataflow::operators::iterate::Variable;
+use differential_dataflow::operators::Consolidate;
+use timely::dataflow::operators::Map;
+use timely::dataflow::Scope;
+use timely::order::Product;
+
+use pathway_engine::engine::dataflow::operators::iteration::{InspectIterations, IterationEvent};
+
+fn run_halving(values: Vec<u64>) -> Vec<IterationEvent<u64>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    timely::execute_directly({
+        let events = events.clone();
+        move |worker| {
+            let mut input: InputSession<u64, u64, isize> = InputSession::new();
+            worker.dataflow::<u64, _, _>(|scope| {
+                let numbers = input.to_collection(scope);
+                scope.iterative::<u32, _, _>(|inner| {
+                    let variable = Variable::new_from(numbers.enter(inner), Product::new(0, 1));
+                    let result = variable.map(|x| x / 2).consolidate();
+                    variable.set(&result);
+                    result
+                        .inner
+                        .map(|(_data, time, diff)| (time, diff.unsigned_abs()))
+                        .inspect_iterations(move |event| events.lock().unwrap().push(event));
+                });
+            });
+            for value in values {
+                input.insert(value);
+            }
+        }
+    });
+    Arc::try_unwrap(events).unwrap().into_inner().unwrap()
+}
+
+#[test]
+fn test_updates_per_iteration() {
+    assert_eq!(
+        run_halving(vec![8]),
+        vec![
+            IterationEvent::Iteration {
+                time: 0,
+                iteration: 1,
+                updates: 1
+            },
+            IterationEvent::Iteration {
+                time: 0,
+                iteration: 2,
+                updates: 2
+            },
+            IterationEvent::Iteration {
+                time: 0,
+                iteration: 3,
+                updates: 2
+            },
+            IterationEvent::Iteration {
+                time: 0,
+                iteration: 4,
+                updates: 2
+            },
+            IterationEvent::Converged {
+                time: 0,
+                iterations: 4
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_updates_of_many_rows_are_summed() {
+    let events = run_halving(vec![2, 3, 4]);
+    assert_eq!(
+        events,
+        vec![
+            IterationEvent::Iteration {
+                time: 0,
+                iteration: 1,
+                updates: 3
+            },
+            IterationEvent::Iteration {
+                time: 0,
+                iteration: 2,
+                updates: 4
+            },
+            IterationEvent::Iteration {
+                time: 0,
+                iteration: 3,
+                updates: 2
+            },
+            IterationEvent::Converged {
+                time: 0,
+                iterations: 3
+            },
+        ]
+    );
+}
@@ -0,0 +1,81 @@
+// Copyright © 2024 Pathway
+
+use std::collections::HashMap;
+
+use pathway_engine::engine::value::{HashInto, Hasher};
+use pathway_engine::engine::{Key, KeyHashing, Value};
+
+fn key_for_values(hashing: KeyHashing, values: &[Value]) -> Key {
+    let mut hasher = Hasher::new(hashing);
+    values.iter().for_each(|value| value.hash_into(&mut hasher));
+    Key::from_hasher(&hasher)
+}
+
+fn siphash(seed: u128) -> KeyHashing {
+    KeyHashing::SipHash {
+        seed,
+        per_run_seed: false,
+    }
+}
+
+#[test]
+fn test_xxh3_by_default() {
+    assert_eq!(KeyHashing::configured(), Ok(KeyHashing::Xxh3));
+    let values = [Value::from("a"), Value::Int(1)];
+    assert_eq!(
+        key_for_values(KeyHashing::Xxh3, &values),
+        Key::for_values(&values)
+    );
+}
+
+#[test]
+fn test_siphash_depends_on_seed() {
+    let values = [Value::from("a"), Value::Int(1)];
+    let key = key_for_values(siphash(1), &values);
+    assert_eq!(key, key_for_values(siphash(1), &values));
+    assert_ne!(key, key_for_values(siphash(2), &values));
+    assert_ne!(key, key_for_values(siphash(1 << 64), &values));
+    assert_ne!(key, key_for_values(KeyHashing::Xxh3, &values));
+    assert_ne!(
+        key,
+        key_for_values(siphash(1), &[Value::from("a"), Value::Int(2)])
+    );
+}
+
+fn hashing_from_vars(vars: &[(&str, &str)]) -> Result<KeyHashing, String> {
+    let vars: HashMap<_, _> = vars.iter().copied().collect();
+    KeyHashing::from_vars(|name| vars.get(name).map(ToString::to_string))
+}
+
+#[test]
+fn test_siphash_seed_required_for_many_processes() {
+    // The settings shared by the processes, like the run id derived from the addresses,
+    // may be guessed, so the seed isn't derived from them
+    let cluster_vars = [
+        ("PATHWAY_KEY_HASHING", "siphash"),
+        ("PATHWAY_PROCESSES", "2"),
+        ("PATHWAY_ADDRESSES", "10.0.0.1:2000,10.0.0.2:2000"),
+        ("PATHWAY_RUN_ID", "4d1c1c5e-8b7f-5f3a-9a57-25b3a7a6e2b1"),
+    ];
+    let error = hashing_from_vars(&cluster_vars).unwrap_err();
+    assert!(error.contains("PATHWAY_KEY_HASHING_SEED"), "{error}");
+
+    let mut vars = cluster_vars.to_vec();
+    vars.push(("PATHWAY_KEY_HASHING_SEED", "12345"));
+    assert_eq!(hashing_from_vars(&vars), Ok(siphash(12345)));
+}
+
+#[test]
+fn test_siphash_seed_generated_for_single_process() {
+    let hashing = hashing_from_vars(&[
+        ("PATHWAY_KEY_HASHING", "siphash"),
+        ("PATHWAY_PROCESSES", "1"),
+    ]);
+    assert!(matches!(
+        hashing,
+        Ok(KeyHashing::SipHash {
+            per_run_seed: true,
+            ..
+        })
+    ));
+}
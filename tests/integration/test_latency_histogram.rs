@@ -0,0 +1,51 @@
+// Copyright © 2024 Pathway
+
+use std::time::{Duration, SystemTime};
+
+use pathway_engine::engine::graph::LATENCY_BUCKETS_MS;
+use pathway_engine::engine::LatencyHistogram;
+
+fn emitted_at(time: u64, latency_ms: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(time + latency_ms)
+}
+
+#[test]
+fn test_latencies_are_put_into_buckets() {
+    let mut histogram = LatencyHistogram::default();
+    histogram.observe(1000, 2, emitted_at(1000, 0));
+    histogram.observe(1000, 3, emitted_at(1000, 7));
+    histogram.observe(1000, 1, emitted_at(1000, 100));
+    histogram.observe(1000, 1, emitted_at(1000, 100_000));
+
+    let mut expected_counts = vec![0; LATENCY_BUCKETS_MS.len() + 1];
+    expected_counts[0] = 2;
+    expected_counts[2] = 3;
+    expected_counts[5] = 1;
+    expected_counts[LATENCY_BUCKETS_MS.len()] = 1;
+    assert_eq!(histogram.bucket_counts, expected_counts);
+    assert_eq!(histogram.count, 7);
+    assert_eq!(histogram.sum_ms, 21 + 100 + 100_000);
+}
+
+#[test]
+fn test_rows_from_the_future_have_no_latency() {
+    let mut histogram = LatencyHistogram::default();
+    histogram.observe(2000, 1, emitted_at(1000, 0));
+    assert_eq!(histogram.bucket_counts[0], 1);
+    assert_eq!(histogram.sum_ms, 0);
+}
+
+#[test]
+fn test_quantile_bounds() {
+    let mut histogram = LatencyHistogram::default();
+    assert_eq!(histogram.quantile_bound(0.5), None);
+
+    histogram.observe(1000, 90, emitted_at(1000, 3));
+    histogram.observe(1000, 9, emitted_at(1000, 40));
+    histogram.observe(1000, 1, emitted_at(1000, 100_000));
+    assert_eq!(histogram.quantile_bound(0.0), Some(5));
+    assert_eq!(histogram.quantile_bound(0.5), Some(5));
+    assert_eq!(histogram.quantile_bound(0.95), Some(50));
+    assert_eq!(histogram.quantile_bound(0.99), Some(50));
+    assert_eq!(histogram.quantile_bound(1.0), None);
+}
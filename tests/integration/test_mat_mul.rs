@@ -0,0 +1,44 @@
+// Copyright © 2024 Pathway
+
+use ndarray::{ArrayD, Dimension, IxDyn};
+
+use pathway_engine::mat_mul::mat_mul;
+
+fn int_array(shape: &[usize], seed: usize) -> ArrayD<i64> {
+    ArrayD::from_shape_fn(IxDyn(shape), |index| {
+        let i = index.slice().iter().fold(seed, |i, &x| i * 31 + x);
+        i64::try_from(i % 11).unwrap() - 5
+    })
+}
+
+/// The products of small ints are exact in floats, so the float products, split into tiles
+/// when they are large, have to be equal to the int ones.
+fn check_float_product_is_exact(a_shape: &[usize], b_shape: &[usize]) {
+    let a = int_array(a_shape, 1);
+    let b = int_array(b_shape, 2);
+    let expected = mat_mul(&a.view(), &b.view()).unwrap().mapv(|x| x as f64);
+    let (a, b) = (a.mapv(|x| x as f64), b.mapv(|x| x as f64));
+    assert_eq!(mat_mul(&a.view(), &b.view()).unwrap(), expected);
+    let (a, b) = (a.mapv(|x| x as f32), b.mapv(|x| x as f32));
+    assert_eq!(
+        mat_mul(&a.view(), &b.view()).unwrap(),
+        expected.mapv(|x| x as f32)
+    );
+}
+
+#[test]
+fn test_small_products() {
+    check_float_product_is_exact(&[3, 4], &[4, 5]);
+    check_float_product_is_exact(&[4], &[4, 5]);
+    check_float_product_is_exact(&[3, 4], &[4]);
+    check_float_product_is_exact(&[4], &[4]);
+    check_float_product_is_exact(&[0, 4], &[4, 0]);
+}
+
+#[test]
+fn test_large_products() {
+    check_float_product_is_exact(&[300, 200], &[200, 300]);
+    check_float_product_is_exact(&[1000, 70], &[70, 129]);
+    check_float_product_is_exact(&[2000, 3000], &[3000]);
+    check_float_product_is_exact(&[3000], &[3000, 2000]);
+}
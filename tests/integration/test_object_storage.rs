@@ -0,0 +1,180 @@
+// Copyright © 2024 Pathway
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use reqwest::StatusCode;
+
+use pathway_engine::connectors::snapshot::Event as SnapshotEvent;
+use pathway_engine::connectors::snapshot::{
+    ObjectStorageSnapshotReader, ObjectStorageSnapshotWriter, SnapshotEncoding, SnapshotReader,
+    SnapshotReaderImpl, SnapshotWriter,
+};
+use pathway_engine::engine::{Key, Value};
+use pathway_engine::persistence::metadata_backends::{MetadataBackend, ObjectStorageKVStorage};
+use pathway_engine::persistence::object_storage::{Error, ObjectStorage, SharedObjectStorage};
+
+/// An object storage keeping the objects in memory.
+#[derive(Debug, Default)]
+struct MemoryStorage {
+    objects: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    fn keys(&self) -> Vec<String> {
+        self.objects.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+impl ObjectStorage for MemoryStorage {
+    fn list(&self, prefix: &str) -> Result<Vec<String>, Error> {
+        Ok(self
+            .keys()
+            .into_iter()
+            .filter(|key| key.starts_with(prefix))
+            .collect())
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| Error::Status {
+                method: "get",
+                key: key.to_string(),
+                status: StatusCode::NOT_FOUND,
+                body: String::new(),
+            })
+    }
+
+    fn put(&self, key: &str, data: Vec<u8>) -> Result<(), Error> {
+        self.objects.lock().unwrap().insert(key.to_string(), data);
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Error> {
+        self.objects.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+fn read_snapshot(storage: &SharedObjectStorage, path: &str) -> eyre::Result<Vec<SnapshotEvent>> {
+    let mut reader = ObjectStorageSnapshotReader::new(storage.clone(), path, None)?;
+    let mut events = Vec::new();
+    loop {
+        match reader.read()? {
+            SnapshotEvent::Finished => return Ok(events),
+            event => events.push(event),
+        }
+    }
+}
+
+#[test]
+fn test_object_storage_snapshot_io() -> eyre::Result<()> {
+    let memory_storage = Arc::new(MemoryStorage::default());
+    let storage: SharedObjectStorage = memory_storage.clone();
+    let event1 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]);
+    let event2 = SnapshotEvent::Insert(Key::random(), vec![Value::from("two")]);
+
+    {
+        let mut writer =
+            ObjectStorageSnapshotWriter::new(storage.clone(), "root", SnapshotEncoding::default());
+        writer.write(&event1)?;
+        writer.write(&SnapshotEvent::AdvanceTime(2))?;
+        futures::executor::block_on(writer.flush())??;
+        // Nothing is uploaded for an empty flush
+        futures::executor::block_on(writer.flush())??;
+        writer.write(&event2)?;
+        writer.write(&SnapshotEvent::AdvanceTime(4))?;
+        futures::executor::block_on(writer.flush())??;
+        // Not flushed, so it isn't persisted
+        writer.write(&SnapshotEvent::AdvanceTime(6))?;
+    }
+
+    assert_eq!(memory_storage.keys().len(), 2);
+    assert_eq!(
+        read_snapshot(&storage, "root")?,
+        vec![
+            event1,
+            SnapshotEvent::AdvanceTime(2),
+            event2,
+            SnapshotEvent::AdvanceTime(4)
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_object_storage_snapshot_truncate() -> eyre::Result<()> {
+    let memory_storage = Arc::new(MemoryStorage::default());
+    let storage: SharedObjectStorage = memory_storage.clone();
+    let event1 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]);
+    let event2 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(2)]);
+    let event3 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(3)]);
+
+    {
+        let mut writer =
+            ObjectStorageSnapshotWriter::new(storage.clone(), "root", SnapshotEncoding::default());
+        writer.write(&event1)?;
+        writer.write(&SnapshotEvent::AdvanceTime(2))?;
+        writer.write(&event2)?;
+        writer.write(&SnapshotEvent::AdvanceTime(4))?;
+        writer.write(&event3)?;
+        futures::executor::block_on(writer.flush())??;
+        writer.write(&SnapshotEvent::AdvanceTime(6))?;
+        futures::executor::block_on(writer.flush())??;
+    }
+    assert_eq!(memory_storage.keys().len(), 2);
+
+    let mut snapshot_reader = SnapshotReader::new(
+        Box::new(ObjectStorageSnapshotReader::new(
+            storage.clone(),
+            "root",
+            None,
+        )?),
+        4,
+        None,
+    )?;
+    while snapshot_reader.read()? != SnapshotEvent::Finished {}
+
+    // The events after the threshold are removed, with the chunks containing only them
+    assert_eq!(memory_storage.keys().len(), 1);
+    assert_eq!(
+        read_snapshot(&storage, "root")?,
+        vec![
+            event1,
+            SnapshotEvent::AdvanceTime(2),
+            event2,
+            SnapshotEvent::AdvanceTime(4)
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_object_storage_kv_operations() -> eyre::Result<()> {
+    let storage: SharedObjectStorage = Arc::new(MemoryStorage::default());
+    let mut kv_storage = ObjectStorageKVStorage::new(storage.clone(), "root");
+    assert_eq!(kv_storage.list_keys()?, Vec::<String>::new());
+
+    kv_storage.put_value("1", "one")?;
+    kv_storage.put_value("2", "two")?;
+    // The snapshots next to the metadata aren't its keys
+    storage.put("root/streams/0/42/1", vec![0])?;
+    storage.put("other/3", vec![0])?;
+    assert_eq!(kv_storage.list_keys()?, vec!["1", "2"]);
+    assert_eq!(kv_storage.get_value("2")?, "two");
+
+    kv_storage.put_value("1", "three")?;
+    assert_eq!(kv_storage.get_value("1")?, "three");
+    kv_storage.remove_key("1")?;
+    kv_storage.remove_key("1")?;
+    assert_eq!(kv_storage.list_keys()?, vec!["2"]);
+    assert!(kv_storage.get_value("1").is_err());
+
+    Ok(())
+}
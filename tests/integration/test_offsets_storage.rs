@@ -1,20 +1,38 @@
 // Copyright © 2024 Pathway
 
 use super::helpers::create_metadata_storage;
+use super::helpers::create_persistence_config;
 use super::helpers::create_persistence_manager;
+use super::helpers::create_persistence_manager_with_background_checkpoints;
+use super::helpers::create_persistence_manager_with_intervals;
+use super::helpers::create_persistence_manager_with_retention;
 use super::helpers::get_entries_in_receiver;
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
+use assert_matches::assert_matches;
 use tempfile::tempdir;
 
+use pathway_engine::connectors::snapshot::Event as SnapshotEvent;
 use pathway_engine::connectors::{Connector, Entry, PersistenceMode};
+use pathway_engine::engine::{Key, PersistenceStats, Value};
 
-use pathway_engine::connectors::data_storage::StorageType;
+use pathway_engine::connectors::data_storage::{ReadError, SourceAcknowledger, StorageType};
 use pathway_engine::connectors::{OffsetKey, OffsetValue};
+use pathway_engine::persistence::cluster::ClusterCheckpoints;
 use pathway_engine::persistence::frontier::OffsetAntichain;
+use pathway_engine::persistence::metadata_backends::{
+    Error as MetadataBackendError, FilesystemKVStorage,
+};
+use pathway_engine::persistence::state::{
+    restore_savepoint, MetadataAccessor, RetentionPolicy, METADATA_FORMAT_VERSION,
+};
+use pathway_engine::persistence::sync::{CheckpointHook, SharedWorkersPersistenceCoordinator};
+use pathway_engine::persistence::tracker::SingleWorkerPersistentStorage;
 
 fn assert_frontiers_equal(
     mut lhs: Vec<(OffsetKey, OffsetValue)>,
@@ -211,6 +229,142 @@ fn test_state_dump_and_load() -> eyre::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_savepoint_restore() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    {
+        let mut storage = create_metadata_storage(test_storage_path, true);
+        storage.register_input_source(1, &StorageType::FileSystem);
+        storage.save_offset(1, &OffsetKey::Empty, &OffsetValue::KafkaOffset(1));
+        storage.accept_finalized_timestamp(2);
+        storage.save_savepoint("before-upgrade")?;
+        storage.save_offset(1, &OffsetKey::Empty, &OffsetValue::KafkaOffset(5));
+        storage.accept_finalized_timestamp(6);
+    }
+
+    {
+        let storage = create_metadata_storage(test_storage_path, false);
+        assert_eq!(storage.last_advanced_timestamp(), 6);
+    }
+
+    let mut backend = FilesystemKVStorage::new(test_storage_path)?;
+    assert_matches!(
+        restore_savepoint(&mut backend, "unknown"),
+        Err(MetadataBackendError::SavepointNotFound(_))
+    );
+    restore_savepoint(&mut backend, "before-upgrade")?;
+
+    for _ in 0..2 {
+        let storage = create_metadata_storage(test_storage_path, false);
+        assert_eq!(storage.last_advanced_timestamp(), 2);
+        assert_eq!(
+            storage.past_runs_threshold_times(),
+            &HashMap::from([(0, 2)])
+        );
+        assert_frontiers_equal(
+            storage.frontier_for(1).as_vec(),
+            vec![(OffsetKey::Empty, OffsetValue::KafkaOffset(1))],
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_corrupted_state_falls_back_to_previous() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    {
+        let mut storage = create_metadata_storage(test_storage_path, true);
+        storage.register_input_source(1, &StorageType::FileSystem);
+        storage.save_offset(1, &OffsetKey::Empty, &OffsetValue::KafkaOffset(1));
+        storage.accept_finalized_timestamp(2);
+        storage.save_current_state()?;
+        storage.save_offset(1, &OffsetKey::Empty, &OffsetValue::KafkaOffset(5));
+        storage.accept_finalized_timestamp(6);
+    }
+
+    // A bit flip in the latest state is detected by its checksum
+    for entry in std::fs::read_dir(test_storage_path)? {
+        let path = entry?.path();
+        let block = std::fs::read_to_string(&path)?;
+        if block.contains("\"last_advanced_timestamp\":6") {
+            std::fs::write(
+                &path,
+                block.replace(
+                    "\"last_advanced_timestamp\":6",
+                    "\"last_advanced_timestamp\":7",
+                ),
+            )?;
+        }
+    }
+
+    let storage = create_metadata_storage(test_storage_path, false);
+    assert_eq!(storage.last_advanced_timestamp(), 2);
+    assert_frontiers_equal(
+        storage.frontier_for(1).as_vec(),
+        vec![(OffsetKey::Empty, OffsetValue::KafkaOffset(1))],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_state_without_checksum_is_loaded() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    std::fs::write(
+        test_storage_path.join("1-0-0"),
+        r#"{"frontiers":{"antichains":{}},"storage_types":{},"last_advanced_timestamp":4}"#,
+    )?;
+
+    let storage = create_metadata_storage(test_storage_path, false);
+    assert_eq!(storage.last_advanced_timestamp(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_state_format_version() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    {
+        let mut storage = create_metadata_storage(test_storage_path, true);
+        storage.accept_finalized_timestamp(4);
+        storage.save_current_state()?;
+    }
+    let block = std::fs::read_to_string(
+        test_storage_path.join(
+            std::fs::read_dir(test_storage_path)?
+                .next()
+                .expect("metadata block should be saved")?
+                .file_name(),
+        ),
+    )?;
+    assert!(block.contains(&format!(r#""format_version":{METADATA_FORMAT_VERSION}"#)));
+
+    // The state saved by a newer version of the engine is not skipped silently
+    std::fs::write(
+        test_storage_path.join("1-0-0"),
+        r#"{"format_version":1000,"frontiers":{"antichains":{}},"storage_types":{},"last_advanced_timestamp":5}"#,
+    )?;
+    let backend = Box::new(FilesystemKVStorage::new(test_storage_path)?);
+    assert_matches!(
+        MetadataAccessor::new(backend, 0, RetentionPolicy::default()),
+        Err(MetadataBackendError::UnsupportedFormatVersion(
+            1000,
+            METADATA_FORMAT_VERSION
+        ))
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_rewind_for_empty_persistent_storage() -> eyre::Result<()> {
     let test_storage = tempdir()?;
@@ -218,7 +372,13 @@ fn test_rewind_for_empty_persistent_storage() -> eyre::Result<()> {
 
     let (sender, receiver) = mpsc::channel();
     let (tracker, _global_tracker) = create_persistence_manager(test_storage_path, false);
-    Connector::<u64>::rewind_from_disk_snapshot(1, &tracker, &sender, PersistenceMode::Batch);
+    Connector::<u64>::rewind_from_disk_snapshot(
+        1,
+        &tracker,
+        &sender,
+        PersistenceMode::Batch,
+        None,
+    )?;
     assert_eq!(get_entries_in_receiver::<Entry>(receiver).len(), 0); // We would not even start rewind when there is no frontier
 
     Ok(())
@@ -255,6 +415,185 @@ fn test_timestamp_advancement_in_tracker() -> eyre::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_commit_by_unpersisted_rows() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let (tracker, global_tracker) = create_persistence_manager_with_intervals(
+        test_storage_path,
+        true,
+        Duration::from_secs(3600),
+        Some(2),
+    );
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+    let snapshot_writer = tracker.lock().unwrap().create_snapshot_writer(42)?;
+
+    // The first time is committed immediately, the next ones wait for the interval
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(1));
+    assert_eq!(tracker.lock().unwrap().last_finalized_timestamp(), 1);
+    snapshot_writer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]))?;
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(3));
+    assert_eq!(tracker.lock().unwrap().last_finalized_timestamp(), 1);
+
+    // Unless enough rows are waiting for the commit
+    snapshot_writer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Insert(Key::random(), vec![Value::Int(2)]))?;
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(5));
+    assert_eq!(tracker.lock().unwrap().last_finalized_timestamp(), 5);
+
+    // The commit resets the count
+    snapshot_writer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Insert(Key::random(), vec![Value::Int(3)]))?;
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(7));
+    assert_eq!(tracker.lock().unwrap().last_finalized_timestamp(), 5);
+
+    Ok(())
+}
+
+#[test]
+fn test_checkpoint_stats() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let (tracker, global_tracker) = create_persistence_manager(test_storage_path, true);
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+    let snapshot_writer = tracker.lock().unwrap().create_snapshot_writer(42)?;
+    let persistence_stats = global_tracker.lock().unwrap().persistence_stats().clone();
+    assert_eq!(
+        *persistence_stats.lock().unwrap(),
+        PersistenceStats::default()
+    );
+
+    for value in 0..2 {
+        snapshot_writer
+            .lock()
+            .unwrap()
+            .write(&SnapshotEvent::Insert(
+                Key::random(),
+                vec![Value::Int(value)],
+            ))?;
+    }
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(1));
+    let stats = *persistence_stats.lock().unwrap();
+    assert_eq!(stats.checkpoints, 1);
+    assert_eq!(stats.failed_checkpoints, 0);
+    assert_eq!(stats.last_checkpoint_rows, Some(2));
+    assert!(stats.last_checkpoint_bytes.is_some_and(|bytes| bytes > 0));
+    assert!(stats.last_checkpoint_duration_ms.is_some());
+    assert!(stats.last_checkpoint_age(SystemTime::now()).is_some());
+
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(3));
+    let stats = *persistence_stats.lock().unwrap();
+    assert_eq!(stats.checkpoints, 2);
+    assert_eq!(stats.last_checkpoint_rows, Some(0));
+    assert_eq!(stats.last_checkpoint_bytes, Some(0));
+
+    Ok(())
+}
+
+fn metadata_block_keys(path: &Path) -> eyre::Result<Vec<String>> {
+    let mut keys = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            keys.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    keys.sort();
+    Ok(keys)
+}
+
+#[test]
+fn test_retention_of_checkpoints_and_snapshots() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+    let retention_policy = RetentionPolicy {
+        keep_last_checkpoints: Some(1),
+        keep_checkpoints_for: None,
+    };
+
+    {
+        let (tracker, global_tracker) =
+            create_persistence_manager_with_retention(test_storage_path, true, retention_policy);
+        let mock_sink_id = tracker.lock().unwrap().register_sink();
+        for persistent_id in [1, 2] {
+            tracker.lock().unwrap().register_input_source(
+                persistent_id,
+                &StorageType::FileSystem,
+                Arc::new(Mutex::new(HashMap::new())),
+            );
+            tracker
+                .lock()
+                .unwrap()
+                .create_snapshot_writer(persistent_id)?
+                .lock()
+                .unwrap()
+                .write(&SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]))?;
+        }
+        global_tracker
+            .lock()
+            .unwrap()
+            .accept_finalized_timestamp(0, mock_sink_id, Some(2));
+    }
+    // One checkpoint is saved by the commit and one when the run finishes
+    assert_eq!(metadata_block_keys(test_storage_path)?.len(), 2);
+
+    // The keys of the checkpoints contain the time of the start of the run
+    std::thread::sleep(Duration::from_millis(10));
+
+    // The input 2 is no longer read
+    let (tracker, global_tracker) =
+        create_persistence_manager_with_retention(test_storage_path, false, retention_policy);
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+    tracker.lock().unwrap().register_input_source(
+        1,
+        &StorageType::FileSystem,
+        Arc::new(Mutex::new(HashMap::new())),
+    );
+    let old_keys = metadata_block_keys(test_storage_path)?;
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(4));
+
+    // Only the checkpoint of this run is kept and the snapshot of the input 2 is removed
+    let keys = metadata_block_keys(test_storage_path)?;
+    assert_eq!(keys.len(), 1);
+    assert!(!old_keys.contains(&keys[0]));
+    let worker_snapshots_path = test_storage_path.join("streams").join("0");
+    assert!(worker_snapshots_path.join("1").exists());
+    assert!(!worker_snapshots_path.join("2").exists());
+    assert_eq!(tracker.lock().unwrap().last_finalized_timestamp(), 4);
+
+    Ok(())
+}
+
 #[test]
 fn test_frontier_dumping_in_tracker() -> eyre::Result<()> {
     let test_storage = tempdir()?;
@@ -492,6 +831,125 @@ fn test_global_finalized_timestamp() -> eyre::Result<()> {
     Ok(())
 }
 
+struct MockSourceAcknowledger {
+    acknowledged: Arc<Mutex<Vec<OffsetAntichain>>>,
+}
+
+impl SourceAcknowledger for MockSourceAcknowledger {
+    fn acknowledge(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        self.acknowledged.lock().unwrap().push(frontier.clone());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_sources_acknowledged_after_commit() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let frontiers_by_time = Arc::new(Mutex::new(HashMap::<u64, OffsetAntichain>::new()));
+    let acknowledged = Arc::new(Mutex::new(Vec::new()));
+    let (tracker, global_tracker) = create_persistence_manager(test_storage_path, true);
+
+    tracker.lock().unwrap().register_input_source(
+        1,
+        &StorageType::Kafka,
+        frontiers_by_time.clone(),
+    );
+    tracker.lock().unwrap().register_source_acknowledger(
+        1,
+        Box::new(MockSourceAcknowledger {
+            acknowledged: acknowledged.clone(),
+        }),
+    );
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+
+    let offset_key = OffsetKey::Kafka("test".to_string().into(), 0);
+    for (time, offset) in [(1, 10), (5, 20)] {
+        let mut frontier = OffsetAntichain::new();
+        frontier.advance_offset(offset_key.clone(), OffsetValue::KafkaOffset(offset));
+        frontiers_by_time.lock().unwrap().insert(time, frontier);
+    }
+
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(4));
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(8));
+
+    // The acknowledged frontiers are the committed ones, not the latest read
+    let acknowledged = acknowledged.lock().unwrap();
+    assert_eq!(acknowledged.len(), 2);
+    assert_eq!(
+        acknowledged[0].get_offset(&offset_key),
+        Some(&OffsetValue::KafkaOffset(10))
+    );
+    assert_eq!(
+        acknowledged[1].get_offset(&offset_key),
+        Some(&OffsetValue::KafkaOffset(20))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_background_checkpoints() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let frontiers_by_time = Arc::new(Mutex::new(HashMap::<u64, OffsetAntichain>::new()));
+    let (tracker, global_tracker) =
+        create_persistence_manager_with_background_checkpoints(test_storage_path, true);
+
+    tracker.lock().unwrap().register_input_source(
+        1,
+        &StorageType::FileSystem,
+        frontiers_by_time.clone(),
+    );
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+
+    let mut frontier = OffsetAntichain::new();
+    frontier.advance_offset(OffsetKey::Empty, OffsetValue::KafkaOffset(1));
+    frontiers_by_time
+        .lock()
+        .unwrap()
+        .insert(1, frontier.clone());
+    frontiers_by_time.lock().unwrap().insert(6, frontier);
+
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(4));
+    // The commit is done in the background, so it has to be waited for
+    let started_at = SystemTime::now();
+    while tracker.lock().unwrap().last_finalized_timestamp() != 4 {
+        assert!(started_at.elapsed()? < Duration::from_secs(10));
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    // The last commit is done before returning
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, None);
+    assert_eq!(tracker.lock().unwrap().last_finalized_timestamp(), 7);
+    assert_eq!(
+        global_tracker
+            .lock()
+            .unwrap()
+            .persistence_stats()
+            .lock()
+            .unwrap()
+            .checkpoints,
+        2
+    );
+
+    Ok(())
+}
+
 #[test]
 #[should_panic(expected = "Same persistent_id belongs to more than one data source: 512")]
 fn test_unique_persistent_id() {
@@ -561,3 +1019,237 @@ fn test_several_sinks_finalized_timestamp_calculation() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[derive(Debug)]
+struct MockCheckpointHook {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl CheckpointHook for MockCheckpointHook {
+    fn on_checkpoint_start(&mut self, timestamp: Option<u64>) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("start {timestamp:?}"));
+    }
+
+    fn on_checkpoint_complete(&mut self, timestamp: Option<u64>) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("complete {timestamp:?}"));
+    }
+
+    fn on_restore_complete(&mut self, timestamp: u64) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("restore {timestamp}"));
+    }
+}
+
+#[test]
+fn test_checkpoint_hooks() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let config = create_persistence_config(test_storage_path);
+    config
+        .checkpoint_hooks()
+        .lock()
+        .unwrap()
+        .push(Box::new(MockCheckpointHook {
+            events: events.clone(),
+        }));
+    let global_tracker = Arc::new(Mutex::new(
+        config.create_workers_persistence_coordinator(0..1, 1)?,
+    ));
+    let tracker = Arc::new(Mutex::new(SingleWorkerPersistentStorage::new(
+        config.into_inner(0, 1),
+    )?));
+    global_tracker
+        .lock()
+        .unwrap()
+        .register_worker(tracker.clone());
+
+    let frontiers_by_time = Arc::new(Mutex::new(HashMap::<u64, OffsetAntichain>::new()));
+    tracker
+        .lock()
+        .unwrap()
+        .register_input_source(1, &StorageType::FileSystem, frontiers_by_time);
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(2));
+    // The restore is complete only after the outputs finish the time of the replayed data
+    tracker.lock().unwrap().accept_rewind_finished(1, 4);
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(3));
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(4));
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, None);
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            "start Some(2)",
+            "complete Some(2)",
+            "start Some(3)",
+            "complete Some(3)",
+            "restore 4",
+            "start Some(4)",
+            "complete Some(4)",
+            "start None",
+            "complete None",
+        ]
+    );
+
+    Ok(())
+}
+
+/// Creates the coordinator and the worker of the process `process_id` of a cluster of
+/// two processes with one worker each, sharing the storage.
+fn create_cluster_process(
+    fs_path: &Path,
+    process_id: usize,
+) -> eyre::Result<(
+    Arc<Mutex<SingleWorkerPersistentStorage>>,
+    SharedWorkersPersistenceCoordinator,
+    usize,
+)> {
+    let config = create_persistence_config(fs_path);
+    let coordinator = Arc::new(Mutex::new(
+        config.create_workers_persistence_coordinator(process_id..process_id + 1, 2)?,
+    ));
+    let tracker = Arc::new(Mutex::new(SingleWorkerPersistentStorage::new(
+        config.into_inner(process_id, 2),
+    )?));
+    coordinator.lock().unwrap().register_worker(tracker.clone());
+    let sink_id = tracker.lock().unwrap().register_sink();
+    Ok((tracker, coordinator, sink_id))
+}
+
+fn wait_for_commit(
+    tracker: &Mutex<SingleWorkerPersistentStorage>,
+    timestamp: u64,
+) -> eyre::Result<()> {
+    let started_at = SystemTime::now();
+    while tracker.lock().unwrap().last_finalized_timestamp() != timestamp {
+        assert!(started_at.elapsed()? < Duration::from_secs(10));
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_cluster_checkpoints_agreed() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let (leader, leader_coordinator, leader_sink) = create_cluster_process(test_storage_path, 0)?;
+    let (follower, follower_coordinator, follower_sink) =
+        create_cluster_process(test_storage_path, 1)?;
+
+    // The other processes make checkpoints only when the first one proposes them
+    follower_coordinator
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(1, follower_sink, Some(4));
+    assert_eq!(follower.lock().unwrap().last_finalized_timestamp(), 0);
+
+    leader_coordinator
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, leader_sink, Some(4));
+    // The checkpoint is committed only after all processes prepare it
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(leader.lock().unwrap().last_finalized_timestamp(), 0);
+
+    follower_coordinator
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(1, follower_sink, Some(6));
+    wait_for_commit(&leader, 4)?;
+    wait_for_commit(&follower, 4)?;
+
+    // The final checkpoint is agreed too, each process waits for the other one
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            leader_coordinator
+                .lock()
+                .unwrap()
+                .accept_finalized_timestamp(0, leader_sink, None);
+        });
+        follower_coordinator
+            .lock()
+            .unwrap()
+            .accept_finalized_timestamp(1, follower_sink, None);
+    });
+    assert_eq!(leader.lock().unwrap().last_finalized_timestamp(), 5);
+    assert_eq!(follower.lock().unwrap().last_finalized_timestamp(), 5);
+
+    Ok(())
+}
+
+#[test]
+fn test_cluster_restarts_from_last_agreed_checkpoint() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let (leader, leader_coordinator, leader_sink) = create_cluster_process(test_storage_path, 0)?;
+    let (follower, follower_coordinator, follower_sink) =
+        create_cluster_process(test_storage_path, 1)?;
+
+    leader_coordinator
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, leader_sink, Some(4));
+    follower_coordinator
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(1, follower_sink, Some(4));
+    wait_for_commit(&leader, 4)?;
+    wait_for_commit(&follower, 4)?;
+
+    // The second process prepares the next checkpoint, but stops before committing it
+    ClusterCheckpoints::new(Box::new(FilesystemKVStorage::new(test_storage_path)?), 1, 2)?
+        .mark_prepared(8, true)?;
+    // The next checkpoint starts once the thread of the previous one is finished
+    let started_at = SystemTime::now();
+    while leader.lock().unwrap().last_finalized_timestamp() != 8 {
+        assert!(started_at.elapsed()? < Duration::from_secs(10));
+        leader_coordinator
+            .lock()
+            .unwrap()
+            .accept_finalized_timestamp(0, leader_sink, Some(8));
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    drop(leader_coordinator);
+    drop(follower_coordinator);
+    drop(leader);
+    drop(follower);
+
+    // The next run starts from the last checkpoint committed by both processes
+    let metadata = MetadataAccessor::new(
+        Box::new(FilesystemKVStorage::new(test_storage_path)?),
+        0,
+        RetentionPolicy::default(),
+    )?;
+    assert_eq!(metadata.last_advanced_timestamp(), 4);
+    assert_eq!(
+        *metadata.past_runs_threshold_times(),
+        HashMap::from([(0, 4), (1, 4)])
+    );
+
+    Ok(())
+}
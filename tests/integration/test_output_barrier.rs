@@ -0,0 +1,101 @@
+// Copyright © 2024 Pathway
+
+use std::sync::{Arc, Mutex};
+
+use differential_dataflow::input::InputSession;
+use timely::dataflow::operators::{Concatenate, ConnectLoop, Feedback, Inspect, Map, Probe};
+
+use pathway_engine::engine::dataflow::operators::output::{ConsolidateForOutput, HoldUntil};
+
+#[test]
+fn test_output_held_until_barrier_passes() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    timely::execute_directly({
+        let seen = seen.clone();
+        move |worker| {
+            let mut first: InputSession<u64, i32, isize> = InputSession::new();
+            let mut second: InputSession<u64, i32, isize> = InputSession::new();
+            let inspected = seen.clone();
+            let probe = worker.dataflow::<u64, _, _>(|scope| {
+                let output = first.to_collection(scope).consolidate_for_output(true);
+                let barrier = second.to_collection(scope).inner.flat_map(|_| None::<()>);
+                output
+                    .hold_until(&barrier)
+                    .inspect(move |batch| {
+                        inspected
+                            .lock()
+                            .unwrap()
+                            .push((batch.time, batch.data.clone()));
+                    })
+                    .probe()
+            });
+
+            first.insert(1);
+            first.advance_to(1);
+            first.flush();
+            for _ in 0..10 {
+                worker.step();
+            }
+            assert!(seen.lock().unwrap().is_empty());
+
+            second.advance_to(1);
+            second.flush();
+            worker.step_while(|| probe.less_than(&1));
+            assert_eq!(*seen.lock().unwrap(), vec![(0, vec![(1, 1)])]);
+
+            first.insert(2);
+            first.remove(1);
+            first.advance_to(3);
+            first.flush();
+            second.advance_to(2);
+            second.flush();
+            worker.step_while(|| probe.less_than(&2));
+            assert_eq!(
+                *seen.lock().unwrap(),
+                vec![(0, vec![(1, 1)]), (1, vec![(1, -1), (2, 1)])]
+            );
+        }
+    });
+}
+
+#[test]
+fn test_outputs_wait_for_each_other() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    timely::execute_directly({
+        let seen = seen.clone();
+        move |worker| {
+            let mut fast: InputSession<u64, i32, isize> = InputSession::new();
+            let mut slow: InputSession<u64, i32, isize> = InputSession::new();
+            let inspected = seen.clone();
+            let probe = worker.dataflow::<u64, _, _>(|scope| {
+                let (handle, barrier) = scope.feedback(Default::default());
+                let outputs = [
+                    fast.to_collection(scope).consolidate_for_output(true),
+                    slow.to_collection(scope).consolidate_for_output(true),
+                ];
+                scope
+                    .concatenate(outputs.iter().map(|output| output.flat_map(|_| None::<()>)))
+                    .connect_loop(handle);
+                let [fast_output, slow_output] = outputs;
+                slow_output.hold_until(&barrier).probe();
+                fast_output
+                    .hold_until(&barrier)
+                    .inspect(move |batch| inspected.lock().unwrap().push(batch.time))
+                    .probe()
+            });
+
+            fast.insert(1);
+            fast.advance_to(1);
+            fast.flush();
+            for _ in 0..10 {
+                worker.step();
+            }
+            assert!(seen.lock().unwrap().is_empty());
+
+            slow.advance_to(1);
+            slow.flush();
+            worker.step_while(|| probe.less_than(&1));
+            assert_eq!(*seen.lock().unwrap(), vec![0]);
+        }
+    });
+}
@@ -16,7 +16,7 @@ fn test_utf8_decode_error() -> eyre::Result<()> {
 
     let invalid_utf8_bytes: &[u8] = &[0xC0, 0x80, 0xE0, 0x80, 0x80];
     assert_error_shown_for_reader_context(
-        &ReaderContext::KeyValue((Some(invalid_utf8_bytes.to_vec()), Some(invalid_utf8_bytes.to_vec()))),
+        &ReaderContext::KeyValue((Some(invalid_utf8_bytes.to_vec()), Some(invalid_utf8_bytes.into()))),
         Box::new(parser),
         "received plaintext message is not in utf-8 format: invalid utf-8 sequence of 1 bytes from index 0",
     );
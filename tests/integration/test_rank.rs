@@ -0,0 +1,59 @@
+// Copyright © 2024 Pathway
+
+#![allow(clippy::disallowed_methods)]
+
+use super::operator_test_utils::run_test;
+
+use differential_dataflow::operators::arrange::ArrangeByKey;
+
+use pathway_engine::engine::dataflow::operators::rank::{Rank, Ranks};
+
+fn ranks(row_number: usize, rank: usize, dense_rank: usize) -> Ranks {
+    Ranks {
+        row_number,
+        rank,
+        dense_rank,
+    }
+}
+
+#[test]
+fn test_rank_with_ties() {
+    let input = vec![
+        vec![
+            ((0, (10, 1)), 0, 1),
+            ((0, (20, 2)), 0, 1),
+            ((0, (10, 3)), 0, 1),
+            ((1, (5, 4)), 0, 1),
+        ],
+        vec![((0, (15, 5)), 1, 1)],
+    ];
+    let expected = vec![
+        vec![
+            ((0, (1, ranks(1, 1, 1))), 0, 1),
+            ((0, (3, ranks(2, 1, 1))), 0, 1),
+            ((0, (2, ranks(3, 3, 2))), 0, 1),
+            ((1, (4, ranks(1, 1, 1))), 0, 1),
+        ],
+        vec![
+            ((0, (2, ranks(3, 3, 2))), 1, -1),
+            ((0, (5, ranks(3, 3, 2))), 1, 1),
+            ((0, (2, ranks(4, 4, 3))), 1, 1),
+        ],
+    ];
+    run_test(input, expected, |coll| coll.rank(false).arrange_by_key());
+}
+
+#[test]
+fn test_rank_descending() {
+    let input = vec![vec![
+        ((0, (10, 1)), 0, 1),
+        ((0, (20, 2)), 0, 1),
+        ((0, (10, 3)), 0, 1),
+    ]];
+    let expected = vec![vec![
+        ((0, (2, ranks(1, 1, 1))), 0, 1),
+        ((0, (1, ranks(2, 2, 2))), 0, 1),
+        ((0, (3, ranks(3, 2, 2))), 0, 1),
+    ]];
+    run_test(input, expected, |coll| coll.rank(true).arrange_by_key());
+}
@@ -15,8 +15,9 @@ use pathway_engine::connectors::data_storage::ReaderBuilder;
 use pathway_engine::connectors::data_storage::{
     ConnectorMode, CsvFilesystemReader, FilesystemReader, ReadMethod,
 };
-use pathway_engine::connectors::SessionType;
+use pathway_engine::connectors::{OffsetDeduplicator, OffsetKey, OffsetValue, SessionType};
 use pathway_engine::engine::Value;
+use pathway_engine::persistence::frontier::OffsetAntichain;
 use pathway_engine::persistence::sync::SharedWorkersPersistenceCoordinator;
 use pathway_engine::persistence::tracker::SingleWorkerPersistentStorage;
 
@@ -418,3 +419,46 @@ fn test_json_recovery_from_empty_folder() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_deduplication_of_entries_read_again() {
+    let topic = Arc::new("topic".to_string());
+    let mut frontier = OffsetAntichain::new();
+    frontier.advance_offset(OffsetKey::Empty, OffsetValue::PythonEntrySequentialId(2));
+    frontier.advance_offset(
+        OffsetKey::Kafka(topic.clone(), 0),
+        OffsetValue::KafkaOffset(10),
+    );
+    let mut deduplicator = OffsetDeduplicator::new(frontier);
+
+    // The entries read before the restart are skipped
+    for sequential_id in 1..=2 {
+        assert!(deduplicator.is_duplicate(&(
+            OffsetKey::Empty,
+            OffsetValue::PythonEntrySequentialId(sequential_id)
+        )));
+    }
+    assert!(
+        !deduplicator.is_duplicate(&(OffsetKey::Empty, OffsetValue::PythonEntrySequentialId(3)))
+    );
+
+    // Each offset key is checked separately, only until the reader gets past the frontier
+    let partition = OffsetKey::Kafka(topic.clone(), 0);
+    assert!(deduplicator.is_duplicate(&(partition.clone(), OffsetValue::KafkaOffset(10))));
+    assert!(!deduplicator.is_duplicate(&(partition.clone(), OffsetValue::KafkaOffset(11))));
+    assert!(!deduplicator.is_duplicate(&(partition, OffsetValue::KafkaOffset(5))));
+    assert!(!deduplicator.is_duplicate(&(OffsetKey::Kafka(topic, 1), OffsetValue::KafkaOffset(0))));
+}
+
+#[test]
+fn test_file_positions_are_only_covered_within_the_same_file() {
+    let position = |path: &str, bytes_offset| OffsetValue::FilePosition {
+        total_entries_read: 0,
+        path: Arc::new(path.into()),
+        bytes_offset,
+    };
+    assert!(position("a.json", 10).is_covered_by(&position("a.json", 20)));
+    assert!(position("a.json", 20).is_covered_by(&position("a.json", 20)));
+    assert!(!position("a.json", 30).is_covered_by(&position("a.json", 20)));
+    assert!(!position("b.json", 10).is_covered_by(&position("a.json", 20)));
+}
@@ -0,0 +1,99 @@
+// Copyright © 2024 Pathway
+
+#![allow(clippy::disallowed_methods)]
+
+use super::operator_test_utils::run_test;
+
+use differential_dataflow::operators::arrange::ArrangeByKey;
+
+use pathway_engine::engine::dataflow::operators::session_window::{within_gap, SessionWindows};
+use pathway_engine::engine::{DateTimeNaive, Duration, Value};
+
+#[test]
+fn test_sessions_merged_by_late_entry() {
+    let input = vec![
+        vec![
+            ((0, (1, 10)), 0, 1),
+            ((0, (2, 11)), 0, 1),
+            ((0, (5, 12)), 0, 1),
+            ((1, (3, 13)), 0, 1),
+        ],
+        vec![((0, (3, 14)), 1, 1)],
+    ];
+    let expected = vec![
+        vec![
+            ((0, (10, (1, 2))), 0, 1),
+            ((0, (11, (1, 2))), 0, 1),
+            ((0, (12, (5, 5))), 0, 1),
+            ((1, (13, (3, 3))), 0, 1),
+        ],
+        vec![
+            ((0, (10, (1, 2))), 1, -1),
+            ((0, (11, (1, 2))), 1, -1),
+            ((0, (12, (5, 5))), 1, -1),
+            ((0, (10, (1, 5))), 1, 1),
+            ((0, (11, (1, 5))), 1, 1),
+            ((0, (12, (1, 5))), 1, 1),
+            ((0, (14, (1, 5))), 1, 1),
+        ],
+    ];
+    run_test(input, expected, |coll| {
+        coll.session_windows(|prev: &i32, next: &i32| next - prev < 3)
+            .arrange_by_key()
+    });
+}
+
+#[test]
+fn test_sessions_split_by_deletion() {
+    let input = vec![
+        vec![
+            ((0, (1, 10)), 0, 1),
+            ((0, (2, 11)), 0, 1),
+            ((0, (3, 12)), 0, 1),
+        ],
+        vec![((0, (2, 11)), 1, -1)],
+    ];
+    let expected = vec![
+        vec![
+            ((0, (10, (1, 3))), 0, 1),
+            ((0, (11, (1, 3))), 0, 1),
+            ((0, (12, (1, 3))), 0, 1),
+        ],
+        vec![
+            ((0, (10, (1, 3))), 1, -1),
+            ((0, (11, (1, 3))), 1, -1),
+            ((0, (12, (1, 3))), 1, -1),
+            ((0, (10, (1, 1))), 1, 1),
+            ((0, (12, (3, 3))), 1, 1),
+        ],
+    ];
+    run_test(input, expected, |coll| {
+        coll.session_windows(|prev: &i32, next: &i32| next - prev < 2)
+            .arrange_by_key()
+    });
+}
+
+#[test]
+fn test_within_gap() -> eyre::Result<()> {
+    assert!(within_gap(&Value::Int(1), &Value::Int(2), &Value::Int(2))?);
+    assert!(!within_gap(&Value::Int(1), &Value::Int(3), &Value::Int(2))?);
+    assert!(within_gap(
+        &Value::Float(1.0.into()),
+        &Value::Int(2),
+        &Value::Float(1.5.into())
+    )?);
+    let prev = Value::DateTimeNaive(DateTimeNaive::new(0));
+    let next = Value::DateTimeNaive(DateTimeNaive::new(1_000));
+    assert!(within_gap(
+        &prev,
+        &next,
+        &Value::Duration(Duration::new(1_001))
+    )?);
+    assert!(!within_gap(
+        &prev,
+        &next,
+        &Value::Duration(Duration::new(1_000))
+    )?);
+    assert!(within_gap(&prev, &next, &Value::Int(1)).is_err());
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// Copyright © 2024 Pathway
+
+use assert_matches::assert_matches;
+use ndarray::array;
+
+use pathway_engine::engine::value_encoding::{decode, encode};
+use pathway_engine::engine::{Error, SparseMatrix, Value};
+
+#[test]
+fn test_new_sorts_columns_within_rows() {
+    let matrix =
+        SparseMatrix::new((2, 3), vec![0, 2, 3], vec![2, 0, 1], vec![1.0, 2.0, 3.0]).unwrap();
+    assert_eq!(matrix.shape(), (2, 3));
+    assert_eq!(matrix.nnz(), 3);
+    assert_eq!(matrix.indices(), [0, 2, 1]);
+    assert_eq!(matrix.data(), [2.0, 1.0, 3.0]);
+    assert_eq!(matrix.row(0).collect::<Vec<_>>(), [(0, 2.0), (2, 1.0)]);
+    assert_eq!(matrix.to_dense(), array![[2.0, 0.0, 1.0], [0.0, 3.0, 0.0]]);
+}
+
+#[test]
+fn test_new_rejects_invalid_parts() {
+    assert_matches!(
+        SparseMatrix::new((2, 3), vec![0, 1], vec![0], vec![1.0]),
+        Err(Error::ValueError(_))
+    );
+    assert_matches!(
+        SparseMatrix::new((1, 3), vec![1, 1], vec![0], vec![1.0]),
+        Err(Error::ValueError(_))
+    );
+    assert_matches!(
+        SparseMatrix::new((2, 3), vec![0, 2, 1], vec![0], vec![1.0]),
+        Err(Error::ValueError(_))
+    );
+    assert_matches!(
+        SparseMatrix::new((1, 3), vec![0, 1], vec![0], vec![1.0, 2.0]),
+        Err(Error::ValueError(_))
+    );
+    assert_matches!(
+        SparseMatrix::new((1, 3), vec![0, 1], vec![3], vec![1.0]),
+        Err(Error::ValueError(_))
+    );
+}
+
+#[test]
+fn test_encoding_round_trip() {
+    let values = [
+        Value::from(SparseMatrix::new((0, 0), vec![0], vec![], vec![]).unwrap()),
+        Value::from(
+            SparseMatrix::new(
+                (3, 1_000_000),
+                vec![0, 2, 2, 3],
+                vec![7, 999_999, 0],
+                vec![0.5, -1.0, f64::INFINITY],
+            )
+            .unwrap(),
+        ),
+    ];
+    assert_eq!(decode(&encode(&values)).unwrap(), values);
+}
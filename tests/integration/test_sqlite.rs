@@ -1,7 +1,5 @@
 // Copyright © 2024 Pathway
 
-use std::sync::Arc;
-
 use rusqlite::Connection as SqliteConnection;
 use rusqlite::OpenFlags as SqliteOpenFlags;
 
@@ -58,7 +56,7 @@ fn test_sqlite_read_table() -> eyre::Result<()> {
                         Value::Int(2),
                         Value::String("Bread".into()),
                         Value::Float(0.75.into()),
-                        Value::Bytes(Arc::new([0, 0]))
+                        Value::Bytes(vec![0, 0].into())
                     ]
                 )),
                 EMPTY_OFFSET
@@ -120,7 +118,7 @@ fn test_sqlite_read_table_with_parser() -> eyre::Result<()> {
                     Value::Int(2),
                     Value::String("Bread".into()),
                     Value::Float(0.75.into()),
-                    Value::Bytes(Arc::new([0, 0]))
+                    Value::Bytes(vec![0, 0].into())
                 ]
             )),
         ]
@@ -1,9 +1,11 @@
 // Copyright © 2024 Pathway
 
+use super::helpers::create_persistence_config;
 use super::helpers::create_persistence_manager;
 use super::helpers::get_entries_in_receiver;
 
 use assert_matches::assert_matches;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
@@ -11,12 +13,22 @@ use std::sync::{mpsc, Arc};
 
 use tempfile::tempdir;
 
+use pathway_engine::connectors::data_storage::ReadError;
 use pathway_engine::connectors::snapshot::Event as SnapshotEvent;
 use pathway_engine::connectors::snapshot::{
-    LocalBinarySnapshotReader, LocalBinarySnapshotWriter, SnapshotReaderImpl, SnapshotWriter,
+    encrypt_local_snapshot, LocalBinarySnapshotReader, LocalBinarySnapshotWriter,
+    MockSnapshotReader, SnapshotCompression, SnapshotEncoding, SnapshotReader, SnapshotReaderImpl,
+    SnapshotWriter,
+};
+use pathway_engine::connectors::{Connector, Entry, PersistenceMode, RewindError};
+use pathway_engine::engine::{Key, Type, Value};
+use pathway_engine::persistence::dump::{
+    export_persisted_rows, import_persisted_rows, PersistedRows,
+};
+use pathway_engine::persistence::encryption::EncryptionKey;
+use pathway_engine::persistence::schema::{
+    ColumnMigration, InputSchema, PersistedColumn, SchemaColumn, SchemaMigrationError,
 };
-use pathway_engine::connectors::{Connector, Entry, PersistenceMode};
-use pathway_engine::engine::{Key, Value};
 use pathway_engine::persistence::PersistentId;
 
 fn get_snapshot_reader_entries(
@@ -37,19 +49,49 @@ fn get_snapshot_reader_entries(
 }
 
 fn read_persistent_buffer(chunks_root: &Path) -> Vec<SnapshotEvent> {
-    let snapshot_reader = LocalBinarySnapshotReader::new(chunks_root.to_path_buf())
+    read_encrypted_persistent_buffer(chunks_root, None)
+}
+
+fn read_encrypted_persistent_buffer(
+    chunks_root: &Path,
+    encryption_key: Option<EncryptionKey>,
+) -> Vec<SnapshotEvent> {
+    let snapshot_reader = LocalBinarySnapshotReader::new(chunks_root.to_path_buf(), encryption_key)
         .expect("Failed to create reader for test snapshot storage");
     get_snapshot_reader_entries(Box::new(snapshot_reader))
 }
 
+fn compressed(compression: SnapshotCompression) -> SnapshotEncoding {
+    SnapshotEncoding {
+        compression: Some(compression),
+        encryption_key: None,
+    }
+}
+
 fn read_persistent_buffer_full(
     chunks_root: &Path,
     persistent_id: PersistentId,
     persistence_mode: PersistenceMode,
 ) -> Vec<SnapshotEvent> {
+    read_migrated_persistent_buffer(chunks_root, persistent_id, persistence_mode, None)
+        .expect("Rewind should not fail")
+}
+
+fn read_migrated_persistent_buffer(
+    chunks_root: &Path,
+    persistent_id: PersistentId,
+    persistence_mode: PersistenceMode,
+    input_schema: Option<&InputSchema>,
+) -> Result<Vec<SnapshotEvent>, RewindError> {
     let (tracker, _global_tracker) = create_persistence_manager(chunks_root, false);
     let (sender, receiver) = mpsc::channel();
-    Connector::<u64>::rewind_from_disk_snapshot(persistent_id, &tracker, &sender, persistence_mode);
+    Connector::<u64>::rewind_from_disk_snapshot(
+        persistent_id,
+        &tracker,
+        &sender,
+        persistence_mode,
+        input_schema,
+    )?;
     let entries: Vec<Entry> = get_entries_in_receiver(receiver);
     let mut result = Vec::new();
     for entry in entries {
@@ -59,7 +101,7 @@ fn read_persistent_buffer_full(
             unreachable!("this part should be unreachable");
         }
     }
-    result
+    Ok(result)
 }
 
 #[test]
@@ -85,8 +127,9 @@ fn test_stream_snapshot_io() -> eyre::Result<()> {
     let test_storage_path = test_storage.path();
 
     {
-        let mut snapshot_writer = LocalBinarySnapshotWriter::new(test_storage_path)
-            .expect("Failed to create test snapshot storage");
+        let mut snapshot_writer =
+            LocalBinarySnapshotWriter::new(test_storage_path, SnapshotEncoding::default())
+                .expect("Failed to create test snapshot storage");
         snapshot_writer
             .write(&event1)
             .expect("Failed to write event into snapshot file");
@@ -103,6 +146,279 @@ fn test_stream_snapshot_io() -> eyre::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_stream_snapshot_io_compressed() -> eyre::Result<()> {
+    let events: Vec<_> = (0..1000)
+        .map(|i| SnapshotEvent::Insert(Key::random(), vec![Value::Int(i), Value::from("value")]))
+        .collect();
+
+    let test_storage = tempdir()?;
+
+    for compression in [
+        SnapshotCompression::Zstd { level: 19 },
+        SnapshotCompression::Lz4,
+    ] {
+        let test_storage_path = test_storage.path().join(format!("{compression:?}"));
+        // An uncompressed chunk written before the compression was enabled is still read
+        {
+            let mut snapshot_writer =
+                LocalBinarySnapshotWriter::new(&test_storage_path, SnapshotEncoding::default())?;
+            snapshot_writer.write(&events[0])?;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        {
+            let mut snapshot_writer =
+                LocalBinarySnapshotWriter::new(&test_storage_path, compressed(compression))?;
+            for event in &events[1..500] {
+                snapshot_writer.write(event)?;
+            }
+            futures::executor::block_on(snapshot_writer.flush())??;
+            for event in &events[500..] {
+                snapshot_writer.write(event)?;
+            }
+            futures::executor::block_on(snapshot_writer.flush())??;
+        }
+
+        assert_eq!(read_persistent_buffer(&test_storage_path), events);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_snapshot_unknown_codec_rejected() -> eyre::Result<()> {
+    let event = SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]);
+
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    {
+        let mut snapshot_writer = LocalBinarySnapshotWriter::new(
+            test_storage_path,
+            compressed(SnapshotCompression::Lz4),
+        )?;
+        snapshot_writer.write(&event)?;
+        futures::executor::block_on(snapshot_writer.flush())??;
+    }
+
+    // The codec is recorded in the second byte of the block header
+    let snapshot_path = std::fs::read_dir(test_storage_path)?
+        .next()
+        .unwrap()?
+        .path();
+    let mut contents = std::fs::read(&snapshot_path)?;
+    contents[1] = 0x7f;
+    std::fs::write(&snapshot_path, contents)?;
+
+    let mut snapshot_reader =
+        LocalBinarySnapshotReader::new(test_storage_path.to_path_buf(), None)?;
+    assert_matches!(snapshot_reader.read(), Err(_));
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_snapshot_truncate_compressed() -> eyre::Result<()> {
+    let event1 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]);
+    let event2 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(2)]);
+    let event3 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(3)]);
+
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    {
+        let mut snapshot_writer = LocalBinarySnapshotWriter::new(
+            test_storage_path,
+            compressed(SnapshotCompression::Zstd { level: 3 }),
+        )?;
+        snapshot_writer.write(&event1)?;
+        snapshot_writer.write(&SnapshotEvent::AdvanceTime(2))?;
+        snapshot_writer.write(&event2)?;
+        snapshot_writer.write(&SnapshotEvent::AdvanceTime(4))?;
+        snapshot_writer.write(&event3)?;
+        futures::executor::block_on(snapshot_writer.flush())??;
+    }
+
+    let mut snapshot_reader = SnapshotReader::new(
+        Box::new(LocalBinarySnapshotReader::new(
+            test_storage_path.to_path_buf(),
+            None,
+        )?),
+        4,
+        None,
+    )?;
+    while snapshot_reader.read()? != SnapshotEvent::Finished {}
+
+    assert_eq!(
+        read_persistent_buffer(test_storage_path),
+        vec![
+            event1,
+            SnapshotEvent::AdvanceTime(2),
+            event2,
+            SnapshotEvent::AdvanceTime(4)
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_snapshot_encrypted() -> eyre::Result<()> {
+    let events: Vec<_> = (0..100)
+        .map(|i| SnapshotEvent::Insert(Key::random(), vec![Value::Int(i), Value::from("secret")]))
+        .collect();
+    let key = EncryptionKey::new(&[7; 32])?;
+
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    for compression in [
+        None,
+        Some(SnapshotCompression::Zstd { level: 3 }),
+        Some(SnapshotCompression::Lz4),
+    ] {
+        let storage_path = test_storage_path.join(format!("{compression:?}"));
+        {
+            let mut snapshot_writer = LocalBinarySnapshotWriter::new(
+                &storage_path,
+                SnapshotEncoding {
+                    compression,
+                    encryption_key: Some(key.clone()),
+                },
+            )?;
+            for event in &events {
+                snapshot_writer.write(event)?;
+            }
+            futures::executor::block_on(snapshot_writer.flush())??;
+        }
+
+        let contents = std::fs::read(std::fs::read_dir(&storage_path)?.next().unwrap()?.path())?;
+        assert!(!contents.windows(6).any(|window| window == b"secret"));
+        assert_eq!(
+            read_encrypted_persistent_buffer(&storage_path, Some(key.clone())),
+            events
+        );
+
+        for wrong_key in [None, Some(EncryptionKey::new(&[8; 32])?)] {
+            let mut snapshot_reader =
+                LocalBinarySnapshotReader::new(storage_path.clone(), wrong_key)?;
+            assert_matches!(snapshot_reader.read(), Err(_));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_snapshot_truncate_encrypted() -> eyre::Result<()> {
+    let event1 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]);
+    let event2 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(2)]);
+    let key = EncryptionKey::new(&[7; 32])?;
+
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    {
+        let mut snapshot_writer = LocalBinarySnapshotWriter::new(
+            test_storage_path,
+            SnapshotEncoding {
+                compression: None,
+                encryption_key: Some(key.clone()),
+            },
+        )?;
+        snapshot_writer.write(&event1)?;
+        snapshot_writer.write(&SnapshotEvent::AdvanceTime(2))?;
+        snapshot_writer.write(&event2)?;
+        futures::executor::block_on(snapshot_writer.flush())??;
+    }
+
+    let mut snapshot_reader = SnapshotReader::new(
+        Box::new(LocalBinarySnapshotReader::new(
+            test_storage_path.to_path_buf(),
+            Some(key.clone()),
+        )?),
+        2,
+        None,
+    )?;
+    while snapshot_reader.read()? != SnapshotEvent::Finished {}
+
+    assert_eq!(
+        read_encrypted_persistent_buffer(test_storage_path, Some(key)),
+        vec![event1, SnapshotEvent::AdvanceTime(2)]
+    );
+    assert_matches!(
+        LocalBinarySnapshotReader::new(test_storage_path.to_path_buf(), None)?.read(),
+        Err(_)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_snapshot_encryption_migration() -> eyre::Result<()> {
+    let events: Vec<_> = (0..100)
+        .map(|i| SnapshotEvent::Insert(Key::random(), vec![Value::Int(i), Value::from("secret")]))
+        .collect();
+    let key = EncryptionKey::new(&[7; 32])?;
+    let encoding = SnapshotEncoding {
+        compression: Some(SnapshotCompression::Lz4),
+        encryption_key: Some(key.clone()),
+    };
+
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    {
+        let mut snapshot_writer =
+            LocalBinarySnapshotWriter::new(test_storage_path, SnapshotEncoding::default())?;
+        for event in &events[..50] {
+            snapshot_writer.write(event)?;
+        }
+        futures::executor::block_on(snapshot_writer.flush())??;
+    }
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    {
+        let mut snapshot_writer =
+            LocalBinarySnapshotWriter::new(test_storage_path, encoding.clone())?;
+        for event in &events[50..] {
+            snapshot_writer.write(event)?;
+        }
+        futures::executor::block_on(snapshot_writer.flush())??;
+    }
+
+    // The chunk written without encryption is rejected
+    let mut snapshot_reader =
+        LocalBinarySnapshotReader::new(test_storage_path.to_path_buf(), Some(key.clone()))?;
+    assert_matches!(snapshot_reader.read(), Err(_));
+
+    // until it's migrated, while the encrypted one stays as it was
+    let mut chunk_paths: Vec<_> = std::fs::read_dir(test_storage_path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    chunk_paths.sort();
+    let encrypted_chunk = std::fs::read(&chunk_paths[1])?;
+    encrypt_local_snapshot(test_storage_path, &encoding)?;
+    assert_eq!(std::fs::read(&chunk_paths[1])?, encrypted_chunk);
+    assert!(!std::fs::read(&chunk_paths[0])?
+        .windows(6)
+        .any(|window| window == b"secret"));
+    assert_eq!(
+        read_encrypted_persistent_buffer(test_storage_path, Some(key.clone())),
+        events
+    );
+
+    // The blocks are bound to their chunks, so they can't be moved to another one
+    std::fs::copy(&chunk_paths[0], &chunk_paths[1])?;
+    let mut snapshot_reader =
+        LocalBinarySnapshotReader::new(test_storage_path.to_path_buf(), Some(key))?;
+    for _ in 0..50 {
+        snapshot_reader.read()?;
+    }
+    assert_matches!(snapshot_reader.read(), Err(_));
+
+    Ok(())
+}
+
 #[test]
 fn test_stream_snapshot_io_broken_format() -> eyre::Result<()> {
     let test_storage = tempdir()?;
@@ -116,7 +432,7 @@ fn test_stream_snapshot_io_broken_format() -> eyre::Result<()> {
             .expect("Failed to write");
     }
 
-    let mut snapshot_reader = LocalBinarySnapshotReader::new(test_storage_path.to_path_buf())
+    let mut snapshot_reader = LocalBinarySnapshotReader::new(test_storage_path.to_path_buf(), None)
         .expect("Failed to create reader for test snapshot storage");
     let entry = snapshot_reader.read();
     assert_matches!(entry, Err(_));
@@ -129,7 +445,7 @@ fn test_stream_empty() -> eyre::Result<()> {
     let test_storage = tempdir()?;
     let test_storage_path = test_storage.path();
 
-    let mut snapshot_reader = LocalBinarySnapshotReader::new(test_storage_path.to_path_buf())
+    let mut snapshot_reader = LocalBinarySnapshotReader::new(test_storage_path.to_path_buf(), None)
         .expect("Failed to create reader for test snapshot storage");
     let entry = snapshot_reader.read();
     assert_matches!(entry, Ok(SnapshotEvent::Finished));
@@ -150,7 +466,7 @@ fn test_stream_not_from_dir() -> eyre::Result<()> {
             .expect("Failed to write");
     }
 
-    let snapshot_reader = LocalBinarySnapshotReader::new(test_storage_path.join("1"));
+    let snapshot_reader = LocalBinarySnapshotReader::new(test_storage_path.join("1"), None);
     assert!(snapshot_reader.is_err());
 
     Ok(())
@@ -221,6 +537,48 @@ fn test_buffer_dont_read_beyond_threshold_time() -> eyre::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_buffer_replay_until() -> eyre::Result<()> {
+    let event1 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]);
+    let event2 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(2)]);
+    let event3 = SnapshotEvent::Insert(Key::random(), vec![Value::Int(3)]);
+    let events = vec![
+        event1.clone(),
+        SnapshotEvent::AdvanceTime(2),
+        event2.clone(),
+        SnapshotEvent::AdvanceTime(4),
+        event3.clone(),
+        SnapshotEvent::AdvanceTime(6),
+    ];
+
+    let read_until = |replay_until| -> eyre::Result<Vec<SnapshotEvent>> {
+        let mut reader = SnapshotReader::new(
+            Box::new(MockSnapshotReader::new(events.clone())),
+            10,
+            Some(replay_until),
+        )?;
+        let mut entries = Vec::new();
+        loop {
+            match reader.read()? {
+                SnapshotEvent::Finished => break,
+                SnapshotEvent::AdvanceTime(_) => {}
+                entry => entries.push(entry),
+            }
+        }
+        Ok(entries)
+    };
+
+    assert_eq!(read_until(0)?, vec![event1.clone()]);
+    assert_eq!(read_until(3)?, vec![event1.clone(), event2.clone()]);
+    assert_eq!(
+        read_until(4)?,
+        vec![event1.clone(), event2.clone(), event3.clone()]
+    );
+    assert_eq!(read_until(100)?, vec![event1, event2, event3]);
+
+    Ok(())
+}
+
 #[test]
 fn test_buffer_scenario_several_writes() -> eyre::Result<()> {
     let test_storage = tempdir()?;
@@ -347,3 +705,301 @@ fn test_stream_snapshot_speedrun() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_stream_snapshot_corrupted_block_fails_rewind() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let (tracker, global_tracker) = create_persistence_manager(test_storage_path, true);
+    let buffer = tracker
+        .lock()
+        .unwrap()
+        .create_snapshot_writer(42)
+        .expect("Failed to create snapshot writer");
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Insert(Key::random(), vec![Value::Int(1)]))?;
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::AdvanceTime(2))?;
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(3));
+
+    // The last byte of the chunk is a part of the payload of its only block
+    let chunk_path = std::fs::read_dir(test_storage_path.join("streams/0/42"))?
+        .next()
+        .unwrap()?
+        .path();
+    let mut contents = std::fs::read(&chunk_path)?;
+    *contents.last_mut().unwrap() ^= 0x01;
+    std::fs::write(&chunk_path, contents)?;
+
+    assert_matches!(
+        read_migrated_persistent_buffer(test_storage_path, 42, PersistenceMode::Batch, None),
+        Err(RewindError::CorruptedSnapshot {
+            persistent_id: 42,
+            entries_read: 0,
+            error: ReadError::Io(e),
+        }) if e.kind() == std::io::ErrorKind::InvalidData
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_snapshot_schema_evolution() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let (tracker, global_tracker) = create_persistence_manager(test_storage_path, true);
+    let buffer = tracker
+        .lock()
+        .unwrap()
+        .create_snapshot_writer(42)
+        .expect("Failed to create snapshot writer");
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+
+    // A row persisted before the schemas were saved, and a row with a known schema
+    let key1 = Key::random();
+    let key2 = Key::random();
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Insert(key1, vec![Value::Int(1)]))?;
+    buffer.lock().unwrap().write(&SnapshotEvent::Schema(vec![
+        PersistedColumn {
+            name: "price".to_string(),
+            type_: Type::Int,
+        },
+        PersistedColumn {
+            name: "name".to_string(),
+            type_: Type::String,
+        },
+    ]))?;
+    buffer.lock().unwrap().write(&SnapshotEvent::Insert(
+        key2,
+        vec![Value::Int(2), Value::String("abc".into())],
+    ))?;
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::AdvanceTime(2))?;
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(3));
+
+    let column = |name: &str, type_, default| SchemaColumn {
+        name: name.to_string(),
+        type_,
+        default,
+    };
+    let input_schema = InputSchema::new(
+        vec![
+            column("price", Type::Float, None),
+            column("title", Type::String, None),
+            column("quantity", Type::Int, Some(Value::Int(1))),
+            column("note", Type::String, None),
+        ],
+        HashMap::from([(
+            "title".to_string(),
+            ColumnMigration {
+                renamed_from: Some("name".to_string()),
+                fill_value: None,
+            },
+        )]),
+    );
+    assert_eq!(
+        read_migrated_persistent_buffer(
+            test_storage_path,
+            42,
+            PersistenceMode::Batch,
+            Some(&input_schema),
+        )?,
+        vec![
+            SnapshotEvent::Insert(
+                key1,
+                vec![
+                    Value::Float(1.0.into()),
+                    Value::None,
+                    Value::Int(1),
+                    Value::None
+                ]
+            ),
+            SnapshotEvent::Insert(
+                key2,
+                vec![
+                    Value::Float(2.0.into()),
+                    Value::String("abc".into()),
+                    Value::Int(1),
+                    Value::None
+                ]
+            ),
+        ]
+    );
+
+    let incompatible_schema = InputSchema::new(
+        vec![
+            column("price", Type::Int, None),
+            column("name", Type::Bool, None),
+        ],
+        HashMap::new(),
+    );
+    assert_matches!(
+        read_migrated_persistent_buffer(
+            test_storage_path,
+            42,
+            PersistenceMode::Batch,
+            Some(&incompatible_schema),
+        ),
+        Err(RewindError::SchemaMigration(SchemaMigrationError::IncompatibleType(name, Type::String, Type::Bool))) if name == "name"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_export_and_import_persisted_rows() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let (tracker, global_tracker) = create_persistence_manager(test_storage_path, true);
+    let buffer = tracker
+        .lock()
+        .unwrap()
+        .create_snapshot_writer(42)
+        .expect("Failed to create snapshot writer");
+    let mock_sink_id = tracker.lock().unwrap().register_sink();
+
+    let key1 = Key::random();
+    let key2 = Key::random();
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Insert(key1, vec![Value::Int(1)]))?;
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Schema(vec![PersistedColumn {
+            name: "price".to_string(),
+            type_: Type::Int,
+        }]))?;
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Insert(key2, vec![Value::Int(2)]))?;
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::Delete(key1, vec![Value::Int(1)]))?;
+    buffer
+        .lock()
+        .unwrap()
+        .write(&SnapshotEvent::AdvanceTime(2))?;
+    global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, mock_sink_id, Some(3));
+
+    let config = create_persistence_config(test_storage_path);
+    let persisted_rows = export_persisted_rows(config.clone(), 42)?;
+    assert_eq!(
+        persisted_rows.columns,
+        vec![PersistedColumn {
+            name: "price".to_string(),
+            type_: Type::Int,
+        }]
+    );
+    assert_eq!(persisted_rows.rows, vec![(key2, vec![Value::Int(2)])]);
+    // Exporting doesn't modify the persisted state
+    assert_eq!(
+        export_persisted_rows(config.clone(), 42)?.rows,
+        persisted_rows.rows
+    );
+
+    let key3 = Key::random();
+    import_persisted_rows(
+        &config,
+        42,
+        PersistedRows {
+            columns: persisted_rows.columns.clone(),
+            rows: vec![(key3, vec![Value::Int(3)])],
+        },
+    )?;
+    assert_eq!(
+        read_persistent_buffer_full(test_storage_path, 42, PersistenceMode::Batch),
+        vec![SnapshotEvent::Insert(key3, vec![Value::Int(3)])]
+    );
+
+    // The rows imported to a new storage are read as well
+    let new_storage = tempdir()?;
+    import_persisted_rows(
+        &create_persistence_config(new_storage.path()),
+        42,
+        persisted_rows,
+    )?;
+    assert_eq!(
+        read_persistent_buffer_full(new_storage.path(), 42, PersistenceMode::Batch),
+        vec![SnapshotEvent::Insert(key2, vec![Value::Int(2)])]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_snapshot_legacy_values() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    // The events stored before the compact encoding of the values, as bincode serialized
+    // them: the variant indices as `u32` and the lengths as `u64`
+    let key = Key::random();
+    let mut legacy_events = Vec::new();
+    legacy_events.extend_from_slice(&0_u32.to_le_bytes());
+    legacy_events.extend_from_slice(&key.0.to_le_bytes());
+    legacy_events.extend_from_slice(&3_u64.to_le_bytes());
+    legacy_events.extend_from_slice(&2_u32.to_le_bytes());
+    legacy_events.extend_from_slice(&5_i64.to_le_bytes());
+    legacy_events.extend_from_slice(&5_u32.to_le_bytes());
+    legacy_events.extend_from_slice(&1_u64.to_le_bytes());
+    legacy_events.extend_from_slice(b"a");
+    legacy_events.extend_from_slice(&7_u32.to_le_bytes());
+    legacy_events.extend_from_slice(&1_u64.to_le_bytes());
+    legacy_events.extend_from_slice(&0_u32.to_le_bytes());
+    legacy_events.extend_from_slice(&2_u32.to_le_bytes());
+    legacy_events.extend_from_slice(&key.0.to_le_bytes());
+    legacy_events.push(0);
+    File::create(test_storage_path.join("1"))?.write_all(&legacy_events)?;
+
+    let event = SnapshotEvent::Insert(key, vec![Value::from("a"), Value::from("a")]);
+    {
+        let mut snapshot_writer =
+            LocalBinarySnapshotWriter::new(test_storage_path, SnapshotEncoding::default())?;
+        snapshot_writer.write(&event)?;
+    }
+
+    assert_eq!(
+        read_persistent_buffer(test_storage_path),
+        vec![
+            SnapshotEvent::Insert(
+                key,
+                vec![
+                    Value::Int(5),
+                    Value::from("a"),
+                    Value::Tuple(Arc::new([Value::None]))
+                ]
+            ),
+            SnapshotEvent::Upsert(key, None),
+            event,
+        ]
+    );
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 // Copyright © 2024 Pathway
 
-use pathway_engine::engine::Duration;
+use pathway_engine::engine::time::{CalendarUnit, DateTime};
+use pathway_engine::engine::{DateTimeNaive, DateTimeUtc, Duration};
 
 #[test]
 fn test_duration_1() -> eyre::Result<()> {
@@ -37,3 +38,349 @@ fn test_duration_zero_sec() -> eyre::Result<()> {
     assert_eq!(d.to_string(), "-13d -20h -43m");
     Ok(())
 }
+
+#[test]
+fn test_calendar_accessors() -> eyre::Result<()> {
+    let t = DateTimeNaive::strptime("2021-01-01T10:13:00", "%Y-%m-%dT%H:%M:%S")?;
+    assert_eq!(t.iso_week(), 53);
+    assert_eq!(t.iso_year(), 2020);
+    assert_eq!(t.quarter(), 1);
+    assert_eq!(t.day_of_year(), 1);
+
+    let t = DateTimeNaive::strptime("2024-12-31T12:00:00", "%Y-%m-%dT%H:%M:%S")?;
+    assert_eq!(t.iso_week(), 1);
+    assert_eq!(t.iso_year(), 2025);
+    assert_eq!(t.quarter(), 4);
+    assert_eq!(t.day_of_year(), 366);
+
+    let t = DateTimeUtc::strptime("2023-04-01T00:30:00+01:00", "%Y-%m-%dT%H:%M:%S%z")?;
+    assert_eq!(t.quarter(), 1);
+    assert_eq!(t.day_of_year(), 90);
+    assert_eq!(t.iso_week(), 13);
+    Ok(())
+}
+
+#[test]
+fn test_excel_serial() -> eyre::Result<()> {
+    let format = "%Y-%m-%dT%H:%M:%S";
+    let t = DateTimeNaive::from_excel_serial(45000.5)?;
+    assert_eq!(t, DateTimeNaive::strptime("2023-03-15T12:00:00", format)?);
+    assert_eq!(t.excel_serial(), 45000.5);
+
+    let t = DateTimeNaive::from_excel_serial(1.0)?;
+    assert_eq!(t, DateTimeNaive::strptime("1900-01-01T00:00:00", format)?);
+    assert_eq!(t.excel_serial(), 1.0);
+
+    let t = DateTimeNaive::from_excel_serial(59.0)?;
+    assert_eq!(t, DateTimeNaive::strptime("1900-02-28T00:00:00", format)?);
+    assert_eq!(t.excel_serial(), 59.0);
+
+    let t = DateTimeNaive::from_excel_serial(59.5)?;
+    assert_eq!(t, DateTimeNaive::strptime("1900-02-28T12:00:00", format)?);
+    assert_eq!(t.excel_serial(), 59.5);
+
+    let t = DateTimeNaive::from_excel_serial(61.0)?;
+    assert_eq!(t, DateTimeNaive::strptime("1900-03-01T00:00:00", format)?);
+    assert_eq!(t.excel_serial(), 61.0);
+
+    assert!(DateTimeNaive::from_excel_serial(60.0).is_err());
+    assert!(DateTimeNaive::from_excel_serial(-1.0).is_err());
+    assert!(DateTimeNaive::from_excel_serial(f64::NAN).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_julian_day() -> eyre::Result<()> {
+    let t = DateTimeNaive::from_julian_day(2_451_545.0)?;
+    assert_eq!(
+        t,
+        DateTimeNaive::strptime("2000-01-01T12:00:00", "%Y-%m-%dT%H:%M:%S")?
+    );
+    assert_eq!(t.julian_day(), 2_451_545.0);
+    assert_eq!(DateTimeUtc::new(0).julian_day(), 2_440_587.5);
+    assert!(DateTimeNaive::from_julian_day(1e12).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_strptime_leap_second() -> eyre::Result<()> {
+    let format = "%Y-%m-%dT%H:%M:%S%.f";
+    let leap = DateTimeNaive::strptime("2016-12-31T23:59:60.5", format)?;
+    let before = DateTimeNaive::strptime("2016-12-31T23:59:59.999999999", format)?;
+    let after = DateTimeNaive::strptime("2017-01-01T00:00:00", format)?;
+    assert_eq!(leap, before);
+    assert!(leap < after);
+
+    let leap = DateTimeUtc::strptime("2016-12-31T23:59:60+0000", "%Y-%m-%dT%H:%M:%S%z")?;
+    let before = DateTimeUtc::strptime(
+        "2016-12-31T23:59:59.999999999+0000",
+        "%Y-%m-%dT%H:%M:%S%.f%z",
+    )?;
+    assert_eq!(leap, before);
+    assert_eq!(leap.second(), 59);
+    Ok(())
+}
+
+#[test]
+fn test_calendar_units() -> eyre::Result<()> {
+    let format = "%Y-%m-%dT%H:%M:%S";
+    let date = DateTimeNaive::strptime("2023-11-15T10:00:00", format)?;
+    let expected = [
+        (
+            CalendarUnit::Month,
+            "2023-11-01T00:00:00",
+            "2023-11-01T00:00:00",
+        ),
+        (
+            CalendarUnit::Quarter,
+            "2023-10-01T00:00:00",
+            "2023-10-01T00:00:00",
+        ),
+        (
+            CalendarUnit::Year,
+            "2023-01-01T00:00:00",
+            "2024-01-01T00:00:00",
+        ),
+    ];
+    for (unit, floor, round) in expected {
+        assert_eq!(
+            date.truncate_to_calendar_unit(unit)?,
+            DateTimeNaive::strptime(floor, format)?
+        );
+        assert_eq!(
+            date.round_to_calendar_unit(unit)?,
+            DateTimeNaive::strptime(round, format)?
+        );
+    }
+
+    let end_of_january = DateTimeNaive::strptime("2024-01-31T12:00:00", format)?;
+    assert_eq!(
+        end_of_january.add_calendar_units(CalendarUnit::Month, 1)?,
+        DateTimeNaive::strptime("2024-02-29T12:00:00", format)?
+    );
+    assert_eq!(
+        end_of_january.add_calendar_units(CalendarUnit::Quarter, -1)?,
+        DateTimeNaive::strptime("2023-10-31T12:00:00", format)?
+    );
+
+    let date = DateTimeUtc::strptime("1969-12-31T23:00:00+0100", "%Y-%m-%dT%H:%M:%S%z")?;
+    assert_eq!(
+        date.truncate_to_calendar_unit(CalendarUnit::Year)?,
+        DateTimeUtc::strptime("1969-01-01T00:00:00+0000", "%Y-%m-%dT%H:%M:%S%z")?
+    );
+    assert!("week".parse::<CalendarUnit>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_calendar_units_in_timezone() -> eyre::Result<()> {
+    let format = "%Y-%m-%dT%H:%M:%S%z";
+    let expected = [
+        // DST starts, the day is 23 hours long
+        (
+            CalendarUnit::Day,
+            "Europe/Warsaw",
+            "2023-03-26T12:00:00+0000",
+            "2023-03-25T23:00:00+0000",
+            "2023-03-26T22:00:00+0000",
+        ),
+        // DST ends, the day is 25 hours long
+        (
+            CalendarUnit::Day,
+            "Europe/Warsaw",
+            "2023-10-29T12:00:00+0000",
+            "2023-10-28T22:00:00+0000",
+            "2023-10-29T23:00:00+0000",
+        ),
+        // the first and the second 02:30 of the day are in different hours
+        (
+            CalendarUnit::Hour,
+            "Europe/Warsaw",
+            "2023-10-29T00:30:00+0000",
+            "2023-10-29T00:00:00+0000",
+            "2023-10-29T01:00:00+0000",
+        ),
+        (
+            CalendarUnit::Hour,
+            "Europe/Warsaw",
+            "2023-10-29T01:30:00+0000",
+            "2023-10-29T01:00:00+0000",
+            "2023-10-29T02:00:00+0000",
+        ),
+        (
+            CalendarUnit::Hour,
+            "Asia/Kolkata",
+            "2023-01-01T10:00:00+0000",
+            "2023-01-01T09:30:00+0000",
+            "2023-01-01T10:30:00+0000",
+        ),
+        (
+            CalendarUnit::Month,
+            "America/New_York",
+            "2023-03-01T03:00:00+0000",
+            "2023-02-01T05:00:00+0000",
+            "2023-03-01T05:00:00+0000",
+        ),
+        // midnight does not exist, the day starts at 01:00
+        (
+            CalendarUnit::Day,
+            "America/Havana",
+            "2023-03-12T12:00:00+0000",
+            "2023-03-12T05:00:00+0000",
+            "2023-03-13T04:00:00+0000",
+        ),
+    ];
+    for (unit, timezone, date, start, end) in expected {
+        let date = DateTimeUtc::strptime(date, format)?;
+        let start = DateTimeUtc::strptime(start, format)?;
+        let end = DateTimeUtc::strptime(end, format)?;
+        assert_eq!(
+            date.truncate_to_calendar_unit_in_timezone(unit, timezone)?,
+            start
+        );
+        assert_eq!(
+            start
+                .add_calendar_units_in_timezone(unit, 1, timezone)?
+                .truncate_to_calendar_unit_in_timezone(unit, timezone)?,
+            end
+        );
+        let middle = start.checked_add((end.checked_duration_since(start)? / 2)?)?;
+        assert_eq!(
+            middle.round_to_calendar_unit_in_timezone(unit, timezone)?,
+            end
+        );
+        assert_eq!(
+            middle
+                .checked_sub(Duration::new(1))?
+                .round_to_calendar_unit_in_timezone(unit, timezone)?,
+            start
+        );
+    }
+
+    // the local time is kept
+    let date = DateTimeUtc::strptime("2023-03-12T05:00:00+0000", format)?;
+    assert_eq!(
+        date.add_calendar_units_in_timezone(CalendarUnit::Day, 1, "America/Havana")?,
+        DateTimeUtc::strptime("2023-03-13T05:00:00+0000", format)?
+    );
+    let date = DateTimeUtc::strptime("2023-03-12T12:00:00+0000", format)?;
+    assert_eq!(
+        date.add_calendar_units_in_timezone(CalendarUnit::Day, -1, "America/Havana")?,
+        DateTimeUtc::strptime("2023-03-11T13:00:00+0000", format)?
+    );
+    assert!(date
+        .truncate_to_calendar_unit_in_timezone(CalendarUnit::Day, "Mars/Olympus_Mons")
+        .is_err());
+
+    // local times in a gap are moved past it, ambiguous ones resolve to the later instant
+    let naive_format = "%Y-%m-%dT%H:%M:%S";
+    assert_eq!(
+        DateTimeNaive::strptime("2023-03-26T02:30:00", naive_format)?
+            .to_utc_from_timezone("Europe/Warsaw")?,
+        DateTimeUtc::strptime("2023-03-26T01:00:00+0000", format)?
+    );
+    assert_eq!(
+        DateTimeNaive::strptime("2023-10-29T02:30:00", naive_format)?
+            .to_utc_from_timezone("Europe/Warsaw")?,
+        DateTimeUtc::strptime("2023-10-29T01:30:00+0000", format)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rfc_parsers() -> eyre::Result<()> {
+    let expected = DateTimeUtc::strptime("2023-03-25T09:13:00+0000", "%Y-%m-%dT%H:%M:%S%z")?;
+    assert_eq!(
+        DateTimeUtc::parse_rfc3339("2023-03-25T10:13:00+01:00")?,
+        expected
+    );
+    assert_eq!(
+        DateTimeUtc::parse_rfc3339("2023-03-25T09:13:00Z")?,
+        expected
+    );
+    assert_eq!(
+        DateTimeUtc::parse_rfc2822("Sat, 25 Mar 2023 10:13:00 +0100")?,
+        expected
+    );
+    assert_eq!(
+        DateTimeUtc::parse_rfc2822("25 Mar 2023 09:13:00 GMT")?,
+        expected
+    );
+    assert!(DateTimeUtc::parse_rfc3339("2023-03-25T10:13:00").is_err());
+    assert!(DateTimeUtc::parse_rfc2822("2023-03-25T10:13:00+01:00").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_utc_in_timezone() -> eyre::Result<()> {
+    let format = "%Y-%m-%dT%H:%M:%S%z";
+    let winter = DateTimeUtc::strptime("2023-03-26T00:59:59+0000", format)?;
+    let summer = DateTimeUtc::strptime("2023-03-26T01:00:00+0000", format)?;
+    assert_eq!(winter.offset_seconds("Europe/Warsaw")?, 3600);
+    assert_eq!(summer.offset_seconds("Europe/Warsaw")?, 7200);
+    assert_eq!(summer.offset_seconds("America/New_York")?, -4 * 3600);
+    assert_eq!(
+        summer.strftime_in_timezone(format, "Europe/Warsaw")?,
+        "2023-03-26T03:00:00+0200"
+    );
+    assert_eq!(
+        summer.strftime_in_timezone(format, "UTC")?,
+        summer.strftime(format)
+    );
+    assert!(summer.offset_seconds("Mars/Olympus_Mons").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_checked_arithmetic() -> eyre::Result<()> {
+    let max = DateTimeNaive::new(i64::MAX);
+    let min = DateTimeUtc::new(i64::MIN);
+    let one = Duration::new(1);
+    assert!(max.checked_add(one).is_err());
+    assert!(min.checked_sub(one).is_err());
+    assert_eq!(max.checked_sub(one)?, DateTimeNaive::new(i64::MAX - 1));
+    assert_eq!(max.saturating_add(one), max);
+    assert_eq!(min.saturating_sub(one), min);
+    assert!(max.checked_duration_since(DateTimeNaive::new(-1)).is_err());
+
+    let duration = Duration::new(i64::MAX / 2 + 1);
+    assert!(duration.checked_mul(2).is_err());
+    assert!(duration.checked_add(duration).is_err());
+    assert!(Duration::new(i64::MIN).checked_neg().is_err());
+    assert!(duration.checked_mul_f64(2.5).is_err());
+    assert!(duration.checked_mul_f64(f64::NAN).is_err());
+    assert_eq!(
+        duration.checked_mul_f64(0.5)?,
+        Duration::new(i64::MAX / 4 + 1)
+    );
+    assert_eq!(duration.saturating_mul(-3), Duration::new(i64::MIN));
+    assert!((Duration::new(i64::MIN) / -1).is_err());
+    assert!((Duration::new(i64::MIN) / Duration::new(-1)).is_err());
+    assert_eq!(
+        (Duration::new(i64::MIN) % Duration::new(-1))?,
+        Duration::new(0)
+    );
+
+    let hour = Duration::new(3_600_000_000_000);
+    assert!(max.round(hour).is_err());
+    assert_eq!(max.truncate(hour)?.timestamp() % hour.nanoseconds(), 0);
+    assert!(min.truncate(hour).is_err());
+    assert!(DateTimeUtc::new(i64::MIN + 1).round(hour).is_err());
+    assert!(max.round(Duration::new(0)).is_err());
+    assert_eq!(
+        DateTimeUtc::new(-1_800_000_000_000).round(hour)?,
+        DateTimeUtc::new(0)
+    );
+    assert_eq!(
+        DateTimeUtc::new(-1_800_000_000_001).truncate(hour)?,
+        DateTimeUtc::new(-3_600_000_000_000)
+    );
+    assert!(DateTimeNaive::from_timestamp(i64::MAX / 1000, "s").is_err());
+    assert!(DateTimeNaive::from_timestamp_f64(1e20, "s").is_err());
+    assert!(DateTimeNaive::from_timestamp_f64(f64::INFINITY, "ms").is_err());
+    assert_eq!(
+        DateTimeNaive::from_timestamp(-2, "us")?,
+        DateTimeNaive::new(-2000)
+    );
+    Ok(())
+}
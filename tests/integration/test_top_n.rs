@@ -0,0 +1,105 @@
+// Copyright © 2024 Pathway
+
+#![allow(clippy::disallowed_methods)]
+
+use super::operator_test_utils::run_test;
+
+use differential_dataflow::operators::arrange::ArrangeByKey;
+
+use pathway_engine::engine::dataflow::operators::rank::Rank;
+use pathway_engine::engine::dataflow::operators::top_n::TopN;
+
+#[test]
+fn test_top_n_updates_positions() {
+    let input = vec![
+        vec![
+            ((0, (5, 1)), 0, 1),
+            ((0, (3, 2)), 0, 1),
+            ((0, (9, 3)), 0, 1),
+            ((1, (1, 4)), 0, 1),
+        ],
+        vec![((0, (4, 5)), 1, 1)],
+        vec![((0, (3, 2)), 2, -1)],
+    ];
+    let expected = vec![
+        vec![
+            ((0, ((3, 2), 0)), 0, 1),
+            ((0, ((5, 1), 1)), 0, 1),
+            ((1, ((1, 4), 0)), 0, 1),
+        ],
+        vec![((0, ((5, 1), 1)), 1, -1), ((0, ((4, 5), 1)), 1, 1)],
+        vec![
+            ((0, ((3, 2), 0)), 2, -1),
+            ((0, ((4, 5), 1)), 2, -1),
+            ((0, ((4, 5), 0)), 2, 1),
+            ((0, ((5, 1), 1)), 2, 1),
+        ],
+    ];
+    run_test(input, expected, |coll| {
+        coll.top_n(2, false).arrange_by_key()
+    });
+}
+
+#[test]
+fn test_top_n_descending_with_ties() {
+    let input = vec![vec![
+        ((0, (5, 1)), 0, 1),
+        ((0, (5, 2)), 0, 1),
+        ((0, (3, 3)), 0, 1),
+    ]];
+    let expected = vec![vec![((0, ((5, 1), 0)), 0, 1), ((0, ((5, 2), 1)), 0, 1)]];
+    run_test(input, expected, |coll| coll.top_n(2, true).arrange_by_key());
+}
+
+#[test]
+fn test_top_n_descending_ties_in_rank_order() {
+    let input = vec![vec![
+        ((0, (7, 1)), 0, 1),
+        ((0, (7, 4)), 0, 1),
+        ((0, (7, 2)), 0, 1),
+        ((0, (3, 5)), 0, 1),
+        ((0, (9, 6)), 0, 1),
+        ((0, (9, 3)), 0, 1),
+    ]];
+
+    let expected = vec![vec![
+        ((0, ((9, 3), 0)), 0, 1),
+        ((0, ((9, 6), 1)), 0, 1),
+        ((0, ((7, 1), 2)), 0, 1),
+        ((0, ((7, 2), 3)), 0, 1),
+    ]];
+    run_test(input.clone(), expected, |coll| {
+        coll.top_n(4, true).arrange_by_key()
+    });
+
+    // the row numbers of `rank` are the positions of `top_n` plus one
+    let expected = vec![[3, 6, 1, 2, 4, 5]
+        .into_iter()
+        .enumerate()
+        .map(|(position, id)| ((0, (id, position + 1)), 0, 1))
+        .collect()];
+    run_test(input, expected, |coll| {
+        coll.rank(true)
+            .map(|(instance, (id, ranks))| (instance, (id, ranks.row_number)))
+            .arrange_by_key()
+    });
+}
+
+#[test]
+fn test_top_n_many_entries() {
+    let input = vec![(0..1000)
+        .map(|i| ((0, ((i * 7) % 1000, i)), 0, 1))
+        .collect()];
+    let expected = vec![(0..3)
+        .map(|i| {
+            (
+                (0, ((i, (i * 143) % 1000), usize::try_from(i).unwrap())),
+                0,
+                1,
+            )
+        })
+        .collect()];
+    run_test(input, expected, |coll| {
+        coll.top_n(3, false).arrange_by_key()
+    });
+}
@@ -0,0 +1,283 @@
+// Copyright © 2024 Pathway
+
+use super::helpers::create_persistence_manager;
+
+use std::mem::take;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use tempfile::tempdir;
+
+use pathway_engine::connectors::data_format::FormatterContext;
+use pathway_engine::connectors::data_storage::{SharedWriter, WriteError, Writer};
+use pathway_engine::engine::Key;
+use pathway_engine::persistence::prepared_output::PreparedOutputStorage;
+use pathway_engine::persistence::sync::SharedWorkersPersistenceCoordinator;
+
+/// An external system that, like Kafka, keeps only the committed data and the last
+/// epoch committed together with it.
+#[derive(Debug, Default)]
+struct ExternalSystem {
+    committed: Vec<String>,
+    last_committed_epoch: Option<u64>,
+    fail_commits: bool,
+}
+
+/// A writer keeping the prepared epochs in the persisted state until they are
+/// committed to the external system, like the transactional `KafkaWriter`.
+struct RecoverableWriter {
+    external: Arc<Mutex<ExternalSystem>>,
+    unprepared: Vec<String>,
+    prepared: Vec<(u64, Vec<String>)>,
+    prepared_output: Option<PreparedOutputStorage>,
+}
+
+impl RecoverableWriter {
+    fn new(external: &Arc<Mutex<ExternalSystem>>) -> Self {
+        Self {
+            external: external.clone(),
+            unprepared: Vec::new(),
+            prepared: Vec::new(),
+            prepared_output: None,
+        }
+    }
+}
+
+impl Writer for RecoverableWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        for payload in data.payloads {
+            self.unprepared.push(String::from_utf8(payload).unwrap());
+        }
+        Ok(())
+    }
+
+    fn transactional(&self) -> bool {
+        true
+    }
+
+    fn prepared_output_name(&self) -> Option<&str> {
+        Some("recoverable")
+    }
+
+    fn prepare_commit(&mut self, time: u64) -> Result<(), WriteError> {
+        let messages = take(&mut self.unprepared);
+        self.prepared_output
+            .as_mut()
+            .unwrap()
+            .save(time, messages.join("\n").as_bytes())?;
+        self.prepared.push((time, messages));
+        Ok(())
+    }
+
+    fn commit_prepared(&mut self, time: u64) -> Result<(), WriteError> {
+        let mut external = self.external.lock().unwrap();
+        if external.fail_commits {
+            return Err(WriteError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "commit failed",
+            )));
+        }
+        let committed_count = self
+            .prepared
+            .iter()
+            .take_while(|(epoch, _messages)| *epoch <= time)
+            .count();
+        for (epoch, messages) in self.prepared.drain(..committed_count) {
+            external.committed.extend(messages);
+            external.last_committed_epoch = Some(epoch);
+            self.prepared_output.as_mut().unwrap().remove(epoch)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_prepared(
+        &mut self,
+        last_finalized_time: u64,
+        prepared_output: Option<PreparedOutputStorage>,
+    ) -> Result<(), WriteError> {
+        let mut prepared_output = prepared_output.unwrap();
+        let last_committed_epoch = self.external.lock().unwrap().last_committed_epoch;
+        for (epoch, data) in prepared_output.recover(last_finalized_time, last_committed_epoch)? {
+            let messages = String::from_utf8(data).unwrap();
+            self.prepared
+                .push((epoch, messages.split('\n').map(str::to_string).collect()));
+        }
+        self.prepared_output = Some(prepared_output);
+        self.commit_prepared(last_finalized_time)
+    }
+}
+
+fn write(writer: &SharedWriter, message: &str) -> eyre::Result<()> {
+    writer
+        .lock()
+        .unwrap()
+        .write(FormatterContext::new_single_payload(
+            message.as_bytes().to_vec(),
+            Key::random(),
+            Vec::new(),
+        ))?;
+    Ok(())
+}
+
+/// Reports the time finished by the sink and runs the commit of the output if it
+/// completes a checkpoint, like the output threads do.
+fn finish_time(
+    global_tracker: &SharedWorkersPersistenceCoordinator,
+    sink_id: usize,
+    time: Option<u64>,
+) -> Result<(), WriteError> {
+    let output_commit = global_tracker
+        .lock()
+        .unwrap()
+        .accept_finalized_timestamp(0, sink_id, time);
+    output_commit.map_or(Ok(()), |output_commit| output_commit.run())
+}
+
+/// Starts a run with the persisted state in `path` and a writer to the external system,
+/// returning the writer, the id of its sink and the coordinator of the run.
+fn start_run(
+    path: &Path,
+    external: &Arc<Mutex<ExternalSystem>>,
+) -> eyre::Result<(SharedWriter, usize, SharedWorkersPersistenceCoordinator)> {
+    start_run_with_sinks(path, external, 1)
+        .map(|(writer, sink_ids, global_tracker)| (writer, sink_ids[0], global_tracker))
+}
+
+fn start_run_with_sinks(
+    path: &Path,
+    external: &Arc<Mutex<ExternalSystem>>,
+    sink_count: usize,
+) -> eyre::Result<(
+    SharedWriter,
+    Vec<usize>,
+    SharedWorkersPersistenceCoordinator,
+)> {
+    let (tracker, global_tracker) = create_persistence_manager(path, false);
+    let mut writer = RecoverableWriter::new(external);
+    let last_finalized_time = tracker.lock().unwrap().last_finalized_timestamp();
+    let prepared_output = tracker
+        .lock()
+        .unwrap()
+        .create_prepared_output_storage("recoverable")?;
+    writer.resolve_prepared(last_finalized_time, Some(prepared_output))?;
+    let writer: SharedWriter = Arc::new(Mutex::new(Box::new(writer)));
+    let sink_ids = (0..sink_count)
+        .map(|_| tracker.lock().unwrap().register_sink())
+        .collect();
+    global_tracker
+        .lock()
+        .unwrap()
+        .register_transactional_sink(writer.clone());
+    Ok((writer, sink_ids, global_tracker))
+}
+
+#[test]
+fn test_prepared_epochs_committed_with_frontier() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let external = Arc::new(Mutex::new(ExternalSystem::default()));
+    let (writer, sink_ids, global_tracker) =
+        start_run_with_sinks(test_storage.path(), &external, 2)?;
+    let [first_sink_id, second_sink_id] = sink_ids[..] else {
+        unreachable!()
+    };
+
+    // The frontier doesn't move until both sinks finish the time
+    write(&writer, "a")?;
+    writer.lock().unwrap().prepare_commit(2)?;
+    write(&writer, "b")?;
+    writer.lock().unwrap().prepare_commit(4)?;
+    finish_time(&global_tracker, first_sink_id, Some(4))?;
+    assert!(external.lock().unwrap().committed.is_empty());
+
+    finish_time(&global_tracker, second_sink_id, Some(2))?;
+    assert_eq!(external.lock().unwrap().committed, ["a"]);
+
+    // Once the output of all sinks is finished, everything prepared is committed
+    write(&writer, "c")?;
+    writer.lock().unwrap().prepare_commit(7)?;
+    finish_time(&global_tracker, first_sink_id, None)?;
+    finish_time(&global_tracker, second_sink_id, None)?;
+    assert_eq!(external.lock().unwrap().committed, ["a", "b", "c"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_prepared_epochs_recovered_after_crash_before_commit() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let external = Arc::new(Mutex::new(ExternalSystem::default()));
+
+    {
+        let (writer, sink_id, global_tracker) = start_run(test_storage.path(), &external)?;
+        write(&writer, "a")?;
+        writer.lock().unwrap().prepare_commit(2)?;
+        finish_time(&global_tracker, sink_id, Some(2))?;
+        assert_eq!(external.lock().unwrap().committed, ["a"]);
+
+        write(&writer, "b")?;
+        writer.lock().unwrap().prepare_commit(4)?;
+        write(&writer, "c")?;
+        writer.lock().unwrap().prepare_commit(6)?;
+        // The checkpoint at time 4 is saved, but the process crashes before its
+        // output is committed
+        let output_commit =
+            global_tracker
+                .lock()
+                .unwrap()
+                .accept_finalized_timestamp(0, sink_id, Some(4));
+        assert!(output_commit.is_some());
+        drop(output_commit);
+        assert_eq!(external.lock().unwrap().committed, ["a"]);
+    }
+
+    // The restart commits the epoch covered by the checkpoint, while the epoch after it
+    // is dropped, as it's produced again from the replayed data
+    {
+        let (writer, sink_id, global_tracker) = start_run(test_storage.path(), &external)?;
+        assert_eq!(external.lock().unwrap().committed, ["a", "b"]);
+
+        write(&writer, "c")?;
+        writer.lock().unwrap().prepare_commit(6)?;
+        finish_time(&global_tracker, sink_id, None)?;
+        assert_eq!(external.lock().unwrap().committed, ["a", "b", "c"]);
+    }
+
+    // Nothing is committed twice by the next restart
+    start_run(test_storage.path(), &external)?;
+    assert_eq!(external.lock().unwrap().committed, ["a", "b", "c"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_commit_fails_checkpoint_and_is_retried() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let external = Arc::new(Mutex::new(ExternalSystem::default()));
+    let (writer, sink_id, global_tracker) = start_run(test_storage.path(), &external)?;
+
+    external.lock().unwrap().fail_commits = true;
+    write(&writer, "a")?;
+    writer.lock().unwrap().prepare_commit(2)?;
+    assert!(finish_time(&global_tracker, sink_id, Some(2)).is_err());
+    {
+        let global_tracker = global_tracker.lock().unwrap();
+        let stats = global_tracker.persistence_stats().lock().unwrap();
+        assert!(stats.last_checkpoint_failed);
+        assert_eq!(stats.failed_checkpoints, 1);
+    }
+
+    external.lock().unwrap().fail_commits = false;
+    finish_time(&global_tracker, sink_id, Some(3))?;
+    assert_eq!(external.lock().unwrap().committed, ["a"]);
+    assert!(
+        !global_tracker
+            .lock()
+            .unwrap()
+            .persistence_stats()
+            .lock()
+            .unwrap()
+            .last_checkpoint_failed
+    );
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+// Copyright © 2024 Pathway
+
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+
+use pathway_engine::engine::error::{Error, Trace};
+use pathway_engine::engine::graph::{ColumnPath, ColumnProperties, JoinType, TableProperties};
+use pathway_engine::engine::{
+    AnyExpression, BoolExpression, Expression, FloatExpression, IntExpression, StringExpression,
+    Type, Value,
+};
+
+fn argument(index: usize) -> Arc<Expression> {
+    Arc::new(Expression::Any(AnyExpression::Argument(index)))
+}
+
+#[test]
+fn test_matching_types() {
+    let expression = Expression::Bool(BoolExpression::FloatLt(
+        Arc::new(Expression::Float(FloatExpression::CastFromInt(argument(0)))),
+        argument(1),
+    ));
+    assert!(expression.check_types(&[Type::Int, Type::Float]).is_ok());
+    assert_eq!(
+        expression.result_type(&[Type::Int, Type::Float]),
+        Type::Bool
+    );
+}
+
+#[test]
+fn test_unknown_types_are_not_checked() {
+    let expression = Expression::Int(IntExpression::Add(argument(0), argument(1)));
+    assert!(expression.check_types(&[Type::Any, Type::Int]).is_ok());
+    assert!(expression.check_types(&[]).is_ok());
+}
+
+#[test]
+fn test_argument_type_mismatch() {
+    let expression = Expression::Int(IntExpression::Add(argument(0), argument(1)));
+    assert_matches!(
+        expression.check_types(&[Type::Int, Type::String]),
+        Err(Error::OperandTypeMismatch {
+            operation,
+            expected: Type::Int,
+            actual: Type::String,
+        }) if operation == "IntExpression::Add"
+    );
+}
+
+#[test]
+fn test_nested_mismatch() {
+    let expression = Expression::String(StringExpression::CastFromInt(Arc::new(Expression::Any(
+        AnyExpression::IfElse(
+            argument(0),
+            Arc::new(Expression::Float(FloatExpression::Neg(argument(1)))),
+            Arc::new(Expression::new_const(Value::Float(0.0.into()))),
+        ),
+    ))));
+    assert_eq!(
+        expression.result_type(&[Type::Bool, Type::Float]),
+        Type::String
+    );
+    assert_matches!(
+        expression.check_types(&[Type::Bool, Type::Float]),
+        Err(Error::OperandTypeMismatch {
+            expected: Type::Int,
+            actual: Type::Float,
+            ..
+        })
+    );
+    assert_matches!(
+        expression.check_types(&[Type::Int, Type::Float]),
+        Err(Error::OperandTypeMismatch {
+            expected: Type::Bool,
+            actual: Type::Int,
+            ..
+        })
+    );
+}
+
+#[test]
+fn test_extract_type() {
+    let column = |dtype| {
+        TableProperties::Column(Arc::new(ColumnProperties {
+            dtype,
+            nullable: false,
+            append_only: false,
+            trace: Trace::Empty,
+        }))
+    };
+    let properties = TableProperties::Table(
+        vec![
+            column(Type::Int),
+            TableProperties::Table(vec![column(Type::String)].into()),
+        ]
+        .into(),
+    );
+    assert_eq!(ColumnPath::Key.extract_type(&properties), Type::Pointer);
+    assert_eq!(
+        ColumnPath::ValuePath(vec![0]).extract_type(&properties),
+        Type::Int
+    );
+    assert_eq!(
+        ColumnPath::ValuePath(vec![1, 0]).extract_type(&properties),
+        Type::String
+    );
+    assert_eq!(
+        ColumnPath::ValuePath(vec![0, 0]).extract_type(&properties),
+        Type::Any
+    );
+    assert_eq!(
+        ColumnPath::ValuePath(vec![2]).extract_type(&properties),
+        Type::Any
+    );
+    assert_eq!(
+        ColumnPath::ValuePath(vec![0]).extract_type(&TableProperties::Empty),
+        Type::Any
+    );
+}
+
+#[test]
+fn test_nullability() {
+    let sum = Expression::Int(IntExpression::Add(argument(0), argument(1)));
+    assert!(!sum.is_nullable(&[true, true]));
+    assert!(argument(0).is_nullable(&[true]));
+    assert!(!argument(0).is_nullable(&[false]));
+    assert!(argument(1).is_nullable(&[false]));
+
+    let if_else = Expression::Any(AnyExpression::IfElse(
+        argument(0),
+        argument(1),
+        Arc::new(Expression::new_const(Value::None)),
+    ));
+    assert!(if_else.is_nullable(&[false, false]));
+    assert!(if_else.check_nullability().is_ok());
+
+    let sum = Expression::Int(IntExpression::Add(Arc::new(if_else), argument(2)));
+    assert_matches!(
+        sum.check_nullability(),
+        Err(Error::NullableOperand {
+            operation,
+            expected: Type::Int,
+        }) if operation == "IntExpression::Add"
+    );
+    // the arguments can be narrowed by the user
+    let sum = Expression::Int(IntExpression::Add(argument(0), argument(1)));
+    assert!(sum.check_nullability().is_ok());
+}
+
+#[test]
+fn test_nullable_properties() {
+    let column = |nullable| {
+        TableProperties::Column(Arc::new(ColumnProperties {
+            dtype: Type::Int,
+            nullable,
+            append_only: false,
+            trace: Trace::Empty,
+        }))
+    };
+    let properties = TableProperties::Table(vec![column(false), column(true)].into());
+    assert!(!ColumnPath::Key.extract_nullable(&properties));
+    assert!(!ColumnPath::ValuePath(vec![0]).extract_nullable(&properties));
+    assert!(ColumnPath::ValuePath(vec![1]).extract_nullable(&properties));
+    assert!(ColumnPath::ValuePath(vec![2]).extract_nullable(&properties));
+    assert_eq!(
+        properties.with_nullable(),
+        TableProperties::Table(vec![column(true), column(true)].into())
+    );
+
+    assert!(JoinType::LeftOuter.keeps_unmatched_left());
+    assert!(!JoinType::LeftOuter.keeps_unmatched_right());
+    assert!(JoinType::FullOuter.keeps_unmatched_right());
+    assert!(!JoinType::Inner.keeps_unmatched_left());
+}
@@ -0,0 +1,148 @@
+// Copyright © 2024 Pathway
+
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use ndarray::{ArrayD, IxDyn};
+
+use pathway_engine::engine::value_encoding::{
+    decode, decode_value, encode, encode_value, DecodeError, FORMAT_VERSION,
+};
+use pathway_engine::engine::{DateTimeNaive, DateTimeUtc, Duration, Key, SerializedObject, Value};
+
+fn all_kinds_of_values() -> Vec<Value> {
+    vec![
+        Value::None,
+        Value::Bool(false),
+        Value::Bool(true),
+        Value::Int(0),
+        Value::Int(-1),
+        Value::Int(i64::MIN),
+        Value::Int(i64::MAX),
+        Value::Float(1.5.into()),
+        Value::Float(f64::NAN.into()),
+        Value::Pointer(Key::random()),
+        Value::from(""),
+        Value::from("żółw"),
+        Value::from(&b"\x00\xff"[..]),
+        Value::Tuple(Arc::new([Value::Int(1), Value::from("a"), Value::None])),
+        Value::Tuple(Arc::new([])),
+        Value::from(
+            ArrayD::from_shape_vec(IxDyn(&[2, 3]), vec![1, -2, 3, -4, 5, i64::MIN]).unwrap(),
+        ),
+        Value::from(ArrayD::<i64>::zeros(IxDyn(&[0, 4]))),
+        Value::from(ArrayD::from_shape_vec(IxDyn(&[3]), vec![0.5, -1.0, f64::INFINITY]).unwrap()),
+        Value::DateTimeNaive(DateTimeNaive::new(1_700_000_000_000_000_000)),
+        Value::DateTimeUtc(DateTimeUtc::new(-1)),
+        Value::Duration(Duration::new(i64::MIN)),
+        Value::from(serde_json::json!({"a": [1, 2.5, null], "b": "c"})),
+        Value::from(SerializedObject::new("pickle", b"\x80\x04")),
+    ]
+}
+
+#[test]
+fn test_round_trip() {
+    let values = all_kinds_of_values();
+    assert_eq!(decode(&encode(&values)).unwrap(), values);
+    for value in values {
+        let encoded = encode(std::slice::from_ref(&value));
+        assert_eq!(encoded[0], FORMAT_VERSION);
+        assert_eq!(decode(&encoded).unwrap(), [value.clone()]);
+
+        // the version and the count are left out
+        let encoded_value = encode_value(&value);
+        assert_eq!(encoded_value, encoded[2..]);
+        assert_eq!(decode_value(&encoded_value).unwrap(), value);
+    }
+}
+
+#[test]
+fn test_serde_round_trip() {
+    let values = all_kinds_of_values();
+    let serialized = bincode::serialize(&values).unwrap();
+    let deserialized: Vec<Value> = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, values);
+}
+
+#[test]
+fn test_exchanged_size() {
+    let key = Key::random();
+    let key_size = bincode::serialized_size(&key).unwrap();
+
+    // the key, the length of the bytes of the value, its tag and the varint
+    assert_eq!(
+        bincode::serialized_size(&(key, Value::Int(5))).unwrap(),
+        key_size + 8 + 2
+    );
+
+    // the tag and the length of the tuple, and its values, with the repeated string stored
+    // once, instead of 64 bytes for the values in the serialization derived before
+    let row = Value::Tuple(Arc::new([
+        Value::Int(1),
+        Value::from("status"),
+        Value::from("status"),
+        Value::None,
+    ]));
+    let serialized = bincode::serialize(&(key, row.clone())).unwrap();
+    assert_eq!(serialized.len() as u64, key_size + 8 + 15);
+    let deserialized: (Key, Value) = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, (key, row));
+}
+
+#[test]
+fn test_small_ints_are_short() {
+    assert_eq!(encode(&[Value::Int(-3), Value::Int(60)]).len(), 6);
+}
+
+#[test]
+fn test_repeated_strings_are_stored_once() {
+    let string = Value::from("a string repeated in the row");
+    let row = [
+        string.clone(),
+        Value::Tuple(Arc::new([string.clone(), string.clone()])),
+    ];
+    // the tuple takes its tag, its length and a tag and a reference for each string
+    assert_eq!(encode(&row).len(), encode(&[string]).len() + 6);
+    assert_eq!(decode(&encode(&row)).unwrap(), row);
+
+    let object = Value::from(SerializedObject::new("a string repeated in the row", b""));
+    let row = [Value::from("a string repeated in the row"), object];
+    assert_eq!(decode(&encode(&row)).unwrap(), row);
+}
+
+#[test]
+fn test_invalid_encodings() {
+    let encoded = encode(&all_kinds_of_values());
+    for len in 0..encoded.len() {
+        assert_matches!(decode(&encoded[..len]), Err(DecodeError::UnexpectedEnd));
+    }
+    assert_matches!(
+        decode(&[FORMAT_VERSION + 1, 0]),
+        Err(DecodeError::UnsupportedVersion(version)) if version == FORMAT_VERSION + 1
+    );
+    assert_matches!(decode(&[0, 0]), Err(DecodeError::UnsupportedVersion(0)));
+    assert_matches!(
+        decode(&[FORMAT_VERSION, 1, 0xff]),
+        Err(DecodeError::InvalidTag(0xff))
+    );
+    assert_matches!(
+        decode(&[FORMAT_VERSION, 1, 6, 3]),
+        Err(DecodeError::InvalidStringReference(1))
+    );
+    assert_matches!(
+        decode(&[FORMAT_VERSION, 1, 0, 0]),
+        Err(DecodeError::TrailingValues { expected: 1 })
+    );
+
+    let encoded_value = encode_value(&Value::from("żółw"));
+    for len in 0..encoded_value.len() {
+        assert_matches!(
+            decode_value(&encoded_value[..len]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+    }
+    assert_matches!(
+        decode_value(&[0, 0]),
+        Err(DecodeError::TrailingValues { expected: 1 })
+    );
+}
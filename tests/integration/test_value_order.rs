@@ -0,0 +1,87 @@
+// Copyright © 2024 Pathway
+
+use std::cmp::Ordering;
+
+use pathway_engine::engine::{Key, Value};
+
+#[test]
+fn test_numbers_ordered_by_value() {
+    let mut values = vec![
+        Value::Float(f64::NAN.into()),
+        Value::Int(2),
+        Value::Float(1.5.into()),
+        Value::Float(1.0.into()),
+        Value::Int(1),
+        Value::Float(f64::NEG_INFINITY.into()),
+        Value::Int(i64::MIN),
+        Value::Float(f64::INFINITY.into()),
+        Value::Int(i64::MAX),
+    ];
+    values.sort();
+    assert_eq!(
+        values,
+        [
+            Value::Float(f64::NEG_INFINITY.into()),
+            Value::Int(i64::MIN),
+            Value::Int(1),
+            Value::Float(1.0.into()),
+            Value::Float(1.5.into()),
+            Value::Int(2),
+            Value::Int(i64::MAX),
+            Value::Float(f64::INFINITY.into()),
+            Value::Float(f64::NAN.into()),
+        ]
+    );
+}
+
+#[test]
+fn test_types_ordered() {
+    let values = [
+        Value::None,
+        Value::Bool(true),
+        Value::Int(-5),
+        Value::Pointer(Key(0)),
+        Value::from("a"),
+        Value::from(&b"a"[..]),
+        Value::from(&[Value::None][..]),
+    ];
+    for (index, value) in values.iter().enumerate() {
+        for (other_index, other) in values.iter().enumerate() {
+            assert_eq!(value.cmp(other), index.cmp(&other_index));
+        }
+    }
+}
+
+#[test]
+fn test_tuples_ordered_by_elements() {
+    let tuple = |values: &[Value]| Value::from(values);
+    assert_eq!(
+        tuple(&[Value::Int(1), Value::from("b")]).cmp(&tuple(&[Value::Float(1.5.into())])),
+        Ordering::Less
+    );
+    assert_eq!(
+        tuple(&[Value::Int(1)]).cmp(&tuple(&[Value::Int(1), Value::None])),
+        Ordering::Less
+    );
+    assert_eq!(
+        tuple(&[Value::None, Value::Int(2)]).cmp(&tuple(&[Value::None, Value::Float(1.0.into())])),
+        Ordering::Greater
+    );
+}
+
+#[test]
+fn test_order_consistent_with_equality() {
+    let values = [
+        Value::Int(0),
+        Value::Float(0.0.into()),
+        Value::Float((-0.0).into()),
+        Value::Float(f64::NAN.into()),
+        Value::Float((-f64::NAN).into()),
+    ];
+    for value in &values {
+        for other in &values {
+            assert_eq!(value.cmp(other) == Ordering::Equal, value == other);
+            assert_eq!(value.cmp(other), other.cmp(value).reverse());
+        }
+    }
+}